@@ -0,0 +1,122 @@
+#![allow(dead_code)]
+//! Flavor text: the short shouts the game uses for feedback
+//!
+//! "CLANK", "NOPE", "TIME'S UP!" and "BLAM!" are part of the game's voice,
+//! but players on other languages (or running a themed night) may want
+//! their own. Overrides live in `flavor.txt` in the data directory, one
+//! `key = value` per line:
+//!
+//! ```text
+//! # Lines starting with # are ignored
+//! not_in_dictionary = NEIN
+//! time_up = ZEIT!
+//! ```
+//!
+//! Keys left out keep their default.
+
+use crate::network::RoundEndReason;
+use std::path::Path;
+
+/// Name of the overrides file inside the data directory
+pub const FLAVOR_FILE: &str = "flavor.txt";
+
+/// The game's feedback strings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlavorText {
+    /// Word uses letters that aren't on the rack
+    pub invalid_letters: String,
+    /// Word isn't in the dictionary
+    pub not_in_dictionary: String,
+    /// Round ran out of time
+    pub time_up: String,
+    /// Countdown reached zero
+    pub go: String,
+}
+
+impl Default for FlavorText {
+    fn default() -> Self {
+        Self {
+            invalid_letters: "CLANK".to_string(),
+            not_in_dictionary: "NOPE".to_string(),
+            time_up: "TIME'S UP!".to_string(),
+            go: "BLAM!".to_string(),
+        }
+    }
+}
+
+impl FlavorText {
+    /// Defaults with the overrides in `contents` applied
+    ///
+    /// Unknown keys, blank values and lines without `=` are skipped.
+    pub fn parse(contents: &str) -> Self {
+        let mut flavor = Self::default();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            let slot = match key.trim() {
+                "invalid_letters" => &mut flavor.invalid_letters,
+                "not_in_dictionary" => &mut flavor.not_in_dictionary,
+                "time_up" => &mut flavor.time_up,
+                "go" => &mut flavor.go,
+                _ => continue,
+            };
+            *slot = value.to_string();
+        }
+        flavor
+    }
+
+    /// Load overrides from `flavor.txt` in `data_dir`
+    ///
+    /// A missing or unreadable file just means the defaults.
+    pub fn load(data_dir: &Path) -> Self {
+        std::fs::read_to_string(data_dir.join(FLAVOR_FILE))
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    /// Headline for the end-of-round screen
+    pub fn headline(&self, reason: RoundEndReason) -> &str {
+        match reason {
+            RoundEndReason::TimeExpired => &self.time_up,
+            _ => reason.headline(),
+        }
+    }
+
+    /// Whether `feedback` is one of our rejection shouts
+    pub fn is_rejection(&self, feedback: &str) -> bool {
+        feedback == self.invalid_letters || feedback == self.not_in_dictionary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_overrides_known_keys_only() {
+        let flavor = FlavorText::parse(
+            "# German night\nnot_in_dictionary = NEIN\n time_up=ZEIT! \ngo =\nbogus = x\nno equals\n",
+        );
+        assert_eq!(flavor.not_in_dictionary, "NEIN");
+        assert_eq!(flavor.time_up, "ZEIT!");
+        assert_eq!(flavor.go, "BLAM!", "blank value keeps the default");
+        assert_eq!(flavor.invalid_letters, "CLANK");
+        assert_eq!(flavor.headline(RoundEndReason::TimeExpired), "ZEIT!");
+        assert_eq!(flavor.headline(RoundEndReason::HostEnded), "ROUND ENDED BY HOST");
+    }
+
+    #[test]
+    fn test_missing_file_uses_defaults() {
+        let dir = std::env::temp_dir().join(format!("blam-flavor-{}", std::process::id()));
+        assert_eq!(FlavorText::load(&dir), FlavorText::default());
+    }
+}
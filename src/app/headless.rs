@@ -0,0 +1,330 @@
+#![allow(dead_code)]
+//! Headless mode: play without the TUI and print results as JSON lines
+//!
+//! `blam --headless` plays solo rounds on a timer. Words to claim are read
+//! from stdin, one per line. `blam --headless --host` hosts a lobby instead
+//! and starts a round whenever enough players have joined; `--bots N` seats
+//! N bot opponents in it from the start. Either way, each
+//! finished round is printed to stdout as one JSON line:
+//!
+//! ```text
+//! {"mode":"solo","round":1,"rack":"BLAMTES...","duration_secs":60,
+//!  "result":{"match_id":...,"scores":[["Player",7]],...},
+//!  "words":[{"word":"CAT","player":"Player","points":5,"seq":1}]}
+//! ```
+//!
+//! `result` is the round's `MatchResult` as it would be logged. Progress
+//! messages go to stderr so stdout stays machine-readable.
+
+use crate::game::arbitrator::{ClaimRecord, RoundArbitrator};
+use crate::game::LetterRack;
+use crate::lobby::{HostedLobby, LobbyEvent, LobbyState};
+use crate::stats::{escape_json, MatchResult};
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::state::DEFAULT_ROUND_DURATION;
+
+/// Handle used when `--handle` isn't given
+pub const DEFAULT_HEADLESS_HANDLE: &str = "Player";
+
+/// Pause between hosted rounds, so players can see the results
+pub const BETWEEN_ROUNDS: Duration = Duration::from_secs(5);
+
+/// Headless run settings, from the command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeadlessOptions {
+    /// Host a lobby instead of playing solo
+    pub host: bool,
+    /// Rounds to play before exiting
+    pub rounds: u32,
+    /// Length of each round (seconds)
+    pub duration: u32,
+    /// Our player name
+    pub handle: String,
+    /// Bots to add to a hosted lobby
+    pub bots: usize,
+}
+
+impl Default for HeadlessOptions {
+    fn default() -> Self {
+        Self {
+            host: false,
+            rounds: 1,
+            duration: DEFAULT_ROUND_DURATION,
+            handle: DEFAULT_HEADLESS_HANDLE.to_string(),
+            bots: 0,
+        }
+    }
+}
+
+impl HeadlessOptions {
+    /// Parse the command line (without the program name)
+    ///
+    /// Returns `Ok(None)` unless `--headless` is given.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Option<Self>, String> {
+        let args: Vec<String> = args.into_iter().collect();
+        if !args.iter().any(|a| a == "--headless") {
+            return Ok(None);
+        }
+
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--headless" => {}
+                "--host" => options.host = true,
+                "--rounds" => options.rounds = parse_count(&arg, args.next())?,
+                "--duration" => options.duration = parse_count(&arg, args.next())?,
+                "--bots" => options.bots = parse_count(&arg, args.next())? as usize,
+                "--handle" => {
+                    options.handle = args
+                        .next()
+                        .filter(|h| !h.trim().is_empty())
+                        .ok_or("--handle needs a name")?;
+                }
+                other => return Err(format!("unknown option {}", other)),
+            }
+        }
+        Ok(Some(options))
+    }
+}
+
+/// Parse a positive number given after `flag`
+fn parse_count(flag: &str, value: Option<String>) -> Result<u32, String> {
+    value
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|n| *n > 0)
+        .ok_or_else(|| format!("{} needs a positive number", flag))
+}
+
+/// Run headless until the requested rounds are played
+pub fn run(options: &HeadlessOptions) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    if options.host {
+        run_host(options, &mut out)
+    } else {
+        let words = spawn_stdin_reader();
+        for round in 1..=options.rounds {
+            let letters = LetterRack::generate().letters().to_vec();
+            let duration = Duration::from_secs(options.duration as u64);
+            run_solo_round(round, letters, duration, &options.handle, &words, &mut out)?;
+        }
+        Ok(())
+    }
+}
+
+/// Forward stdin lines to a channel, closing it at end of input
+fn spawn_stdin_reader() -> Receiver<String> {
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Play one solo round, claiming words as they arrive on `words`
+///
+/// The round runs until its time is up, even if `words` closes first (so
+/// every round of a run lasts as long once stdin is done). Its result is
+/// written to `out` as a JSON line.
+pub fn run_solo_round<W: Write>(
+    round: u32,
+    letters: Vec<char>,
+    duration: Duration,
+    handle: &str,
+    words: &Receiver<String>,
+    out: &mut W,
+) -> io::Result<()> {
+    let mut arbitrator = RoundArbitrator::new(letters.clone(), &[handle.to_string()]);
+    let deadline = Instant::now() + duration;
+    eprintln!("blam: round {} rack {}", round, letters.iter().collect::<String>());
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match words.recv_timeout(remaining) {
+            Ok(word) => {
+                let word = word.trim();
+                if !word.is_empty() {
+                    arbitrator.try_claim(word, handle);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => break,
+            // No more words will come, but the clock still runs
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                break;
+            }
+        }
+    }
+    arbitrator.end_round();
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+    let result = MatchResult::with_host_counter(
+        now_ms,
+        round as u64,
+        arbitrator.scores(),
+        handle.to_string(),
+    );
+    writeln!(
+        out,
+        "{}",
+        round_json("solo", round, &letters, duration.as_secs() as u32, &result, arbitrator.claims())
+    )?;
+    out.flush()
+}
+
+/// Host a lobby, starting a round whenever enough players are in
+fn run_host<W: Write>(options: &HeadlessOptions, out: &mut W) -> io::Result<()> {
+    let mut lobby = HostedLobby::new(options.handle.clone()).map_err(io::Error::other)?;
+    eprintln!("blam: hosting {} on port {}", lobby.lobby_name, lobby.port());
+    if options.bots > 0 {
+        let added = lobby.add_bots(options.bots);
+        eprintln!("blam: added {} bot(s)", added);
+    }
+
+    let mut round = 0;
+    let mut next_start = Instant::now();
+    let mut last_second = Instant::now();
+    while round < options.rounds {
+        for event in lobby.poll() {
+            if let LobbyEvent::RoundEnd { .. } = event {
+                round += 1;
+                let now_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as i64)
+                    .unwrap_or(0);
+                let Some(result) = lobby.take_match_result(now_ms, round as u64) else {
+                    continue;
+                };
+                let letters = lobby.current_letters().to_vec();
+                let claims = lobby.round_claims();
+                let line = round_json("host", round, &letters, options.duration, &result, &claims);
+                writeln!(out, "{}", line)?;
+                out.flush()?;
+                next_start = Instant::now() + BETWEEN_ROUNDS;
+            }
+        }
+
+        if round < options.rounds
+            && lobby.state == LobbyState::Waiting
+            && lobby.can_start()
+            && Instant::now() >= next_start
+        {
+            let letters = lobby.generate_letters();
+            eprintln!("blam: round {} starting", round + 1);
+            lobby.start_countdown(letters, options.duration);
+        }
+
+        if last_second.elapsed() >= Duration::from_secs(1) {
+            lobby.tick_countdown();
+            last_second = Instant::now();
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    lobby.shutdown().map_err(io::Error::other)
+}
+
+/// One round's result as a JSON line
+pub fn round_json(
+    mode: &str,
+    round: u32,
+    letters: &[char],
+    duration_secs: u32,
+    result: &MatchResult,
+    claims: &[ClaimRecord],
+) -> String {
+    let words: Vec<String> = claims
+        .iter()
+        .map(|c| {
+            format!(
+                r#"{{"word":"{}","player":"{}","points":{},"seq":{}}}"#,
+                escape_json(&c.word),
+                escape_json(&c.player_name),
+                c.points,
+                c.claim_sequence
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"mode":"{}","round":{},"rack":"{}","duration_secs":{},"result":{},"words":[{}]}}"#,
+        mode,
+        round,
+        letters.iter().collect::<String>(),
+        duration_secs,
+        result.to_json(),
+        words.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_options_parsed_only_when_headless() {
+        assert_eq!(HeadlessOptions::from_args(args(&[])), Ok(None));
+
+        let options = HeadlessOptions::from_args(args(&[
+            "--headless", "--host", "--rounds", "3", "--duration", "30", "--handle", "Bot", "--bots",
+            "2",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert!(options.host);
+        assert_eq!(options.rounds, 3);
+        assert_eq!(options.duration, 30);
+        assert_eq!(options.handle, "Bot");
+        assert_eq!(options.bots, 2);
+
+        assert!(HeadlessOptions::from_args(args(&["--headless", "--rounds", "0"])).is_err());
+        assert!(HeadlessOptions::from_args(args(&["--headless", "--bogus"])).is_err());
+    }
+
+    #[test]
+    fn test_headless_solo_round_prints_json_line() {
+        let letters = vec!['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'];
+        let (tx, rx) = channel();
+        for word in ["cat", "xyz", "", "dogs", "cat"] {
+            tx.send(word.to_string()).unwrap();
+        }
+        // Closing the input doesn't cut the round short
+        drop(tx);
+
+        let mut out = Vec::new();
+        let started = Instant::now();
+        let duration = Duration::from_secs(1);
+        run_solo_round(1, letters, duration, "Bot", &rx, &mut out).unwrap();
+        assert!(started.elapsed() >= duration);
+
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let line = lines[0];
+        assert!(line.starts_with(r#"{"mode":"solo","round":1,"rack":"CATDOGERSTAN","duration_secs":1,"#));
+        assert!(line.contains(
+            r#""words":[{"word":"CAT","player":"Bot","points":5,"seq":1},{"word":"DOGS","player":"Bot","points":7,"seq":2}]"#
+        ));
+
+        let parsed = crate::json::parse(line).unwrap();
+        let result = MatchResult::from_value(parsed.get("result").unwrap()).unwrap();
+        assert_eq!(result.scores, vec![("Bot".to_string(), 12)]);
+        assert_eq!(result.host_counter, Some(1));
+    }
+}
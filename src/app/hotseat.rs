@@ -0,0 +1,259 @@
+#![allow(dead_code)]
+//! Local two-player (hotseat) rounds
+//!
+//! Two players share one keyboard and race on the same rack. Only one of
+//! them types at a time; Tab hands the keyboard over. Each keeps their own
+//! input line and score, but claimed words are shared: once one player has
+//! CAT, the other can't grab it too.
+
+use crate::game::input::InputMap;
+use crate::game::scoring::score_on_rack;
+use crate::game::validation::{bonus_points, validate_word, ValidationResult};
+use std::collections::HashMap;
+
+use super::flavor::FlavorText;
+use super::state::ClaimedWord;
+
+/// Name given to the second player (the first plays under our handle)
+pub const HOTSEAT_GUEST: &str = "Guest";
+
+/// One of the two players sharing the keyboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotseatPlayer {
+    /// Display name
+    pub name: String,
+    /// What this player has typed so far
+    pub input: String,
+    /// Points this round
+    pub score: u32,
+    /// Words this player claimed this round, in order
+    pub claimed_words: Vec<ClaimedWord>,
+}
+
+impl HotseatPlayer {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            input: String::new(),
+            score: 0,
+            claimed_words: Vec::new(),
+        }
+    }
+}
+
+/// A hotseat round: two players, one rack, one keyboard
+#[derive(Debug, Clone)]
+pub struct HotseatGame {
+    /// Letter rack both players draw from
+    pub letters: Vec<char>,
+    /// The two players
+    pub players: [HotseatPlayer; 2],
+    /// Index of the player at the keyboard
+    pub active: usize,
+    /// Feedback from the last submission
+    pub feedback: String,
+    /// Time remaining in seconds
+    pub time_remaining: u32,
+    /// Whether the round has ended
+    pub round_ended: bool,
+    /// Words claimed by either player (uppercase word -> player index)
+    claimed: HashMap<String, usize>,
+    /// Maps typed characters onto the dictionary alphabet
+    input_map: InputMap,
+    /// Feedback strings (CLANK, NOPE, ...)
+    pub flavor: FlavorText,
+}
+
+impl HotseatGame {
+    /// Start a round for two players on `letters`
+    pub fn new(names: [String; 2], letters: Vec<char>, duration: u32) -> Self {
+        let [first, second] = names;
+        Self {
+            letters,
+            players: [HotseatPlayer::new(first), HotseatPlayer::new(second)],
+            active: 0,
+            feedback: String::new(),
+            time_remaining: duration,
+            round_ended: false,
+            claimed: HashMap::new(),
+            input_map: InputMap::default(),
+            flavor: FlavorText::default(),
+        }
+    }
+
+    /// The player at the keyboard
+    pub fn active_player(&self) -> &HotseatPlayer {
+        &self.players[self.active]
+    }
+
+    /// Hand the keyboard to the other player
+    ///
+    /// Each player's half-typed word is kept for when they get it back.
+    pub fn switch_player(&mut self) {
+        if self.round_ended {
+            return;
+        }
+        self.active = 1 - self.active;
+        self.feedback.clear();
+    }
+
+    /// Handle a raw typed character for the active player
+    pub fn on_key_char(&mut self, c: char) {
+        if self.round_ended {
+            return;
+        }
+        let Some(letter) = self.input_map.normalize(c) else {
+            return;
+        };
+        let input = &mut self.players[self.active].input;
+        if input.chars().count() < self.letters.len() {
+            input.push(letter);
+            self.feedback.clear();
+        }
+    }
+
+    /// Delete the active player's last character
+    pub fn on_backspace(&mut self) {
+        if self.round_ended {
+            return;
+        }
+        self.players[self.active].input.pop();
+        self.feedback.clear();
+    }
+
+    /// Claim the active player's word
+    pub fn on_submit(&mut self) {
+        if self.round_ended || self.players[self.active].input.is_empty() {
+            return;
+        }
+        let word = std::mem::take(&mut self.players[self.active].input).to_uppercase();
+
+        if let Some(&by) = self.claimed.get(&word) {
+            self.feedback = format!("ALREADY CLAIMED by {}", self.players[by].name);
+            return;
+        }
+
+        let result = validate_word(&word, &self.letters);
+        self.feedback = match result {
+            ValidationResult::Valid => {
+                let points =
+                    score_on_rack(&word, &self.letters) + bonus_points(&word, &self.letters);
+                let player = &mut self.players[self.active];
+                player.score += points;
+                player.claimed_words.push(ClaimedWord {
+                    word: word.clone(),
+                    points,
+                });
+                self.claimed.insert(word.clone(), self.active);
+                format!("OK +{} ({})", points, word)
+            }
+            ValidationResult::TooShort { .. } | ValidationResult::TooFewLetters { .. } => {
+                result.message()
+            }
+            ValidationResult::InvalidLetters { .. } => self.flavor.invalid_letters.clone(),
+            ValidationResult::NotInDictionary => self.flavor.not_in_dictionary.clone(),
+        };
+    }
+
+    /// Count down one second, ending the round at zero
+    pub fn tick(&mut self) {
+        if self.time_remaining > 0 {
+            self.time_remaining -= 1;
+            if self.time_remaining == 0 {
+                self.end_round();
+            }
+        }
+    }
+
+    /// End the round and lock input
+    pub fn end_round(&mut self) {
+        self.round_ended = true;
+        for player in &mut self.players {
+            player.input.clear();
+        }
+    }
+
+    /// Check if the round is over
+    pub fn is_round_over(&self) -> bool {
+        self.round_ended
+    }
+
+    /// The player ahead on points, or `None` on a tie
+    pub fn leader(&self) -> Option<&HotseatPlayer> {
+        let [first, second] = &self.players;
+        match first.score.cmp(&second.score) {
+            std::cmp::Ordering::Greater => Some(first),
+            std::cmp::Ordering::Less => Some(second),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game() -> HotseatGame {
+        let letters = vec!['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'];
+        HotseatGame::new(["Alice".to_string(), "Bob".to_string()], letters, 60)
+    }
+
+    fn claim(game: &mut HotseatGame, word: &str) {
+        for c in word.chars() {
+            game.on_key_char(c);
+        }
+        game.on_submit();
+    }
+
+    #[test]
+    fn test_word_claimed_by_one_player_rejected_for_other() {
+        let mut game = game();
+        claim(&mut game, "cat");
+        assert_eq!(game.players[0].score, 5);
+
+        game.switch_player();
+        claim(&mut game, "CAT");
+        assert_eq!(game.feedback, "ALREADY CLAIMED by Alice");
+        assert_eq!(game.players[1].score, 0);
+        assert!(game.players[1].claimed_words.is_empty());
+
+        claim(&mut game, "dogs");
+        assert_eq!(game.players[1].score, 7);
+        assert_eq!(game.players[0].score, 5);
+        assert_eq!(game.leader().map(|p| p.name.as_str()), Some("Bob"));
+    }
+
+    #[test]
+    fn test_switching_keeps_each_players_input() {
+        let mut game = game();
+        game.on_key_char('c');
+        game.on_key_char('a');
+        game.switch_player();
+        game.on_key_char('d');
+        assert_eq!(game.players[0].input, "CA");
+        assert_eq!(game.players[1].input, "D");
+
+        game.switch_player();
+        game.on_key_char('t');
+        game.on_submit();
+        assert_eq!(game.players[0].score, 5);
+        assert_eq!(game.players[1].input, "D");
+    }
+
+    #[test]
+    fn test_round_end_locks_both_players() {
+        let mut game = game();
+        game.on_key_char('c');
+        for _ in 0..60 {
+            game.tick();
+        }
+        assert!(game.is_round_over());
+        assert!(game.players[0].input.is_empty());
+
+        claim(&mut game, "cat");
+        game.switch_player();
+        assert_eq!(game.active, 0);
+        assert_eq!(game.players[0].score, 0);
+        assert!(game.leader().is_none());
+    }
+}
@@ -0,0 +1,322 @@
+#![allow(dead_code)]
+//! Turning key presses into coordinator calls
+//!
+//! Each press is looked up in the coordinator's `KeyBindings` first, so
+//! the screens below dispatch on `Action`s rather than on physical keys.
+
+use crate::lobby::LobbyEvent;
+
+use super::keys::{Action, Key, KeyMode};
+use super::screen::{AppCoordinator, Screen};
+use super::state::{MissReason, DEFAULT_ROUND_DURATION};
+
+impl AppCoordinator {
+    /// Whether the current screen (or open prompt) takes typed text
+    pub fn key_mode(&self) -> KeyMode {
+        if self.announcement_draft.is_some() || self.status_draft.is_some() {
+            return KeyMode::Typing;
+        }
+        match &self.screen {
+            Screen::Menu { editing_handle: true, .. }
+            | Screen::Playing { .. }
+            | Screen::Hotseat { .. }
+            | Screen::Settings { .. } => KeyMode::Typing,
+            _ => KeyMode::Navigating,
+        }
+    }
+
+    /// Handle a key press on whatever screen is showing
+    pub fn handle_key(&mut self, key: Key) {
+        let Some(action) = self.key_bindings.action(key, self.key_mode()) else {
+            return;
+        };
+
+        // The host's announcement prompt captures all keys while it's open
+        if self.announcement_draft.is_some() {
+            match action {
+                Action::Quit => self.cancel_announcement(),
+                Action::Submit => self.send_announcement(),
+                Action::Backspace => self.announcement_backspace(),
+                Action::Type(c) => self.announcement_char(c),
+                _ => {}
+            }
+            return;
+        }
+
+        // Likewise the lobby status prompt
+        if self.status_draft.is_some() {
+            match action {
+                Action::Quit => self.cancel_status(),
+                Action::Submit => self.submit_status(),
+                Action::Backspace => self.status_backspace(),
+                Action::Type(c) => self.status_char(c),
+                _ => {}
+            }
+            return;
+        }
+
+        match &mut self.screen {
+            Screen::Menu { editing_handle, .. } => {
+                if *editing_handle {
+                    // Handle editing mode
+                    match action {
+                        Action::Quit => self.menu_cancel_handle(),
+                        Action::Submit | Action::NextField => self.menu_commit_handle(),
+                        Action::Backspace => self.menu_backspace(),
+                        Action::Type(c) if c.is_ascii_alphanumeric() || c == '_' => {
+                            self.menu_char(c)
+                        }
+                        _ => {}
+                    }
+                } else {
+                    // Handle navigation mode
+                    match action {
+                        Action::Quit => self.quit(),
+                        Action::MenuUp => self.menu_up(),
+                        Action::MenuDown => self.menu_down(),
+                        Action::Select => self.menu_select(),
+                        Action::NextField => self.menu_tab(),
+                        _ => {}
+                    }
+                }
+            }
+            Screen::Browser { .. } => match action {
+                Action::Quit => self.go_to_menu(),
+                Action::MenuUp => self.browser_up(),
+                Action::MenuDown => self.browser_down(),
+                Action::Select => self.browser_select(),
+                Action::Type('r' | 'R') => self.browser_retry(),
+                _ => {}
+            },
+            Screen::RackPreview { .. } => match action {
+                Action::Quit => self.go_to_menu(),
+                Action::Select => self.rack_preview_host(),
+                Action::Type('r' | 'R' | ' ') => self.rack_preview_reroll(),
+                _ => {}
+            },
+            Screen::HostLobby { lobby, countdown, .. } => match action {
+                Action::Quit => self.quit_hosting(),
+                Action::NextField => self.start_announcement(),
+                Action::Backspace => self.host_cancel_countdown(),
+                Action::MenuUp => self.host_lobby_up(),
+                Action::MenuDown => self.host_lobby_down(),
+                Action::MenuLeft => self.host_lobby_adjust_handicap(false),
+                Action::MenuRight => self.host_lobby_adjust_handicap(true),
+                Action::Type('r' | 'R') => self.host_lobby_cycle_rack(),
+                Action::Type('s' | 'S') => self.start_status(),
+                Action::Type('n' | 'N') => self.host_lobby_toggle_no_repeats(),
+                Action::Type('l' | 'L') => self.host_lobby_cycle_min_word_length(),
+                Action::Type('u' | 'U') => self.host_lobby_cycle_letters_used(),
+                Action::Type('p' | 'P') => self.host_lobby_cycle_reject_penalty(),
+                Action::Type('t' | 'T') => self.host_lobby_cycle_target_score(),
+                Action::Type('g' | 'G') => self.host_lobby_cycle_disconnect_grace(),
+                Action::Type('c' | 'C') => self.host_lobby_toggle_combos(),
+                Action::Type('*') => self.host_lobby_toggle_blanks(),
+                Action::Type('a' | 'A') => self.host_lobby_cycle_language(),
+                Action::Type('w' | 'W') => self.host_lobby_toggle_warm_up(),
+                Action::Type('b' | 'B') => self.host_lobby_cycle_bots(),
+                Action::Type('k' | 'K') => self.host_lobby_cycle_bot_skill(),
+                Action::Type('e' | 'E') => self.host_lobby_toggle_bots_rated(),
+                Action::Type('d' | 'D') => self.host_lobby_toggle_diagnostics(),
+                Action::Select => {
+                    // Only start countdown if we're not already counting down
+                    if lobby.can_start() && countdown.is_none() {
+                        // Generate letters and start countdown
+                        let letters = lobby.generate_letters();
+                        let count = lobby.start_countdown(letters, DEFAULT_ROUND_DURATION);
+                        *countdown = Some(count);
+                    }
+                }
+                _ => {}
+            },
+            Screen::JoinedLobby { .. } => match action {
+                // Leave the lobby
+                Action::Quit => self.go_to_menu(),
+                Action::Type('s' | 'S') => self.start_status(),
+                Action::Type('t' | 'T') => self.joined_lobby_toggle_share_input(),
+                _ => {}
+            },
+            Screen::Playing { app, hosted_lobby, joined_lobby, .. } => match action {
+                Action::Quit => {
+                    if app.is_round_over() {
+                        self.decline_rematch();
+                        self.go_to_menu();
+                    } else if hosted_lobby.is_some() {
+                        self.quit_hosting();
+                    }
+                }
+                // Round over: the host offers a rematch, players accept one
+                Action::Submit if app.is_round_over() => self.accept_rematch(),
+                Action::Submit => {
+                    let Some(word) = app.get_pending_claim() else {
+                        return;
+                    };
+                    if let Some(lobby) = hosted_lobby {
+                        // Host: arbitrate locally and broadcast
+                        for event in lobby.host_claim(&word).unwrap_or_default() {
+                            match event {
+                                LobbyEvent::ClaimAccepted { word, player_name, points, combo } => {
+                                    app.on_claim_accepted(word, player_name, points, combo);
+                                }
+                                LobbyEvent::ClaimRejected { word, reason } => {
+                                    app.on_claim_rejected(
+                                        word,
+                                        AppCoordinator::map_reject_reason_pub(reason),
+                                    );
+                                }
+                                LobbyEvent::RoundEnd { reason } => app.end_round(reason),
+                                _ => {}
+                            }
+                        }
+                        app.clear_input();
+                    } else if let Some(lobby) = joined_lobby {
+                        // Client: send claim to host, unless we already know
+                        // it's been taken
+                        if let Some(by) = app.claimed_by(&word).map(str::to_string) {
+                            app.on_claim_rejected(word, MissReason::AlreadyClaimed { by });
+                        } else {
+                            let _ = lobby.send_claim(&word);
+                        }
+                        app.clear_input();
+                    } else {
+                        // Solo: local validation
+                        app.on_submit();
+                    }
+                }
+                Action::Backspace => app.on_backspace(),
+                Action::NextField => self.start_announcement(),
+                Action::Type('?') => {
+                    // The host knows the round's dictionary; everyone else
+                    // counts from their own
+                    let remaining = hosted_lobby
+                        .as_ref()
+                        .and_then(|lobby| lobby.remaining_valid_count())
+                        .unwrap_or_else(|| app.remaining_valid_count());
+                    app.show_hint(remaining);
+                }
+                Action::Type(c) => app.on_key_char(c),
+                _ => {}
+            },
+            Screen::Hotseat { game } => match action {
+                Action::Quit => {
+                    if game.is_round_over() {
+                        self.go_to_menu();
+                    }
+                }
+                Action::Submit => game.on_submit(),
+                Action::Backspace => game.on_backspace(),
+                Action::NextField => game.switch_player(),
+                Action::Type(c) => game.on_key_char(c),
+                _ => {}
+            },
+            Screen::Rankings { .. } => match action {
+                Action::Quit => self.go_to_menu(),
+                Action::MenuUp => self.rankings_up(),
+                Action::MenuDown => self.rankings_down(),
+                Action::MenuRight | Action::Type('s' | 'S') => self.rankings_sort_next(),
+                Action::MenuLeft => self.rankings_sort_prev(),
+                Action::Type('h' | 'H') => self.go_to_match_history(),
+                _ => {}
+            },
+            Screen::MatchHistory { .. } => match action {
+                Action::Quit => self.match_history_back(),
+                Action::MenuUp => self.match_history_up(),
+                Action::MenuDown => self.match_history_down(),
+                Action::Select | Action::Type('c' | 'C') => self.match_history_copy_report(),
+                _ => {}
+            },
+            Screen::Settings { house_word_input: Some(_), .. } => match action {
+                Action::Quit => self.settings_cancel_house_word(),
+                Action::Submit => self.settings_submit_house_word(),
+                Action::Backspace => self.settings_house_word_backspace(),
+                Action::Type(c) => self.settings_house_word_char(c),
+                _ => {}
+            },
+            Screen::Settings { .. } => match action {
+                Action::Quit => self.go_to_menu(),
+                Action::Function(3) => self.settings_start_house_word(),
+                Action::Submit => self.settings_save(),
+                Action::NextField => self.settings_next_season(),
+                Action::Function(2) => self.settings_toggle_rack_telemetry(),
+                Action::Function(4) => self.settings_next_elo_k(),
+                Action::Function(5) => self.settings_next_theme(),
+                Action::Backspace => self.settings_backspace(),
+                Action::Type(c) if c.is_ascii_alphanumeric() || c == '_' => self.settings_char(c),
+                _ => {}
+            },
+            Screen::ResumePrompt { .. } => match action {
+                Action::Select => self.resume_round(),
+                Action::Quit => self.discard_resume(),
+                _ => {}
+            },
+            Screen::Error { .. } => match action {
+                Action::Quit | Action::Select => self.go_to_menu(),
+                _ => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::keys::KeyBindings;
+
+    /// A coordinator in a solo round with `word` typed
+    fn solo_round_with_input(keys: KeyBindings, word: &str) -> AppCoordinator {
+        let mut coordinator = AppCoordinator::new();
+        coordinator.key_bindings = keys;
+        // Solo Practice is the third menu item
+        coordinator.handle_key(Key::Down);
+        coordinator.handle_key(Key::Down);
+        coordinator.handle_key(Key::Enter);
+        for c in word.chars() {
+            coordinator.handle_key(Key::Char(c));
+        }
+        coordinator
+    }
+
+    fn solo_app(coordinator: &AppCoordinator) -> &super::super::App {
+        let Screen::Playing { app, .. } = &coordinator.screen else {
+            panic!("expected a solo round");
+        };
+        app
+    }
+
+    #[test]
+    fn test_enter_submits_with_default_bindings() {
+        let mut coordinator = solo_round_with_input(KeyBindings::default(), "qz");
+        assert_eq!(solo_app(&coordinator).input, "QZ");
+
+        coordinator.handle_key(Key::Enter);
+        assert!(solo_app(&coordinator).input.is_empty());
+        assert!(!solo_app(&coordinator).feedback.is_empty());
+    }
+
+    #[test]
+    fn test_remapped_submit_replaces_enter() {
+        let mut keys = KeyBindings::default();
+        keys.bind(Action::Submit, Key::Char(';'));
+        let mut coordinator = solo_round_with_input(keys, "qz");
+
+        // Enter no longer claims
+        coordinator.handle_key(Key::Enter);
+        assert_eq!(solo_app(&coordinator).input, "QZ");
+        assert!(solo_app(&coordinator).feedback.is_empty());
+
+        // The new key does, without being typed into the word
+        coordinator.handle_key(Key::Char(';'));
+        assert!(solo_app(&coordinator).input.is_empty());
+        assert!(!solo_app(&coordinator).feedback.is_empty());
+    }
+
+    #[test]
+    fn test_remapped_menu_keys_navigate() {
+        let mut coordinator = AppCoordinator::new();
+        coordinator.key_bindings.bind(Action::MenuDown, Key::Char('j'));
+        coordinator.handle_key(Key::Char('j'));
+        coordinator.handle_key(Key::Down);
+        assert!(matches!(coordinator.screen, Screen::Menu { selected: 1, .. }));
+        assert_eq!(coordinator.key_mode(), KeyMode::Navigating);
+    }
+}
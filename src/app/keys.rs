@@ -0,0 +1,289 @@
+#![allow(dead_code)]
+//! Key bindings: which key triggers which action
+//!
+//! The defaults match the keys shown on each screen. Players on other
+//! layouts can rebind them in `keys.txt` in the data directory, one
+//! `action = key` per line:
+//!
+//! ```text
+//! # Lines starting with # are ignored
+//! submit = ;
+//! menu_up = k
+//! menu_down = j
+//! ```
+//!
+//! Keys are a single character, `enter`, `esc`, `tab`, `backspace`,
+//! `space`, an arrow (`up`, `down`, `left`, `right`) or `f1`-`f12`.
+//! Actions left out keep their default.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Name of the bindings file inside the data directory
+pub const KEYS_FILE: &str = "keys.txt";
+
+/// A key press, independent of the terminal library
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Esc,
+    Tab,
+    Backspace,
+    Up,
+    Down,
+    Left,
+    Right,
+    F(u8),
+    /// Anything else (Home, Insert, ...), which nothing is bound to
+    Other,
+}
+
+impl Key {
+    /// Parse a key name as written in `keys.txt`
+    pub fn parse(name: &str) -> Option<Key> {
+        let mut chars = name.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Some(Key::Char(c));
+        }
+        let key = match name.to_ascii_lowercase().as_str() {
+            "enter" => Key::Enter,
+            "esc" => Key::Esc,
+            "tab" => Key::Tab,
+            "backspace" => Key::Backspace,
+            "space" => Key::Char(' '),
+            "up" => Key::Up,
+            "down" => Key::Down,
+            "left" => Key::Left,
+            "right" => Key::Right,
+            other => {
+                let n: u8 = other.strip_prefix('f')?.parse().ok()?;
+                if !(1..=12).contains(&n) {
+                    return None;
+                }
+                Key::F(n)
+            }
+        };
+        Some(key)
+    }
+}
+
+#[cfg(feature = "tui")]
+impl From<crossterm::event::KeyCode> for Key {
+    fn from(code: crossterm::event::KeyCode) -> Self {
+        use crossterm::event::KeyCode;
+        match code {
+            KeyCode::Char(c) => Key::Char(c),
+            KeyCode::Enter => Key::Enter,
+            KeyCode::Esc => Key::Esc,
+            KeyCode::Tab => Key::Tab,
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Up => Key::Up,
+            KeyCode::Down => Key::Down,
+            KeyCode::Left => Key::Left,
+            KeyCode::Right => Key::Right,
+            KeyCode::F(n) => Key::F(n),
+            _ => Key::Other,
+        }
+    }
+}
+
+/// Whether the current screen is moving through a list or taking text
+///
+/// Select and Submit share Enter by default; the mode says which one a
+/// press means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyMode {
+    Navigating,
+    Typing,
+}
+
+/// What a key press asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    /// Pick the highlighted item
+    Select,
+    /// Claim the typed word, or confirm the text being typed
+    Submit,
+    Backspace,
+    /// Back out of the current screen (quits from the main menu)
+    Quit,
+    /// Switch field, player or season, depending on the screen
+    NextField,
+    /// A character with no action bound to it: typed text or a shortcut
+    Type(char),
+    /// A function key with no action bound to it
+    Function(u8),
+}
+
+impl Action {
+    /// Every action that can be rebound, with its name in `keys.txt`
+    pub const BINDABLE: [(Action, &'static str); 9] = [
+        (Action::MenuUp, "menu_up"),
+        (Action::MenuDown, "menu_down"),
+        (Action::MenuLeft, "menu_left"),
+        (Action::MenuRight, "menu_right"),
+        (Action::Select, "select"),
+        (Action::Submit, "submit"),
+        (Action::Backspace, "backspace"),
+        (Action::Quit, "quit"),
+        (Action::NextField, "next_field"),
+    ];
+
+    /// Whether a press can mean this action in `mode`
+    fn applies_in(self, mode: KeyMode) -> bool {
+        match self {
+            Action::MenuUp
+            | Action::MenuDown
+            | Action::MenuLeft
+            | Action::MenuRight
+            | Action::Select => mode == KeyMode::Navigating,
+            Action::Submit => mode == KeyMode::Typing,
+            _ => true,
+        }
+    }
+}
+
+/// The key bound to each action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBindings {
+    keys: HashMap<Action, Key>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let keys = HashMap::from([
+            (Action::MenuUp, Key::Up),
+            (Action::MenuDown, Key::Down),
+            (Action::MenuLeft, Key::Left),
+            (Action::MenuRight, Key::Right),
+            (Action::Select, Key::Enter),
+            (Action::Submit, Key::Enter),
+            (Action::Backspace, Key::Backspace),
+            (Action::Quit, Key::Esc),
+            (Action::NextField, Key::Tab),
+        ]);
+        Self { keys }
+    }
+}
+
+impl KeyBindings {
+    /// Defaults with the rebindings in `contents` applied
+    ///
+    /// Unknown actions, unparseable keys and lines without `=` are skipped.
+    pub fn parse(contents: &str) -> Self {
+        let mut bindings = Self::default();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((name, key)) = line.split_once('=') else {
+                continue;
+            };
+            let action = Action::BINDABLE
+                .iter()
+                .find(|(_, n)| *n == name.trim())
+                .map(|(action, _)| *action);
+            let key = Key::parse(key.trim());
+            if let (Some(action), Some(key)) = (action, key) {
+                bindings.bind(action, key);
+            }
+        }
+        bindings
+    }
+
+    /// Load rebindings from `keys.txt` in `data_dir`
+    ///
+    /// A missing or unreadable file just means the defaults.
+    pub fn load(data_dir: &Path) -> Self {
+        std::fs::read_to_string(data_dir.join(KEYS_FILE))
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    /// Bind `action` to `key`, replacing its previous key
+    ///
+    /// Panics if `action` is `Type` or `Function`, which aren't bindable.
+    pub fn bind(&mut self, action: Action, key: Key) {
+        assert!(
+            Action::BINDABLE.iter().any(|(a, _)| *a == action),
+            "{:?} can't be bound",
+            action
+        );
+        self.keys.insert(action, key);
+    }
+
+    /// The key bound to `action`
+    pub fn key(&self, action: Action) -> Option<Key> {
+        self.keys.get(&action).copied()
+    }
+
+    /// What pressing `key` means in `mode`
+    ///
+    /// Bound actions win; otherwise characters and function keys pass
+    /// through as `Type` and `Function`.
+    pub fn action(&self, key: Key, mode: KeyMode) -> Option<Action> {
+        let bound = Action::BINDABLE
+            .iter()
+            .map(|(action, _)| *action)
+            .find(|action| action.applies_in(mode) && self.key(*action) == Some(key));
+        bound.or(match key {
+            Key::Char(c) => Some(Action::Type(c)),
+            Key::F(n) => Some(Action::Function(n)),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bindings_share_enter_by_mode() {
+        let keys = KeyBindings::default();
+        assert_eq!(keys.action(Key::Enter, KeyMode::Navigating), Some(Action::Select));
+        assert_eq!(keys.action(Key::Enter, KeyMode::Typing), Some(Action::Submit));
+        assert_eq!(keys.action(Key::Up, KeyMode::Typing), None);
+        assert_eq!(keys.action(Key::Esc, KeyMode::Typing), Some(Action::Quit));
+        assert_eq!(keys.action(Key::Char('r'), KeyMode::Navigating), Some(Action::Type('r')));
+        assert_eq!(keys.action(Key::F(4), KeyMode::Typing), Some(Action::Function(4)));
+        assert_eq!(keys.action(Key::Other, KeyMode::Typing), None);
+    }
+
+    #[test]
+    fn test_rebinding_submit_frees_enter() {
+        let mut keys = KeyBindings::default();
+        keys.bind(Action::Submit, Key::Char(';'));
+        assert_eq!(keys.action(Key::Char(';'), KeyMode::Typing), Some(Action::Submit));
+        assert_eq!(keys.action(Key::Enter, KeyMode::Typing), None);
+        // Select still has Enter
+        assert_eq!(keys.action(Key::Enter, KeyMode::Navigating), Some(Action::Select));
+    }
+
+    #[test]
+    fn test_parse_rebindings_known_actions_only() {
+        let keys = KeyBindings::parse(
+            "# Dvorak\nsubmit = ;\nmenu_up=k\nmenu_down = F3\nquit = \nbogus = x\nselect = nope\n",
+        );
+        assert_eq!(keys.key(Action::Submit), Some(Key::Char(';')));
+        assert_eq!(keys.key(Action::MenuUp), Some(Key::Char('k')));
+        assert_eq!(keys.key(Action::MenuDown), Some(Key::F(3)));
+        assert_eq!(keys.key(Action::Quit), Some(Key::Esc));
+        assert_eq!(keys.key(Action::Select), Some(Key::Enter));
+    }
+
+    #[test]
+    fn test_key_parse_names() {
+        assert_eq!(Key::parse("Space"), Some(Key::Char(' ')));
+        assert_eq!(Key::parse("backspace"), Some(Key::Backspace));
+        assert_eq!(Key::parse("f12"), Some(Key::F(12)));
+        assert_eq!(Key::parse("f13"), None);
+        assert_eq!(Key::parse(""), None);
+    }
+}
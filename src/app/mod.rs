@@ -1,7 +1,25 @@
 //! Application state and core logic
 
+//!
+//! `App` (one player's round) and the hotseat game are plain game logic.
+//! The screens and headless mode drive lobbies, so they need the `lan`
+//! feature.
+
+pub mod flavor;
+#[cfg(feature = "lan")]
+pub mod headless;
+pub mod hotseat;
+#[cfg(feature = "lan")]
+pub mod input;
+pub mod keys;
+#[cfg(feature = "lan")]
 pub mod screen;
 pub mod state;
+pub mod theme;
 
-pub use screen::{AppCoordinator, MenuOption, Screen};
+#[cfg(feature = "lan")]
+pub use screen::{AppCoordinator, MenuOption, RankingsSort, RankingsStatus, Screen};
+pub use flavor::FlavorText;
+pub use keys::{Action, Key, KeyBindings};
 pub use state::{App, DEFAULT_ROUND_DURATION};
+pub use theme::ThemeChoice;
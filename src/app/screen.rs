@@ -7,14 +7,65 @@
 //! - Hosted lobby
 //! - Joined lobby
 //! - Playing (solo or multiplayer)
+//! - Hotseat (two players sharing the keyboard)
 //! - End of round results
+//! - Match history, with shareable match reports
+//! - Resume prompt for an interrupted solo round
 
+use crate::game::seed::with_session_rng;
 use crate::game::LetterRack;
-use crate::lobby::{HostedLobby, JoinedLobby, LobbyBrowser, LobbyEvent};
-use crate::network::{ClaimRejectReason, PeerInfo};
-use crate::storage::CachedPlayerStats;
-
-use super::state::{App, DEFAULT_ROUND_DURATION};
+use crate::lobby::{
+    BrowserStatus, HostedLobby, JoinedLobby, LobbyBrowser, LobbyEvent, MAX_ANNOUNCEMENT_LEN,
+    MAX_RECONNECT_ATTEMPTS, MAX_STATUS_LEN,
+};
+use crate::network::{ClaimRejectReason, Message, PeerInfo, RoundEndReason};
+use crate::stats::MatchResult;
+use crate::storage::rebuild::CacheRebuild;
+use crate::storage::sync;
+use crate::storage::{CachedPlayerStats, RoundCheckpoint};
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+use super::flavor::FlavorText;
+use super::hotseat::{HotseatGame, HOTSEAT_GUEST};
+use super::keys::KeyBindings;
+use super::state::{App, Rematch, DEFAULT_ROUND_DURATION};
+use super::theme::ThemeChoice;
+
+/// How often (in seconds of round time) a solo round is checkpointed
+pub const CHECKPOINT_INTERVAL_SECS: u32 = 5;
+
+/// Longest house word that can be typed in Settings
+pub const MAX_HOUSE_WORD_LEN: usize = 20;
+
+/// Elo K factors F4 cycles through in Settings
+pub const ELO_K_CHOICES: [f64; 5] = [16.0, 24.0, 32.0, 48.0, 64.0];
+
+/// Most matches listed in the match history
+pub const MATCH_HISTORY_LEN: usize = 50;
+
+/// Longest handle that can be typed
+pub const MAX_HANDLE_LEN: usize = 12;
+
+/// How long typing has to pause before a handle edit is saved on its own
+pub const AUTOSAVE_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Trim a typed handle and check it can be used
+///
+/// Returns the message to show when it can't.
+pub fn normalize_handle(input: &str) -> Result<String, &'static str> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err("Handle cannot be empty");
+    }
+    if !trimmed.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err("Only letters, numbers, and _ allowed");
+    }
+    if trimmed.chars().count() > MAX_HANDLE_LEN {
+        return Err("Handle is too long");
+    }
+    Ok(trimmed.to_string())
+}
 
 /// Menu option on the main screen
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +73,7 @@ pub enum MenuOption {
     StartLobby,
     JoinLobby,
     SoloPractice,
+    Hotseat,
     Rankings,
     Settings,
     Quit,
@@ -34,6 +86,7 @@ impl MenuOption {
             MenuOption::StartLobby,
             MenuOption::JoinLobby,
             MenuOption::SoloPractice,
+            MenuOption::Hotseat,
             MenuOption::Rankings,
             MenuOption::Settings,
             MenuOption::Quit,
@@ -46,6 +99,7 @@ impl MenuOption {
             MenuOption::StartLobby => "Start Lobby",
             MenuOption::JoinLobby => "Join Lobby",
             MenuOption::SoloPractice => "Solo Practice",
+            MenuOption::Hotseat => "Hotseat (2 Players)",
             MenuOption::Rankings => "Rankings",
             MenuOption::Settings => "Settings",
             MenuOption::Quit => "Quit",
@@ -53,6 +107,85 @@ impl MenuOption {
     }
 }
 
+/// What the rankings screen has to show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankingsStatus {
+    /// Cached rankings are up to date
+    Ready,
+    /// No matches have been recorded yet
+    Empty,
+    /// New matches arrived and the cached rankings are being rebuilt
+    Rebuilding,
+}
+
+/// Column the rankings table is ordered by (highest first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankingsSort {
+    Elo,
+    Wins,
+    Points,
+    BestScore,
+    LongestWord,
+}
+
+impl RankingsSort {
+    /// Sort columns in the order they're cycled through
+    pub fn all() -> &'static [RankingsSort] {
+        &[
+            RankingsSort::Elo,
+            RankingsSort::Wins,
+            RankingsSort::Points,
+            RankingsSort::BestScore,
+            RankingsSort::LongestWord,
+        ]
+    }
+
+    /// Header label for the column
+    pub fn label(&self) -> &'static str {
+        match self {
+            RankingsSort::Elo => "Elo",
+            RankingsSort::Wins => "Wins",
+            RankingsSort::Points => "Points",
+            RankingsSort::BestScore => "Best",
+            RankingsSort::LongestWord => "Longest",
+        }
+    }
+
+    /// The column after this one, wrapping around
+    pub fn next(self) -> Self {
+        let all = Self::all();
+        let i = all.iter().position(|s| *s == self).unwrap_or(0);
+        all[(i + 1) % all.len()]
+    }
+
+    /// The column before this one, wrapping around
+    pub fn prev(self) -> Self {
+        let all = Self::all();
+        let i = all.iter().position(|s| *s == self).unwrap_or(0);
+        all[(i + all.len() - 1) % all.len()]
+    }
+
+    /// Order `players` by this column, highest first
+    ///
+    /// Ties fall back to Elo, then handle, so the order never jumps around.
+    pub fn sort(self, players: &mut [CachedPlayerStats]) {
+        players.sort_by(|a, b| {
+            let by_column = match self {
+                RankingsSort::Elo => b.elo.total_cmp(&a.elo),
+                RankingsSort::Wins => b.wins.cmp(&a.wins),
+                RankingsSort::Points => b.total_points.cmp(&a.total_points),
+                RankingsSort::BestScore => b.best_score.cmp(&a.best_score),
+                RankingsSort::LongestWord => {
+                    b.longest_word.chars().count().cmp(&a.longest_word.chars().count())
+                }
+            };
+            by_column
+                .then_with(|| b.elo.total_cmp(&a.elo))
+                .then_with(|| a.handle.cmp(&b.handle))
+        });
+    }
+}
+
 /// The current application screen
 pub enum Screen {
     /// Main menu
@@ -61,6 +194,8 @@ pub enum Screen {
         handle: String,
         handle_input: String,
         editing_handle: bool,
+        /// Why the typed handle was rejected, shown until it's edited
+        handle_error: Option<String>,
     },
     /// Browsing for lobbies to join
     Browser {
@@ -69,10 +204,19 @@ pub enum Screen {
         selected: usize,
         player_name: String,
     },
+    /// Sampling racks before hosting; the one shown is dealt first
+    RackPreview {
+        handle: String,
+        /// Seed the shown rack was dealt from
+        seed: u64,
+        letters: Vec<char>,
+    },
     /// Hosting a lobby
     HostLobby {
         lobby: HostedLobby,
         countdown: Option<u32>,
+        /// Player highlighted for handicap adjustment
+        selected: usize,
     },
     /// Joined a lobby
     JoinedLobby {
@@ -87,11 +231,24 @@ pub enum Screen {
         hosted_lobby: Option<HostedLobby>,
         joined_lobby: Option<JoinedLobby>,
     },
+    /// Two players racing on one rack at the same keyboard
+    Hotseat { game: HotseatGame },
     /// Rankings leaderboard
     Rankings {
         players: Vec<CachedPlayerStats>,
         current_handle: String,
         scroll_offset: usize,
+        status: RankingsStatus,
+        /// Column the table is ordered by
+        sort: RankingsSort,
+    },
+    /// Recent matches, most recent first
+    MatchHistory {
+        matches: Vec<MatchResult>,
+        selected: usize,
+        current_handle: String,
+        /// Outcome of the last report save
+        feedback: String,
     },
     /// Settings page
     Settings {
@@ -99,6 +256,23 @@ pub enum Screen {
         handle_input: String,
         editing: bool,
         feedback: String,
+        /// Season new matches are recorded into
+        season: u32,
+        /// Whether rack difficulty is recorded after solo rounds
+        rack_telemetry: bool,
+        /// Rounds recorded and average fraction of max points achieved
+        rack_report: (usize, f64),
+        /// Extra words this device's dictionary accepts
+        house_words: Vec<String>,
+        /// House word being typed (`Some` while the prompt is open)
+        house_word_input: Option<String>,
+        /// K factor the Elo ratings are computed with
+        elo_k: f64,
+    },
+    /// Offer to resume a solo round interrupted by a crash
+    ResumePrompt {
+        checkpoint: RoundCheckpoint,
+        handle: String,
     },
     /// Connection error
     Error {
@@ -112,6 +286,22 @@ pub struct AppCoordinator {
     pub screen: Screen,
     /// Whether the application should quit
     pub should_quit: bool,
+    /// Announcement being typed by the host (`Some` while the prompt is open)
+    pub announcement_draft: Option<String>,
+    /// Lobby status being typed (`Some` while the prompt is open)
+    pub status_draft: Option<String>,
+    /// Derived cache rebuild running in the background, if any
+    pub cache_rebuild: Option<CacheRebuild>,
+    /// When the handle being typed was last changed, while it's unsaved
+    handle_edited_at: Option<Instant>,
+    /// Whether the host's connection diagnostics overlay is showing
+    pub show_diagnostics: bool,
+    /// Feedback strings, with any overrides from the data directory
+    pub flavor: FlavorText,
+    /// Color theme the UI draws with (for this session)
+    pub theme: ThemeChoice,
+    /// Which key triggers which action, with any rebindings from the data directory
+    pub key_bindings: KeyBindings,
 }
 
 impl Default for AppCoordinator {
@@ -138,8 +328,21 @@ impl AppCoordinator {
                 handle: default_handle.clone(),
                 handle_input: default_handle,
                 editing_handle: false,
+                handle_error: None,
             },
             should_quit: false,
+            announcement_draft: None,
+            status_draft: None,
+            cache_rebuild: None,
+            handle_edited_at: None,
+            show_diagnostics: false,
+            flavor: crate::storage::Storage::data_dir()
+                .map(|dir| FlavorText::load(&dir))
+                .unwrap_or_default(),
+            theme: ThemeChoice::default(),
+            key_bindings: crate::storage::Storage::data_dir()
+                .map(|dir| KeyBindings::load(&dir))
+                .unwrap_or_default(),
         }
     }
 
@@ -157,6 +360,170 @@ impl AppCoordinator {
         }
     }
 
+    /// Show the resume prompt if a solo round was interrupted last session
+    pub fn offer_resume(&mut self) {
+        use crate::storage::Storage;
+        if !matches!(self.screen, Screen::Menu { .. }) {
+            return;
+        }
+        let checkpoint = Storage::open()
+            .ok()
+            .and_then(|storage| storage.load_round_checkpoint().ok().flatten());
+        if let Some(checkpoint) = checkpoint {
+            let handle = self.get_current_handle();
+            self.screen = Screen::ResumePrompt { checkpoint, handle };
+        }
+    }
+
+    /// Resume the interrupted solo round from the prompt
+    pub fn resume_round(&mut self) {
+        if let Screen::ResumePrompt { checkpoint, .. } = &self.screen {
+            let mut app = App::from_checkpoint(checkpoint);
+            app.flavor = self.flavor.clone();
+            self.screen = Screen::Playing {
+                app,
+                is_host: true,
+                hosted_lobby: None,
+                joined_lobby: None,
+            };
+        }
+    }
+
+    /// Discard the interrupted solo round and go to the menu
+    pub fn discard_resume(&mut self) {
+        use crate::storage::Storage;
+        if let Screen::ResumePrompt { .. } = &self.screen {
+            if let Ok(storage) = Storage::open() {
+                let _ = storage.clear_round_checkpoint();
+            }
+            self.go_to_menu();
+        }
+    }
+
+    /// Save (or clear, once finished) the checkpoint for the current solo round
+    ///
+    /// Called once per second of round time; only writes every
+    /// `CHECKPOINT_INTERVAL_SECS` to keep disk churn low.
+    pub fn checkpoint_solo_round(&mut self) {
+        use crate::storage::Storage;
+        if let Screen::Playing {
+            app,
+            hosted_lobby: None,
+            joined_lobby: None,
+            ..
+        } = &mut self.screen
+        {
+            if app.is_round_over() {
+                if let Ok(storage) = Storage::open() {
+                    let _ = storage.clear_round_checkpoint();
+                    // No-op unless the player opted in from Settings
+                    let _ = storage.record_rack_difficulty(&app.rack_difficulty());
+                    // Read the cached records before this round is written,
+                    // so they're the ones to beat
+                    let previous = storage
+                        .handle()
+                        .ok()
+                        .flatten()
+                        .and_then(|handle| storage.get_cached_stats(&handle).ok().flatten());
+                    if let Some(previous) = previous {
+                        app.new_best = Self::personal_best(app, &previous);
+                    }
+                    if let Ok(Some(handle)) = storage.handle() {
+                        let _ = Self::record_solo_match(&storage, app, &handle);
+                    }
+                }
+            } else if app.time_remaining % CHECKPOINT_INTERVAL_SECS == 0 {
+                if let Ok(storage) = Storage::open() {
+                    let _ = storage.save_round_checkpoint(&app.checkpoint());
+                }
+            }
+        }
+    }
+
+    /// Log a finished solo round as a single-player `match_end`, with a
+    /// `word_claimed` for each word, so it counts toward lifetime stats
+    ///
+    /// Elo only rates matches with two or more players, so ratings are
+    /// unaffected.
+    fn record_solo_match(
+        storage: &crate::storage::Storage,
+        app: &App,
+        handle: &str,
+    ) -> Result<(), crate::storage::StorageError> {
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        let season = storage.current_season()?;
+        let mut result = MatchResult::with_host_counter(
+            now_ms,
+            storage.next_match_counter()?,
+            vec![(handle.to_string(), app.score)],
+            storage.actor_id().to_hex(),
+        )
+        .in_season(season);
+        // A solo round is its own match, so its claims hang off the match ID
+        result.round_id = Some(result.match_id);
+
+        // Handles and rack words are plain letters, digits and underscores,
+        // so nothing here needs escaping
+        for claimed in app.claimed_words() {
+            let payload = format!(
+                r#"{{"word":"{}","player_name":"{}","points":{},"round_id":{}}}"#,
+                claimed.word, handle, claimed.points, result.match_id
+            );
+            storage.append_event("word_claimed", &payload)?;
+        }
+        storage.append_event("match_end", &result.to_json())?;
+        Ok(())
+    }
+
+    /// Log a finished hosted round as a `match_end`, with a `word_claimed`
+    /// for each word
+    ///
+    /// A warm-up round with the host alone is logged like a solo round.
+    fn record_hosted_match(
+        storage: &crate::storage::Storage,
+        lobby: &mut HostedLobby,
+    ) -> Result<(), crate::storage::StorageError> {
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        let counter = storage.next_match_counter()?;
+        let season = storage.current_season()?;
+        let Some(result) = lobby.take_match_result(now_ms, counter) else {
+            return Ok(());
+        };
+        let result = result.in_season(season);
+        let round_id = result.round_id.unwrap_or(result.match_id);
+        // Players' names weren't picked under our handle rules, so escape them
+        for claim in lobby.round_claims() {
+            let payload = format!(
+                r#"{{"word":"{}","player_name":"{}","points":{},"round_id":{}}}"#,
+                claim.word,
+                crate::stats::escape_json(&claim.player_name),
+                claim.points,
+                round_id
+            );
+            storage.append_event("word_claimed", &payload)?;
+        }
+        storage.append_event("match_end", &result.to_json())?;
+        Ok(())
+    }
+
+    /// Celebration line for a solo round that beat the player's stored records
+    ///
+    /// A higher score takes precedence over a longer word.
+    fn personal_best(app: &App, previous: &CachedPlayerStats) -> Option<String> {
+        if app.score > previous.best_score {
+            return Some(format!("NEW BEST: {} pts!", app.score));
+        }
+        let longest = app.longest_claimed_word()?;
+        (longest.word.len() > previous.longest_word.len())
+            .then(|| format!("NEW LONGEST WORD: {}!", longest.word.to_uppercase()))
+    }
+
     /// Quit the application
     pub fn quit(&mut self) {
         self.should_quit = true;
@@ -164,17 +531,181 @@ impl AppCoordinator {
 
     /// Go back to the main menu
     pub fn go_to_menu(&mut self) {
+        self.announcement_draft = None;
+        self.status_draft = None;
         let handle = self.get_current_handle();
         self.screen = Screen::Menu {
             selected: 0,
             handle: handle.clone(),
             handle_input: handle,
             editing_handle: false,
+            handle_error: None,
+        };
+    }
+
+    /// Whether the current screen belongs to a lobby we're hosting
+    fn is_hosting(&self) -> bool {
+        matches!(
+            self.screen,
+            Screen::HostLobby { .. } | Screen::Playing { hosted_lobby: Some(_), .. }
+        )
+    }
+
+    /// Open the announcement prompt (host only)
+    pub fn start_announcement(&mut self) {
+        if self.is_hosting() {
+            self.announcement_draft = Some(String::new());
+        }
+    }
+
+    /// Close the announcement prompt without sending
+    pub fn cancel_announcement(&mut self) {
+        self.announcement_draft = None;
+    }
+
+    /// Announcement prompt: type a character
+    pub fn announcement_char(&mut self, c: char) {
+        if let Some(draft) = &mut self.announcement_draft {
+            if draft.chars().count() < MAX_ANNOUNCEMENT_LEN {
+                draft.push(c);
+            }
+        }
+    }
+
+    /// Announcement prompt: delete a character
+    pub fn announcement_backspace(&mut self) {
+        if let Some(draft) = &mut self.announcement_draft {
+            draft.pop();
+        }
+    }
+
+    /// Announcement prompt: broadcast the draft and close the prompt
+    pub fn send_announcement(&mut self) {
+        let Some(draft) = self.announcement_draft.take() else {
+            return;
+        };
+        match &mut self.screen {
+            Screen::HostLobby { lobby, .. } => {
+                lobby.announce(&draft);
+            }
+            Screen::Playing {
+                app,
+                hosted_lobby: Some(lobby),
+                ..
+            } => {
+                if let Some(LobbyEvent::Announcement { text }) = lobby.announce(&draft) {
+                    app.on_announcement(text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Open the status prompt (while waiting in a lobby)
+    pub fn start_status(&mut self) {
+        if matches!(self.screen, Screen::HostLobby { .. } | Screen::JoinedLobby { .. }) {
+            self.status_draft = Some(String::new());
+        }
+    }
+
+    /// Close the status prompt without changing anything
+    pub fn cancel_status(&mut self) {
+        self.status_draft = None;
+    }
+
+    /// Status prompt: type a character
+    pub fn status_char(&mut self, c: char) {
+        if let Some(draft) = &mut self.status_draft {
+            if draft.chars().count() < MAX_STATUS_LEN && !c.is_control() {
+                draft.push(c);
+            }
+        }
+    }
+
+    /// Status prompt: delete a character
+    pub fn status_backspace(&mut self) {
+        if let Some(draft) = &mut self.status_draft {
+            draft.pop();
+        }
+    }
+
+    /// Status prompt: set the draft as our status and close the prompt
+    ///
+    /// An empty draft clears the status.
+    pub fn submit_status(&mut self) {
+        let Some(draft) = self.status_draft.take() else {
+            return;
         };
+        match &mut self.screen {
+            Screen::HostLobby { lobby, .. } => {
+                lobby.set_status(&draft);
+            }
+            Screen::JoinedLobby { lobby, .. } => {
+                let _ = lobby.set_status(&draft);
+            }
+            _ => {}
+        }
+    }
+
+    /// Joined lobby: opt in to (or out of) sharing what we type with the host
+    pub fn joined_lobby_toggle_share_input(&mut self) {
+        if let Screen::JoinedLobby { lobby, countdown: None } = &mut self.screen {
+            let share = !lobby.share_input();
+            let _ = lobby.set_share_input(share);
+        }
+    }
+
+    /// Enter on the end-of-round screen: the host offers a rematch, a
+    /// player accepts the one on offer
+    pub fn accept_rematch(&mut self) {
+        let Screen::Playing { app, hosted_lobby, joined_lobby, .. } = &mut self.screen else {
+            return;
+        };
+        if !app.is_round_over() || app.countdown.is_some() {
+            return;
+        }
+        if let Some(lobby) = hosted_lobby {
+            if app.rematch == Some(Rematch::Waiting) {
+                return;
+            }
+            match lobby.request_rematch() {
+                Some(LobbyEvent::Countdown { countdown, .. }) => {
+                    app.rematch = Some(Rematch::Waiting);
+                    app.begin_countdown(countdown);
+                }
+                Some(_) => app.rematch = Some(Rematch::Waiting),
+                None => {}
+            }
+        } else if let Some(lobby) = joined_lobby {
+            if app.rematch == Some(Rematch::Offered) && lobby.vote_rematch(true).is_ok() {
+                app.rematch = Some(Rematch::Waiting);
+            }
+        }
+    }
+
+    /// Turn down the rematch on offer, if there is one (before leaving)
+    pub fn decline_rematch(&mut self) {
+        if let Screen::Playing { app, joined_lobby: Some(lobby), .. } = &mut self.screen {
+            if app.rematch == Some(Rematch::Offered) {
+                let _ = lobby.vote_rematch(false);
+            }
+        }
+    }
+
+    /// Tick the host's rematch countdown, starting the round at zero
+    pub fn tick_rematch_countdown(&mut self) {
+        let Screen::Playing { app, hosted_lobby, joined_lobby, .. } = &mut self.screen else {
+            return;
+        };
+        if let Some(event) = hosted_lobby.as_mut().and_then(|lobby| lobby.tick_countdown()) {
+            Self::handle_multiplayer_event(app, hosted_lobby, joined_lobby, event);
+        }
     }
 
     /// Quit hosting: properly shut down the lobby and return to menu
     pub fn quit_hosting(&mut self) {
+        self.announcement_draft = None;
+        self.status_draft = None;
         let handle = self.get_current_handle();
         let old_screen = std::mem::replace(
             &mut self.screen,
@@ -183,6 +714,7 @@ impl AppCoordinator {
                 handle: handle.clone(),
                 handle_input: handle,
                 editing_handle: false,
+                handle_error: None,
             },
         );
         match old_screen {
@@ -190,7 +722,7 @@ impl AppCoordinator {
                 let _ = lobby.shutdown();
             }
             Screen::Playing { hosted_lobby: Some(mut lobby), .. } => {
-                lobby.end_round();
+                lobby.end_round(RoundEndReason::HostEnded);
                 let _ = lobby.shutdown();
             }
             _ => {}
@@ -202,11 +734,15 @@ impl AppCoordinator {
         match &self.screen {
             Screen::Menu { handle, .. } => handle.clone(),
             Screen::Browser { player_name, .. } => player_name.clone(),
+            Screen::RackPreview { handle, .. } => handle.clone(),
             Screen::HostLobby { lobby, .. } => lobby.host_name.clone(),
             Screen::JoinedLobby { lobby, .. } => lobby.player_name.clone(),
             Screen::Playing { .. } => "Player".to_string(),
+            Screen::Hotseat { game } => game.players[0].name.clone(),
             Screen::Rankings { current_handle, .. } => current_handle.clone(),
+            Screen::MatchHistory { current_handle, .. } => current_handle.clone(),
             Screen::Settings { handle, .. } => handle.clone(),
+            Screen::ResumePrompt { handle, .. } => handle.clone(),
             Screen::Error { .. } => "Player".to_string(),
         }
     }
@@ -231,73 +767,105 @@ impl AppCoordinator {
 
     /// Handle menu character input (for handle editing)
     pub fn menu_char(&mut self, c: char) {
-        if let Screen::Menu { handle_input, editing_handle, .. } = &mut self.screen {
-            if *editing_handle && handle_input.len() < 12 {
+        if let Screen::Menu { handle_input, editing_handle, handle_error, .. } = &mut self.screen {
+            if *editing_handle && handle_input.len() < MAX_HANDLE_LEN {
                 handle_input.push(c);
+                *handle_error = None;
+                self.handle_edited_at = Some(Instant::now());
             }
         }
     }
 
     /// Handle menu backspace (for handle editing)
     pub fn menu_backspace(&mut self) {
-        if let Screen::Menu { handle_input, editing_handle, .. } = &mut self.screen {
+        if let Screen::Menu { handle_input, editing_handle, handle_error, .. } = &mut self.screen {
             if *editing_handle {
                 handle_input.pop();
+                *handle_error = None;
+                self.handle_edited_at = Some(Instant::now());
             }
         }
     }
 
-    /// Handle Tab key to toggle handle editing
+    /// Handle Tab key: start editing the handle, or commit the edit
     pub fn menu_tab(&mut self) {
-        if let Screen::Menu { handle, handle_input, editing_handle, .. } = &mut self.screen {
+        if let Screen::Menu { editing_handle, .. } = &mut self.screen {
             if *editing_handle {
-                // Finish editing - save the input
-                if !handle_input.is_empty() {
-                    *handle = handle_input.clone();
-                    Self::persist_handle(handle);
-                } else {
-                    // Restore previous handle if empty
-                    *handle_input = handle.clone();
+                self.menu_commit_handle();
+            } else {
+                *editing_handle = true;
+            }
+        }
+    }
+
+    /// Commit the typed handle
+    ///
+    /// An invalid handle keeps the editor open with an error; only a valid
+    /// one is saved.
+    pub fn menu_commit_handle(&mut self) {
+        if let Screen::Menu { handle, handle_input, editing_handle, handle_error, .. } = &mut self.screen {
+            if !*editing_handle {
+                return;
+            }
+            match normalize_handle(handle_input) {
+                Ok(normalized) => {
+                    if normalized != *handle {
+                        Self::persist_handle(&normalized);
+                    }
+                    *handle = normalized.clone();
+                    *handle_input = normalized;
+                    *handle_error = None;
+                    *editing_handle = false;
+                    self.handle_edited_at = None;
                 }
+                Err(message) => *handle_error = Some(message.to_string()),
+            }
+        }
+    }
+
+    /// Stop editing the handle without saving, restoring the previous one
+    pub fn menu_cancel_handle(&mut self) {
+        if let Screen::Menu { handle, handle_input, editing_handle, handle_error, .. } = &mut self.screen {
+            *handle_input = handle.clone();
+            *handle_error = None;
+            *editing_handle = false;
+            self.handle_edited_at = None;
+        }
+    }
+
+    /// Pick the menu option at `index`, as if it was highlighted and chosen
+    ///
+    /// Ignored while the handle is being edited.
+    pub fn menu_click(&mut self, index: usize) {
+        if let Screen::Menu { selected, editing_handle: false, .. } = &mut self.screen {
+            if index < MenuOption::all().len() {
+                *selected = index;
+                self.menu_select();
             }
-            *editing_handle = !*editing_handle;
         }
     }
 
     /// Handle menu selection (Enter)
     pub fn menu_select(&mut self) {
-        let (selected, handle, _editing_handle) = match &self.screen {
-            Screen::Menu { selected, handle, editing_handle, handle_input, .. } => {
-                if *editing_handle {
-                    // Just finish editing
-                    let mut h = handle.clone();
-                    if !handle_input.is_empty() {
-                        h = handle_input.clone();
-                    }
-                    self.screen = Screen::Menu {
-                        selected: *selected,
-                        handle: h.clone(),
-                        handle_input: h,
-                        editing_handle: false,
-                    };
-                    return;
-                }
-                (*selected, handle.clone(), *editing_handle)
+        let (selected, handle) = match &self.screen {
+            Screen::Menu { editing_handle: true, .. } => {
+                // Enter finishes editing rather than picking a menu item
+                self.menu_commit_handle();
+                return;
             }
+            Screen::Menu { selected, handle, .. } => (*selected, handle.clone()),
             _ => return,
         };
 
         let option = MenuOption::all()[selected];
         match option {
             MenuOption::StartLobby => {
-                match HostedLobby::new(handle) {
-                    Ok(lobby) => {
-                        self.screen = Screen::HostLobby { lobby, countdown: None };
-                    }
-                    Err(e) => {
-                        self.screen = Screen::Error { message: e };
-                    }
-                }
+                let seed = with_session_rng(|rng| rng.random());
+                self.screen = Screen::RackPreview {
+                    handle,
+                    seed,
+                    letters: Self::preview_letters(seed),
+                };
             }
             MenuOption::JoinLobby => {
                 match LobbyBrowser::new() {
@@ -316,6 +884,7 @@ impl AppCoordinator {
             }
             MenuOption::SoloPractice => {
                 let mut app = App::new();
+                app.flavor = self.flavor.clone();
                 let letters = LetterRack::generate().letters().to_vec();
                 app.start_round(letters, DEFAULT_ROUND_DURATION);
                 self.screen = Screen::Playing {
@@ -325,6 +894,16 @@ impl AppCoordinator {
                     joined_lobby: None,
                 };
             }
+            MenuOption::Hotseat => {
+                let letters = LetterRack::generate().letters().to_vec();
+                let mut game = HotseatGame::new(
+                    [handle, HOTSEAT_GUEST.to_string()],
+                    letters,
+                    DEFAULT_ROUND_DURATION,
+                );
+                game.flavor = self.flavor.clone();
+                self.screen = Screen::Hotseat { game };
+            }
             MenuOption::Rankings => {
                 self.go_to_rankings(handle);
             }
@@ -337,20 +916,55 @@ impl AppCoordinator {
         }
     }
 
+    /// Rack preview: deal a different sample rack
+    pub fn rack_preview_reroll(&mut self) {
+        if let Screen::RackPreview { seed, letters, .. } = &mut self.screen {
+            *seed = with_session_rng(|rng| rng.random());
+            *letters = Self::preview_letters(*seed);
+        }
+    }
+
+    /// The rack a newly hosted lobby deals first for `seed`
+    fn preview_letters(seed: u64) -> Vec<char> {
+        HostedLobby::default_rack_config().rack_for_seed(seed).letters().to_vec()
+    }
+
+    /// Rack preview: host a lobby whose first round deals the shown rack
+    pub fn rack_preview_host(&mut self) {
+        let (handle, seed) = match &self.screen {
+            Screen::RackPreview { handle, seed, .. } => (handle.clone(), *seed),
+            _ => return,
+        };
+        match HostedLobby::new(handle) {
+            Ok(mut lobby) => {
+                lobby.set_first_rack_seed(seed);
+                self.screen = Screen::HostLobby {
+                    lobby,
+                    countdown: None,
+                    selected: 0,
+                };
+            }
+            Err(e) => {
+                self.screen = Screen::Error { message: e };
+            }
+        }
+    }
+
     /// Navigate to rankings screen
     fn go_to_rankings(&mut self, handle: String) {
         use crate::storage::Storage;
 
-        let mut players = Vec::new();
-        if let Ok(storage) = Storage::open() {
-            // Rebuild caches if needed
-            let _ = storage.rebuild_derived_caches();
-            if let Ok(leaderboard) = storage.get_cached_leaderboard() {
-                for (player_handle, _elo) in &leaderboard {
-                    if let Ok(Some(stats)) = storage.get_cached_stats(player_handle) {
-                        players.push(stats);
-                    }
-                }
+        // A stale cache is shown with an indicator while it's rebuilt in the
+        // background
+        let storage = Storage::open().ok();
+        let (players, status) = storage
+            .as_ref()
+            .map(Self::load_rankings)
+            .unwrap_or((Vec::new(), RankingsStatus::Empty));
+        if status == RankingsStatus::Rebuilding && self.cache_rebuild.is_none() {
+            if let Ok(path) = Storage::db_path() {
+                let key = storage.as_ref().and_then(Storage::payload_key);
+                self.cache_rebuild = Some(CacheRebuild::spawn(path, key));
             }
         }
 
@@ -358,25 +972,250 @@ impl AppCoordinator {
             players,
             current_handle: handle,
             scroll_offset: 0,
+            status,
+            sort: RankingsSort::Elo,
         };
     }
 
+    /// Read the cached rankings and whether they need a rebuild first
+    fn load_rankings(storage: &crate::storage::Storage) -> (Vec<CachedPlayerStats>, RankingsStatus) {
+        let players = storage.get_all_cached_stats().unwrap_or_default();
+
+        let status = if storage.caches_need_rebuild().unwrap_or(false) {
+            RankingsStatus::Rebuilding
+        } else if players.is_empty() {
+            RankingsStatus::Empty
+        } else {
+            RankingsStatus::Ready
+        };
+        (players, status)
+    }
+
+    /// Check on a background cache rebuild, showing fresh rankings once done
+    ///
+    /// If the rebuild fails, whatever the cache holds is shown rather than
+    /// retrying.
+    fn finish_cache_rebuild(&mut self) {
+        use crate::storage::Storage;
+
+        let Some(rebuild) = &self.cache_rebuild else {
+            return;
+        };
+        if rebuild.try_finish().is_none() {
+            return;
+        }
+        self.cache_rebuild = None;
+
+        if let Screen::Rankings { players, status, scroll_offset, sort, .. } = &mut self.screen {
+            if let Ok(storage) = Storage::open() {
+                *players = Self::load_rankings(&storage).0;
+                sort.sort(players);
+            }
+            *status = if players.is_empty() {
+                RankingsStatus::Empty
+            } else {
+                RankingsStatus::Ready
+            };
+            *scroll_offset = (*scroll_offset).min(players.len().saturating_sub(1));
+        }
+    }
+
     /// Navigate to settings screen
     fn go_to_settings(&mut self, handle: String) {
+        use crate::storage::Storage;
+
+        let season = Storage::open()
+            .ok()
+            .and_then(|storage| storage.current_season().ok())
+            .unwrap_or(1);
+        let (rack_telemetry, rack_report) = Self::load_rack_telemetry();
+        let elo_k = Storage::open()
+            .ok()
+            .and_then(|storage| storage.elo_k_factor().ok())
+            .unwrap_or(crate::stats::DEFAULT_K);
+
         self.screen = Screen::Settings {
             handle: handle.clone(),
             handle_input: handle,
             editing: true,
             feedback: String::new(),
+            season,
+            rack_telemetry,
+            rack_report,
+            house_words: crate::game::dictionary::house_words(),
+            house_word_input: None,
+            elo_k,
+        };
+    }
+
+    /// Load the rack telemetry opt-in and a summary of what's been recorded
+    fn load_rack_telemetry() -> (bool, (usize, f64)) {
+        use crate::storage::Storage;
+
+        let Ok(storage) = Storage::open() else {
+            return (false, (0, 0.0));
+        };
+        let enabled = storage.rack_telemetry_enabled().unwrap_or(false);
+        let report = storage.rack_difficulty_report().unwrap_or_default();
+        let average = if report.is_empty() {
+            0.0
+        } else {
+            report.iter().map(|r| r.achieved_fraction()).sum::<f64>() / report.len() as f64
         };
+        (enabled, (report.len(), average))
+    }
+
+    /// Settings: opt in to (or out of) local rack difficulty recording
+    pub fn settings_toggle_rack_telemetry(&mut self) {
+        use crate::storage::{Storage, StorageError};
+
+        if let Screen::Settings { rack_telemetry, feedback, .. } = &mut self.screen {
+            let enabled = !*rack_telemetry;
+            let changed = Storage::open()
+                .and_then(|storage| storage.set_rack_telemetry(enabled).map_err(StorageError::from));
+            match changed {
+                Ok(()) => {
+                    *rack_telemetry = enabled;
+                    *feedback = if enabled {
+                        "Rack stats on (kept on this device only)".to_string()
+                    } else {
+                        "Rack stats off".to_string()
+                    };
+                }
+                Err(e) => {
+                    *feedback = format!("Couldn't change rack stats: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Settings: end the current season and start the next one
+    pub fn settings_next_season(&mut self) {
+        use crate::storage::{Storage, StorageError};
+
+        if let Screen::Settings { season, feedback, .. } = &mut self.screen {
+            let advanced = Storage::open()
+                .and_then(|storage| storage.advance_season().map_err(StorageError::from));
+            match advanced {
+                Ok(next) => {
+                    *season = next;
+                    *feedback = format!("Season {} started", next);
+                }
+                Err(e) => {
+                    *feedback = format!("Couldn't start a new season: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Settings: switch to the next Elo K factor and recompute ratings with it
+    pub fn settings_next_elo_k(&mut self) {
+        use crate::storage::Storage;
+
+        if let Screen::Settings { elo_k, feedback, .. } = &mut self.screen {
+            let next = ELO_K_CHOICES
+                .iter()
+                .copied()
+                .find(|&k| k > *elo_k)
+                .unwrap_or(ELO_K_CHOICES[0]);
+            let changed = Storage::open().and_then(|storage| storage.set_elo_k_factor(next));
+            match changed {
+                Ok(()) => {
+                    *elo_k = next;
+                    *feedback = format!("Elo K {} (ratings recomputed)", next);
+                }
+                Err(e) => {
+                    *feedback = format!("Couldn't change Elo K: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Settings: switch to the next color theme (for this session)
+    pub fn settings_next_theme(&mut self) {
+        if let Screen::Settings { feedback, .. } = &mut self.screen {
+            self.theme = self.theme.next();
+            *feedback = format!("Theme {}", self.theme.label());
+        }
+    }
+
+    /// Settings: open the prompt for adding or removing a house word
+    pub fn settings_start_house_word(&mut self) {
+        if let Screen::Settings { house_word_input, .. } = &mut self.screen {
+            *house_word_input = Some(String::new());
+        }
+    }
+
+    /// Settings: close the house word prompt without changing anything
+    pub fn settings_cancel_house_word(&mut self) {
+        if let Screen::Settings { house_word_input, .. } = &mut self.screen {
+            *house_word_input = None;
+        }
+    }
+
+    /// Settings: type a letter into the house word prompt
+    pub fn settings_house_word_char(&mut self, c: char) {
+        if let Screen::Settings { house_word_input: Some(input), .. } = &mut self.screen {
+            if c.is_ascii_alphabetic() && input.len() < MAX_HOUSE_WORD_LEN {
+                input.push(c.to_ascii_lowercase());
+            }
+        }
+    }
+
+    /// Settings: delete the last letter of the house word prompt
+    pub fn settings_house_word_backspace(&mut self) {
+        if let Screen::Settings { house_word_input: Some(input), .. } = &mut self.screen {
+            input.pop();
+        }
+    }
+
+    /// Settings: add the typed house word, or remove it if it's already one
+    ///
+    /// The list is saved and takes effect immediately, including for games
+    /// this device hosts.
+    pub fn settings_submit_house_word(&mut self) {
+        use crate::game::dictionary;
+        use crate::storage::Storage;
+
+        if let Screen::Settings { house_words, house_word_input, feedback, .. } = &mut self.screen {
+            let Some(input) = house_word_input.take() else {
+                return;
+            };
+            let Some(word) = dictionary::normalize_house_word(&input) else {
+                return;
+            };
+
+            *feedback = if dictionary::remove_house_word(&word) {
+                format!("House word removed: {}", word)
+            } else if dictionary::is_valid_word(&word) {
+                format!("{} is already in the dictionary", word)
+            } else {
+                dictionary::add_house_word(&word);
+                format!("House word added: {}", word)
+            };
+
+            *house_words = dictionary::house_words();
+            if let Ok(storage) = Storage::open() {
+                let _ = storage.set_house_words(house_words);
+            }
+        }
+    }
+
+    /// Load saved house words into the dictionary (call once at startup)
+    pub fn load_house_words() {
+        use crate::storage::Storage;
+        if let Some(words) = Storage::open().ok().and_then(|storage| storage.house_words().ok()) {
+            crate::game::dictionary::set_house_words(&words);
+        }
     }
 
     /// Settings: type a character
     pub fn settings_char(&mut self, c: char) {
         if let Screen::Settings { handle_input, editing, feedback, .. } = &mut self.screen {
-            if *editing && handle_input.len() < 12 {
+            if *editing && handle_input.len() < MAX_HANDLE_LEN {
                 handle_input.push(c);
                 *feedback = String::new();
+                self.handle_edited_at = Some(Instant::now());
             }
         }
     }
@@ -387,6 +1226,48 @@ impl AppCoordinator {
             if *editing {
                 handle_input.pop();
                 *feedback = String::new();
+                self.handle_edited_at = Some(Instant::now());
+            }
+        }
+    }
+
+    /// Save a handle edit once typing has paused for `AUTOSAVE_DEBOUNCE`,
+    /// so a crash mid-edit doesn't lose it
+    ///
+    /// The editor stays open; Enter still commits (and validates) as before.
+    fn autosave_handle(&mut self, now: Instant) {
+        use crate::storage::Storage;
+
+        if !self.take_due_autosave(now) {
+            return;
+        }
+        if let Ok(storage) = Storage::open() {
+            self.save_typed_handle(&storage);
+        }
+    }
+
+    /// Whether a handle edit has gone unsaved past the debounce (clears it
+    /// if so)
+    fn take_due_autosave(&mut self, now: Instant) -> bool {
+        match self.handle_edited_at {
+            Some(at) if now.saturating_duration_since(at) >= AUTOSAVE_DEBOUNCE => {
+                self.handle_edited_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Save the handle being typed in the menu or Settings, if it's valid
+    fn save_typed_handle(&mut self, storage: &crate::storage::Storage) {
+        let (handle, handle_input) = match &mut self.screen {
+            Screen::Menu { handle, handle_input, .. } => (handle, handle_input),
+            Screen::Settings { handle, handle_input, .. } => (handle, handle_input),
+            _ => return,
+        };
+        if let Ok(normalized) = normalize_handle(handle_input) {
+            if normalized != *handle && storage.set_handle(&normalized).is_ok() {
+                *handle = normalized;
             }
         }
     }
@@ -394,17 +1275,16 @@ impl AppCoordinator {
     /// Settings: save the handle
     pub fn settings_save(&mut self) {
         if let Screen::Settings { handle, handle_input, feedback, .. } = &mut self.screen {
-            let trimmed = handle_input.trim().to_string();
-            if trimmed.is_empty() {
-                *feedback = "Handle cannot be empty".to_string();
-                return;
-            }
-            if !trimmed.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
-                *feedback = "Only letters, numbers, and _ allowed".to_string();
-                return;
-            }
+            let trimmed = match normalize_handle(handle_input) {
+                Ok(trimmed) => trimmed,
+                Err(message) => {
+                    *feedback = message.to_string();
+                    return;
+                }
+            };
             *handle = trimmed.clone();
             *handle_input = trimmed.clone();
+            self.handle_edited_at = None;
             Self::persist_handle(&trimmed);
             *feedback = "Saved!".to_string();
         }
@@ -426,6 +1306,98 @@ impl AppCoordinator {
         }
     }
 
+    /// Rankings: order by the next column
+    pub fn rankings_sort_next(&mut self) {
+        if let Screen::Rankings { players, sort, scroll_offset, .. } = &mut self.screen {
+            *sort = sort.next();
+            sort.sort(players);
+            *scroll_offset = 0;
+        }
+    }
+
+    /// Rankings: order by the previous column
+    pub fn rankings_sort_prev(&mut self) {
+        if let Screen::Rankings { players, sort, scroll_offset, .. } = &mut self.screen {
+            *sort = sort.prev();
+            sort.sort(players);
+            *scroll_offset = 0;
+        }
+    }
+
+    /// Rankings: open the match history
+    pub fn go_to_match_history(&mut self) {
+        use crate::storage::Storage;
+
+        let matches = Storage::open()
+            .and_then(|storage| storage.recent_matches(MATCH_HISTORY_LEN))
+            .unwrap_or_default();
+        self.screen = Screen::MatchHistory {
+            matches,
+            selected: 0,
+            current_handle: self.get_current_handle(),
+            feedback: String::new(),
+        };
+    }
+
+    /// Match history: back to the rankings
+    pub fn match_history_back(&mut self) {
+        let handle = self.get_current_handle();
+        self.go_to_rankings(handle);
+    }
+
+    /// Match history navigation (up)
+    pub fn match_history_up(&mut self) {
+        if let Screen::MatchHistory { selected, .. } = &mut self.screen {
+            *selected = selected.saturating_sub(1);
+        }
+    }
+
+    /// Match history navigation (down)
+    pub fn match_history_down(&mut self) {
+        if let Screen::MatchHistory { selected, matches, .. } = &mut self.screen {
+            if *selected < matches.len().saturating_sub(1) {
+                *selected += 1;
+            }
+        }
+    }
+
+    /// Match history: write the selected match's report to a text file that
+    /// can be pasted into a chat
+    pub fn match_history_copy_report(&mut self) {
+        use crate::storage::Storage;
+
+        let Screen::MatchHistory { matches, selected, feedback, .. } = &mut self.screen else {
+            return;
+        };
+        let Some(match_id) = matches.get(*selected).map(|m| m.match_id) else {
+            return;
+        };
+
+        let report = match Storage::open().and_then(|storage| storage.format_match_report(match_id)) {
+            Ok(Some(report)) => report,
+            Ok(None) => {
+                *feedback = "Match not found".to_string();
+                return;
+            }
+            Err(e) => {
+                *feedback = format!("Couldn't build report: {}", e);
+                return;
+            }
+        };
+        let dir = match Storage::data_dir() {
+            Ok(dir) => dir.join("reports"),
+            Err(e) => {
+                *feedback = format!("Couldn't save report: {}", e);
+                return;
+            }
+        };
+        let path = dir.join(format!("match-{}.txt", match_id));
+        *feedback = match std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, report)) {
+            Ok(()) => format!("Report saved to {}", path.display()),
+            Err(e) => format!("Couldn't save report: {}", e),
+        };
+    }
+
     /// Browser navigation (up)
     pub fn browser_up(&mut self) {
         if let Screen::Browser { selected, lobbies: _, .. } = &mut self.screen {
@@ -444,25 +1416,204 @@ impl AppCoordinator {
         }
     }
 
+    /// Browser: start discovery again after it failed
+    pub fn browser_retry(&mut self) {
+        if let Screen::Browser { browser, .. } = &mut self.screen {
+            if matches!(browser.status(), BrowserStatus::Failed(_)) {
+                browser.retry();
+            }
+        }
+    }
+
+    /// Join the lobby at `index`, as if it was highlighted and chosen
+    pub fn browser_click(&mut self, index: usize) {
+        if let Screen::Browser { selected, lobbies, .. } = &mut self.screen {
+            if index < lobbies.len() {
+                *selected = index;
+                self.browser_select();
+            }
+        }
+    }
+
     /// Browser selection (Enter)
     pub fn browser_select(&mut self) {
         let (peer, player_name) = match &self.screen {
             Screen::Browser { lobbies, selected, player_name, .. } => {
-                if lobbies.is_empty() {
-                    return;
+                // Lobbies still resolving an address can't be joined yet
+                match lobbies.get(*selected) {
+                    Some(peer) if peer.is_joinable() => (peer.clone(), player_name.clone()),
+                    _ => return,
                 }
-                (lobbies[*selected].clone(), player_name.clone())
             }
             _ => return,
         };
 
         match JoinedLobby::join(&peer, player_name) {
             Ok(lobby) => {
+                // Catch up on the host's history over the lobby connection
+                if let Some(request) = Self::sync_request() {
+                    let _ = lobby.send_sync(request);
+                }
                 self.screen = Screen::JoinedLobby { lobby, countdown: None };
             }
             Err(e) => {
-                self.screen = Screen::Error { message: e };
+                self.screen = Screen::Error {
+                    message: format!("{}\n{}", e, e.guidance()),
+                };
+            }
+        }
+    }
+
+    /// Host lobby: move the player selection up
+    pub fn host_lobby_up(&mut self) {
+        if let Screen::HostLobby { selected, .. } = &mut self.screen {
+            *selected = selected.saturating_sub(1);
+        }
+    }
+
+    /// Host lobby: move the player selection down
+    pub fn host_lobby_down(&mut self) {
+        if let Screen::HostLobby { lobby, selected, .. } = &mut self.screen {
+            if *selected < lobby.player_count().saturating_sub(1) {
+                *selected += 1;
+            }
+        }
+    }
+
+    /// Host lobby: call off the countdown and return to the lobby
+    pub fn host_cancel_countdown(&mut self) {
+        if let Screen::HostLobby { lobby, countdown, .. } = &mut self.screen {
+            if countdown.is_some() {
+                lobby.cancel_countdown();
+                *countdown = None;
+            }
+        }
+    }
+
+    /// Host lobby: raise or lower the selected player's handicap
+    pub fn host_lobby_adjust_handicap(&mut self, increase: bool) {
+        if let Screen::HostLobby { lobby, selected, countdown } = &mut self.screen {
+            if countdown.is_some() {
+                return;
             }
+            if let Some(name) = lobby.players().get(*selected).map(|p| p.name.clone()) {
+                lobby.adjust_handicap(&name, increase);
+            }
+        }
+    }
+
+    /// Host lobby: switch to the next rack size preset
+    pub fn host_lobby_cycle_rack(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            lobby.cycle_rack_preset();
+        }
+    }
+
+    /// Host lobby: step the minimum word length
+    pub fn host_lobby_cycle_min_word_length(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            lobby.cycle_min_word_length();
+        }
+    }
+
+    /// Host lobby: step the minimum-letters-used rule
+    pub fn host_lobby_cycle_letters_used(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            lobby.cycle_letters_used_rule();
+        }
+    }
+
+    /// Host lobby: step the points a claim that misses outright costs
+    pub fn host_lobby_cycle_reject_penalty(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            lobby.cycle_reject_penalty();
+        }
+    }
+
+    /// Host lobby: step the score that wins the round outright
+    pub fn host_lobby_cycle_target_score(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            lobby.cycle_target_score();
+        }
+    }
+
+    /// Host lobby: cycle how long players who drop are held for them to reconnect
+    pub fn host_lobby_cycle_disconnect_grace(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            lobby.cycle_disconnect_grace();
+        }
+    }
+
+    /// Host lobby: toggle the no-repeats house rule for the rest of the match
+    pub fn host_lobby_toggle_no_repeats(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            let enabled = !lobby.no_repeats();
+            lobby.set_no_repeats(enabled);
+        }
+    }
+
+    /// Host lobby: show or hide the connection diagnostics overlay
+    pub fn host_lobby_toggle_diagnostics(&mut self) {
+        if let Screen::HostLobby { .. } = &self.screen {
+            self.show_diagnostics = !self.show_diagnostics;
+        }
+    }
+
+    /// Host lobby: allow (or stop allowing) a warm-up round with the host alone
+    pub fn host_lobby_toggle_warm_up(&mut self) {
+        use crate::lobby::WARM_UP_MIN_PLAYERS;
+
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            let enabled = lobby.min_players() != WARM_UP_MIN_PLAYERS;
+            lobby.set_warm_up(enabled);
+        }
+    }
+
+    /// Host lobby: switch racks to the next language with a word list
+    pub fn host_lobby_cycle_language(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            if let Ok(dir) = crate::storage::Storage::data_dir() {
+                lobby.cycle_language(&dir);
+            }
+        }
+    }
+
+    /// Host lobby: toggle blank tiles on upcoming racks
+    pub fn host_lobby_toggle_blanks(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            let enabled = !lobby.blanks();
+            lobby.set_blanks(enabled);
+        }
+    }
+
+    /// Host lobby: toggle combo multipliers for quick claims
+    pub fn host_lobby_toggle_combos(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            let enabled = !lobby.combos();
+            lobby.set_combos(enabled);
+        }
+    }
+
+    /// Host lobby: add a bot, or clear them all once the lobby has its fill
+    pub fn host_lobby_cycle_bots(&mut self) {
+        if let Screen::HostLobby { lobby, selected, countdown: None } = &mut self.screen {
+            lobby.cycle_bots();
+            *selected = (*selected).min(lobby.player_count().saturating_sub(1));
+        }
+    }
+
+    /// Host lobby: step how well the bots play
+    pub fn host_lobby_cycle_bot_skill(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            lobby.cycle_bot_skill();
+        }
+    }
+
+    /// Host lobby: toggle whether bots' scores count toward Elo
+    pub fn host_lobby_toggle_bots_rated(&mut self) {
+        if let Screen::HostLobby { lobby, countdown: None, .. } = &mut self.screen {
+            let rated = !lobby.bots_rated();
+            lobby.set_bots_rated(rated);
         }
     }
 
@@ -470,7 +1621,7 @@ impl AppCoordinator {
     pub fn host_start_round(&mut self) {
         if let Screen::HostLobby { lobby, .. } = &mut self.screen {
             if lobby.can_start() {
-                let letters = LetterRack::generate().letters().to_vec();
+                let letters = lobby.generate_letters();
                 lobby.start_round(letters.clone(), DEFAULT_ROUND_DURATION);
 
                 // Transition to playing
@@ -485,12 +1636,30 @@ impl AppCoordinator {
 
     /// Poll for updates (call regularly)
     pub fn poll(&mut self) {
+        self.finish_cache_rebuild();
+        self.autosave_handle(Instant::now());
+
         match &mut self.screen {
             Screen::Browser { browser, lobbies, .. } => {
                 *lobbies = browser.poll();
             }
             Screen::HostLobby { lobby, .. } => {
-                let _events = lobby.poll();
+                for event in lobby.poll() {
+                    match event {
+                        // Ask each newcomer for the history we're missing
+                        LobbyEvent::PlayerJoined(player_name) => {
+                            if let Some(request) = Self::sync_request() {
+                                lobby.send_sync(&player_name, &request);
+                            }
+                        }
+                        LobbyEvent::Sync { player_name, message } => {
+                            if let Some(reply) = Self::sync_reply(message) {
+                                lobby.send_sync(&player_name, &reply);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
             }
             Screen::JoinedLobby { lobby, countdown } => {
                 let events = lobby.poll();
@@ -504,8 +1673,23 @@ impl AppCoordinator {
                         } => {
                             *countdown = Some((count, letters, duration));
                         }
-                        LobbyEvent::RoundStart { letters, duration } => {
-                            transition = Some((letters, duration));
+                        LobbyEvent::CountdownCancelled => {
+                            *countdown = None;
+                        }
+                        LobbyEvent::Sync { message, .. } => {
+                            if let Some(reply) = Self::sync_reply(message) {
+                                let _ = lobby.send_sync(reply);
+                            }
+                        }
+                        LobbyEvent::RoundStart {
+                            letters,
+                            duration,
+                            min_letters_used,
+                            reject_penalty,
+                            language,
+                        } => {
+                            transition =
+                                Some((letters, duration, min_letters_used, reject_penalty, language));
                             break;
                         }
                         LobbyEvent::Disconnected => {
@@ -520,10 +1704,16 @@ impl AppCoordinator {
                             };
                             return;
                         }
+                        LobbyEvent::Kicked { reason } => {
+                            self.screen = Screen::Error {
+                                message: format!("Removed from lobby: {}", reason),
+                            };
+                            return;
+                        }
                         _ => {}
                     }
                 }
-                if let Some((letters, duration)) = transition {
+                if let Some((letters, duration, min_letters_used, reject_penalty, language)) = transition {
                     // Take ownership of the JoinedLobby by replacing the screen
                     let old_screen = std::mem::replace(
                         &mut self.screen,
@@ -537,9 +1727,20 @@ impl AppCoordinator {
                         let player_name = lobby.player_name.clone();
 
                         let mut app = App::new();
+                        app.flavor = self.flavor.clone();
                         app.set_player_name(player_name);
                         app.set_scoreboard(player_names);
+                        app.set_handicaps(lobby.handicaps().clone());
                         app.start_round(letters, duration);
+                        app.min_letters_used = min_letters_used;
+                        app.reject_penalty = reject_penalty;
+                        app.set_language(language);
+                        // Rejoining mid-round: the host sent what's been claimed
+                        app.restore_claims(&lobby.round_claims());
+                        if let Some(text) = lobby.announcement() {
+                            app.on_announcement(text.to_string());
+                        }
+                        app.set_house_words(lobby.house_words());
 
                         self.screen = Screen::Playing {
                             app,
@@ -578,28 +1779,139 @@ impl AppCoordinator {
         };
 
         for event in events {
-            match event {
-                LobbyEvent::ClaimAccepted {
-                    word,
-                    player_name,
-                    points,
-                } => {
-                    app.on_claim_accepted(word, player_name, points);
-                }
-                LobbyEvent::ClaimRejected { word, reason } => {
-                    app.on_claim_rejected(word, Self::map_reject_reason(reason));
-                }
-                LobbyEvent::ScoreUpdate { scores } => {
-                    app.update_scoreboard(scores);
+            Self::handle_multiplayer_event(app, hosted_lobby, joined_lobby, event);
+        }
+
+        // Log the round once the host's arbitrator has closed it, whether
+        // the clock, a target score or a claim ended it
+        if let Some(lobby) = hosted_lobby {
+            if lobby.result_pending() {
+                if let Ok(storage) = crate::storage::Storage::open() {
+                    let _ = Self::record_hosted_match(&storage, lobby);
                 }
-                LobbyEvent::RoundEnd => {
-                    app.force_end_round();
+            }
+        }
+
+        // Once the round is over the host can offer a rematch
+        if hosted_lobby.is_some() && app.is_round_over() && app.rematch.is_none() {
+            app.rematch = Some(Rematch::Available);
+        }
+
+        // Mirror our typing to the host, if we opted in
+        if let Some(lobby) = joined_lobby {
+            let text = if app.input_locked() { "" } else { app.input.as_str() };
+            lobby.preview_input(text, Instant::now());
+        }
+    }
+
+    /// Apply one lobby event to the game in progress
+    fn handle_multiplayer_event(
+        app: &mut App,
+        hosted_lobby: &mut Option<HostedLobby>,
+        joined_lobby: &mut Option<JoinedLobby>,
+        event: LobbyEvent,
+    ) {
+        match event {
+            LobbyEvent::ClaimAccepted {
+                word,
+                player_name,
+                points,
+                combo,
+            } => {
+                app.on_claim_accepted(word, player_name, points, combo);
+            }
+            LobbyEvent::ClaimRejected { word, reason } => {
+                app.on_claim_rejected(word, Self::map_reject_reason(reason));
+            }
+            LobbyEvent::ScoreUpdate { scores } => {
+                app.update_scoreboard(scores);
+            }
+            LobbyEvent::RoundClaims { claims } => {
+                app.restore_claims(&claims);
+            }
+            LobbyEvent::Announcement { text } => {
+                app.on_announcement(text);
+            }
+            LobbyEvent::HouseWords { words } => {
+                app.set_house_words(&words);
+            }
+            LobbyEvent::Handicaps { handicaps } => {
+                app.set_handicaps(handicaps);
+            }
+            LobbyEvent::InputPreview { player_name, text } => {
+                app.set_input_preview(player_name, text);
+            }
+            LobbyEvent::RoundEnd { reason } => {
+                app.end_round(reason);
+            }
+            // The host resends the round once we're back, so all that's
+            // left to do here is keep the player posted
+            LobbyEvent::Reconnecting { attempt } => {
+                app.on_connection_status(format!(
+                    "Connection lost, reconnecting ({}/{})...",
+                    attempt, MAX_RECONNECT_ATTEMPTS
+                ));
+            }
+            LobbyEvent::Reconnected => {
+                app.on_connection_status("Reconnected".to_string());
+            }
+            LobbyEvent::Disconnected => {
+                app.on_connection_status("Connection lost to host".to_string());
+            }
+            LobbyEvent::Kicked { reason } => {
+                app.on_connection_status(format!("Removed from lobby: {}", reason));
+            }
+            // The host counting down to its next round: hold input
+            // until it actually starts
+            LobbyEvent::Countdown { countdown, .. } => {
+                app.begin_countdown(countdown);
+            }
+            LobbyEvent::CountdownCancelled => {
+                app.cancel_countdown();
+            }
+            LobbyEvent::RematchOffered => {
+                app.rematch = Some(Rematch::Offered);
+            }
+            LobbyEvent::RematchDeclined(name) => {
+                app.rematch = Some(Rematch::Declined(name));
+            }
+            LobbyEvent::RoundStart {
+                letters,
+                duration,
+                min_letters_used,
+                reject_penalty,
+                language,
+            } => {
+                app.start_round(letters, duration);
+                app.min_letters_used = min_letters_used;
+                app.reject_penalty = reject_penalty;
+                app.set_language(language);
+            }
+            LobbyEvent::Sync { player_name, message } => {
+                if let Some(reply) = Self::sync_reply(message) {
+                    if let Some(lobby) = hosted_lobby {
+                        lobby.send_sync(&player_name, &reply);
+                    } else if let Some(lobby) = joined_lobby {
+                        let _ = lobby.send_sync(reply);
+                    }
                 }
-                _ => {}
             }
+            _ => {}
         }
     }
 
+    /// Our vector clock, to ask a lobby member for the events we're missing
+    fn sync_request() -> Option<Message> {
+        let storage = crate::storage::Storage::open().ok()?;
+        sync::create_sync_request(&storage).ok()
+    }
+
+    /// Answer a lobby member's sync message from our event log
+    fn sync_reply(message: Message) -> Option<Message> {
+        let storage = crate::storage::Storage::open().ok()?;
+        sync::handle_sync_message(&storage, message).ok().flatten()
+    }
+
     /// Convert network ClaimRejectReason to app MissReason (public for main.rs)
     pub fn map_reject_reason_pub(reason: ClaimRejectReason) -> super::state::MissReason {
         Self::map_reject_reason(reason)
@@ -609,6 +1921,10 @@ impl AppCoordinator {
     fn map_reject_reason(reason: ClaimRejectReason) -> super::state::MissReason {
         match reason {
             ClaimRejectReason::TooShort => super::state::MissReason::TooShort,
+            ClaimRejectReason::TooLong { .. } => super::state::MissReason::TooLong,
+            ClaimRejectReason::TooFewLetters { min } => {
+                super::state::MissReason::TooFewLetters { min: min as usize }
+            }
             ClaimRejectReason::InvalidLetters { .. } => super::state::MissReason::InvalidLetters,
             ClaimRejectReason::NotInDictionary => super::state::MissReason::NotInDictionary,
             ClaimRejectReason::AlreadyClaimed { by } => {
@@ -626,13 +1942,14 @@ mod tests {
     #[test]
     fn test_menu_option_all() {
         let options = MenuOption::all();
-        assert_eq!(options.len(), 6);
+        assert_eq!(options.len(), 7);
         assert_eq!(options[0], MenuOption::StartLobby);
         assert_eq!(options[1], MenuOption::JoinLobby);
         assert_eq!(options[2], MenuOption::SoloPractice);
-        assert_eq!(options[3], MenuOption::Rankings);
-        assert_eq!(options[4], MenuOption::Settings);
-        assert_eq!(options[5], MenuOption::Quit);
+        assert_eq!(options[3], MenuOption::Hotseat);
+        assert_eq!(options[4], MenuOption::Rankings);
+        assert_eq!(options[5], MenuOption::Settings);
+        assert_eq!(options[6], MenuOption::Quit);
     }
 
     #[test]
@@ -640,6 +1957,7 @@ mod tests {
         assert_eq!(MenuOption::StartLobby.label(), "Start Lobby");
         assert_eq!(MenuOption::JoinLobby.label(), "Join Lobby");
         assert_eq!(MenuOption::SoloPractice.label(), "Solo Practice");
+        assert_eq!(MenuOption::Hotseat.label(), "Hotseat (2 Players)");
         assert_eq!(MenuOption::Rankings.label(), "Rankings");
         assert_eq!(MenuOption::Settings.label(), "Settings");
         assert_eq!(MenuOption::Quit.label(), "Quit");
@@ -652,6 +1970,38 @@ mod tests {
         assert!(matches!(app.screen, Screen::Menu { selected: 0, .. }));
     }
 
+    #[test]
+    fn test_status_prompt_lobby_only() {
+        let mut app = AppCoordinator::new();
+        app.start_status();
+        assert!(app.status_draft.is_none(), "No status prompt outside a lobby");
+
+        app.status_draft = Some(String::new());
+        for c in "brb and more text".chars() {
+            app.status_char(c);
+        }
+        assert_eq!(app.status_draft.as_ref().map(|d| d.chars().count()), Some(MAX_STATUS_LEN));
+        app.status_backspace();
+        app.submit_status();
+        assert!(app.status_draft.is_none());
+    }
+
+    #[test]
+    fn test_announcement_prompt_host_only() {
+        let mut app = AppCoordinator::new();
+        app.start_announcement();
+        assert!(app.announcement_draft.is_none());
+
+        // Editing and sending a draft outside a hosted lobby is a no-op
+        app.announcement_draft = Some(String::new());
+        app.announcement_char('h');
+        app.announcement_char('i');
+        app.announcement_backspace();
+        assert_eq!(app.announcement_draft.as_deref(), Some("h"));
+        app.send_announcement();
+        assert!(app.announcement_draft.is_none());
+    }
+
     #[test]
     fn test_menu_navigation_up_down() {
         let mut app = AppCoordinator::new();
@@ -674,34 +2024,40 @@ mod tests {
             assert_eq!(*selected, 2);
         }
 
-        // Go down to Rankings
+        // Go down to Hotseat
         app.menu_down();
         if let Screen::Menu { selected, .. } = &app.screen {
             assert_eq!(*selected, 3);
         }
 
-        // Go down to Settings
+        // Go down to Rankings
         app.menu_down();
         if let Screen::Menu { selected, .. } = &app.screen {
             assert_eq!(*selected, 4);
         }
 
-        // Go down to last (Quit)
+        // Go down to Settings
         app.menu_down();
         if let Screen::Menu { selected, .. } = &app.screen {
             assert_eq!(*selected, 5);
         }
 
+        // Go down to last (Quit)
+        app.menu_down();
+        if let Screen::Menu { selected, .. } = &app.screen {
+            assert_eq!(*selected, 6);
+        }
+
         // Can't go past last
         app.menu_down();
         if let Screen::Menu { selected, .. } = &app.screen {
-            assert_eq!(*selected, 5);
+            assert_eq!(*selected, 6);
         }
 
         // Go back up
         app.menu_up();
         if let Screen::Menu { selected, .. } = &app.screen {
-            assert_eq!(*selected, 4);
+            assert_eq!(*selected, 5);
         }
     }
 
@@ -811,7 +2167,8 @@ mod tests {
     fn test_menu_select_quit() {
         let mut app = AppCoordinator::new();
 
-        // Navigate to Quit (index 5)
+        // Navigate to Quit (index 6)
+        app.menu_down();
         app.menu_down();
         app.menu_down();
         app.menu_down();
@@ -822,6 +2179,37 @@ mod tests {
         assert!(app.should_quit);
     }
 
+    #[test]
+    fn test_start_lobby_previews_rack_first() {
+        let mut app = AppCoordinator::new();
+        app.menu_select();
+
+        let Screen::RackPreview { seed, letters, .. } = &app.screen else {
+            panic!("expected the rack preview");
+        };
+        let first_seed = *seed;
+        assert_eq!(letters, crate::game::RackPreset::Standard.rack_for_seed(first_seed).letters());
+
+        // Rerolling deals the rack for the new seed
+        app.rack_preview_reroll();
+        let Screen::RackPreview { seed, letters, .. } = &app.screen else {
+            panic!("expected the rack preview");
+        };
+        assert_ne!(*seed, first_seed);
+        assert_eq!(letters, crate::game::RackPreset::Standard.rack_for_seed(*seed).letters());
+
+        // Hosting deals exactly the rack on show
+        let shown = letters.clone();
+        app.rack_preview_host();
+        let Screen::HostLobby { lobby, .. } = &mut app.screen else {
+            panic!("expected the host lobby");
+        };
+        assert_eq!(lobby.generate_letters(), shown);
+
+        app.quit_hosting();
+        assert!(matches!(app.screen, Screen::Menu { .. }));
+    }
+
     #[test]
     fn test_menu_select_solo_practice() {
         let mut app = AppCoordinator::new();
@@ -834,6 +2222,36 @@ mod tests {
         assert!(matches!(app.screen, Screen::Playing { .. }));
     }
 
+    #[test]
+    fn test_menu_click_picks_option() {
+        let mut app = AppCoordinator::new();
+        app.menu_click(MenuOption::all().len());
+        assert!(matches!(app.screen, Screen::Menu { selected: 0, .. }));
+
+        // Solo Practice is index 2
+        app.menu_click(2);
+        assert!(matches!(app.screen, Screen::Playing { .. }));
+    }
+
+    #[test]
+    fn test_menu_select_hotseat() {
+        let mut app = AppCoordinator::new();
+        let handle = app.get_current_handle();
+
+        // Navigate to Hotseat (index 3)
+        for _ in 0..3 {
+            app.menu_down();
+        }
+        app.menu_select();
+
+        let Screen::Hotseat { game } = &app.screen else {
+            panic!("expected a hotseat round");
+        };
+        assert_eq!(game.players[0].name, handle);
+        assert_eq!(game.players[1].name, HOTSEAT_GUEST);
+        assert_eq!(game.time_remaining, DEFAULT_ROUND_DURATION);
+    }
+
     #[test]
     fn test_map_reject_reasons() {
         assert_eq!(
@@ -907,8 +2325,8 @@ mod tests {
     fn test_settings_char_input() {
         let mut app = AppCoordinator::new();
 
-        // Navigate to Settings (index 4)
-        for _ in 0..4 {
+        // Navigate to Settings (index 5)
+        for _ in 0..5 {
             app.menu_down();
         }
         app.menu_select();
@@ -929,12 +2347,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_settings_house_word_prompt() {
+        let mut app = AppCoordinator::new();
+        for _ in 0..5 {
+            app.menu_down();
+        }
+        app.menu_select();
+
+        // Typing goes nowhere until the prompt is open
+        app.settings_house_word_char('x');
+        assert!(matches!(app.screen, Screen::Settings { house_word_input: None, .. }));
+
+        app.settings_start_house_word();
+        for c in "Ya1y!".chars() {
+            app.settings_house_word_char(c);
+        }
+        app.settings_house_word_backspace();
+        if let Screen::Settings { house_word_input, .. } = &app.screen {
+            assert_eq!(house_word_input.as_deref(), Some("ya"));
+        }
+
+        app.settings_cancel_house_word();
+        assert!(matches!(app.screen, Screen::Settings { house_word_input: None, .. }));
+    }
+
+    #[test]
+    fn test_settings_next_theme() {
+        let mut app = AppCoordinator::new();
+        // Only Settings switches the theme
+        app.settings_next_theme();
+        assert_eq!(app.theme, ThemeChoice::Classic);
+
+        for _ in 0..5 {
+            app.menu_down();
+        }
+        app.menu_select();
+        app.settings_next_theme();
+        assert_eq!(app.theme, ThemeChoice::HighContrast);
+        if let Screen::Settings { feedback, .. } = &app.screen {
+            assert_eq!(feedback, "Theme High contrast");
+        }
+    }
+
     #[test]
     fn test_settings_backspace() {
         let mut app = AppCoordinator::new();
 
         // Navigate to Settings
-        for _ in 0..4 {
+        for _ in 0..5 {
             app.menu_down();
         }
         app.menu_select();
@@ -957,7 +2418,7 @@ mod tests {
         let mut app = AppCoordinator::new();
 
         // Navigate to Settings
-        for _ in 0..4 {
+        for _ in 0..5 {
             app.menu_down();
         }
         app.menu_select();
@@ -983,7 +2444,7 @@ mod tests {
         let mut app = AppCoordinator::new();
 
         // Navigate to Settings
-        for _ in 0..4 {
+        for _ in 0..5 {
             app.menu_down();
         }
         app.menu_select();
@@ -1005,7 +2466,7 @@ mod tests {
         let mut app = AppCoordinator::new();
 
         // Navigate to Settings
-        for _ in 0..4 {
+        for _ in 0..5 {
             app.menu_down();
         }
         app.menu_select();
@@ -1030,7 +2491,7 @@ mod tests {
         let mut app = AppCoordinator::new();
 
         // Navigate to Settings
-        for _ in 0..4 {
+        for _ in 0..5 {
             app.menu_down();
         }
         app.menu_select();
@@ -1052,8 +2513,8 @@ mod tests {
     fn test_menu_select_settings() {
         let mut app = AppCoordinator::new();
 
-        // Navigate to Settings (index 4)
-        for _ in 0..4 {
+        // Navigate to Settings (index 5)
+        for _ in 0..5 {
             app.menu_down();
         }
         app.menu_select();
@@ -1065,8 +2526,8 @@ mod tests {
     fn test_menu_select_rankings() {
         let mut app = AppCoordinator::new();
 
-        // Navigate to Rankings (index 3)
-        for _ in 0..3 {
+        // Navigate to Rankings (index 4)
+        for _ in 0..4 {
             app.menu_down();
         }
         app.menu_select();
@@ -1074,12 +2535,84 @@ mod tests {
         assert!(matches!(app.screen, Screen::Rankings { .. }));
     }
 
+    #[test]
+    fn test_rankings_empty_state_for_fresh_db() {
+        let storage = crate::storage::Storage::open_in_memory().unwrap();
+        let (players, status) = AppCoordinator::load_rankings(&storage);
+        assert!(players.is_empty());
+        assert_eq!(status, RankingsStatus::Empty);
+    }
+
+    #[test]
+    fn test_solo_match_counts_toward_stats_but_not_elo() {
+        let storage = crate::storage::Storage::open_in_memory().unwrap();
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'B', 'T'], 60);
+        for word in ["CAB", "CAT"] {
+            word.chars().for_each(|c| app.on_char(c));
+            app.on_submit();
+        }
+        app.end_round(RoundEndReason::TimeExpired);
+        assert!(app.score > 0);
+
+        AppCoordinator::record_solo_match(&storage, &app, "Solo").unwrap();
+        AppCoordinator::record_solo_match(&storage, &app, "Solo").unwrap();
+        storage.rebuild_derived_caches().unwrap();
+
+        let stats = storage.get_cached_stats("Solo").unwrap().unwrap();
+        assert_eq!(stats.rounds_played, 2);
+        assert_eq!(stats.total_points, 2 * app.score);
+        assert_eq!(stats.best_score, app.score);
+        assert_eq!(stats.words_claimed, 4);
+        assert_eq!(stats.wins, 0);
+        assert_eq!(stats.elo, 1200.0);
+        assert_eq!(storage.get_cached_leaderboard().unwrap(), vec![("Solo".to_string(), 1200.0)]);
+    }
+
+    #[test]
+    fn test_rankings_rebuilding_for_dirty_cache() {
+        let storage = crate::storage::Storage::open_in_memory().unwrap();
+        let payload = r#"{"match_id":1,"scores":[["Alice",50],["Bob",30]],"host_actor_id":"host1","completed":true}"#;
+        storage.append_event("match_end", payload).unwrap();
+
+        let (_, status) = AppCoordinator::load_rankings(&storage);
+        assert_eq!(status, RankingsStatus::Rebuilding);
+
+        storage.rebuild_derived_caches().unwrap();
+        let (players, status) = AppCoordinator::load_rankings(&storage);
+        assert_eq!(status, RankingsStatus::Ready);
+        assert_eq!(players.len(), 2);
+    }
+
+    #[test]
+    fn e2e_hosted_round_logged_once() {
+        let storage = crate::storage::Storage::open_in_memory().unwrap();
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.set_warm_up(true);
+        lobby.start_round(vec!['C', 'A', 'T', 'S', 'D', 'O', 'G'], 60);
+        lobby.host_claim("cat").unwrap();
+        assert!(!lobby.result_pending(), "Nothing to log while the round runs");
+
+        lobby.end_round(RoundEndReason::TimeExpired);
+        assert!(lobby.result_pending());
+        AppCoordinator::record_hosted_match(&storage, &mut lobby).unwrap();
+        assert!(!lobby.result_pending());
+        AppCoordinator::record_hosted_match(&storage, &mut lobby).unwrap();
+        let _ = lobby.shutdown();
+
+        storage.rebuild_derived_caches().unwrap();
+        let stats = storage.get_cached_stats("Host").unwrap().unwrap();
+        assert_eq!(stats.rounds_played, 1);
+        assert_eq!(stats.total_points, 5);
+        assert_eq!(stats.longest_word, "CAT");
+    }
+
     #[test]
     fn test_rankings_scroll() {
         let mut app = AppCoordinator::new();
 
         // Navigate to Rankings
-        for _ in 0..3 {
+        for _ in 0..4 {
             app.menu_down();
         }
         app.menu_select();
@@ -1094,6 +2627,70 @@ mod tests {
         app.rankings_down();
     }
 
+    fn ranked(
+        handle: &str,
+        elo: f64,
+        wins: u32,
+        total_points: u32,
+        best: u32,
+        longest: &str,
+    ) -> CachedPlayerStats {
+        CachedPlayerStats {
+            handle: handle.to_string(),
+            elo,
+            wins,
+            total_points,
+            best_score: best,
+            longest_word: longest.to_string(),
+            ..stored_stats(0, "")
+        }
+    }
+
+    #[test]
+    fn test_rankings_sort_cycles_and_reorders() {
+        let mut app = AppCoordinator::new();
+        let players = vec![
+            ranked("Alice", 1300.0, 1, 90, 30, "CAT"),
+            ranked("Bob", 1250.0, 4, 60, 45, "DOG"),
+            ranked("Cara", 1200.0, 2, 120, 20, "QUARTZES"),
+        ];
+        app.screen = Screen::Rankings {
+            players,
+            current_handle: "Alice".to_string(),
+            scroll_offset: 2,
+            status: RankingsStatus::Ready,
+            sort: RankingsSort::Elo,
+        };
+
+        let names = |list: &[&str]| -> Vec<String> { list.iter().map(|s| s.to_string()).collect() };
+        let order = |app: &AppCoordinator| -> (RankingsSort, Vec<String>) {
+            match &app.screen {
+                Screen::Rankings { players, sort, .. } => {
+                    (*sort, players.iter().map(|p| p.handle.clone()).collect())
+                }
+                _ => panic!("left the rankings"),
+            }
+        };
+
+        app.rankings_sort_next();
+        assert_eq!(order(&app), (RankingsSort::Wins, names(&["Bob", "Cara", "Alice"])));
+        if let Screen::Rankings { scroll_offset, .. } = &app.screen {
+            assert_eq!(*scroll_offset, 0);
+        }
+        app.rankings_sort_next();
+        assert_eq!(order(&app), (RankingsSort::Points, names(&["Cara", "Alice", "Bob"])));
+        app.rankings_sort_next();
+        assert_eq!(order(&app), (RankingsSort::BestScore, names(&["Bob", "Alice", "Cara"])));
+        app.rankings_sort_next();
+        // CAT and DOG tie on length, so Elo decides
+        assert_eq!(order(&app), (RankingsSort::LongestWord, names(&["Cara", "Alice", "Bob"])));
+        app.rankings_sort_next();
+        assert_eq!(order(&app), (RankingsSort::Elo, names(&["Alice", "Bob", "Cara"])));
+
+        app.rankings_sort_prev();
+        assert_eq!(order(&app).0, RankingsSort::LongestWord);
+    }
+
     #[test]
     fn test_map_reject_reason_round_ended() {
         let result = AppCoordinator::map_reject_reason_pub(ClaimRejectReason::RoundEnded);
@@ -1205,4 +2802,158 @@ mod tests {
             assert_eq!(handle, "HI");
         }
     }
+
+    #[test]
+    fn test_empty_handle_keeps_editing_with_error() {
+        let mut app = AppCoordinator::new();
+        let original = app.get_current_handle();
+
+        app.menu_tab();
+        for _ in 0..20 {
+            app.menu_backspace();
+        }
+
+        // Neither Tab nor Enter commits an empty handle
+        app.menu_tab();
+        app.menu_select();
+        if let Screen::Menu { editing_handle, handle, handle_error, .. } = &app.screen {
+            assert!(*editing_handle);
+            assert_eq!(handle, &original);
+            assert_eq!(handle_error.as_deref(), Some("Handle cannot be empty"));
+        } else {
+            panic!("left the menu");
+        }
+
+        // Typing clears the error
+        app.menu_char('Z');
+        if let Screen::Menu { handle_error, .. } = &app.screen {
+            assert_eq!(*handle_error, None);
+        }
+    }
+
+    #[test]
+    fn test_cancel_handle_edit_restores_previous() {
+        let mut app = AppCoordinator::new();
+        let original = app.get_current_handle();
+
+        app.menu_tab();
+        app.menu_char('Q');
+        app.menu_cancel_handle();
+        if let Screen::Menu { editing_handle, handle, handle_input, .. } = &app.screen {
+            assert!(!*editing_handle);
+            assert_eq!(handle, &original);
+            assert_eq!(handle_input, &original);
+        }
+    }
+
+    #[test]
+    fn test_normalize_handle() {
+        assert_eq!(normalize_handle("  Alice_1 "), Ok("Alice_1".to_string()));
+        assert_eq!(normalize_handle("   "), Err("Handle cannot be empty"));
+        assert_eq!(normalize_handle("a b"), Err("Only letters, numbers, and _ allowed"));
+        assert_eq!(normalize_handle("ABCDEFGHIJKLM"), Err("Handle is too long"));
+    }
+
+    #[test]
+    fn test_handle_autosaved_after_idle() {
+        let storage = crate::storage::Storage::open_in_memory().unwrap();
+        let mut app = AppCoordinator::new();
+        app.menu_tab();
+        for _ in 0..20 {
+            app.menu_backspace();
+        }
+        for c in "Idle_1".chars() {
+            app.menu_char(c);
+        }
+
+        // Still typing: nothing is due yet
+        let typed_at = Instant::now();
+        assert!(!app.take_due_autosave(typed_at));
+
+        // A pause past the debounce saves without Enter
+        assert!(app.take_due_autosave(typed_at + AUTOSAVE_DEBOUNCE + Duration::from_millis(100)));
+        app.save_typed_handle(&storage);
+        assert_eq!(storage.handle().unwrap().as_deref(), Some("Idle_1"));
+        if let Screen::Menu { editing_handle, handle, .. } = &app.screen {
+            assert!(*editing_handle);
+            assert_eq!(handle, "Idle_1");
+        }
+
+        // Saved once; nothing pending until the next keystroke
+        assert!(!app.take_due_autosave(typed_at + AUTOSAVE_DEBOUNCE * 5));
+    }
+
+    #[test]
+    fn test_invalid_handle_not_autosaved() {
+        let storage = crate::storage::Storage::open_in_memory().unwrap();
+        let mut app = AppCoordinator::new();
+        app.menu_tab();
+        for _ in 0..20 {
+            app.menu_backspace();
+        }
+
+        app.save_typed_handle(&storage);
+        assert_eq!(storage.handle().unwrap(), None);
+    }
+
+    fn stored_stats(best_score: u32, longest_word: &str) -> CachedPlayerStats {
+        CachedPlayerStats {
+            handle: "Alice".to_string(),
+            elo: 1200.0,
+            rounds_played: 3,
+            total_points: 90,
+            best_score,
+            longest_word: longest_word.to_string(),
+            words_claimed: 12,
+            wins: 1,
+            total_claim_chars: 60,
+        }
+    }
+
+    #[test]
+    fn test_personal_best_celebrated_above_stored_best() {
+        let mut app = App::new();
+        app.score = 42;
+
+        let celebration = AppCoordinator::personal_best(&app, &stored_stats(30, "quartz"));
+        assert_eq!(celebration.as_deref(), Some("NEW BEST: 42 pts!"));
+    }
+
+    #[test]
+    fn test_personal_best_not_celebrated_below_stored_best() {
+        let mut app = App::new();
+        app.score = 20;
+
+        assert_eq!(AppCoordinator::personal_best(&app, &stored_stats(30, "quartz")), None);
+        // Matching the best isn't beating it
+        app.score = 30;
+        assert_eq!(AppCoordinator::personal_best(&app, &stored_stats(30, "quartz")), None);
+    }
+
+    #[test]
+    fn test_resume_round_restores_solo_game() {
+        let mut app = AppCoordinator::new();
+        app.screen = Screen::ResumePrompt {
+            checkpoint: RoundCheckpoint {
+                letters: vec!['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'],
+                time_remaining: 25,
+                score: 3,
+                claimed_words: vec![("CAT".to_string(), 3)],
+                saved_at: 0,
+            },
+            handle: "Alice".to_string(),
+        };
+
+        app.resume_round();
+
+        if let Screen::Playing { app: game_app, hosted_lobby, joined_lobby, .. } = &app.screen {
+            assert!(hosted_lobby.is_none());
+            assert!(joined_lobby.is_none());
+            assert_eq!(game_app.time_remaining, 25);
+            assert_eq!(game_app.score, 3);
+            assert_eq!(game_app.claimed_words().len(), 1);
+        } else {
+            panic!("Expected Playing screen");
+        }
+    }
 }
@@ -1,8 +1,21 @@
 #![allow(dead_code)]
 //! Application state management
 
-use crate::game::validation::{validate_word, ValidationResult};
-use std::collections::{HashSet, VecDeque};
+use crate::game::arbitrator::{ClaimRecord, COMBO_WINDOW_MS};
+use crate::game::dictionary;
+use crate::game::input::InputMap;
+use crate::game::Language;
+use crate::game::scoring::score_on_rack;
+use crate::game::validation::{
+    bonus_points, letters_available, validate_word, ValidationResult, WordBonus,
+};
+use crate::game::solver;
+use crate::network::RoundEndReason;
+use crate::storage::{RackDifficulty, RoundCheckpoint};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use super::flavor::FlavorText;
 
 /// Default round duration in seconds
 pub const DEFAULT_ROUND_DURATION: u32 = 60;
@@ -25,6 +38,8 @@ pub struct MissedWord {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MissReason {
     TooShort,
+    TooLong,
+    TooFewLetters { min: usize },
     InvalidLetters,
     NotInDictionary,
     AlreadyClaimed { by: String },
@@ -34,6 +49,8 @@ impl MissReason {
     pub fn label(&self) -> &'static str {
         match self {
             MissReason::TooShort => "Too Short",
+            MissReason::TooLong => "Too Long",
+            MissReason::TooFewLetters { .. } => "Too Few Letters",
             MissReason::InvalidLetters => "Invalid Letters",
             MissReason::NotInDictionary => "Not In Dictionary",
             MissReason::AlreadyClaimed { .. } => "Already Claimed",
@@ -44,6 +61,8 @@ impl MissReason {
 /// End-of-round summary statistics
 #[derive(Debug, Clone, Default)]
 pub struct RoundSummary {
+    /// The round's letter rack
+    pub letters: Vec<char>,
     /// Total score for the round
     pub total_score: u32,
     /// Words successfully claimed
@@ -53,6 +72,9 @@ pub struct RoundSummary {
     pub invalid_letters: Vec<String>,
     pub not_in_dictionary: Vec<String>,
     pub already_claimed: Vec<String>,
+    /// Definitions of the claimed words, in claim order (words without a
+    /// loaded definition are left out)
+    pub definitions: Vec<(String, &'static str)>,
 }
 
 /// A claim in the feed (visible to all players)
@@ -61,6 +83,8 @@ pub struct ClaimFeedEntry {
     pub player_name: String,
     pub word: String,
     pub points: u32,
+    /// Pangram and length bonuses included in `points`
+    pub bonus: WordBonus,
 }
 
 /// Player score in multiplayer
@@ -70,6 +94,58 @@ pub struct PlayerScore {
     pub score: u32,
 }
 
+/// Where the end-of-round rematch handshake stands (multiplayer)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rematch {
+    /// The round is over and we're hosting: Enter offers a rematch
+    Available,
+    /// The host offered one and wants our answer
+    Offered,
+    /// We offered or accepted, and are waiting on the others
+    Waiting,
+    /// This player turned the offer down
+    Declined(String),
+}
+
+/// Who came out on top of a scoreboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WinnerResult {
+    /// One player has the top score on their own
+    Single(String),
+    /// Several players share the top score (in scoreboard order)
+    Tie(Vec<String>),
+    /// Nobody on the scoreboard
+    Empty,
+}
+
+impl WinnerResult {
+    /// Banner for the end-of-round screen (`None` with nobody to crown)
+    pub fn banner(&self) -> Option<String> {
+        match self {
+            WinnerResult::Single(name) => Some(format!("👑 {} wins!", name)),
+            WinnerResult::Tie(names) => {
+                let (last, rest) = names.split_last()?;
+                Some(format!("It's a tie: {} & {}.", rest.join(", "), last))
+            }
+            WinnerResult::Empty => None,
+        }
+    }
+}
+
+/// Find the winner of a final scoreboard
+pub fn winner_of(scores: &[PlayerScore]) -> WinnerResult {
+    let Some(top) = scores.iter().map(|p| p.score).max() else {
+        return WinnerResult::Empty;
+    };
+    let mut leaders: Vec<String> =
+        scores.iter().filter(|p| p.score == top).map(|p| p.name.clone()).collect();
+    if leaders.len() == 1 {
+        WinnerResult::Single(leaders.remove(0))
+    } else {
+        WinnerResult::Tie(leaders)
+    }
+}
+
 impl RoundSummary {
     /// Total number of successful claims
     pub fn claim_count(&self) -> usize {
@@ -80,6 +156,27 @@ impl RoundSummary {
     pub fn miss_count(&self) -> usize {
         self.too_short.len() + self.invalid_letters.len() + self.not_in_dictionary.len()
     }
+
+    /// How many times each rack letter was used across the claimed words
+    ///
+    /// One entry per distinct rack letter, in rack order (letters that were
+    /// never used are included with a count of 0).
+    pub fn letter_usage(&self) -> Vec<(char, u32)> {
+        let mut usage: Vec<(char, u32)> = Vec::new();
+        for &letter in &self.letters {
+            if !usage.iter().any(|(c, _)| *c == letter) {
+                usage.push((letter, 0));
+            }
+        }
+        for claimed in &self.claimed_words {
+            for c in claimed.word.chars().flat_map(char::to_uppercase) {
+                if let Some((_, count)) = usage.iter_mut().find(|(l, _)| *l == c) {
+                    *count += 1;
+                }
+            }
+        }
+        usage
+    }
 }
 
 /// Main application state
@@ -98,10 +195,18 @@ pub struct App {
     pub time_remaining: u32,
     /// Whether the round has ended (timer hit 0)
     pub round_ended: bool,
+    /// Seconds until the host starts the next round, while it counts down
+    /// (input stays locked until the round actually starts)
+    pub countdown: Option<u32>,
+    /// Why the round ended (set once it has)
+    pub round_end_reason: Option<RoundEndReason>,
+    /// Personal record beaten this round, shown on the end screen (solo only)
+    pub new_best: Option<String>,
     /// Words claimed this round (by the local player)
     claimed_words: Vec<ClaimedWord>,
-    /// All accepted words seen this round (for idempotent multiplayer event handling)
-    accepted_words: HashSet<String>,
+    /// All accepted words seen this round, with who claimed them (for
+    /// idempotent multiplayer event handling)
+    accepted_words: HashMap<String, String>,
     /// Missed submissions this round
     missed_words: Vec<MissedWord>,
     /// Multiplayer scoreboard (all players)
@@ -112,6 +217,35 @@ pub struct App {
     claim_feed_max: usize,
     /// Local player name (for multiplayer)
     pub player_name: Option<String>,
+    /// Handicap multipliers set by the host, shown on the scoreboard
+    pub handicaps: HashMap<String, f32>,
+    /// Latest notice from the host, shown as a banner
+    pub announcement: Option<String>,
+    /// What players who share their typing currently have in their input
+    /// (host only)
+    pub input_previews: HashMap<String, String>,
+    /// Extra words the host's dictionary accepts (lowercase)
+    house_words: HashSet<String>,
+    /// Maps typed characters onto the dictionary alphabet
+    input_map: InputMap,
+    /// Feedback strings (CLANK, NOPE, ...)
+    pub flavor: FlavorText,
+    /// Fewest rack letters the host accepts in a claim this round (0 for
+    /// no limit)
+    pub min_letters_used: usize,
+    /// Points the host takes off for a claim that misses outright (0 when
+    /// penalty mode is off)
+    pub reject_penalty: u32,
+    /// Current combo multiplier for our claims (0 when no combo is going)
+    combo: u32,
+    /// When our last combo claim was accepted
+    combo_at: Option<Instant>,
+    /// Rematch handshake, once the round is over (multiplayer)
+    pub rematch: Option<Rematch>,
+    /// Points from earlier rounds of this match, per player
+    match_totals: Vec<PlayerScore>,
+    /// Rounds folded into `match_totals`
+    rounds_banked: u32,
 }
 
 impl Default for App {
@@ -124,13 +258,29 @@ impl Default for App {
             score: 0,
             time_remaining: DEFAULT_ROUND_DURATION,
             round_ended: false,
+            countdown: None,
+            round_end_reason: None,
+            new_best: None,
             claimed_words: Vec::new(),
-            accepted_words: HashSet::new(),
+            accepted_words: HashMap::new(),
             missed_words: Vec::new(),
             scoreboard: Vec::new(),
             claim_feed: VecDeque::new(),
             claim_feed_max: 10,
             player_name: None,
+            handicaps: HashMap::new(),
+            announcement: None,
+            input_previews: HashMap::new(),
+            house_words: HashSet::new(),
+            input_map: InputMap::default(),
+            flavor: FlavorText::default(),
+            min_letters_used: 0,
+            reject_penalty: 0,
+            combo: 0,
+            combo_at: None,
+            rematch: None,
+            match_totals: Vec::new(),
+            rounds_banked: 0,
         }
     }
 }
@@ -146,27 +296,55 @@ impl App {
         self.should_quit = true;
     }
 
-    /// Handle character input (locked when round is over)
+    /// Whether typing is ignored: the round is over, or the next one hasn't
+    /// started yet
+    pub fn input_locked(&self) -> bool {
+        self.round_ended || self.countdown.is_some()
+    }
+
+    /// Handle character input (locked outside a running round)
+    ///
+    /// Input is capped at the rack size since no word can use more tiles
+    /// than exist.
     pub fn on_char(&mut self, c: char) {
-        if self.round_ended {
+        if self.input_locked() {
+            return;
+        }
+        if self.input.chars().count() >= self.max_input_len() {
             return;
         }
         self.input.push(c);
         self.feedback.clear();
     }
 
-    /// Handle backspace (locked when round is over)
+    /// Handle a raw typed character
+    ///
+    /// The character goes through the input map first, so layout-specific
+    /// letters (e.g. accented ones) become dictionary letters and anything
+    /// unplayable is ignored.
+    pub fn on_key_char(&mut self, c: char) {
+        if let Some(letter) = self.input_map.normalize(c) {
+            self.on_char(letter);
+        }
+    }
+
+    /// Maximum number of characters the input can hold (the rack size)
+    pub fn max_input_len(&self) -> usize {
+        self.letters.len()
+    }
+
+    /// Handle backspace (locked outside a running round)
     pub fn on_backspace(&mut self) {
-        if self.round_ended {
+        if self.input_locked() {
             return;
         }
         self.input.pop();
         self.feedback.clear();
     }
 
-    /// Handle word submission (Enter key, locked when round is over)
+    /// Handle word submission (Enter key, locked outside a running round)
     pub fn on_submit(&mut self) {
-        if self.round_ended {
+        if self.input_locked() {
             return;
         }
         if self.input.is_empty() {
@@ -191,10 +369,14 @@ impl App {
 
         match result {
             ValidationResult::Valid => {
-                let points = word.len() as u32;
+                let bonus = WordBonus::for_word(&word_upper, &self.letters);
+                let points = score_on_rack(&word_upper, &self.letters) + bonus.total();
                 self.score += points;
-                self.feedback = format!("OK +{} ({})", points, word_upper);
-                self.accepted_words.insert(word_upper.clone());
+                self.feedback = match bonus.label() {
+                    Some(label) => format!("OK +{} ({}) {}", points, word_upper, label),
+                    None => format!("OK +{} ({})", points, word_upper),
+                };
+                self.accepted_words.insert(word_upper.clone(), "you".to_string());
                 self.claimed_words.push(ClaimedWord {
                     word: word_upper,
                     points,
@@ -207,15 +389,22 @@ impl App {
                     reason: MissReason::TooShort,
                 });
             }
+            ValidationResult::TooFewLetters { min, .. } => {
+                self.feedback = result.message();
+                self.missed_words.push(MissedWord {
+                    word: word_upper,
+                    reason: MissReason::TooFewLetters { min },
+                });
+            }
             ValidationResult::InvalidLetters { .. } => {
-                self.feedback = "CLANK".to_string();
+                self.feedback = self.flavor.invalid_letters.clone();
                 self.missed_words.push(MissedWord {
                     word: word_upper,
                     reason: MissReason::InvalidLetters,
                 });
             }
             ValidationResult::NotInDictionary => {
-                self.feedback = "NOPE".to_string();
+                self.feedback = self.flavor.not_in_dictionary.clone();
                 self.missed_words.push(MissedWord {
                     word: word_upper,
                     reason: MissReason::NotInDictionary,
@@ -233,10 +422,14 @@ impl App {
 
     /// Update the timer and trigger end-of-round when it hits zero
     pub fn tick(&mut self) {
+        self.expire_combo(Instant::now());
+        if self.countdown.is_some() {
+            return;
+        }
         if self.time_remaining > 0 {
             self.time_remaining -= 1;
             if self.time_remaining == 0 {
-                self.end_round();
+                self.end_round(RoundEndReason::TimeExpired);
             }
         }
     }
@@ -247,34 +440,112 @@ impl App {
     }
 
     /// End the current round (locks input, triggers results)
-    fn end_round(&mut self) {
+    ///
+    /// Only the first reason sticks: a late RoundEnd from the host doesn't
+    /// overwrite a timer that already ran out locally.
+    pub fn end_round(&mut self, reason: RoundEndReason) {
+        if self.round_ended {
+            return;
+        }
         self.round_ended = true;
-        self.feedback = "TIME'S UP!".to_string();
+        self.round_end_reason = Some(reason);
+        self.feedback = self.flavor.headline(reason).to_string();
     }
 
-    /// Force end the round (called when host signals RoundEnd)
+    /// Force end the round (called when the host ends it early)
     pub fn force_end_round(&mut self) {
-        self.end_round();
+        self.end_round(RoundEndReason::HostEnded);
+    }
+
+    /// The host is counting down to the next round
+    ///
+    /// Input is locked and cleared until `start_round`, so nothing typed
+    /// during the countdown is sent as a claim.
+    pub fn begin_countdown(&mut self, seconds: u32) {
+        self.countdown = Some(seconds);
+        self.input.clear();
+    }
+
+    /// The host called off the countdown
+    pub fn cancel_countdown(&mut self) {
+        self.countdown = None;
     }
 
     /// Start a new round with given letters and duration
+    ///
+    /// Starting after a finished multiplayer round (a rematch) adds its
+    /// scores to the match totals first.
     pub fn start_round(&mut self, letters: Vec<char>, duration: u32) {
+        if self.round_ended && !self.scoreboard.is_empty() {
+            self.bank_round();
+        }
+        self.rematch = None;
+        self.countdown = None;
         self.letters = letters;
         self.time_remaining = duration;
         self.score = 0;
         self.input.clear();
         self.feedback.clear();
         self.round_ended = false;
+        self.round_end_reason = None;
         self.claimed_words.clear();
         self.accepted_words.clear();
         self.missed_words.clear();
         self.claim_feed.clear();
+        self.input_previews.clear();
+        self.combo = 0;
+        self.combo_at = None;
         // Reset scoreboard scores but keep players
         for player in &mut self.scoreboard {
             player.score = 0;
         }
     }
 
+    /// Add the finished round's scores to the match totals
+    fn bank_round(&mut self) {
+        for player in &self.scoreboard {
+            match self.match_totals.iter_mut().find(|p| p.name == player.name) {
+                Some(total) => total.score += player.score,
+                None => self.match_totals.push(player.clone()),
+            }
+        }
+        self.rounds_banked += 1;
+    }
+
+    /// Rounds played in this match before the current one
+    pub fn rounds_banked(&self) -> u32 {
+        self.rounds_banked
+    }
+
+    /// Points across the whole match, this round included, best first
+    pub fn match_standings(&self) -> Vec<PlayerScore> {
+        let mut standings = self.match_totals.clone();
+        for player in &self.scoreboard {
+            match standings.iter_mut().find(|p| p.name == player.name) {
+                Some(total) => total.score += player.score,
+                None => standings.push(player.clone()),
+            }
+        }
+        standings.sort_by(|a, b| b.score.cmp(&a.score));
+        standings
+    }
+
+    /// Instructions for the end-of-round screen
+    pub fn end_of_round_hint(&self) -> String {
+        if let Some(count) = self.countdown {
+            return format!("Rematch starting in {}...", count);
+        }
+        match &self.rematch {
+            None => "Press ESC to return to menu".to_string(),
+            Some(Rematch::Available) => "Enter Rematch  Esc Menu".to_string(),
+            Some(Rematch::Offered) => "Host offers a rematch: Enter Accept  Esc Leave".to_string(),
+            Some(Rematch::Waiting) => "Waiting for everyone to accept the rematch...".to_string(),
+            Some(Rematch::Declined(name)) => {
+                format!("{} declined the rematch: Enter Offer again  Esc Menu", name)
+            }
+        }
+    }
+
     /// Set the local player name (for multiplayer)
     pub fn set_player_name(&mut self, name: String) {
         self.player_name = Some(name);
@@ -288,6 +559,47 @@ impl App {
             .collect();
     }
 
+    /// Set the handicap multipliers the host scores with, to show on the
+    /// scoreboard and apply to our point previews
+    pub fn set_handicaps(&mut self, handicaps: HashMap<String, f32>) {
+        self.handicaps = handicaps;
+    }
+
+    /// Play in the host's rack language, keeping letters it has of its own
+    /// (Spanish Ñ) instead of folding them
+    pub fn set_language(&mut self, language: Language) {
+        self.input_map = InputMap::for_language(language);
+    }
+
+    /// Set the extra words the host's dictionary accepts
+    pub fn set_house_words(&mut self, words: &[String]) {
+        self.house_words = words.iter().map(|w| w.to_lowercase()).collect();
+    }
+
+    /// Whether a word only counts because it's one of the host's house words
+    pub fn is_house_word(&self, word: &str) -> bool {
+        self.house_words.contains(&word.to_lowercase())
+    }
+
+    /// Show a notice from the host (replaces any earlier one)
+    pub fn on_announcement(&mut self, text: String) {
+        self.announcement = Some(text);
+    }
+
+    /// Tell the player how the connection to the host is doing
+    pub fn on_connection_status(&mut self, status: String) {
+        self.feedback = status;
+    }
+
+    /// Show (or clear, when empty) what a player is typing
+    pub fn set_input_preview(&mut self, player_name: String, text: String) {
+        if text.is_empty() {
+            self.input_previews.remove(&player_name);
+        } else {
+            self.input_previews.insert(player_name, text);
+        }
+    }
+
     /// Update scoreboard from score update message
     pub fn update_scoreboard(&mut self, scores: Vec<(String, u32)>) {
         for (name, score) in scores {
@@ -302,19 +614,29 @@ impl App {
     }
 
     /// Handle a claim accepted from the host (multiplayer)
-    pub fn on_claim_accepted(&mut self, word: String, player_name: String, points: u32) {
+    ///
+    /// `combo` is the multiplier the host applied to `points` (1 for none).
+    pub fn on_claim_accepted(
+        &mut self,
+        word: String,
+        player_name: String,
+        points: u32,
+        combo: u32,
+    ) {
         let word_upper = word.to_uppercase();
 
         // Network delivery may duplicate messages. Ignore repeated accepted claims for a word.
-        if !self.accepted_words.insert(word_upper.clone()) {
+        if self.accepted_words.contains_key(&word_upper) {
             return;
         }
+        self.accepted_words.insert(word_upper.clone(), player_name.clone());
 
         // Add to claim feed
         self.claim_feed.push_back(ClaimFeedEntry {
             player_name: player_name.clone(),
             word: word_upper.clone(),
             points,
+            bonus: WordBonus::for_word(&word_upper, &self.letters),
         });
         // Trim feed if too long (O(1) with VecDeque)
         while self.claim_feed.len() > self.claim_feed_max {
@@ -325,6 +647,7 @@ impl App {
         if self.player_name.as_ref() == Some(&player_name) {
             self.score += points;
             self.feedback = format!("OK +{} ({})", points, word_upper.clone());
+            self.track_combo(combo, Instant::now());
             self.claimed_words.push(ClaimedWord {
                 word: word_upper,
                 points,
@@ -339,24 +662,122 @@ impl App {
         self.scoreboard.sort_by(|a, b| b.score.cmp(&a.score));
     }
 
+    /// Catch up on the claims made this round before we (re)joined it
+    ///
+    /// Fills in the words already taken, the claim feed, and our own words
+    /// and score, without the feedback and combos of a live claim. The
+    /// scoreboard follows from the host's next score update.
+    pub fn restore_claims(&mut self, claims: &[ClaimRecord]) {
+        for claim in claims {
+            if self.accepted_words.contains_key(&claim.word) {
+                continue;
+            }
+            self.accepted_words.insert(claim.word.clone(), claim.player_name.clone());
+            self.claim_feed.push_back(ClaimFeedEntry {
+                player_name: claim.player_name.clone(),
+                word: claim.word.clone(),
+                points: claim.points,
+                bonus: WordBonus::for_word(&claim.word, &self.letters),
+            });
+            if self.player_name.as_ref() == Some(&claim.player_name) {
+                self.score += claim.points;
+                self.claimed_words.push(ClaimedWord {
+                    word: claim.word.clone(),
+                    points: claim.points,
+                });
+            }
+        }
+        while self.claim_feed.len() > self.claim_feed_max {
+            self.claim_feed.pop_front();
+        }
+    }
+
+    /// Who claimed `word` this round, if anyone has
+    pub fn claimed_by(&self, word: &str) -> Option<&str> {
+        self.accepted_words.get(&word.to_uppercase()).map(String::as_str)
+    }
+
+    /// Every word accepted this round, by anyone (sorted)
+    pub fn round_claimed_words(&self) -> Vec<String> {
+        let mut words: Vec<String> = self.accepted_words.keys().cloned().collect();
+        words.sort();
+        words
+    }
+
+    /// Current combo multiplier (0 when no combo is going)
+    pub fn combo(&self) -> u32 {
+        self.combo
+    }
+
+    /// Show the combo multiplier the host applied to our latest claim
+    fn track_combo(&mut self, multiplier: u32, now: Instant) {
+        if multiplier >= 2 {
+            self.combo = multiplier;
+            self.combo_at = Some(now);
+        } else {
+            self.combo = 0;
+            self.combo_at = None;
+        }
+    }
+
+    /// Drop the combo badge once the window for extending it has passed
+    fn expire_combo(&mut self, now: Instant) {
+        if let Some(at) = self.combo_at {
+            if now.duration_since(at) > Duration::from_millis(COMBO_WINDOW_MS) {
+                self.combo = 0;
+                self.combo_at = None;
+            }
+        }
+    }
+
     /// Handle a claim rejected from the host (multiplayer)
     pub fn on_claim_rejected(&mut self, word: String, reason: MissReason) {
         let word_upper = word.to_uppercase();
         self.feedback = match &reason {
             MissReason::TooShort => "Too short".to_string(),
-            MissReason::InvalidLetters => "CLANK".to_string(),
-            MissReason::NotInDictionary => "NOPE".to_string(),
+            MissReason::TooLong => "Too long".to_string(),
+            MissReason::TooFewLetters { min } => format!("Use {}+ letters", min),
+            MissReason::InvalidLetters => self.flavor.invalid_letters.clone(),
+            MissReason::NotInDictionary => self.flavor.not_in_dictionary.clone(),
             MissReason::AlreadyClaimed { by } => format!("TOO LATE (already claimed by {})", by),
         };
+        // The host docks the same points; our copy of the score follows suit
+        if matches!(reason, MissReason::InvalidLetters | MissReason::NotInDictionary) {
+            self.score = self.score.saturating_sub(self.reject_penalty);
+        }
         self.missed_words.push(MissedWord {
             word: word_upper,
             reason,
         });
     }
 
+    /// Points the current input would score if claimed
+    ///
+    /// Returns `None` while the input is empty or can't be spelled from the
+    /// rack. Dictionary membership isn't checked: this is a preview, not a
+    /// verdict.
+    pub fn pending_points(&self) -> Option<u32> {
+        if self.input.is_empty() || !letters_available(&self.input, &self.letters) {
+            return None;
+        }
+        if self.input.chars().count() < self.min_letters_used {
+            return None;
+        }
+        let raw =
+            score_on_rack(&self.input, &self.letters) + bonus_points(&self.input, &self.letters);
+        // Scaled the way the host will score it
+        let handicap = self
+            .player_name
+            .as_ref()
+            .and_then(|name| self.handicaps.get(name))
+            .copied()
+            .unwrap_or(1.0);
+        Some((raw as f32 * handicap).round() as u32)
+    }
+
     /// Get current input for sending to host (multiplayer)
     pub fn get_pending_claim(&self) -> Option<String> {
-        if self.input.is_empty() || self.round_ended {
+        if self.input.is_empty() || self.input_locked() {
             None
         } else {
             Some(self.input.clone())
@@ -383,18 +804,109 @@ impl App {
         &self.missed_words
     }
 
+    /// Snapshot the in-progress round so it can be resumed after a crash
+    pub fn checkpoint(&self) -> RoundCheckpoint {
+        let saved_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+
+        RoundCheckpoint {
+            letters: self.letters.clone(),
+            time_remaining: self.time_remaining,
+            score: self.score,
+            claimed_words: self
+                .claimed_words
+                .iter()
+                .map(|cw| (cw.word.clone(), cw.points))
+                .collect(),
+            saved_at,
+        }
+    }
+
+    /// How many valid words nobody has claimed yet this round
+    ///
+    /// The rack is solved once and cached by the solver, so asking again
+    /// after each claim only checks that list against the claimed words.
+    pub fn remaining_valid_count(&self) -> usize {
+        solver::solve_rack(&self.letters)
+            .words
+            .iter()
+            .filter(|word| word.chars().count() >= self.min_letters_used)
+            .filter(|word| !self.accepted_words.contains_key(&word.to_uppercase()))
+            .count()
+    }
+
+    /// Show a hint: how many valid words are left, without naming any
+    ///
+    /// `remaining` comes from whoever arbitrates the round (the host's
+    /// arbitrator, or `remaining_valid_count` when playing solo).
+    pub fn show_hint(&mut self, remaining: usize) {
+        if self.input_locked() {
+            return;
+        }
+        self.feedback = match remaining {
+            0 => "HINT: no words left".to_string(),
+            1 => "HINT: 1 word left".to_string(),
+            n => format!("HINT: {} words left", n),
+        };
+    }
+
+    /// Measure how much of the rack's potential the player got this round
+    pub fn rack_difficulty(&self) -> RackDifficulty {
+        let recorded_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        let solution = solver::solve_rack(&self.letters);
+
+        RackDifficulty {
+            letters: self.letters.clone(),
+            valid_words: solution.words.len() as u32,
+            max_points: solution.max_points,
+            achieved_points: self.score,
+            recorded_at,
+        }
+    }
+
+    /// Rebuild a solo round from a saved checkpoint
+    pub fn from_checkpoint(checkpoint: &RoundCheckpoint) -> Self {
+        let mut app = Self::new();
+        app.start_round(checkpoint.letters.clone(), checkpoint.time_remaining);
+        app.score = checkpoint.score;
+        for (word, points) in &checkpoint.claimed_words {
+            app.accepted_words.insert(word.clone(), "you".to_string());
+            app.claimed_words.push(ClaimedWord {
+                word: word.clone(),
+                points: *points,
+            });
+        }
+        app
+    }
+
     /// Generate end-of-round summary with categorized misses
     pub fn round_summary(&self) -> RoundSummary {
         let mut summary = RoundSummary {
+            letters: self.letters.clone(),
             total_score: self.score,
             claimed_words: self.claimed_words.clone(),
+            definitions: self
+                .claimed_words
+                .iter()
+                .filter_map(|c| Some((c.word.clone(), dictionary::lookup_definition(&c.word)?)))
+                .collect(),
             ..Default::default()
         };
 
         for miss in &self.missed_words {
             match &miss.reason {
-                MissReason::TooShort => summary.too_short.push(miss.word.clone()),
-                MissReason::InvalidLetters => summary.invalid_letters.push(miss.word.clone()),
+                MissReason::TooShort | MissReason::TooFewLetters { .. } => {
+                    summary.too_short.push(miss.word.clone())
+                }
+                // Overlong words can't be spelled from the rack either
+                MissReason::InvalidLetters | MissReason::TooLong => {
+                    summary.invalid_letters.push(miss.word.clone())
+                }
                 MissReason::NotInDictionary => summary.not_in_dictionary.push(miss.word.clone()),
                 MissReason::AlreadyClaimed { .. } => summary.already_claimed.push(miss.word.clone()),
             }
@@ -437,6 +949,29 @@ mod tests {
         assert!(app.is_round_over());
     }
 
+    #[test]
+    fn test_input_locked_during_countdown_until_round_starts() {
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'T'], 5);
+        app.on_char('C');
+        app.begin_countdown(3);
+        assert!(app.input.is_empty(), "half-typed word is dropped");
+
+        app.on_char('C');
+        app.on_char('A');
+        app.on_char('T');
+        assert!(app.input.is_empty());
+        assert_eq!(app.get_pending_claim(), None);
+        app.tick();
+        assert_eq!(app.time_remaining, 5, "round timer waits for the start");
+
+        app.start_round(vec!['D', 'O', 'G'], 60);
+        app.on_char('D');
+        app.on_char('O');
+        app.on_char('G');
+        assert_eq!(app.get_pending_claim(), Some("DOG".to_string()));
+    }
+
     #[test]
     fn test_timer_triggers_end_of_round() {
         let mut app = App::new();
@@ -446,6 +981,7 @@ mod tests {
         app.tick();
         assert!(app.round_ended);
         assert_eq!(app.feedback, "TIME'S UP!");
+        assert_eq!(app.round_end_reason, Some(RoundEndReason::TimeExpired));
     }
 
     #[test]
@@ -544,7 +1080,7 @@ mod tests {
 
         assert_eq!(app.claimed_words().len(), 1);
         assert_eq!(app.claimed_words()[0].word, "CAT");
-        assert_eq!(app.claimed_words()[0].points, 3);
+        assert_eq!(app.claimed_words()[0].points, 5);
 
         // Submit "CAB"
         app.on_char('C');
@@ -554,7 +1090,7 @@ mod tests {
 
         assert_eq!(app.claimed_words().len(), 2);
         assert_eq!(app.claimed_words()[1].word, "CAB");
-        assert_eq!(app.claimed_words()[1].points, 3);
+        assert_eq!(app.claimed_words()[1].points, 8);
     }
 
     #[test]
@@ -581,7 +1117,7 @@ mod tests {
         app.on_submit();
 
         assert_eq!(app.longest_claimed_word().unwrap().word, "CATS");
-        assert_eq!(app.longest_claimed_word().unwrap().points, 4);
+        assert_eq!(app.longest_claimed_word().unwrap().points, 6);
 
         // Submit "DOG" (3 letters) - shouldn't change longest
         app.on_char('D');
@@ -619,6 +1155,77 @@ mod tests {
         assert_eq!(summary.not_in_dictionary[0], "CAG");
     }
 
+    #[test]
+    fn test_round_summary_letter_usage() {
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'T', 'S', 'E', 'A', 'Z'], 60);
+
+        for word in ["CAT", "SEAT", "CATS"] {
+            for c in word.chars() {
+                app.on_char(c);
+            }
+            app.on_submit();
+        }
+
+        let usage = app.round_summary().letter_usage();
+        assert_eq!(
+            usage,
+            vec![('C', 2), ('A', 3), ('T', 3), ('S', 2), ('E', 1), ('Z', 0)]
+        );
+        let total: u32 = usage.iter().map(|(_, n)| n).sum();
+        assert_eq!(total, 11); // 3 + 4 + 4 letters
+    }
+
+    #[test]
+    fn test_restore_claims_after_rejoin() {
+        let record = |player: &str, word: &str, points: u32, seq: u64| ClaimRecord {
+            player_name: player.to_string(),
+            word: word.to_string(),
+            points,
+            claim_sequence: seq,
+        };
+        let mut app = App::new();
+        app.set_player_name("Flaky".to_string());
+        app.set_scoreboard(vec!["Host".to_string(), "Flaky".to_string()]);
+        app.start_round(vec!['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'], 42);
+
+        let claims = [record("Flaky", "CAT", 3, 1), record("Host", "DOGS", 4, 2)];
+        app.restore_claims(&claims);
+        app.restore_claims(&claims);
+
+        assert_eq!(app.round_claimed_words(), vec!["CAT".to_string(), "DOGS".to_string()]);
+        assert_eq!(app.claimed_by("dogs"), Some("Host"));
+        assert_eq!(app.score, 3);
+        assert_eq!(app.claimed_words().len(), 1);
+        assert_eq!(app.claim_feed.len(), 2);
+        assert!(app.feedback.is_empty());
+
+        // A late echo of a restored claim isn't counted twice
+        app.on_claim_accepted("CAT".to_string(), "Flaky".to_string(), 3, 1);
+        assert_eq!(app.score, 3);
+    }
+
+    #[test]
+    fn test_winner_of_final_scoreboard() {
+        let board = |scores: &[(&str, u32)]| -> Vec<PlayerScore> {
+            scores
+                .iter()
+                .map(|(name, score)| PlayerScore { name: name.to_string(), score: *score })
+                .collect()
+        };
+
+        let clear = winner_of(&board(&[("Bob", 7), ("Alice", 12), ("Carol", 3)]));
+        assert_eq!(clear, WinnerResult::Single("Alice".to_string()));
+        assert_eq!(clear.banner().as_deref(), Some("👑 Alice wins!"));
+
+        let tie = winner_of(&board(&[("Alice", 9), ("Carol", 2), ("Bob", 9)]));
+        assert_eq!(tie, WinnerResult::Tie(vec!["Alice".to_string(), "Bob".to_string()]));
+        assert_eq!(tie.banner().as_deref(), Some("It's a tie: Alice & Bob."));
+
+        assert_eq!(winner_of(&[]), WinnerResult::Empty);
+        assert_eq!(WinnerResult::Empty.banner(), None);
+    }
+
     #[test]
     fn test_round_summary_totals() {
         let mut app = App::new();
@@ -642,11 +1249,34 @@ mod tests {
         app.on_submit();
 
         let summary = app.round_summary();
-        assert_eq!(summary.total_score, 6); // CAT(3) + DOG(3)
+        assert_eq!(summary.total_score, 11); // CAT(5) + DOG(6)
         assert_eq!(summary.claim_count(), 2);
         assert_eq!(summary.miss_count(), 1);
     }
 
+    #[test]
+    fn test_round_summary_includes_definitions() {
+        let dir = std::env::temp_dir().join(format!("blam-summary-defs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(dictionary::DEFINITIONS_FILE), "cab\ta taxi\n").unwrap();
+        dictionary::load_definitions(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'T', 'B', 'S'], 60);
+        for word in ["cab", "tab"] {
+            for c in word.chars() {
+                app.on_char(c);
+            }
+            app.on_submit();
+        }
+
+        let summary = app.round_summary();
+        assert_eq!(summary.claim_count(), 2);
+        // TAB has no definition, so it's left out
+        assert_eq!(summary.definitions, vec![("CAB".to_string(), "a taxi")]);
+    }
+
     #[test]
     fn test_start_round_clears_tracking() {
         let mut app = App::new();
@@ -674,27 +1304,27 @@ mod tests {
     }
 
     #[test]
-    fn test_points_per_letter() {
+    fn test_points_per_letter_value() {
         let mut app = App::new();
         app.start_round(
             vec!['C', 'A', 'T', 'S', 'E', 'R', 'A', 'T', 'E', 'D', 'O', 'G'],
             60,
         );
 
-        // 3-letter word: 3 points
+        // C (3) + A (1) + T (1): 5 points
         app.on_char('C');
         app.on_char('A');
         app.on_char('T');
         app.on_submit();
-        assert_eq!(app.score, 3);
+        assert_eq!(app.score, 5);
 
-        // 4-letter word: 4 points (total: 7)
+        // Adding an S (1): 6 points (total: 11)
         app.on_char('C');
         app.on_char('A');
         app.on_char('T');
         app.on_char('S');
         app.on_submit();
-        assert_eq!(app.score, 7);
+        assert_eq!(app.score, 11);
     }
 
     #[test]
@@ -712,7 +1342,7 @@ mod tests {
         app.on_char('A');
         app.on_char('T');
         app.on_submit();
-        assert_eq!(app.feedback, "OK +3 (CAT)");
+        assert_eq!(app.feedback, "OK +5 (CAT)");
     }
 
     #[test]
@@ -737,6 +1367,26 @@ mod tests {
         assert_eq!(app.feedback, "CLANK");
     }
 
+    #[test]
+    fn test_custom_flavor_text_used_for_feedback() {
+        let mut app = App::new();
+        app.flavor = FlavorText::parse("not_in_dictionary = NEIN\ntime_up = ZEIT!");
+        app.start_round(vec!['C', 'A', 'G', 'D', 'O', 'T', 'E', 'R', 'S', 'T', 'A', 'N'], 1);
+        app.on_char('C');
+        app.on_char('A');
+        app.on_char('G');
+        app.on_submit();
+        assert_eq!(app.feedback, "NEIN");
+
+        app.on_claim_rejected("XYZ".to_string(), MissReason::NotInDictionary);
+        assert_eq!(app.feedback, "NEIN");
+        app.on_claim_rejected("QQQ".to_string(), MissReason::InvalidLetters);
+        assert_eq!(app.feedback, "CLANK");
+
+        app.tick();
+        assert_eq!(app.feedback, "ZEIT!");
+    }
+
     #[test]
     fn test_claim_feedback_too_late() {
         let mut app = App::new();
@@ -784,6 +1434,33 @@ mod tests {
         assert_eq!(app.scoreboard[1].score, 5);
     }
 
+    #[test]
+    fn test_match_totals_carry_across_rematches() {
+        let mut app = App::new();
+        app.set_scoreboard(vec!["Alice".into(), "Bob".into()]);
+        app.start_round(vec!['C', 'A', 'T'], 60);
+        app.update_scoreboard(vec![("Alice".into(), 10), ("Bob".into(), 4)]);
+        app.end_round(RoundEndReason::TimeExpired);
+        assert_eq!(app.rounds_banked(), 0);
+
+        app.rematch = Some(Rematch::Waiting);
+        app.begin_countdown(3);
+        assert_eq!(app.end_of_round_hint(), "Rematch starting in 3...");
+        app.start_round(vec!['D', 'O', 'G'], 60);
+        assert_eq!(app.rounds_banked(), 1);
+        assert_eq!(app.rematch, None);
+        assert!(app.scoreboard.iter().all(|p| p.score == 0));
+
+        app.update_scoreboard(vec![("Bob".into(), 9), ("Alice".into(), 2)]);
+        let standings = app.match_standings();
+        assert_eq!(standings[0], PlayerScore { name: "Bob".into(), score: 13 });
+        assert_eq!(standings[1], PlayerScore { name: "Alice".into(), score: 12 });
+
+        // A round that was never finished isn't banked
+        app.start_round(vec!['C', 'A', 'T'], 60);
+        assert_eq!(app.rounds_banked(), 1);
+    }
+
     #[test]
     fn test_claim_feed_updates_on_accepted() {
         let mut app = App::new();
@@ -791,12 +1468,14 @@ mod tests {
         app.set_scoreboard(vec!["Alice".into(), "Bob".into()]);
         app.start_round(vec!['A', 'B', 'C'], 60);
 
-        app.on_claim_accepted("CAB".into(), "Bob".into(), 3);
+        app.on_claim_accepted("CAB".into(), "Bob".into(), 3, 1);
 
         assert_eq!(app.claim_feed.len(), 1);
         assert_eq!(app.claim_feed[0].player_name, "Bob");
         assert_eq!(app.claim_feed[0].word, "CAB");
         assert_eq!(app.claim_feed[0].points, 3);
+        // CAB uses the whole rack, so the feed can call out the pangram
+        assert_eq!(app.claim_feed[0].bonus.label().as_deref(), Some("PANGRAM!"));
         // Bob's score should be updated in scoreboard
         assert_eq!(app.scoreboard[0].name, "Bob");
         assert_eq!(app.scoreboard[0].score, 3);
@@ -811,7 +1490,7 @@ mod tests {
 
         // Add more than max entries
         for i in 0..15 {
-            app.on_claim_accepted(format!("WORD{}", i), "Bob".into(), 3);
+            app.on_claim_accepted(format!("WORD{}", i), "Bob".into(), 3, 1);
         }
 
         assert_eq!(app.claim_feed.len(), 10); // Max is 10
@@ -824,12 +1503,55 @@ mod tests {
         app.set_scoreboard(vec!["Alice".into(), "Bob".into()]);
         app.start_round(vec!['A', 'B', 'C'], 60);
 
-        app.on_claim_accepted("CAB".into(), "Alice".into(), 3);
+        app.on_claim_accepted("CAB".into(), "Alice".into(), 3, 1);
 
         assert_eq!(app.score, 3);
         assert_eq!(app.claimed_words().len(), 1);
     }
 
+    #[test]
+    fn test_combo_taken_from_host() {
+        let mut app = App::new();
+        app.set_player_name("Alice".into());
+        app.set_scoreboard(vec!["Alice".into(), "Bob".into()]);
+        app.start_round(vec!['C', 'A', 'T', 'D', 'O', 'G', 'N'], 60);
+
+        // The host scaled three quick claims x1, x2, x3
+        app.on_claim_accepted("CAT".into(), "Alice".into(), 5, 1);
+        assert_eq!(app.combo(), 0);
+        app.on_claim_accepted("DOG".into(), "Alice".into(), 12, 2);
+        assert_eq!(app.combo(), 2);
+        app.on_claim_accepted("ANT".into(), "Alice".into(), 9, 3);
+        assert_eq!(app.combo(), 3);
+        assert_eq!(app.score, 26);
+
+        // Other players' combos don't show on our badge
+        app.on_claim_accepted("TOG".into(), "Bob".into(), 10, 2);
+        assert_eq!(app.combo(), 3);
+
+        // A pause resets it
+        let at = app.combo_at.unwrap();
+        app.expire_combo(at + Duration::from_millis(COMBO_WINDOW_MS / 2));
+        assert_eq!(app.combo(), 3);
+        app.expire_combo(at + Duration::from_millis(COMBO_WINDOW_MS + 1));
+        assert_eq!(app.combo(), 0);
+    }
+
+    #[test]
+    fn test_handicap_scaling_is_not_a_combo() {
+        let mut app = App::new();
+        app.set_player_name("Alice".into());
+        app.set_handicaps(HashMap::from([("Alice".to_string(), 3.0)]));
+        app.start_round(vec!['C', 'A', 'T', 'D', 'O', 'G'], 60);
+
+        // 6 points x3 handicap looks like a combo, but the host applied none
+        app.on_claim_accepted("DOG".into(), "Alice".into(), 18, 1);
+        assert_eq!(app.combo(), 0);
+        // 5 x3 x2 = 30
+        app.on_claim_accepted("CAT".into(), "Alice".into(), 30, 2);
+        assert_eq!(app.combo(), 2);
+    }
+
     #[test]
     fn test_duplicate_claim_accepted_ignored_for_local_player() {
         let mut app = App::new();
@@ -837,8 +1559,8 @@ mod tests {
         app.set_scoreboard(vec!["Alice".into(), "Bob".into()]);
         app.start_round(vec!['A', 'B', 'C'], 60);
 
-        app.on_claim_accepted("cab".into(), "Alice".into(), 3);
-        app.on_claim_accepted("CAB".into(), "Alice".into(), 3);
+        app.on_claim_accepted("cab".into(), "Alice".into(), 3, 1);
+        app.on_claim_accepted("CAB".into(), "Alice".into(), 3, 1);
 
         assert_eq!(app.score, 3);
         assert_eq!(app.claimed_words().len(), 1);
@@ -854,8 +1576,8 @@ mod tests {
         app.set_scoreboard(vec!["Alice".into(), "Bob".into()]);
         app.start_round(vec!['A', 'B', 'C'], 60);
 
-        app.on_claim_accepted("cab".into(), "Bob".into(), 3);
-        app.on_claim_accepted("CAB".into(), "Bob".into(), 3);
+        app.on_claim_accepted("cab".into(), "Bob".into(), 3, 1);
+        app.on_claim_accepted("CAB".into(), "Bob".into(), 3, 1);
 
         assert_eq!(app.score, 0);
         assert_eq!(app.claimed_words().len(), 0);
@@ -871,11 +1593,11 @@ mod tests {
         app.set_scoreboard(vec!["Alice".into(), "Bob".into()]);
         app.start_round(vec!['A', 'B', 'C'], 60);
 
-        app.on_claim_accepted("CAB".into(), "Alice".into(), 3);
+        app.on_claim_accepted("CAB".into(), "Alice".into(), 3, 1);
         assert_eq!(app.score, 3);
 
         app.start_round(vec!['A', 'B', 'C'], 60);
-        app.on_claim_accepted("CAB".into(), "Alice".into(), 3);
+        app.on_claim_accepted("CAB".into(), "Alice".into(), 3, 1);
 
         assert_eq!(app.score, 3);
         assert_eq!(app.claimed_words().len(), 1);
@@ -889,7 +1611,7 @@ mod tests {
         app.set_scoreboard(vec!["Alice".into(), "Bob".into()]);
         app.start_round(vec!['A', 'B', 'C'], 60);
 
-        app.on_claim_accepted("CAB".into(), "Bob".into(), 3);
+        app.on_claim_accepted("CAB".into(), "Bob".into(), 3, 1);
 
         assert_eq!(app.score, 0); // Alice's score unchanged
         assert_eq!(app.claimed_words().len(), 0); // Not in Alice's claimed list
@@ -907,6 +1629,22 @@ mod tests {
         assert_eq!(app.missed_words().len(), 1);
     }
 
+    #[test]
+    fn test_penalty_follows_host_for_bad_guesses_only() {
+        let mut app = App::new();
+        app.set_player_name("Alice".into());
+        app.start_round(vec!['C', 'A', 'T'], 60);
+        app.reject_penalty = 2;
+        app.on_claim_accepted("CAT".into(), "Alice".into(), 3, 1);
+
+        app.on_claim_rejected("TAC".into(), MissReason::NotInDictionary);
+        assert_eq!(app.score, 1);
+        app.on_claim_rejected("ACT".into(), MissReason::AlreadyClaimed { by: "Bob".into() });
+        assert_eq!(app.score, 1);
+        app.on_claim_rejected("ZAP".into(), MissReason::InvalidLetters);
+        assert_eq!(app.score, 0, "Penalties stop at zero");
+    }
+
     #[test]
     fn test_force_end_round() {
         let mut app = App::new();
@@ -915,6 +1653,20 @@ mod tests {
         assert!(!app.is_round_over());
         app.force_end_round();
         assert!(app.is_round_over());
+        assert_eq!(app.round_end_reason, Some(RoundEndReason::HostEnded));
+        assert_eq!(app.feedback, "ROUND ENDED BY HOST");
+    }
+
+    #[test]
+    fn test_first_end_reason_sticks() {
+        let mut app = App::new();
+        app.start_round(vec!['A', 'B', 'C'], 1);
+        app.tick();
+        app.force_end_round();
+        assert_eq!(app.round_end_reason, Some(RoundEndReason::TimeExpired));
+
+        app.start_round(vec!['A', 'B', 'C'], 60);
+        assert_eq!(app.round_end_reason, None);
     }
 
     #[test]
@@ -939,7 +1691,7 @@ mod tests {
         app.set_scoreboard(vec!["Alice".into(), "Bob".into()]);
         app.start_round(vec!['A', 'B', 'C'], 60);
 
-        app.on_claim_accepted("CAB".into(), "Alice".into(), 3);
+        app.on_claim_accepted("CAB".into(), "Alice".into(), 3, 1);
         assert_eq!(app.scoreboard[0].score, 3);
 
         // Starting new round resets scores but keeps players
@@ -1091,8 +1843,8 @@ mod tests {
         app.set_scoreboard(vec!["Alice".into(), "Bob".into()]);
         app.start_round(vec!['A', 'B', 'C'], 60);
 
-        app.on_claim_accepted("CAB".into(), "Alice".into(), 3);
-        app.on_claim_accepted("BAC".into(), "Bob".into(), 3);
+        app.on_claim_accepted("CAB".into(), "Alice".into(), 3, 1);
+        app.on_claim_accepted("BAC".into(), "Bob".into(), 3, 1);
 
         assert_eq!(app.claim_feed.len(), 2);
         assert_eq!(app.claim_feed[0].player_name, "Alice");
@@ -1106,7 +1858,7 @@ mod tests {
         app.set_scoreboard(vec!["Alice".into()]);
         app.start_round(vec!['A', 'B', 'C'], 60);
 
-        app.on_claim_accepted("CAB".into(), "Alice".into(), 3);
+        app.on_claim_accepted("CAB".into(), "Alice".into(), 3, 1);
         assert_eq!(app.feedback, "OK +3 (CAB)");
     }
 
@@ -1120,7 +1872,7 @@ mod tests {
         // Clear any existing feedback
         app.feedback.clear();
 
-        app.on_claim_accepted("CAB".into(), "Bob".into(), 3);
+        app.on_claim_accepted("CAB".into(), "Bob".into(), 3, 1);
         // Feedback should not change for other player's claims
         assert!(app.feedback.is_empty());
     }
@@ -1167,12 +1919,39 @@ mod tests {
         assert_eq!(ps, ps2);
     }
 
+    #[test]
+    fn test_announcement_shown_apart_from_claim_feed() {
+        let mut app = App::new();
+        app.set_player_name("Alice".into());
+        app.start_round(vec!['C', 'A', 'T'], 60);
+        app.on_claim_accepted("CAT".into(), "Bob".into(), 3, 1);
+
+        app.on_announcement("Final round!".into());
+        assert_eq!(app.announcement.as_deref(), Some("Final round!"));
+        assert_eq!(app.claim_feed.len(), 1);
+        assert_eq!(app.score, 0);
+
+        app.on_announcement("5 min break".into());
+        assert_eq!(app.announcement.as_deref(), Some("5 min break"));
+    }
+
+    #[test]
+    fn test_key_char_normalized_to_dictionary_letter() {
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'F', 'E'], 60);
+        for c in ['c', 'à', 'f', 'é', '1'] {
+            app.on_key_char(c);
+        }
+        assert_eq!(app.input, "CAFE");
+    }
+
     #[test]
     fn test_claim_feed_entry_struct() {
         let entry = ClaimFeedEntry {
             player_name: "Bob".into(),
             word: "DOG".into(),
             points: 3,
+            bonus: WordBonus::default(),
         };
         assert_eq!(entry.player_name, "Bob");
         assert_eq!(entry.word, "DOG");
@@ -1192,7 +1971,7 @@ mod tests {
         app.on_char('A');
         app.on_char('T');
         app.on_submit();
-        assert_eq!(app.score, 3);
+        assert_eq!(app.score, 5);
         assert_eq!(app.claimed_words().len(), 1);
 
         // Try to claim "CAT" again - should be rejected
@@ -1200,7 +1979,7 @@ mod tests {
         app.on_char('A');
         app.on_char('T');
         app.on_submit();
-        assert_eq!(app.score, 3); // Score unchanged
+        assert_eq!(app.score, 5); // Score unchanged
         assert_eq!(app.claimed_words().len(), 1); // Still only one claim
         assert_eq!(app.feedback, "ALREADY CLAIMED");
         assert_eq!(app.missed_words().len(), 1);
@@ -1220,7 +1999,7 @@ mod tests {
         app.on_char('A');
         app.on_char('T');
         app.on_submit();
-        assert_eq!(app.score, 3);
+        assert_eq!(app.score, 5);
 
         // Try "cat" (lowercase) - input is uppercased by on_char in main.rs,
         // but on_submit handles raw input. Both should be caught.
@@ -1228,7 +2007,7 @@ mod tests {
         app.on_char('a');
         app.on_char('t');
         app.on_submit();
-        assert_eq!(app.score, 3); // Score unchanged
+        assert_eq!(app.score, 5); // Score unchanged
     }
 
     #[test]
@@ -1241,11 +2020,158 @@ mod tests {
         app.on_char('A');
         app.on_char('T');
         app.on_submit();
-        assert_eq!(app.score, 3);
+        assert_eq!(app.score, 5);
 
         // Round 2 - score resets
         app.start_round(vec!['D', 'O', 'G', 'C', 'A', 'T', 'E', 'R', 'S', 'T', 'A', 'N'], 60);
         assert_eq!(app.score, 0);
         assert!(app.claimed_words().is_empty());
     }
+
+    #[test]
+    fn test_rack_difficulty_measures_round() {
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'T'], 60);
+        for c in "CAT".chars() {
+            app.on_char(c);
+        }
+        app.on_submit();
+
+        let difficulty = app.rack_difficulty();
+        assert_eq!(difficulty.letters, vec!['C', 'A', 'T']);
+        assert!(difficulty.valid_words >= 2);
+        assert_eq!(difficulty.achieved_points, app.score);
+        assert!(difficulty.max_points >= app.score);
+        assert!(difficulty.achieved_fraction() > 0.0);
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip_through_storage() {
+        use crate::storage::Storage;
+
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'], 60);
+        for c in "CAT".chars() {
+            app.on_char(c);
+        }
+        app.on_submit();
+        for c in "DOGS".chars() {
+            app.on_char(c);
+        }
+        app.on_submit();
+        app.tick();
+        app.tick();
+
+        let storage = Storage::open_in_memory().unwrap();
+        storage.save_round_checkpoint(&app.checkpoint()).unwrap();
+        let checkpoint = storage.load_round_checkpoint().unwrap().unwrap();
+        let restored = App::from_checkpoint(&checkpoint);
+
+        assert_eq!(restored.letters, app.letters);
+        assert_eq!(restored.time_remaining, 58);
+        assert_eq!(restored.score, 12);
+        assert_eq!(restored.claimed_words(), app.claimed_words());
+        assert!(!restored.is_round_over());
+    }
+
+    #[test]
+    fn test_restored_round_rejects_reclaim() {
+        let checkpoint = RoundCheckpoint {
+            letters: vec!['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'],
+            time_remaining: 30,
+            score: 3,
+            claimed_words: vec![("CAT".to_string(), 3)],
+            saved_at: 0,
+        };
+        let mut app = App::from_checkpoint(&checkpoint);
+
+        for c in "CAT".chars() {
+            app.on_char(c);
+        }
+        app.on_submit();
+        assert_eq!(app.feedback, "ALREADY CLAIMED");
+        assert_eq!(app.score, 3);
+    }
+
+    #[test]
+    fn test_input_capped_at_rack_size() {
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'T'], 60);
+
+        for c in "CATSDOG".chars() {
+            app.on_char(c);
+        }
+        assert_eq!(app.input, "CAT");
+        assert_eq!(app.max_input_len(), 3);
+
+        // Backspace frees room for another letter
+        app.on_backspace();
+        app.on_char('B');
+        assert_eq!(app.input, "CAB");
+    }
+
+    #[test]
+    fn test_too_long_rejection_feedback() {
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'T'], 60);
+        app.on_claim_rejected("CATS".to_string(), MissReason::TooLong);
+        assert_eq!(app.feedback, "Too long");
+        assert_eq!(app.round_summary().invalid_letters, vec!["CATS".to_string()]);
+    }
+
+    #[test]
+    fn test_remaining_valid_count_drops_as_words_are_claimed() {
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'T'], 60);
+        let total = solver::possible_words(&['C', 'A', 'T']).len();
+        assert!(total >= 2, "CAT and ACT should both be words");
+        assert_eq!(app.remaining_valid_count(), total);
+
+        for word in ["cat", "act", "xyz"] {
+            for c in word.chars() {
+                app.on_char(c);
+            }
+            app.on_submit();
+        }
+        assert_eq!(app.remaining_valid_count(), total - 2);
+
+        app.show_hint(app.remaining_valid_count());
+        assert!(app.feedback.starts_with("HINT: "), "{}", app.feedback);
+        assert!(!app.feedback.contains("CAT"));
+    }
+
+    #[test]
+    fn test_pending_points_for_spellable_input() {
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'], 60);
+        assert_eq!(app.pending_points(), None);
+
+        for c in "CATS".chars() {
+            app.on_char(c);
+        }
+        assert_eq!(app.pending_points(), Some(6));
+
+        // The host will scale it by our handicap
+        app.set_player_name("Alice".into());
+        app.set_handicaps(HashMap::from([("Alice".to_string(), 1.5)]));
+        assert_eq!(app.pending_points(), Some(9));
+    }
+
+    #[test]
+    fn test_pending_points_none_for_invalid_letters() {
+        let mut app = App::new();
+        app.start_round(vec!['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'], 60);
+
+        for c in "ZAP".chars() {
+            app.on_char(c);
+        }
+        assert_eq!(app.pending_points(), None);
+
+        // Needs two C's but the rack has one
+        app.clear_input();
+        for c in "CC".chars() {
+            app.on_char(c);
+        }
+        assert_eq!(app.pending_points(), None);
+    }
 }
@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+//! Which color theme the terminal UI draws with
+//!
+//! The styles themselves live with the renderer (`tui::theme`); the app
+//! only tracks which one is picked, so it doesn't need the `tui` feature.
+
+/// A built-in color theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeChoice {
+    /// The original yellow/cyan/green/red palette
+    #[default]
+    Classic,
+    /// Bright colors and reversed selections for low-contrast terminals
+    HighContrast,
+    /// Blue/orange in place of green/red, for red-green color blindness
+    Colorblind,
+}
+
+impl ThemeChoice {
+    /// Every theme, in the order Settings cycles through them
+    pub fn all() -> [ThemeChoice; 3] {
+        [ThemeChoice::Classic, ThemeChoice::HighContrast, ThemeChoice::Colorblind]
+    }
+
+    /// The theme after this one, wrapping around
+    pub fn next(self) -> ThemeChoice {
+        let all = Self::all();
+        let i = all.iter().position(|&t| t == self).unwrap_or(0);
+        all[(i + 1) % all.len()]
+    }
+
+    /// Name shown in Settings
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeChoice::Classic => "Classic",
+            ThemeChoice::HighContrast => "High contrast",
+            ThemeChoice::Colorblind => "Colorblind",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_cycles_through_every_theme() {
+        let mut theme = ThemeChoice::default();
+        let mut seen = vec![theme];
+        for _ in 1..ThemeChoice::all().len() {
+            theme = theme.next();
+            seen.push(theme);
+        }
+        assert_eq!(seen, ThemeChoice::all().to_vec());
+        assert_eq!(theme.next(), ThemeChoice::Classic);
+    }
+}
@@ -0,0 +1,37 @@
+//! The game engine, for embedding BLAM! in another frontend
+//!
+//! One place to find the pure game pieces: racks, word validation,
+//! scoring, claim arbitration, a player's round state, match results and
+//! Elo. Nothing here touches the terminal or the network, so it all builds
+//! with `default-features = false`.
+//!
+//! A round, start to finish:
+//!
+//! ```
+//! use blam::engine::{LetterRack, MatchResult, RoundArbitrator};
+//!
+//! let letters = LetterRack::generate().letters().to_vec();
+//! let players = vec!["Ada".to_string(), "Grace".to_string()];
+//! let mut round = RoundArbitrator::new(letters, &players);
+//! round.try_claim("a", "Ada");
+//! round.end_round();
+//! let result = MatchResult::new(1, round.raw_scores(), "host".to_string());
+//! assert_eq!(result.scores.len(), 2);
+//! ```
+
+pub use crate::app::hotseat::{HotseatGame, HotseatPlayer};
+pub use crate::app::state::{
+    winner_of, App, ClaimedWord, MissReason, MissedWord, PlayerScore, RoundSummary, WinnerResult,
+    DEFAULT_ROUND_DURATION,
+};
+pub use crate::app::FlavorText;
+pub use crate::game::arbitrator::{ClaimRecord, ClaimResult, RoundArbitrator};
+pub use crate::game::dictionary::{self, Dictionary};
+pub use crate::game::scoring::{letter_points, score_on_rack, score_word, LETTER_POINTS};
+pub use crate::game::solver::{solve_rack, RackSolution};
+pub use crate::game::validation::{
+    validate_word, validate_word_with_rules, LettersUsedRule, ValidationResult,
+};
+pub use crate::game::{Language, LetterRack, RackConfig, RackPreset, BLANK};
+pub use crate::network::RoundEndReason;
+pub use crate::stats::{EloCalculator, MatchResult, PlayerStats, StatsTracker, DEFAULT_ELO};
@@ -4,9 +4,55 @@
 //! The host runs the arbitrator to validate claims and ensure only
 //! the first claimant gets points. This provides the authoritative
 //! "first claimant wins" logic for the game.
-
-use super::validation::{validate_word, ValidationResult};
-use std::collections::HashMap;
+//!
+//! Optional per-player handicaps scale the points a player's claims are
+//! worth. Handicapped totals decide the round, but Elo should be fed
+//! `raw_scores()`: a handicap is there to make a mixed-skill game fun, not to
+//! move anyone's rating.
+//!
+//! Combos are optional too: accepted claims in quick succession multiply a
+//! player's points (x2, then x3). The host applies them at acceptance, so
+//! everyone sees the same totals. Like handicaps, they stay out of
+//! `raw_scores()`.
+//!
+//! Under the minimum-letters-used house rule, a claim must use at least K
+//! of the rack's tiles; shorter words are turned away even when they're in
+//! the dictionary.
+//!
+//! Penalty mode deducts points for guesses that miss outright: a word that
+//! isn't in the dictionary or uses letters the rack doesn't have. Scores
+//! never drop below zero, and losing a race for a word (`AlreadyClaimed`)
+//! costs nothing. Penalties stay out of `raw_scores()` as well.
+//!
+//! In team mode players are put on numbered teams and `scores()` sums each
+//! team's claims into one "Team N" entry. `raw_scores()` stays per player,
+//! with the teams passed alongside, so ratings still reach each member.
+//!
+//! A player who drops mid-round can be marked away: they keep their points
+//! and their claimed words, but can't claim anything new until they're back.
+//!
+//! For hints, the arbitrator can say how many valid words are still up for
+//! grabs. The rack's words are found with one pass over the dictionary the
+//! first time anyone asks; later hints only check that list against the
+//! claims.
+
+use super::dictionary::{self, Dictionary};
+use super::scoring::score_on_rack;
+use super::solver;
+use super::validation::{
+    bonus_points, validate_word_with_rules, ValidationResult, MIN_WORD_LENGTH,
+};
+use crate::stats::team_name;
+use once_cell::sync::OnceCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Longest gap between a player's accepted claims that keeps a combo going
+pub const COMBO_WINDOW_MS: u64 = 3000;
+
+/// Highest combo multiplier (reached on the third quick claim in a row)
+pub const MAX_COMBO: u32 = 3;
 
 /// Result of attempting to claim a word
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -24,29 +70,80 @@ pub enum ClaimResult {
     TooShort,
     /// Claim rejected - word uses invalid letters
     InvalidLetters { missing: Vec<char> },
+    /// Claim rejected - word uses fewer rack letters than the house rule asks
+    TooFewLetters { min: usize },
     /// Claim rejected - word not in dictionary
     NotInDictionary,
     /// Claim rejected - round has ended
     RoundEnded,
+    /// Claim rejected - the player is away (disconnected)
+    Away,
+}
+
+/// An accepted claim, as recorded by the arbitrator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimRecord {
+    /// Player who made the claim
+    pub player_name: String,
+    /// Claimed word (uppercase)
+    pub word: String,
+    /// Points awarded (with handicap applied)
+    pub points: u32,
+    /// Sequence number the claim was accepted with
+    pub claim_sequence: u64,
 }
 
 /// Tracks claimed words and player scores during a round
 pub struct RoundArbitrator {
     /// The letter rack for this round
     letters: Vec<char>,
+    /// Word list claims are checked against (shared, never re-parsed)
+    dictionary: Arc<Dictionary>,
     /// Words claimed this round, mapping word -> claimant
     claimed_words: HashMap<String, String>,
-    /// Player scores
+    /// Accepted claims in the order they were accepted
+    claims: Vec<ClaimRecord>,
+    /// Player scores (with handicaps applied)
     scores: HashMap<String, u32>,
+    /// Player scores before handicaps (for Elo)
+    raw_scores: HashMap<String, u32>,
+    /// Per-player points multipliers (missing means 1.0)
+    multipliers: HashMap<String, f32>,
     /// Whether the round is still active
     round_active: bool,
     /// Monotonic counter for claim ordering (for CRDT log)
     claim_sequence: u64,
+    /// Whether quick claims build a combo multiplier
+    combos_enabled: bool,
+    /// Per-player combo streak and when their last claim was accepted (ms)
+    streaks: HashMap<String, (u32, u64)>,
+    /// Shortest word that can be claimed
+    min_word_length: usize,
+    /// Fewest rack letters a claim must use (0 for no limit)
+    min_letters_used: usize,
+    /// Players currently away; their points stand but they can't claim
+    away: HashSet<String>,
+    /// Points deducted for a claim that misses outright (0 for none)
+    reject_penalty: u32,
+    /// Dictionary words the rack can spell (uppercase), found on first use
+    formable: OnceCell<Vec<String>>,
+    /// Team each player is on (missing means playing alone)
+    teams: HashMap<String, u8>,
 }
 
 impl RoundArbitrator {
-    /// Create a new arbitrator for a round
+    /// Create a new arbitrator for a round, checking claims against the
+    /// shared dictionary
     pub fn new(letters: Vec<char>, players: &[String]) -> Self {
+        Self::with_dictionary(letters, players, dictionary::shared())
+    }
+
+    /// Create a new arbitrator that checks claims against `dictionary`
+    pub fn with_dictionary(
+        letters: Vec<char>,
+        players: &[String],
+        dictionary: Arc<Dictionary>,
+    ) -> Self {
         let mut scores = HashMap::new();
         for player in players {
             scores.insert(player.clone(), 0);
@@ -54,19 +151,165 @@ impl RoundArbitrator {
 
         Self {
             letters,
+            dictionary,
             claimed_words: HashMap::new(),
+            claims: Vec::new(),
+            raw_scores: scores.clone(),
             scores,
+            multipliers: HashMap::new(),
             round_active: true,
             claim_sequence: 0,
+            combos_enabled: false,
+            streaks: HashMap::new(),
+            min_word_length: MIN_WORD_LENGTH,
+            min_letters_used: 0,
+            away: HashSet::new(),
+            reject_penalty: 0,
+            formable: OnceCell::new(),
+            teams: HashMap::new(),
+        }
+    }
+
+    /// Word list claims are checked against
+    pub fn dictionary(&self) -> &Arc<Dictionary> {
+        &self.dictionary
+    }
+
+    /// Put a player on a team (`None` to play alone)
+    pub fn set_team(&mut self, player_name: &str, team: Option<u8>) {
+        match team {
+            Some(team) => self.teams.insert(player_name.to_string(), team),
+            None => self.teams.remove(player_name),
+        };
+    }
+
+    /// Team each player is on, sorted by player
+    pub fn teams(&self) -> Vec<(String, u8)> {
+        let mut teams: Vec<_> = self.teams.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        teams.sort();
+        teams
+    }
+
+    /// Mark a player away (or back)
+    pub fn set_away(&mut self, player_name: &str, away: bool) {
+        if away {
+            self.away.insert(player_name.to_string());
+        } else {
+            self.away.remove(player_name);
+        }
+    }
+
+    /// Whether a player is away
+    pub fn is_away(&self, player_name: &str) -> bool {
+        self.away.contains(player_name)
+    }
+
+    /// Set the shortest word that can be claimed (at least `MIN_WORD_LENGTH`)
+    pub fn set_min_word_length(&mut self, length: usize) {
+        self.min_word_length = length.max(MIN_WORD_LENGTH);
+    }
+
+    /// Set the fewest rack letters a claim must use (0 for no limit)
+    pub fn set_min_letters_used(&mut self, count: usize) {
+        self.min_letters_used = count;
+    }
+
+    /// Fewest rack letters a claim must use (0 for no limit)
+    pub fn min_letters_used(&self) -> usize {
+        self.min_letters_used
+    }
+
+    /// Shortest word that can be claimed
+    pub fn min_word_length(&self) -> usize {
+        self.min_word_length
+    }
+
+    /// Set the points deducted for a claim that misses outright (0 for none)
+    pub fn set_reject_penalty(&mut self, points: u32) {
+        self.reject_penalty = points;
+    }
+
+    /// Points deducted for a claim that misses outright (0 for none)
+    pub fn reject_penalty(&self) -> u32 {
+        self.reject_penalty
+    }
+
+    /// Take the reject penalty off a player's score, stopping at zero
+    fn penalize(&mut self, player_name: &str) {
+        if let Some(score) = self.scores.get_mut(player_name) {
+            *score = score.saturating_sub(self.reject_penalty);
+        }
+    }
+
+    /// Turn combo multipliers on or off
+    pub fn set_combos(&mut self, enabled: bool) {
+        self.combos_enabled = enabled;
+    }
+
+    /// A player's current combo streak (1 when they have none going)
+    pub fn combo(&self, player_name: &str) -> u32 {
+        self.streaks.get(player_name).map_or(1, |(count, _)| *count)
+    }
+
+    /// Extend (or restart) a player's streak for a claim accepted at `now_ms`
+    fn next_combo(&mut self, player_name: &str, now_ms: u64) -> u32 {
+        if !self.combos_enabled {
+            return 1;
         }
+        let (count, last) = self
+            .streaks
+            .entry(player_name.to_string())
+            .or_insert((0, now_ms));
+        *count = if *count > 0 && now_ms.saturating_sub(*last) <= COMBO_WINDOW_MS {
+            (*count + 1).min(MAX_COMBO)
+        } else {
+            1
+        };
+        *last = now_ms;
+        *count
+    }
+
+    /// Treat words as already claimed before the round starts
+    ///
+    /// Used for match rules that carry claims over from earlier rounds.
+    /// Seeded words are rejected as `AlreadyClaimed` by their original
+    /// claimant, but add nothing to this round's scores or claims.
+    pub fn seed_claimed_words(&mut self, claimed: &HashMap<String, String>) {
+        for (word, player_name) in claimed {
+            self.claimed_words
+                .entry(word.to_uppercase())
+                .or_insert_with(|| player_name.clone());
+        }
+    }
+
+    /// Set a player's handicap multiplier
+    pub fn set_multiplier(&mut self, player_name: &str, multiplier: f32) {
+        self.multipliers.insert(player_name.to_string(), multiplier);
+    }
+
+    /// Get a player's handicap multiplier (1.0 if none set)
+    pub fn multiplier(&self, player_name: &str) -> f32 {
+        self.multipliers.get(player_name).copied().unwrap_or(1.0)
     }
 
     /// Attempt to claim a word for a player
     pub fn try_claim(&mut self, word: &str, player_name: &str) -> ClaimResult {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        self.try_claim_at(word, player_name, now_ms)
+    }
+
+    /// Attempt to claim a word for a player at a given time (ms since epoch)
+    pub fn try_claim_at(&mut self, word: &str, player_name: &str, now_ms: u64) -> ClaimResult {
         // Check if round is still active
         if !self.round_active {
             return ClaimResult::RoundEnded;
         }
+        if self.is_away(player_name) {
+            return ClaimResult::Away;
+        }
 
         let word_upper = word.to_uppercase();
 
@@ -78,21 +321,38 @@ impl RoundArbitrator {
         }
 
         // Validate the word
-        let result = validate_word(&word_upper, &self.letters);
+        let result = validate_word_with_rules(
+            &self.dictionary,
+            &word_upper,
+            &self.letters,
+            self.min_word_length,
+            self.min_letters_used,
+        );
         match result {
             ValidationResult::Valid => {
                 // Word is valid and unclaimed - accept the claim
-                let points = word_upper.len() as u32;
+                let raw_points = score_on_rack(&word_upper, &self.letters)
+                    + bonus_points(&word_upper, &self.letters);
+                let combo = self.next_combo(player_name, now_ms);
+                let points =
+                    (raw_points as f32 * self.multiplier(player_name) * combo as f32).round() as u32;
 
                 // Record the claim
                 self.claimed_words
-                    .insert(word_upper, player_name.to_string());
+                    .insert(word_upper.clone(), player_name.to_string());
 
                 // Update player's score
                 *self.scores.entry(player_name.to_string()).or_insert(0) += points;
+                *self.raw_scores.entry(player_name.to_string()).or_insert(0) += raw_points;
 
                 // Increment and return sequence number for CRDT ordering
                 self.claim_sequence += 1;
+                self.claims.push(ClaimRecord {
+                    player_name: player_name.to_string(),
+                    word: word_upper,
+                    points,
+                    claim_sequence: self.claim_sequence,
+                });
                 ClaimResult::Accepted {
                     points,
                     claim_sequence: self.claim_sequence,
@@ -100,9 +360,14 @@ impl RoundArbitrator {
             }
             ValidationResult::TooShort { .. } => ClaimResult::TooShort,
             ValidationResult::InvalidLetters { missing } => {
+                self.penalize(player_name);
                 ClaimResult::InvalidLetters { missing }
             }
-            ValidationResult::NotInDictionary => ClaimResult::NotInDictionary,
+            ValidationResult::TooFewLetters { min, .. } => ClaimResult::TooFewLetters { min },
+            ValidationResult::NotInDictionary => {
+                self.penalize(player_name);
+                ClaimResult::NotInDictionary
+            }
         }
     }
 
@@ -116,9 +381,53 @@ impl RoundArbitrator {
         self.round_active
     }
 
+    /// Longest word that could possibly be claimed (one per rack tile)
+    pub fn max_word_length(&self) -> usize {
+        self.letters.len()
+    }
+
+    /// How many valid words nobody has claimed yet
+    ///
+    /// Counts dictionary words the rack can spell that meet the round's
+    /// length rules and aren't claimed (or seeded from earlier rounds).
+    /// House words aren't counted.
+    pub fn remaining_valid_count(&self) -> usize {
+        let min_length = self.min_word_length.max(self.min_letters_used);
+        self.formable
+            .get_or_init(|| {
+                solver::possible_words_in(&self.dictionary, &self.letters)
+                    .into_iter()
+                    .map(str::to_uppercase)
+                    .collect()
+            })
+            .iter()
+            .filter(|word| word.chars().count() >= min_length)
+            .filter(|word| !self.claimed_words.contains_key(*word))
+            .count()
+    }
+
     /// Get current scores as a sorted list (highest first)
+    ///
+    /// Once anyone is on a team, each team shows as one entry with its
+    /// members' points summed; players without a team keep their own.
     pub fn scores(&self) -> Vec<(String, u32)> {
-        let mut scores: Vec<_> = self.scores.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        let mut scores: Vec<(String, u32)> = Vec::new();
+        for (name, points) in &self.scores {
+            let entry = self.teams.get(name).map(|t| team_name(*t)).unwrap_or_else(|| name.clone());
+            match scores.iter_mut().find(|(n, _)| *n == entry) {
+                Some((_, total)) => *total += points,
+                None => scores.push((entry, *points)),
+            }
+        }
+        scores.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        scores
+    }
+
+    /// Get scores without handicaps applied, sorted (highest first)
+    ///
+    /// This is what Elo should be computed from.
+    pub fn raw_scores(&self) -> Vec<(String, u32)> {
+        let mut scores: Vec<_> = self.raw_scores.iter().map(|(k, v)| (k.clone(), *v)).collect();
         scores.sort_by(|a, b| b.1.cmp(&a.1));
         scores
     }
@@ -128,6 +437,11 @@ impl RoundArbitrator {
         &self.claimed_words
     }
 
+    /// Accepted claims this round, in claim sequence order
+    pub fn claims(&self) -> &[ClaimRecord] {
+        &self.claims
+    }
+
     /// Get a player's score
     pub fn player_score(&self, player_name: &str) -> u32 {
         *self.scores.get(player_name).unwrap_or(&0)
@@ -146,13 +460,54 @@ mod tests {
         vec!["Alice".to_string(), "Bob".to_string()]
     }
 
+    #[test]
+    fn test_remaining_valid_count_drops_as_words_are_claimed() {
+        let dictionary = Arc::new(Dictionary::parse(
+            "cat\nact\ntac\nat\ndog\ncats\n",
+            crate::game::Language::English,
+        ));
+        let mut arb =
+            RoundArbitrator::with_dictionary(vec!['C', 'A', 'T'], &test_players(), dictionary);
+        // CAT, ACT, TAC and AT; DOG and CATS can't be spelled
+        assert_eq!(arb.remaining_valid_count(), 4);
+
+        arb.try_claim("cat", "Alice");
+        assert_eq!(arb.remaining_valid_count(), 3);
+        arb.try_claim("act", "Bob");
+        assert_eq!(arb.remaining_valid_count(), 2);
+        // Misses don't change anything
+        arb.try_claim("dog", "Bob");
+        assert_eq!(arb.remaining_valid_count(), 2);
+
+        // Words the round's rules turn away aren't counted
+        arb.set_min_word_length(3);
+        assert_eq!(arb.remaining_valid_count(), 1);
+        arb.try_claim("tac", "Alice");
+        assert_eq!(arb.remaining_valid_count(), 0);
+    }
+
+    #[test]
+    fn test_claims_recorded_in_sequence() {
+        let mut arb = RoundArbitrator::new(test_letters(), &test_players());
+        arb.try_claim("cat", "Alice");
+        arb.try_claim("cat", "Bob");
+        arb.try_claim("dog", "Bob");
+
+        let claims = arb.claims();
+        assert_eq!(claims.len(), 2);
+        assert_eq!(claims[0].word, "CAT");
+        assert_eq!(claims[0].player_name, "Alice");
+        assert_eq!(claims[1].word, "DOG");
+        assert_eq!(claims[1].claim_sequence, 2);
+    }
+
     #[test]
     fn test_first_claim_wins() {
         let mut arb = RoundArbitrator::new(test_letters(), &test_players());
 
         // Alice claims CAT first
         let result = arb.try_claim("cat", "Alice");
-        assert!(matches!(result, ClaimResult::Accepted { points: 3, claim_sequence: 1 }));
+        assert!(matches!(result, ClaimResult::Accepted { points: 5, claim_sequence: 1 }));
 
         // Bob tries to claim CAT - rejected
         let result = arb.try_claim("cat", "Bob");
@@ -162,29 +517,79 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_seeded_words_rejected_without_scoring() {
+        let mut arb = RoundArbitrator::new(test_letters(), &test_players());
+        let earlier = HashMap::from([("CAT".to_string(), "Alice".to_string())]);
+        arb.seed_claimed_words(&earlier);
+
+        let result = arb.try_claim("cat", "Bob");
+        assert!(matches!(result, ClaimResult::AlreadyClaimed { by } if by == "Alice"));
+        assert!(arb.claims().is_empty());
+        assert_eq!(arb.player_score("Alice"), 0);
+    }
+
+    #[test]
+    fn test_quick_claims_build_combo() {
+        let mut arb = RoundArbitrator::new(test_letters(), &test_players());
+        arb.set_combos(true);
+
+        // CAT (5), DOG (6) and ANT (3) at x1, x2, x3
+        let points: Vec<u32> = [("cat", 0), ("dog", 1000), ("ant", 2500)]
+            .iter()
+            .map(|(word, at)| match arb.try_claim_at(word, "Alice", *at) {
+                ClaimResult::Accepted { points, .. } => points,
+                other => panic!("{} not accepted: {:?}", word, other),
+            })
+            .collect();
+        assert_eq!(points, vec![5, 12, 9]);
+        assert_eq!(arb.combo("Alice"), 3);
+
+        // Capped at MAX_COMBO
+        let result = arb.try_claim_at("tan", "Alice", 3000);
+        assert!(matches!(result, ClaimResult::Accepted { points: 9, .. }));
+
+        // A pause ends the streak
+        let result = arb.try_claim_at("rat", "Alice", 3000 + COMBO_WINDOW_MS + 1);
+        assert!(matches!(result, ClaimResult::Accepted { points: 3, .. }));
+        assert_eq!(arb.combo("Alice"), 1);
+
+        // Combos don't count toward Elo
+        assert_eq!(arb.raw_scores()[0], ("Alice".to_string(), 20));
+    }
+
+    #[test]
+    fn test_combos_off_by_default() {
+        let mut arb = RoundArbitrator::new(test_letters(), &test_players());
+        arb.try_claim_at("cat", "Alice", 0);
+        let result = arb.try_claim_at("dog", "Alice", 500);
+        assert!(matches!(result, ClaimResult::Accepted { points: 6, .. }));
+        assert_eq!(arb.combo("Alice"), 1);
+    }
+
     #[test]
     fn test_different_words_can_be_claimed() {
         let mut arb = RoundArbitrator::new(test_letters(), &test_players());
 
         // Alice claims CAT
         let result = arb.try_claim("cat", "Alice");
-        assert!(matches!(result, ClaimResult::Accepted { points: 3, claim_sequence: 1 }));
+        assert!(matches!(result, ClaimResult::Accepted { points: 5, claim_sequence: 1 }));
 
         // Bob claims DOG
         let result = arb.try_claim("dog", "Bob");
-        assert!(matches!(result, ClaimResult::Accepted { points: 3, claim_sequence: 2 }));
+        assert!(matches!(result, ClaimResult::Accepted { points: 6, claim_sequence: 2 }));
     }
 
     #[test]
     fn test_scores_tracked() {
         let mut arb = RoundArbitrator::new(test_letters(), &test_players());
 
-        arb.try_claim("cat", "Alice"); // 3 points
-        arb.try_claim("dog", "Bob"); // 3 points
-        arb.try_claim("dogs", "Alice"); // 4 points
+        arb.try_claim("cat", "Alice"); // 5 points
+        arb.try_claim("dog", "Bob"); // 6 points
+        arb.try_claim("dogs", "Alice"); // 7 points
 
-        assert_eq!(arb.player_score("Alice"), 7);
-        assert_eq!(arb.player_score("Bob"), 3);
+        assert_eq!(arb.player_score("Alice"), 12);
+        assert_eq!(arb.player_score("Bob"), 6);
     }
 
     #[test]
@@ -204,6 +609,44 @@ mod tests {
         assert!(matches!(result, ClaimResult::NotInDictionary));
     }
 
+    #[test]
+    fn test_min_word_length_threshold() {
+        let letters = vec!['O', 'X', 'E', 'N'];
+        let mut arb = RoundArbitrator::new(letters, &test_players());
+
+        arb.set_min_word_length(3);
+        assert!(matches!(arb.try_claim("ox", "Alice"), ClaimResult::TooShort));
+        assert!(matches!(arb.try_claim("oxen", "Alice"), ClaimResult::Accepted { .. }));
+
+        arb.set_min_word_length(2);
+        assert!(matches!(arb.try_claim("ox", "Bob"), ClaimResult::Accepted { .. }));
+
+        // Can't go below the global minimum
+        arb.set_min_word_length(0);
+        assert_eq!(arb.min_word_length(), 1);
+    }
+
+    #[test]
+    fn test_min_letters_used_rejects_short_dictionary_words() {
+        let mut arb = RoundArbitrator::new(test_letters(), &test_players());
+        arb.set_min_letters_used(5);
+
+        assert_eq!(arb.try_claim("dogs", "Alice"), ClaimResult::TooFewLetters { min: 5 });
+        assert!(matches!(arb.try_claim("codes", "Alice"), ClaimResult::Accepted { .. }));
+        assert_eq!(arb.scores()[0], ("Alice".to_string(), 8));
+
+        // Turning it off lets the short word through
+        arb.set_min_letters_used(0);
+        assert!(matches!(arb.try_claim("dogs", "Bob"), ClaimResult::Accepted { .. }));
+    }
+
+    #[test]
+    fn test_consecutive_rounds_share_one_dictionary() {
+        let first = RoundArbitrator::new(test_letters(), &test_players());
+        let second = RoundArbitrator::new(test_letters(), &test_players());
+        assert!(Arc::ptr_eq(first.dictionary(), second.dictionary()));
+    }
+
     #[test]
     fn test_round_ended() {
         let mut arb = RoundArbitrator::new(test_letters(), &test_players());
@@ -232,14 +675,14 @@ mod tests {
     fn test_scores_sorted() {
         let mut arb = RoundArbitrator::new(test_letters(), &test_players());
 
-        arb.try_claim("cat", "Bob"); // 3 points
-        arb.try_claim("dogs", "Alice"); // 4 points
+        arb.try_claim("cat", "Bob"); // 5 points
+        arb.try_claim("dogs", "Alice"); // 7 points
 
         let scores = arb.scores();
         assert_eq!(scores[0].0, "Alice");
-        assert_eq!(scores[0].1, 4);
+        assert_eq!(scores[0].1, 7);
         assert_eq!(scores[1].0, "Bob");
-        assert_eq!(scores[1].1, 3);
+        assert_eq!(scores[1].1, 5);
     }
 
     #[test]
@@ -298,26 +741,26 @@ mod tests {
     fn test_multiple_claims_same_player() {
         let mut arb = RoundArbitrator::new(test_letters(), &test_players());
 
-        arb.try_claim("cat", "Alice"); // 3 points
-        arb.try_claim("dog", "Alice"); // 3 points
+        arb.try_claim("cat", "Alice"); // 5 points
+        arb.try_claim("dog", "Alice"); // 6 points
         arb.try_claim("tan", "Alice"); // 3 points
 
-        assert_eq!(arb.player_score("Alice"), 9);
+        assert_eq!(arb.player_score("Alice"), 14);
         assert_eq!(arb.player_score("Bob"), 0);
     }
 
     #[test]
-    fn test_points_equal_word_length() {
+    fn test_points_follow_letter_values() {
         let letters = vec!['C', 'A', 'T', 'S', 'D', 'O', 'G', 'E', 'R', 'N', 'A', 'T'];
         let mut arb = RoundArbitrator::new(letters, &test_players());
 
-        // 3-letter word = 3 points
+        // C (3) + A (1) + T (1)
         let r1 = arb.try_claim("cat", "Alice");
-        assert!(matches!(r1, ClaimResult::Accepted { points: 3, .. }));
+        assert!(matches!(r1, ClaimResult::Accepted { points: 5, .. }));
 
-        // 4-letter word = 4 points
-        let r2 = arb.try_claim("dogs", "Bob");
-        assert!(matches!(r2, ClaimResult::Accepted { points: 4, .. }));
+        // Same length, common letters only
+        let r2 = arb.try_claim("tan", "Bob");
+        assert!(matches!(r2, ClaimResult::Accepted { points: 3, .. }));
     }
 
     #[test]
@@ -352,7 +795,7 @@ mod tests {
         // Player not in initial list can still claim
         let r = arb.try_claim("cat", "Charlie");
         assert!(matches!(r, ClaimResult::Accepted { .. }));
-        assert_eq!(arb.player_score("Charlie"), 3);
+        assert_eq!(arb.player_score("Charlie"), 5);
     }
 
     #[test]
@@ -371,4 +814,91 @@ mod tests {
         let arb = RoundArbitrator::new(test_letters(), &test_players());
         assert!(arb.claimed_words().is_empty());
     }
+
+    #[test]
+    fn test_handicap_multiplier_scales_points() {
+        let mut arb = RoundArbitrator::new(test_letters(), &test_players());
+        arb.set_multiplier("Alice", 1.5);
+
+        let result = arb.try_claim("cats", "Alice");
+        assert!(matches!(result, ClaimResult::Accepted { points: 9, .. }));
+        assert_eq!(arb.player_score("Alice"), 9);
+
+        // Players without a handicap score normally
+        let result = arb.try_claim("dog", "Bob");
+        assert!(matches!(result, ClaimResult::Accepted { points: 6, .. }));
+    }
+
+    #[test]
+    fn test_raw_scores_ignore_handicap() {
+        let mut arb = RoundArbitrator::new(test_letters(), &test_players());
+        arb.set_multiplier("Alice", 2.0);
+        arb.try_claim("cats", "Alice");
+
+        assert_eq!(arb.player_score("Alice"), 12);
+        let raw = arb.raw_scores();
+        assert_eq!(raw[0], ("Alice".to_string(), 6));
+        assert_eq!(arb.multiplier("Bob"), 1.0);
+    }
+
+    #[test]
+    fn test_team_scores_sum_members_claims() {
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
+        let mut arb = RoundArbitrator::new(test_letters(), &players);
+        arb.set_team("Alice", Some(1));
+        arb.set_team("Bob", Some(1));
+        arb.set_team("Carol", Some(2));
+
+        arb.try_claim("cat", "Alice");
+        arb.try_claim("dog", "Bob");
+        arb.try_claim("cats", "Carol");
+
+        // Carol out-scores each of them alone, but not the two together
+        assert_eq!(arb.player_score("Carol"), 6);
+        assert_eq!(
+            arb.scores(),
+            vec![("Team 1".to_string(), 11), ("Team 2".to_string(), 6)]
+        );
+        assert_eq!(arb.raw_scores().len(), 3, "raw scores stay per player");
+        assert_eq!(arb.teams()[2], ("Carol".to_string(), 2));
+
+        arb.set_team("Carol", None);
+        assert!(arb.scores().contains(&("Carol".to_string(), 6)));
+    }
+
+    #[test]
+    fn test_away_player_keeps_points_but_cannot_claim() {
+        let mut arb = RoundArbitrator::new(test_letters(), &test_players());
+        arb.try_claim("cat", "Alice");
+        arb.set_away("Alice", true);
+
+        assert_eq!(arb.try_claim("dogs", "Alice"), ClaimResult::Away);
+        assert_eq!(arb.player_score("Alice"), 5);
+        // Their words stay theirs
+        assert_eq!(arb.try_claim("cat", "Bob"), ClaimResult::AlreadyClaimed { by: "Alice".to_string() });
+
+        arb.set_away("Alice", false);
+        assert!(matches!(arb.try_claim("dogs", "Alice"), ClaimResult::Accepted { .. }));
+        assert_eq!(arb.player_score("Alice"), 12);
+    }
+
+    #[test]
+    fn test_reject_penalty_floors_at_zero() {
+        let mut arb = RoundArbitrator::new(test_letters(), &test_players());
+        arb.set_reject_penalty(2);
+        arb.try_claim("tan", "Alice");
+
+        assert_eq!(arb.try_claim("tacd", "Alice"), ClaimResult::NotInDictionary);
+        assert_eq!(arb.player_score("Alice"), 1);
+        assert!(matches!(arb.try_claim("zap", "Alice"), ClaimResult::InvalidLetters { .. }));
+        assert_eq!(arb.player_score("Alice"), 0, "Penalties stop at zero");
+        assert!(matches!(arb.try_claim("zap", "Bob"), ClaimResult::InvalidLetters { .. }));
+        assert_eq!(arb.player_score("Bob"), 0);
+
+        // Losing a race for a word isn't a bad guess
+        arb.try_claim("dogs", "Bob");
+        assert!(matches!(arb.try_claim("dogs", "Alice"), ClaimResult::AlreadyClaimed { .. }));
+        assert_eq!(arb.player_score("Bob"), 7);
+        assert_eq!(arb.raw_scores()[1], ("Alice".to_string(), 3));
+    }
 }
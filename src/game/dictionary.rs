@@ -3,29 +3,328 @@
 //!
 //! Embeds SCOWL American size-60 wordlist at build time.
 //! Provides O(1) hash set lookup with case-insensitive matching.
+//!
+//! Groups can extend the list at runtime with "house words" (in-jokes,
+//! regional words). These live alongside the embedded list and count as
+//! valid everywhere the dictionary is consulted.
+//!
+//! The embedded list is parsed once, into a shared `Dictionary` that every
+//! round's arbitrator holds on to, so starting a round never re-parses it.
+//!
+//! A fingerprint of the language, word list and house words lets players
+//! spot a host whose dictionary differs from theirs before joining.
+//!
+//! Lists for other rack languages are read from the data directory (see
+//! `Language::word_list_file`) the first time a host picks the language,
+//! and kept for the rest of the process.
+//!
+//! Definitions are optional: `definitions.txt` in the data directory, one
+//! `word<TAB>definition` per line, is read at startup if it's there. Without
+//! it every lookup just comes back empty.
 
+use super::Language;
 use once_cell::sync::Lazy;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 
 /// Embedded wordlist (SCOWL American size-60, ~90K words)
 /// Words are lowercase, alphabetic only, one per line
 static WORDS_DATA: &str = include_str!("../../data/words.txt");
 
-/// Pre-built hash set for O(1) word lookup
-static DICTIONARY: Lazy<HashSet<&'static str>> = Lazy::new(|| {
-    WORDS_DATA.lines().collect()
-});
+/// The embedded list, parsed on first use and shared from then on
+static SHARED: Lazy<Arc<Dictionary>> = Lazy::new(|| Arc::new(Dictionary::embedded()));
+
+/// Language of the embedded wordlist
+pub const LANGUAGE: &str = "en-US";
+
+/// Word lists loaded for other languages, by language
+static LOADED: Lazy<Mutex<HashMap<Language, Arc<Dictionary>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Name of the definitions file inside the data directory
+pub const DEFINITIONS_FILE: &str = "definitions.txt";
+
+/// Definitions loaded from `DEFINITIONS_FILE`, by lowercase word
+///
+/// Definitions are kept for the life of the process, so lookups can hand
+/// out `&'static str`s.
+static DEFINITIONS: Lazy<RwLock<HashMap<String, &'static str>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Words added at runtime on top of the embedded list (lowercase)
+static HOUSE_WORDS: Lazy<RwLock<HashSet<String>>> = Lazy::new(|| RwLock::new(HashSet::new()));
+
+/// A parsed word list
+///
+/// Cheap to share: hand out clones of the `Arc` from `shared()` rather than
+/// parsing another.
+#[derive(Debug)]
+pub struct Dictionary {
+    /// Hash set for O(1) word lookup (lowercase)
+    words: HashSet<&'static str>,
+    /// Language the words are in
+    language: Language,
+    /// Fingerprint of the language and list, before house words
+    base_fingerprint: OnceLock<u64>,
+}
+
+impl Dictionary {
+    /// Parse the embedded wordlist
+    fn embedded() -> Self {
+        Self {
+            words: WORDS_DATA.lines().collect(),
+            language: Language::English,
+            base_fingerprint: OnceLock::new(),
+        }
+    }
+
+    /// Parse a word list (one word per line, any case)
+    ///
+    /// The text is kept for the life of the process, as the embedded list
+    /// is, so only parse each list once.
+    pub(crate) fn parse(text: &str, language: Language) -> Self {
+        let text: &'static str = Box::leak(text.to_lowercase().into_boxed_str());
+        Self {
+            words: text.lines().map(str::trim).filter(|w| !w.is_empty()).collect(),
+            language,
+            base_fingerprint: OnceLock::new(),
+        }
+    }
+
+    /// Language the words are in
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Check if a word is in this list or is a house word.
+    /// Case-insensitive: input is converted to lowercase before lookup.
+    pub fn contains(&self, word: &str) -> bool {
+        let lower = word.to_lowercase();
+        self.words.contains(lower.as_str())
+            || HOUSE_WORDS.read().is_ok_and(|house| house.contains(&lower))
+    }
+
+    /// Iterate over every word in the list (lowercase)
+    pub fn words(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.words.iter().copied()
+    }
+
+    /// Fingerprint of this list in its language, plus the house words
+    ///
+    /// The list is hashed the first time and remembered, so this is cheap
+    /// enough to call on every poll.
+    pub fn fingerprint(&self) -> u64 {
+        let base = self.base_fingerprint.get_or_init(|| {
+            // The embedded list keeps the tag older builds advertised
+            let tag = match self.language {
+                Language::English => LANGUAGE,
+                other => other.code(),
+            };
+            hash_words(tag, self.words())
+        });
+        hash_house_words(*base, &house_words())
+    }
+
+    /// Number of words in the list
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Whether the list has no words
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// The shared dictionary, parsed the first time it's asked for
+pub fn shared() -> Arc<Dictionary> {
+    Arc::clone(&SHARED)
+}
+
+/// The dictionary for `language`, loading its word list from `data_dir`
+/// the first time it's asked for
+///
+/// English is always the embedded list. Fails if the list is missing,
+/// unreadable or empty.
+pub fn for_language(language: Language, data_dir: &Path) -> Result<Arc<Dictionary>, String> {
+    let Some(file) = language.word_list_file() else {
+        return Ok(shared());
+    };
+    let mut loaded = LOADED.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(dictionary) = loaded.get(&language) {
+        return Ok(Arc::clone(dictionary));
+    }
+    let path = data_dir.join(&file);
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("No {} word list ({}): {}", language.label(), path.display(), e))?;
+    let dictionary = Arc::new(Dictionary::parse(&text, language));
+    if dictionary.is_empty() {
+        return Err(format!("{} is empty", path.display()));
+    }
+    loaded.insert(language, Arc::clone(&dictionary));
+    Ok(dictionary)
+}
 
 /// Check if a word is valid in the dictionary.
 /// Case-insensitive: input is converted to lowercase before lookup.
 pub fn is_valid_word(word: &str) -> bool {
-    let lower = word.to_lowercase();
-    DICTIONARY.contains(lower.as_str())
+    SHARED.contains(word)
+}
+
+/// Normalize a candidate house word, or `None` if it can't be one.
+/// House words must be non-empty and ASCII letters only.
+pub fn normalize_house_word(word: &str) -> Option<String> {
+    let word = word.trim();
+    if word.is_empty() || !word.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some(word.to_ascii_lowercase())
+}
+
+/// Add a house word. Returns false if the word isn't usable or is
+/// already a house word.
+pub fn add_house_word(word: &str) -> bool {
+    let Some(word) = normalize_house_word(word) else {
+        return false;
+    };
+    HOUSE_WORDS.write().is_ok_and(|mut house| house.insert(word))
+}
+
+/// Remove a house word. Returns false if it wasn't one.
+pub fn remove_house_word(word: &str) -> bool {
+    let lower = word.trim().to_ascii_lowercase();
+    HOUSE_WORDS.write().is_ok_and(|mut house| house.remove(&lower))
+}
+
+/// Replace all house words (e.g. with the list saved in settings)
+pub fn set_house_words<S: AsRef<str>>(words: &[S]) {
+    if let Ok(mut house) = HOUSE_WORDS.write() {
+        *house = words
+            .iter()
+            .filter_map(|w| normalize_house_word(w.as_ref()))
+            .collect();
+    }
+}
+
+/// Current house words, sorted
+pub fn house_words() -> Vec<String> {
+    let mut words: Vec<String> = HOUSE_WORDS
+        .read()
+        .map(|house| house.iter().cloned().collect())
+        .unwrap_or_default();
+    words.sort();
+    words
+}
+
+/// Parse definitions (`word<TAB>definition` per line)
+///
+/// Words are lowercased. Lines starting with `#`, without a tab, or with
+/// an empty word or definition are skipped; a later line for the same word
+/// replaces an earlier one.
+pub fn parse_definitions(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(word, definition)| (word.trim().to_lowercase(), definition.trim()))
+        .filter(|(word, definition)| !word.is_empty() && !definition.is_empty())
+        .map(|(word, definition)| (word, definition.to_string()))
+        .collect()
+}
+
+/// Load `definitions.txt` from `data_dir`, adding to any already loaded
+///
+/// Returns how many definitions were read. A missing or unreadable file
+/// reads none and leaves lookups as they were.
+pub fn load_definitions(data_dir: &Path) -> usize {
+    let Ok(text) = std::fs::read_to_string(data_dir.join(DEFINITIONS_FILE)) else {
+        return 0;
+    };
+    let parsed = parse_definitions(&text);
+    let count = parsed.len();
+    if let Ok(mut definitions) = DEFINITIONS.write() {
+        for (word, definition) in parsed {
+            definitions.insert(word, Box::leak(definition.into_boxed_str()));
+        }
+    }
+    count
+}
+
+/// Definition of `word` (any case), if one was loaded
+pub fn lookup_definition(word: &str) -> Option<&'static str> {
+    let definitions = DEFINITIONS.read().ok()?;
+    definitions.get(&word.to_lowercase()).copied()
+}
+
+/// Iterate over every word in the dictionary (lowercase)
+pub fn words() -> impl Iterator<Item = &'static str> {
+    SHARED.words.iter().copied()
 }
 
 /// Returns the total number of words in the dictionary
 pub fn word_count() -> usize {
-    DICTIONARY.len()
+    SHARED.len()
+}
+
+/// Fingerprint of the shared (embedded) dictionary
+///
+/// Covers the language, the embedded list and the house words. Two
+/// players with equal fingerprints validate claims the same way. A lobby
+/// playing in another language advertises `Dictionary::fingerprint`.
+pub fn fingerprint() -> u64 {
+    SHARED.fingerprint()
+}
+
+/// Fingerprint of an arbitrary language, word list and house words
+pub fn fingerprint_of<'a>(
+    language: &str,
+    words: impl IntoIterator<Item = &'a str>,
+    house_words: &[String],
+) -> u64 {
+    let mut house_words = house_words.to_vec();
+    house_words.sort();
+    hash_house_words(hash_words(language, words), &house_words)
+}
+
+/// FNV-1a offset basis
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a prime
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Feed bytes into an FNV-1a hash
+///
+/// FNV rather than `DefaultHasher`, whose output may change between Rust
+/// releases; fingerprints are compared across builds.
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Hash a language and word list, independent of the list's order
+fn hash_words<'a>(language: &str, words: impl IntoIterator<Item = &'a str>) -> u64 {
+    let mut words: Vec<&str> = words.into_iter().collect();
+    words.sort_unstable();
+    words.dedup();
+    let mut hash = fnv1a(FNV_OFFSET, language.as_bytes());
+    for word in words {
+        hash = fnv1a(hash, b"\n");
+        hash = fnv1a(hash, word.as_bytes());
+    }
+    hash
+}
+
+/// Extend a word list hash with sorted house words
+fn hash_house_words(mut hash: u64, house_words: &[String]) -> u64 {
+    hash = fnv1a(hash, b"\0");
+    for word in house_words {
+        hash = fnv1a(hash, b"\n");
+        hash = fnv1a(hash, word.as_bytes());
+    }
+    hash
 }
 
 #[cfg(test)]
@@ -61,6 +360,110 @@ mod tests {
         assert!(count < 100000, "Expected <100K words, got {}", count);
     }
 
+    #[test]
+    fn test_house_word_validates_until_removed() {
+        // Unique word so tests running in parallel don't interfere
+        assert!(!is_valid_word("blamzorp"));
+
+        assert!(add_house_word("Blamzorp"));
+        assert!(!add_house_word("blamzorp"), "Adding twice is a no-op");
+        assert!(is_valid_word("BLAMZORP"));
+        assert!(house_words().contains(&"blamzorp".to_string()));
+
+        assert!(remove_house_word("blamzorp"));
+        assert!(!is_valid_word("blamzorp"));
+        assert!(!remove_house_word("blamzorp"));
+    }
+
+    #[test]
+    fn test_house_word_must_be_letters() {
+        assert!(!add_house_word(""));
+        assert!(!add_house_word("two words"));
+        assert!(!add_house_word("l33t"));
+        assert_eq!(normalize_house_word("  Yeet "), Some("yeet".to_string()));
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_word_set() {
+        let base = fingerprint_of(LANGUAGE, ["cat", "dog"], &[]);
+        assert_eq!(base, fingerprint_of(LANGUAGE, ["dog", "cat"], &[]));
+        assert_ne!(base, fingerprint_of(LANGUAGE, ["cat", "dot"], &[]));
+        assert_ne!(base, fingerprint_of(LANGUAGE, ["cat", "dog", "eel"], &[]));
+        assert_ne!(base, fingerprint_of("fr-FR", ["cat", "dog"], &[]));
+        // A house word isn't the same as a listed word
+        assert_ne!(
+            fingerprint_of(LANGUAGE, ["cat", "dog"], &["yeet".to_string()]),
+            fingerprint_of(LANGUAGE, ["cat", "dog", "yeet"], &[])
+        );
+        assert_eq!(
+            fingerprint_of(LANGUAGE, ["cat"], &["b".to_string(), "a".to_string()]),
+            fingerprint_of(LANGUAGE, ["cat"], &["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_dictionary_fingerprint_covers_its_language() {
+        let spanish = Dictionary::parse("gato\nperro\n", Language::Spanish);
+        let english = Dictionary::parse("gato\nperro\n", Language::English);
+        assert_eq!(spanish.fingerprint(), spanish.fingerprint());
+        assert_ne!(spanish.fingerprint(), english.fingerprint());
+        assert_ne!(spanish.fingerprint(), fingerprint());
+    }
+
+    #[test]
+    fn test_parse_definitions_skips_bad_lines() {
+        let definitions =
+            parse_definitions("# comment\nCat\ta small feline\nno tab here\ndog\t\n\tstray\n");
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions.get("cat").map(String::as_str), Some("a small feline"));
+    }
+
+    #[test]
+    fn test_definition_looked_up_once_loaded() {
+        let dir = std::env::temp_dir().join(format!("blam-defs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(DEFINITIONS_FILE), "oxo\ta test-only word\n").unwrap();
+
+        assert_eq!(load_definitions(&dir), 1);
+        assert_eq!(lookup_definition("OXO"), Some("a test-only word"));
+        assert_eq!(lookup_definition("oxoxo"), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_missing_definitions_file_reads_nothing() {
+        let dir = std::env::temp_dir().join(format!("blam-no-defs-{}", std::process::id()));
+        assert_eq!(load_definitions(&dir), 0);
+        // Lookups still work, they just find nothing
+        assert_eq!(lookup_definition("zyzzyva"), None);
+    }
+
+    #[test]
+    fn test_language_list_loaded_from_data_dir() {
+        let dir = std::env::temp_dir().join(format!("blam-dict-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(for_language(Language::French, &dir).is_err());
+        assert_eq!(for_language(Language::English, &dir).unwrap().language(), Language::English);
+
+        std::fs::write(dir.join("words-fr.txt"), "Chat\n\nCHIEN\n").unwrap();
+        let french = for_language(Language::French, &dir).unwrap();
+        assert_eq!(french.language(), Language::French);
+        assert_eq!(french.len(), 2);
+        assert!(french.contains("chat"));
+        assert!(french.contains("Chien"));
+        assert!(!french.contains("cat"));
+        // Loaded once and shared from then on
+        assert!(Arc::ptr_eq(&french, &for_language(Language::French, &dir).unwrap()));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_two_letter_words() {
+        for word in ["aa", "ox", "at", "be", "go", "hi", "of", "up"] {
+            assert!(is_valid_word(word), "{} should be in the dictionary", word);
+        }
+    }
+
     #[test]
     fn test_three_letter_words() {
         // Common 3-letter words should be in dictionary
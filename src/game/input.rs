@@ -0,0 +1,170 @@
+#![allow(dead_code)]
+//! Keyboard input normalization
+//!
+//! Typed characters are mapped onto the dictionary's alphabet before they
+//! reach the input line. Players on AZERTY, Dvorak or international layouts
+//! can produce accented letters for keys that a QWERTY player would type
+//! plain; folding those to their base letter keeps them playable instead of
+//! silently dropping the keystroke.
+
+use super::Language;
+use std::collections::HashMap;
+
+/// Accented Latin letters and the base letter they fold to
+const LATIN_FOLDS: [(&str, char); 13] = [
+    ("ÀÁÂÃÄÅĀĂĄ", 'A'),
+    ("ÇĆĈĊČ", 'C'),
+    ("ĎĐ", 'D'),
+    ("ÈÉÊËĒĔĖĘĚ", 'E'),
+    ("ĜĞĠĢ", 'G'),
+    ("ÌÍÎÏĨĪĬĮİ", 'I'),
+    ("ŁĹĻĽ", 'L'),
+    ("ÑŃŅŇ", 'N'),
+    ("ÒÓÔÕÖØŌŎŐ", 'O'),
+    ("ŔŖŘ", 'R'),
+    ("ŚŜŞŠ", 'S'),
+    ("ÙÚÛÜŨŪŬŮŰŲ", 'U'),
+    ("ÝŸŶ", 'Y'),
+];
+
+/// Maps typed characters onto a dictionary alphabet
+#[derive(Debug, Clone)]
+pub struct InputMap {
+    /// Letters the dictionary uses (uppercase)
+    alphabet: Vec<char>,
+    /// Extra characters that stand in for a letter of the alphabet
+    folds: HashMap<char, char>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        Self::ascii()
+    }
+}
+
+impl InputMap {
+    /// Create a map for the given alphabet with no folds
+    pub fn new(alphabet: impl IntoIterator<Item = char>) -> Self {
+        Self {
+            alphabet: alphabet.into_iter().flat_map(char::to_uppercase).collect(),
+            folds: HashMap::new(),
+        }
+    }
+
+    /// Map for the ASCII-only English dictionary
+    ///
+    /// Accented Latin letters fold to their base letter.
+    pub fn ascii() -> Self {
+        let mut map = Self::new('A'..='Z');
+        for (accented, base) in LATIN_FOLDS {
+            for c in accented.chars() {
+                map = map.with_fold(c, base);
+            }
+        }
+        map
+    }
+
+    /// Map for a rack language
+    ///
+    /// Like `ascii`, but letters the language has of its own (Spanish Ñ)
+    /// are kept rather than folded.
+    pub fn for_language(language: Language) -> Self {
+        let mut map = Self::ascii();
+        for letter in language.alphabet().filter(|c| !c.is_ascii()) {
+            map.folds.remove(&letter);
+            map.alphabet.push(letter);
+        }
+        map
+    }
+
+    /// Add a mapping from a typed character to a letter of the alphabet
+    pub fn with_fold(mut self, from: char, to: char) -> Self {
+        if let (Some(from), Some(to)) = (single_upper(from), single_upper(to)) {
+            self.folds.insert(from, to);
+        }
+        self
+    }
+
+    /// Translate a typed character into a letter of the alphabet
+    ///
+    /// Returns `None` for characters that can't be played (digits,
+    /// punctuation, letters from other scripts).
+    pub fn normalize(&self, c: char) -> Option<char> {
+        let upper = single_upper(c)?;
+        let letter = if self.alphabet.contains(&upper) {
+            upper
+        } else {
+            *self.folds.get(&upper)?
+        };
+        self.alphabet.contains(&letter).then_some(letter)
+    }
+}
+
+/// Uppercase a character, if it uppercases to exactly one character
+fn single_upper(c: char) -> Option<char> {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => Some(u),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_letters_uppercased() {
+        let map = InputMap::ascii();
+        assert_eq!(map.normalize('a'), Some('A'));
+        assert_eq!(map.normalize('Z'), Some('Z'));
+    }
+
+    #[test]
+    fn test_accented_letter_maps_to_base() {
+        let map = InputMap::ascii();
+        assert_eq!(map.normalize('é'), Some('E'));
+        assert_eq!(map.normalize('À'), Some('A'));
+        assert_eq!(map.normalize('ç'), Some('C'));
+        assert_eq!(map.normalize('ü'), Some('U'));
+    }
+
+    #[test]
+    fn test_non_letters_rejected() {
+        let map = InputMap::ascii();
+        assert_eq!(map.normalize('1'), None);
+        assert_eq!(map.normalize('&'), None);
+        assert_eq!(map.normalize(' '), None);
+        assert_eq!(map.normalize('ж'), None);
+        // ß uppercases to two letters, so it has no single-letter mapping
+        assert_eq!(map.normalize('ß'), None);
+    }
+
+    #[test]
+    fn test_configured_fold() {
+        let map = InputMap::new('A'..='Z').with_fold('ø', 'o');
+        assert_eq!(map.normalize('ø'), Some('O'));
+        // Unconfigured accents aren't folded
+        assert_eq!(map.normalize('é'), None);
+    }
+
+    #[test]
+    fn test_fold_outside_alphabet_ignored() {
+        let map = InputMap::new(['A', 'B']).with_fold('é', 'e');
+        assert_eq!(map.normalize('é'), None);
+    }
+
+    #[test]
+    fn test_spanish_keeps_enye() {
+        let map = InputMap::for_language(Language::Spanish);
+        assert_eq!(map.normalize('ñ'), Some('Ñ'));
+        assert_eq!(map.normalize('é'), Some('E'));
+        assert_eq!(InputMap::for_language(Language::German).normalize('ñ'), Some('N'));
+    }
+
+    #[test]
+    fn test_accented_letter_kept_when_in_alphabet() {
+        let map = InputMap::new(('A'..='Z').chain(['É']));
+        assert_eq!(map.normalize('é'), Some('É'));
+    }
+}
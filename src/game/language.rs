@@ -0,0 +1,207 @@
+#![allow(dead_code)]
+//! Rack languages: which letters a rack is drawn from, and how often
+//!
+//! English racks use the built-in word list. Other languages need their
+//! own list, dropped into the data directory as `words-<code>.txt` (one
+//! word per line, e.g. `words-es.txt`); a host can only pick a language
+//! whose list is there.
+//!
+//! Accented letters are folded to their base letter, as players type
+//! them, except where the language treats them as letters of their own
+//! (Spanish Ñ).
+
+use super::LETTER_WEIGHTS;
+
+/// Spanish letter frequencies (percentages * 100), Ñ included
+const SPANISH_WEIGHTS: [(char, u32); 27] = [
+    ('A', 1253),
+    ('B', 142),
+    ('C', 468),
+    ('D', 586),
+    ('E', 1323),
+    ('F', 69),
+    ('G', 101),
+    ('H', 70),
+    ('I', 625),
+    ('J', 44),
+    ('K', 2),
+    ('L', 497),
+    ('M', 315),
+    ('N', 671),
+    ('Ñ', 31),
+    ('O', 868),
+    ('P', 251),
+    ('Q', 88),
+    ('R', 687),
+    ('S', 798),
+    ('T', 463),
+    ('U', 393),
+    ('V', 90),
+    ('W', 1),
+    ('X', 22),
+    ('Y', 90),
+    ('Z', 52),
+];
+
+/// German letter frequencies (percentages * 100), umlauts folded
+///
+/// Heavier on N, H, G and Z than English, as German text is.
+const GERMAN_WEIGHTS: [(char, u32); 26] = [
+    ('A', 651),
+    ('B', 189),
+    ('C', 306),
+    ('D', 508),
+    ('E', 1740),
+    ('F', 166),
+    ('G', 301),
+    ('H', 476),
+    ('I', 755),
+    ('J', 27),
+    ('K', 121),
+    ('L', 344),
+    ('M', 253),
+    ('N', 978),
+    ('O', 251),
+    ('P', 79),
+    ('Q', 2),
+    ('R', 700),
+    ('S', 727),
+    ('T', 615),
+    ('U', 435),
+    ('V', 67),
+    ('W', 189),
+    ('X', 3),
+    ('Y', 4),
+    ('Z', 113),
+];
+
+/// French letter frequencies (percentages * 100), accents folded
+const FRENCH_WEIGHTS: [(char, u32); 26] = [
+    ('A', 764),
+    ('B', 90),
+    ('C', 326),
+    ('D', 367),
+    ('E', 1746),
+    ('F', 107),
+    ('G', 87),
+    ('H', 74),
+    ('I', 753),
+    ('J', 61),
+    ('K', 7),
+    ('L', 546),
+    ('M', 297),
+    ('N', 710),
+    ('O', 580),
+    ('P', 252),
+    ('Q', 136),
+    ('R', 669),
+    ('S', 795),
+    ('T', 724),
+    ('U', 631),
+    ('V', 184),
+    ('W', 5),
+    ('X', 43),
+    ('Y', 13),
+    ('Z', 33),
+];
+
+/// Language a rack is dealt (and its claims checked) in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    German,
+    French,
+}
+
+impl Language {
+    /// Every language, in the order the host cycles through them
+    pub fn all() -> [Language; 4] {
+        [Language::English, Language::Spanish, Language::German, Language::French]
+    }
+
+    /// The language after this one (wrapping)
+    pub fn next(self) -> Self {
+        let all = Self::all();
+        let idx = all.iter().position(|l| *l == self).unwrap_or(0);
+        all[(idx + 1) % all.len()]
+    }
+
+    /// Two-letter code, as sent to peers and used in word list names
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::German => "de",
+            Language::French => "fr",
+        }
+    }
+
+    /// Language for a two-letter code
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::all().into_iter().find(|l| l.code() == code)
+    }
+
+    /// Name for the lobby info line
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+            Language::German => "Deutsch",
+            Language::French => "Français",
+        }
+    }
+
+    /// Letters racks are drawn from, with their weights
+    pub fn letter_weights(&self) -> &'static [(char, u32)] {
+        match self {
+            Language::English => &LETTER_WEIGHTS,
+            Language::Spanish => &SPANISH_WEIGHTS,
+            Language::German => &GERMAN_WEIGHTS,
+            Language::French => &FRENCH_WEIGHTS,
+        }
+    }
+
+    /// Letters of this language's alphabet (uppercase)
+    pub fn alphabet(&self) -> impl Iterator<Item = char> {
+        self.letter_weights().iter().map(|(c, _)| *c)
+    }
+
+    /// File in the data directory holding the word list, or `None` for the
+    /// built-in English list
+    pub fn word_list_file(&self) -> Option<String> {
+        match self {
+            Language::English => None,
+            _ => Some(format!("words-{}.txt", self.code())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spanish_weights_sum_to_ten_thousand() {
+        let total: u32 = Language::Spanish.letter_weights().iter().map(|(_, w)| w).sum();
+        assert_eq!(total, 10_000);
+        assert!(Language::Spanish.alphabet().any(|c| c == 'Ñ'));
+        for language in [Language::German, Language::French] {
+            let total: u32 = language.letter_weights().iter().map(|(_, w)| w).sum();
+            assert_eq!(total, 10_000, "{:?}", language);
+            assert!(language.alphabet().all(|c| c.is_ascii_uppercase()));
+        }
+    }
+
+    #[test]
+    fn test_codes_round_trip() {
+        for language in Language::all() {
+            assert_eq!(Language::from_code(language.code()), Some(language));
+        }
+        assert_eq!(Language::from_code("xx"), None);
+        assert_eq!(Language::French.next(), Language::English);
+        assert_eq!(Language::English.word_list_file(), None);
+        assert_eq!(Language::Spanish.word_list_file().as_deref(), Some("words-es.txt"));
+    }
+}
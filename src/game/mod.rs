@@ -3,11 +3,27 @@
 
 pub mod arbitrator;
 pub mod dictionary;
+pub mod input;
+pub mod language;
+pub mod scoring;
+pub mod seed;
+pub mod solver;
 pub mod validation;
 
 use rand::distr::weighted::WeightedIndex;
 use rand::prelude::*;
 
+pub use language::Language;
+
+/// Maximum number of players in a lobby (and so in one match)
+pub const MAX_PLAYERS: usize = 12;
+
+/// A blank tile: stands in for any letter, but scores nothing
+pub const BLANK: char = '*';
+
+/// Chance that each blank a configuration allows turns up on a rack
+const BLANK_CHANCE: f64 = 1.0 / 3.0;
+
 /// English letter frequencies (percentages * 100 for integer weights).
 /// Based on standard English text frequency analysis.
 const LETTER_WEIGHTS: [(char, u32); 26] = [
@@ -39,11 +55,271 @@ const LETTER_WEIGHTS: [(char, u32); 26] = [
     ('Z', 7),
 ];
 
+/// Tile counts for a standard bag (Scrabble's distribution, no blanks).
+const BAG_TILE_COUNTS: [(char, u32); 26] = [
+    ('A', 9),
+    ('B', 2),
+    ('C', 2),
+    ('D', 4),
+    ('E', 12),
+    ('F', 2),
+    ('G', 3),
+    ('H', 2),
+    ('I', 9),
+    ('J', 1),
+    ('K', 1),
+    ('L', 4),
+    ('M', 2),
+    ('N', 6),
+    ('O', 8),
+    ('P', 2),
+    ('Q', 1),
+    ('R', 6),
+    ('S', 4),
+    ('T', 6),
+    ('U', 4),
+    ('V', 2),
+    ('W', 2),
+    ('X', 1),
+    ('Y', 2),
+    ('Z', 1),
+];
+
 const VOWELS: [char; 5] = ['A', 'E', 'I', 'O', 'U'];
 const MIN_VOWELS: usize = 2;
 const MIN_RACK_SIZE: usize = 12;
 const MAX_RACK_SIZE: usize = 20;
 
+/// Smallest rack a configuration may ask for
+const RACK_SIZE_FLOOR: usize = 3;
+/// Largest rack a configuration may ask for
+const RACK_SIZE_CEILING: usize = 26;
+
+/// Size and vowel constraints for generating a rack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RackConfig {
+    min_size: usize,
+    max_size: usize,
+    min_vowels: usize,
+    blanks: usize,
+    language: Language,
+}
+
+impl Default for RackConfig {
+    fn default() -> Self {
+        Self {
+            min_size: MIN_RACK_SIZE,
+            max_size: MAX_RACK_SIZE,
+            min_vowels: MIN_VOWELS,
+            blanks: 0,
+            language: Language::English,
+        }
+    }
+}
+
+impl RackConfig {
+    /// Create a rack configuration.
+    ///
+    /// Sizes must satisfy 3 <= min <= max <= 26, and at most half the
+    /// smallest rack may be required to be vowels. Vowels are drawn by
+    /// frequency and the rack rerolled until there are enough, so a higher
+    /// floor could take forever to hit.
+    pub fn new(min_size: usize, max_size: usize, min_vowels: usize) -> Result<Self, String> {
+        if min_size < RACK_SIZE_FLOOR || max_size > RACK_SIZE_CEILING {
+            return Err(format!(
+                "Rack size must be between {} and {}",
+                RACK_SIZE_FLOOR, RACK_SIZE_CEILING
+            ));
+        }
+        if min_size > max_size {
+            return Err(format!("Minimum rack size {} is above maximum {}", min_size, max_size));
+        }
+        if min_vowels > min_size / 2 {
+            return Err(format!(
+                "Can't require {} vowels in a rack of {} letters (at most {})",
+                min_vowels,
+                min_size,
+                min_size / 2
+            ));
+        }
+        Ok(Self {
+            min_size,
+            max_size,
+            min_vowels,
+            blanks: 0,
+            language: Language::English,
+        })
+    }
+
+    /// Allow up to `blanks` blank tiles, each turning up occasionally.
+    ///
+    /// A blank replaces one of the drawn letters, so the rack size stays
+    /// within the configured range.
+    pub fn with_blanks(mut self, blanks: usize) -> Self {
+        self.blanks = blanks.min(self.min_size - self.min_vowels);
+        self
+    }
+
+    /// Draw letters with `language`'s frequencies.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Smallest rack that will be generated.
+    pub fn min_size(&self) -> usize {
+        self.min_size
+    }
+
+    /// Largest rack that will be generated.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Fewest vowels a generated rack will have.
+    pub fn min_vowels(&self) -> usize {
+        self.min_vowels
+    }
+
+    /// Most blank tiles a generated rack can have.
+    pub fn blanks(&self) -> usize {
+        self.blanks
+    }
+
+    /// Language whose letter frequencies are used.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// The rack this configuration deals for `seed`, the same on every
+    /// call and every machine.
+    pub fn rack_for_seed(&self, seed: u64) -> LetterRack {
+        LetterRack::generate_with_config(&mut StdRng::seed_from_u64(seed), self)
+    }
+}
+
+/// Rack size presets the host can pick in the lobby.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RackPreset {
+    /// 12-20 letters
+    #[default]
+    Standard,
+    /// 9-12 letters
+    Tiny,
+    /// 6-8 letters, for quick rounds
+    Blitz,
+    /// 20-26 letters
+    Big,
+}
+
+impl RackPreset {
+    /// Rack configuration for this preset.
+    pub fn config(self) -> RackConfig {
+        match self {
+            RackPreset::Standard => RackConfig::default(),
+            RackPreset::Tiny => RackConfig::new(9, 12, MIN_VOWELS).expect("valid preset"),
+            RackPreset::Blitz => RackConfig::new(6, 8, MIN_VOWELS).expect("valid preset"),
+            RackPreset::Big => RackConfig::new(20, 26, 4).expect("valid preset"),
+        }
+    }
+
+    /// Display name for the lobby screen.
+    pub fn label(self) -> &'static str {
+        match self {
+            RackPreset::Standard => "Standard",
+            RackPreset::Tiny => "Tiny",
+            RackPreset::Blitz => "Blitz",
+            RackPreset::Big => "Big",
+        }
+    }
+
+    /// The preset after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            RackPreset::Standard => RackPreset::Tiny,
+            RackPreset::Tiny => RackPreset::Blitz,
+            RackPreset::Blitz => RackPreset::Big,
+            RackPreset::Big => RackPreset::Standard,
+        }
+    }
+
+    /// The rack this preset deals for `seed`, the same on every call.
+    pub fn rack_for_seed(self, seed: u64) -> LetterRack {
+        self.config().rack_for_seed(seed)
+    }
+}
+
+/// A finite supply of letter tiles, drawn without replacement.
+///
+/// Unlike the weighted draw, a rare letter can't turn up more often than
+/// the bag holds it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileBag {
+    /// Tiles left per letter
+    counts: Vec<(char, u32)>,
+}
+
+impl Default for TileBag {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl TileBag {
+    /// A full bag with the standard tile distribution (98 tiles).
+    pub fn standard() -> Self {
+        Self::with_counts(BAG_TILE_COUNTS)
+    }
+
+    /// A bag holding the given number of tiles per letter.
+    pub fn with_counts(counts: impl IntoIterator<Item = (char, u32)>) -> Self {
+        Self {
+            counts: counts
+                .into_iter()
+                .map(|(c, n)| (c.to_ascii_uppercase(), n))
+                .collect(),
+        }
+    }
+
+    /// Tiles of a letter left in the bag.
+    pub fn count(&self, letter: char) -> u32 {
+        let letter = letter.to_ascii_uppercase();
+        self.counts
+            .iter()
+            .filter(|(c, _)| *c == letter)
+            .map(|(_, n)| n)
+            .sum()
+    }
+
+    /// Tiles left in the bag.
+    pub fn remaining(&self) -> usize {
+        self.counts.iter().map(|(_, n)| *n as usize).sum()
+    }
+
+    /// Check if the bag is empty.
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Take a random tile out of the bag, each tile equally likely.
+    pub fn draw<R: Rng>(&mut self, rng: &mut R) -> Option<char> {
+        let remaining = self.remaining();
+        if remaining == 0 {
+            return None;
+        }
+        let mut pick = rng.random_range(0..remaining);
+        for (letter, count) in &mut self.counts {
+            let count_usize = *count as usize;
+            if pick < count_usize {
+                *count -= 1;
+                return Some(*letter);
+            }
+            pick -= count_usize;
+        }
+        None
+    }
+}
+
 /// A rack of letters for a game round.
 #[derive(Debug, Clone)]
 pub struct LetterRack {
@@ -54,29 +330,57 @@ impl LetterRack {
     /// Generate a new random letter rack with 12-20 letters.
     /// Letters are weighted to English frequency.
     /// Guarantees at least 2 vowels by rerolling if needed.
+    /// Draws from the session RNG, so `BLAM_SEED` replays the same racks.
     pub fn generate() -> Self {
-        Self::generate_with_rng(&mut rand::rng())
+        seed::with_session_rng(Self::generate_with_rng)
     }
 
     /// Generate a letter rack using a specific RNG (for testing/seeding).
     pub fn generate_with_rng<R: Rng>(rng: &mut R) -> Self {
+        Self::generate_with_config(rng, &RackConfig::default())
+    }
+
+    /// Generate a letter rack within the configured size and vowel limits.
+    pub fn generate_with_config<R: Rng>(rng: &mut R, config: &RackConfig) -> Self {
         loop {
-            let rack = Self::generate_once(rng);
-            if rack.vowel_count() >= MIN_VOWELS {
+            let rack = Self::generate_once(rng, config);
+            if rack.vowel_count() >= config.min_vowels {
                 return rack;
             }
-            // Reroll if fewer than 2 vowels
+            // Reroll if too few vowels
         }
     }
 
-    fn generate_once<R: Rng>(rng: &mut R) -> Self {
-        let size = rng.random_range(MIN_RACK_SIZE..=MAX_RACK_SIZE);
+    /// Generate a standard-size letter rack in `language`.
+    pub fn generate_for_language<R: Rng>(language: Language, rng: &mut R) -> Self {
+        Self::generate_with_config(rng, &RackConfig::default().with_language(language))
+    }
 
-        let letters: Vec<char> = LETTER_WEIGHTS.iter().map(|(c, _)| *c).collect();
-        let weights: Vec<u32> = LETTER_WEIGHTS.iter().map(|(_, w)| *w).collect();
+    /// Draw a rack of `size` tiles from a bag, without replacement.
+    ///
+    /// The tiles leave the bag. A nearly empty bag gives a short rack, and
+    /// there's no vowel guarantee: rerolling would mean putting tiles back.
+    pub fn from_bag<R: Rng>(bag: &mut TileBag, size: usize, rng: &mut R) -> Self {
+        Self {
+            letters: (0..size).map_while(|_| bag.draw(rng)).collect(),
+        }
+    }
+
+    fn generate_once<R: Rng>(rng: &mut R, config: &RackConfig) -> Self {
+        let size = rng.random_range(config.min_size..=config.max_size);
+
+        let table = config.language.letter_weights();
+        let letters: Vec<char> = table.iter().map(|(c, _)| *c).collect();
+        let weights: Vec<u32> = table.iter().map(|(_, w)| *w).collect();
         let dist = WeightedIndex::new(&weights).expect("valid weights");
 
-        let rack_letters: Vec<char> = (0..size).map(|_| letters[dist.sample(rng)]).collect();
+        let mut rack_letters: Vec<char> = (0..size).map(|_| letters[dist.sample(rng)]).collect();
+        for _ in 0..config.blanks {
+            if rng.random_bool(BLANK_CHANCE) {
+                let pos = rng.random_range(0..rack_letters.len());
+                rack_letters[pos] = BLANK;
+            }
+        }
 
         Self {
             letters: rack_letters,
@@ -88,6 +392,11 @@ impl LetterRack {
         self.letters.iter().filter(|c| VOWELS.contains(c)).count()
     }
 
+    /// Count the blank tiles in the rack.
+    pub fn blank_count(&self) -> usize {
+        self.letters.iter().filter(|c| **c == BLANK).count()
+    }
+
     /// Get the letters in the rack.
     pub fn letters(&self) -> &[char] {
         &self.letters
@@ -158,6 +467,28 @@ mod tests {
         assert_eq!(rack1.as_string(), rack2.as_string());
     }
 
+    #[test]
+    fn test_seed_deals_same_rack_on_independent_rngs() {
+        let configs = [
+            RackConfig::default(),
+            RackPreset::Big.config().with_blanks(2),
+            RackPreset::Tiny.config().with_language(Language::Spanish),
+        ];
+        for config in configs {
+            for seed in [0, 7, u64::MAX] {
+                // What the host deals and what a peer rebuilds from the seed
+                let mut rng = StdRng::seed_from_u64(seed);
+                let host = LetterRack::generate_with_config(&mut rng, &config);
+                let peer = config.rack_for_seed(seed);
+                assert_eq!(host.letters(), peer.letters());
+            }
+        }
+        assert_ne!(
+            RackConfig::default().rack_for_seed(1).letters(),
+            RackConfig::default().rack_for_seed(2).letters()
+        );
+    }
+
     #[test]
     fn test_rack_as_string() {
         use rand::SeedableRng;
@@ -217,6 +548,82 @@ mod tests {
         assert_eq!(rack.len(), rack.as_string().len());
     }
 
+    #[test]
+    fn test_configured_tiny_rack_in_range_with_vowels() {
+        use rand::SeedableRng;
+        let config = RackConfig::new(9, 12, 3).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        for _ in 0..200 {
+            let rack = LetterRack::generate_with_config(&mut rng, &config);
+            assert!((9..=12).contains(&rack.len()), "Rack {} out of range", rack.as_string());
+            assert!(rack.vowel_count() >= 3, "Rack {} has too few vowels", rack.as_string());
+        }
+    }
+
+    #[test]
+    fn test_rack_config_validation() {
+        assert!(RackConfig::new(12, 9, 2).is_err());
+        assert!(RackConfig::new(2, 9, 1).is_err());
+        assert!(RackConfig::new(20, 27, 2).is_err());
+        assert!(RackConfig::new(9, 12, 10).is_err());
+        assert!(RackConfig::new(9, 9, 4).is_ok());
+        assert!(RackConfig::new(9, 9, 5).is_err());
+        assert!(RackConfig::new(26, 26, 26).is_err());
+    }
+
+    #[test]
+    fn test_spanish_racks_can_deal_enye() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let racks: Vec<LetterRack> =
+            (0..500).map(|_| LetterRack::generate_for_language(Language::Spanish, &mut rng)).collect();
+        assert!(racks.iter().any(|r| r.letters().contains(&'Ñ')), "No Ñ in 500 Spanish racks");
+        assert!(racks.iter().all(|r| (MIN_RACK_SIZE..=MAX_RACK_SIZE).contains(&r.len())));
+
+        let english = LetterRack::generate_for_language(Language::English, &mut rng);
+        assert!(english.letters().iter().all(|c| c.is_ascii_uppercase()));
+    }
+
+    #[test]
+    fn test_blitz_racks_stay_in_range() {
+        use rand::SeedableRng;
+        let config = RackPreset::Blitz.config();
+        assert_eq!((config.min_size(), config.max_size()), (6, 8));
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        for _ in 0..500 {
+            let rack = LetterRack::generate_with_config(&mut rng, &config);
+            assert!((6..=8).contains(&rack.len()), "Rack {} out of range", rack.as_string());
+            assert!(rack.vowel_count() >= MIN_VOWELS);
+        }
+    }
+
+    #[test]
+    fn test_unreachable_vowel_floor_rejected_up_front() {
+        // Rerolling could never find 7 vowels in a 6-letter rack
+        let err = RackConfig::new(6, 8, 7).unwrap_err();
+        assert!(err.contains("7 vowels"), "unexpected error: {}", err);
+    }
+
+    #[test]
+    fn test_default_config_matches_constants() {
+        let config = RackConfig::default();
+        assert_eq!(config.min_size(), MIN_RACK_SIZE);
+        assert_eq!(config.max_size(), MAX_RACK_SIZE);
+        assert_eq!(config.min_vowels(), MIN_VOWELS);
+        assert_eq!(RackPreset::default().config(), config);
+    }
+
+    #[test]
+    fn test_rack_presets_cycle() {
+        let mut preset = RackPreset::Standard;
+        for _ in 0..4 {
+            // Every preset must be a valid configuration
+            let _ = preset.config();
+            preset = preset.next();
+        }
+        assert_eq!(preset, RackPreset::Standard);
+    }
+
     #[test]
     fn test_rack_clone() {
         let rack = LetterRack::generate();
@@ -224,4 +631,43 @@ mod tests {
         assert_eq!(rack.as_string(), cloned.as_string());
         assert_eq!(rack.len(), cloned.len());
     }
+
+    #[test]
+    fn test_bag_never_exceeds_tile_counts() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let mut bag = TileBag::standard();
+        assert_eq!(bag.remaining(), 98);
+
+        let mut drawn: std::collections::HashMap<char, u32> = std::collections::HashMap::new();
+        while let Some(letter) = bag.draw(&mut rng) {
+            *drawn.entry(letter).or_default() += 1;
+        }
+        assert!(bag.is_empty());
+        for (letter, count) in BAG_TILE_COUNTS {
+            assert_eq!(drawn.get(&letter).copied().unwrap_or(0), count, "letter {}", letter);
+        }
+    }
+
+    #[test]
+    fn test_rack_from_bag_draws_without_replacement() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        let mut bag = TileBag::with_counts([('a', 2), ('Q', 1)]);
+
+        let rack = LetterRack::from_bag(&mut bag, 2, &mut rng);
+        assert_eq!(rack.len(), 2);
+        assert_eq!(bag.remaining(), 1);
+
+        // Only one tile is left, so asking for more gives a short rack
+        let rest = LetterRack::from_bag(&mut bag, 5, &mut rng);
+        assert_eq!(rest.len(), 1);
+        assert!(bag.is_empty());
+
+        let mut all: Vec<char> = rack.letters().iter().chain(rest.letters()).copied().collect();
+        all.sort_unstable();
+        assert_eq!(all, vec!['A', 'A', 'Q']);
+    }
 }
@@ -0,0 +1,135 @@
+#![allow(dead_code)]
+//! Word scoring shared by solo play, the host arbitrator and the UI
+//!
+//! Keeping a single scoring function guarantees the points previewed while
+//! typing match what the arbitrator actually awards.
+//!
+//! Letters are worth more the rarer they are, Scrabble-style: each letter's
+//! value comes from its weight in the rack generator's frequency table, so
+//! a word built around Q or Z beats one padded out with E's. A blank tile
+//! is worth nothing, whichever letter it stands in for.
+
+use super::validation::blanked_letters;
+use super::{Language, BLANK, LETTER_WEIGHTS};
+
+/// Points each letter is worth, A to Z
+pub const LETTER_POINTS: [(char, u32); 26] = letter_points_table();
+
+/// Points for a letter no rack language deals (house words may use one)
+pub const DEFAULT_LETTER_POINTS: u32 = 1;
+
+/// Points for a letter drawn `weight` times in 10,000
+const fn points_for_weight(weight: u32) -> u32 {
+    match weight {
+        600.. => 1,
+        300..=599 => 2,
+        200..=299 => 3,
+        100..=199 => 4,
+        50..=99 => 5,
+        15..=49 => 8,
+        _ => 10,
+    }
+}
+
+const fn letter_points_table() -> [(char, u32); 26] {
+    let mut table = [(' ', 0); 26];
+    let mut i = 0;
+    while i < table.len() {
+        let (letter, weight) = LETTER_WEIGHTS[i];
+        table[i] = (letter, points_for_weight(weight));
+        i += 1;
+    }
+    table
+}
+
+/// Points a single letter is worth (either case; 0 for a blank)
+///
+/// Letters outside A-Z, like Spanish Ñ, are valued by their weight in the
+/// language that deals them.
+pub fn letter_points(letter: char) -> u32 {
+    if letter == BLANK {
+        return 0;
+    }
+    let upper = letter.to_uppercase().next().unwrap_or(letter);
+    if let Some((_, points)) = LETTER_POINTS.iter().find(|(l, _)| *l == upper) {
+        return *points;
+    }
+    Language::all()
+        .iter()
+        .flat_map(|language| language.letter_weights())
+        .find(|(l, _)| *l == upper)
+        .map_or(DEFAULT_LETTER_POINTS, |(_, weight)| points_for_weight(*weight))
+}
+
+/// Score a word that has already passed validation: the sum of its
+/// letters' points
+pub fn score_word(word: &str) -> u32 {
+    word.chars().map(letter_points).sum()
+}
+
+/// Score a validated word as spelled from `rack`
+///
+/// Letters a blank stood in for score nothing.
+pub fn score_on_rack(word: &str, rack: &[char]) -> u32 {
+    let word = word.to_uppercase();
+    let blanked: u32 = blanked_letters(&word, rack).into_iter().map(letter_points).sum();
+    score_word(&word) - blanked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_sums_letter_points() {
+        assert_eq!(score_word("CAT"), 5);
+        assert_eq!(score_word("DOGS"), 7);
+        assert_eq!(score_word(""), 0);
+    }
+
+    #[test]
+    fn test_score_case_insensitive() {
+        assert_eq!(score_word("cat"), score_word("CAT"));
+    }
+
+    #[test]
+    fn test_rare_letters_outscore_common_ones() {
+        assert!(score_word("QUIZ") > score_word("TREE"));
+        assert_eq!(letter_points('Q'), 10);
+        assert_eq!(letter_points('z'), 10);
+        assert_eq!(letter_points('J'), 8);
+        assert_eq!(letter_points('X'), 8);
+        for vowel in ['A', 'E', 'I', 'O'] {
+            assert_eq!(letter_points(vowel), 1);
+        }
+        assert_eq!(letter_points('ñ'), 8);
+        assert_eq!(letter_points('Ø'), DEFAULT_LETTER_POINTS);
+    }
+
+    #[test]
+    fn test_blank_scores_nothing() {
+        let rack = ['C', 'A', 'T', BLANK];
+        assert_eq!(letter_points(BLANK), 0);
+        assert_eq!(score_on_rack("cats", &rack), score_word("CAT"));
+        // With the C on the rack, the blank only covers the missing S
+        assert_eq!(score_on_rack("CAT", &rack), 5);
+        let rack = ['A', 'T', 'S', BLANK];
+        assert_eq!(score_on_rack("cats", &rack), 3);
+    }
+
+    #[test]
+    fn test_rarer_letters_never_worth_less() {
+        for (letter, weight) in LETTER_WEIGHTS {
+            for (other, other_weight) in LETTER_WEIGHTS {
+                if weight < other_weight {
+                    assert!(
+                        letter_points(letter) >= letter_points(other),
+                        "{} is rarer than {} but worth less",
+                        letter,
+                        other
+                    );
+                }
+            }
+        }
+    }
+}
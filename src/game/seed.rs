@@ -0,0 +1,99 @@
+#![allow(dead_code)]
+//! Session-wide random number generation
+//!
+//! Racks, lobby names, lobby actor IDs and round IDs all draw from one
+//! shared generator. Setting `BLAM_SEED` to a number seeds it, so a whole
+//! session deals the same racks and picks the same names every time it's
+//! run (handy for demos and CI). Unset, it defers to the thread RNG.
+
+use once_cell::sync::Lazy;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::sync::Mutex;
+
+/// Environment variable holding the session seed
+pub const SEED_VAR: &str = "BLAM_SEED";
+
+static SESSION_RNG: Lazy<Mutex<SessionRng>> = Lazy::new(|| {
+    Mutex::new(SessionRng::from_seed_var(std::env::var(SEED_VAR).ok().as_deref()))
+});
+
+/// The session's random number generator
+pub struct SessionRng {
+    /// Generator seeded from `BLAM_SEED` (`None` defers to the thread RNG)
+    seeded: Option<StdRng>,
+}
+
+impl SessionRng {
+    /// Build the generator for a `BLAM_SEED` value
+    ///
+    /// Anything that isn't a `u64` is ignored, leaving the thread RNG.
+    pub fn from_seed_var(value: Option<&str>) -> Self {
+        Self {
+            seeded: value
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(StdRng::seed_from_u64),
+        }
+    }
+
+    /// Whether the session replays from a seed
+    pub fn is_seeded(&self) -> bool {
+        self.seeded.is_some()
+    }
+}
+
+impl RngCore for SessionRng {
+    fn next_u32(&mut self) -> u32 {
+        match &mut self.seeded {
+            Some(rng) => rng.next_u32(),
+            None => rand::rng().next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match &mut self.seeded {
+            Some(rng) => rng.next_u64(),
+            None => rand::rng().next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match &mut self.seeded {
+            Some(rng) => rng.fill_bytes(dest),
+            None => rand::rng().fill_bytes(dest),
+        }
+    }
+}
+
+/// Run `f` with the session's shared generator
+pub fn with_session_rng<T>(f: impl FnOnce(&mut SessionRng) -> T) -> T {
+    let mut rng = SESSION_RNG.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::LetterRack;
+    use rand::Rng;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SessionRng::from_seed_var(Some("42"));
+        let mut b = SessionRng::from_seed_var(Some(" 42\n"));
+        assert!(a.is_seeded());
+        let rolls_a: Vec<u32> = (0..8).map(|_| a.random()).collect();
+        let rolls_b: Vec<u32> = (0..8).map(|_| b.random()).collect();
+        assert_eq!(rolls_a, rolls_b);
+
+        let first_rack = LetterRack::generate_with_rng(&mut SessionRng::from_seed_var(Some("7")));
+        let again = LetterRack::generate_with_rng(&mut SessionRng::from_seed_var(Some("7")));
+        assert_eq!(first_rack.letters(), again.letters());
+    }
+
+    #[test]
+    fn test_unset_or_invalid_seed_uses_thread_rng() {
+        assert!(!SessionRng::from_seed_var(None).is_seeded());
+        assert!(!SessionRng::from_seed_var(Some("banana")).is_seeded());
+    }
+}
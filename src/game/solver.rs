@@ -0,0 +1,189 @@
+#![allow(dead_code)]
+//! Rack analysis: which dictionary words a rack can make
+//!
+//! Used to judge how generous a generated rack is, not during play.
+//!
+//! Solving scans the whole dictionary, so `solve_rack` remembers the result
+//! for the current rack: every feature that asks about the same rack during
+//! a round shares one scan.
+
+use super::dictionary::{self, Dictionary};
+use super::scoring::score_on_rack;
+use super::validation::bonus_points;
+use super::BLANK;
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+
+/// Cache shared by every caller of `solve_rack`
+static RACK_CACHE: Lazy<Mutex<RackCache>> = Lazy::new(|| Mutex::new(RackCache::default()));
+
+/// Everything the solver knows about one rack
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RackSolution {
+    /// Dictionary words the rack can spell
+    pub words: Vec<&'static str>,
+    /// Points available if every one of them were claimed
+    pub max_points: u32,
+}
+
+/// Remembers the solution for the most recently solved rack
+///
+/// Holds a single rack (the current round's), so it never grows.
+#[derive(Debug, Default)]
+pub struct RackCache {
+    /// Canonical rack key and its solution
+    last: Option<(String, Arc<RackSolution>)>,
+}
+
+impl RackCache {
+    /// Solve the rack, reusing the cached solution if it's the same rack
+    pub fn solve(&mut self, rack: &[char]) -> Arc<RackSolution> {
+        self.solve_in(&dictionary::shared(), rack)
+    }
+
+    /// Solve the rack against `dictionary`
+    pub fn solve_in(&mut self, dictionary: &Dictionary, rack: &[char]) -> Arc<RackSolution> {
+        let key = format!("{}:{}", dictionary.language().code(), rack_key(rack));
+        if let Some((cached_key, solution)) = &self.last {
+            if *cached_key == key {
+                return Arc::clone(solution);
+            }
+        }
+
+        let words = possible_words_in(dictionary, rack);
+        let solution = Arc::new(RackSolution {
+            max_points: max_points(&words, rack),
+            words,
+        });
+        self.last = Some((key, Arc::clone(&solution)));
+        solution
+    }
+}
+
+/// Solve a rack, sharing the result with anyone else asking about it
+pub fn solve_rack(rack: &[char]) -> Arc<RackSolution> {
+    solve_rack_in(&dictionary::shared(), rack)
+}
+
+/// Solve a rack against `dictionary` (for racks in another language)
+pub fn solve_rack_in(dictionary: &Dictionary, rack: &[char]) -> Arc<RackSolution> {
+    RACK_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .solve_in(dictionary, rack)
+}
+
+/// Canonical form of a rack: its letters uppercased and sorted, so the same
+/// tiles in a different order share a cache entry
+fn rack_key(rack: &[char]) -> String {
+    let mut letters: Vec<char> = rack.iter().map(|c| c.to_ascii_uppercase()).collect();
+    letters.sort_unstable();
+    letters.into_iter().collect()
+}
+
+/// Count of each letter A-Z (and Ñ) in a word or rack, or `None` if it has
+/// other characters
+fn letter_counts(letters: impl Iterator<Item = char>) -> Option<[u8; 27]> {
+    let mut counts = [0u8; 27];
+    for c in letters {
+        let index = match c.to_uppercase().next()? {
+            c @ 'A'..='Z' => (c as u8 - b'A') as usize,
+            'Ñ' => 26,
+            _ => return None,
+        };
+        counts[index] += 1;
+    }
+    Some(counts)
+}
+
+/// All dictionary words that can be spelled from the rack
+///
+/// Each blank on the rack covers one letter the tiles are short of.
+pub fn possible_words(rack: &[char]) -> Vec<&'static str> {
+    possible_words_in(&dictionary::shared(), rack)
+}
+
+/// All words in `dictionary` that can be spelled from the rack
+pub fn possible_words_in(dictionary: &Dictionary, rack: &[char]) -> Vec<&'static str> {
+    let blanks = rack.iter().filter(|&&c| c == BLANK).count();
+    let Some(available) = letter_counts(rack.iter().copied().filter(|&c| c != BLANK)) else {
+        return Vec::new();
+    };
+
+    dictionary
+        .words()
+        .filter(|word| word.chars().count() <= rack.len())
+        .filter(|word| {
+            letter_counts(word.chars()).is_some_and(|needed| {
+                let short: usize =
+                    needed.iter().zip(&available).map(|(n, a)| n.saturating_sub(*a) as usize).sum();
+                short <= blanks
+            })
+        })
+        .collect()
+}
+
+/// Points available on `rack` if every one of `words` were claimed,
+/// bonuses included
+pub fn max_points(words: &[&str], rack: &[char]) -> u32 {
+    words
+        .iter()
+        .map(|word| score_on_rack(word, rack) + bonus_points(word, rack))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::validation::PANGRAM_BONUS;
+
+    #[test]
+    fn test_possible_words_respect_multiplicity() {
+        let words = possible_words(&['C', 'A', 'T']);
+        assert!(words.contains(&"cat"));
+        assert!(words.contains(&"act"));
+        assert!(!words.contains(&"tact"));
+    }
+
+    #[test]
+    fn test_possible_words_empty_rack() {
+        assert!(possible_words(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_same_rack_solved_once() {
+        let mut cache = RackCache::default();
+        let first = cache.solve(&['C', 'A', 'T']);
+        // Same tiles in a different order hit the cache
+        let second = cache.solve(&['t', 'a', 'c']);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_different_rack_recomputed() {
+        let mut cache = RackCache::default();
+        let cat = cache.solve(&['C', 'A', 'T']);
+        let dog = cache.solve(&['D', 'O', 'G']);
+        assert!(!Arc::ptr_eq(&cat, &dog));
+        assert!(dog.words.contains(&"dog"));
+        assert!(!dog.words.contains(&"cat"));
+    }
+
+    #[test]
+    fn test_max_points_sums_word_scores() {
+        assert_eq!(max_points(&["cat", "act", "at"], &['C', 'A', 'T', 'S']), 12);
+        // A blank C scores nothing
+        assert_eq!(max_points(&["cat", "act", "at"], &[BLANK, 'A', 'T', 'S']), 6);
+        // Bonuses are there for the taking too
+        assert_eq!(max_points(&["cats"], &['C', 'A', 'T', 'S']), 6 + PANGRAM_BONUS);
+    }
+
+    #[test]
+    fn test_blank_fills_one_gap() {
+        let words = possible_words(&['C', 'A', 'T', BLANK]);
+        assert!(words.contains(&"cats"));
+        assert!(words.contains(&"cat"));
+        assert!(!words.contains(&"coats"));
+    }
+}
@@ -2,24 +2,100 @@
 //! Word validation for BLAM! game
 //!
 //! Validates submitted words against:
-//! - Minimum length (1 character)
-//! - Letter availability in rack (with multiplicity)
+//! - Minimum length (1 character by default; house rules can raise it)
+//! - Minimum letters used (off by default; a house rule for big racks)
+//! - Letter availability in rack (with multiplicity, blanks filling gaps)
 //! - Dictionary presence
+//!
+//! It also works out the bonuses a valid word earns on top of its letter
+//! points: one for a pangram (every distinct tile on the rack used) and
+//! one that grows with length from seven letters up.
 
-use super::dictionary;
+use super::dictionary::{self, Dictionary};
+use super::BLANK;
 
 /// Minimum word length for valid submissions
 pub const MIN_WORD_LENGTH: usize = 1;
 
+/// Highest minimum word length a house rule can set
+pub const MAX_MIN_WORD_LENGTH: usize = 3;
+
+/// Rack tiles per required letter under `LettersUsedRule::Scaled`
+pub const TILES_PER_REQUIRED_LETTER: usize = 4;
+
+/// Bonus for a word that uses every distinct letter on the rack
+pub const PANGRAM_BONUS: u32 = 10;
+
+/// Shortest word that earns a length bonus
+pub const LONG_WORD_LENGTH: usize = 7;
+
+/// Length bonus for a `LONG_WORD_LENGTH` word, and for each letter past it
+pub const LONG_WORD_STEP: u32 = 5;
+
+/// House rule: how many of the rack's tiles a claim must use
+///
+/// Keeps the game ambitious on big racks, where three-letter words are
+/// easy pickings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LettersUsedRule {
+    /// Any number of letters will do
+    #[default]
+    Off,
+    /// One letter per `TILES_PER_REQUIRED_LETTER` tiles on the rack
+    Scaled,
+    /// At least this many letters
+    AtLeast(usize),
+}
+
+impl LettersUsedRule {
+    /// Every rule, in the order the host cycles through them
+    pub fn all() -> [LettersUsedRule; 5] {
+        [
+            LettersUsedRule::Off,
+            LettersUsedRule::Scaled,
+            LettersUsedRule::AtLeast(4),
+            LettersUsedRule::AtLeast(5),
+            LettersUsedRule::AtLeast(6),
+        ]
+    }
+
+    /// The rule after this one (wrapping)
+    pub fn next(self) -> Self {
+        let all = Self::all();
+        let idx = all.iter().position(|r| *r == self).unwrap_or(0);
+        all[(idx + 1) % all.len()]
+    }
+
+    /// Letters a claim must use on a rack of `rack_len` tiles (0 for none)
+    pub fn threshold(&self, rack_len: usize) -> usize {
+        match self {
+            LettersUsedRule::Off => 0,
+            LettersUsedRule::Scaled => rack_len / TILES_PER_REQUIRED_LETTER,
+            LettersUsedRule::AtLeast(min) => *min,
+        }
+    }
+
+    /// Short label for the lobby info line
+    pub fn label(&self) -> String {
+        match self {
+            LettersUsedRule::Off => "off".to_string(),
+            LettersUsedRule::Scaled => "scaled".to_string(),
+            LettersUsedRule::AtLeast(min) => format!("{}+", min),
+        }
+    }
+}
+
 /// Result of word validation with specific error messages
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ValidationResult {
     /// Word is valid
     Valid,
-    /// Word is too short (empty string)
-    TooShort { length: usize },
+    /// Word is shorter than the minimum length
+    TooShort { length: usize, min: usize },
     /// Word uses letters not available in the rack
     InvalidLetters { missing: Vec<char> },
+    /// Word uses fewer of the rack's letters than the house rule asks for
+    TooFewLetters { used: usize, min: usize },
     /// Word not found in dictionary
     NotInDictionary,
 }
@@ -34,13 +110,16 @@ impl ValidationResult {
     pub fn message(&self) -> String {
         match self {
             ValidationResult::Valid => "Valid word!".to_string(),
-            ValidationResult::TooShort { length } => {
-                format!("Too short ({} chars, need {}+)", length, MIN_WORD_LENGTH)
+            ValidationResult::TooShort { length, min } => {
+                format!("Too short ({} chars, need {}+)", length, min)
             }
             ValidationResult::InvalidLetters { missing } => {
                 let letters: String = missing.iter().collect();
                 format!("Missing letters: {}", letters)
             }
+            ValidationResult::TooFewLetters { used, min } => {
+                format!("Too few letters ({} used, need {}+)", used, min)
+            }
             ValidationResult::NotInDictionary => "Not in dictionary".to_string(),
         }
     }
@@ -53,12 +132,40 @@ impl ValidationResult {
 /// 2. All letters available in rack (with multiplicity)
 /// 3. Word exists in dictionary
 pub fn validate_word(word: &str, rack: &[char]) -> ValidationResult {
+    validate_word_with_min_length(word, rack, MIN_WORD_LENGTH)
+}
+
+/// Validate a word, requiring at least `min_length` letters
+///
+/// `min_length` below `MIN_WORD_LENGTH` is treated as `MIN_WORD_LENGTH`.
+pub fn validate_word_with_min_length(
+    word: &str,
+    rack: &[char],
+    min_length: usize,
+) -> ValidationResult {
+    validate_word_with_rules(&dictionary::shared(), word, rack, min_length, 0)
+}
+
+/// Validate a word against `dictionary` under the house rules
+///
+/// On top of `min_length`, a word that can be spelled from the rack must
+/// use at least `min_letters_used` of its tiles (0 turns that check off).
+pub fn validate_word_with_rules(
+    dictionary: &Dictionary,
+    word: &str,
+    rack: &[char],
+    min_length: usize,
+    min_letters_used: usize,
+) -> ValidationResult {
     let word_upper = word.to_uppercase();
+    let min_length = min_length.max(MIN_WORD_LENGTH);
 
-    // Check minimum length
-    if word_upper.len() < MIN_WORD_LENGTH {
+    // Check minimum length, in letters rather than bytes
+    let length = word_upper.chars().count();
+    if length < min_length {
         return ValidationResult::TooShort {
-            length: word_upper.len(),
+            length,
+            min: min_length,
         };
     }
 
@@ -67,18 +174,33 @@ pub fn validate_word(word: &str, rack: &[char]) -> ValidationResult {
         return ValidationResult::InvalidLetters { missing };
     }
 
+    // Check the word uses enough of the rack
+    let used = word_upper.chars().count();
+    if used < min_letters_used {
+        return ValidationResult::TooFewLetters {
+            used,
+            min: min_letters_used,
+        };
+    }
+
     // Check word is in dictionary
-    if !dictionary::is_valid_word(&word_upper) {
+    if !dictionary.contains(&word_upper) {
         return ValidationResult::NotInDictionary;
     }
 
     ValidationResult::Valid
 }
 
-/// Check if all letters in word are available in rack (respecting multiplicity)
-/// Returns None if valid, Some(missing_letters) if invalid
-fn check_letters_available(word: &str, rack: &[char]) -> Option<Vec<char>> {
-    let mut available: Vec<char> = rack.to_vec();
+/// Check whether a word can be spelled from the rack, ignoring the dictionary
+pub fn letters_available(word: &str, rack: &[char]) -> bool {
+    check_letters_available(&word.to_uppercase(), rack).is_none()
+}
+
+/// Letters of `word` the rack has no tile for, in word order
+///
+/// Real tiles are used up first; blanks only cover what's left.
+fn letters_without_tiles(word: &str, rack: &[char]) -> Vec<char> {
+    let mut available: Vec<char> = rack.iter().copied().filter(|&c| c != BLANK).collect();
     let mut missing: Vec<char> = Vec::new();
 
     for c in word.chars() {
@@ -88,6 +210,93 @@ fn check_letters_available(word: &str, rack: &[char]) -> Option<Vec<char>> {
             missing.push(c);
         }
     }
+    missing
+}
+
+/// Letters of `word` (uppercase) that the rack's blanks stand in for
+///
+/// Empty when the word can be spelled from real tiles alone.
+pub fn blanked_letters(word: &str, rack: &[char]) -> Vec<char> {
+    let mut missing = letters_without_tiles(word, rack);
+    let blanks = rack.iter().filter(|&&c| c == BLANK).count();
+    missing.truncate(blanks);
+    missing
+}
+
+/// Bonus points a word earns, broken down for display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WordBonus {
+    /// `PANGRAM_BONUS` if the word uses every distinct rack letter, else 0
+    pub pangram: u32,
+    /// Bonus for a word of `LONG_WORD_LENGTH` letters or more
+    pub length: u32,
+}
+
+impl WordBonus {
+    /// The bonuses `word` earns on `rack`
+    ///
+    /// Doesn't validate the word: call this once it's been accepted.
+    pub fn for_word(word: &str, rack: &[char]) -> Self {
+        let word = word.to_uppercase();
+        let pangram = if is_pangram(&word, rack) { PANGRAM_BONUS } else { 0 };
+        Self {
+            pangram,
+            length: length_bonus(word.chars().count()),
+        }
+    }
+
+    /// Both bonuses together
+    pub fn total(&self) -> u32 {
+        self.pangram + self.length
+    }
+
+    /// Shout for the feed, e.g. "PANGRAM!" (`None` without a bonus)
+    pub fn label(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if self.pangram > 0 {
+            parts.push("PANGRAM!");
+        }
+        if self.length > 0 {
+            parts.push("LONG WORD!");
+        }
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+}
+
+/// Bonus points `word` earns on `rack` on top of its letter points
+pub fn bonus_points(word: &str, rack: &[char]) -> u32 {
+    WordBonus::for_word(word, rack).total()
+}
+
+/// Whether `word` uses every distinct letter on the rack
+///
+/// Blanks count as rack letters too: a pangram has to use them all.
+pub fn is_pangram(word: &str, rack: &[char]) -> bool {
+    let word = word.to_uppercase();
+    let blanks = rack.iter().filter(|&&c| c == BLANK).count();
+    rack.iter().filter(|&&c| c != BLANK).all(|&c| word.contains(c))
+        && blanked_letters(&word, rack).len() == blanks
+}
+
+/// Bonus for a word of `length` letters
+///
+/// `LONG_WORD_STEP` at `LONG_WORD_LENGTH` letters, and another step for
+/// each letter after that.
+pub fn length_bonus(length: usize) -> u32 {
+    if length < LONG_WORD_LENGTH {
+        return 0;
+    }
+    (length - LONG_WORD_LENGTH + 1) as u32 * LONG_WORD_STEP
+}
+
+/// Check if all letters in word are available in rack (respecting multiplicity)
+/// Returns None if valid, Some(missing_letters) if invalid
+///
+/// Blanks cover missing letters before any are reported.
+fn check_letters_available(word: &str, rack: &[char]) -> Option<Vec<char>> {
+    let mut missing = letters_without_tiles(word, rack);
+    let blanks = rack.iter().filter(|&&c| c == BLANK).count();
+    missing.drain(..blanks.min(missing.len()));
 
     if missing.is_empty() {
         None
@@ -111,14 +320,100 @@ mod tests {
         assert_eq!(validate_word("dog", &rack), ValidationResult::Valid);
     }
 
+    #[test]
+    fn test_blank_covers_one_missing_letter() {
+        let rack = ['C', 'A', 'T', BLANK];
+        assert_eq!(validate_word("cats", &rack), ValidationResult::Valid);
+        assert_eq!(blanked_letters("CATS", &rack), vec!['S']);
+        // Real tiles are used before the blank
+        assert!(blanked_letters("CAT", &rack).is_empty());
+    }
+
+    #[test]
+    fn test_one_blank_cannot_cover_two_missing_letters() {
+        let rack = ['C', 'A', 'T', BLANK];
+        assert_eq!(
+            validate_word("coats", &rack),
+            ValidationResult::InvalidLetters { missing: vec!['S'] }
+        );
+        let rack = ['C', 'A', 'T'];
+        assert_eq!(
+            validate_word("coats", &rack),
+            ValidationResult::InvalidLetters { missing: vec!['O', 'S'] }
+        );
+    }
+
+    #[test]
+    fn test_pangram_bonus_on_small_rack() {
+        // Distinct letters are what count: the second A can stay unused
+        let rack = ['C', 'A', 'T', 'A'];
+        assert!(is_pangram("cat", &rack));
+        assert!(!is_pangram("at", &rack));
+        let bonus = WordBonus::for_word("cat", &rack);
+        assert_eq!(bonus, WordBonus { pangram: PANGRAM_BONUS, length: 0 });
+        assert_eq!(bonus.label().as_deref(), Some("PANGRAM!"));
+        assert_eq!(bonus_points("at", &rack), 0);
+
+        // A blank on the rack has to be used as well
+        let rack = ['C', 'A', 'T', BLANK];
+        assert!(!is_pangram("cat", &rack));
+        assert!(is_pangram("cats", &rack));
+    }
+
+    #[test]
+    fn test_long_word_bonus_without_pangram() {
+        let rack = ['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'];
+        assert_eq!(validate_word("dragons", &rack), ValidationResult::Valid);
+        let bonus = WordBonus::for_word("dragons", &rack);
+        assert_eq!(bonus, WordBonus { pangram: 0, length: LONG_WORD_STEP });
+        assert_eq!(bonus.label().as_deref(), Some("LONG WORD!"));
+        assert_eq!(bonus_points("dogs", &rack), 0);
+        assert_eq!(length_bonus(6), 0);
+        assert_eq!(length_bonus(8), 2 * LONG_WORD_STEP);
+    }
+
     #[test]
     fn test_too_short() {
         let rack = ['A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L'];
         // Only empty string is too short now
         assert_eq!(
             validate_word("", &rack),
-            ValidationResult::TooShort { length: 0 }
+            ValidationResult::TooShort { length: 0, min: 1 }
+        );
+    }
+
+    #[test]
+    fn test_two_letter_threshold() {
+        let rack = ['O', 'X', 'A', 'A', 'T', 'E', 'R', 'S', 'N', 'I', 'L', 'D'];
+        assert_eq!(validate_word_with_min_length("ox", &rack, 2), ValidationResult::Valid);
+        assert_eq!(validate_word_with_min_length("aa", &rack, 2), ValidationResult::Valid);
+        assert_eq!(
+            validate_word_with_min_length("ox", &rack, 3),
+            ValidationResult::TooShort { length: 2, min: 3 }
+        );
+        assert_eq!(
+            validate_word_with_min_length("a", &rack, 2),
+            ValidationResult::TooShort { length: 1, min: 2 }
+        );
+        // A threshold of 0 still rejects the empty word
+        assert!(matches!(
+            validate_word_with_min_length("", &rack, 0),
+            ValidationResult::TooShort { min: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_length_counts_letters_not_bytes() {
+        let rack = ['Ñ', 'U', 'A', 'O'];
+        // "ÑU" is three bytes but two letters
+        assert_eq!(
+            validate_word_with_min_length("ÑU", &rack, 3),
+            ValidationResult::TooShort { length: 2, min: 3 }
         );
+        assert!(!matches!(
+            validate_word_with_min_length("ÑU", &rack, 2),
+            ValidationResult::TooShort { .. }
+        ));
     }
 
     #[test]
@@ -189,9 +484,13 @@ mod tests {
     fn test_message_format() {
         assert_eq!(ValidationResult::Valid.message(), "Valid word!");
         assert_eq!(
-            ValidationResult::TooShort { length: 0 }.message(),
+            ValidationResult::TooShort { length: 0, min: 1 }.message(),
             "Too short (0 chars, need 1+)"
         );
+        assert_eq!(
+            ValidationResult::TooShort { length: 2, min: 3 }.message(),
+            "Too short (2 chars, need 3+)"
+        );
         assert_eq!(
             ValidationResult::InvalidLetters {
                 missing: vec!['X', 'Y']
@@ -208,7 +507,7 @@ mod tests {
     #[test]
     fn test_is_valid_method() {
         assert!(ValidationResult::Valid.is_valid());
-        assert!(!ValidationResult::TooShort { length: 0 }.is_valid());
+        assert!(!ValidationResult::TooShort { length: 0, min: 1 }.is_valid());
         assert!(!ValidationResult::InvalidLetters { missing: vec!['X'] }.is_valid());
         assert!(!ValidationResult::NotInDictionary.is_valid());
     }
@@ -250,6 +549,31 @@ mod tests {
         assert_eq!(validate_word("i", &rack), ValidationResult::Valid);
     }
 
+    #[test]
+    fn test_letters_used_rule_threshold() {
+        assert_eq!(LettersUsedRule::Off.threshold(18), 0);
+        assert_eq!(LettersUsedRule::Scaled.threshold(18), 4);
+        assert_eq!(LettersUsedRule::Scaled.threshold(12), 3);
+        assert_eq!(LettersUsedRule::AtLeast(5).threshold(12), 5);
+
+        let mut rule = LettersUsedRule::default();
+        for _ in 0..LettersUsedRule::all().len() {
+            rule = rule.next();
+        }
+        assert_eq!(rule, LettersUsedRule::Off);
+
+        let rack = ['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'];
+        assert_eq!(
+            validate_word_with_rules(&dictionary::shared(), "cat", &rack, MIN_WORD_LENGTH, 4),
+            ValidationResult::TooFewLetters { used: 3, min: 4 }
+        );
+        // Letters off the rack are still the bigger problem
+        assert!(matches!(
+            validate_word_with_rules(&dictionary::shared(), "zap", &rack, MIN_WORD_LENGTH, 4),
+            ValidationResult::InvalidLetters { .. }
+        ));
+    }
+
     #[test]
     fn test_validation_result_clone_eq() {
         let v1 = ValidationResult::Valid;
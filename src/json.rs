@@ -0,0 +1,338 @@
+#![allow(dead_code)]
+//! A small JSON parser for message and event payloads
+//!
+//! Payloads are written by hand with `format!`, and read back by parsing
+//! them into a `Value` tree and looking fields up by key. Looking keys up in
+//! the tree (rather than searching the text) means a key name showing up
+//! inside a string, or in a nested object, can't be mistaken for the field.
+//!
+//! Numbers keep their text, so each caller parses them into the integer
+//! type it needs without going through `f64` (match IDs and rack seeds
+//! don't fit in one).
+
+use std::str::FromStr;
+
+/// Deepest nesting `parse` accepts, so a hostile message can't exhaust the
+/// stack
+pub const MAX_DEPTH: usize = 32;
+
+/// A parsed JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// The number as written, e.g. `-42` or `1.5e3`
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    /// Fields in the order written
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Field `key` of an object (the first, if it's repeated)
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// The number as `T`, if it's a number that fits
+    pub fn as_number<T: FromStr>(&self) -> Option<T> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// A `["name", number]` pair
+    pub fn as_pair<T: FromStr>(&self) -> Option<(String, T)> {
+        match self.as_array()? {
+            [name, value] => Some((name.as_str()?.to_string(), value.as_number()?)),
+            _ => None,
+        }
+    }
+
+    /// String field `key` of an object
+    pub fn str_field(&self, key: &str) -> Option<String> {
+        self.get(key)?.as_str().map(str::to_string)
+    }
+
+    /// Number field `key` of an object
+    pub fn number_field<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.get(key)?.as_number()
+    }
+
+    /// Boolean field `key` of an object
+    pub fn bool_field(&self, key: &str) -> Option<bool> {
+        self.get(key)?.as_bool()
+    }
+}
+
+/// Parse a complete JSON document
+///
+/// Returns `None` for anything that isn't valid JSON, including trailing
+/// text after the value and nesting deeper than `MAX_DEPTH`.
+pub fn parse(text: &str) -> Option<Value> {
+    let mut parser = Parser { text, pos: 0 };
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    (parser.pos == text.len()).then_some(value)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    /// Byte offset of the next unread character
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> Option<()> {
+        (self.next()? == c).then_some(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start_matches([' ', '\t', '\n', '\r']);
+        self.pos = self.text.len() - trimmed.len();
+    }
+
+    fn value(&mut self, depth: usize) -> Option<Value> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        self.skip_whitespace();
+        match self.peek()? {
+            '{' => self.object(depth),
+            '[' => self.array(depth),
+            '"' => self.string().map(Value::String),
+            't' => self.keyword("true", Value::Bool(true)),
+            'f' => self.keyword("false", Value::Bool(false)),
+            'n' => self.keyword("null", Value::Null),
+            _ => self.number(),
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: Value) -> Option<Value> {
+        self.rest().starts_with(word).then(|| {
+            self.pos += word.len();
+            value
+        })
+    }
+
+    fn object(&mut self, depth: usize) -> Option<Value> {
+        self.eat('{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek()? == '}' {
+            self.pos += 1;
+            return Some(Value::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.eat(':')?;
+            fields.push((key, self.value(depth + 1)?));
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => continue,
+                '}' => return Some(Value::Object(fields)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Option<Value> {
+        self.eat('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek()? == ']' {
+            self.pos += 1;
+            return Some(Value::Array(items));
+        }
+        loop {
+            items.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            match self.next()? {
+                ',' => continue,
+                ']' => return Some(Value::Array(items)),
+                _ => return None,
+            }
+        }
+    }
+
+    /// A string, with escapes decoded
+    ///
+    /// Raw control characters are let through, since older builds wrote
+    /// them unescaped.
+    fn string(&mut self) -> Option<String> {
+        self.eat('"')?;
+        let mut s = String::new();
+        loop {
+            match self.next()? {
+                '"' => return Some(s),
+                '\\' => match self.next()? {
+                    '"' => s.push('"'),
+                    '\\' => s.push('\\'),
+                    '/' => s.push('/'),
+                    'b' => s.push('\u{8}'),
+                    'f' => s.push('\u{c}'),
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+                    'u' => s.push(self.unicode_escape()?),
+                    _ => return None,
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    /// The character of a `\uXXXX` escape (just past the `u`), joining
+    /// surrogate pairs
+    fn unicode_escape(&mut self) -> Option<char> {
+        let high = self.hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high);
+        }
+        if !self.rest().starts_with("\\u") {
+            return None;
+        }
+        self.pos += 2;
+        let low = self.hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return None;
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let digits = self.rest().get(..4)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.pos += 4;
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    fn number(&mut self) -> Option<Value> {
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')))
+            .unwrap_or(self.rest().len());
+        let text = &self.rest()[..len];
+        // Checked as a float, kept as text
+        if len == 0 || text.starts_with('+') || text.parse::<f64>().is_err() {
+            return None;
+        }
+        self.pos += len;
+        Some(Value::Number(text.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nested_values() {
+        let value =
+            parse(r#" {"a":[1,-2.5,true,null],"b":{"c":"d"},"e":[]} "#).unwrap();
+        let a = value.get("a").unwrap().as_array().unwrap();
+        assert_eq!(a[0].as_number::<u32>(), Some(1));
+        assert_eq!(a[1].as_number::<f64>(), Some(-2.5));
+        assert_eq!(a[1].as_number::<i64>(), None);
+        assert_eq!(a[2].as_bool(), Some(true));
+        assert_eq!(a[3], Value::Null);
+        assert_eq!(value.get("b").unwrap().str_field("c"), Some("d".to_string()));
+        assert_eq!(value.get("e").unwrap().as_array(), Some(&[][..]));
+    }
+
+    #[test]
+    fn test_keys_only_match_fields_of_the_object() {
+        let value = parse(r#"{"note":"\"id\":7","inner":{"id":8},"id":9}"#).unwrap();
+        assert_eq!(value.number_field::<u32>("id"), Some(9));
+        let value = parse(r#"{"note":"\"id\":7","inner":{"id":8}}"#).unwrap();
+        assert_eq!(value.number_field::<u32>("id"), None);
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        let value = parse(r#"["a\"b\\c\n\t\/", "ñ😀", "tab	raw"]"#).unwrap();
+        let items = value.as_array().unwrap();
+        assert_eq!(items[0].as_str(), Some("a\"b\\c\n\t/"));
+        assert_eq!(items[1].as_str(), Some("ñ😀"));
+        assert_eq!(items[2].as_str(), Some("tab\traw"));
+        // Unknown escapes, a lone surrogate and an unterminated string
+        assert_eq!(parse(r#""\x""#), None);
+        assert_eq!(parse(r#""\ud83d""#), None);
+        assert_eq!(parse(r#""abc\"#), None);
+    }
+
+    #[test]
+    fn test_big_integers_keep_their_precision() {
+        let value = parse(r#"{"seed":18446744073709551615,"id":-9223372036854775808}"#).unwrap();
+        assert_eq!(value.number_field::<u64>("seed"), Some(u64::MAX));
+        assert_eq!(value.number_field::<i64>("id"), Some(i64::MIN));
+        assert_eq!(value.number_field::<u32>("seed"), None);
+    }
+
+    #[test]
+    fn test_rejects_malformed_documents() {
+        for bad in ["", "{", "[1,]", r#"{"a":1,}"#, r#"{"a" 1}"#, "{} {}", "+1", "tru", "01x"] {
+            assert_eq!(parse(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let nested = |depth: usize| "[".repeat(depth) + &"]".repeat(depth);
+        assert!(parse(&nested(MAX_DEPTH + 1)).is_some());
+        assert_eq!(parse(&nested(MAX_DEPTH + 2)), None);
+    }
+
+    #[test]
+    fn test_pairs() {
+        let value = parse(r#"[["Alice",50],["Bob","x"],["Carol",1,2]]"#).unwrap();
+        let items = value.as_array().unwrap();
+        assert_eq!(items[0].as_pair::<u32>(), Some(("Alice".to_string(), 50)));
+        assert_eq!(items[1].as_pair::<u32>(), None);
+        assert_eq!(items[2].as_pair::<u32>(), None);
+    }
+}
@@ -0,0 +1,20 @@
+//! BLAM! - LAN-first, local-first, multiplayer word brawler
+//!
+//! Type fast. Claim first. Keep the crown.
+//!
+//! The game is a library with the terminal app on top. Frontends that only
+//! need the rules, scoring and ratings should use [`engine`] and build with
+//! `default-features = false`, which leaves out the terminal (`tui`) and
+//! LAN discovery (`lan`) crates.
+
+pub mod app;
+pub mod engine;
+pub mod game;
+mod json;
+#[cfg(feature = "lan")]
+pub mod lobby;
+pub mod network;
+pub mod stats;
+pub mod storage;
+#[cfg(all(feature = "tui", feature = "lan"))]
+pub mod tui;
@@ -0,0 +1,172 @@
+#![allow(dead_code)]
+//! Bot opponents for a hosted lobby
+//!
+//! With nobody else around, the host can fill seats with bots. A bot plays
+//! on the host's machine: every so often it picks an unclaimed word the
+//! rack can make and claims it through the same path as a remote player,
+//! so scoring, combos and the event log treat it like anyone else.
+
+use rand::prelude::*;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Most bots a host can add to one lobby
+pub const MAX_BOTS: usize = 3;
+
+/// How well (and how fast) a bot plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BotSkill {
+    /// Slow, sticks to short words
+    Easy,
+    /// Steady, words up to six letters
+    #[default]
+    Medium,
+    /// Quick, any word the rack can make
+    Hard,
+}
+
+impl BotSkill {
+    /// Every skill, in the order the host cycles through them
+    pub fn all() -> [BotSkill; 3] {
+        [BotSkill::Easy, BotSkill::Medium, BotSkill::Hard]
+    }
+
+    /// The skill after this one (wrapping)
+    pub fn next(self) -> Self {
+        let all = Self::all();
+        let idx = all.iter().position(|s| *s == self).unwrap_or(0);
+        all[(idx + 1) % all.len()]
+    }
+
+    /// Time between a bot's claims
+    pub fn claim_interval(&self) -> Duration {
+        match self {
+            BotSkill::Easy => Duration::from_secs(8),
+            BotSkill::Medium => Duration::from_secs(5),
+            BotSkill::Hard => Duration::from_secs(3),
+        }
+    }
+
+    /// Longest word the bot will go for
+    pub fn max_word_length(&self) -> usize {
+        match self {
+            BotSkill::Easy => 4,
+            BotSkill::Medium => 6,
+            BotSkill::Hard => usize::MAX,
+        }
+    }
+
+    /// Short label for the lobby info line
+    pub fn label(&self) -> &'static str {
+        match self {
+            BotSkill::Easy => "easy",
+            BotSkill::Medium => "medium",
+            BotSkill::Hard => "hard",
+        }
+    }
+}
+
+/// A computer-controlled player
+#[derive(Debug, Clone)]
+pub struct Bot {
+    /// Name shown in the lobby and on the scoreboard
+    pub name: String,
+    /// When the bot next claims a word (`None` between rounds)
+    next_claim: Option<Instant>,
+}
+
+impl Bot {
+    /// A bot that sits out until the next round starts
+    pub fn new(name: String) -> Self {
+        Self { name, next_claim: None }
+    }
+
+    /// Get ready for a round starting at `now`
+    ///
+    /// The first claim comes one interval in, like a player reading the rack.
+    pub fn start_round(&mut self, now: Instant, skill: BotSkill) {
+        self.next_claim = Some(now + skill.claim_interval());
+    }
+
+    /// Stop claiming until the next round
+    pub fn end_round(&mut self) {
+        self.next_claim = None;
+    }
+
+    /// Whether it's time for the bot's next claim
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.next_claim.is_some_and(|at| now >= at)
+    }
+
+    /// Pick the word to claim now, and schedule the claim after it
+    ///
+    /// Chooses at random among `words` the skill allows that are at least
+    /// `min_length` letters and not yet in `claimed` (uppercase words).
+    /// Returns `None` if nothing is left to claim.
+    pub fn pick_word<R: Rng>(
+        &mut self,
+        words: &[&str],
+        claimed: &HashMap<String, String>,
+        min_length: usize,
+        skill: BotSkill,
+        now: Instant,
+        rng: &mut R,
+    ) -> Option<String> {
+        self.next_claim = Some(now + skill.claim_interval());
+        let candidates: Vec<String> = words
+            .iter()
+            .filter(|w| (min_length..=skill.max_word_length()).contains(&w.len()))
+            .map(|w| w.to_uppercase())
+            .filter(|w| !claimed.contains_key(w))
+            .collect();
+        candidates.choose(rng).cloned()
+    }
+}
+
+/// Name for the `n`th bot in a lobby (counting from 1)
+pub fn bot_name(n: usize) -> String {
+    format!("Bot {}", n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_bot_picks_unclaimed_words_within_its_skill() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let words = ["at", "cat", "cats", "coast", "tacos"];
+        let mut claimed = HashMap::new();
+        claimed.insert("CATS".to_string(), "Alice".to_string());
+
+        let now = Instant::now();
+        let mut bot = Bot::new(bot_name(1));
+        assert!(!bot.is_due(now));
+        bot.start_round(now, BotSkill::Easy);
+        assert!(!bot.is_due(now));
+        assert!(bot.is_due(now + BotSkill::Easy.claim_interval()));
+
+        for _ in 0..20 {
+            let word = bot.pick_word(&words, &claimed, 2, BotSkill::Easy, now, &mut rng).unwrap();
+            assert!(word == "AT" || word == "CAT", "easy bot picked {}", word);
+        }
+        claimed.insert("AT".to_string(), "Bob".to_string());
+        claimed.insert("CAT".to_string(), "Bob".to_string());
+        assert_eq!(bot.pick_word(&words, &claimed, 2, BotSkill::Easy, now, &mut rng), None);
+        assert!(bot.pick_word(&words, &claimed, 2, BotSkill::Hard, now, &mut rng).is_some());
+
+        bot.end_round();
+        assert!(!bot.is_due(now + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_skill_cycles_through_all() {
+        let mut skill = BotSkill::default();
+        for _ in 0..BotSkill::all().len() {
+            skill = skill.next();
+        }
+        assert_eq!(skill, BotSkill::default());
+        assert!(BotSkill::Hard.claim_interval() < BotSkill::Easy.claim_interval());
+    }
+}
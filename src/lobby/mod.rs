@@ -7,24 +7,129 @@
 //! - Player list management
 //! - Synchronized round start
 //! - Claim arbitration during gameplay
+//! - Bot opponents played by the host
 
-use crate::game::arbitrator::{ClaimResult, RoundArbitrator};
+pub mod bot;
+
+use crate::game::arbitrator::{ClaimRecord, ClaimResult, RoundArbitrator};
+use crate::game::seed::with_session_rng;
+use crate::game::dictionary::{self, Dictionary};
+use crate::game::solver;
+use crate::game::{Language, RackConfig, RackPreset};
+use crate::game::validation::{LettersUsedRule, MAX_MIN_WORD_LENGTH, MIN_WORD_LENGTH};
+use crate::stats::MatchResult;
 use crate::network::{
-    ClaimRejectReason, Client, DiscoveryEvent, JoinRejectReason, Message, PeerInfo, PeerTracker,
-    Server, ServerEvent, ServiceDiscovery,
+    ClaimRejectReason, Client, DiscoveryEvent, EventQueue, JoinRejectReason, LobbyAdvert, Message,
+    PeerInfo, PeerTracker, RoundClaim, RoundConfig, RoundEndReason, Server, ServerEvent,
+    ServiceDiscovery, TrafficStats, PEER_TTL,
 };
 use rand::prelude::*;
-use std::collections::HashMap;
+pub use bot::{Bot, BotSkill, MAX_BOTS};
+pub use crate::game::MAX_PLAYERS;
+use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
-use std::sync::mpsc::Receiver;
-use std::time::{SystemTime, UNIX_EPOCH};
-
-/// Maximum number of players in a lobby
-pub const MAX_PLAYERS: usize = 12;
+use std::path::Path;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Minimum number of players to start a game
 pub const MIN_PLAYERS: usize = 2;
 
+/// Minimum number of players when the host allows a warm-up on their own
+pub const WARM_UP_MIN_PLAYERS: usize = 1;
+
+/// Longest announcement the host can send (in characters)
+pub const MAX_ANNOUNCEMENT_LEN: usize = 80;
+
+/// Smallest handicap multiplier the host can assign
+pub const MIN_HANDICAP: f32 = 0.5;
+
+/// Largest handicap multiplier the host can assign
+pub const MAX_HANDICAP: f32 = 3.0;
+
+/// Step used when the host adjusts a handicap from the lobby screen
+pub const HANDICAP_STEP: f32 = 0.25;
+
+/// Consecutive failed sends after which the host drops a player
+pub const MAX_SEND_FAILURES: u32 = 3;
+
+/// Reason sent to a player the host removes from the lobby
+pub const KICK_REASON: &str = "Removed by the host";
+
+/// Longest status a player can set (in characters)
+pub const MAX_STATUS_LEN: usize = 16;
+
+/// Grace periods the host can pick for players who drop (in seconds; 0
+/// removes them straight away)
+pub const DISCONNECT_GRACE_CHOICES: [u64; 4] = [0, 15, 30, 60];
+
+/// Penalties the host can pick for a claim that misses outright (in points;
+/// 0 turns penalty mode off)
+pub const REJECT_PENALTY_CHOICES: [u32; 4] = [0, 1, 2, 5];
+
+/// Scores the host can pick for a player to win the round outright by
+/// reaching (0 turns the target off)
+pub const TARGET_SCORE_CHOICES: [u32; 4] = [0, 50, 100, 200];
+
+/// Most blank tiles a rack deals when the host turns blanks on
+pub const MAX_BLANKS: usize = 1;
+
+/// Most spectators a lobby admits on top of its players
+pub const MAX_SPECTATORS: usize = 8;
+
+/// Most claims a client keeps from one round's claim list; no rack makes
+/// more words than this, so anything past it is junk from a bad host
+pub const MAX_ROUND_CLAIMS: usize = 1024;
+
+/// How long a dropped player is kept (score and all) waiting to reconnect
+pub const DEFAULT_DISCONNECT_GRACE: Duration = Duration::from_secs(30);
+
+/// Shortest gap between two typing previews a client sends to the host
+pub const INPUT_PREVIEW_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Longest typing preview the host keeps (in characters)
+pub const MAX_INPUT_PREVIEW_LEN: usize = 32;
+
+/// How often the lobby browser asks the network again, so lobbies that
+/// are still up get resolved again before `PEER_TTL` runs out
+pub const BROWSE_REFRESH: Duration = Duration::from_secs(10);
+
+/// How long a client waits for the host's verdict before it will resend
+/// the same claim
+pub const CLAIM_RESEND_AFTER: Duration = Duration::from_secs(2);
+
+/// How many times a client tries to reconnect to its host before giving up
+pub const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+
+/// Wait after the first failed reconnect; it doubles after each one after
+/// that
+pub const RECONNECT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// How often the host pings its peers to check they're still there
+pub const PING_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long the host waits to hear anything (a pong will do) from a peer
+/// before dropping it as dead
+pub const PEER_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Most events the host handles from one peer per poll; the rest wait for
+/// the next poll so a flooding peer can't hold up everyone else
+pub const MAX_EVENTS_PER_PEER_PER_POLL: usize = 16;
+
+/// Snapshot of the host's connection health, for the diagnostics overlay
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Diagnostics {
+    /// How long the lobby's server has been up
+    pub uptime: Duration,
+    /// Peers currently connected
+    pub peers: usize,
+    /// Messages and bytes exchanged so far
+    pub traffic: TrafficStats,
+    /// Recent throughput, both directions
+    pub bytes_per_sec: u64,
+}
+
 /// A player in the lobby
 #[derive(Debug, Clone)]
 pub struct Player {
@@ -36,6 +141,78 @@ pub struct Player {
     pub is_local: bool,
     /// Whether this is the host
     pub is_host: bool,
+    /// Short status the player set (e.g. "brb"), shown next to their name
+    pub status: Option<String>,
+    /// Whether this is a bot played by the host
+    pub is_bot: bool,
+    /// Team the player is on in team mode (`None` plays alone)
+    pub team: Option<u8>,
+}
+
+impl Player {
+    /// Name as shown in the lobby list, followed by the player's status
+    pub fn display_name(&self) -> String {
+        match &self.status {
+            Some(status) => format!("{} · {}", self.name, status),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Why joining a lobby failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JoinError {
+    /// Nothing is listening at the host's address (host gone, wrong port)
+    Refused,
+    /// The host never answered (firewall, other network, address unreachable)
+    TimedOut,
+    /// The host didn't advertise an address to connect to
+    NoAddress,
+    /// Connected, but the join request couldn't be sent
+    HandshakeFailed(String),
+    /// Connecting failed some other way (no route, network down, ...)
+    Failed(std::io::ErrorKind),
+}
+
+impl JoinError {
+    /// Classify a failed connection attempt
+    pub fn from_connect(e: &std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::ConnectionRefused => JoinError::Refused,
+            std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock => JoinError::TimedOut,
+            kind => JoinError::Failed(kind),
+        }
+    }
+
+    /// Get a user-friendly message for the failure
+    pub fn message(&self) -> String {
+        match self {
+            JoinError::Refused => "The host refused the connection".to_string(),
+            JoinError::TimedOut => "The host didn't respond".to_string(),
+            JoinError::NoAddress => "The host didn't share an address".to_string(),
+            JoinError::HandshakeFailed(reason) => format!("Couldn't join the lobby: {}", reason),
+            JoinError::Failed(kind) => format!("Couldn't connect to the host: {}", kind),
+        }
+    }
+
+    /// What the player can try next
+    pub fn guidance(&self) -> &'static str {
+        match self {
+            JoinError::Refused => "The lobby may have closed. Refresh the browser and try again.",
+            JoinError::TimedOut => {
+                "A firewall may be blocking the game. Check you're on the same network, or connect manually by IP."
+            }
+            JoinError::NoAddress => "Try again once the lobby shows up with an address, or connect manually by IP.",
+            JoinError::HandshakeFailed(_) => "The connection dropped while joining. Try again.",
+            JoinError::Failed(_) => "Check your network connection and try again.",
+        }
+    }
+}
+
+impl std::fmt::Display for JoinError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message())
+    }
 }
 
 /// State of the lobby
@@ -56,21 +233,53 @@ pub enum LobbyEvent {
     PlayerJoined(String),
     /// A player left the lobby
     PlayerLeft(String),
+    /// A player dropped and is held for the grace period
+    PlayerAway(String),
+    /// A player who dropped reconnected within the grace period
+    PlayerReturned(String),
+    /// Someone started watching (asked to, or joined a full lobby)
+    SpectatorJoined(String),
+    /// A spectator left
+    SpectatorLeft(String),
+    /// The host seated us as a spectator: we see the game but can't claim
+    Spectating,
     /// Host rejected our join request
     JoinRejected { reason: JoinRejectReason },
+    /// The host removed us from the lobby
+    Kicked { reason: String },
+    /// The host offered a rematch (to clients, waiting for our vote; to
+    /// the host, waiting on everyone else's)
+    RematchOffered,
+    /// A player turned the rematch down
+    RematchDeclined(String),
     /// Countdown to round start
     Countdown {
         letters: Vec<char>,
         duration: u32,
         countdown: u32,
     },
+    /// The host called off the countdown
+    CountdownCancelled,
     /// The round is starting with these letters
-    RoundStart { letters: Vec<char>, duration: u32 },
+    ///
+    /// `min_letters_used` is the fewest rack letters a claim must use this
+    /// round (0 when the house rule is off). `reject_penalty` is what a claim
+    /// that misses outright costs (0 when penalty mode is off). `language` is
+    /// the language the rack was dealt in.
+    RoundStart {
+        letters: Vec<char>,
+        duration: u32,
+        min_letters_used: usize,
+        reject_penalty: u32,
+        language: Language,
+    },
     /// A claim was accepted (broadcast to all)
     ClaimAccepted {
         word: String,
         player_name: String,
         points: u32,
+        /// Combo multiplier applied to the points (1 for none)
+        combo: u32,
     },
     /// A claim was rejected (sent to requester only)
     ClaimRejected {
@@ -85,11 +294,39 @@ pub enum LobbyEvent {
         actor_id: String,
         timestamp_ms: u64,
         claim_sequence: u64,
+        /// `None` when the host predates round IDs
+        round_id: Option<i64>,
     },
     /// Score update
     ScoreUpdate { scores: Vec<(String, u32)> },
-    /// Round has ended
-    RoundEnd,
+    /// Claims accepted this round before we (re)joined it
+    RoundClaims { claims: Vec<ClaimRecord> },
+    /// Notice from the host
+    Announcement { text: String },
+    /// Extra words the host's dictionary accepts
+    HouseWords { words: Vec<String> },
+    /// Handicap multipliers for the round starting (players not listed
+    /// have none)
+    Handicaps { handicaps: HashMap<String, f32> },
+    /// A player set (or cleared) their lobby status
+    PlayerStatus {
+        player_name: String,
+        status: Option<String>,
+    },
+    /// What a player who shares their typing currently has in their input
+    /// (empty once cleared)
+    InputPreview { player_name: String, text: String },
+    /// Round has ended, and why
+    RoundEnd { reason: RoundEndReason },
+    /// History sync from another lobby member (`SyncRequest`, `SyncEvents`
+    /// or `SyncEventsCompressed`), for the app to answer from its event log
+    Sync { player_name: String, message: Message },
+    /// Connection to the host dropped; trying again (attempt `attempt` of
+    /// `MAX_RECONNECT_ATTEMPTS` failed)
+    Reconnecting { attempt: u32 },
+    /// Back in touch with the host after a drop; the round in progress is
+    /// on its way
+    Reconnected,
     /// Connection was lost
     Disconnected,
 }
@@ -104,6 +341,8 @@ pub struct HostedLobby {
     server: Server,
     /// mDNS service discovery
     discovery: ServiceDiscovery,
+    /// Player count and state in our advertisement, updated when they change
+    advertised: LobbyAdvert,
     /// Players in the lobby (including host)
     players: Vec<Player>,
     /// Mapping from socket address to player index
@@ -122,17 +361,99 @@ pub struct HostedLobby {
     round_duration: u32,
     /// Current countdown value (seconds remaining until start)
     countdown_remaining: u32,
+    /// Per-player points multipliers set by the host (missing means 1.0)
+    handicaps: HashMap<String, f32>,
+    /// Absolute time at which the current round ends (host is authoritative)
+    round_deadline: Option<Instant>,
+    /// Most recent announcement sent to the lobby
+    announcement: Option<String>,
+    /// ID of the current round, shared with clients in claim events so
+    /// devices that log the same claim can be deduplicated
+    round_id: i64,
+    /// Round whose result has already been handed out for logging
+    recorded_round: Option<i64>,
+    /// Consecutive failed sends per peer, reset on a successful send
+    send_failures: HashMap<SocketAddr, u32>,
+    /// Rack size preset picked by the host
+    rack_preset: RackPreset,
+    /// House rule: racks may deal a blank tile
+    blanks: bool,
+    /// Language racks are dealt in (and claims checked against)
+    language: Language,
+    /// House rule: words claimed in an earlier round of the match can't be
+    /// claimed again
+    no_repeats: bool,
+    /// House rule: quick successive claims build a points multiplier
+    combos: bool,
+    /// House rule: shortest word that can be claimed
+    min_word_length: usize,
+    /// House rule: how many of the rack's letters a claim must use
+    letters_used_rule: LettersUsedRule,
+    /// Penalty mode: points a claim that misses outright costs (0 for off)
+    reject_penalty: u32,
+    /// Score that ends the round as soon as a player reaches it (0 for none)
+    target_score: u32,
+    /// Word list every round's claims are checked against
+    dictionary: Arc<Dictionary>,
+    /// Players needed before the host can start a round
+    min_players: usize,
+    /// Words claimed in finished rounds of the current match (uppercase
+    /// word -> claimant)
+    match_claims: HashMap<String, String>,
+    /// ID shared by every round of the current match, so they can be rated
+    /// together
+    match_group: i64,
+    /// Server events not yet handled, queued per peer
+    inbox: EventQueue,
+    /// Seed of the rack the host previewed, used for the next rack dealt
+    first_rack_seed: Option<u64>,
+    /// Seed and config the latest rack was dealt from, so clients can deal
+    /// it themselves
+    rack_seed: Option<(u64, RackConfig)>,
+    /// Latest typing preview from each player who shares it
+    input_previews: HashMap<String, String>,
+    /// How long a dropped player is held before being removed
+    disconnect_grace: Duration,
+    /// Players who dropped and may still reconnect (name -> when they're
+    /// removed for good)
+    away: HashMap<String, Instant>,
+    /// Bots the host added, each also in `players`
+    bots: Vec<Bot>,
+    /// People watching without playing, in the order they arrived
+    spectators: Vec<String>,
+    /// Spectators' connections (address -> name)
+    spectator_addrs: HashMap<SocketAddr, String>,
+    /// Connections the host closed itself (kicked or gone silent), until
+    /// the server reports them closed
+    closed: HashSet<SocketAddr>,
+    /// When we last heard from each connection
+    last_seen: HashMap<SocketAddr, Instant>,
+    /// When we last pinged everyone
+    last_ping: Option<Instant>,
+    /// Players yet to accept the rematch on offer (`None` when there's
+    /// no offer)
+    rematch_voters: Option<HashSet<String>>,
+    /// How well the bots play
+    bot_skill: BotSkill,
+    /// Whether bots' scores go into the match result (and so Elo)
+    bots_rated: bool,
 }
 
 impl HostedLobby {
-    /// Create a new hosted lobby
+    /// Create a new hosted lobby, named from the session RNG
     pub fn new(host_name: String) -> Result<Self, String> {
-        // Generate a unique actor ID
-        let actor_id = format!("blam-{:08x}", rand::rng().random::<u32>());
+        let (actor_id, lobby_name) = with_session_rng(lobby_identity);
+        Self::start(host_name, actor_id, lobby_name)
+    }
 
-        // Generate a lobby name
-        let lobby_name = generate_lobby_name();
+    /// Create a new hosted lobby, drawing its actor ID and name from `rng`
+    pub fn new_with_rng<R: Rng>(host_name: String, rng: &mut R) -> Result<Self, String> {
+        let (actor_id, lobby_name) = lobby_identity(rng);
+        Self::start(host_name, actor_id, lobby_name)
+    }
 
+    /// Start the server and advertise the lobby
+    fn start(host_name: String, actor_id: String, lobby_name: String) -> Result<Self, String> {
         // Start the server
         let server = Server::start().map_err(|e| format!("Failed to start server: {}", e))?;
         let port = server.port();
@@ -140,8 +461,14 @@ impl HostedLobby {
         // Create mDNS discovery
         let mut discovery = ServiceDiscovery::new(actor_id.clone())?;
 
-        // Advertise our lobby
-        discovery.advertise(&host_name, Some(&lobby_name), port)?;
+        // Advertise our lobby, with just us in it
+        let advertised = LobbyAdvert {
+            players: 1,
+            max_players: MAX_PLAYERS,
+            in_progress: false,
+            dictionary: dictionary::fingerprint(),
+        };
+        discovery.advertise(&host_name, Some(&lobby_name), port, Some(advertised))?;
 
         // Add host as the first player
         let host_player = Player {
@@ -149,6 +476,9 @@ impl HostedLobby {
             ready: true,
             is_local: true,
             is_host: true,
+            status: None,
+            is_bot: false,
+            team: None,
         };
 
         Ok(Self {
@@ -156,6 +486,7 @@ impl HostedLobby {
             lobby_name,
             server,
             discovery,
+            advertised,
             players: vec![host_player],
             addr_to_player: HashMap::new(),
             player_to_addr: HashMap::new(),
@@ -165,6 +496,40 @@ impl HostedLobby {
             current_letters: Vec::new(),
             round_duration: 0,
             countdown_remaining: 0,
+            handicaps: HashMap::new(),
+            round_deadline: None,
+            announcement: None,
+            round_id: 0,
+            recorded_round: None,
+            send_failures: HashMap::new(),
+            rack_preset: RackPreset::default(),
+            blanks: false,
+            language: Language::default(),
+            no_repeats: false,
+            combos: false,
+            min_word_length: MIN_WORD_LENGTH,
+            letters_used_rule: LettersUsedRule::default(),
+            reject_penalty: 0,
+            target_score: 0,
+            dictionary: dictionary::shared(),
+            min_players: MIN_PLAYERS,
+            match_claims: HashMap::new(),
+            match_group: generate_round_id(),
+            inbox: EventQueue::new(),
+            first_rack_seed: None,
+            rack_seed: None,
+            input_previews: HashMap::new(),
+            disconnect_grace: DEFAULT_DISCONNECT_GRACE,
+            away: HashMap::new(),
+            spectators: Vec::new(),
+            spectator_addrs: HashMap::new(),
+            closed: HashSet::new(),
+            last_seen: HashMap::new(),
+            last_ping: None,
+            rematch_voters: None,
+            bots: Vec::new(),
+            bot_skill: BotSkill::default(),
+            bots_rated: false,
         })
     }
 
@@ -173,6 +538,16 @@ impl HostedLobby {
         self.server.port()
     }
 
+    /// Uptime, connections and traffic of the lobby's server
+    pub fn diagnostics(&self) -> Diagnostics {
+        Diagnostics {
+            uptime: self.server.uptime(),
+            peers: self.server.peer_count(),
+            traffic: self.server.traffic(),
+            bytes_per_sec: self.server.bytes_per_sec(),
+        }
+    }
+
     /// Get all players in the lobby
     pub fn players(&self) -> &[Player] {
         &self.players
@@ -183,304 +558,1292 @@ impl HostedLobby {
         self.players.len()
     }
 
-    /// Check if we can start the game
-    pub fn can_start(&self) -> bool {
-        self.players.len() >= MIN_PLAYERS && self.state == LobbyState::Waiting
+    /// Get a player's handicap multiplier (1.0 if none set)
+    pub fn handicap(&self, player_name: &str) -> f32 {
+        self.handicaps.get(player_name).copied().unwrap_or(1.0)
     }
 
-    /// Poll for lobby events
-    pub fn poll(&mut self) -> Vec<LobbyEvent> {
-        let mut events = Vec::new();
+    /// Get all handicaps that differ from 1.0
+    pub fn handicaps(&self) -> &HashMap<String, f32> {
+        &self.handicaps
+    }
 
-        // Poll server for new connections and messages
-        for server_event in self.server.poll() {
-            match server_event {
-                ServerEvent::PeerConnected { addr } => {
-                    // Don't add player yet - wait for Join message
-                    // Just track that someone connected
-                    let _ = addr;
-                }
-                ServerEvent::PeerDisconnected { addr, player_name } => {
-                    if let Some(idx) = self.addr_to_player.remove(&addr) {
-                        if idx < self.players.len() {
-                            let player = self.players.remove(idx);
-                            self.player_to_addr.remove(&player.name);
-                            events.push(LobbyEvent::PlayerLeft(player.name.clone()));
-
-                            // Update indices for remaining players
-                            for (_a, i) in self.addr_to_player.iter_mut() {
-                                if *i > idx {
-                                    *i -= 1;
-                                }
-                            }
-                        }
-                    } else if let Some(name) = player_name {
-                        self.player_to_addr.remove(&name);
-                        events.push(LobbyEvent::PlayerLeft(name));
-                    }
-                }
-                ServerEvent::MessageReceived { from, message, .. } => {
-                    match message {
-                        Message::Join { player_name } => {
-                            // Check if we're at capacity
-                            if self.players.len() >= MAX_PLAYERS {
-                                let _ = self.server.send_to(
-                                    from,
-                                    &Message::JoinRejected {
-                                        reason: JoinRejectReason::LobbyFull,
-                                    },
-                                );
-                                continue;
-                            }
+    /// Rack size preset for upcoming rounds
+    pub fn rack_preset(&self) -> RackPreset {
+        self.rack_preset
+    }
 
-                            // Add the player
-                            let player = Player {
-                                name: player_name.clone(),
-                                ready: true,
-                                is_local: false,
-                                is_host: false,
-                            };
-                            let idx = self.players.len();
-                            self.players.push(player);
-                            self.addr_to_player.insert(from, idx);
-                            self.player_to_addr.insert(player_name.clone(), from);
-
-                            events.push(LobbyEvent::PlayerJoined(player_name));
-                        }
-                        Message::Leave { player_name } => {
-                            if let Some(idx) = self.addr_to_player.remove(&from) {
-                                if idx < self.players.len() {
-                                    self.players.remove(idx);
-                                    // Update indices
-                                    for (_, i) in self.addr_to_player.iter_mut() {
-                                        if *i > idx {
-                                            *i -= 1;
-                                        }
-                                    }
-                                }
-                            }
-                            self.player_to_addr.remove(&player_name);
-                            events.push(LobbyEvent::PlayerLeft(player_name));
-                        }
-                        Message::ClaimAttempt { word } => {
-                            // Handle claim attempt from a player
-                            if let Some(idx) = self.addr_to_player.get(&from) {
-                                if let Some(player) = self.players.get(*idx) {
-                                    let player_name = player.name.clone();
-                                    if let Some(claim_events) =
-                                        self.handle_claim_attempt(&word, &player_name, Some(from))
-                                    {
-                                        events.extend(claim_events);
-                                    }
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        }
+    /// Switch to the next rack size preset. Takes effect from the next rack.
+    pub fn cycle_rack_preset(&mut self) -> RackPreset {
+        self.rack_preset = self.rack_preset.next();
+        self.rack_preset
+    }
 
-        events
+    /// Whether racks may deal a blank tile
+    pub fn blanks(&self) -> bool {
+        self.blanks
     }
 
-    /// Handle a claim attempt (can be called for host's own claims too)
-    fn handle_claim_attempt(
-        &mut self,
-        word: &str,
-        player_name: &str,
-        requester_addr: Option<SocketAddr>,
-    ) -> Option<Vec<LobbyEvent>> {
-        let arbitrator = self.arbitrator.as_mut()?;
+    /// Turn blank tiles on or off. Takes effect from the next rack.
+    pub fn set_blanks(&mut self, enabled: bool) {
+        self.blanks = enabled;
+    }
 
-        let result = arbitrator.try_claim(word, player_name);
+    /// Language upcoming racks are dealt in
+    pub fn language(&self) -> Language {
+        self.language
+    }
 
-        match result {
-            ClaimResult::Accepted { points, claim_sequence } => {
-                let word_upper = word.to_uppercase();
+    /// Deal racks in `language` from the next rack on, checking claims
+    /// against its word list in `data_dir`
+    ///
+    /// Fails (and keeps the current language) if the word list can't be
+    /// loaded.
+    pub fn set_language(&mut self, language: Language, data_dir: &Path) -> Result<(), String> {
+        self.dictionary = dictionary::for_language(language, data_dir)?;
+        self.language = language;
+        Ok(())
+    }
 
-                // Get timestamp for CRDT event
-                let timestamp_ms = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .map(|d| d.as_millis() as u64)
-                    .unwrap_or(0);
+    /// Switch to the next language with a word list in `data_dir`
+    ///
+    /// English is always available, so this always lands somewhere.
+    pub fn cycle_language(&mut self, data_dir: &Path) -> Language {
+        let mut language = self.language.next();
+        while self.set_language(language, data_dir).is_err() {
+            language = language.next();
+        }
+        self.language
+    }
 
-                // Broadcast ClaimAccepted to all clients
-                let msg = Message::ClaimAccepted {
-                    word: word_upper.clone(),
-                    player_name: player_name.to_string(),
-                    points,
-                };
-                self.server.broadcast(&msg);
+    /// Language of the round in progress (or the next one, between rounds)
+    fn round_language(&self) -> Language {
+        match &self.arbitrator {
+            Some(arbitrator) => arbitrator.dictionary().language(),
+            None => self.language,
+        }
+    }
 
-                // Broadcast WordClaimed for CRDT log
-                let crdt_msg = Message::WordClaimed {
-                    word: word_upper.clone(),
-                    player_name: player_name.to_string(),
-                    points,
-                    actor_id: self.actor_id.clone(),
-                    timestamp_ms,
-                    claim_sequence,
-                };
-                self.server.broadcast(&crdt_msg);
+    /// Whether words from earlier rounds of the match are off limits
+    pub fn no_repeats(&self) -> bool {
+        self.no_repeats
+    }
 
-                // Also broadcast updated scores
-                let scores = arbitrator.scores();
-                let score_msg = Message::ScoreUpdate { scores: scores.clone() };
-                self.server.broadcast(&score_msg);
+    /// Turn the no-repeats house rule on or off. Takes effect from the next
+    /// round; claims from earlier rounds are remembered either way.
+    pub fn set_no_repeats(&mut self, enabled: bool) {
+        self.no_repeats = enabled;
+    }
 
-                Some(vec![
-                    LobbyEvent::ClaimAccepted {
-                        word: word_upper.clone(),
-                        player_name: player_name.to_string(),
-                        points,
-                    },
-                    LobbyEvent::WordClaimed {
-                        word: word_upper,
-                        player_name: player_name.to_string(),
-                        points,
-                        actor_id: self.actor_id.clone(),
-                        timestamp_ms,
-                        claim_sequence,
-                    },
-                    LobbyEvent::ScoreUpdate { scores },
-                ])
-            }
-            ClaimResult::AlreadyClaimed { by } => {
-                let reason = ClaimRejectReason::AlreadyClaimed { by };
-                self.send_rejection(word, &reason, requester_addr);
-                Some(vec![LobbyEvent::ClaimRejected {
-                    word: word.to_uppercase(),
-                    reason,
-                }])
-            }
-            ClaimResult::TooShort => {
-                let reason = ClaimRejectReason::TooShort;
-                self.send_rejection(word, &reason, requester_addr);
-                Some(vec![LobbyEvent::ClaimRejected {
-                    word: word.to_uppercase(),
-                    reason,
-                }])
-            }
-            ClaimResult::InvalidLetters { missing } => {
-                let reason = ClaimRejectReason::InvalidLetters { missing };
-                self.send_rejection(word, &reason, requester_addr);
-                Some(vec![LobbyEvent::ClaimRejected {
-                    word: word.to_uppercase(),
-                    reason,
-                }])
-            }
-            ClaimResult::NotInDictionary => {
-                let reason = ClaimRejectReason::NotInDictionary;
-                self.send_rejection(word, &reason, requester_addr);
-                Some(vec![LobbyEvent::ClaimRejected {
-                    word: word.to_uppercase(),
-                    reason,
-                }])
-            }
-            ClaimResult::RoundEnded => {
-                let reason = ClaimRejectReason::RoundEnded;
-                self.send_rejection(word, &reason, requester_addr);
-                Some(vec![LobbyEvent::ClaimRejected {
-                    word: word.to_uppercase(),
-                    reason,
-                }])
-            }
-        }
+    /// Whether quick successive claims build a combo multiplier
+    pub fn combos(&self) -> bool {
+        self.combos
     }
 
-    /// Send rejection message to a specific client
-    fn send_rejection(
-        &self,
-        word: &str,
-        reason: &ClaimRejectReason,
-        requester_addr: Option<SocketAddr>,
-    ) {
-        if let Some(addr) = requester_addr {
-            let msg = Message::ClaimRejected {
-                word: word.to_uppercase(),
-                reason: reason.clone(),
-            };
-            let _ = self.server.send_to(addr, &msg);
-        }
+    /// Turn combo multipliers on or off. Takes effect from the next round.
+    pub fn set_combos(&mut self, enabled: bool) {
+        self.combos = enabled;
     }
 
-    /// Host submits a claim (called from local gameplay)
-    pub fn host_claim(&mut self, word: &str) -> Option<Vec<LobbyEvent>> {
-        self.handle_claim_attempt(word, &self.host_name.clone(), None)
+    /// Shortest word that can be claimed
+    pub fn min_word_length(&self) -> usize {
+        self.min_word_length
     }
 
-    /// End the current round
-    pub fn end_round(&mut self) -> Vec<LobbyEvent> {
-        if let Some(arbitrator) = &mut self.arbitrator {
-            arbitrator.end_round();
-        }
-        self.state = LobbyState::Waiting;
+    /// Step the minimum word length up to `MAX_MIN_WORD_LENGTH`, then back
+    /// to `MIN_WORD_LENGTH`. Takes effect from the next round.
+    pub fn cycle_min_word_length(&mut self) -> usize {
+        self.min_word_length = if self.min_word_length >= MAX_MIN_WORD_LENGTH {
+            MIN_WORD_LENGTH
+        } else {
+            self.min_word_length + 1
+        };
+        self.min_word_length
+    }
 
-        // Broadcast round end to all clients
-        self.server.broadcast(&Message::RoundEnd);
+    /// How many of the rack's letters a claim must use
+    pub fn letters_used_rule(&self) -> LettersUsedRule {
+        self.letters_used_rule
+    }
 
-        // Get final scores
-        let scores = self
-            .arbitrator
-            .as_ref()
-            .map(|a| a.scores())
-            .unwrap_or_default();
+    /// Step to the next letters-used rule (wrapping back to off). Takes
+    /// effect from the next round.
+    pub fn cycle_letters_used_rule(&mut self) -> LettersUsedRule {
+        self.letters_used_rule = self.letters_used_rule.next();
+        self.letters_used_rule
+    }
 
-        vec![
-            LobbyEvent::RoundEnd,
-            LobbyEvent::ScoreUpdate { scores },
-        ]
+    /// Fewest rack letters a claim must use on the current rack (0 for none)
+    pub fn min_letters_used(&self) -> usize {
+        match &self.arbitrator {
+            Some(arbitrator) => arbitrator.min_letters_used(),
+            None => self.letters_used_rule.threshold(self.current_letters.len()),
+        }
     }
 
-    /// Get current scores
-    pub fn scores(&self) -> Vec<(String, u32)> {
-        self.arbitrator
-            .as_ref()
-            .map(|a| a.scores())
-            .unwrap_or_default()
+    /// Points a claim that misses outright costs (0 when penalty mode is off)
+    pub fn reject_penalty(&self) -> u32 {
+        self.reject_penalty
     }
 
-    /// Start the countdown sequence (3-2-1-BLAM!)
-    /// Returns the initial countdown value
-    pub fn start_countdown(&mut self, letters: Vec<char>, duration: u32) -> u32 {
-        const COUNTDOWN_SECONDS: u32 = 3;
+    /// Set the penalty for a claim that misses outright (0 turns it off).
+    /// Takes effect from the next round.
+    pub fn set_reject_penalty(&mut self, points: u32) {
+        self.reject_penalty = points;
+    }
 
-        self.current_letters = letters.clone();
-        self.round_duration = duration;
-        self.countdown_remaining = COUNTDOWN_SECONDS;
-        self.state = LobbyState::Countdown(COUNTDOWN_SECONDS);
+    /// Step to the next of `REJECT_PENALTY_CHOICES` (wrapping back to off)
+    pub fn cycle_reject_penalty(&mut self) -> u32 {
+        let idx = REJECT_PENALTY_CHOICES
+            .iter()
+            .position(|p| *p == self.reject_penalty)
+            .map_or(0, |i| (i + 1) % REJECT_PENALTY_CHOICES.len());
+        self.reject_penalty = REJECT_PENALTY_CHOICES[idx];
+        self.reject_penalty
+    }
 
-        // Broadcast countdown to all clients
-        let msg = Message::Countdown {
-            letters,
-            duration_secs: duration,
-            countdown_secs: COUNTDOWN_SECONDS,
-        };
-        self.server.broadcast(&msg);
+    /// Score that ends the round when a player reaches it (0 when off)
+    pub fn target_score(&self) -> u32 {
+        self.target_score
+    }
 
-        COUNTDOWN_SECONDS
+    /// Set the score that ends the round when a player reaches it (0 turns
+    /// it off). Applies to claims from then on.
+    pub fn set_target_score(&mut self, points: u32) {
+        self.target_score = points;
     }
 
-    /// Tick the countdown, returns true if countdown finished and round should start
-    pub fn tick_countdown(&mut self) -> Option<LobbyEvent> {
-        if let LobbyState::Countdown(count) = &mut self.state {
-            if *count > 1 {
-                *count -= 1;
-                self.countdown_remaining = *count;
+    /// Step to the next of `TARGET_SCORE_CHOICES` (wrapping back to off)
+    pub fn cycle_target_score(&mut self) -> u32 {
+        let idx = TARGET_SCORE_CHOICES
+            .iter()
+            .position(|p| *p == self.target_score)
+            .map_or(0, |i| (i + 1) % TARGET_SCORE_CHOICES.len());
+        self.target_score = TARGET_SCORE_CHOICES[idx];
+        self.target_score
+    }
 
-                // Broadcast updated countdown
-                let msg = Message::Countdown {
-                    letters: self.current_letters.clone(),
-                    duration_secs: self.round_duration,
-                    countdown_secs: *count,
-                };
-                self.server.broadcast(&msg);
+    /// Penalty in force for the current round
+    fn round_reject_penalty(&self) -> u32 {
+        match &self.arbitrator {
+            Some(arbitrator) => arbitrator.reject_penalty(),
+            None => self.reject_penalty,
+        }
+    }
 
-                Some(LobbyEvent::Countdown {
+    /// How long a dropped player is held waiting to reconnect
+    pub fn disconnect_grace(&self) -> Duration {
+        self.disconnect_grace
+    }
+
+    /// Set how long a dropped player is held (zero removes them at once)
+    pub fn set_disconnect_grace(&mut self, grace: Duration) {
+        self.disconnect_grace = grace;
+    }
+
+    /// Step to the next of `DISCONNECT_GRACE_CHOICES`
+    pub fn cycle_disconnect_grace(&mut self) -> Duration {
+        let current = self.disconnect_grace.as_secs();
+        let next = DISCONNECT_GRACE_CHOICES
+            .iter()
+            .copied()
+            .find(|&secs| secs > current)
+            .unwrap_or(DISCONNECT_GRACE_CHOICES[0]);
+        self.disconnect_grace = Duration::from_secs(next);
+        self.disconnect_grace
+    }
+
+    /// Whether a player dropped and is being held for the grace period
+    pub fn is_away(&self, player_name: &str) -> bool {
+        self.away.contains_key(player_name)
+    }
+
+    /// Players needed before a round can start
+    pub fn min_players(&self) -> usize {
+        self.min_players
+    }
+
+    /// Allow (or stop allowing) the host to start a round on their own
+    ///
+    /// A one-player round is a warm-up: its result has a single score, so
+    /// it counts toward stats like a solo round and never moves Elo.
+    pub fn set_warm_up(&mut self, enabled: bool) {
+        self.min_players = if enabled { WARM_UP_MIN_PLAYERS } else { MIN_PLAYERS };
+    }
+
+    /// People watching without playing
+    pub fn spectators(&self) -> &[String] {
+        &self.spectators
+    }
+
+    /// Bots in the lobby
+    pub fn bots(&self) -> &[Bot] {
+        &self.bots
+    }
+
+    /// Add a bot, named after the first free "Bot N"
+    ///
+    /// Returns `None` (and adds nothing) while a round is on, once there are
+    /// `MAX_BOTS` bots or when the lobby is full.
+    pub fn add_bot(&mut self) -> Option<LobbyEvent> {
+        if self.state != LobbyState::Waiting
+            || self.bots.len() >= MAX_BOTS
+            || self.players.len() >= MAX_PLAYERS
+        {
+            return None;
+        }
+        let name = (1..)
+            .map(bot::bot_name)
+            .find(|name| self.players.iter().all(|p| p.name != *name))?;
+        self.players.push(Player {
+            name: name.clone(),
+            ready: true,
+            is_local: false,
+            is_host: false,
+            status: None,
+            is_bot: true,
+            team: None,
+        });
+        self.bots.push(Bot::new(name.clone()));
+        Some(LobbyEvent::PlayerJoined(name))
+    }
+
+    /// Add up to `count` bots, returning how many were added
+    pub fn add_bots(&mut self, count: usize) -> usize {
+        (0..count).map_while(|_| self.add_bot()).count()
+    }
+
+    /// Remove the most recently added bot
+    pub fn remove_bot(&mut self) -> Option<LobbyEvent> {
+        if self.state != LobbyState::Waiting {
+            return None;
+        }
+        let bot = self.bots.pop()?;
+        let idx = self.players.iter().position(|p| p.name == bot.name)?;
+        Some(self.remove_player(idx))
+    }
+
+    /// Add another bot, or remove them all once `MAX_BOTS` are in
+    pub fn cycle_bots(&mut self) -> usize {
+        if self.add_bot().is_none() {
+            while self.remove_bot().is_some() {}
+        }
+        self.bots.len()
+    }
+
+    /// How well the bots play
+    pub fn bot_skill(&self) -> BotSkill {
+        self.bot_skill
+    }
+
+    /// Step to the next bot skill (wrapping). Takes effect from the next
+    /// round.
+    pub fn cycle_bot_skill(&mut self) -> BotSkill {
+        self.bot_skill = self.bot_skill.next();
+        self.bot_skill
+    }
+
+    /// Whether bots' scores count toward Elo
+    pub fn bots_rated(&self) -> bool {
+        self.bots_rated
+    }
+
+    /// Count (or stop counting) bots' scores toward Elo
+    ///
+    /// Unrated bots still score and show on the scoreboard; they're just
+    /// left out of the match result, so a host playing only bots gets a
+    /// solo result that never moves Elo.
+    pub fn set_bots_rated(&mut self, rated: bool) {
+        self.bots_rated = rated;
+    }
+
+    fn is_bot(&self, player_name: &str) -> bool {
+        self.bots.iter().any(|b| b.name == player_name)
+    }
+
+    /// Forget claims from earlier rounds, so the next round starts a new match
+    ///
+    /// A rematch carries on the same match; only a new lobby or the host
+    /// asking for a fresh match clears them.
+    pub fn start_new_match(&mut self) {
+        self.match_claims.clear();
+        self.match_group = generate_round_id();
+    }
+
+    /// Generate letters for the next round using the host's rack preset
+    ///
+    /// The rack is dealt from a fresh seed (drawn from the session RNG, so
+    /// `BLAM_SEED` replays it), which is all clients are sent at round start.
+    ///
+    /// The first call after `set_first_rack_seed` deals the previewed rack.
+    pub fn generate_letters(&mut self) -> Vec<char> {
+        let config = self.rack_config();
+        let seed = match self.first_rack_seed.take() {
+            Some(seed) => seed,
+            None => with_session_rng(|rng| rng.random()),
+        };
+        self.rack_seed = Some((seed, config));
+        config.rack_for_seed(seed).letters().to_vec()
+    }
+
+    /// How the next round's rack is dealt: the preset, in the lobby's
+    /// language, with blanks if they're on
+    pub fn rack_config(&self) -> RackConfig {
+        rack_config(self.rack_preset, self.blanks, self.language)
+    }
+
+    /// How a new lobby deals its racks until the host changes its settings
+    ///
+    /// Lets a rack be previewed before the lobby exists.
+    pub fn default_rack_config() -> RackConfig {
+        rack_config(RackPreset::default(), false, Language::default())
+    }
+
+    /// The message starting a round on `letters` with `duration_secs` left
+    ///
+    /// Sends just the seed when `letters` is the rack it deals in the
+    /// round's language, and the letters themselves otherwise (e.g. a rack
+    /// the caller picked).
+    fn round_start_message(&self, letters: &[char], duration_secs: u32) -> Message {
+        let min_letters_used = self.min_letters_used() as u32;
+        let reject_penalty = self.round_reject_penalty();
+        let language = self.round_language();
+        match self.rack_seed {
+            Some((seed, rack))
+                if rack.language() == language
+                    && rack.rack_for_seed(seed).letters() == letters =>
+            {
+                Message::RoundStartSeeded {
+                    seed,
+                    config: RoundConfig { rack, min_letters_used, reject_penalty },
+                    duration_secs,
+                }
+            }
+            _ => Message::RoundStart {
+                letters: letters.to_vec(),
+                duration_secs,
+                min_letters_used,
+                reject_penalty,
+                language,
+            },
+        }
+    }
+
+    /// The countdown to the next round, `countdown_secs` from its start
+    ///
+    /// Like `round_start_message`, sends just the seed when the round's
+    /// letters are the rack it deals.
+    fn countdown_message(&self, countdown_secs: u32) -> Message {
+        let duration_secs = self.round_duration;
+        match self.rack_seed {
+            Some((seed, rack))
+                if rack.language() == self.round_language()
+                    && rack.rack_for_seed(seed).letters() == self.current_letters =>
+            {
+                Message::CountdownSeeded { seed, rack, duration_secs, countdown_secs }
+            }
+            _ => Message::Countdown {
+                letters: self.current_letters.clone(),
+                duration_secs,
+                countdown_secs,
+            },
+        }
+    }
+
+    /// The handicaps the host scores this round with, for players to apply
+    /// too
+    fn handicaps_message(&self) -> Message {
+        let mut handicaps: Vec<(String, u32)> = self
+            .handicaps
+            .iter()
+            .map(|(name, multiplier)| (name.clone(), (multiplier * 100.0).round() as u32))
+            .collect();
+        handicaps.sort();
+        Message::Handicaps { handicaps }
+    }
+
+    /// Deal the rack for `seed` (as previewed before hosting) next
+    pub fn set_first_rack_seed(&mut self, seed: u64) {
+        self.first_rack_seed = Some(seed);
+    }
+
+    /// Set a player's handicap, clamped to `MIN_HANDICAP..=MAX_HANDICAP`.
+    /// Takes effect from the next round.
+    pub fn set_handicap(&mut self, player_name: &str, multiplier: f32) {
+        let multiplier = multiplier.clamp(MIN_HANDICAP, MAX_HANDICAP);
+        if (multiplier - 1.0).abs() < f32::EPSILON {
+            self.handicaps.remove(player_name);
+        } else {
+            self.handicaps.insert(player_name.to_string(), multiplier);
+        }
+    }
+
+    /// Put a player on a team (`None` to play alone), for 2v2 or 3v3
+    /// games. Takes effect from the next round.
+    ///
+    /// Returns false if nobody by that name is in the lobby.
+    pub fn assign_team(&mut self, player_name: &str, team: Option<u8>) -> bool {
+        match self.players.iter_mut().find(|p| p.name == player_name) {
+            Some(player) => {
+                player.team = team;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Nudge a player's handicap up or down by `HANDICAP_STEP`
+    pub fn adjust_handicap(&mut self, player_name: &str, increase: bool) {
+        let step = if increase { HANDICAP_STEP } else { -HANDICAP_STEP };
+        self.set_handicap(player_name, self.handicap(player_name) + step);
+    }
+
+    /// Build the arbitrator for a new round with current players and handicaps
+    ///
+    /// Under the no-repeats rule, words from earlier rounds of the match are
+    /// seeded in as already claimed.
+    fn new_arbitrator(&self, letters: Vec<char>) -> RoundArbitrator {
+        let player_names: Vec<String> = self.players.iter().map(|p| p.name.clone()).collect();
+        let min_letters_used = self.letters_used_rule.threshold(letters.len());
+        let mut arbitrator =
+            RoundArbitrator::with_dictionary(letters, &player_names, Arc::clone(&self.dictionary));
+        for (name, multiplier) in &self.handicaps {
+            arbitrator.set_multiplier(name, *multiplier);
+        }
+        for name in self.away.keys() {
+            arbitrator.set_away(name, true);
+        }
+        for player in &self.players {
+            arbitrator.set_team(&player.name, player.team);
+        }
+        if self.no_repeats {
+            arbitrator.seed_claimed_words(&self.match_claims);
+        }
+        arbitrator.set_combos(self.combos);
+        arbitrator.set_min_word_length(self.min_word_length);
+        arbitrator.set_min_letters_used(min_letters_used);
+        arbitrator.set_reject_penalty(self.reject_penalty);
+        arbitrator
+    }
+
+    /// Check if we can start the game
+    ///
+    /// Players who dropped and haven't reconnected don't count.
+    pub fn can_start(&self) -> bool {
+        self.players.len() - self.away.len() >= self.min_players && self.state == LobbyState::Waiting
+    }
+
+    /// Result of the round just played, ready to be logged as a `match_end`
+    /// event
+    ///
+    /// Built from raw scores so handicaps and combos don't reach Elo, with
+    /// each player's team (if any) so teams are rated as sides.
+    /// Bots are left out unless they're rated.
+    /// Returns `None` while a round is still running or before the first.
+    pub fn match_result(&self, timestamp_ms: i64, host_counter: u64) -> Option<MatchResult> {
+        let arbitrator = self.arbitrator.as_ref().filter(|a| !a.is_active())?;
+        let rated = |name: &String| self.bots_rated || !self.is_bot(name);
+        let scores = arbitrator.raw_scores().into_iter().filter(|(name, _)| rated(name)).collect();
+        let teams = arbitrator.teams().into_iter().filter(|(name, _)| rated(name)).collect();
+        let result =
+            MatchResult::with_host_counter(timestamp_ms, host_counter, scores, self.actor_id.clone());
+        Some(result.for_round(self.round_id).in_match_group(self.match_group).with_teams(teams))
+    }
+
+    /// Whether the last round has ended and its result hasn't been taken
+    pub fn result_pending(&self) -> bool {
+        self.recorded_round != Some(self.round_id)
+            && self.arbitrator.as_ref().is_some_and(|a| !a.is_active())
+    }
+
+    /// Result of the round just played, the first time it's asked for
+    ///
+    /// Every frontend logs hosted rounds through this, so each is logged
+    /// once however (and however often) it notices the round has ended.
+    pub fn take_match_result(
+        &mut self,
+        timestamp_ms: i64,
+        host_counter: u64,
+    ) -> Option<MatchResult> {
+        if !self.result_pending() {
+            return None;
+        }
+        let result = self.match_result(timestamp_ms, host_counter)?;
+        self.recorded_round = Some(self.round_id);
+        Some(result)
+    }
+
+    /// Poll for lobby events
+    pub fn poll(&mut self) -> Vec<LobbyEvent> {
+        // End an expired round before looking at any incoming claims, so a
+        // claim that arrives after the deadline can never sneak in
+        let now = Instant::now();
+        let mut events = self.expire_round_if_due(now);
+        events.extend(self.expire_away_players(now));
+
+        // Stay discoverable after a network change. If re-registering fails,
+        // players already connected are unaffected, so carry on regardless
+        let _ = self.discovery.keep_advertised();
+
+        // Poll server for new connections and messages. Events are handled
+        // one at a time, so each claim is settled (and recorded by the
+        // arbitrator) before the next one in the same batch is looked at.
+        // Peers take turns, and a busy peer's overflow waits for the next
+        // poll.
+        for server_event in self.server.poll() {
+            self.note_liveness(&server_event, now);
+            // A peer that has flooded us past its queue is cut off
+            let addr = server_event.addr();
+            if !self.inbox.push(server_event) {
+                self.server.drop_peer(addr);
+            }
+        }
+        for server_event in self.inbox.next_batch(MAX_EVENTS_PER_PEER_PER_POLL) {
+            events.extend(self.handle_server_event(server_event));
+        }
+        events.extend(self.check_liveness(now));
+        events.extend(self.start_rematch_if_agreed());
+        events.extend(self.play_bots(Instant::now()));
+        self.keep_advert_current();
+
+        events
+    }
+
+    /// What browsers should see about the lobby right now
+    fn lobby_advert(&self) -> LobbyAdvert {
+        LobbyAdvert {
+            players: self.players.len(),
+            max_players: MAX_PLAYERS,
+            in_progress: self.state == LobbyState::Starting,
+            dictionary: self.dictionary.fingerprint(),
+        }
+    }
+
+    /// Update our advertisement if players joined or left, or a round
+    /// started or ended, since we last did, so browsers can tell a full or
+    /// busy lobby
+    fn keep_advert_current(&mut self) {
+        let advert = self.lobby_advert();
+        if advert == self.advertised {
+            return;
+        }
+        // Tried again on the next poll if it fails
+        if self.discovery.update_advertisement(advert).is_ok() {
+            self.advertised = advert;
+        }
+    }
+
+    /// Keep track of when each connection was last heard from
+    fn note_liveness(&mut self, event: &ServerEvent, now: Instant) {
+        match event {
+            ServerEvent::PeerConnected { addr }
+            | ServerEvent::MessageReceived { from: addr, .. }
+            | ServerEvent::FrameRejected { from: addr, .. } => {
+                self.last_seen.insert(*addr, now);
+            }
+            ServerEvent::PeerDisconnected { addr, .. } => {
+                self.last_seen.remove(addr);
+            }
+        }
+    }
+
+    /// Ping everyone every `PING_INTERVAL`, and drop connections we haven't
+    /// heard from in `PEER_TIMEOUT`
+    ///
+    /// A client that loses power never says goodbye, and its connection
+    /// can stay open for a long time; this is what notices. A silent player
+    /// is removed straight away (`PlayerLeft`): they've had longer than any
+    /// grace period to answer.
+    fn check_liveness(&mut self, now: Instant) -> Vec<LobbyEvent> {
+        if self.last_ping.is_none_or(|at| now.saturating_duration_since(at) >= PING_INTERVAL) {
+            self.broadcast(&Message::Ping);
+            self.last_ping = Some(now);
+        }
+
+        let silent: Vec<SocketAddr> = self
+            .last_seen
+            .iter()
+            .filter(|(_, seen)| now.saturating_duration_since(**seen) >= PEER_TIMEOUT)
+            .map(|(addr, _)| *addr)
+            .collect();
+        silent.into_iter().filter_map(|addr| self.drop_silent_peer(addr)).collect()
+    }
+
+    /// Close a connection that stopped answering, and let go of whoever
+    /// was on it
+    fn drop_silent_peer(&mut self, addr: SocketAddr) -> Option<LobbyEvent> {
+        self.last_seen.remove(&addr);
+        self.send_failures.remove(&addr);
+        if self.server.drop_peer(addr) {
+            self.closed.insert(addr);
+        }
+        if let Some(event) = self.remove_spectator(addr) {
+            return Some(event);
+        }
+        let idx = self.addr_to_player.remove(&addr)?;
+        Some(self.remove_player(idx))
+    }
+
+    /// Let every bot that's due claim a word
+    ///
+    /// Claims go through `handle_claim_attempt` like a remote player's. A
+    /// bot has nobody to tell about a rejected claim, so rejections (say, a
+    /// word a player claimed first) are dropped.
+    fn play_bots(&mut self, now: Instant) -> Vec<LobbyEvent> {
+        let Some(arbitrator) = self.arbitrator.as_ref().filter(|a| a.is_active()) else {
+            return Vec::new();
+        };
+        if !self.bots.iter().any(|b| b.is_due(now)) {
+            return Vec::new();
+        }
+        let solution = solver::solve_rack_in(&self.dictionary, &self.current_letters);
+        let min_length = arbitrator.min_word_length().max(arbitrator.min_letters_used());
+
+        let mut events = Vec::new();
+        for i in 0..self.bots.len() {
+            if !self.bots[i].is_due(now) {
+                continue;
+            }
+            let Some(arbitrator) = &self.arbitrator else { break };
+            let skill = self.bot_skill;
+            let claimed = arbitrator.claimed_words();
+            let bot = &mut self.bots[i];
+            let picked = with_session_rng(|rng| {
+                bot.pick_word(&solution.words, claimed, min_length, skill, now, rng)
+            });
+            let Some(word) = picked else { continue };
+            let name = self.bots[i].name.clone();
+            if let Some(claim_events) = self.handle_claim_attempt(&word, &name, None) {
+                events.extend(
+                    claim_events
+                        .into_iter()
+                        .filter(|e| !matches!(e, LobbyEvent::ClaimRejected { .. })),
+                );
+            }
+        }
+        events
+    }
+
+    /// Handle one event from the server
+    fn handle_server_event(&mut self, server_event: ServerEvent) -> Vec<LobbyEvent> {
+        let mut events = Vec::new();
+        match server_event {
+            ServerEvent::PeerConnected { addr } => {
+                // Don't add player yet - wait for Join message
+                // Just track that someone connected
+                let _ = addr;
+            }
+            ServerEvent::PeerDisconnected { addr, player_name } => {
+                self.send_failures.remove(&addr);
+                if self.closed.remove(&addr) {
+                    // Already reported as left when we closed it
+                } else if let Some(event) = self.remove_spectator(addr) {
+                    events.push(event);
+                } else if let Some(idx) = self.addr_to_player.remove(&addr) {
+                    if idx < self.players.len() {
+                        let name = self.players[idx].name.clone();
+                        self.player_to_addr.remove(&name);
+                        if self.disconnect_grace.is_zero() {
+                            events.push(self.remove_player(idx));
+                        } else {
+                            // Hold their place (and points) in case they're back soon
+                            self.away.insert(name.clone(), Instant::now() + self.disconnect_grace);
+                            self.input_previews.remove(&name);
+                            if let Some(arbitrator) = &mut self.arbitrator {
+                                arbitrator.set_away(&name, true);
+                            }
+                            events.push(LobbyEvent::PlayerAway(name));
+                        }
+                    }
+                } else if let Some(name) = player_name {
+                    self.player_to_addr.remove(&name);
+                    self.input_previews.remove(&name);
+                    events.push(LobbyEvent::PlayerLeft(name));
+                }
+            }
+            // Counted in the traffic stats; the next frame may well be fine
+            ServerEvent::FrameRejected { .. } => {}
+            ServerEvent::MessageReceived { from, message, .. } => {
+                match message {
+                    Message::Join { player_name } if self.is_away(&player_name) => {
+                        events.extend(self.rejoin(from, player_name));
+                    }
+                    Message::Join { player_name } => {
+                        // Once every seat is taken, newcomers get to watch
+                        if self.players.len() >= MAX_PLAYERS {
+                            events.extend(self.admit_spectator(from, player_name));
+                            return events;
+                        }
+
+                        // Add the player
+                        let player = Player {
+                            name: player_name.clone(),
+                            ready: true,
+                            is_local: false,
+                            is_host: false,
+                            status: None,
+                            is_bot: false,
+                            team: None,
+                        };
+                        let idx = self.players.len();
+                        self.players.push(player);
+                        self.addr_to_player.insert(from, idx);
+                        self.player_to_addr.insert(player_name.clone(), from);
+                        self.welcome(from);
+
+                        events.push(LobbyEvent::PlayerJoined(player_name));
+                    }
+                    Message::JoinSpectator { name } => {
+                        if !self.addr_to_player.contains_key(&from) {
+                            events.extend(self.admit_spectator(from, name));
+                        }
+                    }
+                    Message::Leave { .. } if self.spectator_addrs.contains_key(&from) => {
+                        events.extend(self.remove_spectator(from));
+                    }
+                    Message::Leave { player_name } => {
+                        if let Some(idx) = self.addr_to_player.remove(&from) {
+                            if idx < self.players.len() {
+                                self.players.remove(idx);
+                                // Update indices
+                                for (_, i) in self.addr_to_player.iter_mut() {
+                                    if *i > idx {
+                                        *i -= 1;
+                                    }
+                                }
+                            }
+                        }
+                        self.player_to_addr.remove(&player_name);
+                        self.input_previews.remove(&player_name);
+                        events.push(LobbyEvent::PlayerLeft(player_name));
+                    }
+                    Message::SetStatus { status } => {
+                        if let Some(idx) = self.addr_to_player.get(&from).copied() {
+                            events.extend(self.update_status(idx, &status));
+                        }
+                    }
+                    Message::InputPreview { text } => {
+                        if let Some(idx) = self.addr_to_player.get(&from).copied() {
+                            events.extend(self.update_input_preview(idx, &text));
+                        }
+                    }
+                    Message::Resync => {
+                        if self.addr_to_player.contains_key(&from)
+                            || self.spectator_addrs.contains_key(&from)
+                        {
+                            self.send_round_state(from);
+                        }
+                    }
+                    message @ (Message::SyncRequest { .. }
+                    | Message::SyncEvents { .. }
+                    | Message::SyncEventsCompressed { .. }) => {
+                        let player = self.addr_to_player.get(&from).and_then(|i| self.players.get(*i));
+                        if let Some(player) = player {
+                            events.push(LobbyEvent::Sync {
+                                player_name: player.name.clone(),
+                                message,
+                            });
+                        }
+                    }
+                    Message::RematchVote { accept } => {
+                        let player = self.addr_to_player.get(&from).and_then(|i| self.players.get(*i));
+                        if let Some(name) = player.map(|p| p.name.clone()) {
+                            events.extend(self.record_rematch_vote(name, accept));
+                        }
+                    }
+                    Message::ClaimAttempt { word } => {
+                        // Handle claim attempt from a player
+                        if let Some(idx) = self.addr_to_player.get(&from) {
+                            if let Some(player) = self.players.get(*idx) {
+                                let player_name = player.name.clone();
+                                if let Some(claim_events) =
+                                    self.handle_claim_attempt(&word, &player_name, Some(from))
+                                {
+                                    events.extend(claim_events);
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        events
+    }
+
+    /// Catch a player who just (re)joined up on the lobby
+    fn welcome(&mut self, to: SocketAddr) {
+        // The host's dictionary decides claims, so tell the newcomer which
+        // extra words it accepts
+        let words = dictionary::house_words();
+        if !words.is_empty() {
+            self.send_to(to, &Message::HouseWords { words });
+        }
+
+        // Statuses set before they joined
+        let statuses: Vec<Message> = self
+            .players
+            .iter()
+            .filter_map(|p| {
+                Some(Message::PlayerStatus {
+                    player_name: p.name.clone(),
+                    status: p.status.clone()?,
+                })
+            })
+            .collect();
+        for msg in statuses {
+            self.send_to(to, &msg);
+        }
+    }
+
+    /// Let someone watch the lobby without a seat
+    ///
+    /// They hear everything the players do (countdowns, claims, scores) and
+    /// are caught up on a round in progress, but their claims are ignored.
+    /// Turned away as `LobbyFull` once there are `MAX_SPECTATORS`.
+    fn admit_spectator(&mut self, from: SocketAddr, name: String) -> Option<LobbyEvent> {
+        if self.spectators.len() >= MAX_SPECTATORS {
+            self.send_to(from, &Message::JoinRejected { reason: JoinRejectReason::LobbyFull });
+            return None;
+        }
+        self.spectators.push(name.clone());
+        self.spectator_addrs.insert(from, name.clone());
+        self.send_to(from, &Message::JoinSpectator { name: name.clone() });
+        self.welcome(from);
+        self.send_round_state(from);
+        Some(LobbyEvent::SpectatorJoined(name))
+    }
+
+    /// Forget the spectator at `addr`, if it is one
+    fn remove_spectator(&mut self, addr: SocketAddr) -> Option<LobbyEvent> {
+        let name = self.spectator_addrs.remove(&addr)?;
+        if let Some(idx) = self.spectators.iter().position(|s| *s == name) {
+            self.spectators.remove(idx);
+        }
+        Some(LobbyEvent::SpectatorLeft(name))
+    }
+
+    /// Give a player who dropped their place back
+    ///
+    /// If a round is running they're dropped straight back into it, with
+    /// the time that's left, the claims accepted so far and the scores.
+    fn rejoin(&mut self, from: SocketAddr, player_name: String) -> Option<LobbyEvent> {
+        let idx = self.players.iter().position(|p| p.name == player_name)?;
+        self.away.remove(&player_name);
+        self.addr_to_player.insert(from, idx);
+        self.player_to_addr.insert(player_name.clone(), from);
+        self.welcome(from);
+
+        if let Some(arbitrator) = &mut self.arbitrator {
+            arbitrator.set_away(&player_name, false);
+        }
+        self.send_round_state(from);
+        Some(LobbyEvent::PlayerReturned(player_name))
+    }
+
+    /// Catch a player up on the round in progress, if there is one: the
+    /// rack with the time that's left, the claims accepted so far and the
+    /// scores
+    fn send_round_state(&mut self, to: SocketAddr) {
+        let remaining = self
+            .round_deadline
+            .filter(|_| self.arbitrator.as_ref().is_some_and(|a| a.is_active()))
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()).as_secs() as u32);
+        if let Some(remaining) = remaining {
+            self.send_to(to, &self.handicaps_message());
+            let msg = self.round_start_message(&self.current_letters, remaining);
+            self.send_to(to, &msg);
+            let claims = self
+                .round_claims()
+                .into_iter()
+                .map(|c| RoundClaim {
+                    player_name: c.player_name,
+                    word: c.word,
+                    points: c.points,
+                    claim_sequence: c.claim_sequence,
+                })
+                .collect();
+            self.send_to(to, &Message::RoundClaims { claims });
+            let scores = self.scores();
+            self.send_to(to, &Message::ScoreUpdate { scores });
+        }
+    }
+
+    /// Remove a player from the lobby for good
+    fn remove_player(&mut self, idx: usize) -> LobbyEvent {
+        let player = self.players.remove(idx);
+        self.player_to_addr.remove(&player.name);
+        self.input_previews.remove(&player.name);
+        self.away.remove(&player.name);
+
+        // Update indices for remaining players
+        for i in self.addr_to_player.values_mut() {
+            if *i > idx {
+                *i -= 1;
+            }
+        }
+        LobbyEvent::PlayerLeft(player.name)
+    }
+
+    /// Remove a player from the lobby and close their connection
+    ///
+    /// They're told why (`Message::Kicked`) before being dropped. Only
+    /// players on a connection can be kicked: returns `None` for the host,
+    /// bots and unknown names.
+    pub fn kick_player(&mut self, name: &str) -> Option<LobbyEvent> {
+        let addr = self.player_to_addr.get(name).copied()?;
+        let idx = self.addr_to_player.remove(&addr)?;
+        self.send_to(addr, &Message::Kicked { reason: KICK_REASON.to_string() });
+        self.send_failures.remove(&addr);
+        if self.server.drop_peer(addr) {
+            self.closed.insert(addr);
+        }
+        Some(self.remove_player(idx))
+    }
+
+    /// Remove players whose grace period ran out before they reconnected
+    fn expire_away_players(&mut self, now: Instant) -> Vec<LobbyEvent> {
+        let expired: Vec<String> = self
+            .away
+            .iter()
+            .filter(|(_, until)| now >= **until)
+            .map(|(name, _)| name.clone())
+            .collect();
+        expired
+            .into_iter()
+            .filter_map(|name| {
+                self.away.remove(&name);
+                let idx = self.players.iter().position(|p| p.name == name)?;
+                Some(self.remove_player(idx))
+            })
+            .collect()
+    }
+
+    /// Handle a claim attempt (can be called for host's own claims too)
+    fn handle_claim_attempt(
+        &mut self,
+        word: &str,
+        player_name: &str,
+        requester_addr: Option<SocketAddr>,
+    ) -> Option<Vec<LobbyEvent>> {
+        // Spectators watch; they don't get to play
+        if requester_addr.is_some_and(|addr| self.spectator_addrs.contains_key(&addr)) {
+            return None;
+        }
+        let mut events = self.expire_round_if_due(Instant::now());
+        events.extend(self.arbitrate_claim(word, player_name, requester_addr)?);
+        Some(events)
+    }
+
+    /// Run a claim through the arbitrator and report the outcome
+    fn arbitrate_claim(
+        &mut self,
+        word: &str,
+        player_name: &str,
+        requester_addr: Option<SocketAddr>,
+    ) -> Option<Vec<LobbyEvent>> {
+        let arbitrator = self.arbitrator.as_mut()?;
+
+        // Reject overlong claims before any validation work. A word can't use
+        // more tiles than the rack holds, so this bounds per-claim cost.
+        let max = arbitrator.max_word_length();
+        if arbitrator.is_active() && word.chars().count() > max {
+            let reason = ClaimRejectReason::TooLong { max: max as u32 };
+            let shown: String = word.chars().take(max).collect();
+            self.send_rejection(&shown, &reason, requester_addr);
+            return Some(vec![LobbyEvent::ClaimRejected {
+                word: shown.to_uppercase(),
+                reason,
+            }]);
+        }
+
+        let result = arbitrator.try_claim(word, player_name);
+
+        match result {
+            ClaimResult::Accepted { points, claim_sequence } => {
+                let word_upper = word.to_uppercase();
+                let scores = arbitrator.scores();
+                let combo = arbitrator.combo(player_name);
+
+                // Get timestamp for CRDT event
+                let timestamp_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0);
+
+                // Broadcast ClaimAccepted to all clients
+                let msg = Message::ClaimAccepted {
+                    word: word_upper.clone(),
+                    player_name: player_name.to_string(),
+                    points,
+                    combo,
+                };
+                self.broadcast(&msg);
+
+                // Broadcast WordClaimed for CRDT log
+                let crdt_msg = Message::WordClaimed {
+                    word: word_upper.clone(),
+                    player_name: player_name.to_string(),
+                    points,
+                    actor_id: self.actor_id.clone(),
+                    timestamp_ms,
+                    claim_sequence,
+                    round_id: Some(self.round_id),
+                };
+                self.broadcast(&crdt_msg);
+
+                // Also broadcast updated scores
+                let score_msg = Message::ScoreUpdate { scores: scores.clone() };
+                self.broadcast(&score_msg);
+
+                // Scores are per team in team mode, so check them all
+                let reached_target = self.target_score > 0
+                    && scores.iter().any(|(_, score)| *score >= self.target_score);
+
+                let mut events = vec![
+                    LobbyEvent::ClaimAccepted {
+                        word: word_upper.clone(),
+                        player_name: player_name.to_string(),
+                        points,
+                        combo,
+                    },
+                    LobbyEvent::WordClaimed {
+                        word: word_upper,
+                        player_name: player_name.to_string(),
+                        points,
+                        actor_id: self.actor_id.clone(),
+                        timestamp_ms,
+                        claim_sequence,
+                        round_id: Some(self.round_id),
+                    },
+                    LobbyEvent::ScoreUpdate { scores },
+                ];
+                // First to the target wins the round there and then
+                if reached_target {
+                    events.extend(self.end_round(RoundEndReason::TargetReached));
+                }
+                Some(events)
+            }
+            ClaimResult::AlreadyClaimed { by } => {
+                let reason = ClaimRejectReason::AlreadyClaimed { by };
+                self.send_rejection(word, &reason, requester_addr);
+                Some(vec![LobbyEvent::ClaimRejected {
+                    word: word.to_uppercase(),
+                    reason,
+                }])
+            }
+            ClaimResult::TooShort => {
+                let reason = ClaimRejectReason::TooShort;
+                self.send_rejection(word, &reason, requester_addr);
+                Some(vec![LobbyEvent::ClaimRejected {
+                    word: word.to_uppercase(),
+                    reason,
+                }])
+            }
+            ClaimResult::TooFewLetters { min } => {
+                let reason = ClaimRejectReason::TooFewLetters { min: min as u32 };
+                self.send_rejection(word, &reason, requester_addr);
+                Some(vec![LobbyEvent::ClaimRejected {
+                    word: word.to_uppercase(),
+                    reason,
+                }])
+            }
+            ClaimResult::InvalidLetters { missing } => {
+                let reason = ClaimRejectReason::InvalidLetters { missing };
+                self.send_rejection(word, &reason, requester_addr);
+                let mut events = vec![LobbyEvent::ClaimRejected {
+                    word: word.to_uppercase(),
+                    reason,
+                }];
+                events.extend(self.penalty_score_update());
+                Some(events)
+            }
+            ClaimResult::NotInDictionary => {
+                let reason = ClaimRejectReason::NotInDictionary;
+                self.send_rejection(word, &reason, requester_addr);
+                let mut events = vec![LobbyEvent::ClaimRejected {
+                    word: word.to_uppercase(),
+                    reason,
+                }];
+                events.extend(self.penalty_score_update());
+                Some(events)
+            }
+            ClaimResult::RoundEnded => {
+                let reason = ClaimRejectReason::RoundEnded;
+                self.send_rejection(word, &reason, requester_addr);
+                Some(vec![LobbyEvent::ClaimRejected {
+                    word: word.to_uppercase(),
+                    reason,
+                }])
+            }
+            // An away player has no connection to hear a verdict
+            ClaimResult::Away => Some(Vec::new()),
+        }
+    }
+
+    /// Share the scores after a claim that may have cost a penalty
+    ///
+    /// Nothing to share when penalty mode is off.
+    fn penalty_score_update(&mut self) -> Option<LobbyEvent> {
+        let scores = self.arbitrator.as_ref().filter(|a| a.reject_penalty() > 0)?.scores();
+        self.broadcast(&Message::ScoreUpdate { scores: scores.clone() });
+        Some(LobbyEvent::ScoreUpdate { scores })
+    }
+
+    /// Send rejection message to a specific client
+    fn send_rejection(
+        &mut self,
+        word: &str,
+        reason: &ClaimRejectReason,
+        requester_addr: Option<SocketAddr>,
+    ) {
+        if let Some(addr) = requester_addr {
+            let msg = Message::ClaimRejected {
+                word: word.to_uppercase(),
+                reason: reason.clone(),
+            };
+            self.send_to(addr, &msg);
+        }
+    }
+
+    /// Send a history sync message to a player over their lobby connection
+    ///
+    /// Returns whether it was handed to the connection (false for the host
+    /// or a player who isn't connected).
+    pub fn send_sync(&mut self, player_name: &str, message: &Message) -> bool {
+        match self.player_to_addr.get(player_name).copied() {
+            Some(addr) => self.send_to(addr, message),
+            None => false,
+        }
+    }
+
+    /// Send a message to one peer, tracking failures
+    ///
+    /// Returns whether the message was handed to the connection.
+    fn send_to(&mut self, addr: SocketAddr, msg: &Message) -> bool {
+        match self.server.send_to(addr, msg) {
+            Ok(()) => {
+                self.send_failures.remove(&addr);
+                true
+            }
+            Err(_) => {
+                self.record_send_failure(addr);
+                false
+            }
+        }
+    }
+
+    /// Send a message to every peer, tracking failures
+    fn broadcast(&mut self, msg: &Message) {
+        let failed = self.server.broadcast(msg);
+        self.send_failures.retain(|addr, _| failed.contains(addr));
+        for addr in failed {
+            self.record_send_failure(addr);
+        }
+    }
+
+    /// Count a failed send, dropping the peer once it keeps failing
+    ///
+    /// The dropped peer is reported as `PlayerLeft` on the next poll, so the
+    /// rest of the lobby stops waiting on a client that can't hear us.
+    fn record_send_failure(&mut self, addr: SocketAddr) {
+        let failures = self.send_failures.entry(addr).or_insert(0);
+        *failures += 1;
+        if *failures >= MAX_SEND_FAILURES {
+            self.send_failures.remove(&addr);
+            self.server.drop_peer(addr);
+        }
+    }
+
+    /// Host submits a claim (called from local gameplay)
+    pub fn host_claim(&mut self, word: &str) -> Option<Vec<LobbyEvent>> {
+        self.handle_claim_attempt(word, &self.host_name.clone(), None)
+    }
+
+    /// End the round if the host's timer has run out
+    ///
+    /// Returns the round end events the first time the deadline passes, and
+    /// nothing otherwise.
+    fn expire_round_if_due(&mut self, now: Instant) -> Vec<LobbyEvent> {
+        let active = self.arbitrator.as_ref().is_some_and(|a| a.is_active());
+        match self.round_deadline {
+            Some(deadline) if active && now >= deadline => self.end_round(RoundEndReason::TimeExpired),
+            _ => Vec::new(),
+        }
+    }
+
+    /// End the current round, telling every player why
+    pub fn end_round(&mut self, reason: RoundEndReason) -> Vec<LobbyEvent> {
+        if let Some(arbitrator) = &mut self.arbitrator {
+            arbitrator.end_round();
+            // Remember this round's words for the rest of the match
+            for claim in arbitrator.claims() {
+                self.match_claims
+                    .entry(claim.word.clone())
+                    .or_insert_with(|| claim.player_name.clone());
+            }
+        }
+        self.round_deadline = None;
+        self.state = LobbyState::Waiting;
+        for bot in &mut self.bots {
+            bot.end_round();
+        }
+
+        // Broadcast round end to all clients
+        self.broadcast(&Message::RoundEnd { reason });
+
+        // Get final scores
+        let scores = self
+            .arbitrator
+            .as_ref()
+            .map(|a| a.scores())
+            .unwrap_or_default();
+
+        vec![
+            LobbyEvent::RoundEnd { reason },
+            LobbyEvent::ScoreUpdate { scores },
+        ]
+    }
+
+    /// Get current scores
+    pub fn scores(&self) -> Vec<(String, u32)> {
+        self.arbitrator
+            .as_ref()
+            .map(|a| a.scores())
+            .unwrap_or_default()
+    }
+
+    /// Start the countdown sequence (3-2-1-BLAM!)
+    /// Returns the initial countdown value
+    pub fn start_countdown(&mut self, letters: Vec<char>, duration: u32) -> u32 {
+        const COUNTDOWN_SECONDS: u32 = 3;
+
+        self.current_letters = letters.clone();
+        self.round_duration = duration;
+        self.countdown_remaining = COUNTDOWN_SECONDS;
+        self.state = LobbyState::Countdown(COUNTDOWN_SECONDS);
+
+        // Broadcast countdown to all clients
+        self.broadcast(&self.countdown_message(COUNTDOWN_SECONDS));
+
+        COUNTDOWN_SECONDS
+    }
+
+    /// Tick the countdown, returns true if countdown finished and round should start
+    pub fn tick_countdown(&mut self) -> Option<LobbyEvent> {
+        if let LobbyState::Countdown(count) = self.state {
+            if count > 1 {
+                let count = count - 1;
+                self.state = LobbyState::Countdown(count);
+                self.countdown_remaining = count;
+
+                // Broadcast updated countdown
+                self.broadcast(&self.countdown_message(count));
+
+                Some(LobbyEvent::Countdown {
                     letters: self.current_letters.clone(),
                     duration: self.round_duration,
-                    countdown: *count,
+                    countdown: count,
                 })
             } else {
                 // Countdown finished - start the round
@@ -488,6 +1851,9 @@ impl HostedLobby {
                 Some(LobbyEvent::RoundStart {
                     letters: self.current_letters.clone(),
                     duration: self.round_duration,
+                    min_letters_used: self.min_letters_used(),
+                    reject_penalty: self.round_reject_penalty(),
+                    language: self.round_language(),
                 })
             }
         } else {
@@ -495,23 +1861,95 @@ impl HostedLobby {
         }
     }
 
+    /// Call off a running countdown and go back to waiting for players
+    ///
+    /// Returns false if no countdown was running.
+    pub fn cancel_countdown(&mut self) -> bool {
+        if !matches!(self.state, LobbyState::Countdown(_)) {
+            return false;
+        }
+
+        self.state = LobbyState::Waiting;
+        self.countdown_remaining = 0;
+        self.broadcast(&Message::CountdownCancelled);
+        true
+    }
+
+    /// Offer everyone another round on a fresh rack, once this one is over
+    ///
+    /// Players keep their seats. Each is sent a `RematchOffer`, and the
+    /// countdown starts once they've all said yes; right away if there's
+    /// nobody to ask (only bots). Returns `RematchOffered` while waiting on
+    /// votes, `Countdown` once it starts, or `None` if no round has been
+    /// played or one is under way.
+    pub fn request_rematch(&mut self) -> Option<LobbyEvent> {
+        let round_over = self.arbitrator.as_ref().is_some_and(|a| !a.is_active());
+        if !round_over || self.state != LobbyState::Waiting {
+            return None;
+        }
+        self.rematch_voters = Some(self.player_to_addr.keys().cloned().collect());
+        self.broadcast(&Message::RematchOffer);
+        self.start_rematch_if_agreed().or(Some(LobbyEvent::RematchOffered))
+    }
+
+    /// Whether a rematch is on offer and still waiting on votes
+    pub fn rematch_pending(&self) -> bool {
+        self.rematch_voters.is_some()
+    }
+
+    /// Count a player's answer to the rematch offer
+    ///
+    /// A "no" calls the offer off; the host can make a new one.
+    fn record_rematch_vote(&mut self, name: String, accept: bool) -> Option<LobbyEvent> {
+        let voters = self.rematch_voters.as_mut()?;
+        if accept {
+            voters.remove(&name);
+            return None;
+        }
+        self.rematch_voters = None;
+        Some(LobbyEvent::RematchDeclined(name))
+    }
+
+    /// Start the rematch once every player still here has accepted
+    ///
+    /// Players who left since the offer don't hold it up.
+    fn start_rematch_if_agreed(&mut self) -> Option<LobbyEvent> {
+        let voters = self.rematch_voters.as_mut()?;
+        voters.retain(|name| self.player_to_addr.contains_key(name));
+        if !voters.is_empty() {
+            return None;
+        }
+        self.rematch_voters = None;
+        self.arbitrator = None;
+        let letters = self.generate_letters();
+        let duration = self.round_duration;
+        let countdown = self.start_countdown(letters.clone(), duration);
+        Some(LobbyEvent::Countdown { letters, duration, countdown })
+    }
+
     /// Internal: Actually begin the round after countdown
     fn begin_round(&mut self) {
         self.state = LobbyState::Starting;
 
         // Create the arbitrator with all player names
-        let player_names: Vec<String> = self.players.iter().map(|p| p.name.clone()).collect();
-        self.arbitrator = Some(RoundArbitrator::new(
-            self.current_letters.clone(),
-            &player_names,
-        ));
+        self.arbitrator = Some(self.new_arbitrator(self.current_letters.clone()));
+        self.round_id = generate_round_id();
+        self.round_deadline =
+            Some(Instant::now() + Duration::from_secs(self.round_duration as u64));
+        self.start_bots();
 
         // Broadcast round start to all connected clients
-        let msg = Message::RoundStart {
-            letters: self.current_letters.clone(),
-            duration_secs: self.round_duration,
-        };
-        self.server.broadcast(&msg);
+        self.broadcast(&self.handicaps_message());
+        let msg = self.round_start_message(&self.current_letters, self.round_duration);
+        self.broadcast(&msg);
+    }
+
+    /// Set the bots' clocks going for the round just started
+    fn start_bots(&mut self) {
+        let now = Instant::now();
+        for bot in &mut self.bots {
+            bot.start_round(now, self.bot_skill);
+        }
     }
 
     /// Get the current countdown remaining (0 if not in countdown)
@@ -524,26 +1962,109 @@ impl HostedLobby {
         &self.current_letters
     }
 
+    /// Valid words nobody has claimed yet this round (`None` between
+    /// rounds)
+    pub fn remaining_valid_count(&self) -> Option<usize> {
+        self.arbitrator.as_ref().map(|a| a.remaining_valid_count())
+    }
+
     /// Get the round duration (for display during countdown)
     pub fn round_duration(&self) -> u32 {
         self.round_duration
     }
 
+    /// Get the ID of the current (or most recent) round
+    pub fn round_id(&self) -> i64 {
+        self.round_id
+    }
+
     /// Start the round - broadcast to all players
     pub fn start_round(&mut self, letters: Vec<char>, duration: u32) {
         self.state = LobbyState::Starting;
         self.current_letters = letters.clone();
 
         // Create the arbitrator with all player names
-        let player_names: Vec<String> = self.players.iter().map(|p| p.name.clone()).collect();
-        self.arbitrator = Some(RoundArbitrator::new(letters.clone(), &player_names));
+        self.arbitrator = Some(self.new_arbitrator(letters.clone()));
+        self.round_id = generate_round_id();
+        self.round_duration = duration;
+        self.round_deadline = Some(Instant::now() + Duration::from_secs(duration as u64));
+        self.input_previews.clear();
+        self.start_bots();
 
         // Broadcast round start to all connected clients
-        let msg = Message::RoundStart {
-            letters,
-            duration_secs: duration,
+        self.broadcast(&self.handicaps_message());
+        let msg = self.round_start_message(&letters, duration);
+        self.broadcast(&msg);
+    }
+
+    /// Broadcast a notice to every player in the lobby
+    ///
+    /// Text is trimmed and capped at `MAX_ANNOUNCEMENT_LEN` characters.
+    /// Returns `None` (and sends nothing) if there's nothing left to say.
+    pub fn announce(&mut self, text: &str) -> Option<LobbyEvent> {
+        let text: String = text.trim().chars().take(MAX_ANNOUNCEMENT_LEN).collect();
+        if text.is_empty() {
+            return None;
+        }
+
+        self.broadcast(&Message::SystemAnnouncement { text: text.clone() });
+        self.announcement = Some(text.clone());
+        Some(LobbyEvent::Announcement { text })
+    }
+
+    /// Most recent announcement sent to the lobby
+    pub fn announcement(&self) -> Option<&str> {
+        self.announcement.as_deref()
+    }
+
+    /// Set the host's own lobby status (empty clears it)
+    pub fn set_status(&mut self, status: &str) -> Option<LobbyEvent> {
+        let idx = self.players.iter().position(|p| p.is_host)?;
+        self.update_status(idx, status)
+    }
+
+    /// Store a player's sanitized status and relay it to everyone
+    fn update_status(&mut self, idx: usize, status: &str) -> Option<LobbyEvent> {
+        let status = sanitize_status(status);
+        let player = self.players.get_mut(idx)?;
+        player.status = status.clone();
+        let player_name = player.name.clone();
+
+        self.broadcast(&Message::PlayerStatus {
+            player_name: player_name.clone(),
+            status: status.clone().unwrap_or_default(),
+        });
+        Some(LobbyEvent::PlayerStatus { player_name, status })
+    }
+
+    /// Store the typing preview a player sent
+    ///
+    /// Returns `None` if the player is unknown or the preview didn't change.
+    fn update_input_preview(&mut self, idx: usize, text: &str) -> Option<LobbyEvent> {
+        let player_name = self.players.get(idx)?.name.clone();
+        let text = sanitize_input_preview(text);
+        let changed = if text.is_empty() {
+            self.input_previews.remove(&player_name).is_some()
+        } else {
+            self.input_previews.insert(player_name.clone(), text.clone()).as_ref() != Some(&text)
         };
-        self.server.broadcast(&msg);
+        changed.then_some(LobbyEvent::InputPreview { player_name, text })
+    }
+
+    /// What a player is currently typing, if they share it
+    pub fn input_preview(&self, player_name: &str) -> Option<&str> {
+        self.input_previews.get(player_name).map(String::as_str)
+    }
+
+    /// All accepted claims in the current (or most recent) round, by sequence
+    pub fn round_claims(&self) -> Vec<ClaimRecord> {
+        let mut claims = self
+            .arbitrator
+            .as_ref()
+            .map(|a| a.claims().to_vec())
+            .unwrap_or_default();
+        claims.sort_by_key(|c| c.claim_sequence);
+        claims
     }
 
     /// Clean up and stop the lobby
@@ -565,6 +2086,16 @@ pub struct JoinedLobby {
     pub host_name: String,
     /// Connection to the host
     client: Client,
+    /// Where the host is, to reconnect if the connection drops
+    host_addr: SocketAddr,
+    /// Reconnects tried since the connection dropped (0 while connected)
+    reconnect_attempts: u32,
+    /// When to try reconnecting next, after a failed attempt
+    next_reconnect: Option<Instant>,
+    /// Whether the host seated us as a spectator (the lobby was full)
+    spectating: bool,
+    /// Whether the host kicked us (so we don't try to reconnect)
+    kicked: bool,
     /// Players in the lobby (as reported by host)
     players: Vec<Player>,
     /// Current lobby state
@@ -575,25 +2106,38 @@ pub struct JoinedLobby {
     pending_duration: u32,
     /// Current countdown value
     countdown_remaining: u32,
+    /// Most recent announcement from the host
+    announcement: Option<String>,
+    /// Claims the host accepted this round, as seen in WordClaimed messages
+    round_claims: Vec<ClaimRecord>,
+    /// Claims sent to the host that haven't had a verdict yet (uppercase word
+    /// -> when it was sent)
+    claims_in_flight: HashMap<String, Instant>,
+    /// Extra words the host's dictionary accepts
+    house_words: Vec<String>,
+    /// Handicaps the host set for the current round
+    handicaps: HashMap<String, f32>,
+    /// Whether we share what we're typing with the host (off by default)
+    share_input: bool,
+    /// Paces the typing previews we send
+    input_throttle: InputPreviewThrottle,
 }
 
 impl JoinedLobby {
     /// Join a lobby by connecting to a peer
-    pub fn join(peer: &PeerInfo, player_name: String) -> Result<Self, String> {
-        // Get the first available address
-        let addr = peer
-            .addresses
-            .first()
-            .ok_or("No address available for peer")?;
-
-        let socket_addr = std::net::SocketAddr::new(*addr, peer.port);
+    pub fn join(peer: &PeerInfo, player_name: String) -> Result<Self, JoinError> {
+        // Link-local addresses come back scoped to the interface they were
+        // seen on
+        let socket_addr = peer.connect_addr().ok_or(JoinError::NoAddress)?;
 
         // Connect to the host
         let mut client = Client::connect_addr(socket_addr, player_name.clone())
-            .map_err(|e| format!("Failed to connect: {}", e))?;
+            .map_err(|e| JoinError::from_connect(&e))?;
 
         // Send join message
-        client.join().map_err(|e| format!("Failed to join: {}", e))?;
+        client
+            .join()
+            .map_err(|e| JoinError::HandshakeFailed(e.to_string()))?;
 
         // Create initial player list (just us and the host)
         let host_player = Player {
@@ -601,6 +2145,9 @@ impl JoinedLobby {
             ready: true,
             is_local: false,
             is_host: true,
+            status: None,
+            is_bot: false,
+            team: None,
         };
 
         let our_player = Player {
@@ -608,6 +2155,9 @@ impl JoinedLobby {
             ready: true,
             is_local: true,
             is_host: false,
+            status: None,
+            is_bot: false,
+            team: None,
         };
 
         Ok(Self {
@@ -615,11 +2165,23 @@ impl JoinedLobby {
             lobby_name: peer.lobby_name.clone().unwrap_or_else(|| "Unknown".to_string()),
             host_name: peer.handle.clone(),
             client,
+            host_addr: socket_addr,
+            reconnect_attempts: 0,
+            next_reconnect: None,
+            spectating: false,
+            kicked: false,
             players: vec![host_player, our_player],
             state: LobbyState::Waiting,
             pending_letters: Vec::new(),
             pending_duration: 0,
             countdown_remaining: 0,
+            announcement: None,
+            round_claims: Vec::new(),
+            claims_in_flight: HashMap::new(),
+            house_words: Vec::new(),
+            handicaps: HashMap::new(),
+            share_input: false,
+            input_throttle: InputPreviewThrottle::new(INPUT_PREVIEW_INTERVAL),
         })
     }
 
@@ -637,12 +2199,19 @@ impl JoinedLobby {
     pub fn poll(&mut self) -> Vec<LobbyEvent> {
         let mut events = Vec::new();
 
-        // Check if still connected
-        if !self.client.is_connected() {
-            events.push(LobbyEvent::Disconnected);
+        if self.kicked {
             return events;
         }
 
+        // Try to get the connection back if it dropped
+        if !self.client.is_connected() {
+            match self.reconnect(Instant::now()) {
+                Some(LobbyEvent::Reconnected) => events.push(LobbyEvent::Reconnected),
+                Some(event) => return vec![event],
+                None => return events,
+            }
+        }
+
         // Poll for messages from host
         for msg in self.client.poll() {
             match msg {
@@ -651,22 +2220,53 @@ impl JoinedLobby {
                     duration_secs,
                     countdown_secs,
                 } => {
-                    self.pending_letters = letters.clone();
-                    self.pending_duration = duration_secs;
-                    self.countdown_remaining = countdown_secs;
-                    self.state = LobbyState::Countdown(countdown_secs);
-                    events.push(LobbyEvent::Countdown {
-                        letters,
+                    events.push(self.begin_countdown(letters, duration_secs, countdown_secs));
+                }
+                Message::CountdownSeeded { seed, rack, duration_secs, countdown_secs } => {
+                    let letters = rack.rack_for_seed(seed).letters().to_vec();
+                    events.push(self.begin_countdown(letters, duration_secs, countdown_secs));
+                }
+                Message::CountdownCancelled => {
+                    self.state = LobbyState::Waiting;
+                    self.countdown_remaining = 0;
+                    self.pending_letters.clear();
+                    events.push(LobbyEvent::CountdownCancelled);
+                }
+                // Spectators have no say in whether there's another round
+                Message::RematchOffer if !self.spectating => {
+                    events.push(LobbyEvent::RematchOffered);
+                }
+                Message::RoundStartSeeded { seed, config, duration_secs } => {
+                    // Deal the host's rack ourselves, then start as usual
+                    self.state = LobbyState::Starting;
+                    self.countdown_remaining = 0;
+                    self.round_claims.clear();
+                    self.claims_in_flight.clear();
+                    events.push(LobbyEvent::RoundStart {
+                        letters: config.rack.rack_for_seed(seed).letters().to_vec(),
                         duration: duration_secs,
-                        countdown: countdown_secs,
+                        min_letters_used: config.min_letters_used as usize,
+                        reject_penalty: config.reject_penalty,
+                        language: config.rack.language(),
                     });
                 }
-                Message::RoundStart { letters, duration_secs } => {
+                Message::RoundStart {
+                    letters,
+                    duration_secs,
+                    min_letters_used,
+                    reject_penalty,
+                    language,
+                } => {
                     self.state = LobbyState::Starting;
                     self.countdown_remaining = 0;
+                    self.round_claims.clear();
+                    self.claims_in_flight.clear();
                     events.push(LobbyEvent::RoundStart {
                         letters,
                         duration: duration_secs,
+                        min_letters_used: min_letters_used as usize,
+                        reject_penalty,
+                        language,
                     });
                 }
                 Message::Join { player_name } => {
@@ -676,13 +2276,27 @@ impl JoinedLobby {
                         ready: true,
                         is_local: false,
                         is_host: false,
+                        status: None,
+                        is_bot: false,
+                        team: None,
                     };
                     self.players.push(player);
                     events.push(LobbyEvent::PlayerJoined(player_name));
                 }
+                Message::Kicked { reason } => {
+                    self.kicked = true;
+                    events.push(LobbyEvent::Kicked { reason });
+                    return events;
+                }
                 Message::JoinRejected { reason } => {
                     events.push(LobbyEvent::JoinRejected { reason });
                 }
+                Message::JoinSpectator { name } if name == self.player_name => {
+                    // No seat for us: watch instead
+                    self.spectating = true;
+                    self.players.retain(|p| !p.is_local);
+                    events.push(LobbyEvent::Spectating);
+                }
                 Message::Leave { player_name } => {
                     self.players.retain(|p| p.name != player_name);
                     events.push(LobbyEvent::PlayerLeft(player_name));
@@ -691,14 +2305,23 @@ impl JoinedLobby {
                     word,
                     player_name,
                     points,
+                    combo,
                 } => {
+                    self.claims_in_flight.remove(&word);
                     events.push(LobbyEvent::ClaimAccepted {
                         word,
                         player_name,
                         points,
+                        combo,
                     });
                 }
                 Message::ClaimRejected { word, reason } => {
+                    if let ClaimRejectReason::TooLong { .. } = reason {
+                        // The host echoes overlong words cut to the rack size
+                        self.claims_in_flight.retain(|w, _| !w.starts_with(word.as_str()));
+                    } else {
+                        self.claims_in_flight.remove(&word);
+                    }
                     events.push(LobbyEvent::ClaimRejected { word, reason });
                 }
                 Message::WordClaimed {
@@ -708,7 +2331,14 @@ impl JoinedLobby {
                     actor_id,
                     timestamp_ms,
                     claim_sequence,
+                    round_id,
                 } => {
+                    self.round_claims.push(ClaimRecord {
+                        player_name: player_name.clone(),
+                        word: word.clone(),
+                        points,
+                        claim_sequence,
+                    });
                     events.push(LobbyEvent::WordClaimed {
                         word,
                         player_name,
@@ -716,42 +2346,263 @@ impl JoinedLobby {
                         actor_id,
                         timestamp_ms,
                         claim_sequence,
+                        round_id,
                     });
                 }
-                Message::ScoreUpdate { scores } => {
+                Message::RoundClaims { claims } => {
+                    let claims: Vec<ClaimRecord> = claims
+                        .into_iter()
+                        .take(MAX_ROUND_CLAIMS)
+                        .map(|c| ClaimRecord {
+                            player_name: c.player_name,
+                            word: c.word,
+                            points: c.points,
+                            claim_sequence: c.claim_sequence,
+                        })
+                        .collect();
+                    for claim in &claims {
+                        if !self.round_claims.iter().any(|c| c.word == claim.word) {
+                            self.round_claims.push(claim.clone());
+                        }
+                    }
+                    events.push(LobbyEvent::RoundClaims { claims });
+                }
+                Message::ScoreUpdate { mut scores } => {
+                    // A lobby never holds more than MAX_PLAYERS, so anything
+                    // past that is junk from a bad host
+                    scores.truncate(MAX_PLAYERS);
                     events.push(LobbyEvent::ScoreUpdate { scores });
                 }
-                Message::RoundEnd => {
+                Message::SystemAnnouncement { text } => {
+                    self.announcement = Some(text.clone());
+                    events.push(LobbyEvent::Announcement { text });
+                }
+                Message::HouseWords { words } => {
+                    self.house_words = words.clone();
+                    events.push(LobbyEvent::HouseWords { words });
+                }
+                Message::Handicaps { handicaps } => {
+                    self.handicaps = handicaps
+                        .into_iter()
+                        .map(|(name, percent)| {
+                            (name, (percent as f32 / 100.0).clamp(MIN_HANDICAP, MAX_HANDICAP))
+                        })
+                        .collect();
+                    events.push(LobbyEvent::Handicaps { handicaps: self.handicaps.clone() });
+                }
+                Message::PlayerStatus { player_name, status } => {
+                    let status = sanitize_status(&status);
+                    if let Some(player) = self.players.iter_mut().find(|p| p.name == player_name) {
+                        player.status = status.clone();
+                    }
+                    events.push(LobbyEvent::PlayerStatus { player_name, status });
+                }
+                Message::RoundEnd { reason } => {
                     self.state = LobbyState::Waiting;
-                    events.push(LobbyEvent::RoundEnd);
+                    self.claims_in_flight.clear();
+                    events.push(LobbyEvent::RoundEnd { reason });
+                }
+                message @ (Message::SyncRequest { .. }
+                | Message::SyncEvents { .. }
+                | Message::SyncEventsCompressed { .. }) => {
+                    events.push(LobbyEvent::Sync {
+                        player_name: self.host_name.clone(),
+                        message,
+                    });
                 }
                 _ => {}
             }
         }
 
-        events
+        events
+    }
+
+    /// Reconnect to the host after the connection dropped
+    ///
+    /// Rejoins under our name and asks the host to resend the round in
+    /// progress. Attempts back off (`RECONNECT_BACKOFF`, doubling) and stop
+    /// after `MAX_RECONNECT_ATTEMPTS`, when we report `Disconnected`.
+    /// Returns `None` while waiting for the next attempt.
+    fn reconnect(&mut self, now: Instant) -> Option<LobbyEvent> {
+        if self.reconnect_attempts >= MAX_RECONNECT_ATTEMPTS {
+            return Some(LobbyEvent::Disconnected);
+        }
+        if self.next_reconnect.is_some_and(|at| now < at) {
+            return None;
+        }
+
+        self.reconnect_attempts += 1;
+        let reconnected = Client::connect_addr(self.host_addr, self.player_name.clone())
+            .and_then(|mut client| {
+                client.join()?;
+                client.request_resync()?;
+                Ok(client)
+            });
+        match reconnected {
+            Ok(client) => {
+                self.client = client;
+                self.reconnect_attempts = 0;
+                self.next_reconnect = None;
+                self.claims_in_flight.clear();
+                Some(LobbyEvent::Reconnected)
+            }
+            Err(_) if self.reconnect_attempts >= MAX_RECONNECT_ATTEMPTS => {
+                Some(LobbyEvent::Disconnected)
+            }
+            Err(_) => {
+                let backoff = RECONNECT_BACKOFF * 2u32.pow(self.reconnect_attempts - 1);
+                self.next_reconnect = Some(now + backoff);
+                Some(LobbyEvent::Reconnecting { attempt: self.reconnect_attempts })
+            }
+        }
+    }
+
+    /// Reconnects tried since the connection dropped (0 while connected)
+    pub fn reconnect_attempts(&self) -> u32 {
+        self.reconnect_attempts
+    }
+
+    /// Whether the host removed us from the lobby
+    pub fn is_kicked(&self) -> bool {
+        self.kicked
+    }
+
+    /// Whether we're watching rather than playing
+    pub fn is_spectating(&self) -> bool {
+        self.spectating
+    }
+
+    /// Get the current countdown remaining (0 if not in countdown)
+    pub fn countdown_remaining(&self) -> u32 {
+        self.countdown_remaining
+    }
+
+    /// Get the pending letters (for display during countdown)
+    pub fn pending_letters(&self) -> &[char] {
+        &self.pending_letters
+    }
+
+    /// Get the pending round duration (for display during countdown)
+    pub fn pending_duration(&self) -> u32 {
+        self.pending_duration
+    }
+
+    /// Most recent announcement from the host
+    pub fn announcement(&self) -> Option<&str> {
+        self.announcement.as_deref()
+    }
+
+    /// Extra words the host's dictionary accepts
+    pub fn house_words(&self) -> &[String] {
+        &self.house_words
+    }
+
+    /// Handicap multipliers the host set for the current round
+    pub fn handicaps(&self) -> &HashMap<String, f32> {
+        &self.handicaps
+    }
+
+    /// All claims the host accepted this round, by sequence
+    ///
+    /// Built from received WordClaimed messages, so it mirrors
+    /// `HostedLobby::round_claims` even if messages arrive out of order.
+    pub fn round_claims(&self) -> Vec<ClaimRecord> {
+        let mut claims = self.round_claims.clone();
+        claims.sort_by_key(|c| c.claim_sequence);
+        claims
+    }
+
+    /// Follow the host's countdown to a round on `letters`
+    fn begin_countdown(&mut self, letters: Vec<char>, duration: u32, countdown: u32) -> LobbyEvent {
+        self.pending_letters = letters.clone();
+        self.pending_duration = duration;
+        self.countdown_remaining = countdown;
+        self.state = LobbyState::Countdown(countdown);
+        LobbyEvent::Countdown { letters, duration, countdown }
+    }
+
+    /// Send a claim attempt to the host
+    ///
+    /// A word already waiting on the host's verdict isn't sent again (for
+    /// `CLAIM_RESEND_AFTER`), so a double-tapped Enter costs one round trip
+    /// and no confusing "already claimed" rejection. Returns whether the
+    /// claim was sent.
+    pub fn send_claim(&mut self, word: &str) -> Result<bool, String> {
+        // The host ignores spectators' claims, so don't bother it
+        if self.spectating {
+            return Ok(false);
+        }
+        let word_upper = word.to_uppercase();
+        let now = Instant::now();
+        if let Some(sent) = self.claims_in_flight.get(&word_upper) {
+            if now.duration_since(*sent) < CLAIM_RESEND_AFTER {
+                return Ok(false);
+            }
+        }
+
+        self.client
+            .send_claim_attempt(word)
+            .map_err(|e| format!("Failed to send claim: {}", e))?;
+        self.claims_in_flight.insert(word_upper, now);
+        Ok(true)
     }
 
-    /// Get the current countdown remaining (0 if not in countdown)
-    pub fn countdown_remaining(&self) -> u32 {
-        self.countdown_remaining
+    /// Set our lobby status (empty clears it)
+    ///
+    /// Shown locally straight away; the host relays it to everyone else.
+    pub fn set_status(&mut self, status: &str) -> Result<(), String> {
+        let status = sanitize_status(status);
+        self.client
+            .send_status(status.as_deref().unwrap_or_default())
+            .map_err(|e| format!("Failed to send status: {}", e))?;
+        if let Some(player) = self.players.iter_mut().find(|p| p.is_local) {
+            player.status = status;
+        }
+        Ok(())
     }
 
-    /// Get the pending letters (for display during countdown)
-    pub fn pending_letters(&self) -> &[char] {
-        &self.pending_letters
+    /// Whether we share what we're typing with the host
+    pub fn share_input(&self) -> bool {
+        self.share_input
     }
 
-    /// Get the pending round duration (for display during countdown)
-    pub fn pending_duration(&self) -> u32 {
-        self.pending_duration
+    /// Opt in to (or out of) sharing what we're typing with the host
+    ///
+    /// Opting out clears the preview the host has for us.
+    pub fn set_share_input(&mut self, share: bool) -> Result<(), String> {
+        if self.share_input && !share {
+            self.client
+                .send_input_preview("")
+                .map_err(|e| format!("Failed to clear typing preview: {}", e))?;
+        }
+        self.share_input = share;
+        self.input_throttle = InputPreviewThrottle::new(INPUT_PREVIEW_INTERVAL);
+        Ok(())
     }
 
-    /// Send a claim attempt to the host
-    pub fn send_claim(&self, word: &str) -> Result<(), String> {
+    /// Share our current input with the host, if we opted in
+    ///
+    /// Call with the current input whenever convenient; sends are throttled
+    /// to one per `INPUT_PREVIEW_INTERVAL` and skipped if nothing changed.
+    pub fn preview_input(&mut self, text: &str, now: Instant) {
+        if !self.share_input {
+            return;
+        }
+        if let Some(text) = self.input_throttle.poll(text, now) {
+            let _ = self.client.send_input_preview(&text);
+        }
+    }
+
+    /// Accept or turn down the host's rematch offer
+    pub fn vote_rematch(&self, accept: bool) -> Result<(), String> {
         self.client
-            .send_claim_attempt(word)
-            .map_err(|e| format!("Failed to send claim: {}", e))
+            .send_rematch_vote(accept)
+            .map_err(|e| format!("Failed to send rematch vote: {}", e))
+    }
+
+    /// Send a history sync message to the host
+    pub fn send_sync(&self, message: Message) -> std::io::Result<()> {
+        self.client.send_sync(message)
     }
 
     /// Leave the lobby
@@ -760,6 +2611,33 @@ impl JoinedLobby {
     }
 }
 
+/// How lobby discovery is doing, so an empty list can be explained
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrowserStatus {
+    /// Browsing, but discovery hasn't confirmed it's up yet
+    Searching,
+    /// Discovery is up; any lobby on the LAN will show up
+    Listening,
+    /// Discovery stopped working (the reason, for display)
+    Failed(String),
+}
+
+/// Which lobbies the browser leaves out (none, by default)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LobbyFilter {
+    /// Hide lobbies with every seat taken
+    pub hide_full: bool,
+    /// Hide lobbies running a protocol version we can't join
+    pub hide_incompatible: bool,
+}
+
+impl LobbyFilter {
+    /// Whether `peer` is listed under this filter
+    pub fn allows(&self, peer: &PeerInfo) -> bool {
+        !(self.hide_full && peer.is_full() || self.hide_incompatible && peer.is_incompatible())
+    }
+}
+
 /// Lobby browser for finding available lobbies on the network
 pub struct LobbyBrowser {
     /// mDNS service discovery
@@ -770,12 +2648,18 @@ pub struct LobbyBrowser {
     peers: PeerTracker,
     /// Actor ID for this instance
     actor_id: String,
+    /// When we last started browsing
+    browsed_at: Instant,
+    /// Whether discovery is working
+    status: BrowserStatus,
+    /// Which lobbies `poll` leaves out
+    filter: LobbyFilter,
 }
 
 impl LobbyBrowser {
     /// Create a new lobby browser
     pub fn new() -> Result<Self, String> {
-        let actor_id = format!("blam-{:08x}", rand::rng().random::<u32>());
+        let actor_id = with_session_rng(generate_actor_id);
         let discovery = ServiceDiscovery::new(actor_id.clone())?;
         let discovery_rx = discovery.browse()?;
 
@@ -784,13 +2668,70 @@ impl LobbyBrowser {
             discovery_rx,
             peers: PeerTracker::new(),
             actor_id,
+            browsed_at: Instant::now(),
+            status: BrowserStatus::Searching,
+            filter: LobbyFilter::default(),
         })
     }
 
+    /// Which lobbies `poll` leaves out
+    pub fn filter(&self) -> LobbyFilter {
+        self.filter
+    }
+
+    /// Leave lobbies out of what `poll` returns (from the next poll on)
+    pub fn set_filter(&mut self, filter: LobbyFilter) {
+        self.filter = filter;
+    }
+
+    /// How discovery is doing
+    pub fn status(&self) -> &BrowserStatus {
+        &self.status
+    }
+
+    /// Start browsing again after discovery failed
+    pub fn retry(&mut self) {
+        self.status = match self.discovery.browse() {
+            Ok(rx) => {
+                self.discovery_rx = rx;
+                BrowserStatus::Searching
+            }
+            Err(e) => BrowserStatus::Failed(e),
+        };
+        self.browsed_at = Instant::now();
+    }
+
     /// Poll for discovered lobbies
+    ///
+    /// Lobbies that haven't been resolved within `PEER_TTL` are dropped,
+    /// even if their host never said goodbye. The rest are sorted by lobby
+    /// name (then actor ID), so the list holds still as lobbies refresh,
+    /// and the filter is applied.
     pub fn poll(&mut self) -> Vec<PeerInfo> {
+        // Browsing again re-sends our query and replays what the daemon
+        // still has cached, which refreshes every lobby that's still up
+        // (a failed browser waits for `retry` instead)
+        let failed = matches!(self.status, BrowserStatus::Failed(_));
+        if !failed && self.browsed_at.elapsed() >= BROWSE_REFRESH {
+            match self.discovery.browse() {
+                Ok(rx) => self.discovery_rx = rx,
+                Err(e) => self.status = BrowserStatus::Failed(e),
+            }
+            self.browsed_at = Instant::now();
+        }
+
         // Process discovery events
-        while let Ok(event) = self.discovery_rx.try_recv() {
+        loop {
+            let event = match self.discovery_rx.try_recv() {
+                Ok(event) => event,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    if !matches!(self.status, BrowserStatus::Failed(_)) {
+                        self.status = BrowserStatus::Failed("discovery stopped".to_string());
+                    }
+                    break;
+                }
+            };
             match event {
                 DiscoveryEvent::PeerDiscovered(peer) => {
                     // Only track peers that are hosting a lobby
@@ -801,11 +2742,22 @@ impl LobbyBrowser {
                 DiscoveryEvent::PeerLost(actor_id) => {
                     self.peers.remove(&actor_id);
                 }
+                DiscoveryEvent::SearchStarted => {
+                    if self.status == BrowserStatus::Searching {
+                        self.status = BrowserStatus::Listening;
+                    }
+                }
+                DiscoveryEvent::BrowseFailed(reason) => {
+                    self.status = BrowserStatus::Failed(reason);
+                }
             }
         }
+        self.peers.prune_stale(Instant::now(), PEER_TTL);
 
-        // Return list of available lobbies
-        self.peers.peers().cloned().collect()
+        let mut lobbies: Vec<PeerInfo> =
+            self.peers.peers().filter(|peer| self.filter.allows(peer)).cloned().collect();
+        lobbies.sort_by(|a, b| (&a.lobby_name, &a.actor_id).cmp(&(&b.lobby_name, &b.actor_id)));
+        lobbies
     }
 
     /// Stop browsing
@@ -815,8 +2767,95 @@ impl LobbyBrowser {
     }
 }
 
+/// Clean up a player status for display
+///
+/// Control characters are dropped, surrounding whitespace trimmed and the
+/// result capped at `MAX_STATUS_LEN` characters. Returns `None` if nothing
+/// is left.
+pub fn sanitize_status(status: &str) -> Option<String> {
+    let cleaned: String = status.chars().filter(|c| !c.is_control()).collect();
+    let status: String = cleaned.trim().chars().take(MAX_STATUS_LEN).collect();
+    let status = status.trim_end().to_string();
+    (!status.is_empty()).then_some(status)
+}
+
+/// Clean up a typing preview: letters only, uppercased, capped at
+/// `MAX_INPUT_PREVIEW_LEN` characters
+pub fn sanitize_input_preview(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_uppercase)
+        .take(MAX_INPUT_PREVIEW_LEN)
+        .collect()
+}
+
+/// Paces typing previews so a fast typist doesn't flood the host
+///
+/// Feed it the current input as often as you like; it hands back text to
+/// send at most once per interval, and only when the text changed since the
+/// last send. Because it's polled, the final state is sent once the interval
+/// has passed.
+#[derive(Debug, Clone)]
+pub struct InputPreviewThrottle {
+    /// Shortest gap between two sends
+    interval: Duration,
+    /// When the last preview went out
+    last_sent_at: Option<Instant>,
+    /// Text of the last preview that went out
+    sent: String,
+}
+
+impl InputPreviewThrottle {
+    /// Create a throttle allowing one send per `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_sent_at: None,
+            sent: String::new(),
+        }
+    }
+
+    /// Text to send now, if any
+    pub fn poll(&mut self, text: &str, now: Instant) -> Option<String> {
+        if text == self.sent {
+            return None;
+        }
+        if let Some(last) = self.last_sent_at {
+            if now.duration_since(last) < self.interval {
+                return None;
+            }
+        }
+        self.last_sent_at = Some(now);
+        self.sent = text.to_string();
+        Some(self.sent.clone())
+    }
+}
+
+/// The rack config for `preset`, in `language`, with blanks if they're on
+fn rack_config(preset: RackPreset, blanks: bool, language: Language) -> RackConfig {
+    let blanks = if blanks { MAX_BLANKS } else { 0 };
+    preset.config().with_blanks(blanks).with_language(language)
+}
+
+/// Generate an ID for a new round (non-negative so it survives JSON as i64)
+fn generate_round_id() -> i64 {
+    (with_session_rng(|rng| rng.random::<u64>()) >> 1) as i64
+}
+
+/// Generate an actor ID for this instance on the network
+fn generate_actor_id<R: Rng>(rng: &mut R) -> String {
+    format!("blam-{:08x}", rng.random::<u32>())
+}
+
+/// Generate the actor ID and name for a new hosted lobby
+fn lobby_identity<R: Rng>(rng: &mut R) -> (String, String) {
+    let actor_id = generate_actor_id(rng);
+    let lobby_name = generate_lobby_name(rng);
+    (actor_id, lobby_name)
+}
+
 /// Generate a random lobby name
-fn generate_lobby_name() -> String {
+fn generate_lobby_name<R: Rng>(rng: &mut R) -> String {
     const ADJECTIVES: &[&str] = &[
         "SWIFT", "BOLD", "WILD", "FAST", "KEEN", "EPIC", "NOVA", "STAR",
     ];
@@ -824,7 +2863,6 @@ fn generate_lobby_name() -> String {
         "ORBIT", "BLAZE", "STORM", "QUEST", "RUSH", "DASH", "BOLT", "ZOOM",
     ];
 
-    let mut rng = rand::rng();
     let adj = ADJECTIVES[rng.random_range(0..ADJECTIVES.len())];
     let noun = NOUNS[rng.random_range(0..NOUNS.len())];
     format!("{}-{}", adj, noun)
@@ -836,12 +2874,28 @@ mod tests {
 
     #[test]
     fn test_lobby_name_generation() {
-        let name = generate_lobby_name();
+        let name = generate_lobby_name(&mut rand::rng());
         assert!(name.contains('-'));
         let parts: Vec<&str> = name.split('-').collect();
         assert_eq!(parts.len(), 2);
     }
 
+    #[test]
+    fn test_same_seed_same_rack_and_lobby() {
+        use crate::game::seed::SessionRng;
+
+        // Two sessions started with the same BLAM_SEED
+        let session = |seed: &str| {
+            let mut rng = SessionRng::from_seed_var(Some(seed));
+            let (actor_id, lobby_name) = lobby_identity(&mut rng);
+            // Racks are dealt from a seed drawn off the session RNG
+            let rack = RackPreset::default().config().rack_for_seed(rng.random());
+            (actor_id, lobby_name, rack.letters().to_vec())
+        };
+        assert_eq!(session("1234"), session("1234"));
+        assert_ne!(session("1234"), session("1235"));
+    }
+
     #[test]
     fn test_player_count_limits() {
         assert!(MIN_PLAYERS >= 2);
@@ -852,7 +2906,7 @@ mod tests {
     #[test]
     fn test_lobby_name_unique() {
         // Generate many names and verify they're non-empty
-        let names: Vec<String> = (0..20).map(|_| generate_lobby_name()).collect();
+        let names: Vec<String> = (0..20).map(|_| generate_lobby_name(&mut rand::rng())).collect();
         for name in &names {
             assert!(!name.is_empty());
             assert!(name.contains('-'));
@@ -874,6 +2928,9 @@ mod tests {
             ready: true,
             is_local: true,
             is_host: true,
+            status: None,
+            is_bot: false,
+            team: None,
         };
         assert_eq!(player.name, "Alice");
         assert!(player.ready);
@@ -888,6 +2945,9 @@ mod tests {
             ready: false,
             is_local: false,
             is_host: false,
+            status: None,
+            is_bot: false,
+            team: None,
         };
         assert_eq!(player.name, "Bob");
         assert!(!player.ready);
@@ -950,11 +3010,13 @@ mod tests {
             word: "CAT".to_string(),
             player_name: "Alice".to_string(),
             points: 3,
+            combo: 1,
         };
-        if let LobbyEvent::ClaimAccepted { word, player_name, points } = event {
+        if let LobbyEvent::ClaimAccepted { word, player_name, points, combo } = event {
             assert_eq!(word, "CAT");
             assert_eq!(player_name, "Alice");
             assert_eq!(points, 3);
+            assert_eq!(combo, 1);
         } else {
             panic!("Expected ClaimAccepted");
         }
@@ -979,8 +3041,11 @@ mod tests {
         let event = LobbyEvent::RoundStart {
             letters: vec!['A', 'B', 'C'],
             duration: 60,
+            min_letters_used: 0,
+            reject_penalty: 0,
+            language: Language::English,
         };
-        if let LobbyEvent::RoundStart { letters, duration } = event {
+        if let LobbyEvent::RoundStart { letters, duration, .. } = event {
             assert_eq!(letters, vec!['A', 'B', 'C']);
             assert_eq!(duration, 60);
         } else {
@@ -990,8 +3055,8 @@ mod tests {
 
     #[test]
     fn test_lobby_event_round_end() {
-        let event = LobbyEvent::RoundEnd;
-        assert!(matches!(event, LobbyEvent::RoundEnd));
+        let event = LobbyEvent::RoundEnd { reason: RoundEndReason::TimeExpired };
+        assert!(matches!(event, LobbyEvent::RoundEnd { .. }));
     }
 
     #[test]
@@ -1039,14 +3104,16 @@ mod tests {
             actor_id: "test-123".to_string(),
             timestamp_ms: 1000000,
             claim_sequence: 1,
+            round_id: Some(7),
         };
-        if let LobbyEvent::WordClaimed { word, player_name, points, actor_id, timestamp_ms, claim_sequence } = event {
+        if let LobbyEvent::WordClaimed { word, player_name, points, actor_id, timestamp_ms, claim_sequence, round_id } = event {
             assert_eq!(word, "BLAM");
             assert_eq!(player_name, "Alice");
             assert_eq!(points, 4);
             assert_eq!(actor_id, "test-123");
             assert_eq!(timestamp_ms, 1000000);
             assert_eq!(claim_sequence, 1);
+            assert_eq!(round_id, Some(7));
         } else {
             panic!("Expected WordClaimed");
         }
@@ -1056,7 +3123,7 @@ mod tests {
     fn test_lobby_name_format() {
         // Verify names follow ADJ-NOUN format with uppercase
         for _ in 0..20 {
-            let name = generate_lobby_name();
+            let name = generate_lobby_name(&mut rand::rng());
             let parts: Vec<&str> = name.split('-').collect();
             assert_eq!(parts.len(), 2);
             assert!(parts[0].chars().all(|c| c.is_ascii_uppercase()));
@@ -1064,6 +3131,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sanitize_status() {
+        assert_eq!(sanitize_status("  brb  ").as_deref(), Some("brb"));
+        assert_eq!(sanitize_status("ready!\n\x07").as_deref(), Some("ready!"));
+        assert_eq!(sanitize_status("   "), None);
+        assert_eq!(sanitize_status(""), None);
+        let long = sanitize_status(&"z".repeat(40)).unwrap();
+        assert_eq!(long.chars().count(), MAX_STATUS_LEN);
+        assert_eq!(sanitize_status("🎉").as_deref(), Some("🎉"));
+    }
+
+    #[test]
+    fn test_input_preview_throttle_sends_at_most_once_per_interval() {
+        let mut throttle = InputPreviewThrottle::new(Duration::from_millis(250));
+        let start = Instant::now();
+
+        assert_eq!(throttle.poll("C", start).as_deref(), Some("C"));
+        // Typing on inside the interval sends nothing...
+        assert_eq!(throttle.poll("CA", start + Duration::from_millis(100)), None);
+        assert_eq!(throttle.poll("CAT", start + Duration::from_millis(200)), None);
+        // ...until it has passed, then the latest text goes out
+        let later = start + Duration::from_millis(250);
+        assert_eq!(throttle.poll("CAT", later).as_deref(), Some("CAT"));
+        // Unchanged text is never resent
+        assert_eq!(throttle.poll("CAT", later + Duration::from_secs(5)), None);
+        // Clearing the input is sent like any other change
+        let cleared = later + Duration::from_secs(6);
+        assert_eq!(throttle.poll("", cleared).as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_sanitize_input_preview() {
+        assert_eq!(sanitize_input_preview("ca t!"), "CAT");
+        assert_eq!(sanitize_input_preview("\x07é1"), "É");
+        assert_eq!(sanitize_input_preview(&"z".repeat(50)).len(), MAX_INPUT_PREVIEW_LEN);
+    }
+
+    #[test]
+    fn test_player_display_name_shows_status() {
+        let mut player = Player {
+            name: "Alice".to_string(),
+            ready: true,
+            is_local: false,
+            is_host: false,
+            status: None,
+            is_bot: false,
+            team: None,
+        };
+        assert_eq!(player.display_name(), "Alice");
+
+        player.status = Some("brb".to_string());
+        assert_eq!(player.display_name(), "Alice · brb");
+    }
+
+    #[test]
+    fn test_join_error_classifies_timeouts() {
+        // A black-holed address never answers, so the connect times out.
+        // Classified directly: whether a real black hole exists depends on
+        // the network the tests run on.
+        let timed_out = std::io::Error::from(std::io::ErrorKind::TimedOut);
+        assert_eq!(JoinError::from_connect(&timed_out), JoinError::TimedOut);
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        assert_eq!(JoinError::from_connect(&refused), JoinError::Refused);
+        assert!(JoinError::TimedOut.guidance().contains("manually"));
+
+        // Anything else is reported as it is, not as a timeout
+        let unreachable = std::io::Error::from(std::io::ErrorKind::AddrNotAvailable);
+        assert_eq!(
+            JoinError::from_connect(&unreachable),
+            JoinError::Failed(std::io::ErrorKind::AddrNotAvailable)
+        );
+    }
+
     #[test]
     fn test_player_clone() {
         let player = Player {
@@ -1071,6 +3211,9 @@ mod tests {
             ready: true,
             is_local: false,
             is_host: false,
+            status: None,
+            is_bot: false,
+            team: None,
         };
         let cloned = player.clone();
         assert_eq!(player.name, cloned.name);
@@ -1078,39 +3221,381 @@ mod tests {
     }
 }
 
-/// End-to-end integration tests covering anti-cheat and cross-network multiplayer
-#[cfg(test)]
-mod e2e_tests {
-    use super::*;
-    use crate::network::client::Client;
-    use crate::network::protocol::Message;
-    use std::thread;
-    use std::time::Duration;
+/// End-to-end integration tests covering anti-cheat and cross-network multiplayer
+#[cfg(test)]
+mod e2e_tests {
+    use super::*;
+    use crate::game::validation::PANGRAM_BONUS;
+    use crate::game::BLANK;
+    use crate::network::client::Client;
+    use crate::network::protocol::Message;
+    use crate::network::PeerAddress;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Fixed letters for deterministic testing.
+    /// Contains letters for: CAT, DOG, CATS, DOGS, TAN, ANT, COD, COG, etc.
+    const TEST_LETTERS: [char; 12] = ['C', 'A', 'T', 'D', 'O', 'G', 'S', 'N', 'E', 'R', 'I', 'T'];
+
+    fn test_letters_vec() -> Vec<char> {
+        TEST_LETTERS.to_vec()
+    }
+
+    // =========================================================================
+    // Anti-cheat: Server-authoritative claim validation
+    // =========================================================================
+
+    #[test]
+    fn e2e_anticheat_host_rejects_word_not_on_board() {
+        // Host creates lobby, starts round, attempts a word using letters NOT in rack
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.start_round(test_letters_vec(), 60);
+
+        // "ZAP" requires Z which is not in the rack
+        let events = lobby.host_claim("zap").unwrap();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::InvalidLetters { .. }, .. }
+        )), "Server should reject words with letters not on the board");
+    }
+
+    #[test]
+    fn e2e_no_repeats_rejects_word_from_earlier_round() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.set_no_repeats(true);
+
+        lobby.start_round(test_letters_vec(), 60);
+        lobby.host_claim("cat").unwrap();
+        lobby.end_round(RoundEndReason::TimeExpired);
+
+        lobby.start_round(test_letters_vec(), 60);
+        let events = lobby.host_claim("cat").unwrap();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::AlreadyClaimed { by }, .. } if by == "Host"
+        )), "A word from round 1 should be off limits in round 2");
+
+        // A new match starts with a clean slate
+        lobby.end_round(RoundEndReason::TimeExpired);
+        lobby.start_new_match();
+        lobby.start_round(test_letters_vec(), 60);
+        let events = lobby.host_claim("cat").unwrap();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::ClaimAccepted { .. })));
+    }
+
+    #[test]
+    fn e2e_rounds_of_a_match_share_a_group() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.set_warm_up(true);
+        let play_round = |lobby: &mut HostedLobby| {
+            lobby.start_round(test_letters_vec(), 60);
+            lobby.end_round(RoundEndReason::TimeExpired);
+            lobby.match_result(0, 1).unwrap()
+        };
+        let first = play_round(&mut lobby);
+        let second = play_round(&mut lobby);
+        assert!(first.match_group.is_some());
+        assert_eq!(first.match_group, second.match_group);
+
+        lobby.start_new_match();
+        assert_ne!(play_round(&mut lobby).match_group, first.match_group);
+    }
+
+    #[test]
+    fn e2e_no_repeats_holds_across_rematch() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.set_warm_up(true);
+        lobby.set_no_repeats(true);
+        lobby.start_round(test_letters_vec(), 60);
+        lobby.host_claim("cat").unwrap();
+        lobby.end_round(RoundEndReason::TimeExpired);
+
+        assert!(matches!(lobby.request_rematch(), Some(LobbyEvent::Countdown { .. })));
+        while lobby.state != LobbyState::Starting {
+            lobby.tick_countdown();
+        }
+        // The rematch deals its own rack; play it on the first one's letters
+        lobby.end_round(RoundEndReason::HostEnded);
+        lobby.start_round(test_letters_vec(), 60);
+        let events = lobby.host_claim("cat").unwrap();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::AlreadyClaimed { by }, .. } if by == "Host"
+        )), "A word from before the rematch should still be off limits");
+    }
+
+    #[test]
+    fn e2e_claim_accepted_carries_applied_combo() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.set_combos(true);
+        lobby.start_round(test_letters_vec(), 60);
+
+        let combo_of = |events: Vec<LobbyEvent>| {
+            events.into_iter().find_map(|e| match e {
+                LobbyEvent::ClaimAccepted { combo, .. } => Some(combo),
+                _ => None,
+            })
+        };
+        assert_eq!(combo_of(lobby.host_claim("cat").unwrap()), Some(1));
+        assert_eq!(combo_of(lobby.host_claim("act").unwrap()), Some(2));
+    }
+
+    #[test]
+    fn e2e_reaching_target_score_ends_round() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.set_warm_up(true);
+        lobby.set_target_score(8);
+        lobby.start_round(test_letters_vec(), 60);
+
+        let events = lobby.host_claim("cat").unwrap();
+        assert!(!events.iter().any(|e| matches!(e, LobbyEvent::RoundEnd { .. })));
+        assert_eq!(lobby.state, LobbyState::Starting);
+
+        let events = lobby.host_claim("act").unwrap();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::RoundEnd { reason: RoundEndReason::TargetReached }
+        )));
+        assert_eq!(lobby.state, LobbyState::Waiting);
+    }
+
+    #[test]
+    fn test_cycle_target_score_wraps_to_off() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        assert_eq!(lobby.target_score(), 0);
+        for &points in TARGET_SCORE_CHOICES[1..].iter() {
+            assert_eq!(lobby.cycle_target_score(), points);
+        }
+        assert_eq!(lobby.cycle_target_score(), 0);
+    }
+
+    #[test]
+    fn e2e_warm_up_round_with_host_alone() {
+        use crate::stats::DEFAULT_ELO;
+        use crate::storage::Storage;
+
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        assert!(!lobby.can_start(), "Two players are needed by default");
+        lobby.set_warm_up(true);
+        assert!(lobby.can_start());
+
+        lobby.start_round(test_letters_vec(), 60);
+        assert!(lobby.match_result(0, 1).is_none(), "No result while the round runs");
+        let events = lobby.host_claim("cat").unwrap();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::ClaimAccepted { .. })));
+        lobby.end_round(RoundEndReason::TimeExpired);
+
+        let result = lobby.match_result(1_700_000_000_000, 1).unwrap();
+        assert_eq!(result.scores, vec![("Host".to_string(), 5)]);
+        assert!(!result.is_multiplayer());
+        assert_eq!(result.round_id, Some(lobby.round_id()));
+
+        // Logged, it counts like a solo round and leaves Elo alone
+        let storage = Storage::open_in_memory().unwrap();
+        storage.append_event("match_end", &result.to_json()).unwrap();
+        storage.rebuild_derived_caches().unwrap();
+        let stats = storage.get_cached_stats("Host").unwrap().unwrap();
+        assert_eq!(stats.rounds_played, 1);
+        assert_eq!(stats.total_points, 5);
+        assert_eq!(stats.wins, 0);
+        assert!((stats.elo - DEFAULT_ELO).abs() < f64::EPSILON);
+
+        lobby.set_warm_up(false);
+        assert!(!lobby.can_start());
+    }
+
+    #[test]
+    fn e2e_bots_claim_valid_words_and_score() {
+        use crate::game::validation::{validate_word, ValidationResult};
+
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        assert!(!lobby.can_start());
+        assert!(matches!(lobby.add_bot(), Some(LobbyEvent::PlayerJoined(name)) if name == "Bot 1"));
+        assert!(lobby.can_start(), "A bot fills the second seat");
+        assert_eq!(lobby.cycle_bot_skill(), BotSkill::Hard);
+
+        lobby.start_round(test_letters_vec(), 60);
+        let start = Instant::now();
+        assert!(lobby.play_bots(start).is_empty(), "Bots take a moment to read the rack");
+
+        let mut points = 0;
+        let interval = BotSkill::Hard.claim_interval();
+        for step in 1..=5 {
+            for event in lobby.play_bots(start + interval * step) {
+                if let LobbyEvent::WordClaimed { word, player_name, points: p, .. } = event {
+                    assert_eq!(player_name, "Bot 1");
+                    assert_eq!(validate_word(&word, &TEST_LETTERS), ValidationResult::Valid);
+                    points += p;
+                }
+            }
+        }
+        assert_eq!(lobby.round_claims().len(), 5, "One claim per interval");
+        assert!(points > 0);
+        assert!(lobby.scores().contains(&("Bot 1".to_string(), points)));
+
+        // Unrated bots score but stay out of the match result
+        lobby.end_round(RoundEndReason::TimeExpired);
+        assert!(lobby.play_bots(start + interval * 10).is_empty());
+        let result = lobby.match_result(0, 1).unwrap();
+        assert_eq!(result.scores, vec![("Host".to_string(), 0)]);
+        lobby.set_bots_rated(true);
+        let result = lobby.match_result(0, 1).unwrap();
+        assert!(result.scores.contains(&("Bot 1".to_string(), points)));
+        assert!(result.is_multiplayer());
+
+        // Between rounds the host can thin the bots out again
+        assert!(lobby.remove_bot().is_some());
+        assert_eq!(lobby.player_count(), 1);
+        assert_eq!(lobby.add_bots(MAX_BOTS + 1), MAX_BOTS);
+        assert_eq!(lobby.cycle_bots(), 0);
+    }
+
+    #[test]
+    fn e2e_team_scores_aggregated_for_score_update_and_result() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        assert_eq!(lobby.add_bots(2), 2);
+        lobby.set_bots_rated(true);
+        assert!(lobby.assign_team("Host", Some(1)));
+        assert!(lobby.assign_team("Bot 1", Some(1)));
+        assert!(lobby.assign_team("Bot 2", Some(2)));
+        assert!(!lobby.assign_team("Nobody", Some(2)));
+
+        lobby.start_round(test_letters_vec(), 60);
+        lobby.host_claim("cat").unwrap();
+        lobby.handle_claim_attempt("dog", "Bot 1", None).unwrap();
+        let events = lobby.handle_claim_attempt("cats", "Bot 2", None).unwrap();
+        let team_totals = vec![("Team 1".to_string(), 11), ("Team 2".to_string(), 6)];
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, LobbyEvent::ScoreUpdate { scores } if *scores == team_totals)));
+
+        lobby.end_round(RoundEndReason::TimeExpired);
+        let result = lobby.match_result(0, 1).unwrap();
+        assert_eq!(result.scores.len(), 3, "the result keeps each player's points");
+        assert_eq!(result.team_of("Bot 1"), Some(1));
+        assert_eq!(result.team_scores(), team_totals);
+    }
+
+    #[test]
+    fn e2e_min_word_length_applies_to_host_claims() {
+        let letters = vec!['O', 'X', 'E', 'N', 'C', 'A', 'T'];
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        assert_eq!(lobby.min_word_length(), MIN_WORD_LENGTH);
+        assert_eq!(lobby.cycle_min_word_length(), 2);
+
+        lobby.start_round(letters.clone(), 60);
+        let events = lobby.host_claim("ox").unwrap();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::ClaimAccepted { .. })));
+        lobby.end_round(RoundEndReason::TimeExpired);
+
+        assert_eq!(lobby.cycle_min_word_length(), 3);
+        lobby.start_round(letters, 60);
+        let events = lobby.host_claim("ox").unwrap();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::TooShort, .. }
+        )));
+
+        // Wraps back to the default
+        assert_eq!(lobby.cycle_min_word_length(), MIN_WORD_LENGTH);
+    }
+
+    #[test]
+    fn e2e_min_letters_used_rejects_short_words_and_reaches_clients() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let port = lobby.port();
+        let mut client = Client::connect(&format!("127.0.0.1:{}", port), "Client".into()).unwrap();
+        client.join().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        assert_eq!(lobby.letters_used_rule(), LettersUsedRule::Off);
+        while lobby.cycle_letters_used_rule() != LettersUsedRule::AtLeast(5) {}
+        lobby.start_round(test_letters_vec(), 60);
+        assert_eq!(lobby.min_letters_used(), 5);
+
+        thread::sleep(Duration::from_millis(200));
+        let messages = client.poll();
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::RoundStart { min_letters_used: 5, .. }
+        )), "Client should hear the rule with the rack");
+
+        let events = lobby.host_claim("dogs").unwrap();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::TooFewLetters { min: 5 }, .. }
+        )));
+        let events = lobby.host_claim("codes").unwrap();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::ClaimAccepted { .. })));
+    }
+
+    #[test]
+    fn e2e_penalty_mode_docks_bad_claims_down_to_zero() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let port = lobby.port();
+        let mut client = Client::connect(&format!("127.0.0.1:{}", port), "Client".into()).unwrap();
+        client.join().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        assert_eq!(lobby.reject_penalty(), 0);
+        assert_eq!(lobby.cycle_reject_penalty(), 1);
+        assert_eq!(lobby.cycle_reject_penalty(), 2);
+        lobby.start_round(test_letters_vec(), 60);
+        thread::sleep(Duration::from_millis(200));
+        assert!(client.poll().iter().any(|m| matches!(
+            m,
+            Message::RoundStart { reject_penalty: 2, .. }
+        )), "Client should hear the penalty with the rack");
 
-    /// Fixed letters for deterministic testing.
-    /// Contains letters for: CAT, DOG, CATS, DOGS, TAN, ANT, COD, COG, etc.
-    const TEST_LETTERS: [char; 12] = ['C', 'A', 'T', 'D', 'O', 'G', 'S', 'N', 'E', 'R', 'I', 'T'];
+        // CAT (5) less one bad guess
+        client.send_claim_attempt("cat").unwrap();
+        client.send_claim_attempt("tacd").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert!(lobby.scores().contains(&("Client".to_string(), 3)));
 
-    fn test_letters_vec() -> Vec<char> {
-        TEST_LETTERS.to_vec()
-    }
+        // Two more misses take it to zero and no further
+        client.send_claim_attempt("zap").unwrap();
+        client.send_claim_attempt("zzz").unwrap();
+        // Losing a race costs nothing
+        lobby.host_claim("dog").unwrap();
+        client.send_claim_attempt("dog").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        assert!(lobby.scores().contains(&("Client".to_string(), 0)));
+        assert!(lobby.scores().contains(&("Host".to_string(), 6)));
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::ScoreUpdate { .. })));
 
-    // =========================================================================
-    // Anti-cheat: Server-authoritative claim validation
-    // =========================================================================
+        thread::sleep(Duration::from_millis(200));
+        let last_scores = client
+            .poll()
+            .into_iter()
+            .filter_map(|m| match m {
+                Message::ScoreUpdate { scores } => Some(scores),
+                _ => None,
+            })
+            .next_back()
+            .unwrap();
+        assert!(last_scores.contains(&("Client".to_string(), 0)));
+    }
 
     #[test]
-    fn e2e_anticheat_host_rejects_word_not_on_board() {
-        // Host creates lobby, starts round, attempts a word using letters NOT in rack
+    fn e2e_repeats_allowed_across_rounds_by_default() {
         let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        assert!(!lobby.no_repeats());
+
         lobby.start_round(test_letters_vec(), 60);
+        lobby.host_claim("cat").unwrap();
+        lobby.end_round(RoundEndReason::TimeExpired);
 
-        // "ZAP" requires Z which is not in the rack
-        let events = lobby.host_claim("zap").unwrap();
+        lobby.start_round(test_letters_vec(), 60);
+        let events = lobby.host_claim("cat").unwrap();
         assert!(events.iter().any(|e| matches!(
             e,
-            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::InvalidLetters { .. }, .. }
-        )), "Server should reject words with letters not on the board");
+            LobbyEvent::ClaimAccepted { word, .. } if word == "CAT"
+        )), "Rounds are independent unless no-repeats is on");
     }
 
     #[test]
@@ -1151,19 +3636,19 @@ mod e2e_tests {
         let mut lobby = HostedLobby::new("Host".into()).unwrap();
         lobby.start_round(test_letters_vec(), 60);
 
-        // Host claims CAT (3 pts) and DOG (3 pts)
+        // Host claims CAT (5 pts) and DOG (6 pts)
         lobby.host_claim("cat");
         lobby.host_claim("dog");
 
         let scores = lobby.scores();
         let host_score = scores.iter().find(|(n, _)| n == "Host").map(|(_, s)| *s).unwrap_or(0);
-        assert_eq!(host_score, 6, "Score should be sum of word lengths: 3 + 3 = 6");
+        assert_eq!(host_score, 11, "Score should be sum of word scores: 5 + 6 = 11");
 
         // Try to claim an invalid word - score should NOT change
         lobby.host_claim("zzz");
         let scores = lobby.scores();
         let host_score = scores.iter().find(|(n, _)| n == "Host").map(|(_, s)| *s).unwrap_or(0);
-        assert_eq!(host_score, 6, "Score should not change after invalid claim");
+        assert_eq!(host_score, 11, "Score should not change after invalid claim");
     }
 
     #[test]
@@ -1178,7 +3663,7 @@ mod e2e_tests {
             let events = lobby.host_claim(word).unwrap();
             assert!(events.iter().any(|e| matches!(e, LobbyEvent::ClaimAccepted { .. })),
                 "Rapid submission of '{}' should be accepted", word);
-            total_expected += word.len() as u32;
+            total_expected += crate::game::scoring::score_word(word);
         }
 
         let scores = lobby.scores();
@@ -1218,7 +3703,7 @@ mod e2e_tests {
         lobby.host_claim("cat");
 
         // End the round
-        lobby.end_round();
+        lobby.end_round(RoundEndReason::HostEnded);
 
         // Try to claim after round end
         let events = lobby.host_claim("dog").unwrap();
@@ -1228,6 +3713,90 @@ mod e2e_tests {
         )), "Claims after round end should be rejected");
     }
 
+    #[test]
+    fn e2e_anticheat_claims_rejected_once_host_timer_expires() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.start_round(test_letters_vec(), 60);
+        assert!(lobby.host_claim("cat").unwrap().iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimAccepted { .. }
+        )));
+
+        // Host's timer runs out without anyone calling end_round
+        lobby.round_deadline = Some(Instant::now() - Duration::from_millis(1));
+
+        let events = lobby.host_claim("dog").unwrap();
+        assert!(matches!(
+            events.first(),
+            Some(LobbyEvent::RoundEnd { reason: RoundEndReason::TimeExpired })
+        ), "Round end should be processed before the late claim");
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::RoundEnded, .. }
+        )), "Claims after the host's deadline should be rejected");
+
+        // The round only ends once
+        let events = lobby.host_claim("act").unwrap();
+        assert!(!events.iter().any(|e| matches!(e, LobbyEvent::RoundEnd { .. })));
+    }
+
+    #[test]
+    fn e2e_handicap_applies_to_host_claims() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.set_handicap("Host", 1.5);
+        lobby.start_round(test_letters_vec(), 60);
+
+        let events = lobby.host_claim("cats").unwrap();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimAccepted { points: 9, .. }
+        )), "1.5x handicap should turn a 6-point word into 9 points");
+    }
+
+    #[test]
+    fn e2e_handicap_clamped_and_stepped() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.set_handicap("Host", 10.0);
+        assert_eq!(lobby.handicap("Host"), MAX_HANDICAP);
+
+        lobby.set_handicap("Host", 1.0);
+        assert!(lobby.handicaps().is_empty());
+
+        lobby.adjust_handicap("Host", false);
+        assert_eq!(lobby.handicap("Host"), 1.0 - HANDICAP_STEP);
+    }
+
+    #[test]
+    fn e2e_anticheat_overlong_claim_rejected_early() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.start_round(test_letters_vec(), 60);
+
+        // A huge claim is rejected as too long rather than reaching letter or
+        // dictionary validation (which would report InvalidLetters/NotInDictionary)
+        let huge = "A".repeat(100_000);
+        let events = lobby.host_claim(&huge).unwrap();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::TooLong { max: 12 }, word }
+                if word.len() == 12
+        )), "Overlong claims should be rejected as TooLong");
+
+        // A claim exactly the rack length still goes through normal validation
+        let events = lobby.host_claim("catdogsnerit").unwrap();
+        assert!(!events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::TooLong { .. }, .. }
+        )));
+
+        // Length is counted in letters, not bytes
+        lobby.start_round(vec!['A', 'Ñ', 'O'], 60);
+        let events = lobby.host_claim("AÑO").unwrap();
+        assert!(!events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::TooLong { .. }, .. }
+        )), "A three-letter word fits a three-tile rack");
+    }
+
     #[test]
     fn e2e_anticheat_case_insensitive_duplicate_detection() {
         let mut lobby = HostedLobby::new("Host".into()).unwrap();
@@ -1296,6 +3865,389 @@ mod e2e_tests {
         assert_eq!(lobby.player_count(), 2, "Lobby should have host + client = 2 players");
     }
 
+    #[test]
+    fn e2e_player_status_relayed_to_lobby() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let port = lobby.port();
+
+        let mut client = Client::connect(&format!("127.0.0.1:{}", port), "Client".into()).unwrap();
+        client.join().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        // Overlong, padded statuses are cleaned up by the host
+        client.send_status("  ready to blam everyone  ").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+
+        let expected: String = "ready to blam everyone".chars().take(MAX_STATUS_LEN).collect();
+        let expected = expected.trim_end().to_string();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::PlayerStatus { player_name, status: Some(status) }
+                if player_name == "Client" && *status == expected
+        )));
+        let player = lobby.players().iter().find(|p| p.name == "Client").unwrap();
+        assert_eq!(player.status.as_deref(), Some(expected.as_str()));
+
+        // The host relays it to everyone, including the sender
+        thread::sleep(Duration::from_millis(200));
+        let relayed = client.poll();
+        assert!(relayed.iter().any(|m| matches!(
+            m,
+            Message::PlayerStatus { player_name, status } if player_name == "Client" && *status == expected
+        )));
+
+        // The host's own status goes out the same way
+        assert!(lobby.set_status("brb").is_some());
+        thread::sleep(Duration::from_millis(200));
+        let relayed = client.poll();
+        assert!(relayed.iter().any(|m| matches!(
+            m,
+            Message::PlayerStatus { player_name, status } if player_name == "Host" && status == "brb"
+        )));
+    }
+
+    #[test]
+    fn e2e_lobby_members_sync_event_logs_over_lobby_connection() {
+        use crate::storage::sync::{create_sync_request, handle_sync_message};
+        use crate::storage::Storage;
+
+        let host_log = Storage::open_in_memory().unwrap();
+        host_log.append_event("match_end", r#"{"match_id":1}"#).unwrap();
+        host_log.append_event("match_end", r#"{"match_id":2}"#).unwrap();
+        let client_log = Storage::open_in_memory().unwrap();
+        client_log.append_event("match_end", r#"{"match_id":3}"#).unwrap();
+
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = peer_at(vec!["127.0.0.1".parse().unwrap()], lobby.port());
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        // Each side sends its clock over the lobby connection
+        joined.send_sync(create_sync_request(&client_log).unwrap()).unwrap();
+        assert!(lobby.send_sync("Client", &create_sync_request(&host_log).unwrap()));
+
+        // Both answer what they're sent (requests get the missing events
+        // back, event batches are merged) until nothing is left in flight
+        for _ in 0..3 {
+            thread::sleep(Duration::from_millis(200));
+            for event in lobby.poll() {
+                if let LobbyEvent::Sync { player_name, message } = event {
+                    assert_eq!(player_name, "Client");
+                    if let Some(reply) = handle_sync_message(&host_log, message).unwrap() {
+                        lobby.send_sync(&player_name, &reply);
+                    }
+                }
+            }
+            for event in joined.poll() {
+                if let LobbyEvent::Sync { player_name, message } = event {
+                    assert_eq!(player_name, "Host");
+                    if let Some(reply) = handle_sync_message(&client_log, message).unwrap() {
+                        joined.send_sync(reply).unwrap();
+                    }
+                }
+            }
+        }
+
+        assert_eq!(host_log.event_count().unwrap(), 3);
+        assert_eq!(client_log.event_count().unwrap(), 3);
+        let mut host_clock = host_log.get_vector_clock().unwrap();
+        let mut client_clock = client_log.get_vector_clock().unwrap();
+        host_clock.sort_by_key(|(actor, _)| actor.to_hex());
+        client_clock.sort_by_key(|(actor, _)| actor.to_hex());
+        assert_eq!(host_clock, client_clock);
+    }
+
+    #[test]
+    fn e2e_large_sync_batch_arrives_compressed() {
+        use crate::storage::sync::{create_sync_request, handle_sync_message};
+        use crate::storage::Storage;
+
+        let host_log = Storage::open_in_memory().unwrap();
+        for i in 0..200 {
+            host_log.append_event("match_end", &format!(r#"{{"match_id":{}}}"#, i)).unwrap();
+        }
+        let client_log = Storage::open_in_memory().unwrap();
+
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = peer_at(vec!["127.0.0.1".parse().unwrap()], lobby.port());
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        joined.send_sync(create_sync_request(&client_log).unwrap()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        for event in lobby.poll() {
+            if let LobbyEvent::Sync { player_name, message } = event {
+                let reply = handle_sync_message(&host_log, message).unwrap().unwrap();
+                assert!(lobby.send_sync(&player_name, &reply));
+            }
+        }
+
+        thread::sleep(Duration::from_millis(200));
+        let batches: Vec<Message> = joined
+            .poll()
+            .into_iter()
+            .filter_map(|event| match event {
+                LobbyEvent::Sync { message, .. } => Some(message),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(batches.len(), 1);
+        assert!(matches!(batches[0], Message::SyncEventsCompressed { .. }));
+        handle_sync_message(&client_log, batches[0].clone()).unwrap();
+        assert_eq!(client_log.event_count().unwrap(), 200);
+    }
+
+    #[test]
+    fn e2e_host_stores_latest_input_preview() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let port = lobby.port();
+
+        let mut client = Client::connect(&format!("127.0.0.1:{}", port), "Client".into()).unwrap();
+        client.join().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert_eq!(lobby.input_preview("Client"), None);
+
+        client.send_input_preview("ca").unwrap();
+        client.send_input_preview("cat").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::InputPreview { player_name, text } if player_name == "Client" && text == "CAT"
+        )));
+        assert_eq!(lobby.input_preview("Client"), Some("CAT"));
+
+        // An empty preview clears it
+        client.send_input_preview("").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert_eq!(lobby.input_preview("Client"), None);
+
+        // Previews don't carry over into a new round
+        client.send_input_preview("dog").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert_eq!(lobby.input_preview("Client"), Some("DOG"));
+        lobby.start_round(vec!['D', 'O', 'G'], 60);
+        assert_eq!(lobby.input_preview("Client"), None);
+    }
+
+    fn peer_at(addresses: Vec<PeerAddress>, port: u16) -> PeerInfo {
+        PeerInfo {
+            actor_id: "blam-test".into(),
+            handle: "Host".into(),
+            lobby_name: Some("TEST-LOBBY".into()),
+            version: "1".into(),
+            hostname: "test.local".into(),
+            addresses,
+            port,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_browser_status_transitions() {
+        let mut browser = LobbyBrowser::new().unwrap();
+        assert_eq!(browser.status(), &BrowserStatus::Searching);
+
+        // Feed the browser our own discovery events
+        let (tx, rx) = std::sync::mpsc::channel();
+        browser.discovery_rx = rx;
+        tx.send(DiscoveryEvent::SearchStarted).unwrap();
+        assert!(browser.poll().is_empty());
+        assert_eq!(browser.status(), &BrowserStatus::Listening);
+
+        tx.send(DiscoveryEvent::BrowseFailed("no interfaces".into())).unwrap();
+        browser.poll();
+        assert_eq!(browser.status(), &BrowserStatus::Failed("no interfaces".into()));
+
+        // A lost discovery channel counts as a failure too
+        browser.retry();
+        assert_eq!(browser.status(), &BrowserStatus::Searching);
+        let (tx, rx) = std::sync::mpsc::channel();
+        browser.discovery_rx = rx;
+        drop(tx);
+        browser.poll();
+        assert!(matches!(browser.status(), BrowserStatus::Failed(_)));
+        let _ = browser.stop();
+    }
+
+    #[test]
+    fn test_browser_drops_lobby_not_seen_within_ttl() {
+        let mut browser = LobbyBrowser::new().unwrap();
+        let mut fresh = peer_at(vec![], 55333);
+        fresh.actor_id = "blam-fresh".into();
+        let mut stale = peer_at(vec![], 55334);
+        stale.actor_id = "blam-stale".into();
+        // Last resolved longer ago than the TTL, and never reported lost
+        stale.last_seen = Instant::now()
+            .checked_sub(PEER_TTL + Duration::from_secs(1))
+            .unwrap();
+        browser.peers.update(fresh);
+        browser.peers.update(stale);
+
+        let listed: Vec<String> = browser.poll().into_iter().map(|p| p.actor_id).collect();
+        assert_eq!(listed, vec!["blam-fresh".to_string()]);
+        let _ = browser.stop();
+    }
+
+    fn lobby_peer(actor_id: &str, lobby_name: &str) -> PeerInfo {
+        let mut peer = peer_at(vec!["127.0.0.1".parse().unwrap()], 55333);
+        peer.actor_id = actor_id.into();
+        peer.lobby_name = Some(lobby_name.into());
+        peer
+    }
+
+    #[test]
+    fn test_browser_sorts_lobbies_stably_across_polls() {
+        let mut browser = LobbyBrowser::new().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        browser.discovery_rx = rx;
+        for (actor_id, lobby_name) in [
+            ("blam-3", "SWIFT-ORBIT"),
+            ("blam-1", "AMBER-FOX"),
+            ("blam-4", "CALM-RIVER"),
+            ("blam-2", "SWIFT-ORBIT"),
+        ] {
+            tx.send(DiscoveryEvent::PeerDiscovered(lobby_peer(actor_id, lobby_name))).unwrap();
+        }
+        let listed = |lobbies: Vec<PeerInfo>| -> Vec<String> {
+            lobbies.into_iter().map(|p| p.actor_id).collect()
+        };
+        let expected = vec!["blam-1", "blam-4", "blam-2", "blam-3"];
+        assert_eq!(listed(browser.poll()), expected);
+
+        // Lobbies refreshing in a different order don't move
+        for (actor_id, lobby_name) in [("blam-2", "SWIFT-ORBIT"), ("blam-1", "AMBER-FOX")] {
+            tx.send(DiscoveryEvent::PeerDiscovered(lobby_peer(actor_id, lobby_name))).unwrap();
+        }
+        for _ in 0..3 {
+            assert_eq!(listed(browser.poll()), expected);
+        }
+        let _ = browser.stop();
+    }
+
+    #[test]
+    fn test_browser_filter_hides_full_and_incompatible_lobbies() {
+        let mut browser = LobbyBrowser::new().unwrap();
+        let mut full = lobby_peer("blam-full", "AMBER-FOX");
+        full.player_count = Some(MAX_PLAYERS);
+        let mut open = lobby_peer("blam-open", "CALM-RIVER");
+        open.player_count = Some(MAX_PLAYERS - 1);
+        let mut newer = lobby_peer("blam-newer", "SWIFT-ORBIT");
+        newer.version = "2".into();
+        // Older hosts don't say how many are in
+        let unknown = lobby_peer("blam-unknown", "ZANY-ZEBRA");
+        for peer in [full, open, newer, unknown] {
+            browser.peers.update(peer);
+        }
+        let listed = |browser: &mut LobbyBrowser| -> Vec<String> {
+            browser.poll().into_iter().map(|p| p.actor_id).collect()
+        };
+        assert_eq!(listed(&mut browser).len(), 4);
+
+        browser.set_filter(LobbyFilter { hide_full: true, hide_incompatible: false });
+        assert_eq!(listed(&mut browser), vec!["blam-open", "blam-newer", "blam-unknown"]);
+        browser.set_filter(LobbyFilter { hide_full: true, hide_incompatible: true });
+        assert_eq!(listed(&mut browser), vec!["blam-open", "blam-unknown"]);
+        browser.set_filter(LobbyFilter::default());
+        assert_eq!(listed(&mut browser).len(), 4);
+        let _ = browser.stop();
+    }
+
+    #[test]
+    fn e2e_host_advertises_current_player_count_and_state() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let waiting = LobbyAdvert {
+            players: 1,
+            max_players: MAX_PLAYERS,
+            in_progress: false,
+            dictionary: dictionary::fingerprint(),
+        };
+        assert_eq!(lobby.advertised, waiting);
+
+        // A simulated join is advertised on the next poll
+        let alice: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+        lobby.handle_server_event(ServerEvent::MessageReceived {
+            from: alice,
+            player_name: None,
+            message: Message::Join { player_name: "Alice".into() },
+        });
+        lobby.poll();
+        assert_eq!(lobby.advertised.players, 2);
+
+        lobby.start_round(vec!['C', 'A', 'T'], 60);
+        lobby.poll();
+        assert!(lobby.advertised.in_progress);
+
+        // A real one too, and the round ending
+        let peer = peer_at(vec!["127.0.0.1".parse().unwrap()], lobby.port());
+        let joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.end_round(RoundEndReason::HostEnded);
+        lobby.poll();
+        assert_eq!(lobby.advertised, LobbyAdvert { players: 3, ..waiting });
+
+        joined.leave();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert_eq!(lobby.advertised.players, 2);
+    }
+
+    #[test]
+    fn e2e_join_closed_port_is_refused() {
+        // Grab a free port, then close it so nothing is listening there
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let peer = peer_at(vec!["127.0.0.1".parse().unwrap()], port);
+        let result = JoinedLobby::join(&peer, "Client".into());
+        assert!(matches!(result, Err(JoinError::Refused)));
+    }
+
+    #[test]
+    fn e2e_join_without_address() {
+        let peer = peer_at(Vec::new(), 55333);
+        let result = JoinedLobby::join(&peer, "Client".into());
+        assert!(matches!(result, Err(JoinError::NoAddress)));
+    }
+
+    #[test]
+    fn e2e_join_skips_link_local_address_without_scope() {
+        let lobby = HostedLobby::new("Host".into()).unwrap();
+        let port = lobby.port();
+
+        // Only a link-local address whose interface we never learned
+        let unscoped: PeerAddress = "fe80::1".parse().unwrap();
+        let peer = peer_at(vec![unscoped.clone()], port);
+        let result = JoinedLobby::join(&peer, "Client".into());
+        assert!(matches!(result, Err(JoinError::NoAddress)));
+
+        // Listed first, it's passed over for one we can reach
+        let peer = peer_at(vec![unscoped, "127.0.0.1".parse().unwrap()], port);
+        let joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+        assert_eq!(joined.host_addr, format!("127.0.0.1:{}", port).parse().unwrap());
+
+        // A scoped one is connected to on its interface
+        let scoped = PeerAddress::scoped("fe80::1".parse().unwrap(), 2, "eth0".to_string());
+        let peer = peer_at(vec![scoped.clone()], 55333);
+        assert_eq!(scoped.with_port(peer.port), "[fe80::1%eth0]:55333");
+        assert_eq!(peer.connect_addr(), Some("[fe80::1%2]:55333".parse().unwrap()));
+    }
+
     #[test]
     fn e2e_multiplayer_client_receives_round_start() {
         let mut lobby = HostedLobby::new("Host".into()).unwrap();
@@ -1320,10 +4272,66 @@ mod e2e_tests {
 
         assert!(messages.iter().any(|m| matches!(
             m,
-            Message::RoundStart { letters: l, duration_secs: 60 } if *l == letters
+            Message::RoundStart { letters: l, duration_secs: 60, .. } if *l == letters
         )), "Client should receive RoundStart with correct letters and duration");
     }
 
+    #[test]
+    fn e2e_seeded_round_start_deals_same_rack_on_client() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = peer_at(vec!["127.0.0.1".parse().unwrap()], lobby.port());
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        // A generated rack goes out as its seed, which the client deals itself
+        let letters = lobby.generate_letters();
+        assert!(matches!(
+            lobby.round_start_message(&letters, 60),
+            Message::RoundStartSeeded { duration_secs: 60, .. }
+        ));
+        lobby.start_round(letters.clone(), 60);
+        thread::sleep(Duration::from_millis(200));
+        assert!(joined.poll().iter().any(|e| matches!(
+            e,
+            LobbyEvent::RoundStart { letters: l, duration: 60, .. } if *l == letters
+        )), "Client should deal the host's rack from the seed");
+
+        // Letters that didn't come from the seed are still sent as they are
+        assert!(matches!(
+            lobby.round_start_message(&test_letters_vec(), 60),
+            Message::RoundStart { .. }
+        ));
+    }
+
+    #[test]
+    fn e2e_seeded_countdown_deals_same_rack_on_client() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = peer_at(vec!["127.0.0.1".parse().unwrap()], lobby.port());
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        let letters = lobby.generate_letters();
+        lobby.start_countdown(letters.clone(), 60);
+        assert!(matches!(lobby.countdown_message(3), Message::CountdownSeeded { .. }));
+        lobby.tick_countdown();
+        thread::sleep(Duration::from_millis(200));
+        let countdowns: Vec<_> = joined
+            .poll()
+            .into_iter()
+            .filter_map(|e| match e {
+                LobbyEvent::Countdown { letters, countdown, .. } => Some((letters, countdown)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(countdowns, vec![(letters.clone(), 3), (letters, 2)]);
+
+        // A rack the host picked is still sent as letters
+        lobby.start_countdown(test_letters_vec(), 60);
+        assert!(matches!(lobby.countdown_message(3), Message::Countdown { .. }));
+    }
+
     #[test]
     fn e2e_multiplayer_client_claim_validated_by_server() {
         let mut lobby = HostedLobby::new("Host".into()).unwrap();
@@ -1351,7 +4359,7 @@ mod e2e_tests {
         // Server should accept
         assert!(events.iter().any(|e| matches!(
             e,
-            LobbyEvent::ClaimAccepted { word, player_name, points: 3 }
+            LobbyEvent::ClaimAccepted { word, player_name, points: 5, .. }
             if word == "CAT" && player_name == "Client"
         )), "Server should validate and accept client's valid claim");
 
@@ -1360,7 +4368,7 @@ mod e2e_tests {
         let messages = client.poll();
         assert!(messages.iter().any(|m| matches!(
             m,
-            Message::ClaimAccepted { word, player_name, points: 3 }
+            Message::ClaimAccepted { word, player_name, points: 5, .. }
             if word == "CAT" && player_name == "Client"
         )), "Client should receive ClaimAccepted confirmation");
     }
@@ -1439,18 +4447,61 @@ mod e2e_tests {
 
         thread::sleep(Duration::from_millis(200));
         let messages = client.poll();
-        assert!(messages.iter().any(|m| matches!(
-            m,
-            Message::ClaimAccepted { word, player_name, points: 3 }
-            if word == "DOG" && player_name == "Client"
-        )), "Client should claim DOG successfully");
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::ClaimAccepted { word, player_name, points: 6, .. }
+            if word == "DOG" && player_name == "Client"
+        )), "Client should claim DOG successfully");
+
+        // Verify final scores: Host=5 (CAT), Client=6 (DOG)
+        let scores = lobby.scores();
+        let host_score = scores.iter().find(|(n, _)| n == "Host").map(|(_, s)| *s).unwrap_or(0);
+        let client_score = scores.iter().find(|(n, _)| n == "Client").map(|(_, s)| *s).unwrap_or(0);
+        assert_eq!(host_score, 5, "Host should have 5 points for CAT");
+        assert_eq!(client_score, 6, "Client should have 6 points for DOG");
+    }
+
+    #[test]
+    fn e2e_multiplayer_announcement_broadcast() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let port = lobby.port();
+
+        let mut client = Client::connect(
+            &format!("127.0.0.1:{}", port),
+            "Client".into(),
+        ).unwrap();
+        client.join().unwrap();
+
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        // Blank notices aren't sent
+        assert!(lobby.announce("   ").is_none());
+
+        let event = lobby.announce("  Final round!  ");
+        assert!(matches!(
+            event,
+            Some(LobbyEvent::Announcement { ref text }) if text == "Final round!"
+        ));
+        assert_eq!(lobby.announcement(), Some("Final round!"));
+
+        thread::sleep(Duration::from_millis(200));
+        let messages = client.poll();
+        let announcements: Vec<_> = messages
+            .iter()
+            .filter(|m| matches!(m, Message::SystemAnnouncement { .. }))
+            .collect();
+        assert_eq!(announcements, vec![&Message::SystemAnnouncement {
+            text: "Final round!".into(),
+        }]);
+    }
 
-        // Verify final scores: Host=3 (CAT), Client=3 (DOG)
-        let scores = lobby.scores();
-        let host_score = scores.iter().find(|(n, _)| n == "Host").map(|(_, s)| *s).unwrap_or(0);
-        let client_score = scores.iter().find(|(n, _)| n == "Client").map(|(_, s)| *s).unwrap_or(0);
-        assert_eq!(host_score, 3, "Host should have 3 points for CAT");
-        assert_eq!(client_score, 3, "Client should have 3 points for DOG");
+    #[test]
+    fn e2e_announcement_capped_at_max_length() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let long = "x".repeat(MAX_ANNOUNCEMENT_LEN * 2);
+        lobby.announce(&long);
+        assert_eq!(lobby.announcement().map(|t| t.len()), Some(MAX_ANNOUNCEMENT_LEN));
     }
 
     #[test]
@@ -1503,13 +4554,15 @@ mod e2e_tests {
         client.poll();
 
         // End the round
-        lobby.end_round();
+        lobby.end_round(RoundEndReason::HostEnded);
 
         // Client should receive RoundEnd
         thread::sleep(Duration::from_millis(200));
         let messages = client.poll();
-        assert!(messages.iter().any(|m| matches!(m, Message::RoundEnd)),
-            "RoundEnd should be broadcast to clients");
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::RoundEnd { reason: RoundEndReason::HostEnded }
+        )), "RoundEnd should be broadcast to clients with the reason");
     }
 
     #[test]
@@ -1531,7 +4584,7 @@ mod e2e_tests {
         client.poll();
 
         // End round
-        lobby.end_round();
+        lobby.end_round(RoundEndReason::HostEnded);
         thread::sleep(Duration::from_millis(100));
         client.poll(); // consume RoundEnd
 
@@ -1548,6 +4601,45 @@ mod e2e_tests {
         )), "Claims after round end should be rejected for remote clients");
     }
 
+    #[test]
+    fn e2e_multiplayer_late_claim_rejected_after_host_deadline() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let port = lobby.port();
+
+        let mut client = Client::connect(
+            &format!("127.0.0.1:{}", port),
+            "Client".into(),
+        ).unwrap();
+        client.join().unwrap();
+
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        lobby.start_round(test_letters_vec(), 60);
+        thread::sleep(Duration::from_millis(100));
+        client.poll();
+
+        // Client claims after the host's timer has expired but before the
+        // host has noticed
+        lobby.round_deadline = Some(Instant::now() - Duration::from_millis(1));
+        client.send_claim_attempt("cat").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::RoundEnd { .. })));
+        assert!(!events.iter().any(|e| matches!(e, LobbyEvent::ClaimAccepted { .. })));
+
+        thread::sleep(Duration::from_millis(200));
+        let messages = client.poll();
+        let round_end = messages.iter().position(|m| matches!(m, Message::RoundEnd { .. }));
+        let rejected = messages.iter().position(|m| matches!(
+            m,
+            Message::ClaimRejected { reason: ClaimRejectReason::RoundEnded, .. }
+        ));
+        assert!(round_end.is_some() && rejected.is_some(),
+            "Client should see the round end and a RoundEnded rejection");
+        assert!(round_end < rejected, "Round end should be sent before the rejection");
+    }
+
     #[test]
     fn e2e_multiplayer_two_clients_full_game() {
         let mut lobby = HostedLobby::new("Host".into()).unwrap();
@@ -1591,8 +4683,8 @@ mod e2e_tests {
         lobby.poll();
 
         // End round
-        let end_events = lobby.end_round();
-        assert!(end_events.iter().any(|e| matches!(e, LobbyEvent::RoundEnd)));
+        let end_events = lobby.end_round(RoundEndReason::HostEnded);
+        assert!(end_events.iter().any(|e| matches!(e, LobbyEvent::RoundEnd { .. })));
 
         // Verify final scores
         let scores = lobby.scores();
@@ -1600,8 +4692,8 @@ mod e2e_tests {
         let alice_score = scores.iter().find(|(n, _)| n == "Alice").map(|(_, s)| *s).unwrap_or(0);
         let bob_score = scores.iter().find(|(n, _)| n == "Bob").map(|(_, s)| *s).unwrap_or(0);
 
-        assert_eq!(host_score, 3, "Host: CAT = 3pts");
-        assert_eq!(alice_score, 3, "Alice: DOG = 3pts");
+        assert_eq!(host_score, 5, "Host: CAT = 5pts");
+        assert_eq!(alice_score, 6, "Alice: DOG = 6pts");
         assert_eq!(bob_score, 3, "Bob: TAN = 3pts");
     }
 
@@ -1647,6 +4739,62 @@ mod e2e_tests {
 
         assert_eq!(accepted_count, 1, "Only one concurrent claim should be accepted");
         assert_eq!(rejected_count, 1, "The other concurrent claim should be rejected as duplicate");
+
+        // The loser is told exactly who beat them
+        let winner = events.iter().find_map(|e| match e {
+            LobbyEvent::ClaimAccepted { player_name, .. } => Some(player_name.clone()),
+            _ => None,
+        }).unwrap();
+        let (mut winner_client, mut loser_client) = if winner == "Alice" {
+            (client1, client2)
+        } else {
+            (client2, client1)
+        };
+        thread::sleep(Duration::from_millis(200));
+        assert!(winner_client.poll().iter().any(|m| matches!(
+            m,
+            Message::ClaimAccepted { player_name, .. } if *player_name == winner
+        )));
+        assert!(loser_client.poll().iter().any(|m| matches!(
+            m,
+            Message::ClaimRejected { reason: ClaimRejectReason::AlreadyClaimed { by }, .. } if *by == winner
+        )), "Loser should be told the word went to {}", winner);
+    }
+
+    #[test]
+    fn e2e_identical_claims_in_one_batch_settled_in_order() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let alice: SocketAddr = "10.0.0.1:4000".parse().unwrap();
+        let bob: SocketAddr = "10.0.0.2:4000".parse().unwrap();
+        let received = |from, message| ServerEvent::MessageReceived {
+            from,
+            player_name: None,
+            message,
+        };
+
+        lobby.handle_server_event(received(alice, Message::Join { player_name: "Alice".into() }));
+        lobby.handle_server_event(received(bob, Message::Join { player_name: "Bob".into() }));
+        lobby.start_round(test_letters_vec(), 60);
+
+        // Both attempts arrive in the same poll batch, Bob's first
+        let batch = vec![
+            received(bob, Message::ClaimAttempt { word: "cat".into() }),
+            received(alice, Message::ClaimAttempt { word: "cat".into() }),
+        ];
+        let events: Vec<LobbyEvent> = batch
+            .into_iter()
+            .flat_map(|e| lobby.handle_server_event(e))
+            .collect();
+
+        let accepted: Vec<&str> = events.iter().filter_map(|e| match e {
+            LobbyEvent::ClaimAccepted { player_name, .. } => Some(player_name.as_str()),
+            _ => None,
+        }).collect();
+        assert_eq!(accepted, vec!["Bob"]);
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::AlreadyClaimed { by }, .. } if by == "Bob"
+        )), "Alice's attempt should be rejected naming Bob");
     }
 
     #[test]
@@ -1671,65 +4819,510 @@ mod e2e_tests {
         thread::sleep(Duration::from_millis(300));
         let events = lobby.poll();
 
-        // Should detect player left
-        assert!(events.iter().any(|e| matches!(
-            e,
-            LobbyEvent::PlayerLeft(name) if name == "Quitter"
-        )), "Server should detect when a client disconnects");
+        // Should detect player left
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::PlayerLeft(name) if name == "Quitter"
+        )), "Server should detect when a client disconnects");
+    }
+
+    #[test]
+    fn e2e_kicked_player_removed_and_told_why() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = PeerInfo {
+            actor_id: "host".into(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let mut joined = JoinedLobby::join(&peer, "Rowdy".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        joined.poll();
+        assert_eq!(lobby.player_count(), 2);
+
+        let event = lobby.kick_player("Rowdy");
+        assert!(matches!(event, Some(LobbyEvent::PlayerLeft(ref name)) if name == "Rowdy"));
+        assert_eq!(lobby.player_count(), 1);
+        assert!(lobby.players().iter().all(|p| p.name != "Rowdy"));
+
+        thread::sleep(Duration::from_millis(300));
+        let events = joined.poll();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::Kicked { reason } if reason == KICK_REASON
+        )));
+        assert!(joined.is_kicked());
+        // Kicked means gone: no trying to get back in
+        thread::sleep(Duration::from_millis(200));
+        assert!(joined.poll().is_empty());
+        assert_eq!(joined.reconnect_attempts(), 0);
+
+        // The closed connection isn't reported as a second departure
+        let events = lobby.poll();
+        assert!(!events.iter().any(|e| matches!(e, LobbyEvent::PlayerLeft(_))));
+        assert_eq!(lobby.player_count(), 1);
+    }
+
+    #[test]
+    fn e2e_rematch_starts_only_after_all_players_vote_yes() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = PeerInfo {
+            actor_id: "host".into(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let mut alice = JoinedLobby::join(&peer, "Alice".into()).unwrap();
+        let mut bob = JoinedLobby::join(&peer, "Bob".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert_eq!(lobby.player_count(), 3);
+
+        // Nothing to rematch before a round has been played
+        assert!(lobby.request_rematch().is_none());
+        lobby.start_round(test_letters_vec(), 60);
+        assert!(lobby.request_rematch().is_none(), "round still running");
+        lobby.end_round(RoundEndReason::HostEnded);
+
+        let offered = |joined: &mut JoinedLobby| {
+            thread::sleep(Duration::from_millis(200));
+            joined.poll().iter().any(|e| matches!(e, LobbyEvent::RematchOffered))
+        };
+
+        // Bob says no: the offer is off
+        assert!(matches!(lobby.request_rematch(), Some(LobbyEvent::RematchOffered)));
+        assert!(offered(&mut alice));
+        assert!(offered(&mut bob));
+        bob.vote_rematch(false).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, LobbyEvent::RematchDeclined(name) if name == "Bob")));
+        assert!(!lobby.rematch_pending());
+        assert_eq!(lobby.state, LobbyState::Waiting);
+
+        // Asked again, the countdown waits for the last yes
+        assert!(matches!(lobby.request_rematch(), Some(LobbyEvent::RematchOffered)));
+        assert!(offered(&mut alice));
+        assert!(offered(&mut bob));
+        alice.vote_rematch(true).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        assert!(!events.iter().any(|e| matches!(e, LobbyEvent::Countdown { .. })));
+        assert!(lobby.rematch_pending());
+        assert_eq!(lobby.state, LobbyState::Waiting);
+
+        bob.vote_rematch(true).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::Countdown { countdown: 3, .. })));
+        assert!(!lobby.rematch_pending());
+        assert!(matches!(lobby.state, LobbyState::Countdown(3)));
+        // Fresh round: no arbitrator (or scores) until it starts
+        assert!(lobby.scores().is_empty());
+
+        thread::sleep(Duration::from_millis(200));
+        assert!(alice.poll().iter().any(|e| matches!(e, LobbyEvent::Countdown { .. })));
+    }
+
+    #[test]
+    fn e2e_silent_peer_dropped_after_timeout() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let addr = format!("127.0.0.1:{}", lobby.port());
+
+        // Joins, then goes quiet (never polls, so never answers a ping)
+        let mut silent = Client::connect(&addr, "Unplugged".into()).unwrap();
+        silent.join().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        let mut awake = Client::connect(&addr, "Awake".into()).unwrap();
+        awake.join().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert_eq!(lobby.player_count(), 3);
+
+        // Time for another ping; only one of them answers
+        lobby.last_ping = None;
+        lobby.poll();
+        thread::sleep(Duration::from_millis(100));
+        assert!(awake.poll().contains(&Message::Ping));
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        // Nobody is dropped before the timeout...
+        let quiet_since = *lobby.last_seen.values().min().unwrap();
+        let events = lobby.check_liveness(quiet_since + PEER_TIMEOUT / 2);
+        assert!(events.is_empty());
+        assert_eq!(lobby.player_count(), 3);
+
+        // ...and only the silent one after it
+        let events = lobby.check_liveness(quiet_since + PEER_TIMEOUT);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], LobbyEvent::PlayerLeft(name) if name == "Unplugged"));
+        let names: Vec<&str> = lobby.players().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["Host", "Awake"]);
+
+        // The closed connection isn't reported a second time
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        assert!(!events.iter().any(|e| matches!(e, LobbyEvent::PlayerLeft(_))));
+        assert_eq!(lobby.player_count(), 2);
+    }
+
+    #[test]
+    fn e2e_kick_unknown_name_is_a_no_op() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let port = lobby.port();
+        let mut client = Client::connect(&format!("127.0.0.1:{}", port), "Alice".into()).unwrap();
+        client.join().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        lobby.add_bot();
+
+        assert!(lobby.kick_player("Nobody").is_none());
+        assert!(lobby.kick_player("Host").is_none(), "The host can't kick themselves");
+        assert!(lobby.kick_player(&bot::bot_name(1)).is_none());
+        assert_eq!(lobby.player_count(), 3);
+
+        thread::sleep(Duration::from_millis(200));
+        assert!(!client.poll().iter().any(|m| matches!(m, Message::Kicked { .. })));
+    }
+
+    #[test]
+    fn e2e_multiplayer_countdown_broadcast() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let port = lobby.port();
+
+        let mut client = Client::connect(
+            &format!("127.0.0.1:{}", port),
+            "Client".into(),
+        ).unwrap();
+        client.join().unwrap();
+
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        // Start countdown
+        let countdown_val = lobby.start_countdown(test_letters_vec(), 60);
+        assert_eq!(countdown_val, 3, "Countdown should start at 3");
+
+        // Client should receive countdown message
+        thread::sleep(Duration::from_millis(200));
+        let messages = client.poll();
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::Countdown { countdown_secs: 3, .. }
+        )), "Client should receive countdown broadcast");
+
+        // Tick countdown
+        lobby.tick_countdown();
+        thread::sleep(Duration::from_millis(200));
+        let messages = client.poll();
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::Countdown { countdown_secs: 2, .. }
+        )), "Client should receive updated countdown");
+
+        // Tick again
+        lobby.tick_countdown();
+        thread::sleep(Duration::from_millis(200));
+        let messages = client.poll();
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::Countdown { countdown_secs: 1, .. }
+        )));
+
+        // Final tick starts the round
+        lobby.tick_countdown();
+        thread::sleep(Duration::from_millis(200));
+        let messages = client.poll();
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::RoundStart { .. }
+        )), "Final countdown tick should trigger RoundStart broadcast");
+    }
+
+    #[test]
+    fn e2e_multiplayer_countdown_cancelled() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = PeerInfo {
+            actor_id: "host".into(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        // Nothing to cancel yet
+        assert!(!lobby.cancel_countdown());
+
+        lobby.start_countdown(test_letters_vec(), 60);
+        lobby.tick_countdown();
+        thread::sleep(Duration::from_millis(200));
+        joined.poll();
+        assert_eq!(joined.state, LobbyState::Countdown(2));
+
+        assert!(lobby.cancel_countdown());
+        assert_eq!(lobby.state, LobbyState::Waiting);
+        assert_eq!(lobby.countdown_remaining(), 0);
+        // A cancelled countdown doesn't start the round
+        assert!(lobby.tick_countdown().is_none());
+
+        thread::sleep(Duration::from_millis(200));
+        let events = joined.poll();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::CountdownCancelled)));
+        assert_eq!(joined.state, LobbyState::Waiting);
+        assert_eq!(joined.countdown_remaining(), 0);
+    }
+
+    #[test]
+    fn e2e_round_claims_complete_and_ordered() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = PeerInfo {
+            actor_id: "host".into(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        lobby.start_round(test_letters_vec(), 60);
+        thread::sleep(Duration::from_millis(100));
+        joined.poll();
+
+        lobby.host_claim("cat");
+        joined.send_claim("dog").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        lobby.host_claim("dog"); // Already claimed, not recorded
+        lobby.host_claim("tan");
+
+        let claims = lobby.round_claims();
+        let summary: Vec<(&str, &str, u64)> = claims
+            .iter()
+            .map(|c| (c.player_name.as_str(), c.word.as_str(), c.claim_sequence))
+            .collect();
+        assert_eq!(summary, vec![("Host", "CAT", 1), ("Client", "DOG", 2), ("Host", "TAN", 3)]);
+        assert!(claims.iter().all(|c| c.points > 0));
+
+        // The client sees the same list via WordClaimed messages
+        thread::sleep(Duration::from_millis(200));
+        joined.poll();
+        assert_eq!(joined.round_claims(), claims);
+    }
+
+    #[test]
+    fn e2e_host_house_words_sent_to_joining_player() {
+        assert!(dictionary::add_house_word("blamquux"));
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = PeerInfo {
+            actor_id: "host".into(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        thread::sleep(Duration::from_millis(200));
+        let events = joined.poll();
+
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::HouseWords { .. })));
+        assert!(joined.house_words().contains(&"blamquux".to_string()));
+
+        // ...and the host accepts it, since the host's dictionary decides
+        lobby.start_round(vec!['B', 'L', 'A', 'M', 'Q', 'U', 'U', 'X'], 60);
+        let events = lobby.host_claim("blamquux").unwrap();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::ClaimAccepted { .. })));
+        dictionary::remove_house_word("blamquux");
+    }
+
+    #[test]
+    fn e2e_handicaps_sent_to_players_at_round_start() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = peer_at(vec!["127.0.0.1".parse().unwrap()], lobby.port());
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        lobby.set_handicap("Client", 1.5);
+        lobby.start_round(test_letters_vec(), 60);
+        thread::sleep(Duration::from_millis(200));
+        let events = joined.poll();
+
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::Handicaps { handicaps } if handicaps.get("Client") == Some(&1.5)
+        )));
+        assert_eq!(joined.handicaps().get("Client"), Some(&1.5));
+        assert_eq!(joined.handicaps().get("Host"), None);
+    }
+
+    #[test]
+    fn e2e_double_tapped_claim_sent_once() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = PeerInfo {
+            actor_id: "host".into(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
+
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        lobby.start_round(test_letters_vec(), 60);
+
+        assert!(joined.send_claim("cat").unwrap());
+        assert!(!joined.send_claim("CAT").unwrap(), "Second tap should not be sent");
+
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        let accepted = events.iter().filter(|e| matches!(e, LobbyEvent::ClaimAccepted { .. })).count();
+        let rejected = events.iter().filter(|e| matches!(e, LobbyEvent::ClaimRejected { .. })).count();
+        assert_eq!((accepted, rejected), (1, 0), "Host should see exactly one attempt");
+
+        // Once the verdict arrives the word can be sent again
+        thread::sleep(Duration::from_millis(200));
+        joined.poll();
+        assert!(joined.send_claim("cat").unwrap());
     }
 
     #[test]
-    fn e2e_multiplayer_countdown_broadcast() {
+    fn e2e_oversized_score_update_clamped_by_client() {
         let mut lobby = HostedLobby::new("Host".into()).unwrap();
-        let port = lobby.port();
-
-        let mut client = Client::connect(
-            &format!("127.0.0.1:{}", port),
-            "Client".into(),
-        ).unwrap();
-        client.join().unwrap();
-
+        let peer = PeerInfo {
+            actor_id: lobby.actor_id.clone(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
         thread::sleep(Duration::from_millis(200));
         lobby.poll();
 
-        // Start countdown
-        let countdown_val = lobby.start_countdown(test_letters_vec(), 60);
-        assert_eq!(countdown_val, 3, "Countdown should start at 3");
-
-        // Client should receive countdown message
+        // A bad host sends far more scores than a lobby can hold players
+        let scores: Vec<(String, u32)> = (0..1000).map(|i| (format!("P{}", i), i)).collect();
+        lobby.broadcast(&Message::ScoreUpdate { scores });
         thread::sleep(Duration::from_millis(200));
-        let messages = client.poll();
-        assert!(messages.iter().any(|m| matches!(
-            m,
-            Message::Countdown { countdown_secs: 3, .. }
-        )), "Client should receive countdown broadcast");
 
-        // Tick countdown
-        lobby.tick_countdown();
-        thread::sleep(Duration::from_millis(200));
-        let messages = client.poll();
-        assert!(messages.iter().any(|m| matches!(
-            m,
-            Message::Countdown { countdown_secs: 2, .. }
-        )), "Client should receive updated countdown");
+        let events = joined.poll();
+        let update = events.iter().find_map(|e| match e {
+            LobbyEvent::ScoreUpdate { scores } => Some(scores),
+            _ => None,
+        });
+        let update = update.expect("score update should still come through");
+        assert_eq!(update.len(), MAX_PLAYERS);
+        assert_eq!(update[0], ("P0".to_string(), 0));
+    }
 
-        // Tick again
-        lobby.tick_countdown();
+    #[test]
+    fn e2e_oversized_round_claims_clamped_by_client() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = PeerInfo {
+            actor_id: lobby.actor_id.clone(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let mut joined = JoinedLobby::join(&peer, "Client".into()).unwrap();
         thread::sleep(Duration::from_millis(200));
-        let messages = client.poll();
-        assert!(messages.iter().any(|m| matches!(
-            m,
-            Message::Countdown { countdown_secs: 1, .. }
-        )));
+        lobby.poll();
 
-        // Final tick starts the round
-        lobby.tick_countdown();
+        // A bad host sends far more claims than any rack can make words
+        let claims: Vec<RoundClaim> = (0..MAX_ROUND_CLAIMS + 500)
+            .map(|i| RoundClaim {
+                player_name: "Host".into(),
+                word: format!("W{}", i),
+                points: 1,
+                claim_sequence: i as u64,
+            })
+            .collect();
+        lobby.broadcast(&Message::RoundClaims { claims });
         thread::sleep(Duration::from_millis(200));
-        let messages = client.poll();
-        assert!(messages.iter().any(|m| matches!(
-            m,
-            Message::RoundStart { .. }
-        )), "Final countdown tick should trigger RoundStart broadcast");
+
+        let events = joined.poll();
+        let claims = events.iter().find_map(|e| match e {
+            LobbyEvent::RoundClaims { claims } => Some(claims),
+            _ => None,
+        });
+        let claims = claims.expect("round claims should still come through");
+        assert_eq!(claims.len(), MAX_ROUND_CLAIMS);
+        assert_eq!(claims[0].word, "W0");
+        assert_eq!(joined.round_claims.len(), MAX_ROUND_CLAIMS);
     }
 
     #[test]
@@ -1806,7 +5399,7 @@ mod e2e_tests {
         // The last score update should reflect all claims
         if let Some(Message::ScoreUpdate { scores }) = score_updates.last() {
             let total: u32 = scores.iter().map(|(_, s)| s).sum();
-            assert_eq!(total, 9, "Total scores should be 3+3+3=9 across all players");
+            assert_eq!(total, 14, "Total scores should be 5+6+3=14 across all players");
         }
     }
 
@@ -1824,31 +5417,31 @@ mod e2e_tests {
         thread::sleep(Duration::from_millis(200));
         lobby.poll();
 
-        // Use letters that allow CATS (4 pts) and shorter words
+        // Use letters that allow CATS (6 pts) and cheaper words
         lobby.start_round(test_letters_vec(), 60);
         thread::sleep(Duration::from_millis(100));
         client.poll();
 
-        // Host claims short words: CAT(3) + DOG(3) = 6
-        lobby.host_claim("cat");
-        lobby.host_claim("dog");
+        // Host claims common letters only: TAN(3) + ANT(3) = 6
+        lobby.host_claim("tan");
+        lobby.host_claim("ant");
 
-        // Client claims a longer word: CATS(4) + TAN(3) = 7
+        // Client claims rarer letters: CATS(6) + DOG(6) = 12
         client.send_claim_attempt("cats").unwrap();
         thread::sleep(Duration::from_millis(200));
         lobby.poll();
 
-        client.send_claim_attempt("tan").unwrap();
+        client.send_claim_attempt("dog").unwrap();
         thread::sleep(Duration::from_millis(200));
         lobby.poll();
 
         // End round and check winner
-        lobby.end_round();
+        lobby.end_round(RoundEndReason::HostEnded);
 
         let scores = lobby.scores();
         // Scores are sorted highest first
-        assert_eq!(scores[0].0, "Client", "Client with 7 points should be ranked first");
-        assert_eq!(scores[0].1, 7);
+        assert_eq!(scores[0].0, "Client", "Client with 12 points should be ranked first");
+        assert_eq!(scores[0].1, 12);
         assert_eq!(scores[1].0, "Host", "Host with 6 points should be ranked second");
         assert_eq!(scores[1].1, 6);
     }
@@ -1879,7 +5472,7 @@ mod e2e_tests {
         assert_eq!(lobby.player_count(), MAX_PLAYERS,
             "Lobby should have exactly MAX_PLAYERS");
 
-        // Try to connect one more - should connect but join should be ignored at capacity
+        // One more joiner: no seat left, so they get to watch instead
         let mut extra = Client::connect(
             &format!("127.0.0.1:{}", port),
             "ExtraPlayer".into(),
@@ -1887,17 +5480,464 @@ mod e2e_tests {
         extra.join().unwrap();
 
         thread::sleep(Duration::from_millis(200));
-        lobby.poll();
+        let events = lobby.poll();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::SpectatorJoined(name) if name == "ExtraPlayer"
+        )));
+        assert_eq!(lobby.spectators(), ["ExtraPlayer".to_string()]);
 
         thread::sleep(Duration::from_millis(200));
         let messages = extra.poll();
         assert!(messages.iter().any(|m| matches!(
             m,
-            Message::JoinRejected { reason: JoinRejectReason::LobbyFull }
-        )), "Extra client should receive a lobby-full rejection");
+            Message::JoinSpectator { name } if name == "ExtraPlayer"
+        )), "Extra client should be told they're spectating");
 
         // Should still be at max
-        assert!(lobby.player_count() <= MAX_PLAYERS,
+        assert_eq!(lobby.player_count(), MAX_PLAYERS,
             "Lobby should not exceed MAX_PLAYERS");
     }
+
+    #[test]
+    fn e2e_spectator_sees_the_round_but_claims_are_ignored() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let port = lobby.port();
+        let mut watcher = Client::connect(&format!("127.0.0.1:{}", port), "Watcher".into()).unwrap();
+        watcher.spectate().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert_eq!(lobby.spectators(), ["Watcher".to_string()]);
+        assert_eq!(lobby.player_count(), 1, "A spectator doesn't take a seat");
+
+        lobby.start_round(test_letters_vec(), 60);
+        lobby.host_claim("dog");
+        watcher.send_claim_attempt("cat").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        assert!(!events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimAccepted { .. } | LobbyEvent::ClaimRejected { .. }
+        )), "The spectator's claim should be ignored");
+        let claims: Vec<String> = lobby.round_claims().into_iter().map(|c| c.word).collect();
+        assert_eq!(claims, vec!["DOG".to_string()]);
+
+        // They still hear everything the players do
+        thread::sleep(Duration::from_millis(200));
+        let messages = watcher.poll();
+        assert!(messages.iter().any(|m| matches!(m, Message::RoundStart { .. })));
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::ClaimAccepted { word, .. } if word == "DOG"
+        )));
+        assert!(messages.iter().any(|m| matches!(m, Message::ScoreUpdate { .. })));
+
+        drop(watcher);
+        thread::sleep(Duration::from_millis(300));
+        let events = lobby.poll();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::SpectatorLeft(n) if n == "Watcher")));
+        assert!(!events.iter().any(|e| matches!(e, LobbyEvent::PlayerLeft(_))));
+        assert!(lobby.spectators().is_empty());
+    }
+
+    #[test]
+    fn e2e_previewed_rack_is_first_round_rack() {
+        let seed = 2024;
+        let preview = RackPreset::Standard.rack_for_seed(seed);
+
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.set_first_rack_seed(seed);
+        let letters = lobby.generate_letters();
+        assert_eq!(letters, preview.letters());
+
+        lobby.start_countdown(letters, 60);
+        assert_eq!(lobby.current_letters(), preview.letters());
+        assert!(lobby.first_rack_seed.is_none(), "later rounds deal fresh racks");
+    }
+
+    #[test]
+    fn e2e_previewed_rack_uses_lobby_rack_settings() {
+        let seed = 2024;
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.set_blanks(true);
+        lobby.set_first_rack_seed(seed);
+        let config = RackPreset::Standard.config().with_blanks(MAX_BLANKS);
+        assert_eq!(lobby.rack_config(), config);
+        assert_eq!(lobby.generate_letters(), config.rack_for_seed(seed).letters());
+    }
+
+    #[test]
+    fn e2e_host_advertises_its_dictionary() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        assert_eq!(lobby.advertised.dictionary, dictionary::fingerprint());
+
+        let spanish = Arc::new(Dictionary::parse("gato\nperro\n", Language::Spanish));
+        lobby.dictionary = Arc::clone(&spanish);
+        lobby.language = Language::Spanish;
+        lobby.poll();
+        assert_eq!(lobby.advertised.dictionary, spanish.fingerprint());
+    }
+
+    #[test]
+    fn e2e_rack_preset_flows_to_countdown_letters() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        assert_eq!(lobby.rack_preset(), RackPreset::Standard);
+        assert_eq!(lobby.cycle_rack_preset(), RackPreset::Tiny);
+
+        let letters = lobby.generate_letters();
+        assert!((9..=12).contains(&letters.len()));
+
+        lobby.start_countdown(letters.clone(), 60);
+        assert_eq!(lobby.current_letters(), letters.as_slice());
+    }
+
+    #[test]
+    fn e2e_blank_tile_stands_in_for_a_letter_and_scores_nothing() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        for _ in 0..20 {
+            assert!(!lobby.generate_letters().contains(&BLANK));
+        }
+        lobby.set_blanks(true);
+        for _ in 0..20 {
+            let blanks = lobby.generate_letters().iter().filter(|c| **c == BLANK).count();
+            assert!(blanks <= MAX_BLANKS);
+        }
+
+        let port = lobby.port();
+        let mut client = Client::connect(&format!("127.0.0.1:{}", port), "Client".into()).unwrap();
+        client.join().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        lobby.start_round(vec!['C', 'A', 'T', BLANK], 60);
+        client.send_claim_attempt("cats").unwrap();
+        client.send_claim_attempt("cots").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        // CATS uses every tile, so it's a pangram; the blank S adds nothing
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimAccepted { word, points, .. }
+                if word == "CATS" && *points == 5 + PANGRAM_BONUS
+        )), "The blank S should score nothing");
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected {
+                reason: ClaimRejectReason::InvalidLetters { missing }, ..
+            } if *missing == vec!['S']
+        )), "One blank can't cover both O and S");
+
+        // Real tiles are used before the blank
+        let events = lobby.host_claim("cat").unwrap();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::ClaimAccepted { points: 5, .. })));
+    }
+
+    #[test]
+    fn e2e_language_reaches_clients_and_checks_claims() {
+        let dir = std::env::temp_dir().join(format!("blam-lobby-lang-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        assert!(lobby.set_language(Language::German, &dir).is_err());
+        assert_eq!(lobby.language(), Language::English);
+
+        std::fs::write(dir.join("words-es.txt"), "año\ngato\n").unwrap();
+        lobby.set_language(Language::Spanish, &dir).unwrap();
+        let port = lobby.port();
+        let mut client = Client::connect(&format!("127.0.0.1:{}", port), "Client".into()).unwrap();
+        client.join().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        lobby.start_round(vec!['A', 'Ñ', 'O', 'G', 'T', 'C'], 60);
+        thread::sleep(Duration::from_millis(200));
+        assert!(client.poll().iter().any(|m| matches!(
+            m,
+            Message::RoundStart { language: Language::Spanish, .. }
+        )), "Client should hear the language with the rack");
+
+        let events = lobby.host_claim("año").unwrap();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::ClaimAccepted { .. })));
+        let events = lobby.host_claim("cat").unwrap();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ClaimRejected { reason: ClaimRejectReason::NotInDictionary, .. }
+        )));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn e2e_disconnect_within_grace_keeps_score_and_reconnect_restores_play() {
+        use std::io::Write;
+
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let mut stream = std::net::TcpStream::connect(("127.0.0.1", lobby.port())).unwrap();
+        stream
+            .write_all(&Message::Join { player_name: "Flaky".into() }.to_bytes())
+            .unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        lobby.start_round(test_letters_vec(), 60);
+        stream
+            .write_all(&Message::ClaimAttempt { word: "cat".into() }.to_bytes())
+            .unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert_eq!(lobby.arbitrator.as_ref().unwrap().player_score("Flaky"), 5);
+
+        // The connection drops mid-round
+        drop(stream);
+        thread::sleep(Duration::from_millis(300));
+        let events = lobby.poll();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::PlayerAway(name) if name == "Flaky")));
+        assert!(!events.iter().any(|e| matches!(e, LobbyEvent::PlayerLeft(_))));
+        assert!(lobby.is_away("Flaky"));
+        assert_eq!(lobby.player_count(), 2);
+        assert!(lobby.scores().contains(&("Flaky".to_string(), 5)));
+
+        // They reconnect under the same name and rejoin the running round
+        let mut client = Client::connect(&format!("127.0.0.1:{}", lobby.port()), "Flaky".into()).unwrap();
+        client.join().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::PlayerReturned(name) if name == "Flaky")));
+        assert!(!lobby.is_away("Flaky"));
+        assert_eq!(lobby.player_count(), 2);
+
+        thread::sleep(Duration::from_millis(200));
+        let messages = client.poll();
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::RoundStart { duration_secs, .. } if *duration_secs > 0 && *duration_secs <= 60
+        )));
+
+        // The claims from before the drop come back with the round
+        assert!(messages.iter().any(|m| matches!(
+            m,
+            Message::RoundClaims { claims } if claims.iter().any(|c| c.word == "CAT")
+        )));
+
+        // Full participation: new claims count on top of the old points
+        client.send_claim_attempt("dogs").unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert_eq!(lobby.arbitrator.as_ref().unwrap().player_score("Flaky"), 12);
+    }
+
+    #[test]
+    fn e2e_rejoining_client_learns_round_claims() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = PeerInfo {
+            actor_id: "host".into(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let joined = JoinedLobby::join(&peer, "Flaky".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+
+        lobby.start_round(test_letters_vec(), 60);
+        lobby.host_claim("cat");
+        lobby.host_claim("dogs");
+
+        drop(joined);
+        thread::sleep(Duration::from_millis(300));
+        lobby.poll();
+        assert!(lobby.is_away("Flaky"));
+
+        let mut joined = JoinedLobby::join(&peer, "Flaky".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        thread::sleep(Duration::from_millis(200));
+        let events = joined.poll();
+
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::RoundClaims { claims } if claims.len() == 2)));
+        let words: Vec<String> = joined.round_claims().into_iter().map(|c| c.word).collect();
+        assert_eq!(words, vec!["CAT".to_string(), "DOGS".to_string()]);
+    }
+
+    #[test]
+    fn e2e_dropped_client_reconnects_and_resyncs_scores() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = PeerInfo {
+            actor_id: "host".into(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let mut joined = JoinedLobby::join(&peer, "Flaky".into()).unwrap();
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        lobby.start_round(test_letters_vec(), 60);
+        thread::sleep(Duration::from_millis(200));
+        joined.poll();
+
+        // The WiFi blips: the host sees the old connection go, and ours is
+        // left dead
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let dead = Client::connect(&listener.local_addr().unwrap().to_string(), "Flaky".into())
+            .unwrap();
+        drop(listener.accept().unwrap());
+        drop(std::mem::replace(&mut joined.client, dead));
+        thread::sleep(Duration::from_millis(300));
+        lobby.poll();
+        assert!(lobby.is_away("Flaky"));
+
+        // A claim lands while they're gone
+        lobby.host_claim("cat");
+
+        // The dead connection is noticed, then replaced
+        let mut events = joined.poll();
+        for _ in 0..2 {
+            if events.iter().any(|e| matches!(e, LobbyEvent::Reconnected)) {
+                break;
+            }
+            events = joined.poll();
+        }
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::Reconnected)), "{:?}", events);
+        assert_eq!(joined.reconnect_attempts(), 0);
+
+        thread::sleep(Duration::from_millis(200));
+        let events = lobby.poll();
+        assert!(events.iter().any(|e| matches!(e, LobbyEvent::PlayerReturned(n) if n == "Flaky")));
+        thread::sleep(Duration::from_millis(200));
+        let events = joined.poll();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            LobbyEvent::ScoreUpdate { scores } if scores.contains(&("Host".to_string(), 5))
+        )), "The claim made while we were gone should be in the scores");
+        assert!(joined.round_claims().iter().any(|c| c.word == "CAT"));
+        assert!(!events.iter().any(|e| matches!(e, LobbyEvent::Disconnected)));
+    }
+
+    #[test]
+    fn e2e_reconnect_gives_up_after_max_attempts() {
+        let lobby = HostedLobby::new("Host".into()).unwrap();
+        let peer = PeerInfo {
+            actor_id: "host".into(),
+            handle: "Host".into(),
+            lobby_name: Some(lobby.lobby_name.clone()),
+            version: "1".into(),
+            hostname: "localhost".into(),
+            addresses: vec!["127.0.0.1".parse().unwrap()],
+            port: lobby.port(),
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        let mut joined = JoinedLobby::join(&peer, "Flaky".into()).unwrap();
+
+        // The connection drops, and nothing answers at the host's address
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let dead = Client::connect(&addr.to_string(), "Flaky".into()).unwrap();
+        drop(listener.accept().unwrap());
+        drop(listener);
+        joined.client = dead;
+        joined.host_addr = addr;
+        thread::sleep(Duration::from_millis(300));
+        joined.poll();
+
+        let now = Instant::now();
+        assert!(matches!(joined.reconnect(now), Some(LobbyEvent::Reconnecting { attempt: 1 })));
+        // Backing off: nothing happens until the wait is up
+        assert!(joined.reconnect(now).is_none());
+        let mut at = now;
+        for attempt in 2..MAX_RECONNECT_ATTEMPTS {
+            at += RECONNECT_BACKOFF * 2u32.pow(attempt - 2);
+            assert!(matches!(
+                joined.reconnect(at),
+                Some(LobbyEvent::Reconnecting { attempt: a }) if a == attempt
+            ));
+        }
+        at += RECONNECT_BACKOFF * 2u32.pow(MAX_RECONNECT_ATTEMPTS - 2);
+        assert!(matches!(joined.reconnect(at), Some(LobbyEvent::Disconnected)));
+        assert!(matches!(joined.poll().as_slice(), [LobbyEvent::Disconnected]));
+    }
+
+    #[test]
+    fn test_away_player_removed_once_grace_runs_out() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        lobby.players.push(Player {
+            name: "Gone".into(),
+            ready: true,
+            is_local: false,
+            is_host: false,
+            status: None,
+            is_bot: false,
+            team: None,
+        });
+        let now = Instant::now();
+        lobby.away.insert("Gone".into(), now + Duration::from_secs(30));
+
+        assert!(lobby.expire_away_players(now).is_empty());
+        assert!(!lobby.can_start(), "away players don't count toward starting");
+
+        let events = lobby.expire_away_players(now + Duration::from_secs(30));
+        assert!(matches!(events.as_slice(), [LobbyEvent::PlayerLeft(name)] if name == "Gone"));
+        assert_eq!(lobby.player_count(), 1);
+        assert!(!lobby.is_away("Gone"));
+    }
+
+    #[test]
+    fn test_disconnect_grace_cycles_through_choices() {
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        assert_eq!(lobby.disconnect_grace(), DEFAULT_DISCONNECT_GRACE);
+        assert_eq!(lobby.cycle_disconnect_grace(), Duration::from_secs(60));
+        assert_eq!(lobby.cycle_disconnect_grace(), Duration::ZERO);
+        assert_eq!(lobby.cycle_disconnect_grace(), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn e2e_host_drops_player_after_repeated_send_failures() {
+        use std::io::Write;
+
+        let mut lobby = HostedLobby::new("Host".into()).unwrap();
+        // No grace period, so the dropped player is removed straight away
+        lobby.set_disconnect_grace(Duration::ZERO);
+        let mut stream = std::net::TcpStream::connect(("127.0.0.1", lobby.port())).unwrap();
+        stream
+            .write_all(&Message::Join { player_name: "Ghost".into() }.to_bytes())
+            .unwrap();
+
+        thread::sleep(Duration::from_millis(200));
+        lobby.poll();
+        assert_eq!(lobby.player_count(), 2);
+
+        // The client vanishes; the host finds out when its sends fail
+        drop(stream);
+        thread::sleep(Duration::from_millis(200));
+
+        for _ in 0..MAX_SEND_FAILURES {
+            lobby.announce("Anyone there?");
+        }
+        assert_eq!(lobby.server.peer_count(), 0, "Peer should be dropped after repeated failures");
+
+        let events = lobby.poll();
+        let left: Vec<_> = events
+            .iter()
+            .filter(|e| matches!(e, LobbyEvent::PlayerLeft(name) if name == "Ghost"))
+            .collect();
+        assert_eq!(left.len(), 1, "Dropped player should be reported as leaving once");
+        assert_eq!(lobby.player_count(), 1);
+    }
 }
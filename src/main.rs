@@ -2,36 +2,58 @@
 //!
 //! Type fast. Claim first. Keep the crown.
 
-#[allow(dead_code)]
-mod app;
-#[allow(dead_code)]
-mod game;
-#[allow(dead_code)]
-mod lobby;
-#[allow(dead_code)]
-mod network;
-#[allow(dead_code)]
-mod stats;
-#[allow(dead_code)]
-mod storage;
-#[allow(dead_code)]
-mod tui;
-
-use app::{AppCoordinator, Screen, DEFAULT_ROUND_DURATION};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use game::LetterRack;
+use blam::{app, game, lobby, storage, tui};
+use app::{AppCoordinator, Screen};
+use crossterm::event::{self, Event, KeyEventKind, MouseButton, MouseEventKind};
 use std::io;
 use std::mem;
+use storage::{Storage, StorageError};
 use std::time::{Duration, Instant};
 use tui::Tui;
 
 fn main() -> io::Result<()> {
+    // Headless runs skip the TUI (and the data directory) entirely
+    match app::headless::HeadlessOptions::from_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => return app::headless::run(&options),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("blam: {}", e);
+            std::process::exit(2);
+        }
+    }
+
+    // Refuse to share a data directory with another running instance, and
+    // unlock an encrypted database up front so every later open can read it
+    let opened = match std::env::var(storage::PASSPHRASE_VAR) {
+        Ok(passphrase) if !passphrase.is_empty() => Storage::open_encrypted(&passphrase),
+        _ => Storage::open(),
+    };
+    match opened {
+        Err(e @ StorageError::PassphraseRequired) => {
+            eprintln!("blam: {} (set {})", e, storage::PASSPHRASE_VAR);
+            std::process::exit(1);
+        }
+        Err(e @ (StorageError::AlreadyOpen(_) | StorageError::WrongPassphrase)) => {
+            eprintln!("blam: {}", e);
+            std::process::exit(1);
+        }
+        _ => {}
+    }
+
     // Initialize terminal
     let mut terminal = Tui::new()?;
     terminal.enter()?;
 
+    // Parse the word list once, up front, rather than on the first claim
+    game::dictionary::shared();
+    if let Ok(dir) = Storage::data_dir() {
+        game::dictionary::load_definitions(&dir);
+    }
+
     // Initialize app coordinator
+    AppCoordinator::load_house_words();
     let mut coordinator = AppCoordinator::new();
+    coordinator.offer_resume();
 
     // Main event loop
     let tick_rate = Duration::from_millis(100); // Faster for responsive UI
@@ -49,11 +71,19 @@ fn main() -> io::Result<()> {
 
         // Poll for events with timeout
         if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
+            match event::read()? {
                 // Only handle key press events (not release)
-                if key.kind == KeyEventKind::Press {
-                    handle_key(&mut coordinator, key.code);
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    coordinator.handle_key(key.code.into());
+                }
+                Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                    tui::handle_click(&mut coordinator, terminal.area()?, mouse.column, mouse.row);
                 }
+                // Redraw at the new size now rather than on the next tick
+                Event::Resize(_, _) => {
+                    terminal.draw(|frame| tui::render(frame, &coordinator))?;
+                }
+                _ => {}
             }
         }
 
@@ -66,12 +96,23 @@ fn main() -> io::Result<()> {
         // Handle second-based timer for game play and countdown
         if last_second.elapsed() >= Duration::from_secs(1) {
             let mut host_round_start = None;
+            let mut solo_ticked = false;
+            let mut rematch_ticked = false;
 
             match &mut coordinator.screen {
-                Screen::Playing { app, .. } => {
+                Screen::Playing {
+                    app,
+                    hosted_lobby,
+                    joined_lobby,
+                    ..
+                } => {
+                    let was_running = !app.is_round_over();
                     app.tick();
+                    solo_ticked = was_running && hosted_lobby.is_none() && joined_lobby.is_none();
+                    rematch_ticked = hosted_lobby.is_some() && app.countdown.is_some();
                 }
-                Screen::HostLobby { lobby, countdown } => {
+                Screen::Hotseat { game } => game.tick(),
+                Screen::HostLobby { lobby, countdown, .. } => {
                     if countdown.is_some() {
                         if let Some(event) = lobby.tick_countdown() {
                             match event {
@@ -80,8 +121,20 @@ fn main() -> io::Result<()> {
                                 } => {
                                     *countdown = Some(count);
                                 }
-                                lobby::LobbyEvent::RoundStart { letters, duration } => {
-                                    host_round_start = Some((letters, duration));
+                                lobby::LobbyEvent::RoundStart {
+                                    letters,
+                                    duration,
+                                    min_letters_used,
+                                    reject_penalty,
+                                    language,
+                                } => {
+                                    host_round_start = Some((
+                                        letters,
+                                        duration,
+                                        min_letters_used,
+                                        reject_penalty,
+                                        language,
+                                    ));
                                 }
                                 _ => {}
                             }
@@ -91,8 +144,17 @@ fn main() -> io::Result<()> {
                 _ => {}
             }
 
+            if solo_ticked {
+                coordinator.checkpoint_solo_round();
+            }
+            if rematch_ticked {
+                coordinator.tick_rematch_countdown();
+            }
+
             // Handle host transition outside the match to allow taking ownership
-            if let Some((letters, duration)) = host_round_start {
+            if let Some((letters, duration, min_letters_used, reject_penalty, language)) =
+                host_round_start
+            {
                 let old_screen = mem::replace(
                     &mut coordinator.screen,
                     Screen::Error {
@@ -105,9 +167,18 @@ fn main() -> io::Result<()> {
                     let host_name = lobby.host_name.clone();
 
                     let mut app = app::App::new();
+                    app.flavor = coordinator.flavor.clone();
                     app.set_player_name(host_name);
                     app.set_scoreboard(player_names);
+                    app.set_handicaps(lobby.handicaps().clone());
                     app.start_round(letters, duration);
+                    app.min_letters_used = min_letters_used;
+                    app.reject_penalty = reject_penalty;
+                    app.set_language(language);
+                    if let Some(text) = lobby.announcement() {
+                        app.on_announcement(text.to_string());
+                    }
+                    app.set_house_words(&game::dictionary::house_words());
 
                     coordinator.screen = Screen::Playing {
                         app,
@@ -130,138 +201,3 @@ fn main() -> io::Result<()> {
     // Terminal cleanup happens automatically via Tui::drop
     Ok(())
 }
-
-fn handle_key(coordinator: &mut AppCoordinator, code: KeyCode) {
-    match &mut coordinator.screen {
-        Screen::Menu { editing_handle, .. } => {
-            if *editing_handle {
-                // Handle editing mode
-                match code {
-                    KeyCode::Esc | KeyCode::Enter | KeyCode::Tab => coordinator.menu_tab(),
-                    KeyCode::Backspace => coordinator.menu_backspace(),
-                    KeyCode::Char(c) if c.is_ascii_alphanumeric() || c == '_' => {
-                        coordinator.menu_char(c)
-                    }
-                    _ => {}
-                }
-            } else {
-                // Handle navigation mode
-                match code {
-                    KeyCode::Esc => coordinator.quit(),
-                    KeyCode::Up => coordinator.menu_up(),
-                    KeyCode::Down => coordinator.menu_down(),
-                    KeyCode::Enter => coordinator.menu_select(),
-                    KeyCode::Tab => coordinator.menu_tab(),
-                    _ => {}
-                }
-            }
-        }
-        Screen::Browser { .. } => match code {
-            KeyCode::Esc => coordinator.go_to_menu(),
-            KeyCode::Up => coordinator.browser_up(),
-            KeyCode::Down => coordinator.browser_down(),
-            KeyCode::Enter => coordinator.browser_select(),
-            _ => {}
-        },
-        Screen::HostLobby { lobby, countdown } => match code {
-            KeyCode::Esc => {
-                coordinator.quit_hosting();
-            }
-            KeyCode::Enter => {
-                // Only start countdown if we're not already counting down
-                if lobby.can_start() && countdown.is_none() {
-                    // Generate letters and start countdown
-                    let letters = LetterRack::generate().letters().to_vec();
-                    let count = lobby.start_countdown(letters, DEFAULT_ROUND_DURATION);
-                    *countdown = Some(count);
-                }
-            }
-            _ => {}
-        },
-        Screen::JoinedLobby { .. } => match code {
-            KeyCode::Esc => {
-                // Leave the lobby
-                coordinator.go_to_menu();
-            }
-            _ => {}
-        },
-        Screen::Playing {
-            app,
-            hosted_lobby,
-            joined_lobby,
-            ..
-        } => match code {
-            KeyCode::Esc => {
-                if app.is_round_over() {
-                    coordinator.go_to_menu();
-                } else if hosted_lobby.is_some() {
-                    coordinator.quit_hosting();
-                }
-            }
-            KeyCode::Enter => {
-                if let Some(word) = app.get_pending_claim() {
-                    if let Some(lobby) = hosted_lobby {
-                        // Host: arbitrate locally and broadcast
-                        if let Some(events) = lobby.host_claim(&word) {
-                            for event in events {
-                                match event {
-                                    lobby::LobbyEvent::ClaimAccepted {
-                                        word,
-                                        player_name,
-                                        points,
-                                    } => {
-                                        app.on_claim_accepted(word, player_name, points);
-                                    }
-                                    lobby::LobbyEvent::ClaimRejected { word, reason } => {
-                                        app.on_claim_rejected(
-                                            word,
-                                            app::AppCoordinator::map_reject_reason_pub(reason),
-                                        );
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        }
-                        app.clear_input();
-                    } else if let Some(lobby) = joined_lobby {
-                        // Client: send claim to host
-                        let _ = lobby.send_claim(&word);
-                        app.clear_input();
-                    } else {
-                        // Solo: local validation
-                        app.on_submit();
-                    }
-                }
-            }
-            KeyCode::Backspace => {
-                app.on_backspace();
-            }
-            KeyCode::Char(c) => {
-                if c.is_ascii_alphabetic() {
-                    app.on_char(c.to_ascii_uppercase());
-                }
-            }
-            _ => {}
-        },
-        Screen::Rankings { .. } => match code {
-            KeyCode::Esc => coordinator.go_to_menu(),
-            KeyCode::Up => coordinator.rankings_up(),
-            KeyCode::Down => coordinator.rankings_down(),
-            _ => {}
-        },
-        Screen::Settings { .. } => match code {
-            KeyCode::Esc => coordinator.go_to_menu(),
-            KeyCode::Enter => coordinator.settings_save(),
-            KeyCode::Backspace => coordinator.settings_backspace(),
-            KeyCode::Char(c) if c.is_ascii_alphanumeric() || c == '_' => {
-                coordinator.settings_char(c)
-            }
-            _ => {}
-        },
-        Screen::Error { .. } => match code {
-            KeyCode::Esc => coordinator.go_to_menu(),
-            KeyCode::Enter => coordinator.go_to_menu(),
-            _ => {}
-        },
-    }
-}
@@ -59,6 +59,18 @@ impl Client {
         Ok(())
     }
 
+    /// Ask the host to let us watch instead of play
+    pub fn spectate(&mut self) -> io::Result<()> {
+        if self.joined {
+            return Ok(());
+        }
+        self.peer.send(Message::JoinSpectator {
+            name: self.player_name.clone(),
+        })?;
+        self.joined = true;
+        Ok(())
+    }
+
     /// Send a claim message to the host (legacy, for compatibility)
     pub fn claim(&self, word: &str, points: u32) -> io::Result<()> {
         self.peer.send(Message::Claim {
@@ -75,6 +87,35 @@ impl Client {
         })
     }
 
+    /// Set our lobby status (empty clears it)
+    pub fn send_status(&self, status: &str) -> io::Result<()> {
+        self.peer.send(Message::SetStatus {
+            status: status.to_string(),
+        })
+    }
+
+    /// Share what we're typing with the host (empty clears it)
+    pub fn send_input_preview(&self, text: &str) -> io::Result<()> {
+        self.peer.send(Message::InputPreview {
+            text: text.to_string(),
+        })
+    }
+
+    /// Answer the host's rematch offer
+    pub fn send_rematch_vote(&self, accept: bool) -> io::Result<()> {
+        self.peer.send(Message::RematchVote { accept })
+    }
+
+    /// Send a history sync message (`SyncRequest` or an events batch) to the host
+    pub fn send_sync(&self, message: Message) -> io::Result<()> {
+        self.peer.send(message)
+    }
+
+    /// Ask the host to catch us up on the round in progress
+    pub fn request_resync(&self) -> io::Result<()> {
+        self.peer.send(Message::Resync)
+    }
+
     /// Send a leave message and disconnect
     pub fn leave(&self) -> io::Result<()> {
         self.peer.send(Message::Leave {
@@ -83,8 +124,16 @@ impl Client {
     }
 
     /// Poll for incoming messages from the host
+    ///
+    /// The host's pings are answered here, so it knows we're still around.
+    /// Frames from the host that couldn't be decoded are skipped.
     pub fn poll(&mut self) -> Vec<Message> {
-        self.peer.recv_all()
+        let messages = self.peer.recv_all();
+        self.peer.take_frame_errors();
+        if messages.contains(&Message::Ping) {
+            let _ = self.peer.send(Message::Pong);
+        }
+        messages
     }
 
     /// Check if still connected
@@ -186,6 +235,9 @@ mod tests {
         server.broadcast(&Message::RoundStart {
             letters: letters.clone(),
             duration_secs: 60,
+            min_letters_used: 0,
+            reject_penalty: 0,
+            language: crate::game::Language::English,
         });
 
         // Wait for message to arrive
@@ -194,7 +246,27 @@ mod tests {
 
         assert!(messages.iter().any(|m| matches!(
             m,
-            Message::RoundStart { letters: l, duration_secs: 60 } if *l == letters
+            Message::RoundStart { letters: l, duration_secs: 60, .. } if *l == letters
+        )));
+    }
+
+    #[test]
+    fn test_client_answers_ping() {
+        let mut server = Server::start_on_port(55423).unwrap();
+        let addr = format!("127.0.0.1:{}", server.port());
+        let mut client = Client::connect(&addr, "TestPlayer".to_string()).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        server.poll();
+
+        server.broadcast(&Message::Ping);
+        thread::sleep(Duration::from_millis(100));
+        assert!(client.poll().contains(&Message::Ping));
+
+        thread::sleep(Duration::from_millis(100));
+        let events = server.poll();
+        assert!(events.iter().any(|e| matches!(
+            e,
+            crate::network::server::ServerEvent::MessageReceived { message: Message::Pong, .. }
         )));
     }
 }
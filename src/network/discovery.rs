@@ -0,0 +1,391 @@
+#![allow(dead_code)]
+//! mDNS-SD discovery: advertising our lobby and finding others on the LAN
+//!
+//! The only part of networking that needs `mdns-sd`, so it's built with the
+//! `lan` feature. The TCP protocol, server and client don't depend on it.
+
+use super::{
+    retry_with_backoff, PeerAddress, PeerInfo, PROTOCOL_VERSION, REGISTER_ATTEMPTS,
+    REGISTER_BACKOFF, SERVICE_TYPE,
+};
+use crate::game::dictionary;
+use mdns_sd::{DaemonEvent, Receiver, ScopedIp, ServiceDaemon, ServiceEvent, ServiceInfo};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Longest we wait for the daemon to withdraw our old record before
+/// registering the new one
+const UNREGISTER_WAIT: Duration = Duration::from_millis(500);
+
+/// Events from the service discovery system
+#[derive(Debug)]
+pub enum DiscoveryEvent {
+    /// A new peer was discovered
+    PeerDiscovered(PeerInfo),
+    /// A peer went offline
+    PeerLost(String), // actor_id
+    /// Our query went out, so discovery is up and listening
+    SearchStarted,
+    /// Browsing stopped working (the reason, for display)
+    BrowseFailed(String),
+}
+
+/// What a host advertises about its lobby, besides its name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LobbyAdvert {
+    /// Players in the lobby, host included
+    pub players: usize,
+    /// Seats in the lobby
+    pub max_players: usize,
+    /// Whether a round is being played
+    pub in_progress: bool,
+    /// Fingerprint of the word list the lobby plays with
+    pub dictionary: u64,
+}
+
+/// Service discovery manager for finding BLAM! instances on the local network
+pub struct ServiceDiscovery {
+    daemon: ServiceDaemon,
+    our_actor_id: String,
+    registered_instance: Option<String>,
+    /// What we last advertised, kept to re-register after a network change
+    advertised: Option<ServiceInfo>,
+    /// Daemon events, watched for interface changes while advertising
+    monitor: Option<Receiver<DaemonEvent>>,
+}
+
+fn build_service_info(
+    actor_id: &str,
+    handle: &str,
+    lobby_name: Option<&str>,
+    port: u16,
+    lobby: Option<LobbyAdvert>,
+) -> Result<ServiceInfo, String> {
+    let mut properties = HashMap::new();
+    properties.insert("version".to_string(), PROTOCOL_VERSION.to_string());
+    properties.insert("handle".to_string(), handle.to_string());
+    properties.insert("actor_id".to_string(), actor_id.to_string());
+    let fingerprint = lobby.map_or_else(dictionary::fingerprint, |lobby| lobby.dictionary);
+    properties.insert("dictionary".to_string(), format!("{:016x}", fingerprint));
+    if let Some(lobby) = lobby_name {
+        properties.insert("lobby_name".to_string(), lobby.to_string());
+    }
+    if let Some(lobby) = lobby {
+        properties.insert("players".to_string(), lobby.players.to_string());
+        properties.insert("max_players".to_string(), lobby.max_players.to_string());
+        let state = if lobby.in_progress { "in_progress" } else { "waiting" };
+        properties.insert("state".to_string(), state.to_string());
+    }
+
+    // Instance name is the actor_id (must be unique on the network).
+    let instance_name = actor_id;
+
+    // Hostname for SRV/A records. We keep it deterministic per instance.
+    let hostname = format!("{}.local.", actor_id);
+
+    ServiceInfo::new(
+        SERVICE_TYPE,
+        instance_name,
+        &hostname,
+        (), // No explicit IPs; daemon will populate local interface addrs.
+        port,
+        properties,
+    )
+    .map(|service| service.enable_addr_auto())
+    .map_err(|e| format!("Failed to create service info: {}", e))
+}
+
+/// A resolved address, keeping the interface an IPv6 one was seen on
+fn peer_address(ip: &ScopedIp) -> PeerAddress {
+    match ip {
+        ScopedIp::V6(v6) if v6.scope_id().index != 0 => {
+            let interface = v6.scope_id();
+            PeerAddress::scoped(*v6.addr(), interface.index, interface.name.clone())
+        }
+        other => PeerAddress::from(other.to_ip_addr()),
+    }
+}
+
+impl ServiceDiscovery {
+    /// Create a new service discovery instance
+    ///
+    /// # Arguments
+    /// * `actor_id` - Unique identifier for this instance
+    pub fn new(actor_id: String) -> Result<Self, String> {
+        let daemon = ServiceDaemon::new().map_err(|e| format!("Failed to create mDNS daemon: {}", e))?;
+
+        Ok(Self {
+            daemon,
+            our_actor_id: actor_id,
+            registered_instance: None,
+            advertised: None,
+            monitor: None,
+        })
+    }
+
+    /// Advertise this instance on the local network
+    ///
+    /// Registration can fail for a moment while interfaces change, so it's
+    /// retried with backoff (`REGISTER_ATTEMPTS` tries) before giving up.
+    ///
+    /// # Arguments
+    /// * `handle` - Player's display name
+    /// * `lobby_name` - Optional lobby name if hosting
+    /// * `port` - Port to advertise
+    /// * `lobby` - Player count and state of the lobby, if hosting
+    pub fn advertise(
+        &mut self,
+        handle: &str,
+        lobby_name: Option<&str>,
+        port: u16,
+        lobby: Option<LobbyAdvert>,
+    ) -> Result<(), String> {
+        let service_info = build_service_info(&self.our_actor_id, handle, lobby_name, port, lobby)?;
+        self.register(service_info.clone())?;
+
+        self.registered_instance = Some(self.our_actor_id.clone());
+        self.advertised = Some(service_info);
+        if self.monitor.is_none() {
+            self.monitor = self.daemon.monitor().ok();
+        }
+        Ok(())
+    }
+
+    /// Advertise new player count and state for our lobby
+    ///
+    /// The old record is withdrawn first, so browsers get a goodbye for it
+    /// rather than keeping both cached, then the new one is registered.
+    pub fn update_advertisement(&mut self, lobby: LobbyAdvert) -> Result<(), String> {
+        let Some(current) = &self.advertised else {
+            return Err("Not advertising".to_string());
+        };
+        let properties = current.get_properties();
+        let handle = properties.get_property_val_str("handle").unwrap_or_default().to_string();
+        let lobby_name = properties.get_property_val_str("lobby_name").map(str::to_string);
+        let port = current.get_port();
+
+        self.unregister()?;
+        self.advertise(&handle, lobby_name.as_deref(), port, Some(lobby))
+    }
+
+    /// Withdraw our registration, waiting (briefly) for the daemon to
+    /// confirm
+    fn unregister(&mut self) -> Result<(), String> {
+        let Some(instance_name) = self.registered_instance.take() else {
+            return Ok(());
+        };
+        let fullname = format!("{}.{}", instance_name, SERVICE_TYPE);
+        let status = self
+            .daemon
+            .unregister(&fullname)
+            .map_err(|e| format!("Failed to unregister service: {}", e))?;
+        // Gone either way; the wait only keeps the goodbye ahead of the
+        // new record
+        let _ = status.recv_timeout(UNREGISTER_WAIT);
+        Ok(())
+    }
+
+    /// Register a service with the daemon, retrying transient failures
+    fn register(&self, service_info: ServiceInfo) -> Result<(), String> {
+        retry_with_backoff(REGISTER_ATTEMPTS, REGISTER_BACKOFF, || {
+            self.daemon.register(service_info.clone())
+        })
+        .map_err(|e| format!("Failed to register service: {}", e))
+    }
+
+    /// Re-register our advertisement if the network changed under it
+    ///
+    /// When an interface comes or goes (a laptop switching networks, say)
+    /// the daemon may stop answering for our service on the new addresses.
+    /// Call this regularly while advertising; returns true if it
+    /// re-registered.
+    pub fn keep_advertised(&mut self) -> Result<bool, String> {
+        let (Some(monitor), Some(service_info)) = (&self.monitor, &self.advertised) else {
+            return Ok(false);
+        };
+        let mut changed = false;
+        while let Ok(event) = monitor.try_recv() {
+            if matches!(event, DaemonEvent::IpAdd(_) | DaemonEvent::IpDel(_)) {
+                changed = true;
+            }
+        }
+        if changed {
+            self.register(service_info.clone())?;
+        }
+        Ok(changed)
+    }
+
+    /// Stop advertising on the network
+    pub fn stop_advertising(&mut self) -> Result<(), String> {
+        self.advertised = None;
+        self.unregister()
+    }
+
+    /// Start browsing for other BLAM! instances
+    ///
+    /// Returns a receiver that will emit DiscoveryEvents as peers are found/lost
+    pub fn browse(&self) -> Result<mpsc::Receiver<DiscoveryEvent>, String> {
+        let receiver = self
+            .daemon
+            .browse(SERVICE_TYPE)
+            .map_err(|e| format!("Failed to start browsing: {}", e))?;
+
+        let (tx, rx) = mpsc::channel();
+        let our_actor_id = self.our_actor_id.clone();
+
+        thread::spawn(move || {
+            while let Ok(event) = receiver.recv() {
+                match event {
+                    ServiceEvent::SearchStarted(_) => {
+                        let _ = tx.send(DiscoveryEvent::SearchStarted);
+                    }
+                    ServiceEvent::SearchStopped(_) => {
+                        let _ = tx.send(DiscoveryEvent::BrowseFailed("search stopped".to_string()));
+                        return;
+                    }
+                    ServiceEvent::ServiceResolved(info) => {
+                        // Extract properties from TXT record
+                        let properties = info.get_properties();
+
+                        let actor_id = properties
+                            .get_property_val_str("actor_id")
+                            .unwrap_or_default()
+                            .to_string();
+
+                        // Skip our own instance
+                        if actor_id == our_actor_id {
+                            continue;
+                        }
+
+                        let handle = properties
+                            .get_property_val_str("handle")
+                            .unwrap_or_default()
+                            .to_string();
+
+                        let lobby_name = properties
+                            .get_property_val_str("lobby_name")
+                            .map(|s| s.to_string());
+
+                        let version = properties
+                            .get_property_val_str("version")
+                            .unwrap_or(PROTOCOL_VERSION)
+                            .to_string();
+
+                        let dictionary = properties
+                            .get_property_val_str("dictionary")
+                            .and_then(|s| u64::from_str_radix(s, 16).ok());
+
+                        let player_count = properties
+                            .get_property_val_str("players")
+                            .and_then(|s| s.parse().ok());
+
+                        let max_players = properties
+                            .get_property_val_str("max_players")
+                            .and_then(|s| s.parse().ok());
+
+                        let in_progress =
+                            properties.get_property_val_str("state") == Some("in_progress");
+
+                        let peer_info = PeerInfo {
+                            actor_id,
+                            handle,
+                            lobby_name,
+                            version,
+                            hostname: info.get_hostname().to_string(),
+                            addresses: info.get_addresses().iter().map(peer_address).collect(),
+                            port: info.get_port(),
+                            dictionary,
+                            player_count,
+                            max_players,
+                            in_progress,
+                            last_seen: Instant::now(),
+                        };
+
+                        let _ = tx.send(DiscoveryEvent::PeerDiscovered(peer_info));
+                    }
+                    ServiceEvent::ServiceRemoved(_, fullname) => {
+                        // Extract actor_id from fullname (format: "actor_id._blam._tcp.local.")
+                        if let Some(actor_id) = fullname.strip_suffix(&format!(".{}", SERVICE_TYPE)) {
+                            let _ = tx.send(DiscoveryEvent::PeerLost(actor_id.to_string()));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            // Nobody reads this once we've browsed again, so it only lands
+            // if the daemon itself went away
+            let _ = tx.send(DiscoveryEvent::BrowseFailed("mDNS daemon stopped".to_string()));
+        });
+
+        Ok(rx)
+    }
+
+    /// Stop browsing for peers
+    pub fn stop_browsing(&self) -> Result<(), String> {
+        self.daemon
+            .stop_browse(SERVICE_TYPE)
+            .map_err(|e| format!("Failed to stop browsing: {}", e))
+    }
+
+    /// Shutdown the discovery service
+    pub fn shutdown(self) -> Result<(), String> {
+        self.daemon
+            .shutdown()
+            .map_err(|e| format!("Failed to shutdown daemon: {}", e))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_service_info_enables_addr_auto() {
+        let lobby =
+            LobbyAdvert { players: 3, max_players: 12, in_progress: true, dictionary: 0xfeed };
+        let info =
+            build_service_info("blam-test-1234", "Alice", Some("LAN-ORBIT"), 55333, Some(lobby))
+                .unwrap();
+
+        assert!(info.is_addr_auto());
+        assert_eq!(info.get_fullname(), "blam-test-1234._blam._tcp.local.");
+        assert_eq!(info.get_hostname(), "blam-test-1234.local.");
+        assert_eq!(info.get_port(), 55333);
+        assert_eq!(info.get_property_val_str("actor_id"), Some("blam-test-1234"));
+        assert_eq!(info.get_property_val_str("handle"), Some("Alice"));
+        assert_eq!(info.get_property_val_str("lobby_name"), Some("LAN-ORBIT"));
+        assert_eq!(info.get_property_val_str("players"), Some("3"));
+        assert_eq!(info.get_property_val_str("max_players"), Some("12"));
+        assert_eq!(info.get_property_val_str("state"), Some("in_progress"));
+        let advertised = info.get_property_val_str("dictionary").unwrap();
+        assert_eq!(u64::from_str_radix(advertised, 16).ok(), Some(0xfeed));
+    }
+
+    #[test]
+    fn test_build_service_info_without_lobby_name() {
+        let info = build_service_info("blam-test-5678", "Bob", None, 55334, None).unwrap();
+
+        assert!(info.is_addr_auto());
+        assert_eq!(info.get_property_val_str("actor_id"), Some("blam-test-5678"));
+        assert_eq!(info.get_property_val_str("handle"), Some("Bob"));
+        assert_eq!(info.get_property_val_str("lobby_name"), None);
+        assert_eq!(info.get_property_val_str("players"), None);
+        assert_eq!(info.get_property_val_str("state"), None);
+        let advertised = info.get_property_val_str("dictionary").unwrap();
+        assert_eq!(u64::from_str_radix(advertised, 16).ok(), Some(dictionary::fingerprint()));
+    }
+
+    #[test]
+    fn test_unscoped_addresses_map_to_plain_ips() {
+        let v4: std::net::IpAddr = "192.168.1.20".parse().unwrap();
+        assert_eq!(peer_address(&ScopedIp::from(v4)), PeerAddress::from(v4));
+        // No interface known, so a link-local address stays unconnectable
+        let link_local: std::net::IpAddr = "fe80::1".parse().unwrap();
+        let address = peer_address(&ScopedIp::from(link_local));
+        assert_eq!(address.scope_id, 0);
+        assert_eq!(address.socket_addr(55333), None);
+    }
+}
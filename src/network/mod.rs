@@ -2,24 +2,34 @@
 //! Networking: mDNS discovery, peer sync, lobby hosting, TCP connections
 //!
 //! This module provides:
-//! - mDNS-SD discovery for finding BLAM! instances on local network
+//! - mDNS-SD discovery for finding BLAM! instances on local network (with
+//!   the `lan` feature)
 //! - TCP server for hosting games (default port 55333 with auto-increment)
 //! - TCP client for joining games (manual connect via --connect IP:PORT)
 //! - Length-prefixed JSON protocol for peer-to-peer messaging
 
 pub mod client;
+#[cfg(feature = "lan")]
+pub mod discovery;
 pub mod peer;
 pub mod protocol;
 pub mod server;
 
 pub use client::Client;
-pub use protocol::{ClaimRejectReason, JoinRejectReason, Message};
-pub use server::{Server, ServerEvent};
+#[cfg(feature = "lan")]
+pub use discovery::{DiscoveryEvent, LobbyAdvert, ServiceDiscovery};
+pub use protocol::{
+    ClaimRejectReason, FrameError, JoinRejectReason, Message, MessageFramer, RoundClaim,
+    RoundConfig, RoundEndReason, MAX_FRAME_LEN,
+};
+pub use server::{EventQueue, Server, ServerEvent, TrafficStats};
 
-use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
 use std::collections::HashMap;
-use std::sync::mpsc;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr, SocketAddrV6};
+use std::str::FromStr;
 use std::thread;
+use std::time::{Duration, Instant};
 
 /// BLAM! service type for mDNS discovery
 pub const SERVICE_TYPE: &str = "_blam._tcp.local.";
@@ -27,6 +37,18 @@ pub const SERVICE_TYPE: &str = "_blam._tcp.local.";
 /// Current protocol version
 pub const PROTOCOL_VERSION: &str = "1";
 
+/// Attempts at registering our service before advertising gives up
+pub const REGISTER_ATTEMPTS: u32 = 4;
+
+/// Wait before the first registration retry (doubled for each one after)
+pub const REGISTER_BACKOFF: Duration = Duration::from_millis(100);
+
+/// How long a peer stays listed without being resolved again
+///
+/// mDNS only reports a removal when the peer says goodbye; one that was
+/// killed never does, so peers that stop showing up are dropped instead.
+pub const PEER_TTL: Duration = Duration::from_secs(30);
+
 /// Information about a discovered peer
 #[derive(Debug, Clone)]
 pub struct PeerInfo {
@@ -40,190 +62,169 @@ pub struct PeerInfo {
     pub version: String,
     /// Hostname of the peer
     pub hostname: String,
-    /// IP addresses of the peer
-    pub addresses: Vec<std::net::IpAddr>,
+    /// Addresses the peer was resolved at
+    pub addresses: Vec<PeerAddress>,
     /// Port the peer is listening on
     pub port: u16,
+    /// Fingerprint of the peer's dictionary (older builds don't send one)
+    pub dictionary: Option<u64>,
+    /// Players in the peer's lobby, host included (older builds don't send
+    /// it)
+    pub player_count: Option<usize>,
+    /// Seats in the peer's lobby (older builds don't send it)
+    pub max_players: Option<usize>,
+    /// Whether the peer's lobby is playing a round
+    pub in_progress: bool,
+    /// When the peer was last resolved
+    pub last_seen: Instant,
 }
 
-/// Events from the service discovery system
-#[derive(Debug)]
-pub enum DiscoveryEvent {
-    /// A new peer was discovered
-    PeerDiscovered(PeerInfo),
-    /// A peer went offline
-    PeerLost(String), // actor_id
-}
+impl PeerInfo {
+    /// Whether the peer can be joined yet
+    ///
+    /// mDNS sometimes reports a service before any of its addresses have
+    /// resolved; such a peer is listed as resolving until they arrive.
+    pub fn is_joinable(&self) -> bool {
+        self.connect_addr().is_some()
+    }
+
+    /// Where to connect to join the peer
+    ///
+    /// The first address that can be connected to: a link-local IPv6
+    /// address is skipped if we don't know which interface it's on.
+    pub fn connect_addr(&self) -> Option<SocketAddr> {
+        self.addresses.iter().find_map(|address| address.socket_addr(self.port))
+    }
+
+    /// Whether the peer's lobby is known to have no seats left
+    pub fn is_full(&self) -> bool {
+        let seats = self.max_players.unwrap_or(crate::game::MAX_PLAYERS);
+        self.player_count.is_some_and(|count| count >= seats)
+    }
+
+    /// How full the lobby is and whether it's mid-round, for the browser
+    /// (e.g. "3/12 — in progress"), if the host said
+    pub fn occupancy(&self) -> Option<String> {
+        let seats = self.max_players.unwrap_or(crate::game::MAX_PLAYERS);
+        let count = format!("{}/{}", self.player_count?, seats);
+        Some(if self.in_progress { format!("{} — in progress", count) } else { count })
+    }
+
+    /// Whether the peer runs a protocol version we can't talk to
+    pub fn is_incompatible(&self) -> bool {
+        self.version != PROTOCOL_VERSION
+    }
 
-/// Service discovery manager for finding BLAM! instances on the local network
-pub struct ServiceDiscovery {
-    daemon: ServiceDaemon,
-    our_actor_id: String,
-    registered_instance: Option<String>,
+    /// Whether the peer is known to validate words against a different
+    /// dictionary than `ours`
+    pub fn dictionary_differs(&self, ours: u64) -> bool {
+        self.dictionary.is_some_and(|theirs| theirs != ours)
+    }
 }
 
-fn build_service_info(
-    actor_id: &str,
-    handle: &str,
-    lobby_name: Option<&str>,
-    port: u16,
-) -> Result<ServiceInfo, String> {
-    let mut properties = HashMap::new();
-    properties.insert("version".to_string(), PROTOCOL_VERSION.to_string());
-    properties.insert("handle".to_string(), handle.to_string());
-    properties.insert("actor_id".to_string(), actor_id.to_string());
-    if let Some(lobby) = lobby_name {
-        properties.insert("lobby_name".to_string(), lobby.to_string());
-    }
-
-    // Instance name is the actor_id (must be unique on the network).
-    let instance_name = actor_id;
-
-    // Hostname for SRV/A records. We keep it deterministic per instance.
-    let hostname = format!("{}.local.", actor_id);
-
-    ServiceInfo::new(
-        SERVICE_TYPE,
-        instance_name,
-        &hostname,
-        (), // No explicit IPs; daemon will populate local interface addrs.
-        port,
-        properties,
-    )
-    .map(|service| service.enable_addr_auto())
-    .map_err(|e| format!("Failed to create service info: {}", e))
+/// An address a peer was resolved at
+///
+/// Link-local IPv6 addresses (`fe80::/10`) exist on every interface, so
+/// they're only usable together with the interface (scope) they were seen
+/// on; mDNS tells us which that is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerAddress {
+    pub ip: IpAddr,
+    /// Index of the interface a link-local address is on (0 if unknown or
+    /// not needed)
+    pub scope_id: u32,
+    /// Name of that interface, e.g. `eth0`, for display
+    pub interface: Option<String>,
 }
 
-impl ServiceDiscovery {
-    /// Create a new service discovery instance
+impl PeerAddress {
+    /// An IPv6 address seen on interface `scope_id`
     ///
-    /// # Arguments
-    /// * `actor_id` - Unique identifier for this instance
-    pub fn new(actor_id: String) -> Result<Self, String> {
-        let daemon = ServiceDaemon::new().map_err(|e| format!("Failed to create mDNS daemon: {}", e))?;
+    /// The scope is only kept for link-local addresses, the only ones that
+    /// need it.
+    pub fn scoped(ip: std::net::Ipv6Addr, scope_id: u32, interface: String) -> Self {
+        if !ip.is_unicast_link_local() {
+            return Self::from(IpAddr::V6(ip));
+        }
+        Self { ip: IpAddr::V6(ip), scope_id, interface: Some(interface) }
+    }
 
-        Ok(Self {
-            daemon,
-            our_actor_id: actor_id,
-            registered_instance: None,
-        })
+    /// Whether the address can't be connected to without a scope
+    pub fn needs_scope(&self) -> bool {
+        matches!(self.ip, IpAddr::V6(ip) if ip.is_unicast_link_local())
     }
 
-    /// Advertise this instance on the local network
-    ///
-    /// # Arguments
-    /// * `handle` - Player's display name
-    /// * `lobby_name` - Optional lobby name if hosting
-    /// * `port` - Port to advertise
-    pub fn advertise(
-        &mut self,
-        handle: &str,
-        lobby_name: Option<&str>,
-        port: u16,
-    ) -> Result<(), String> {
-        let service_info = build_service_info(&self.our_actor_id, handle, lobby_name, port)?;
-
-        self.daemon
-            .register(service_info)
-            .map_err(|e| format!("Failed to register service: {}", e))?;
-
-        self.registered_instance = Some(self.our_actor_id.clone());
-        Ok(())
-    }
-
-    /// Stop advertising on the network
-    pub fn stop_advertising(&mut self) -> Result<(), String> {
-        if let Some(instance_name) = self.registered_instance.take() {
-            let fullname = format!("{}.{}", instance_name, SERVICE_TYPE);
-            self.daemon
-                .unregister(&fullname)
-                .map_err(|e| format!("Failed to unregister service: {}", e))?;
+    /// Socket address for `port`, or `None` for a link-local address
+    /// without a scope
+    pub fn socket_addr(&self, port: u16) -> Option<SocketAddr> {
+        match self.ip {
+            IpAddr::V6(ip) if self.needs_scope() => (self.scope_id != 0)
+                .then(|| SocketAddr::V6(SocketAddrV6::new(ip, port, 0, self.scope_id))),
+            ip => Some(SocketAddr::new(ip, port)),
         }
-        Ok(())
     }
 
-    /// Start browsing for other BLAM! instances
-    ///
-    /// Returns a receiver that will emit DiscoveryEvents as peers are found/lost
-    pub fn browse(&self) -> Result<mpsc::Receiver<DiscoveryEvent>, String> {
-        let receiver = self
-            .daemon
-            .browse(SERVICE_TYPE)
-            .map_err(|e| format!("Failed to start browsing: {}", e))?;
-
-        let (tx, rx) = mpsc::channel();
-        let our_actor_id = self.our_actor_id.clone();
-
-        thread::spawn(move || {
-            while let Ok(event) = receiver.recv() {
-                match event {
-                    ServiceEvent::ServiceResolved(info) => {
-                        // Extract properties from TXT record
-                        let properties = info.get_properties();
-
-                        let actor_id = properties
-                            .get_property_val_str("actor_id")
-                            .unwrap_or_default()
-                            .to_string();
-
-                        // Skip our own instance
-                        if actor_id == our_actor_id {
-                            continue;
-                        }
-
-                        let handle = properties
-                            .get_property_val_str("handle")
-                            .unwrap_or_default()
-                            .to_string();
-
-                        let lobby_name = properties
-                            .get_property_val_str("lobby_name")
-                            .map(|s| s.to_string());
-
-                        let version = properties
-                            .get_property_val_str("version")
-                            .unwrap_or(PROTOCOL_VERSION)
-                            .to_string();
-
-                        let peer_info = PeerInfo {
-                            actor_id,
-                            handle,
-                            lobby_name,
-                            version,
-                            hostname: info.get_hostname().to_string(),
-                            addresses: info.get_addresses().iter().map(|s| s.to_ip_addr()).collect(),
-                            port: info.get_port(),
-                        };
-
-                        let _ = tx.send(DiscoveryEvent::PeerDiscovered(peer_info));
-                    }
-                    ServiceEvent::ServiceRemoved(_, fullname) => {
-                        // Extract actor_id from fullname (format: "actor_id._blam._tcp.local.")
-                        if let Some(actor_id) = fullname.strip_suffix(&format!(".{}", SERVICE_TYPE)) {
-                            let _ = tx.send(DiscoveryEvent::PeerLost(actor_id.to_string()));
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        });
+    /// The address with `port`, as typed into `--connect` (`[fe80::1%eth0]:55333`)
+    pub fn with_port(&self, port: u16) -> String {
+        match self.ip {
+            IpAddr::V4(_) => format!("{}:{}", self, port),
+            IpAddr::V6(_) => format!("[{}]:{}", self, port),
+        }
+    }
+}
+
+impl From<IpAddr> for PeerAddress {
+    fn from(ip: IpAddr) -> Self {
+        Self { ip, scope_id: 0, interface: None }
+    }
+}
 
-        Ok(rx)
+impl FromStr for PeerAddress {
+    type Err = std::net::AddrParseError;
+
+    /// Parse an address, with an optional numeric scope (`fe80::1%2`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((ip, scope)) = s.split_once('%') else {
+            return s.parse::<IpAddr>().map(Self::from);
+        };
+        let ip: std::net::Ipv6Addr = ip.parse()?;
+        // Reuse the socket address parser to reject a bad scope
+        let scoped: SocketAddrV6 = format!("[{}%{}]:0", ip, scope).parse()?;
+        Ok(Self { ip: IpAddr::V6(ip), scope_id: scoped.scope_id(), interface: None })
     }
+}
 
-    /// Stop browsing for peers
-    pub fn stop_browsing(&self) -> Result<(), String> {
-        self.daemon
-            .stop_browse(SERVICE_TYPE)
-            .map_err(|e| format!("Failed to stop browsing: {}", e))
+impl fmt::Display for PeerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.interface, self.scope_id) {
+            (Some(name), _) => write!(f, "{}%{}", self.ip, name),
+            (None, 0) => write!(f, "{}", self.ip),
+            (None, index) => write!(f, "{}%{}", self.ip, index),
+        }
     }
+}
 
-    /// Shutdown the discovery service
-    pub fn shutdown(self) -> Result<(), String> {
-        self.daemon
-            .shutdown()
-            .map_err(|e| format!("Failed to shutdown daemon: {}", e))?;
-        Ok(())
+/// Run `attempt` until it succeeds, up to `attempts` times
+///
+/// Sleeps `backoff` after the first failure and doubles the wait after each
+/// one that follows. Returns the last error if every attempt fails.
+pub fn retry_with_backoff<T, E>(
+    attempts: u32,
+    backoff: Duration,
+    mut attempt: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut wait = backoff;
+    let mut tries = 1;
+    loop {
+        match attempt() {
+            Ok(value) => return Ok(value),
+            Err(e) if tries >= attempts => return Err(e),
+            Err(_) => {
+                thread::sleep(wait);
+                wait *= 2;
+                tries += 1;
+            }
+        }
     }
 }
 
@@ -241,7 +242,16 @@ impl PeerTracker {
     }
 
     /// Add or update a peer
-    pub fn update(&mut self, peer: PeerInfo) {
+    ///
+    /// An update without addresses keeps the ones already known, so a
+    /// re-resolution that comes back empty doesn't make a joinable lobby
+    /// unjoinable.
+    pub fn update(&mut self, mut peer: PeerInfo) {
+        if peer.addresses.is_empty() {
+            if let Some(known) = self.peers.get(&peer.actor_id) {
+                peer.addresses = known.addresses.clone();
+            }
+        }
         self.peers.insert(peer.actor_id.clone(), peer);
     }
 
@@ -250,11 +260,27 @@ impl PeerTracker {
         self.peers.remove(actor_id)
     }
 
+    /// Drop peers not seen within `ttl` of `now`, returning them
+    pub fn prune_stale(&mut self, now: Instant, ttl: Duration) -> Vec<PeerInfo> {
+        let stale: Vec<String> = self
+            .peers
+            .values()
+            .filter(|peer| now.saturating_duration_since(peer.last_seen) > ttl)
+            .map(|peer| peer.actor_id.clone())
+            .collect();
+        stale.iter().filter_map(|actor_id| self.peers.remove(actor_id)).collect()
+    }
+
     /// Get all known peers
     pub fn peers(&self) -> impl Iterator<Item = &PeerInfo> {
         self.peers.values()
     }
 
+    /// Get the peers that have an address to connect to
+    pub fn joinable_peers(&self) -> impl Iterator<Item = &PeerInfo> {
+        self.peers.values().filter(|peer| peer.is_joinable())
+    }
+
     /// Get a specific peer by actor_id
     pub fn get(&self, actor_id: &str) -> Option<&PeerInfo> {
         self.peers.get(actor_id)
@@ -276,6 +302,32 @@ impl Default for PeerTracker {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_transient_register_failure_succeeds_on_retry() {
+        let mut calls = 0;
+        let result: Result<&str, String> = retry_with_backoff(4, Duration::from_millis(1), || {
+            calls += 1;
+            if calls < 3 {
+                Err("interface changing".to_string())
+            } else {
+                Ok("registered")
+            }
+        });
+        assert_eq!(result, Ok("registered"));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn test_register_gives_up_after_attempts() {
+        let mut calls = 0;
+        let result: Result<(), String> = retry_with_backoff(3, Duration::from_millis(1), || {
+            calls += 1;
+            Err(format!("failure {}", calls))
+        });
+        assert_eq!(result, Err("failure 3".to_string()));
+        assert_eq!(calls, 3);
+    }
+
     #[test]
     fn test_service_type_format() {
         assert!(SERVICE_TYPE.starts_with("_"));
@@ -292,6 +344,11 @@ mod tests {
             hostname: "test.local.".to_string(),
             addresses: vec![],
             port: 55333,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
         };
 
         let cloned = peer.clone();
@@ -311,6 +368,11 @@ mod tests {
             hostname: "peer1.local.".to_string(),
             addresses: vec![],
             port: 55333,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
         };
 
         tracker.update(peer);
@@ -334,6 +396,11 @@ mod tests {
             hostname: "peer1.local.".to_string(),
             addresses: vec![],
             port: 55333,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
         };
 
         tracker.update(peer1);
@@ -347,6 +414,11 @@ mod tests {
             hostname: "peer1.local.".to_string(),
             addresses: vec![],
             port: 55333,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
         };
 
         tracker.update(peer1_updated);
@@ -368,6 +440,11 @@ mod tests {
                 hostname: format!("peer{}.local.", i),
                 addresses: vec![],
                 port: 55333 + i as u16,
+                dictionary: None,
+                player_count: None,
+                max_players: None,
+                in_progress: false,
+                last_seen: Instant::now(),
             };
             tracker.update(peer);
         }
@@ -386,6 +463,34 @@ mod tests {
         assert_eq!(tracker.count(), 4);
     }
 
+    #[test]
+    fn test_peer_tracker_prunes_stale_peers() {
+        let mut tracker = PeerTracker::new();
+        let now = Instant::now();
+        for (actor_id, age) in [("peer-old", 31), ("peer-new", 5)] {
+            tracker.update(PeerInfo {
+                actor_id: actor_id.to_string(),
+                handle: "Player".to_string(),
+                lobby_name: None,
+                version: "1".to_string(),
+                hostname: "peer.local.".to_string(),
+                addresses: vec![],
+                port: 55333,
+                dictionary: None,
+                player_count: None,
+                max_players: None,
+                in_progress: false,
+                last_seen: now.checked_sub(Duration::from_secs(age)).unwrap(),
+            });
+        }
+
+        let pruned = tracker.prune_stale(now, PEER_TTL);
+        assert_eq!(pruned.len(), 1);
+        assert_eq!(pruned[0].actor_id, "peer-old");
+        assert!(tracker.get("peer-new").is_some());
+        assert_eq!(tracker.count(), 1);
+    }
+
     #[test]
     fn test_peer_tracker_default() {
         let tracker = PeerTracker::default();
@@ -410,6 +515,11 @@ mod tests {
             hostname: "peer1.local.".to_string(),
             addresses: vec![],
             port: 55333,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
         };
         tracker.update(peer);
 
@@ -420,8 +530,6 @@ mod tests {
 
     #[test]
     fn test_peer_info_with_addresses() {
-        use std::net::IpAddr;
-
         let peer = PeerInfo {
             actor_id: "peer-1".to_string(),
             handle: "Player1".to_string(),
@@ -429,15 +537,100 @@ mod tests {
             version: "1".to_string(),
             hostname: "peer1.local.".to_string(),
             addresses: vec![
-                "127.0.0.1".parse::<IpAddr>().unwrap(),
-                "192.168.1.1".parse::<IpAddr>().unwrap(),
+                "127.0.0.1".parse::<PeerAddress>().unwrap(),
+                "192.168.1.1".parse::<PeerAddress>().unwrap(),
             ],
             port: 55333,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
         };
 
         assert_eq!(peer.addresses.len(), 2);
         assert_eq!(peer.port, 55333);
         assert_eq!(peer.lobby_name.as_deref(), Some("TestLobby"));
+        assert_eq!(peer.connect_addr(), Some("127.0.0.1:55333".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_link_local_addresses_keep_their_scope() {
+        let link_local = PeerAddress::scoped("fe80::1".parse().unwrap(), 2, "eth0".to_string());
+        assert_eq!(link_local.with_port(55333), "[fe80::1%eth0]:55333");
+        let SocketAddr::V6(target) = link_local.socket_addr(55333).unwrap() else {
+            panic!("expected an IPv6 target");
+        };
+        assert_eq!(target.scope_id(), 2);
+        assert_eq!(link_local.socket_addr(55333), Some("[fe80::1%2]:55333".parse().unwrap()));
+
+        // Routable addresses don't need one, even if mDNS saw them on an interface
+        let global = PeerAddress::scoped("2001:db8::1".parse().unwrap(), 2, "eth0".to_string());
+        assert_eq!(global.with_port(55333), "[2001:db8::1]:55333");
+        assert_eq!(global.socket_addr(55333), Some("[2001:db8::1]:55333".parse().unwrap()));
+
+        // Numeric scopes parse, bad ones don't
+        let parsed: PeerAddress = "fe80::1%2".parse().unwrap();
+        assert_eq!(parsed.socket_addr(1), link_local.socket_addr(1));
+        assert!("fe80::1%eth0".parse::<PeerAddress>().is_err());
+        assert!("10.0.0.1%2".parse::<PeerAddress>().is_err());
+    }
+
+    #[test]
+    fn test_unscoped_link_local_address_is_skipped() {
+        let mut peer = PeerInfo {
+            actor_id: "peer-1".to_string(),
+            handle: "Player1".to_string(),
+            lobby_name: None,
+            version: "1".to_string(),
+            hostname: "peer1.local.".to_string(),
+            addresses: vec!["fe80::1".parse().unwrap()],
+            port: 55333,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        assert_eq!(peer.connect_addr(), None);
+        assert!(!peer.is_joinable());
+
+        peer.addresses.push("192.168.1.20".parse().unwrap());
+        assert_eq!(peer.connect_addr(), Some("192.168.1.20:55333".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_peer_without_addresses_not_joinable() {
+        let mut tracker = PeerTracker::new();
+        let mut peer = PeerInfo {
+            actor_id: "peer-1".to_string(),
+            handle: "Player1".to_string(),
+            lobby_name: Some("SWIFT-ORBIT".to_string()),
+            version: "1".to_string(),
+            hostname: "peer1.local.".to_string(),
+            addresses: vec![],
+            port: 55333,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+
+        // Listed, but not offered for joining until an address resolves
+        tracker.update(peer.clone());
+        assert_eq!(tracker.count(), 1);
+        assert!(!tracker.get("peer-1").unwrap().is_joinable());
+        assert_eq!(tracker.joinable_peers().count(), 0);
+
+        peer.addresses = vec!["192.168.1.20".parse().unwrap()];
+        tracker.update(peer.clone());
+        assert_eq!(tracker.joinable_peers().count(), 1);
+
+        // A later empty resolution keeps the known address
+        peer.addresses.clear();
+        tracker.update(peer);
+        assert!(tracker.get("peer-1").unwrap().is_joinable());
     }
 
     #[test]
@@ -447,26 +640,60 @@ mod tests {
     }
 
     #[test]
-    fn test_build_service_info_enables_addr_auto() {
-        let info =
-            build_service_info("blam-test-1234", "Alice", Some("LAN-ORBIT"), 55333).unwrap();
-
-        assert!(info.is_addr_auto());
-        assert_eq!(info.get_fullname(), "blam-test-1234._blam._tcp.local.");
-        assert_eq!(info.get_hostname(), "blam-test-1234.local.");
-        assert_eq!(info.get_port(), 55333);
-        assert_eq!(info.get_property_val_str("actor_id"), Some("blam-test-1234"));
-        assert_eq!(info.get_property_val_str("handle"), Some("Alice"));
-        assert_eq!(info.get_property_val_str("lobby_name"), Some("LAN-ORBIT"));
+    fn test_dictionary_mismatch_only_when_known() {
+        let mut peer = PeerInfo {
+            actor_id: "peer-1".to_string(),
+            handle: "Player1".to_string(),
+            lobby_name: Some("TestLobby".to_string()),
+            version: "1".to_string(),
+            hostname: "peer1.local.".to_string(),
+            addresses: vec![],
+            port: 55333,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        assert!(!peer.dictionary_differs(42), "older hosts don't advertise one");
+        peer.dictionary = Some(42);
+        assert!(!peer.dictionary_differs(42));
+        peer.dictionary = Some(7);
+        assert!(peer.dictionary_differs(42));
     }
 
     #[test]
-    fn test_build_service_info_without_lobby_name() {
-        let info = build_service_info("blam-test-5678", "Bob", None, 55334).unwrap();
-
-        assert!(info.is_addr_auto());
-        assert_eq!(info.get_property_val_str("actor_id"), Some("blam-test-5678"));
-        assert_eq!(info.get_property_val_str("handle"), Some("Bob"));
-        assert_eq!(info.get_property_val_str("lobby_name"), None);
+    fn test_occupancy_from_advertised_counts() {
+        let mut peer = PeerInfo {
+            actor_id: "peer-1".to_string(),
+            handle: "Player1".to_string(),
+            lobby_name: Some("TestLobby".to_string()),
+            version: "1".to_string(),
+            hostname: "peer1.local.".to_string(),
+            addresses: vec![],
+            port: 55333,
+            dictionary: None,
+            player_count: None,
+            max_players: None,
+            in_progress: false,
+            last_seen: Instant::now(),
+        };
+        assert_eq!(peer.occupancy(), None, "older hosts don't advertise a count");
+        assert!(!peer.is_full());
+
+        peer.player_count = Some(3);
+        peer.max_players = Some(12);
+        assert_eq!(peer.occupancy().as_deref(), Some("3/12"));
+        peer.in_progress = true;
+        assert_eq!(peer.occupancy().as_deref(), Some("3/12 — in progress"));
+        assert!(!peer.is_full());
+
+        peer.max_players = Some(3);
+        assert!(peer.is_full());
+        // Without a seat count, the most a lobby can hold
+        peer.max_players = None;
+        assert!(!peer.is_full());
+        peer.player_count = Some(crate::game::MAX_PLAYERS);
+        assert!(peer.is_full());
     }
 }
@@ -1,10 +1,12 @@
 #![allow(dead_code)]
 //! Peer connection handling
 
-use super::protocol::Message;
+use super::protocol::{FrameError, Message, MessageFramer};
 use std::io::{self, ErrorKind, Write};
 use std::net::{SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
@@ -16,10 +18,15 @@ pub struct Peer {
     pub player_name: Option<String>,
     /// Channel to send pre-serialized bytes to this peer
     tx: Sender<Vec<u8>>,
-    /// Channel to receive messages from this peer
-    rx: Receiver<Message>,
+    /// Channel to receive messages with their encoded frame length (or
+    /// frames that failed to decode) from this peer
+    rx: Receiver<Result<(Message, usize), FrameError>>,
+    /// Frames from this peer that were skipped, since last taken
+    frame_errors: Vec<FrameError>,
     /// Whether the connection is still alive
     alive: bool,
+    /// Set once either side of the connection has failed or been dropped
+    closed: Arc<AtomicBool>,
 }
 
 impl Peer {
@@ -33,44 +40,62 @@ impl Peer {
         stream.set_write_timeout(Some(Duration::from_secs(5)))?;
 
         let (outgoing_tx, outgoing_rx) = channel::<Vec<u8>>();
-        let (incoming_tx, incoming_rx) = channel::<Message>();
+        let (incoming_tx, incoming_rx) = channel::<Result<(Message, usize), FrameError>>();
 
         // Clone stream for writer thread
         let read_stream = stream.try_clone()?;
         let mut write_stream = stream;
+        let closed = Arc::new(AtomicBool::new(false));
 
         // Writer thread: receives pre-serialized bytes and writes them
+        let writer_closed = Arc::clone(&closed);
         thread::spawn(move || {
             while let Ok(bytes) = outgoing_rx.recv() {
-                if write_stream.write_all(&bytes).is_err() {
-                    break;
-                }
-                if write_stream.flush().is_err() {
+                if write_stream.write_all(&bytes).is_err() || write_stream.flush().is_err() {
+                    writer_closed.store(true, Ordering::SeqCst);
                     break;
                 }
             }
         });
 
         // Reader thread
+        let reader_closed = Arc::clone(&closed);
         thread::spawn(move || {
             let mut read_stream = read_stream;
-            loop {
-                match Message::read_from(&mut read_stream) {
-                    Ok(msg) => {
-                        if incoming_tx.send(msg).is_err() {
-                            break;
-                        }
+            // Buffers partial frames across read timeouts
+            let mut frames = MessageFramer::new();
+            'read: loop {
+                match frames.read_from(&mut read_stream) {
+                    Ok(0) => {
+                        // Connection closed
+                        break;
                     }
+                    Ok(_) => loop {
+                        // A bad frame is skipped and passed on to be reported
+                        let received = match frames.next_message() {
+                            Ok(Some(framed)) => Ok(framed),
+                            Ok(None) => break,
+                            Err(e) => Err(e),
+                        };
+                        if incoming_tx.send(received).is_err() {
+                            break 'read;
+                        }
+                    },
                     Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => {
-                        // Timeout, continue trying
+                        // Timeout: stop if the peer was dropped, otherwise keep trying
+                        if reader_closed.load(Ordering::SeqCst) {
+                            break;
+                        }
                         continue;
                     }
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
                     Err(_) => {
-                        // Connection closed or error
+                        // Connection error
                         break;
                     }
                 }
             }
+            reader_closed.store(true, Ordering::SeqCst);
         });
 
         Ok(Peer {
@@ -78,7 +103,9 @@ impl Peer {
             player_name: None,
             tx: outgoing_tx,
             rx: incoming_rx,
+            frame_errors: Vec::new(),
             alive: true,
+            closed,
         })
     }
 
@@ -94,24 +121,46 @@ impl Peer {
     }
 
     /// Send pre-serialized bytes to this peer (avoids redundant serialization in broadcast)
+    ///
+    /// Fails once the connection is known to be closed, so a lost message is
+    /// reported rather than queued for a writer that will never send it.
     pub fn send_raw(&self, bytes: Vec<u8>) -> io::Result<()> {
+        if self.closed.load(Ordering::SeqCst) {
+            return Err(io::Error::new(ErrorKind::BrokenPipe, "peer disconnected"));
+        }
         self.tx
             .send(bytes)
             .map_err(|_| io::Error::new(ErrorKind::BrokenPipe, "peer disconnected"))
     }
 
     /// Try to receive a message from this peer (non-blocking)
+    ///
+    /// Frames that failed to decode are set aside for `take_frame_errors`.
     pub fn try_recv(&mut self) -> Option<Message> {
-        match self.rx.try_recv() {
-            Ok(msg) => Some(msg),
-            Err(TryRecvError::Empty) => None,
-            Err(TryRecvError::Disconnected) => {
-                self.alive = false;
-                None
+        self.try_recv_framed().map(|(msg, _)| msg)
+    }
+
+    /// Like `try_recv`, with the length of the frame the message came in
+    /// (length prefix included)
+    pub fn try_recv_framed(&mut self) -> Option<(Message, usize)> {
+        loop {
+            match self.rx.try_recv() {
+                Ok(Ok(framed)) => return Some(framed),
+                Ok(Err(e)) => self.frame_errors.push(e),
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    self.alive = false;
+                    return None;
+                }
             }
         }
     }
 
+    /// Frames skipped since the last call, because they couldn't be decoded
+    pub fn take_frame_errors(&mut self) -> Vec<FrameError> {
+        std::mem::take(&mut self.frame_errors)
+    }
+
     /// Receive all pending messages from this peer
     pub fn recv_all(&mut self) -> Vec<Message> {
         let mut messages = Vec::new();
@@ -121,6 +170,15 @@ impl Peer {
         messages
     }
 
+    /// Receive all pending messages from this peer, with their frame lengths
+    pub fn recv_all_framed(&mut self) -> Vec<(Message, usize)> {
+        let mut messages = Vec::new();
+        while let Some(framed) = self.try_recv_framed() {
+            messages.push(framed);
+        }
+        messages
+    }
+
     /// Check if the peer connection is still alive
     pub fn is_alive(&self) -> bool {
         self.alive
@@ -132,6 +190,13 @@ impl Peer {
     }
 }
 
+impl Drop for Peer {
+    fn drop(&mut self) {
+        // Let the reader thread exit so the socket actually closes
+        self.closed.store(true, Ordering::SeqCst);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,4 +229,19 @@ mod tests {
 
         handle.join().unwrap();
     }
+
+    #[test]
+    fn test_send_after_remote_close_fails() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let remote = TcpStream::connect(addr).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        let peer = Peer::new(stream).unwrap();
+
+        drop(remote);
+        thread::sleep(Duration::from_millis(200));
+
+        assert_eq!(peer.send(Message::Ping).unwrap_err().kind(), ErrorKind::BrokenPipe);
+    }
 }
@@ -3,6 +3,8 @@
 //!
 //! Simple length-prefixed JSON messages over TCP.
 
+use crate::game::{Language, RackConfig};
+use crate::json;
 use std::io::{self, Read, Write};
 use std::net::TcpStream;
 
@@ -17,6 +19,10 @@ pub enum ClaimRejectReason {
     InvalidLetters { missing: Vec<char> },
     /// Word is too short
     TooShort,
+    /// Word is longer than the rack (can't possibly be spelled)
+    TooLong { max: u32 },
+    /// Word uses fewer of the rack's letters than the house rule asks for
+    TooFewLetters { min: u32 },
     /// Round has ended
     RoundEnded,
 }
@@ -34,11 +40,44 @@ impl ClaimRejectReason {
                 format!("Missing letters: {}", letters)
             }
             ClaimRejectReason::TooShort => "Too short".to_string(),
+            ClaimRejectReason::TooLong { max } => format!("Too long (max {} letters)", max),
+            ClaimRejectReason::TooFewLetters { min } => format!("Use at least {} letters", min),
             ClaimRejectReason::RoundEnded => "Round has ended".to_string(),
         }
     }
 }
 
+/// Why a round ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundEndReason {
+    /// The round timer ran out
+    #[default]
+    TimeExpired,
+    /// The host ended the round early
+    HostEnded,
+    /// A player reached the target score
+    TargetReached,
+}
+
+impl RoundEndReason {
+    /// Headline for the end-of-round screen
+    pub fn headline(&self) -> &'static str {
+        match self {
+            RoundEndReason::TimeExpired => "TIME'S UP!",
+            RoundEndReason::HostEnded => "ROUND ENDED BY HOST",
+            RoundEndReason::TargetReached => "TARGET REACHED!",
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            RoundEndReason::TimeExpired => "time_expired",
+            RoundEndReason::HostEnded => "host_ended",
+            RoundEndReason::TargetReached => "target_reached",
+        }
+    }
+}
+
 /// Reason a join request was rejected
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum JoinRejectReason {
@@ -62,8 +101,14 @@ pub enum Message {
     Join { player_name: String },
     /// Host rejects a join request
     JoinRejected { reason: JoinRejectReason },
+    /// Watch the lobby without playing (client -> host); the host sends it
+    /// back to anyone it seats as a spectator, including players who joined
+    /// a full lobby (host -> spectator)
+    JoinSpectator { name: String },
     /// Player is leaving
     Leave { player_name: String },
+    /// The host removed us from the lobby, and why (host -> kicked player)
+    Kicked { reason: String },
     /// Client requests to claim a word (client -> host)
     ClaimAttempt { word: String },
     /// Host accepts a claim and broadcasts to all (host -> all)
@@ -71,6 +116,9 @@ pub enum Message {
         word: String,
         player_name: String,
         points: u32,
+        /// Combo multiplier the host applied (1 for none); older hosts
+        /// don't send it
+        combo: u32,
     },
     /// Host rejects a claim (host -> requester only)
     ClaimRejected {
@@ -94,15 +142,53 @@ pub enum Message {
         timestamp_ms: u64,
         /// Monotonic sequence number for ordering within the round
         claim_sequence: u64,
+        /// Host-assigned ID of the round the claim was made in (`None` from
+        /// hosts that predate round IDs)
+        round_id: Option<i64>,
     },
     /// Word claimed by a player (broadcast, legacy compatibility)
     Claim { player_name: String, word: String, points: u32 },
     /// Countdown to round start (3, 2, 1, BLAM!)
     Countdown { letters: Vec<char>, duration_secs: u32, countdown_secs: u32 },
+    /// Countdown to a round on the rack `rack` deals for `seed`, which
+    /// every peer deals for itself
+    CountdownSeeded {
+        seed: u64,
+        rack: RackConfig,
+        duration_secs: u32,
+        countdown_secs: u32,
+    },
+    /// Host called off the countdown; back to waiting (host -> all)
+    CountdownCancelled,
+    /// Host offers another round once this one is over (host -> all)
+    RematchOffer,
+    /// A player's answer to a rematch offer (client -> host)
+    RematchVote { accept: bool },
     /// Round starting with these letters and duration
-    RoundStart { letters: Vec<char>, duration_secs: u32 },
-    /// Round has ended
-    RoundEnd,
+    ///
+    /// `min_letters_used` is the house rule's threshold for this rack (0 when
+    /// the rule is off). `reject_penalty` is the points a claim that misses
+    /// outright costs (0 when penalty mode is off). `language` is the
+    /// language the rack was dealt in and claims are checked against.
+    RoundStart {
+        letters: Vec<char>,
+        duration_secs: u32,
+        min_letters_used: u32,
+        reject_penalty: u32,
+        language: Language,
+    },
+    /// Round starting with the rack `config.rack` deals for `seed`
+    ///
+    /// Every peer deals the rack itself with `RackConfig::rack_for_seed`,
+    /// so the letters never cross the wire. The round is played in the
+    /// rack's language.
+    RoundStartSeeded {
+        seed: u64,
+        config: RoundConfig,
+        duration_secs: u32,
+    },
+    /// Round has ended, and why
+    RoundEnd { reason: RoundEndReason },
     /// Match completed event for CRDT log (host -> all)
     ///
     /// Contains final scores and match metadata for Elo calculations
@@ -119,15 +205,67 @@ pub enum Message {
     },
     /// Scoreboard update (host -> all)
     ScoreUpdate { scores: Vec<(String, u32)> },
+    /// Claims accepted so far this round (host -> player rejoining mid-round)
+    RoundClaims { claims: Vec<RoundClaim> },
+    /// Notice from the host shown to everyone (host -> all)
+    SystemAnnouncement { text: String },
+    /// Extra words the host's dictionary accepts (host -> joining player)
+    HouseWords { words: Vec<String> },
+    /// Handicaps for the round about to start, as percentages (150 for
+    /// 1.5x); players not listed have none (host -> all)
+    Handicaps { handicaps: Vec<(String, u32)> },
+    /// Set our short lobby status, empty to clear it (client -> host)
+    SetStatus { status: String },
+    /// A player's lobby status changed (host -> all)
+    PlayerStatus { player_name: String, status: String },
+    /// What we're typing right now, empty once cleared (client -> host)
+    ///
+    /// Only sent by players who opted in to sharing their typing.
+    InputPreview { text: String },
+    /// Ask for the round in progress: letters, time left, claims and scores
+    /// (client -> host, after reconnecting)
+    Resync,
     /// Ping to check connection
     Ping,
     /// Response to ping
     Pong,
     /// CRDT sync: Request missing events by sending our vector clock
     /// Each entry is (actor_id_hex, highest_seq_seen)
-    SyncRequest { vector_clock: Vec<(String, i64)> },
+    ///
+    /// `accepts_gzip` says the reply may come as `SyncEventsCompressed`.
+    /// Older builds leave it out, so they're answered with `SyncEvents`.
+    SyncRequest { vector_clock: Vec<(String, i64)>, accepts_gzip: bool },
     /// CRDT sync: Send events the peer is missing
     SyncEvents { events: Vec<SyncEvent> },
+    /// CRDT sync: `SyncEvents` with the events array gzipped and base64
+    /// encoded (see `decompress_sync_events`), for peers that asked with
+    /// `accepts_gzip`
+    SyncEventsCompressed { gzip_base64: String },
+}
+
+/// How a round is dealt and played, for peers that deal the rack
+/// themselves from a seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundConfig {
+    /// Rack size, vowels, blanks and language
+    pub rack: RackConfig,
+    /// The house rule's letters-used threshold (0 when the rule is off)
+    pub min_letters_used: u32,
+    /// Points a claim that misses outright costs (0 when penalty mode is off)
+    pub reject_penalty: u32,
+}
+
+/// A claim the host accepted, as replayed to a player who rejoins
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundClaim {
+    /// Player who made the claim
+    pub player_name: String,
+    /// Claimed word (uppercase)
+    pub word: String,
+    /// Points awarded
+    pub points: u32,
+    /// Sequence number the claim was accepted with
+    pub claim_sequence: u64,
 }
 
 /// An event for CRDT sync (matches storage::Event but with hex actor_id for JSON)
@@ -146,6 +284,24 @@ pub struct SyncEvent {
 }
 
 impl Message {
+    /// This message as it should go to a peer whose `SyncRequest` said
+    /// `accepts_gzip`
+    ///
+    /// A `SyncEvents` batch is compressed once its JSON reaches
+    /// `SYNC_GZIP_MIN_LEN`; anything else is sent as it is.
+    pub fn compress_sync_for(self, accepts_gzip: bool) -> Self {
+        match self {
+            Message::SyncEvents { events } if accepts_gzip => {
+                let json = sync_events_json(&events);
+                if json.len() < SYNC_GZIP_MIN_LEN {
+                    return Message::SyncEvents { events };
+                }
+                Message::SyncEventsCompressed { gzip_base64: gzip_base64(&json) }
+            }
+            other => other,
+        }
+    }
+
     /// Serialize message to bytes (length-prefixed JSON)
     pub fn to_bytes(&self) -> Vec<u8> {
         let json = self.to_json();
@@ -157,43 +313,60 @@ impl Message {
     }
 
     /// Deserialize message from bytes (length-prefixed JSON)
+    ///
+    /// A frame that can't be decoded fails with `InvalidData`, wrapping the
+    /// `FrameError` that says why.
     pub fn from_bytes(bytes: &[u8]) -> io::Result<(Self, usize)> {
         if bytes.len() < 4 {
             return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "need 4 bytes for length"));
         }
         let len = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        if len > MAX_FRAME_LEN {
+            return Err(FrameError::TooLarge { len }.into());
+        }
         if bytes.len() < 4 + len {
             return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "incomplete message"));
         }
-        let json = std::str::from_utf8(&bytes[4..4 + len])
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-        let msg = Self::from_json(json)?;
+        let msg = Self::from_body(&bytes[4..4 + len])?;
         Ok((msg, 4 + len))
     }
 
+    /// Decode a frame's body (everything after the length prefix)
+    fn from_body(body: &[u8]) -> Result<Self, FrameError> {
+        let json = std::str::from_utf8(body).map_err(|_| FrameError::InvalidUtf8)?;
+        Self::from_json(json).map_err(|e| FrameError::Malformed(e.to_string()))
+    }
+
     fn to_json(&self) -> String {
         match self {
             Message::Join { player_name } => {
                 format!(r#"{{"type":"join","player_name":"{}"}}"#, escape_json(player_name))
             }
+            Message::JoinSpectator { name } => {
+                format!(r#"{{"type":"join_spectator","name":"{}"}}"#, escape_json(name))
+            }
             Message::JoinRejected { reason } => {
                 let reason = match reason {
                     JoinRejectReason::LobbyFull => "lobby_full",
                 };
                 format!(r#"{{"type":"join_rejected","reason":"{}"}}"#, reason)
             }
+            Message::Kicked { reason } => {
+                format!(r#"{{"type":"kicked","reason":"{}"}}"#, escape_json(reason))
+            }
             Message::Leave { player_name } => {
                 format!(r#"{{"type":"leave","player_name":"{}"}}"#, escape_json(player_name))
             }
             Message::ClaimAttempt { word } => {
                 format!(r#"{{"type":"claim_attempt","word":"{}"}}"#, escape_json(word))
             }
-            Message::ClaimAccepted { word, player_name, points } => {
+            Message::ClaimAccepted { word, player_name, points, combo } => {
                 format!(
-                    r#"{{"type":"claim_accepted","word":"{}","player_name":"{}","points":{}}}"#,
+                    r#"{{"type":"claim_accepted","word":"{}","player_name":"{}","points":{},"combo":{}}}"#,
                     escape_json(word),
                     escape_json(player_name),
-                    points
+                    points,
+                    combo
                 )
             }
             Message::ClaimRejected { word, reason } => {
@@ -211,6 +384,12 @@ impl Message {
                     ClaimRejectReason::TooShort => {
                         r#"{"reason":"too_short"}"#.to_string()
                     }
+                    ClaimRejectReason::TooLong { max } => {
+                        format!(r#"{{"reason":"too_long","max":{}}}"#, max)
+                    }
+                    ClaimRejectReason::TooFewLetters { min } => {
+                        format!(r#"{{"reason":"too_few_letters","min":{}}}"#, min)
+                    }
                     ClaimRejectReason::RoundEnded => {
                         r#"{"reason":"round_ended"}"#.to_string()
                     }
@@ -221,15 +400,19 @@ impl Message {
                     reason_json
                 )
             }
-            Message::WordClaimed { word, player_name, points, actor_id, timestamp_ms, claim_sequence } => {
+            Message::WordClaimed { word, player_name, points, actor_id, timestamp_ms, claim_sequence, round_id } => {
+                let round_json = round_id
+                    .map(|r| format!(r#","round_id":{}"#, r))
+                    .unwrap_or_default();
                 format!(
-                    r#"{{"type":"word_claimed","word":"{}","player_name":"{}","points":{},"actor_id":"{}","timestamp_ms":{},"claim_sequence":{}}}"#,
+                    r#"{{"type":"word_claimed","word":"{}","player_name":"{}","points":{},"actor_id":"{}","timestamp_ms":{},"claim_sequence":{}{}}}"#,
                     escape_json(word),
                     escape_json(player_name),
                     points,
                     escape_json(actor_id),
                     timestamp_ms,
-                    claim_sequence
+                    claim_sequence,
+                    round_json
                 )
             }
             Message::Claim { player_name, word, points } => {
@@ -249,15 +432,44 @@ impl Message {
                     countdown_secs
                 )
             }
-            Message::RoundStart { letters, duration_secs } => {
+            Message::RoundStart { letters, duration_secs, min_letters_used, reject_penalty, language } => {
                 let letters_json: String = letters.iter().map(|c| format!(r#""{}""#, c)).collect::<Vec<_>>().join(",");
                 format!(
-                    r#"{{"type":"round_start","letters":[{}],"duration_secs":{}}}"#,
+                    r#"{{"type":"round_start","letters":[{}],"duration_secs":{},"min_letters_used":{},"reject_penalty":{},"language":"{}"}}"#,
                     letters_json,
-                    duration_secs
+                    duration_secs,
+                    min_letters_used,
+                    reject_penalty,
+                    language.code()
+                )
+            }
+            Message::CountdownSeeded { seed, rack, duration_secs, countdown_secs } => {
+                format!(
+                    r#"{{"type":"countdown_seeded","seed":{},"duration_secs":{},"countdown_secs":{},{}}}"#,
+                    seed,
+                    duration_secs,
+                    countdown_secs,
+                    rack_fields(rack)
+                )
+            }
+            Message::RoundStartSeeded { seed, config, duration_secs } => {
+                format!(
+                    r#"{{"type":"round_start_seeded","seed":{},"duration_secs":{},{},"min_letters_used":{},"reject_penalty":{}}}"#,
+                    seed,
+                    duration_secs,
+                    rack_fields(&config.rack),
+                    config.min_letters_used,
+                    config.reject_penalty
                 )
             }
-            Message::RoundEnd => r#"{"type":"round_end"}"#.to_string(),
+            Message::CountdownCancelled => r#"{"type":"countdown_cancelled"}"#.to_string(),
+            Message::RematchOffer => r#"{"type":"rematch_offer"}"#.to_string(),
+            Message::RematchVote { accept } => {
+                format!(r#"{{"type":"rematch_vote","accept":{}}}"#, accept)
+            }
+            Message::RoundEnd { reason } => {
+                format!(r#"{{"type":"round_end","reason":"{}"}}"#, reason.as_str())
+            }
             Message::MatchEnded { match_id, scores, host_actor_id, completed } => {
                 let scores_json: String = scores
                     .iter()
@@ -280,133 +492,94 @@ impl Message {
                     .join(",");
                 format!(r#"{{"type":"score_update","scores":[{}]}}"#, scores_json)
             }
+            Message::RoundClaims { claims } => {
+                let claims_json: String = claims
+                    .iter()
+                    .map(|c| {
+                        format!(
+                            r#"{{"player_name":"{}","word":"{}","points":{},"claim_sequence":{}}}"#,
+                            escape_json(&c.player_name),
+                            escape_json(&c.word),
+                            c.points,
+                            c.claim_sequence
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!(r#"{{"type":"round_claims","claims":[{}]}}"#, claims_json)
+            }
+            Message::SystemAnnouncement { text } => {
+                format!(r#"{{"type":"system_announcement","text":"{}"}}"#, escape_json(text))
+            }
+            Message::HouseWords { words } => {
+                let words_json: String = words
+                    .iter()
+                    .map(|w| format!(r#""{}""#, escape_json(w)))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!(r#"{{"type":"house_words","words":[{}]}}"#, words_json)
+            }
+            Message::Handicaps { handicaps } => {
+                let handicaps_json: String = handicaps
+                    .iter()
+                    .map(|(name, percent)| format!(r#"["{}",{}]"#, escape_json(name), percent))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!(r#"{{"type":"handicaps","handicaps":[{}]}}"#, handicaps_json)
+            }
+            Message::SetStatus { status } => {
+                format!(r#"{{"type":"set_status","status":"{}"}}"#, escape_json(status))
+            }
+            Message::InputPreview { text } => {
+                format!(r#"{{"type":"input_preview","text":"{}"}}"#, escape_json(text))
+            }
+            Message::PlayerStatus { player_name, status } => {
+                format!(
+                    r#"{{"type":"player_status","player_name":"{}","status":"{}"}}"#,
+                    escape_json(player_name),
+                    escape_json(status)
+                )
+            }
+            Message::Resync => r#"{"type":"resync"}"#.to_string(),
             Message::Ping => r#"{"type":"ping"}"#.to_string(),
             Message::Pong => r#"{"type":"pong"}"#.to_string(),
-            Message::SyncRequest { vector_clock } => {
+            Message::SyncRequest { vector_clock, accepts_gzip } => {
                 let clock_json: String = vector_clock
                     .iter()
                     .map(|(actor_id, seq)| format!(r#"["{}",{}]"#, escape_json(actor_id), seq))
                     .collect::<Vec<_>>()
                     .join(",");
-                format!(r#"{{"type":"sync_request","vector_clock":[{}]}}"#, clock_json)
+                format!(
+                    r#"{{"type":"sync_request","vector_clock":[{}],"accepts_gzip":{}}}"#,
+                    clock_json, accepts_gzip
+                )
             }
             Message::SyncEvents { events } => {
-                let events_json: String = events
-                    .iter()
-                    .map(|e| {
-                        format!(
-                            r#"{{"actor_id":"{}","seq":{},"event_type":"{}","payload":"{}","created_at":{}}}"#,
-                            escape_json(&e.actor_id),
-                            e.seq,
-                            escape_json(&e.event_type),
-                            escape_json(&e.payload),
-                            e.created_at
-                        )
-                    })
-                    .collect::<Vec<_>>()
-                    .join(",");
-                format!(r#"{{"type":"sync_events","events":[{}]}}"#, events_json)
+                format!(r#"{{"type":"sync_events","events":{}}}"#, sync_events_json(events))
+            }
+            Message::SyncEventsCompressed { gzip_base64 } => {
+                format!(
+                    r#"{{"type":"sync_events_compressed","gzip_base64":"{}"}}"#,
+                    escape_json(gzip_base64)
+                )
             }
         }
     }
 
     fn from_json(json: &str) -> io::Result<Self> {
-        // Simple JSON parsing without serde
-        let json = json.trim();
-
-        let get_str = |key: &str| -> Option<String> {
-            let pattern = format!(r#""{}":""#, key);
-            let start = json.find(&pattern)? + pattern.len();
-            let rest = &json[start..];
-            // Find the closing quote that isn't escaped
-            let end = find_unescaped_quote(rest)?;
-            Some(unescape_json(&rest[..end]))
-        };
-
-        let get_u32 = |key: &str| -> Option<u32> {
-            let pattern = format!(r#""{}":"#, key);
-            let start = json.find(&pattern)? + pattern.len();
-            let rest = &json[start..];
-            let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
-            rest[..end].parse().ok()
-        };
-
-        let get_u64 = |key: &str| -> Option<u64> {
-            let pattern = format!(r#""{}":"#, key);
-            let start = json.find(&pattern)? + pattern.len();
-            let rest = &json[start..];
-            let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
-            rest[..end].parse().ok()
-        };
-
-        let get_i64 = |key: &str| -> Option<i64> {
-            let pattern = format!(r#""{}":"#, key);
-            let start = json.find(&pattern)? + pattern.len();
-            let rest = &json[start..];
-            let end = rest.find(|c: char| !c.is_ascii_digit() && c != '-').unwrap_or(rest.len());
-            rest[..end].parse().ok()
-        };
+        let value = json::parse(json)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid JSON"))?;
 
-        let get_bool = |key: &str| -> Option<bool> {
-            let pattern = format!(r#""{}":"#, key);
-            let start = json.find(&pattern)? + pattern.len();
-            let rest = &json[start..].trim_start();
-            if rest.starts_with("true") {
-                Some(true)
-            } else if rest.starts_with("false") {
-                Some(false)
-            } else {
-                None
-            }
-        };
+        let get_str = |key: &str| value.str_field(key);
+        let get_u32 = |key: &str| value.number_field::<u32>(key);
+        let get_u64 = |key: &str| value.number_field::<u64>(key);
+        let get_i64 = |key: &str| value.number_field::<i64>(key);
+        let get_bool = |key: &str| value.bool_field(key);
 
-        let get_chars = |key: &str| -> Option<Vec<char>> {
-            let pattern = format!(r#""{}":["#, key);
-            let start = json.find(&pattern)? + pattern.len();
-            let rest = &json[start..];
-            let end = rest.find(']')?;
-            let array = &rest[..end];
-            Some(
-                array
-                    .split(',')
-                    .filter_map(|s| {
-                        let s = s.trim().trim_matches('"');
-                        s.chars().next()
-                    })
-                    .collect()
-            )
-        };
+        let get_chars = |key: &str| chars_field(&value, key);
 
         // Parse scores array [[name, score], ...]
-        let get_scores = || -> Option<Vec<(String, u32)>> {
-            let pattern = r#""scores":[["#;
-            let start = json.find(pattern)?;
-            let rest = &json[start + r#""scores":["#.len()..];
-            let end = rest.find("]]")?;
-            let array = &rest[..end + 1]; // Include last ]
-
-            let mut scores = Vec::new();
-            let mut current = array;
-            while let Some(start) = current.find('[') {
-                let rest = &current[start + 1..];
-                let end = rest.find(']')?;
-                let item = &rest[..end];
-
-                // Parse ["name", score]
-                let comma = item.find(',')?;
-                let name = item[..comma].trim().trim_matches('"');
-                let score_str = item[comma + 1..].trim();
-                let score: u32 = score_str.parse().ok()?;
-                scores.push((unescape_json(name), score));
-
-                if end + 1 < rest.len() {
-                    current = &rest[end + 1..];
-                } else {
-                    break;
-                }
-            }
-            Some(scores)
-        };
+        let get_scores = || -> Option<Vec<(String, u32)>> { parse_pairs(&value, "scores") };
 
         // Get type field
         let msg_type = get_str("type")
@@ -418,6 +591,11 @@ impl Message {
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing player_name"))?;
                 Ok(Message::Join { player_name })
             }
+            "join_spectator" => {
+                let name = get_str("name")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing name"))?;
+                Ok(Message::JoinSpectator { name })
+            }
             "join_rejected" => {
                 let reason = get_str("reason")
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing reason"))?;
@@ -435,6 +613,11 @@ impl Message {
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing player_name"))?;
                 Ok(Message::Leave { player_name })
             }
+            "kicked" => {
+                let reason = get_str("reason")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing reason"))?;
+                Ok(Message::Kicked { reason })
+            }
             "claim_attempt" => {
                 let word = get_str("word")
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing word"))?;
@@ -447,28 +630,46 @@ impl Message {
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing player_name"))?;
                 let points = get_u32("points")
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing points"))?;
-                Ok(Message::ClaimAccepted { word, player_name, points })
+                let combo = get_u32("combo").unwrap_or(1);
+                Ok(Message::ClaimAccepted { word, player_name, points, combo })
             }
             "claim_rejected" => {
                 let word = get_str("word")
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing word"))?;
 
                 // Parse the reason from reason_data
-                let reason_str = get_str("reason")
+                let reason_data = value
+                    .get("reason_data")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing reason_data"))?;
+                let reason_str = reason_data
+                    .str_field("reason")
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing reason"))?;
 
                 let reason = match reason_str.as_str() {
                     "already_claimed" => {
-                        let by = get_str("by")
+                        let by = reason_data
+                            .str_field("by")
                             .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing by"))?;
                         ClaimRejectReason::AlreadyClaimed { by }
                     }
                     "not_in_dictionary" => ClaimRejectReason::NotInDictionary,
                     "invalid_letters" => {
-                        let missing = get_chars("missing").unwrap_or_default();
+                        let missing = chars_field(reason_data, "missing").unwrap_or_default();
                         ClaimRejectReason::InvalidLetters { missing }
                     }
                     "too_short" => ClaimRejectReason::TooShort,
+                    "too_long" => {
+                        let max = reason_data
+                            .number_field("max")
+                            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing max"))?;
+                        ClaimRejectReason::TooLong { max }
+                    }
+                    "too_few_letters" => {
+                        let min = reason_data
+                            .number_field("min")
+                            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing min"))?;
+                        ClaimRejectReason::TooFewLetters { min }
+                    }
                     "round_ended" => ClaimRejectReason::RoundEnded,
                     _ => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown reason: {}", reason_str))),
                 };
@@ -488,6 +689,7 @@ impl Message {
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing timestamp_ms"))?;
                 let claim_sequence = get_u64("claim_sequence")
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing claim_sequence"))?;
+                let round_id = get_i64("round_id");
                 Ok(Message::WordClaimed {
                     word,
                     player_name,
@@ -495,6 +697,7 @@ impl Message {
                     actor_id,
                     timestamp_ms,
                     claim_sequence,
+                    round_id,
                 })
             }
             "claim" => {
@@ -520,9 +723,56 @@ impl Message {
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing letters"))?;
                 let duration_secs = get_u32("duration_secs")
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing duration_secs"))?;
-                Ok(Message::RoundStart { letters, duration_secs })
+                // Hosts that predate these rules never set them
+                let min_letters_used = get_u32("min_letters_used").unwrap_or(0);
+                let reject_penalty = get_u32("reject_penalty").unwrap_or(0);
+                let language = get_str("language")
+                    .and_then(|code| Language::from_code(&code))
+                    .unwrap_or_default();
+                Ok(Message::RoundStart { letters, duration_secs, min_letters_used, reject_penalty, language })
+            }
+            "round_start_seeded" => {
+                let seed = get_u64("seed")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing seed"))?;
+                let duration_secs = get_u32("duration_secs")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing duration_secs"))?;
+                let config = RoundConfig {
+                    rack: rack_field(&value)?,
+                    min_letters_used: get_u32("min_letters_used").unwrap_or(0),
+                    reject_penalty: get_u32("reject_penalty").unwrap_or(0),
+                };
+                Ok(Message::RoundStartSeeded { seed, config, duration_secs })
+            }
+            "countdown_seeded" => {
+                let seed = get_u64("seed")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing seed"))?;
+                let duration_secs = get_u32("duration_secs")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing duration_secs"))?;
+                let countdown_secs = get_u32("countdown_secs")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing countdown_secs"))?;
+                let rack = rack_field(&value)?;
+                Ok(Message::CountdownSeeded { seed, rack, duration_secs, countdown_secs })
+            }
+            "countdown_cancelled" => Ok(Message::CountdownCancelled),
+            "rematch_offer" => Ok(Message::RematchOffer),
+            "rematch_vote" => {
+                let accept = get_bool("accept")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing accept"))?;
+                Ok(Message::RematchVote { accept })
+            }
+            "round_end" => {
+                // Hosts that predate end reasons only ended rounds on the timer
+                let reason = match get_str("reason").as_deref() {
+                    None | Some("time_expired") => RoundEndReason::TimeExpired,
+                    Some("host_ended") => RoundEndReason::HostEnded,
+                    Some("target_reached") => RoundEndReason::TargetReached,
+                    Some(other) => return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("unknown reason: {}", other),
+                    )),
+                };
+                Ok(Message::RoundEnd { reason })
             }
-            "round_end" => Ok(Message::RoundEnd),
             "match_ended" => {
                 let match_id = get_i64("match_id")
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing match_id"))?;
@@ -543,18 +793,72 @@ impl Message {
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing or invalid scores"))?;
                 Ok(Message::ScoreUpdate { scores })
             }
+            "round_claims" => {
+                let claims = parse_round_claims(&value)
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing or invalid claims"))?;
+                Ok(Message::RoundClaims { claims })
+            }
+            "system_announcement" => {
+                let text = get_str("text")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing text"))?;
+                Ok(Message::SystemAnnouncement { text })
+            }
+            "house_words" => {
+                let words = value
+                    .get("words")
+                    .and_then(json::Value::as_array)
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing words"))?
+                    .iter()
+                    .map(|w| w.as_str().map(str::to_string))
+                    .collect::<Option<Vec<String>>>()
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid words"))?;
+                let words = words.into_iter().filter(|w| !w.is_empty()).collect();
+                Ok(Message::HouseWords { words })
+            }
+            "handicaps" => {
+                let handicaps = parse_pairs(&value, "handicaps")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing or invalid handicaps"))?;
+                Ok(Message::Handicaps { handicaps })
+            }
+            "set_status" => {
+                let status = get_str("status")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing status"))?;
+                Ok(Message::SetStatus { status })
+            }
+            "input_preview" => {
+                let text = get_str("text")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing text"))?;
+                Ok(Message::InputPreview { text })
+            }
+            "player_status" => {
+                let player_name = get_str("player_name")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing player_name"))?;
+                let status = get_str("status")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing status"))?;
+                Ok(Message::PlayerStatus { player_name, status })
+            }
+            "resync" => Ok(Message::Resync),
             "ping" => Ok(Message::Ping),
             "pong" => Ok(Message::Pong),
             "sync_request" => {
-                let vector_clock = parse_vector_clock(json)
+                let vector_clock = parse_vector_clock(&value)
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid vector_clock"))?;
-                Ok(Message::SyncRequest { vector_clock })
+                // Older builds don't send it, and can't read compressed batches
+                let accepts_gzip = get_bool("accepts_gzip").unwrap_or(false);
+                Ok(Message::SyncRequest { vector_clock, accepts_gzip })
             }
             "sync_events" => {
-                let events = parse_sync_events(json)
+                let events = value
+                    .get("events")
+                    .and_then(parse_sync_events)
                     .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid events"))?;
                 Ok(Message::SyncEvents { events })
             }
+            "sync_events_compressed" => {
+                let gzip_base64 = get_str("gzip_base64")
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing gzip_base64"))?;
+                Ok(Message::SyncEventsCompressed { gzip_base64 })
+            }
             _ => Err(io::Error::new(io::ErrorKind::InvalidData, format!("unknown message type: {}", msg_type))),
         }
     }
@@ -567,39 +871,132 @@ impl Message {
     }
 
     /// Read message from a TCP stream
-    pub fn read_from(stream: &mut TcpStream) -> io::Result<Self> {
+    ///
+    /// Only safe on blocking streams without a read timeout: a timeout partway
+    /// through a frame discards the bytes already read. Use `MessageFramer`
+    /// for streams that can time out.
+    ///
+    /// A bad frame is read to the end before its `FrameError` is returned,
+    /// so the next call starts on the following frame.
+    pub fn read_from<R: Read>(stream: &mut R) -> io::Result<Self> {
         let mut len_buf = [0u8; 4];
         stream.read_exact(&mut len_buf)?;
         let len = u32::from_be_bytes(len_buf) as usize;
 
-        if len > 1024 * 1024 {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "message too large"));
+        if len > MAX_FRAME_LEN {
+            io::copy(&mut stream.take(len as u64), &mut io::sink())?;
+            return Err(FrameError::TooLarge { len }.into());
         }
 
         let mut body = vec![0u8; len];
         stream.read_exact(&mut body)?;
+        Ok(Self::from_body(&body)?)
+    }
+}
+
+/// Largest message body accepted from the network
+pub const MAX_FRAME_LEN: usize = 1024 * 1024;
+
+/// Why a frame couldn't be decoded into a message
+///
+/// Framing survives all of these: the frame is skipped and the connection
+/// carries on with the next one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameError {
+    /// The length prefix is over `MAX_FRAME_LEN`
+    TooLarge { len: usize },
+    /// The body isn't UTF-8
+    InvalidUtf8,
+    /// The body isn't a message we understand
+    Malformed(String),
+}
 
-        let json = std::str::from_utf8(&body)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-        Self::from_json(json)
+impl std::fmt::Display for FrameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameError::TooLarge { len } => {
+                write!(f, "frame of {} bytes is over the {} byte limit", len, MAX_FRAME_LEN)
+            }
+            FrameError::InvalidUtf8 => write!(f, "frame is not valid UTF-8"),
+            FrameError::Malformed(reason) => write!(f, "malformed message: {}", reason),
+        }
     }
 }
 
-/// Find the position of the first unescaped quote in a string
-fn find_unescaped_quote(s: &str) -> Option<usize> {
-    let mut i = 0;
-    let bytes = s.as_bytes();
-    while i < bytes.len() {
-        if bytes[i] == b'"' {
-            return Some(i);
-        } else if bytes[i] == b'\\' && i + 1 < bytes.len() {
-            // Skip escaped character
-            i += 2;
-        } else {
-            i += 1;
+impl std::error::Error for FrameError {}
+
+impl From<FrameError> for io::Error {
+    fn from(e: FrameError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+/// Accumulates bytes from a connection and yields complete messages
+///
+/// A read that times out or would block keeps whatever arrived so far, so a
+/// message split across TCP segments is decoded once the rest shows up
+/// instead of being lost.
+#[derive(Debug, Default)]
+pub struct MessageFramer {
+    /// Bytes received but not yet decoded
+    buf: Vec<u8>,
+    /// Bytes of an oversized frame still to be thrown away as they arrive
+    skipping: usize,
+}
+
+impl MessageFramer {
+    /// Create an empty framer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append received bytes
+    pub fn feed(&mut self, bytes: &[u8]) {
+        let skipped = self.skipping.min(bytes.len());
+        self.skipping -= skipped;
+        self.buf.extend_from_slice(&bytes[skipped..]);
+    }
+
+    /// Read whatever the stream has available into the buffer
+    ///
+    /// Returns the number of bytes read; 0 means the peer closed the
+    /// connection. Timeouts are returned as errors with nothing lost.
+    pub fn read_from<R: Read>(&mut self, reader: &mut R) -> io::Result<usize> {
+        let mut chunk = [0u8; 4096];
+        let n = reader.read(&mut chunk)?;
+        self.feed(&chunk[..n]);
+        Ok(n)
+    }
+
+    /// Decode the next complete message, if one has fully arrived
+    ///
+    /// Returns the message with the number of bytes its frame took up. A
+    /// frame that can't be decoded is dropped and its error returned; call
+    /// again for the messages after it.
+    pub fn next_message(&mut self) -> Result<Option<(Message, usize)>, FrameError> {
+        if self.buf.len() < 4 {
+            return Ok(None);
+        }
+        let len = u32::from_be_bytes([self.buf[0], self.buf[1], self.buf[2], self.buf[3]]) as usize;
+        if len > MAX_FRAME_LEN {
+            // Drop what's here of it, and the rest once it arrives
+            let dropped = self.buf.len().min(4 + len);
+            self.buf.drain(..dropped);
+            self.skipping = 4 + len - dropped;
+            return Err(FrameError::TooLarge { len });
         }
+        if self.buf.len() < 4 + len {
+            return Ok(None);
+        }
+        let frame: Vec<u8> = self.buf.drain(..4 + len).collect();
+        let msg = Message::from_body(&frame[4..])?;
+        Ok(Some((msg, frame.len())))
+    }
+
+    /// Number of buffered bytes not yet decoded
+    pub fn pending(&self) -> usize {
+        self.buf.len()
     }
-    None
 }
 
 fn escape_json(s: &str) -> String {
@@ -611,188 +1008,166 @@ fn escape_json(s: &str) -> String {
 }
 
 /// Parse vector clock from JSON: [["actor_hex", seq], ...]
-fn parse_vector_clock(json: &str) -> Option<Vec<(String, i64)>> {
-    let pattern = r#""vector_clock":["#;
-    let start = json.find(pattern)? + pattern.len();
-    let rest = &json[start..];
-
-    // Find matching close bracket
-    let mut depth = 1;
-    let mut end = 0;
-    for (i, c) in rest.chars().enumerate() {
-        match c {
-            '[' => depth += 1,
-            ']' => {
-                depth -= 1;
-                if depth == 0 {
-                    end = i;
-                    break;
-                }
-            }
-            _ => {}
-        }
-    }
+fn parse_vector_clock(value: &json::Value) -> Option<Vec<(String, i64)>> {
+    parse_pairs(value, "vector_clock")
+}
 
-    let array = &rest[..end];
-    if array.is_empty() {
-        return Some(Vec::new());
-    }
+/// Parse an array of `["text", number]` pairs under `key`
+fn parse_pairs<T: std::str::FromStr>(value: &json::Value, key: &str) -> Option<Vec<(String, T)>> {
+    value.get(key)?.as_array()?.iter().map(json::Value::as_pair).collect()
+}
 
-    let mut result = Vec::new();
-    let mut current = array;
+/// Serialize sync events to JSON: [{actor_id, seq, event_type, payload, created_at}, ...]
+fn sync_events_json(events: &[SyncEvent]) -> String {
+    let events_json: Vec<String> = events
+        .iter()
+        .map(|e| {
+            format!(
+                r#"{{"actor_id":"{}","seq":{},"event_type":"{}","payload":"{}","created_at":{}}}"#,
+                escape_json(&e.actor_id),
+                e.seq,
+                escape_json(&e.event_type),
+                escape_json(&e.payload),
+                e.created_at
+            )
+        })
+        .collect();
+    format!("[{}]", events_json.join(","))
+}
 
-    while let Some(start) = current.find('[') {
-        let rest = &current[start + 1..];
-        let end = rest.find(']')?;
-        let item = &rest[..end];
+/// Parse sync events from JSON: [{actor_id, seq, event_type, payload, created_at}, ...]
+fn parse_sync_events(value: &json::Value) -> Option<Vec<SyncEvent>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|obj| {
+            Some(SyncEvent {
+                actor_id: obj.str_field("actor_id")?,
+                seq: obj.number_field("seq")?,
+                event_type: obj.str_field("event_type")?,
+                payload: obj.str_field("payload")?,
+                created_at: obj.number_field("created_at")?,
+            })
+        })
+        .collect()
+}
 
-        // Parse ["actor_hex", seq]
-        let comma = item.find(',')?;
-        let actor_id = item[..comma].trim().trim_matches('"');
-        let seq_str = item[comma + 1..].trim();
-        let seq: i64 = seq_str.parse().ok()?;
-        result.push((unescape_json(actor_id), seq));
+/// Smallest events JSON worth compressing; shorter batches barely shrink
+pub const SYNC_GZIP_MIN_LEN: usize = 4 * 1024;
 
-        if end + 1 < rest.len() {
-            current = &rest[end + 1..];
-        } else {
-            break;
-        }
-    }
+/// Most a compressed batch may inflate to, so a small frame can't make us
+/// allocate without bound
+pub const MAX_SYNC_BATCH_LEN: usize = 32 * MAX_FRAME_LEN;
 
-    Some(result)
+/// Gzip and base64-encode a batch of sync events, as sent in
+/// `SyncEventsCompressed`
+pub fn compress_sync_events(events: &[SyncEvent]) -> String {
+    gzip_base64(&sync_events_json(events))
 }
 
-/// Parse sync events from JSON: [{actor_id, seq, event_type, payload, created_at}, ...]
-fn parse_sync_events(json: &str) -> Option<Vec<SyncEvent>> {
-    let pattern = r#""events":["#;
-    let start = json.find(pattern)? + pattern.len();
-    let rest = &json[start..];
-
-    // Find matching close bracket, respecting string boundaries
-    let mut depth = 1;
-    let mut end = 0;
-    let mut in_string = false;
-    let mut prev_char = ' ';
-    for (i, c) in rest.chars().enumerate() {
-        if c == '"' && prev_char != '\\' {
-            in_string = !in_string;
-        } else if !in_string {
-            match c {
-                '[' | '{' => depth += 1,
-                ']' | '}' => {
-                    depth -= 1;
-                    if depth == 0 {
-                        end = i;
-                        break;
-                    }
-                }
-                _ => {}
-            }
-        }
-        prev_char = c;
-    }
-
-    let array = &rest[..end];
-    if array.is_empty() {
-        return Some(Vec::new());
-    }
-
-    let mut result = Vec::new();
-    let mut current = array;
-
-    while let Some(obj_start) = current.find('{') {
-        let rest = &current[obj_start + 1..];
-        // Find matching close brace, respecting strings
-        let mut depth = 1;
-        let mut obj_end = 0;
-        let mut in_string = false;
-        let mut prev_char = ' ';
-        for (i, c) in rest.chars().enumerate() {
-            if c == '"' && prev_char != '\\' {
-                in_string = !in_string;
-            } else if !in_string {
-                match c {
-                    '{' => depth += 1,
-                    '}' => {
-                        depth -= 1;
-                        if depth == 0 {
-                            obj_end = i;
-                            break;
-                        }
-                    }
-                    _ => {}
-                }
-            }
-            prev_char = c;
-        }
-        let obj = &rest[..obj_end];
-
-        // Parse object fields
-        let get_str = |key: &str| -> Option<String> {
-            let pattern = format!(r#""{}":""#, key);
-            let s = obj.find(&pattern)? + pattern.len();
-            let r = &obj[s..];
-            let e = find_unescaped_quote(r)?;
-            Some(unescape_json(&r[..e]))
-        };
+/// The events in a `SyncEventsCompressed` batch
+///
+/// Fails with `InvalidData` if the batch isn't base64, isn't gzip, inflates
+/// past `MAX_SYNC_BATCH_LEN` or isn't an events array.
+pub fn decompress_sync_events(gzip_base64: &str) -> io::Result<Vec<SyncEvent>> {
+    use base64::Engine;
+
+    let invalid = |reason: &str| io::Error::new(io::ErrorKind::InvalidData, reason.to_string());
+    let gzipped = base64::engine::general_purpose::STANDARD
+        .decode(gzip_base64)
+        .map_err(|_| invalid("invalid base64"))?;
+    let json = gunzip(&gzipped, MAX_SYNC_BATCH_LEN)?;
+    json::parse(&json)
+        .as_ref()
+        .and_then(parse_sync_events)
+        .ok_or_else(|| invalid("invalid events"))
+}
 
-        let get_i64 = |key: &str| -> Option<i64> {
-            let pattern = format!(r#""{}":"#, key);
-            let s = obj.find(&pattern)? + pattern.len();
-            let r = &obj[s..];
-            let e = r.find(|c: char| !c.is_ascii_digit() && c != '-').unwrap_or(r.len());
-            r[..e].parse().ok()
-        };
+fn gzip_base64(text: &str) -> String {
+    use base64::Engine;
+    use flate2::{write::GzEncoder, Compression};
 
-        let event = SyncEvent {
-            actor_id: get_str("actor_id")?,
-            seq: get_i64("seq")?,
-            event_type: get_str("event_type")?,
-            payload: get_str("payload")?,
-            created_at: get_i64("created_at")?,
-        };
-        result.push(event);
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    // Writing into a Vec can't fail
+    encoder.write_all(text.as_bytes()).expect("gzip into memory");
+    let gzipped = encoder.finish().expect("gzip into memory");
+    base64::engine::general_purpose::STANDARD.encode(gzipped)
+}
 
-        if obj_end + 1 < rest.len() {
-            current = &rest[obj_end + 1..];
-        } else {
-            break;
-        }
+/// Inflate gzipped UTF-8 text, failing if it's longer than `limit` bytes
+fn gunzip(gzipped: &[u8], limit: usize) -> io::Result<String> {
+    let mut text = String::new();
+    flate2::read::GzDecoder::new(gzipped)
+        .take(limit as u64 + 1)
+        .read_to_string(&mut text)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if text.len() > limit {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "batch too large"));
     }
+    Ok(text)
+}
 
-    Some(result)
+/// Parse round claims from JSON: [{player_name, word, points, claim_sequence}, ...]
+fn parse_round_claims(value: &json::Value) -> Option<Vec<RoundClaim>> {
+    value
+        .get("claims")?
+        .as_array()?
+        .iter()
+        .map(|obj| {
+            Some(RoundClaim {
+                player_name: obj.str_field("player_name")?,
+                word: obj.str_field("word")?,
+                points: obj.number_field("points")?,
+                claim_sequence: obj.number_field("claim_sequence")?,
+            })
+        })
+        .collect()
 }
 
-fn unescape_json(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    let mut chars = s.chars().peekable();
-
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            match chars.next() {
-                Some('"') => result.push('"'),
-                Some('\\') => result.push('\\'),
-                Some('n') => result.push('\n'),
-                Some('r') => result.push('\r'),
-                Some('t') => result.push('\t'),
-                Some(other) => {
-                    result.push('\\');
-                    result.push(other);
-                }
-                None => result.push('\\'),
-            }
-        } else {
-            result.push(c);
-        }
-    }
+/// A list of one-letter strings under `key` (empty strings are skipped)
+fn chars_field(value: &json::Value, key: &str) -> Option<Vec<char>> {
+    let items = value.get(key)?.as_array()?;
+    Some(items.iter().filter_map(|item| item.as_str()?.chars().next()).collect())
+}
+
+/// `rack` as the JSON fields seeded messages carry it in
+fn rack_fields(rack: &RackConfig) -> String {
+    format!(
+        r#""rack_min":{},"rack_max":{},"min_vowels":{},"blanks":{},"language":"{}""#,
+        rack.min_size(),
+        rack.max_size(),
+        rack.min_vowels(),
+        rack.blanks(),
+        rack.language().code()
+    )
+}
 
-    result
+/// The rack config a seeded message was sent with
+///
+/// Every field is required: without the exact config the seed would deal
+/// another rack.
+fn rack_field(value: &json::Value) -> io::Result<RackConfig> {
+    let size = |key: &str| {
+        value.number_field::<u32>(key).map(|n| n as usize).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("missing {}", key))
+        })
+    };
+    let language = value
+        .str_field("language")
+        .and_then(|code| Language::from_code(&code))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing language"))?;
+    Ok(RackConfig::new(size("rack_min")?, size("rack_max")?, size("min_vowels")?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        .with_blanks(size("blanks")?)
+        .with_language(language))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::RackPreset;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
     #[test]
     fn test_join_roundtrip() {
@@ -830,13 +1205,51 @@ mod tests {
     #[test]
     fn test_round_start_roundtrip() {
         let msg = Message::RoundStart {
-            letters: vec!['B', 'L', 'A', 'M'],
+            letters: vec!['A', 'Ñ', 'O'],
             duration_secs: 60,
+            min_letters_used: 3,
+            reject_penalty: 2,
+            language: Language::Spanish,
         };
         let bytes = msg.to_bytes();
         let (parsed, len) = Message::from_bytes(&bytes).unwrap();
         assert_eq!(parsed, msg);
         assert_eq!(len, bytes.len());
+
+        // Older hosts don't send the rules at all
+        let legacy = Message::from_json(r#"{"type":"round_start","letters":["A"],"duration_secs":60}"#);
+        assert!(matches!(
+            legacy,
+            Ok(Message::RoundStart {
+                min_letters_used: 0,
+                reject_penalty: 0,
+                language: Language::English,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_round_start_seeded_roundtrip() {
+        let msg = Message::RoundStartSeeded {
+            seed: u64::MAX,
+            config: RoundConfig {
+                rack: RackPreset::Big.config().with_blanks(1).with_language(Language::Spanish),
+                min_letters_used: 4,
+                reject_penalty: 1,
+            },
+            duration_secs: 90,
+        };
+        let bytes = msg.to_bytes();
+        let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, msg);
+        assert_eq!(len, bytes.len());
+
+        // A rack the config can't describe is refused rather than misdealt
+        let bad = r#"{"type":"round_start_seeded","seed":1,"duration_secs":60,"rack_min":9,"rack_max":3,"min_vowels":2,"blanks":0,"language":"en"}"#;
+        assert!(Message::from_json(bad).is_err());
+        let no_language = r#"{"type":"round_start_seeded","seed":1,"duration_secs":60,"rack_min":12,"rack_max":20,"min_vowels":2,"blanks":0}"#;
+        assert!(Message::from_json(no_language).is_err());
     }
 
     #[test]
@@ -874,11 +1287,16 @@ mod tests {
             word: "BLAM".to_string(),
             player_name: "Alice".to_string(),
             points: 4,
+            combo: 2,
         };
         let bytes = msg.to_bytes();
         let (parsed, len) = Message::from_bytes(&bytes).unwrap();
         assert_eq!(parsed, msg);
         assert_eq!(len, bytes.len());
+
+        // Hosts from before combos were sent applied none
+        let old = r#"{"type":"claim_accepted","word":"BLAM","player_name":"Alice","points":4}"#;
+        assert!(matches!(Message::from_json(old), Ok(Message::ClaimAccepted { combo: 1, .. })));
     }
 
     #[test]
@@ -902,11 +1320,26 @@ mod tests {
             actor_id: "blam-12345678".to_string(),
             timestamp_ms: 1704067200000,
             claim_sequence: 42,
+            round_id: Some(1704067140000),
         };
         let bytes = msg.to_bytes();
         let (parsed, len) = Message::from_bytes(&bytes).unwrap();
         assert_eq!(parsed, msg);
         assert_eq!(len, bytes.len());
+
+        // Hosts from before round IDs leave it out
+        let untagged = Message::WordClaimed {
+            word: "BLAM".to_string(),
+            player_name: "Alice".to_string(),
+            points: 4,
+            actor_id: "blam-12345678".to_string(),
+            timestamp_ms: 1704067200000,
+            claim_sequence: 42,
+            round_id: None,
+        };
+        assert!(!untagged.to_json().contains("round_id"));
+        let (parsed, _) = Message::from_bytes(&untagged.to_bytes()).unwrap();
+        assert_eq!(parsed, untagged);
     }
 
     #[test]
@@ -966,6 +1399,59 @@ mod tests {
         assert_eq!(len, bytes.len());
     }
 
+    #[test]
+    fn test_house_words_roundtrip() {
+        for words in [vec![], vec!["blamzorp".to_string(), "yeet".to_string()]] {
+            let msg = Message::HouseWords { words };
+            let bytes = msg.to_bytes();
+            let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+            assert_eq!(parsed, msg);
+            assert_eq!(len, bytes.len());
+        }
+    }
+
+    #[test]
+    fn test_handicaps_roundtrip() {
+        for handicaps in [vec![], vec![("Alice".to_string(), 150), ("Bob".to_string(), 75)]] {
+            let msg = Message::Handicaps { handicaps };
+            let bytes = msg.to_bytes();
+            let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+            assert_eq!(parsed, msg);
+            assert_eq!(len, bytes.len());
+        }
+        assert!(Message::from_json(r#"{"type":"handicaps","handicaps":[["Alice",1.5]]}"#).is_err());
+    }
+
+    #[test]
+    fn test_status_roundtrip() {
+        for msg in [
+            Message::SetStatus { status: "brb \"snacks\" 🍕".to_string() },
+            Message::SetStatus { status: String::new() },
+            Message::InputPreview { text: "BLAM".to_string() },
+            Message::InputPreview { text: String::new() },
+            Message::PlayerStatus {
+                player_name: "Alice".to_string(),
+                status: "ready!".to_string(),
+            },
+        ] {
+            let bytes = msg.to_bytes();
+            let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+            assert_eq!(parsed, msg);
+            assert_eq!(len, bytes.len());
+        }
+    }
+
+    #[test]
+    fn test_system_announcement_roundtrip() {
+        let msg = Message::SystemAnnouncement {
+            text: "Final round! \"Good luck\"".to_string(),
+        };
+        let bytes = msg.to_bytes();
+        let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, msg);
+        assert_eq!(len, bytes.len());
+    }
+
     #[test]
     fn test_countdown_roundtrip() {
         let msg = Message::Countdown {
@@ -979,6 +1465,24 @@ mod tests {
         assert_eq!(len, bytes.len());
     }
 
+    #[test]
+    fn test_countdown_seeded_roundtrip() {
+        let msg = Message::CountdownSeeded {
+            seed: 42,
+            rack: RackPreset::Blitz.config().with_language(Language::Spanish),
+            duration_secs: 60,
+            countdown_secs: 2,
+        };
+        let bytes = msg.to_bytes();
+        let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, msg);
+        assert_eq!(len, bytes.len());
+        assert!(!msg.to_json().contains("letters"));
+
+        let no_rack = r#"{"type":"countdown_seeded","seed":1,"duration_secs":60,"countdown_secs":3}"#;
+        assert!(Message::from_json(no_rack).is_err());
+    }
+
     #[test]
     fn test_sync_request_roundtrip() {
         let msg = Message::SyncRequest {
@@ -986,6 +1490,7 @@ mod tests {
                 ("0123456789abcdef0123456789abcdef".to_string(), 5),
                 ("fedcba9876543210fedcba9876543210".to_string(), 10),
             ],
+            accepts_gzip: true,
         };
         let bytes = msg.to_bytes();
         let (parsed, len) = Message::from_bytes(&bytes).unwrap();
@@ -995,7 +1500,7 @@ mod tests {
 
     #[test]
     fn test_sync_request_empty() {
-        let msg = Message::SyncRequest { vector_clock: vec![] };
+        let msg = Message::SyncRequest { vector_clock: vec![], accepts_gzip: false };
         let bytes = msg.to_bytes();
         let (parsed, len) = Message::from_bytes(&bytes).unwrap();
         assert_eq!(parsed, msg);
@@ -1037,6 +1542,113 @@ mod tests {
         assert_eq!(len, bytes.len());
     }
 
+    /// A batch like one sent after a long time offline
+    fn large_sync_batch(count: i64) -> Vec<SyncEvent> {
+        (1..=count)
+            .map(|seq| SyncEvent {
+                actor_id: "0123456789abcdef0123456789abcdef".to_string(),
+                seq,
+                event_type: "word_claimed".to_string(),
+                payload: format!(
+                    r#"{{"word":"BLAM{}","player":"Alice \"ace\"","points":{},"round_id":{}}}"#,
+                    seq % 97,
+                    seq % 13,
+                    seq / 40
+                ),
+                created_at: 1700000000000 + seq * 1500,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compressed_sync_batch_roundtrip_and_savings() {
+        let events = large_sync_batch(6000);
+        let plain = Message::SyncEvents { events: events.clone() };
+        let compressed = plain.clone().compress_sync_for(true);
+        let Message::SyncEventsCompressed { gzip_base64 } = &compressed else {
+            panic!("expected a compressed batch, got {:?}", compressed);
+        };
+        assert_eq!(decompress_sync_events(gzip_base64).unwrap(), events);
+
+        // Still a frame, even though the plain batch is too big for one
+        let plain_len = plain.to_json().len();
+        let bytes = compressed.to_bytes();
+        assert!(plain_len > MAX_FRAME_LEN);
+        assert!(bytes.len() <= MAX_FRAME_LEN);
+        assert!(
+            bytes.len() * 5 < plain_len,
+            "compressed to {} of {} bytes",
+            bytes.len(),
+            plain_len
+        );
+        let (parsed, _) = Message::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, compressed);
+    }
+
+    #[test]
+    fn test_sync_batches_stay_plain_unless_accepted_and_worth_it() {
+        let large = Message::SyncEvents { events: large_sync_batch(200) };
+        assert_eq!(large.clone().compress_sync_for(false), large);
+        let small = Message::SyncEvents { events: large_sync_batch(2) };
+        assert_eq!(small.clone().compress_sync_for(true), small);
+        assert_eq!(Message::Ping.compress_sync_for(true), Message::Ping);
+    }
+
+    #[test]
+    fn test_sync_request_from_older_build_does_not_accept_gzip() {
+        let body =
+            br#"{"type":"sync_request","vector_clock":[["0123456789abcdef0123456789abcdef",5]]}"#;
+        let mut bytes = (body.len() as u32).to_be_bytes().to_vec();
+        bytes.extend_from_slice(body);
+        let (parsed, _) = Message::from_bytes(&bytes).unwrap();
+        assert!(matches!(parsed, Message::SyncRequest { accepts_gzip: false, .. }));
+    }
+
+    #[test]
+    fn test_bad_compressed_batches_rejected() {
+        use base64::Engine;
+        let encode = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+
+        assert!(decompress_sync_events("not base64!").is_err());
+        assert!(decompress_sync_events(&encode(b"not gzip")).is_err());
+        // Gzipped, but not an events array
+        assert!(decompress_sync_events(&gzip_base64(r#"{"events":[]}"#)).is_err());
+        assert_eq!(decompress_sync_events(&gzip_base64("[]")).unwrap(), vec![]);
+
+        // Inflating past the limit
+        let b64 = gzip_base64(&" ".repeat(1000));
+        let gzipped = base64::engine::general_purpose::STANDARD.decode(b64).unwrap();
+        assert!(gunzip(&gzipped, 1000).is_ok());
+        assert!(gunzip(&gzipped, 999).is_err());
+    }
+
+    #[test]
+    fn test_round_claims_roundtrip() {
+        let msg = Message::RoundClaims {
+            claims: vec![
+                RoundClaim {
+                    player_name: "Alice {\"the\"} [great]".to_string(),
+                    word: "CAT".to_string(),
+                    points: 3,
+                    claim_sequence: 1,
+                },
+                RoundClaim {
+                    player_name: "Bob".to_string(),
+                    word: "DOGS".to_string(),
+                    points: 8,
+                    claim_sequence: 2,
+                },
+            ],
+        };
+        let bytes = msg.to_bytes();
+        let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, msg);
+        assert_eq!(len, bytes.len());
+
+        let empty = Message::RoundClaims { claims: vec![] };
+        assert_eq!(Message::from_bytes(&empty.to_bytes()).unwrap().0, empty);
+    }
+
     #[test]
     fn test_claim_reject_reason_messages() {
         assert_eq!(
@@ -1055,6 +1667,10 @@ mod tests {
             ClaimRejectReason::TooShort.message(),
             "Too short"
         );
+        assert_eq!(
+            ClaimRejectReason::TooLong { max: 12 }.message(),
+            "Too long (max 12 letters)"
+        );
         assert_eq!(
             ClaimRejectReason::RoundEnded.message(),
             "Round has ended"
@@ -1072,11 +1688,81 @@ mod tests {
 
     #[test]
     fn test_round_end_roundtrip() {
-        let msg = Message::RoundEnd;
+        for reason in [
+            RoundEndReason::TimeExpired,
+            RoundEndReason::HostEnded,
+            RoundEndReason::TargetReached,
+        ] {
+            let msg = Message::RoundEnd { reason };
+            let bytes = msg.to_bytes();
+            let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+            assert_eq!(parsed, msg);
+            assert_eq!(len, bytes.len());
+        }
+    }
+
+    #[test]
+    fn test_round_end_without_reason_is_time_expired() {
+        let msg = Message::from_json(r#"{"type":"round_end"}"#).unwrap();
+        assert_eq!(msg, Message::RoundEnd { reason: RoundEndReason::TimeExpired });
+    }
+
+    #[test]
+    fn test_join_spectator_roundtrip() {
+        let msg = Message::JoinSpectator { name: "Watcher \"W\"".to_string() };
         let bytes = msg.to_bytes();
         let (parsed, len) = Message::from_bytes(&bytes).unwrap();
         assert_eq!(parsed, msg);
         assert_eq!(len, bytes.len());
+        assert!(Message::from_json(r#"{"type":"join_spectator"}"#).is_err());
+    }
+
+    #[test]
+    fn test_kicked_roundtrip() {
+        let msg = Message::Kicked { reason: "Removed by the host".to_string() };
+        let bytes = msg.to_bytes();
+        let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, msg);
+        assert_eq!(len, bytes.len());
+        assert!(Message::from_json(r#"{"type":"kicked"}"#).is_err());
+    }
+
+    #[test]
+    fn test_resync_roundtrip() {
+        let msg = Message::Resync;
+        assert_eq!(msg.to_json(), r#"{"type":"resync"}"#);
+        let bytes = msg.to_bytes();
+        let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, msg);
+        assert_eq!(len, bytes.len());
+    }
+
+    #[test]
+    fn test_countdown_cancelled_roundtrip() {
+        let msg = Message::CountdownCancelled;
+        let bytes = msg.to_bytes();
+        let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed, msg);
+        assert_eq!(len, bytes.len());
+    }
+
+    #[test]
+    fn test_rematch_messages_roundtrip() {
+        for msg in [
+            Message::RematchOffer,
+            Message::RematchVote { accept: true },
+            Message::RematchVote { accept: false },
+        ] {
+            let bytes = msg.to_bytes();
+            let (parsed, len) = Message::from_bytes(&bytes).unwrap();
+            assert_eq!(parsed, msg);
+            assert_eq!(len, bytes.len());
+        }
+        assert_eq!(
+            Message::RematchVote { accept: true }.to_json(),
+            r#"{"type":"rematch_vote","accept":true}"#
+        );
+        assert!(Message::from_json(r#"{"type":"rematch_vote"}"#).is_err());
     }
 
     #[test]
@@ -1126,6 +1812,112 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_framer_one_byte_at_a_time() {
+        let msg = Message::ClaimAttempt { word: "quartz".to_string() };
+        let bytes = msg.to_bytes();
+        let mut framer = MessageFramer::new();
+
+        let mut decoded = Vec::new();
+        for byte in &bytes {
+            framer.feed(&[*byte]);
+            while let Some((parsed, used)) = framer.next_message().unwrap() {
+                assert_eq!(used, bytes.len());
+                decoded.push(parsed);
+            }
+        }
+
+        assert_eq!(decoded, vec![msg]);
+        assert_eq!(framer.pending(), 0);
+    }
+
+    #[test]
+    fn test_framer_splits_back_to_back_messages() {
+        let first = Message::Ping;
+        let second = Message::ClaimAttempt { word: "zebra".to_string() };
+        let mut bytes = first.to_bytes();
+        bytes.extend(second.to_bytes());
+
+        let mut framer = MessageFramer::new();
+        // Everything but the last byte of the second message
+        framer.feed(&bytes[..bytes.len() - 1]);
+        assert_eq!(framer.next_message().unwrap(), Some((first.clone(), first.to_bytes().len())));
+        assert_eq!(framer.next_message().unwrap(), None);
+
+        framer.feed(&bytes[bytes.len() - 1..]);
+        assert_eq!(framer.next_message().unwrap().map(|(m, _)| m), Some(second));
+    }
+
+    #[test]
+    fn test_framer_two_messages_across_three_chunks() {
+        let first = Message::ClaimAttempt { word: "banana".to_string() };
+        let second = Message::SystemAnnouncement { text: "last round".to_string() };
+        let mut bytes = first.to_bytes();
+        bytes.extend(second.to_bytes());
+        // Split inside the first length prefix and inside the second body
+        let cuts = [2, first.to_bytes().len() + 7];
+
+        let mut framer = MessageFramer::new();
+        let mut decoded = Vec::new();
+        for chunk in [&bytes[..cuts[0]], &bytes[cuts[0]..cuts[1]], &bytes[cuts[1]..]] {
+            framer.feed(chunk);
+            while let Some((msg, _)) = framer.next_message().unwrap() {
+                decoded.push(msg);
+            }
+        }
+        assert_eq!(decoded, vec![first, second]);
+        assert_eq!(framer.pending(), 0);
+    }
+
+    #[test]
+    fn test_framer_skips_oversized_frame() {
+        let len = MAX_FRAME_LEN + 10;
+        let mut framer = MessageFramer::new();
+        framer.feed(&(len as u32).to_be_bytes());
+        framer.feed(&[b'x'; 6]);
+        assert_eq!(framer.next_message(), Err(FrameError::TooLarge { len }));
+        assert_eq!(framer.pending(), 0);
+
+        // The rest of the body is dropped as it arrives, then framing resumes
+        framer.feed(&vec![b'x'; len - 6]);
+        framer.feed(&Message::Ping.to_bytes());
+        assert_eq!(framer.next_message().unwrap().map(|(m, _)| m), Some(Message::Ping));
+
+        framer.feed(&u32::MAX.to_be_bytes());
+        assert!(framer.next_message().is_err());
+    }
+
+    #[test]
+    fn test_framer_skips_undecodable_frames() {
+        let mut bytes = vec![0, 0, 0, 2, 0xff, 0xfe];
+        bytes.extend([0, 0, 0, 2]);
+        bytes.extend(b"{}");
+        bytes.extend(Message::Pong.to_bytes());
+
+        let mut framer = MessageFramer::new();
+        framer.feed(&bytes);
+        assert_eq!(framer.next_message(), Err(FrameError::InvalidUtf8));
+        assert!(matches!(framer.next_message(), Err(FrameError::Malformed(_))));
+        assert_eq!(framer.next_message().unwrap().map(|(m, _)| m), Some(Message::Pong));
+    }
+
+    #[test]
+    fn test_frame_limit_in_from_bytes_and_read_from() {
+        let len = MAX_FRAME_LEN + 1;
+        let mut bytes = (len as u32).to_be_bytes().to_vec();
+        bytes.resize(4 + len, b' ');
+        let err = Message::from_bytes(&bytes).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let typed = err.get_ref().and_then(|e| e.downcast_ref::<FrameError>());
+        assert_eq!(typed, Some(&FrameError::TooLarge { len }));
+
+        // read_from reads past the bad frame, so the next one still decodes
+        bytes.extend(Message::Ping.to_bytes());
+        let mut stream = io::Cursor::new(bytes);
+        assert!(Message::read_from(&mut stream).is_err());
+        assert_eq!(Message::read_from(&mut stream).unwrap(), Message::Ping);
+    }
+
     #[test]
     fn test_invalid_type_error() {
         let json = r#"{"type":"unknown_type"}"#;
@@ -1141,10 +1933,8 @@ mod tests {
     #[test]
     fn test_score_update_empty() {
         let msg = Message::ScoreUpdate { scores: vec![] };
-        // Serialize
-        let json = msg.to_bytes();
-        // Can't roundtrip empty scores (parser expects [[ pattern), but serialization shouldn't panic
-        assert!(!json.is_empty());
+        let (parsed, _) = Message::from_bytes(&msg.to_bytes()).unwrap();
+        assert_eq!(parsed, msg);
     }
 
     #[test]
@@ -1153,6 +1943,7 @@ mod tests {
             word: "BLAM".to_string(),
             player_name: "Test\\User\"Name".to_string(),
             points: 4,
+            combo: 1,
         };
         let bytes = msg.to_bytes();
         let (parsed, _) = Message::from_bytes(&bytes).unwrap();
@@ -1197,41 +1988,69 @@ mod tests {
     }
 
     #[test]
-    fn test_unescape_json_roundtrip() {
+    fn test_escaped_strings_parse_back() {
         let original = r#"Test"User\Name"#;
-        let escaped = escape_json(original);
-        let unescaped = unescape_json(&escaped);
-        assert_eq!(unescaped, original);
+        let parsed = json::parse(&format!(r#""{}""#, escape_json(original))).unwrap();
+        assert_eq!(parsed.as_str(), Some(original));
     }
 
     #[test]
-    fn test_unescape_json_trailing_backslash() {
-        let result = unescape_json("hello\\");
-        assert_eq!(result, "hello\\");
+    fn test_malformed_escapes_rejected() {
+        // A trailing backslash escapes the closing quote, and \x isn't an escape
+        assert!(Message::from_json(r#"{"type":"join","player_name":"hello\"}"#).is_err());
+        assert!(Message::from_json(r#"{"type":"join","player_name":"hello\xworld"}"#).is_err());
     }
 
     #[test]
-    fn test_unescape_json_unknown_escape() {
-        let result = unescape_json("hello\\xworld");
-        assert_eq!(result, "hello\\xworld");
+    fn test_adversarial_names_and_words_roundtrip() {
+        // Names and words that look like the JSON around them
+        let messages = [
+            Message::ClaimAccepted {
+                word: r#""scores":["#.to_string(),
+                player_name: r#""points""#.to_string(),
+                points: 7,
+                combo: 1,
+            },
+            Message::ScoreUpdate {
+                scores: vec![(r#""points""#.to_string(), 3), (r#"],["x",9]]"#.to_string(), 4)],
+            },
+            Message::ClaimRejected {
+                word: r#"{"reason":"too_short"}"#.to_string(),
+                reason: ClaimRejectReason::AlreadyClaimed {
+                    by: r#"","reason":"round_ended"#.to_string(),
+                },
+            },
+        ];
+        for msg in messages {
+            let (parsed, _) = Message::from_bytes(&msg.to_bytes()).unwrap();
+            assert_eq!(parsed, msg);
+        }
     }
 
     #[test]
-    fn test_find_unescaped_quote_simple() {
-        assert_eq!(find_unescaped_quote("hello\""), Some(5));
-        assert_eq!(find_unescaped_quote("\""), Some(0));
-    }
+    fn test_fields_in_nested_objects_are_not_top_level() {
+        // "word" only appears in a nested object and inside a string, so the
+        // claim attempt has no word of its own
+        let json = r#"{"type":"claim_attempt","extra":{"word":"CAT"},"note":"\"word\":\"DOG\""}"#;
+        assert!(Message::from_json(json).is_err());
 
-    #[test]
-    fn test_find_unescaped_quote_escaped() {
-        // \" should be skipped
-        assert_eq!(find_unescaped_quote("\\\"hello\""), Some(7));
-    }
+        let json = r#"{"type":"claim_attempt","extra":{"word":"CAT"},"word":"DOG"}"#;
+        assert_eq!(
+            Message::from_json(json).unwrap(),
+            Message::ClaimAttempt { word: "DOG".to_string() }
+        );
 
-    #[test]
-    fn test_find_unescaped_quote_none() {
-        assert_eq!(find_unescaped_quote("hello"), None);
-        assert_eq!(find_unescaped_quote(""), None);
+        let events = Message::SyncEvents {
+            events: vec![SyncEvent {
+                actor_id: "abc".to_string(),
+                seq: 1,
+                event_type: "match_end".to_string(),
+                payload: r#"{"seq":99,"nested":{"actor_id":"zzz","events":[{}]}}"#.to_string(),
+                created_at: 5,
+            }],
+        };
+        let (parsed, _) = Message::from_bytes(&events.to_bytes()).unwrap();
+        assert_eq!(parsed, events);
     }
 
     #[test]
@@ -1287,6 +2106,8 @@ mod tests {
             ClaimRejectReason::TooShort,
             ClaimRejectReason::NotInDictionary,
             ClaimRejectReason::RoundEnded,
+            ClaimRejectReason::TooLong { max: 15 },
+            ClaimRejectReason::TooFewLetters { min: 5 },
             ClaimRejectReason::InvalidLetters { missing: vec!['A', 'B'] },
             ClaimRejectReason::AlreadyClaimed { by: "TestPlayer".to_string() },
         ];
@@ -1317,4 +2138,146 @@ mod tests {
         let (parsed, _) = Message::from_bytes(&bytes).unwrap();
         assert_eq!(parsed, msg);
     }
+
+    /// Characters that have tripped up hand-rolled JSON parsers
+    const AWKWARD_CHARS: &[char] = &[
+        'a', 'Z', '7', ' ', ',', '[', ']', '{', '}', ':', '"', '\\', '/', '\n', '\t', 'é', 'ß',
+        '漢', '🎉',
+    ];
+
+    fn random_text(rng: &mut StdRng, max_len: usize) -> String {
+        let len = rng.random_range(0..=max_len);
+        (0..len).map(|_| AWKWARD_CHARS[rng.random_range(0..AWKWARD_CHARS.len())]).collect()
+    }
+
+    fn random_word(rng: &mut StdRng) -> String {
+        let len = rng.random_range(1..=8);
+        (0..len).map(|_| rng.random_range(b'A'..=b'Z') as char).collect()
+    }
+
+    fn random_scores(rng: &mut StdRng) -> Vec<(String, u32)> {
+        let count = rng.random_range(0..=12);
+        (0..count).map(|_| (random_text(rng, 12), rng.random())).collect()
+    }
+
+    fn random_reason(rng: &mut StdRng) -> ClaimRejectReason {
+        match rng.random_range(0..7) {
+            0 => ClaimRejectReason::AlreadyClaimed { by: random_text(rng, 12) },
+            1 => ClaimRejectReason::NotInDictionary,
+            2 => ClaimRejectReason::InvalidLetters { missing: random_word(rng).chars().collect() },
+            3 => ClaimRejectReason::TooShort,
+            4 => ClaimRejectReason::TooLong { max: rng.random() },
+            5 => ClaimRejectReason::TooFewLetters { min: rng.random() },
+            _ => ClaimRejectReason::RoundEnded,
+        }
+    }
+
+    fn random_message(rng: &mut StdRng) -> Message {
+        match rng.random_range(0..12) {
+            0 if rng.random_bool(0.5) => Message::JoinSpectator { name: random_text(rng, 16) },
+            0 => Message::Join { player_name: random_text(rng, 16) },
+            1 => Message::ClaimAccepted {
+                word: random_word(rng),
+                player_name: random_text(rng, 16),
+                points: rng.random(),
+                combo: rng.random(),
+            },
+            2 => Message::ClaimRejected { word: random_word(rng), reason: random_reason(rng) },
+            3 => Message::WordClaimed {
+                word: random_word(rng),
+                player_name: random_text(rng, 16),
+                points: rng.random(),
+                actor_id: random_text(rng, 32),
+                timestamp_ms: rng.random(),
+                claim_sequence: rng.random(),
+                round_id: rng.random_bool(0.5).then(|| rng.random()),
+            },
+            4 if rng.random_bool(0.5) => Message::Handicaps { handicaps: random_scores(rng) },
+            4 => Message::ScoreUpdate { scores: random_scores(rng) },
+            5 => Message::MatchEnded {
+                match_id: rng.random(),
+                scores: random_scores(rng),
+                host_actor_id: random_text(rng, 32),
+                completed: rng.random(),
+            },
+            6 if rng.random_bool(0.5) => Message::Kicked { reason: random_text(rng, 40) },
+            6 if rng.random_bool(0.5) => Message::SystemAnnouncement { text: random_text(rng, 40) },
+            6 => Message::RematchVote { accept: rng.random() },
+            7 => Message::PlayerStatus {
+                player_name: random_text(rng, 16),
+                status: random_text(rng, 24),
+            },
+            8 => Message::RoundClaims {
+                claims: (0..rng.random_range(0..=6))
+                    .map(|_| RoundClaim {
+                        player_name: random_text(rng, 16),
+                        word: random_word(rng),
+                        points: rng.random(),
+                        claim_sequence: rng.random(),
+                    })
+                    .collect(),
+            },
+            9 => Message::SyncRequest {
+                vector_clock: (0..rng.random_range(0..=4))
+                    .map(|_| (random_text(rng, 32), rng.random()))
+                    .collect(),
+                accepts_gzip: rng.random(),
+            },
+            10 if rng.random_bool(0.25) => {
+                Message::SyncEventsCompressed { gzip_base64: random_text(rng, 40) }
+            }
+            10 => Message::SyncEvents {
+                events: (0..rng.random_range(0..=4))
+                    .map(|_| SyncEvent {
+                        actor_id: random_text(rng, 32),
+                        seq: rng.random(),
+                        event_type: random_text(rng, 12),
+                        payload: random_text(rng, 40),
+                        created_at: rng.random(),
+                    })
+                    .collect(),
+            },
+            _ if rng.random_bool(0.5) => {
+                let presets =
+                    [RackPreset::Standard, RackPreset::Tiny, RackPreset::Blitz, RackPreset::Big];
+                let preset = presets[rng.random_range(0..4)];
+                Message::RoundStartSeeded {
+                    seed: rng.random(),
+                    config: RoundConfig {
+                        rack: preset
+                            .config()
+                            .with_blanks(rng.random_range(0..=2))
+                            .with_language(Language::all()[rng.random_range(0..4)]),
+                        min_letters_used: rng.random(),
+                        reject_penalty: rng.random(),
+                    },
+                    duration_secs: rng.random(),
+                }
+            }
+            _ => Message::RoundStart {
+                letters: random_word(rng).chars().collect(),
+                duration_secs: rng.random(),
+                min_letters_used: rng.random(),
+                reject_penalty: rng.random(),
+                language: Language::all()[rng.random_range(0..4)],
+            },
+        }
+    }
+
+    #[test]
+    fn test_fuzz_messages_roundtrip() {
+        // Fixed seed, so a failure reproduces every run
+        let mut rng = StdRng::seed_from_u64(0x626c_616d);
+        for i in 0..2000 {
+            let msg = random_message(&mut rng);
+            let bytes = msg.to_bytes();
+            match Message::from_bytes(&bytes) {
+                Ok((parsed, len)) => {
+                    assert_eq!(parsed, msg, "iteration {}: {}", i, msg.to_json());
+                    assert_eq!(len, bytes.len());
+                }
+                Err(e) => panic!("iteration {}: {} failed to parse: {}", i, msg.to_json(), e),
+            }
+        }
+    }
 }
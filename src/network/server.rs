@@ -2,12 +2,15 @@
 //! TCP server for hosting games
 
 use super::peer::Peer;
-use super::protocol::Message;
+use super::protocol::{FrameError, Message};
+use std::collections::VecDeque;
 use std::io;
 use std::net::{SocketAddr, TcpListener};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Default port for BLAM servers
 pub const DEFAULT_PORT: u16 = 55333;
@@ -15,6 +18,33 @@ pub const DEFAULT_PORT: u16 = 55333;
 /// Maximum port to try when auto-incrementing
 const MAX_PORT: u16 = 55433;
 
+/// Most events an `EventQueue` holds for one peer before refusing more
+pub const MAX_QUEUED_PER_PEER: usize = 256;
+
+/// Messages and bytes a server has exchanged with its peers
+///
+/// Bytes are counted as encoded frames, length prefix included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrafficStats {
+    /// Messages received from peers
+    pub messages_in: u64,
+    /// Messages sent to peers (a broadcast counts once per peer)
+    pub messages_out: u64,
+    /// Bytes received from peers
+    pub bytes_in: u64,
+    /// Bytes sent to peers
+    pub bytes_out: u64,
+    /// Frames from peers skipped because they couldn't be decoded
+    pub frames_rejected: u64,
+}
+
+impl TrafficStats {
+    /// Bytes sent and received
+    pub fn total_bytes(&self) -> u64 {
+        self.bytes_in + self.bytes_out
+    }
+}
+
 /// A BLAM game server that accepts peer connections
 pub struct Server {
     /// Local address the server is bound to
@@ -23,8 +53,20 @@ pub struct Server {
     new_peers_rx: Receiver<Peer>,
     /// Connected peers
     peers: Vec<Peer>,
+    /// Peers dropped by the owner, reported as disconnected on the next poll
+    dropped: Vec<Peer>,
     /// Running flag
     running: bool,
+    /// Cleared to stop the acceptor thread and free the port
+    accepting: Arc<AtomicBool>,
+    /// When the server started listening
+    started: Instant,
+    /// Traffic since the server started
+    traffic: TrafficStats,
+    /// Start of the current throughput sample and the byte total then
+    rate_mark: (Instant, u64),
+    /// Bytes per second over the last full sample
+    bytes_per_sec: u64,
 }
 
 impl Server {
@@ -50,17 +92,25 @@ impl Server {
         listener.set_nonblocking(true)?;
 
         let (new_peers_tx, new_peers_rx) = channel();
+        let accepting = Arc::new(AtomicBool::new(true));
 
         // Spawn acceptor thread
+        let still_accepting = Arc::clone(&accepting);
         thread::spawn(move || {
-            accept_loop(listener, new_peers_tx);
+            accept_loop(listener, new_peers_tx, still_accepting);
         });
 
         Ok(Server {
             addr,
             new_peers_rx,
             peers: Vec::new(),
+            dropped: Vec::new(),
             running: true,
+            accepting,
+            started: Instant::now(),
+            traffic: TrafficStats::default(),
+            rate_mark: (Instant::now(), 0),
+            bytes_per_sec: 0,
         })
     }
 
@@ -76,7 +126,14 @@ impl Server {
 
     /// Poll for new connections and messages
     pub fn poll(&mut self) -> Vec<ServerEvent> {
-        let mut events = Vec::new();
+        let mut events: Vec<ServerEvent> = self
+            .dropped
+            .drain(..)
+            .map(|peer| ServerEvent::PeerDisconnected {
+                addr: peer.addr,
+                player_name: peer.player_name.clone(),
+            })
+            .collect();
 
         // Accept new peers
         loop {
@@ -96,17 +153,27 @@ impl Server {
         // Collect messages from peers and track disconnections
         let mut disconnected = Vec::new();
         for (i, peer) in self.peers.iter_mut().enumerate() {
-            for msg in peer.recv_all() {
+            for (msg, frame_len) in peer.recv_all_framed() {
                 // Handle Join messages to set player name
                 if let Message::Join { ref player_name } = msg {
                     peer.set_player_name(player_name.clone());
                 }
+                self.traffic.messages_in += 1;
+                self.traffic.bytes_in += frame_len as u64;
                 events.push(ServerEvent::MessageReceived {
                     from: peer.addr,
                     player_name: peer.player_name.clone(),
                     message: msg,
                 });
             }
+            for error in peer.take_frame_errors() {
+                self.traffic.frames_rejected += 1;
+                events.push(ServerEvent::FrameRejected {
+                    from: peer.addr,
+                    player_name: peer.player_name.clone(),
+                    error,
+                });
+            }
             if !peer.is_alive() {
                 disconnected.push(i);
             }
@@ -117,32 +184,92 @@ impl Server {
             let peer = self.peers.remove(i);
             events.push(ServerEvent::PeerDisconnected {
                 addr: peer.addr,
-                player_name: peer.player_name,
+                player_name: peer.player_name.clone(),
             });
         }
 
+        self.sample_throughput(Instant::now());
         events
     }
 
+    /// Roll the throughput sample over once it's a second old
+    fn sample_throughput(&mut self, now: Instant) {
+        let (since, bytes_then) = self.rate_mark;
+        let elapsed = now.saturating_duration_since(since);
+        if elapsed >= Duration::from_secs(1) {
+            let bytes = self.traffic.total_bytes() - bytes_then;
+            self.bytes_per_sec = (bytes as f64 / elapsed.as_secs_f64()) as u64;
+            self.rate_mark = (now, self.traffic.total_bytes());
+        }
+    }
+
+    /// Messages and bytes exchanged since the server started
+    pub fn traffic(&self) -> TrafficStats {
+        self.traffic
+    }
+
+    /// Bytes sent and received per second, over the last second or so
+    pub fn bytes_per_sec(&self) -> u64 {
+        self.bytes_per_sec
+    }
+
+    /// How long the server has been up
+    pub fn uptime(&self) -> Duration {
+        self.uptime_at(Instant::now())
+    }
+
+    /// How long the server had been up at `now`
+    pub fn uptime_at(&self, now: Instant) -> Duration {
+        now.saturating_duration_since(self.started)
+    }
+
     /// Broadcast a message to all connected peers (serializes once)
-    pub fn broadcast(&self, msg: &Message) {
+    ///
+    /// Returns the addresses of peers the message couldn't be sent to.
+    pub fn broadcast(&mut self, msg: &Message) -> Vec<SocketAddr> {
         let bytes = msg.to_bytes();
-        for peer in &self.peers {
-            let _ = peer.send_raw(bytes.clone());
-        }
+        let failed: Vec<SocketAddr> = self
+            .peers
+            .iter()
+            .filter(|peer| peer.send_raw(bytes.clone()).is_err())
+            .map(|peer| peer.addr)
+            .collect();
+        let sent = (self.peers.len() - failed.len()) as u64;
+        self.traffic.messages_out += sent;
+        self.traffic.bytes_out += sent * bytes.len() as u64;
+        failed
     }
 
     /// Send a message to a specific peer by address
-    pub fn send_to(&self, addr: SocketAddr, msg: &Message) -> io::Result<()> {
+    pub fn send_to(&mut self, addr: SocketAddr, msg: &Message) -> io::Result<()> {
         let bytes = msg.to_bytes();
+        let len = bytes.len() as u64;
         for peer in &self.peers {
             if peer.addr == addr {
-                return peer.send_raw(bytes);
+                peer.send_raw(bytes)?;
+                self.traffic.messages_out += 1;
+                self.traffic.bytes_out += len;
+                return Ok(());
             }
         }
         Err(io::Error::new(io::ErrorKind::NotFound, "peer not found"))
     }
 
+    /// Disconnect a peer, e.g. after repeated send failures
+    ///
+    /// The peer is reported as `PeerDisconnected` on the next poll. Returns
+    /// false if no peer has that address.
+    pub fn drop_peer(&mut self, addr: SocketAddr) -> bool {
+        match self.peers.iter().position(|p| p.addr == addr) {
+            Some(i) => {
+                let peer = self.peers.remove(i);
+                self.dropped.push(peer);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Get the number of connected peers
     pub fn peer_count(&self) -> usize {
         self.peers.len()
@@ -161,10 +288,18 @@ impl Server {
     /// Stop the server
     pub fn stop(&mut self) {
         self.running = false;
+        self.accepting.store(false, Ordering::Relaxed);
         self.peers.clear();
     }
 }
 
+impl Drop for Server {
+    fn drop(&mut self) {
+        // Otherwise the acceptor keeps the port until someone connects
+        self.accepting.store(false, Ordering::Relaxed);
+    }
+}
+
 /// Events from the server
 #[derive(Debug, Clone)]
 pub enum ServerEvent {
@@ -181,10 +316,96 @@ pub enum ServerEvent {
         player_name: Option<String>,
         message: Message,
     },
+    /// A frame from a peer couldn't be decoded and was skipped; the
+    /// connection is still up
+    FrameRejected {
+        from: SocketAddr,
+        player_name: Option<String>,
+        error: FrameError,
+    },
+}
+
+impl ServerEvent {
+    /// Address of the peer the event concerns
+    pub fn addr(&self) -> SocketAddr {
+        match self {
+            ServerEvent::PeerConnected { addr } | ServerEvent::PeerDisconnected { addr, .. } => *addr,
+            ServerEvent::MessageReceived { from, .. } | ServerEvent::FrameRejected { from, .. } => {
+                *from
+            }
+        }
+    }
+}
+
+/// Server events waiting to be handled, queued per peer
+///
+/// A peer flooding the host would otherwise have all of its events handled
+/// before anyone else's in the same poll. Batches take events round-robin
+/// across peers, a bounded number per peer, and leave the rest for later.
+/// Each peer's own events stay in the order they arrived, and no peer can
+/// have more than `MAX_QUEUED_PER_PEER` waiting.
+#[derive(Debug, Default)]
+pub struct EventQueue {
+    /// Pending events per peer, in the order peers take turns
+    queues: VecDeque<(SocketAddr, VecDeque<ServerEvent>)>,
+}
+
+impl EventQueue {
+    /// Create an empty queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue an event behind any pending events from the same peer
+    ///
+    /// Returns false, dropping the event, if the peer already has
+    /// `MAX_QUEUED_PER_PEER` events waiting. A disconnect is always queued,
+    /// so it's never lost.
+    pub fn push(&mut self, event: ServerEvent) -> bool {
+        let addr = event.addr();
+        match self.queues.iter_mut().find(|(a, _)| *a == addr) {
+            Some((_, queue)) => {
+                let disconnect = matches!(event, ServerEvent::PeerDisconnected { .. });
+                if queue.len() >= MAX_QUEUED_PER_PEER && !disconnect {
+                    return false;
+                }
+                queue.push_back(event);
+            }
+            None => self.queues.push_back((addr, VecDeque::from([event]))),
+        }
+        true
+    }
+
+    /// Take the next batch: up to `per_peer` events from each peer,
+    /// interleaved so every peer gets a turn before anyone gets a second
+    pub fn next_batch(&mut self, per_peer: usize) -> Vec<ServerEvent> {
+        let mut batch = Vec::new();
+        for _ in 0..per_peer {
+            let before = batch.len();
+            batch.extend(self.queues.iter_mut().filter_map(|(_, queue)| queue.pop_front()));
+            if batch.len() == before {
+                break;
+            }
+        }
+        self.queues.retain(|(_, queue)| !queue.is_empty());
+        // Whoever went first this time goes last next time
+        self.queues.rotate_left(1.min(self.queues.len()));
+        batch
+    }
+
+    /// Number of events waiting
+    pub fn len(&self) -> usize {
+        self.queues.iter().map(|(_, queue)| queue.len()).sum()
+    }
+
+    /// Whether no events are waiting
+    pub fn is_empty(&self) -> bool {
+        self.queues.is_empty()
+    }
 }
 
-fn accept_loop(listener: TcpListener, tx: Sender<Peer>) {
-    loop {
+fn accept_loop(listener: TcpListener, tx: Sender<Peer>, accepting: Arc<AtomicBool>) {
+    while accepting.load(Ordering::Relaxed) {
         match listener.accept() {
             Ok((stream, _addr)) => {
                 if let Ok(peer) = Peer::new(stream) {
@@ -205,6 +426,99 @@ fn accept_loop(listener: TcpListener, tx: Sender<Peer>) {
 mod tests {
     use super::*;
 
+    fn claim_from(addr: SocketAddr, word: &str) -> ServerEvent {
+        ServerEvent::MessageReceived {
+            from: addr,
+            player_name: None,
+            message: Message::ClaimAttempt { word: word.to_string() },
+        }
+    }
+
+    fn claimed_word(event: &ServerEvent) -> &str {
+        match event {
+            ServerEvent::MessageReceived {
+                message: Message::ClaimAttempt { word },
+                ..
+            } => word,
+            _ => panic!("expected a claim"),
+        }
+    }
+
+    #[test]
+    fn test_flooding_peer_cannot_starve_others() {
+        let a: SocketAddr = "10.0.0.1:5000".parse().unwrap();
+        let b: SocketAddr = "10.0.0.2:5000".parse().unwrap();
+        let mut queue = EventQueue::new();
+        for i in 0..100 {
+            queue.push(claim_from(a, &format!("A{}", i)));
+        }
+        queue.push(claim_from(b, "B"));
+
+        // B's single claim is handled in the first batch, right after A's first
+        let batch = queue.next_batch(4);
+        let words: Vec<&str> = batch.iter().map(claimed_word).collect();
+        assert_eq!(words, vec!["A0", "B", "A1", "A2", "A3"]);
+
+        // The rest of A's flood is deferred, still in order
+        assert_eq!(queue.len(), 96);
+        let mut rest = Vec::new();
+        while !queue.is_empty() {
+            let batch = queue.next_batch(4);
+            assert!(batch.len() <= 4);
+            rest.extend(batch.iter().map(|e| claimed_word(e).to_string()));
+        }
+        let expected: Vec<String> = (4..100).map(|i| format!("A{}", i)).collect();
+        assert_eq!(rest, expected);
+    }
+
+    #[test]
+    fn test_event_queue_bounded_per_peer() {
+        let a: SocketAddr = "10.0.0.1:5000".parse().unwrap();
+        let b: SocketAddr = "10.0.0.2:5000".parse().unwrap();
+        let mut queue = EventQueue::new();
+        for i in 0..MAX_QUEUED_PER_PEER {
+            assert!(queue.push(claim_from(a, &format!("A{}", i))));
+        }
+        assert!(!queue.push(claim_from(a, "overflow")));
+        assert_eq!(queue.len(), MAX_QUEUED_PER_PEER);
+
+        // Other peers are unaffected, and a disconnect still gets through
+        assert!(queue.push(claim_from(b, "B")));
+        assert!(queue.push(ServerEvent::PeerDisconnected { addr: a, player_name: None }));
+        assert_eq!(queue.len(), MAX_QUEUED_PER_PEER + 2);
+    }
+
+    #[test]
+    fn test_stopped_server_frees_its_port() {
+        let mut server = Server::start_on_port(55445).unwrap();
+        let port = server.port();
+        server.stop();
+        drop(server);
+
+        // The acceptor notices within one sleep
+        thread::sleep(Duration::from_millis(200));
+        let again = Server::start_on_port(port).unwrap();
+        assert_eq!(again.port(), port);
+    }
+
+    #[test]
+    fn test_event_queue_rotates_first_turn() {
+        let a: SocketAddr = "10.0.0.1:5000".parse().unwrap();
+        let b: SocketAddr = "10.0.0.2:5000".parse().unwrap();
+        let mut queue = EventQueue::new();
+        for word in ["A0", "A1"] {
+            queue.push(claim_from(a, word));
+        }
+        for word in ["B0", "B1"] {
+            queue.push(claim_from(b, word));
+        }
+        let words = |batch: Vec<ServerEvent>| -> Vec<String> {
+            batch.iter().map(|e| claimed_word(e).to_string()).collect()
+        };
+        assert_eq!(words(queue.next_batch(1)), vec!["A0", "B0"]);
+        assert_eq!(words(queue.next_batch(1)), vec!["B1", "A1"]);
+    }
+
     #[test]
     fn test_server_starts_on_default_port() {
         let server = Server::start();
@@ -244,4 +558,110 @@ mod tests {
         assert!(events.iter().any(|e| matches!(e, ServerEvent::PeerConnected { .. })));
         assert_eq!(server.peer_count(), 1);
     }
+
+    #[test]
+    fn test_traffic_counts_broadcasts_and_receives() {
+        let mut server = Server::start_on_port(55430).unwrap();
+        let client = Peer::connect(server.addr()).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        server.poll();
+        assert_eq!(server.traffic(), TrafficStats::default());
+
+        let ping_len = Message::Ping.to_bytes().len() as u64;
+        server.broadcast(&Message::Ping);
+        let addr = server.peer_addrs()[0];
+        server.send_to(addr, &Message::Ping).unwrap();
+        client.send(Message::Pong).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        server.poll();
+
+        let traffic = server.traffic();
+        assert_eq!(traffic.messages_out, 2);
+        assert_eq!(traffic.bytes_out, 2 * ping_len);
+        assert_eq!(traffic.messages_in, 1);
+        assert_eq!(traffic.bytes_in, Message::Pong.to_bytes().len() as u64);
+    }
+
+    #[test]
+    fn test_bad_frames_are_reported_and_skipped() {
+        use std::io::Write;
+
+        let mut server = Server::start_on_port(55440).unwrap();
+        let mut client = std::net::TcpStream::connect(("127.0.0.1", server.port())).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        server.poll();
+
+        // Not UTF-8, then over the size limit, then a good message (spaced
+        // out, so it's longer than we'd encode it)
+        let mut bytes = vec![0, 0, 0, 1, 0xff];
+        let oversized = super::super::protocol::MAX_FRAME_LEN + 1;
+        bytes.extend((oversized as u32).to_be_bytes());
+        bytes.resize(bytes.len() + oversized, b'x');
+        let ping = br#"{ "type": "ping" }"#;
+        bytes.extend((ping.len() as u32).to_be_bytes());
+        bytes.extend(ping);
+        client.write_all(&bytes).unwrap();
+        thread::sleep(Duration::from_millis(300));
+
+        let events = server.poll();
+        let errors: Vec<&FrameError> = events
+            .iter()
+            .filter_map(|e| match e {
+                ServerEvent::FrameRejected { error, .. } => Some(error),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(errors, [&FrameError::InvalidUtf8, &FrameError::TooLarge { len: oversized }]);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, ServerEvent::MessageReceived { message: Message::Ping, .. })));
+        assert_eq!(server.traffic().frames_rejected, 2);
+        assert_eq!(server.traffic().bytes_in, 4 + ping.len() as u64);
+        assert_eq!(server.peer_count(), 1);
+    }
+
+    #[test]
+    fn test_uptime_counts_from_start() {
+        let server = Server::start_on_port(55435).unwrap();
+        let later = server.started + Duration::from_secs(90);
+        assert_eq!(server.uptime_at(later), Duration::from_secs(90));
+        // A clock reading from before the start doesn't underflow
+        assert_eq!(server.uptime_at(server.started - Duration::from_secs(1)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_send_to_closed_connection_reports_failure() {
+        let mut server = Server::start_on_port(55420).unwrap();
+        let client = std::net::TcpStream::connect(("127.0.0.1", server.port())).unwrap();
+
+        thread::sleep(Duration::from_millis(100));
+        server.poll();
+        let addr = server.peer_addrs()[0];
+
+        // Close the connection and give the reader a moment to notice
+        drop(client);
+        thread::sleep(Duration::from_millis(200));
+
+        assert!(server.send_to(addr, &Message::Ping).is_err());
+        assert_eq!(server.broadcast(&Message::Ping), vec![addr]);
+    }
+
+    #[test]
+    fn test_drop_peer_reported_on_next_poll() {
+        let mut server = Server::start_on_port(55425).unwrap();
+        let _client = Peer::connect(server.addr()).unwrap();
+
+        thread::sleep(Duration::from_millis(100));
+        server.poll();
+        let addr = server.peer_addrs()[0];
+
+        assert!(server.drop_peer(addr));
+        assert!(!server.drop_peer(addr));
+        assert_eq!(server.peer_count(), 0);
+
+        let events = server.poll();
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, ServerEvent::PeerDisconnected { addr: a, .. } if *a == addr)));
+    }
 }
@@ -10,8 +10,18 @@
 //! - At match end, each player pair gets a win/loss/draw result
 //! - Expected score: E_A = 1 / (1 + 10^((R_B - R_A)/400))
 //! - Rating update: ΔR_A = (K/(N-1)) * Σ(Result - Expected)
+//!
+//! A match played over several rounds can instead be rated once, on its
+//! final standings (`EloCalculator::with_placement`), so a single
+//! high-scoring round can't outweigh the rest of the match.
+//!
+//! In team games each team is one side: its members' points are summed,
+//! it's rated on its members' average, and every member moves by the
+//! team's rating change.
 
-use std::collections::HashMap;
+use crate::json;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, VecDeque};
 
 /// Default K factor for Elo calculations
 pub const DEFAULT_K: f64 = 32.0;
@@ -19,6 +29,24 @@ pub const DEFAULT_K: f64 = 32.0;
 /// Default starting Elo rating
 pub const DEFAULT_ELO: f64 = 1200.0;
 
+/// Low bits of a match_id reserved for the per-host match counter
+pub const MATCH_COUNTER_BITS: u32 = 16;
+
+/// Name a team goes by on the scoreboard
+pub fn team_name(team: u8) -> String {
+    format!("Team {}", team)
+}
+
+/// Build a match_id from the host's wall clock and its per-host counter.
+///
+/// The timestamp occupies the high bits so ids from different hosts still
+/// interleave roughly chronologically; the counter in the low bits keeps ids
+/// from one host unique even if its clock stalls or jumps backwards.
+pub fn make_match_id(timestamp_ms: i64, host_counter: u64) -> i64 {
+    let mask = (1u64 << MATCH_COUNTER_BITS) - 1;
+    (timestamp_ms << MATCH_COUNTER_BITS) | (host_counter & mask) as i64
+}
+
 /// Match result stored in the event log
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchResult {
@@ -30,6 +58,20 @@ pub struct MatchResult {
     pub host_actor_id: String,
     /// Whether the match completed successfully
     pub completed: bool,
+    /// Monotonic per-host match counter (absent in matches recorded by older builds)
+    pub host_counter: Option<u64>,
+    /// Season the match counts toward (absent in matches recorded by older builds)
+    pub season: Option<u32>,
+    /// Round the match was played in, linking it to that round's
+    /// `word_claimed` events (absent in matches recorded by older builds)
+    pub round_id: Option<i64>,
+    /// Lobby match the round belongs to, shared by every round played
+    /// there until the host starts a new match (absent in matches recorded
+    /// by older builds)
+    pub match_group: Option<i64>,
+    /// Team each player was on: (player_handle, team). Empty when nobody
+    /// played on a team
+    pub teams: Vec<(String, u8)>,
 }
 
 impl MatchResult {
@@ -40,22 +82,110 @@ impl MatchResult {
             scores,
             host_actor_id,
             completed: true,
+            host_counter: None,
+            season: None,
+            round_id: None,
+            match_group: None,
+            teams: Vec::new(),
         }
     }
 
+    /// Create a match result stamped with the host's clock and match counter
+    pub fn with_host_counter(
+        timestamp_ms: i64,
+        host_counter: u64,
+        scores: Vec<(String, u32)>,
+        host_actor_id: String,
+    ) -> Self {
+        MatchResult {
+            match_id: make_match_id(timestamp_ms, host_counter),
+            scores,
+            host_actor_id,
+            completed: true,
+            host_counter: Some(host_counter),
+            season: None,
+            round_id: None,
+            match_group: None,
+            teams: Vec::new(),
+        }
+    }
+
+    /// Tag the match with the season it counts toward
+    pub fn in_season(mut self, season: u32) -> Self {
+        self.season = Some(season);
+        self
+    }
+
+    /// Link the match to the round its claims were logged under
+    pub fn for_round(mut self, round_id: i64) -> Self {
+        self.round_id = Some(round_id);
+        self
+    }
+
+    /// Mark the round as part of the lobby match `match_group`
+    pub fn in_match_group(mut self, match_group: i64) -> Self {
+        self.match_group = Some(match_group);
+        self
+    }
+
+    /// Record which team each player was on
+    pub fn with_teams(mut self, teams: Vec<(String, u8)>) -> Self {
+        self.teams = teams;
+        self
+    }
+
+    /// Team a player was on, if any
+    pub fn team_of(&self, player: &str) -> Option<u8> {
+        self.teams.iter().find(|(name, _)| name == player).map(|(_, team)| *team)
+    }
+
+    /// The sides that competed: each team, and each player not on one
+    ///
+    /// Returns (name, members, combined score) in the order sides first
+    /// appear in `scores`.
+    pub fn sides(&self) -> Vec<(String, Vec<String>, u32)> {
+        let mut sides: Vec<(Option<u8>, String, Vec<String>, u32)> = Vec::new();
+        for (name, score) in &self.scores {
+            let team = self.team_of(name);
+            match sides.iter_mut().find(|side| team.is_some() && side.0 == team) {
+                Some((_, _, members, total)) => {
+                    members.push(name.clone());
+                    *total += score;
+                }
+                None => {
+                    let side_name = team.map(team_name).unwrap_or_else(|| name.clone());
+                    sides.push((team, side_name, vec![name.clone()], *score));
+                }
+            }
+        }
+        sides.into_iter().map(|(_, name, members, total)| (name, members, total)).collect()
+    }
+
+    /// Combined score of each side, highest first
+    pub fn team_scores(&self) -> Vec<(String, u32)> {
+        let mut scores: Vec<(String, u32)> =
+            self.sides().into_iter().map(|(name, _, total)| (name, total)).collect();
+        scores.sort_by(|a, b| b.1.cmp(&a.1));
+        scores
+    }
+
     /// Parse match result from JSON payload
     pub fn from_json(json: &str) -> Option<Self> {
-        // Simple JSON parsing without serde
-        let match_id = extract_i64(json, "match_id")?;
-        let host_actor_id = extract_string(json, "host_actor_id")?;
-        let completed = extract_bool(json, "completed").unwrap_or(true);
-        let scores = extract_scores(json)?;
+        Self::from_value(&json::parse(json)?)
+    }
 
+    /// Read a match result from an already parsed payload
+    pub(crate) fn from_value(value: &json::Value) -> Option<Self> {
         Some(MatchResult {
-            match_id,
-            scores,
-            host_actor_id,
-            completed,
+            match_id: value.number_field("match_id")?,
+            scores: extract_pairs(value, "scores")?,
+            host_actor_id: value.str_field("host_actor_id")?,
+            completed: value.bool_field("completed").unwrap_or(true),
+            host_counter: value.number_field("host_counter"),
+            season: value.number_field("season"),
+            round_id: value.number_field("round_id"),
+            match_group: value.number_field("match_group"),
+            teams: teams_of(value),
         })
     }
 
@@ -68,12 +198,44 @@ impl MatchResult {
             .collect::<Vec<_>>()
             .join(",");
 
+        let counter_json = self
+            .host_counter
+            .map(|c| format!(r#","host_counter":{}"#, c))
+            .unwrap_or_default();
+        let season_json = self
+            .season
+            .map(|s| format!(r#","season":{}"#, s))
+            .unwrap_or_default();
+        let round_json = self
+            .round_id
+            .map(|r| format!(r#","round_id":{}"#, r))
+            .unwrap_or_default();
+        let group_json = self
+            .match_group
+            .map(|g| format!(r#","match_group":{}"#, g))
+            .unwrap_or_default();
+        let teams_json = if self.teams.is_empty() {
+            String::new()
+        } else {
+            let teams: Vec<String> = self
+                .teams
+                .iter()
+                .map(|(name, team)| format!(r#"["{}",{}]"#, escape_json(name), team))
+                .collect();
+            format!(r#","teams":[{}]"#, teams.join(","))
+        };
+
         format!(
-            r#"{{"match_id":{},"scores":[{}],"host_actor_id":"{}","completed":{}}}"#,
+            r#"{{"match_id":{},"scores":[{}],"host_actor_id":"{}","completed":{}{}{}{}{}{}}}"#,
             self.match_id,
             scores_json,
             escape_json(&self.host_actor_id),
-            self.completed
+            self.completed,
+            counter_json,
+            season_json,
+            round_json,
+            group_json,
+            teams_json
         )
     }
 
@@ -82,10 +244,107 @@ impl MatchResult {
         self.scores.len()
     }
 
-    /// Check if this is a multiplayer match (2+ players)
+    /// Check if this is a multiplayer match (2+ sides)
     pub fn is_multiplayer(&self) -> bool {
-        self.scores.len() >= 2
+        self.scores.len() >= 2 && (self.teams.is_empty() || self.sides().len() >= 2)
+    }
+
+    /// Sum the rounds of one match into a single result of total points
+    ///
+    /// Takes the match_id and host details from the first round. The match
+    /// is only complete if every round was. Returns `None` with no rounds.
+    pub fn combine_rounds(rounds: &[MatchResult]) -> Option<MatchResult> {
+        let first = rounds.first()?;
+        let mut totals: Vec<(String, u32)> = Vec::new();
+        for (name, score) in rounds.iter().flat_map(|r| &r.scores) {
+            match totals.iter_mut().find(|(n, _)| n == name) {
+                Some((_, total)) => *total += score,
+                None => totals.push((name.clone(), *score)),
+            }
+        }
+        Some(MatchResult {
+            scores: totals,
+            completed: rounds.iter().all(|r| r.completed),
+            ..first.clone()
+        })
     }
+
+    /// Replace each score with placement points: the number of players
+    /// ranked below. Ties share a placement.
+    ///
+    /// Only the order survives, not the margins. In a team game sides are
+    /// placed instead, and each side's placement points go to its first
+    /// member so the side still totals them.
+    pub fn by_placement(&self) -> MatchResult {
+        let sides = self.sides();
+        let scores = self
+            .scores
+            .iter()
+            .map(|(name, score)| {
+                if self.teams.is_empty() {
+                    let beaten = self.scores.iter().filter(|(_, other)| other < score).count();
+                    return (name.clone(), beaten as u32);
+                }
+                let Some((_, members, total)) = sides.iter().find(|s| s.1.contains(name)) else {
+                    return (name.clone(), 0);
+                };
+                let beaten = sides.iter().filter(|(_, _, other)| other < total).count();
+                let points = if members[0] == *name { beaten as u32 } else { 0 };
+                (name.clone(), points)
+            })
+            .collect();
+        MatchResult {
+            scores,
+            ..self.clone()
+        }
+    }
+}
+
+/// Order matches for deterministic replay.
+///
+/// Matches are ordered by match_id, except that matches from the same host
+/// carrying a `host_counter` always replay in counter order. This keeps a
+/// host whose clock jumped backwards from reordering its own matches, while
+/// matches from different hosts still interleave by match_id. Legacy matches
+/// without a counter fall back to plain match_id ordering.
+pub fn sort_for_replay(matches: &mut [MatchResult]) {
+    // One queue per host with counters, and one for every legacy match.
+    // Each match carries a tie-break rank: 0 ahead of a host's counter
+    // queue, its original position for a legacy match.
+    let mut by_host: BTreeMap<&str, Vec<(usize, &MatchResult)>> = BTreeMap::new();
+    let mut legacy: Vec<(usize, &MatchResult)> = Vec::new();
+    for (i, m) in matches.iter().enumerate() {
+        match m.host_counter {
+            Some(_) => by_host.entry(&m.host_actor_id).or_default().push((0, m)),
+            None => legacy.push((i + 1, m)),
+        }
+    }
+    for queue in by_host.values_mut() {
+        queue.sort_by_key(|(_, m)| (m.host_counter, m.match_id));
+    }
+    legacy.sort_by_key(|&(rank, m)| (m.match_id, &m.host_actor_id, rank));
+    let mut queues: Vec<VecDeque<(usize, &MatchResult)>> =
+        by_host.into_values().chain([legacy]).map(VecDeque::from).collect();
+
+    // Merge queue heads by (match_id, host_actor_id)
+    let head = |q: usize, &(rank, m): &(usize, &MatchResult)| {
+        Reverse((m.match_id, m.host_actor_id.clone(), rank, q))
+    };
+    let mut heads: BinaryHeap<_> = queues
+        .iter()
+        .enumerate()
+        .filter_map(|(q, queue)| queue.front().map(|entry| head(q, entry)))
+        .collect();
+    let mut ordered = Vec::with_capacity(matches.len());
+    while let Some(Reverse((_, _, _, q))) = heads.pop() {
+        let (_, m) = queues[q].pop_front().expect("queue has a head");
+        ordered.push(m.clone());
+        if let Some(entry) = queues[q].front() {
+            heads.push(head(q, entry));
+        }
+    }
+
+    matches.clone_from_slice(&ordered);
 }
 
 /// Player lifetime statistics
@@ -141,6 +400,9 @@ pub struct EloCalculator {
     k_factor: f64,
     /// Current ratings for all known players
     ratings: HashMap<String, f64>,
+    /// Whether `replay_matches` rates a multi-round match on its final
+    /// standings rather than round by round
+    placement: bool,
 }
 
 impl EloCalculator {
@@ -149,6 +411,7 @@ impl EloCalculator {
         EloCalculator {
             k_factor: DEFAULT_K,
             ratings: HashMap::new(),
+            placement: false,
         }
     }
 
@@ -157,9 +420,20 @@ impl EloCalculator {
         EloCalculator {
             k_factor,
             ratings: HashMap::new(),
+            placement: false,
         }
     }
 
+    /// Rate each multi-round match once, on its final standings, when
+    /// replaying
+    ///
+    /// Rounds are grouped by `match_group`; rounds without one are still
+    /// rated on their own.
+    pub fn with_placement(mut self, placement: bool) -> Self {
+        self.placement = placement;
+        self
+    }
+
     /// Get a player's current rating (or default if new)
     pub fn rating(&self, player: &str) -> f64 {
         self.ratings.get(player).copied().unwrap_or(DEFAULT_ELO)
@@ -189,31 +463,34 @@ impl EloCalculator {
     /// Uses pairwise comparisons for multiplayer (Section 10.2 of PRD):
     /// - For every pair (A,B): if S_A > S_B, A gets 1.0; tie = 0.5; else 0.0
     /// - ΔR_A = (K/(N-1)) * Σ(Result - Expected)
+    ///
+    /// In a team game the pairs are teams, and each member moves by their
+    /// team's change.
     pub fn process_match(&mut self, result: &MatchResult) {
         if !result.completed || !result.is_multiplayer() {
             return;
         }
 
-        let n = result.player_count();
-        let k_adjusted = self.k_factor / (n - 1) as f64;
-
-        // Collect current ratings for all players
-        let player_ratings: Vec<(String, u32, f64)> = result
-            .scores
-            .iter()
-            .map(|(name, score)| {
-                let rating = self.rating(name);
-                (name.clone(), *score, rating)
+        // Each side (a player, or a team on its members' average rating)
+        let sides: Vec<(Vec<String>, u32, f64)> = result
+            .sides()
+            .into_iter()
+            .map(|(_, members, score)| {
+                let rating = members.iter().map(|m| self.rating(m)).sum::<f64>()
+                    / members.len() as f64;
+                (members, score, rating)
             })
             .collect();
+        let n = sides.len();
+        let k_adjusted = self.k_factor / (n - 1) as f64;
 
-        // Calculate rating changes for each player using pairwise comparisons
+        // Calculate rating changes for each side using pairwise comparisons
         let mut rating_changes: HashMap<String, f64> = HashMap::new();
 
-        for (i, (player_a, score_a, rating_a)) in player_ratings.iter().enumerate() {
+        for (i, (members, score_a, rating_a)) in sides.iter().enumerate() {
             let mut total_change = 0.0;
 
-            for (j, (_, score_b, rating_b)) in player_ratings.iter().enumerate() {
+            for (j, (_, score_b, rating_b)) in sides.iter().enumerate() {
                 if i == j {
                     continue;
                 }
@@ -232,7 +509,9 @@ impl EloCalculator {
                 total_change += k_adjusted * (actual - expected);
             }
 
-            rating_changes.insert(player_a.clone(), total_change);
+            for member in members {
+                rating_changes.insert(member.clone(), total_change);
+            }
         }
 
         // Apply rating changes
@@ -242,20 +521,52 @@ impl EloCalculator {
         }
     }
 
+    /// Rate a multi-round match once, on its final standings
+    ///
+    /// Players are ranked by total points across the rounds and each pair
+    /// scores a win, loss or draw from those ranks. How big a lead was, or
+    /// how it was built up round by round, doesn't matter.
+    pub fn process_match_placements(&mut self, rounds: &[MatchResult]) {
+        if let Some(combined) = MatchResult::combine_rounds(rounds) {
+            self.process_match(&combined.by_placement());
+        }
+    }
+
     /// Replay a list of matches in order to compute final ratings
     ///
-    /// Matches are sorted by match_id to ensure deterministic results
-    /// after CRDT merge (Section 10.3 of PRD)
+    /// Matches are sorted with `sort_for_replay` to ensure deterministic
+    /// results after CRDT merge (Section 10.3 of PRD)
     pub fn replay_matches(&mut self, matches: &mut [MatchResult]) {
-        // Sort by match_id for deterministic ordering
-        matches.sort_by_key(|m| m.match_id);
+        // Sort for deterministic ordering, tolerant of host clock skew
+        sort_for_replay(matches);
 
         // Reset ratings
         self.ratings.clear();
 
-        // Process each match in order
-        for result in matches {
-            self.process_match(result);
+        if !self.placement {
+            // Process each match in order
+            for result in matches {
+                self.process_match(result);
+            }
+            return;
+        }
+
+        // Each multi-round match is rated once its last round comes up
+        let mut groups: HashMap<i64, Vec<MatchResult>> = HashMap::new();
+        for result in matches.iter() {
+            if let Some(group) = result.match_group {
+                groups.entry(group).or_default().push(result.clone());
+            }
+        }
+        for result in matches.iter() {
+            let Some(group) = result.match_group else {
+                self.process_match(result);
+                continue;
+            };
+            if groups[&group].last().map(|r| r.match_id) == Some(result.match_id) {
+                let rounds = groups.remove(&group).unwrap_or_default();
+                self.process_match_placements(&rounds);
+            }
         }
     }
 }
@@ -273,6 +584,9 @@ pub struct StatsTracker {
     stats: HashMap<String, PlayerStats>,
     /// Elo calculator
     elo: EloCalculator,
+    /// Record of each player against each opponent: (player, opponent) ->
+    /// (wins, losses, draws), from the player's side
+    head_to_head: HashMap<(String, String), (u32, u32, u32)>,
 }
 
 impl StatsTracker {
@@ -305,22 +619,50 @@ impl StatsTracker {
             return;
         }
 
-        // Find winner(s)
-        let max_score = result.scores.iter().map(|(_, s)| *s).max().unwrap_or(0);
+        // Find winner(s): every member of the top side(s) wins
+        let sides = result.sides();
+        let max_score = sides.iter().map(|(_, _, s)| *s).max().unwrap_or(0);
+        let winners: Vec<&String> = sides
+            .iter()
+            .filter(|(_, _, s)| *s == max_score)
+            .flat_map(|(_, members, _)| members)
+            .collect();
 
         // Update stats for each player
         for (handle, score) in &result.scores {
+            let is_winner = winners.contains(&handle);
             let stats = self.get_or_create(handle);
             stats.rounds_played += 1;
             stats.total_points += score;
             if *score > stats.best_score {
                 stats.best_score = *score;
             }
-            if result.is_multiplayer() && *score == max_score {
+            if result.is_multiplayer() && is_winner {
                 stats.wins += 1;
             }
         }
 
+        // Every pair of players in the match is one head-to-head result
+        for (i, (me, my_score)) in result.scores.iter().enumerate() {
+            for (them, their_score) in &result.scores[i + 1..] {
+                if me == them {
+                    continue;
+                }
+                let mine = self.head_to_head.entry((me.clone(), them.clone())).or_default();
+                match my_score.cmp(their_score) {
+                    std::cmp::Ordering::Greater => mine.0 += 1,
+                    std::cmp::Ordering::Less => mine.1 += 1,
+                    std::cmp::Ordering::Equal => mine.2 += 1,
+                }
+                let theirs = self.head_to_head.entry((them.clone(), me.clone())).or_default();
+                match their_score.cmp(my_score) {
+                    std::cmp::Ordering::Greater => theirs.0 += 1,
+                    std::cmp::Ordering::Less => theirs.1 += 1,
+                    std::cmp::Ordering::Equal => theirs.2 += 1,
+                }
+            }
+        }
+
         // Update Elo for multiplayer matches
         if result.is_multiplayer() {
             self.elo.process_match(result);
@@ -342,6 +684,14 @@ impl StatsTracker {
         }
     }
 
+    /// `me`'s record against `them`: (wins, losses, draws)
+    pub fn record_vs(&self, me: &str, them: &str) -> (u32, u32, u32) {
+        self.head_to_head
+            .get(&(me.to_string(), them.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Get Elo leaderboard (sorted by rating)
     pub fn elo_leaderboard(&self) -> Vec<(String, f64)> {
         self.elo.leaderboard()
@@ -363,109 +713,34 @@ impl StatsTracker {
         // Clear existing stats
         self.stats.clear();
         self.elo = EloCalculator::new();
+        self.head_to_head.clear();
 
         // Sort and process matches
-        matches.sort_by_key(|m| m.match_id);
+        sort_for_replay(matches);
         for result in matches {
             self.process_match(result);
         }
     }
 }
 
-// Helper functions for simple JSON parsing
-
-fn extract_string(json: &str, key: &str) -> Option<String> {
-    let pattern = format!(r#""{}":""#, key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = &json[start..];
-    let end = find_unescaped_quote(rest)?;
-    Some(unescape_json(&rest[..end]))
-}
-
-fn extract_i64(json: &str, key: &str) -> Option<i64> {
-    let pattern = format!(r#""{}":"#, key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = &json[start..];
-    let end = rest.find(|c: char| !c.is_ascii_digit() && c != '-').unwrap_or(rest.len());
-    rest[..end].parse().ok()
-}
-
-fn extract_bool(json: &str, key: &str) -> Option<bool> {
-    let pattern = format!(r#""{}":"#, key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = &json[start..].trim_start();
-    if rest.starts_with("true") {
-        Some(true)
-    } else if rest.starts_with("false") {
-        Some(false)
-    } else {
-        None
-    }
-}
-
-fn extract_scores(json: &str) -> Option<Vec<(String, u32)>> {
-    let pattern = r#""scores":["#;
-    let start = json.find(pattern)? + pattern.len();
-    let rest = &json[start..];
-
-    // Find matching closing bracket
-    let mut depth = 1;
-    let mut end = 0;
-    for (i, c) in rest.chars().enumerate() {
-        match c {
-            '[' => depth += 1,
-            ']' => {
-                depth -= 1;
-                if depth == 0 {
-                    end = i;
-                    break;
-                }
-            }
-            _ => {}
-        }
-    }
-
-    let array = &rest[..end];
-    let mut scores = Vec::new();
-    let mut current = array;
-
-    while let Some(start) = current.find('[') {
-        let inner = &current[start + 1..];
-        if let Some(end) = inner.find(']') {
-            let item = &inner[..end];
-            // Parse ["name", score]
-            if let Some(comma) = item.find(',') {
-                let name = item[..comma].trim().trim_matches('"');
-                let score_str = item[comma + 1..].trim();
-                if let Ok(score) = score_str.parse() {
-                    scores.push((unescape_json(name), score));
-                }
-            }
-            current = &inner[end + 1..];
-        } else {
-            break;
-        }
-    }
+// Helper functions for reading JSON payloads
 
-    Some(scores)
+/// Teams from a parsed match payload (empty when it has none)
+pub(crate) fn teams_of(value: &json::Value) -> Vec<(String, u8)> {
+    extract_pairs(value, "teams")
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(name, team)| Some((name, u8::try_from(team).ok()?)))
+        .collect()
 }
 
-fn find_unescaped_quote(s: &str) -> Option<usize> {
-    let mut i = 0;
-    let bytes = s.as_bytes();
-    while i < bytes.len() {
-        if bytes[i] == b'"' {
-            return Some(i);
-        } else if bytes[i] == b'\\' && i + 1 < bytes.len() {
-            i += 2;
-        } else {
-            i += 1;
-        }
-    }
-    None
+/// Read a `"key":[["name", n], ...]` list of pairs, skipping malformed ones
+pub(crate) fn extract_pairs(value: &json::Value, key: &str) -> Option<Vec<(String, u32)>> {
+    let items = value.get(key)?.as_array()?;
+    Some(items.iter().filter_map(json::Value::as_pair).collect())
 }
 
-fn escape_json(s: &str) -> String {
+pub(crate) fn escape_json(s: &str) -> String {
     s.replace('\\', "\\\\")
         .replace('"', "\\\"")
         .replace('\n', "\\n")
@@ -473,33 +748,12 @@ fn escape_json(s: &str) -> String {
         .replace('\t', "\\t")
 }
 
-fn unescape_json(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    let mut chars = s.chars().peekable();
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            match chars.next() {
-                Some('"') => result.push('"'),
-                Some('\\') => result.push('\\'),
-                Some('n') => result.push('\n'),
-                Some('r') => result.push('\r'),
-                Some('t') => result.push('\t'),
-                Some(other) => {
-                    result.push('\\');
-                    result.push(other);
-                }
-                None => result.push('\\'),
-            }
-        } else {
-            result.push(c);
-        }
-    }
-    result
-}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
 
     #[test]
     fn test_expected_score() {
@@ -602,6 +856,160 @@ mod tests {
         assert!((calc1.rating("B") - calc2.rating("B")).abs() < 0.001);
     }
 
+    #[test]
+    fn test_make_match_id_orders_by_timestamp_then_counter() {
+        assert!(make_match_id(1000, 5) < make_match_id(1001, 1));
+        assert!(make_match_id(1000, 1) < make_match_id(1000, 2));
+        assert_eq!(make_match_id(1000, 3) & 0xFFFF, 3);
+    }
+
+    #[test]
+    fn test_replay_tolerates_backwards_clock() {
+        // Host's clock jumped backwards between its first and second match
+        let first = MatchResult::with_host_counter(
+            2_000_000,
+            1,
+            vec![("A".to_string(), 50), ("B".to_string(), 10)],
+            "h".to_string(),
+        );
+        let second = MatchResult::with_host_counter(
+            1_000_000,
+            2,
+            vec![("A".to_string(), 10), ("B".to_string(), 50)],
+            "h".to_string(),
+        );
+        assert!(second.match_id < first.match_id);
+
+        let mut expected = EloCalculator::new();
+        expected.process_match(&first);
+        expected.process_match(&second);
+
+        let mut calc = EloCalculator::new();
+        calc.replay_matches(&mut [second.clone(), first.clone()]);
+
+        assert!((calc.rating("A") - expected.rating("A")).abs() < 0.001);
+        assert!((calc.rating("B") - expected.rating("B")).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_sort_for_replay_interleaves_hosts() {
+        let skewed_1 = MatchResult::with_host_counter(5000, 1, vec![], "skewed".to_string());
+        let skewed_2 = MatchResult::with_host_counter(1000, 2, vec![], "skewed".to_string());
+        let other = MatchResult::with_host_counter(3000, 1, vec![], "other".to_string());
+        let legacy = MatchResult::new(make_match_id(2000, 0), vec![], "old".to_string());
+
+        let mut matches = vec![skewed_2.clone(), other.clone(), legacy.clone(), skewed_1.clone()];
+        sort_for_replay(&mut matches);
+
+        assert_eq!(matches, vec![legacy, other, skewed_1, skewed_2]);
+    }
+
+    #[test]
+    fn test_sort_for_replay_many_legacy_matches() {
+        // Legacy matches from several hosts, interleaved with a counted host
+        let mut matches: Vec<MatchResult> = (0..20_000)
+            .rev()
+            .map(|i| match i % 3 {
+                0 => MatchResult::with_host_counter(1_000 + i, i as u64, vec![], "new".to_string()),
+                host => {
+                    MatchResult::new(make_match_id(1_000 + i, 0), vec![], format!("old{}", host))
+                }
+            })
+            .collect();
+        sort_for_replay(&mut matches);
+        assert!(matches.windows(2).all(|pair| pair[0].match_id < pair[1].match_id));
+    }
+
+    #[test]
+    fn test_host_counter_json_roundtrip() {
+        let result = MatchResult::with_host_counter(
+            1_700_000_000_000,
+            7,
+            vec![("Alice".to_string(), 12)],
+            "actor".to_string(),
+        );
+        let parsed = MatchResult::from_json(&result.to_json()).unwrap();
+        assert_eq!(parsed, result);
+
+        // Legacy payloads have no counter
+        let legacy = MatchResult::new(1, vec![("Alice".to_string(), 12)], "actor".to_string());
+        assert_eq!(MatchResult::from_json(&legacy.to_json()).unwrap().host_counter, None);
+    }
+
+    #[test]
+    fn test_match_result_season_roundtrip() {
+        let result = MatchResult::new(1, vec![("Alice".to_string(), 12)], "actor".to_string())
+            .in_season(3);
+        let parsed = MatchResult::from_json(&result.to_json()).unwrap();
+        assert_eq!(parsed.season, Some(3));
+        assert_eq!(parsed, result);
+
+        let untagged = MatchResult::new(1, vec![("Alice".to_string(), 12)], "actor".to_string());
+        assert_eq!(MatchResult::from_json(&untagged.to_json()).unwrap().season, None);
+    }
+
+    #[test]
+    fn test_match_result_round_roundtrip() {
+        let result = MatchResult::new(1, vec![("Alice".to_string(), 12)], "actor".to_string())
+            .for_round(1704067140000);
+        let parsed = MatchResult::from_json(&result.to_json()).unwrap();
+        assert_eq!(parsed.round_id, Some(1704067140000));
+        assert_eq!(parsed, result);
+    }
+
+    #[test]
+    fn test_match_result_group_roundtrip() {
+        let result = MatchResult::new(1, vec![("Alice".to_string(), 12)], "actor".to_string())
+            .in_match_group(42);
+        let parsed = MatchResult::from_json(&result.to_json()).unwrap();
+        assert_eq!(parsed.match_group, Some(42));
+        assert_eq!(parsed, result);
+        let legacy = r#"{"match_id":1,"scores":[],"host_actor_id":"a"}"#;
+        assert_eq!(MatchResult::from_json(legacy).unwrap().match_group, None);
+    }
+
+    #[test]
+    fn test_team_match_rates_teams_as_sides() {
+        // Alice and Bob (team 1) each score less than Carol (team 2), but
+        // more together
+        let result = MatchResult::new(
+            1,
+            vec![
+                ("Carol".to_string(), 9),
+                ("Alice".to_string(), 7),
+                ("Bob".to_string(), 5),
+            ],
+            "host1".to_string(),
+        )
+        .with_teams(vec![
+            ("Alice".to_string(), 1),
+            ("Bob".to_string(), 1),
+            ("Carol".to_string(), 2),
+        ]);
+        assert_eq!(
+            result.team_scores(),
+            vec![("Team 1".to_string(), 12), ("Team 2".to_string(), 9)]
+        );
+        let parsed = MatchResult::from_json(&result.to_json()).unwrap();
+        assert_eq!(parsed, result);
+
+        let mut calc = EloCalculator::new();
+        calc.process_match(&result);
+        assert!(calc.rating("Alice") > DEFAULT_ELO);
+        assert!((calc.rating("Alice") - calc.rating("Bob")).abs() < 0.001);
+        let team_change = calc.rating("Alice") - DEFAULT_ELO;
+        assert!((team_change + calc.rating("Carol") - DEFAULT_ELO).abs() < 0.001);
+
+        let mut tracker = StatsTracker::new();
+        tracker.process_match(&result);
+        assert_eq!(tracker.get("Bob").unwrap().wins, 1);
+        assert_eq!(tracker.get("Carol").unwrap().wins, 0);
+
+        // Placement keeps the teams' order
+        let placed = result.by_placement();
+        assert_eq!(placed.team_scores()[0], ("Team 1".to_string(), 1));
+    }
+
     #[test]
     fn test_solo_match_ignored() {
         let mut calc = EloCalculator::new();
@@ -750,6 +1158,38 @@ mod tests {
         assert_eq!(b_stats.wins, 1);
     }
 
+    #[test]
+    fn test_head_to_head_win_loss_draw() {
+        let ab = |a: u32, b: u32| vec![("Alice".to_string(), a), ("Bob".to_string(), b)];
+        let mut matches = vec![
+            MatchResult::new(3, ab(20, 20), "h".to_string()),
+            MatchResult::new(1, ab(50, 30), "h".to_string()),
+            MatchResult::new(2, ab(10, 40), "h".to_string()),
+        ];
+
+        let mut tracker = StatsTracker::new();
+        tracker.rebuild_from_matches(&mut matches);
+        assert_eq!(tracker.record_vs("Alice", "Bob"), (1, 1, 1));
+        assert_eq!(tracker.record_vs("Bob", "Alice"), (1, 1, 1));
+        assert_eq!(tracker.record_vs("Alice", "Carol"), (0, 0, 0));
+
+        // A three-player match counts against each opponent separately
+        let three = MatchResult::new(
+            4,
+            vec![("Alice".to_string(), 30), ("Bob".to_string(), 10), ("Carol".to_string(), 40)],
+            "h".to_string(),
+        );
+        tracker.process_match(&three);
+        assert_eq!(tracker.record_vs("Alice", "Bob"), (2, 1, 1));
+        assert_eq!(tracker.record_vs("Alice", "Carol"), (0, 1, 0));
+        assert_eq!(tracker.record_vs("Carol", "Bob"), (1, 0, 0));
+
+        // Rebuilding starts over rather than adding to what's there
+        tracker.rebuild_from_matches(&mut matches);
+        assert_eq!(tracker.record_vs("Alice", "Bob"), (1, 1, 1));
+        assert_eq!(tracker.record_vs("Carol", "Bob"), (0, 0, 0));
+    }
+
     #[test]
     fn test_elo_zero_sum_two_players() {
         // Elo changes should sum to zero for equal-rated players
@@ -850,6 +1290,99 @@ mod tests {
         assert!(high_k_change > low_k_change, "Higher K should produce larger changes");
     }
 
+    #[test]
+    fn test_placement_elo_ignores_score_magnitude() {
+        let round = |scores: &[(&str, u32)]| {
+            MatchResult::new(
+                1,
+                scores.iter().map(|(n, s)| (n.to_string(), *s)).collect(),
+                "h".to_string(),
+            )
+        };
+        // Same final standings (A, B, C), very different point totals
+        let blowout = [round(&[("A", 90), ("B", 40), ("C", 1)]), round(&[("A", 80), ("B", 45), ("C", 2)])];
+        let close = [round(&[("A", 3), ("B", 2), ("C", 1)]), round(&[("A", 2), ("B", 1), ("C", 1)])];
+
+        let mut calc_blowout = EloCalculator::new();
+        calc_blowout.process_match_placements(&blowout);
+        let mut calc_close = EloCalculator::new();
+        calc_close.process_match_placements(&close);
+
+        for player in ["A", "B", "C"] {
+            assert!(
+                (calc_blowout.rating(player) - calc_close.rating(player)).abs() < 1e-9,
+                "{} should move the same in both matches",
+                player
+            );
+        }
+        assert!(calc_close.rating("A") > DEFAULT_ELO);
+        assert!(calc_close.rating("C") < DEFAULT_ELO);
+    }
+
+    #[test]
+    fn test_placement_elo_rates_match_not_rounds() {
+        let round = |a: u32, b: u32| {
+            MatchResult::new(1, vec![("A".to_string(), a), ("B".to_string(), b)], "h".to_string())
+        };
+        // A takes the match on one big round; B wins the other two narrowly
+        let rounds = [round(40, 5), round(1, 2), round(1, 2)];
+
+        let mut per_round = EloCalculator::new();
+        for r in &rounds {
+            per_round.process_match(r);
+        }
+        let mut placement = EloCalculator::new();
+        placement.process_match_placements(&rounds);
+
+        assert!(per_round.rating("B") > DEFAULT_ELO);
+        assert!(placement.rating("A") > DEFAULT_ELO);
+        assert!(placement.rating("B") < DEFAULT_ELO);
+    }
+
+    #[test]
+    fn test_replay_with_placement_rates_each_match_once() {
+        let round = |id: i64, group: i64, a: u32, b: u32| {
+            MatchResult::new(id, vec![("A".to_string(), a), ("B".to_string(), b)], "h".to_string())
+                .in_match_group(group)
+        };
+        // Match 7: A wins on one big round, B takes two narrow ones
+        let mut matches = vec![round(1, 7, 40, 5), round(2, 7, 1, 2), round(3, 7, 1, 2)];
+
+        let mut per_round = EloCalculator::new();
+        per_round.replay_matches(&mut matches);
+        let mut placement = EloCalculator::new().with_placement(true);
+        placement.replay_matches(&mut matches);
+
+        let mut expected = EloCalculator::new();
+        expected.process_match_placements(&matches);
+        assert!(per_round.rating("B") > DEFAULT_ELO);
+        assert_eq!(placement.rating("A"), expected.rating("A"));
+        assert_eq!(placement.rating("B"), expected.rating("B"));
+
+        // A round outside any match is still rated alone
+        let scores = vec![("A".to_string(), 1), ("B".to_string(), 9)];
+        matches.push(MatchResult::new(4, scores, "h".to_string()));
+        let mut placement = EloCalculator::new().with_placement(true);
+        placement.replay_matches(&mut matches);
+        assert!(placement.rating("A") < expected.rating("A"));
+    }
+
+    #[test]
+    fn test_combine_rounds_sums_totals() {
+        let first = MatchResult::new(7, vec![("A".to_string(), 5), ("B".to_string(), 3)], "h".to_string());
+        let mut second = MatchResult::new(8, vec![("B".to_string(), 9), ("C".to_string(), 1)], "h".to_string());
+        second.completed = false;
+
+        let combined = MatchResult::combine_rounds(&[first, second]).unwrap();
+        assert_eq!(combined.match_id, 7);
+        assert_eq!(
+            combined.scores,
+            vec![("A".to_string(), 5), ("B".to_string(), 12), ("C".to_string(), 1)]
+        );
+        assert!(!combined.completed);
+        assert!(MatchResult::combine_rounds(&[]).is_none());
+    }
+
     #[test]
     fn test_match_result_json_special_chars() {
         let result = MatchResult::new(
@@ -863,6 +1396,27 @@ mod tests {
         assert_eq!(result.host_actor_id, parsed.host_actor_id);
     }
 
+    #[test]
+    fn test_match_result_adversarial_payloads() {
+        // Names that spell out keys and brackets of the payload itself
+        let result = MatchResult::new(
+            7,
+            vec![("\"points\"".to_string(), 50), ("\"scores\":[[\"x\",9]]".to_string(), 30)],
+            "\"match_id\":1".to_string(),
+        )
+        .with_teams(vec![("\"points\"".to_string(), 1)]);
+        assert_eq!(MatchResult::from_json(&result.to_json()), Some(result));
+
+        // Fields of nested objects don't stand in for the match's own
+        let nested = r#"{"meta":{"match_id":1,"scores":[["Eve",99]]},"match_id":2,
+            "scores":[["Al",5]],"host_actor_id":"h"}"#;
+        let parsed = MatchResult::from_json(nested).unwrap();
+        assert_eq!(parsed.match_id, 2);
+        assert_eq!(parsed.scores, vec![("Al".to_string(), 5)]);
+        let missing = r#"{"meta":{"match_id":1},"scores":[],"host_actor_id":"h"}"#;
+        assert_eq!(MatchResult::from_json(missing), None);
+    }
+
     #[test]
     fn test_match_result_player_count() {
         let single = MatchResult::new(1, vec![("A".to_string(), 50)], "h".to_string());
@@ -961,4 +1515,39 @@ mod tests {
         assert_eq!(tracker.get("X").unwrap().rounds_played, 1);
         assert_eq!(tracker.get("Y").unwrap().rounds_played, 1);
     }
+
+    #[test]
+    fn test_fuzz_match_result_roundtrip() {
+        // Names mix in everything the JSON has to escape or could mistake
+        // for structure
+        const AWKWARD_CHARS: &[char] = &[
+            'a', 'Z', '7', ' ', ',', '[', ']', '{', '}', ':', '"', '\\', '\n', '\t', 'é', '漢', '🎉',
+        ];
+        let mut rng = StdRng::seed_from_u64(0x656c_6f);
+        let text = |rng: &mut StdRng, max_len: usize| -> String {
+            let len = rng.random_range(0..=max_len);
+            (0..len).map(|_| AWKWARD_CHARS[rng.random_range(0..AWKWARD_CHARS.len())]).collect()
+        };
+
+        for i in 0..2000 {
+            let scores = (0..rng.random_range(0..=12))
+                .map(|_| (text(&mut rng, 12), rng.random()))
+                .collect();
+            let result = MatchResult {
+                match_id: rng.random(),
+                scores,
+                host_actor_id: text(&mut rng, 32),
+                completed: rng.random(),
+                host_counter: rng.random_bool(0.5).then(|| rng.random_range(0..i64::MAX as u64)),
+                season: rng.random_bool(0.5).then(|| rng.random()),
+                round_id: rng.random_bool(0.5).then(|| rng.random()),
+                match_group: rng.random_bool(0.5).then(|| rng.random()),
+                teams: (0..rng.random_range(0..=3))
+                    .map(|_| (text(&mut rng, 12), rng.random()))
+                    .collect(),
+            };
+            let json = result.to_json();
+            assert_eq!(MatchResult::from_json(&json), Some(result), "iteration {}: {}", i, json);
+        }
+    }
 }
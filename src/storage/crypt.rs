@@ -0,0 +1,157 @@
+#![allow(dead_code)]
+//! Encryption of event payloads at rest
+//!
+//! A database opened with `Storage::open_encrypted` keeps every event's
+//! `payload` encrypted with ChaCha20-Poly1305, under a key derived from the
+//! player's passphrase with Argon2id. The random salt, and a known value
+//! encrypted with the key (to tell a wrong passphrase from a right one),
+//! are stored in `meta`. Event types, timestamps, actor IDs, seqs and the
+//! match_id column stay in the clear so the indexes and vector clock keep
+//! working.
+//!
+//! The key is per device and never leaves it. Sync decrypts events before
+//! building `SyncEvent`s for a peer and encrypts what it receives under the
+//! local key, so peers exchange plaintext over the LAN as they always have
+//! and need not share a passphrase (or encrypt at all).
+
+use super::StorageError;
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::Rng;
+
+/// Length of the Argon2 salt stored in `meta`
+pub const SALT_LEN: usize = 16;
+
+/// Length of the random nonce in front of each ciphertext
+const NONCE_LEN: usize = 12;
+
+/// Marks a stored payload as encrypted: `enc1:` then base64 of nonce and
+/// ciphertext. Plain payloads are JSON, so they never start with it.
+const PREFIX: &str = "enc1:";
+
+/// What `meta.encryption_check` holds, encrypted, to verify a passphrase
+const CHECK_PLAINTEXT: &str = "blam payload key";
+
+/// Encrypts and decrypts payloads under one device's key
+#[derive(Clone)]
+pub struct PayloadCipher {
+    cipher: ChaCha20Poly1305,
+}
+
+impl std::fmt::Debug for PayloadCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the key
+        f.write_str("PayloadCipher")
+    }
+}
+
+impl PayloadCipher {
+    /// Derive the key for `passphrase` with `salt`
+    pub fn derive(passphrase: &str, salt: &[u8]) -> Result<Self, StorageError> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| StorageError::InvalidSetting(format!("can't derive key: {}", e)))?;
+        Ok(Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&key)),
+        })
+    }
+
+    /// A fresh random salt for a database being encrypted
+    pub fn generate_salt() -> [u8; SALT_LEN] {
+        let mut salt = [0u8; SALT_LEN];
+        rand::rng().fill(&mut salt);
+        salt
+    }
+
+    /// Encrypt `payload` for storage, under a fresh random nonce
+    pub fn encrypt(&self, payload: &str) -> String {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rng().fill(&mut nonce);
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), payload.as_bytes())
+            .expect("encrypting into a Vec can't fail");
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend(ciphertext);
+        format!("{}{}", PREFIX, BASE64.encode(sealed))
+    }
+
+    /// The payload stored as `stored`
+    ///
+    /// Payloads that were never encrypted pass through unchanged. Returns
+    /// `None` if the payload is encrypted but not under this key, or has
+    /// been tampered with.
+    pub fn decrypt(&self, stored: &str) -> Option<String> {
+        let Some(encoded) = stored.strip_prefix(PREFIX) else {
+            return Some(stored.to_string());
+        };
+        let sealed = BASE64.decode(encoded).ok()?;
+        if sealed.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let plaintext = self.cipher.decrypt(Nonce::from_slice(nonce), ciphertext).ok()?;
+        String::from_utf8(plaintext).ok()
+    }
+
+    /// The value to store in `meta.encryption_check`
+    pub fn check_value(&self) -> String {
+        self.encrypt(CHECK_PLAINTEXT)
+    }
+
+    /// Whether `check` (from `meta.encryption_check`) was made with this key
+    pub fn verifies(&self, check: &str) -> bool {
+        is_encrypted(check) && self.decrypt(check).as_deref() == Some(CHECK_PLAINTEXT)
+    }
+}
+
+/// Whether a stored payload is encrypted
+pub fn is_encrypted(stored: &str) -> bool {
+    stored.starts_with(PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_fresh_nonces() {
+        let cipher = PayloadCipher::derive("hunter2", &[1; SALT_LEN]).unwrap();
+        let payload = r#"{"word":"CAT","player_name":"Alice"}"#;
+        let a = cipher.encrypt(payload);
+        let b = cipher.encrypt(payload);
+        assert!(is_encrypted(&a));
+        assert!(!a.contains("Alice"));
+        assert_ne!(a, b);
+        assert_eq!(cipher.decrypt(&a).as_deref(), Some(payload));
+        assert_eq!(cipher.decrypt(&b).as_deref(), Some(payload));
+        // Plain payloads pass through
+        assert_eq!(cipher.decrypt(payload).as_deref(), Some(payload));
+    }
+
+    #[test]
+    fn test_other_keys_and_tampering_rejected() {
+        let cipher = PayloadCipher::derive("hunter2", &[1; SALT_LEN]).unwrap();
+        let wrong = PayloadCipher::derive("hunter3", &[1; SALT_LEN]).unwrap();
+        let other_salt = PayloadCipher::derive("hunter2", &[2; SALT_LEN]).unwrap();
+
+        let check = cipher.check_value();
+        assert!(cipher.verifies(&check));
+        assert!(!wrong.verifies(&check));
+        assert!(!other_salt.verifies(&check));
+        assert!(!cipher.verifies(CHECK_PLAINTEXT));
+
+        let stored = cipher.encrypt("{}");
+        let mut tampered = stored.clone().into_bytes();
+        let last = tampered.len() - 2;
+        tampered[last] = if tampered[last] == b'A' { b'B' } else { b'A' };
+        assert_eq!(cipher.decrypt(&String::from_utf8(tampered).unwrap()), None);
+        assert_eq!(cipher.decrypt("enc1:not base64!"), None);
+        assert_eq!(cipher.decrypt("enc1:AAAA"), None);
+    }
+}
@@ -0,0 +1,118 @@
+#![allow(dead_code)]
+//! Single-instance lock on a data directory.
+//!
+//! Two processes sharing one `blam.db` append events under the same actor
+//! ID and collide on `(actor_id, seq)`. The first process to open a data
+//! directory takes an exclusive SQLite lock on `blam.lock` next to the
+//! database and keeps it until it exits; the OS drops the lock if the
+//! process dies, so a crash never leaves the directory stuck.
+//!
+//! The lock is per process, not per connection: the app opens `Storage`
+//! for nearly every action and the cache rebuild opens its own connection,
+//! and none of those should trip over each other.
+
+use crate::storage::StorageError;
+use once_cell::sync::Lazy;
+use rusqlite::{Connection, ErrorCode};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Name of the lock file inside the data directory
+pub const LOCK_FILE: &str = "blam.lock";
+
+/// Locks this process holds, by data directory
+static HELD: Lazy<Mutex<HashMap<PathBuf, InstanceLock>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// An exclusive claim on a data directory, released when dropped
+pub struct InstanceLock {
+    /// Connection holding the exclusive lock on the lock file
+    conn: Connection,
+}
+
+impl InstanceLock {
+    /// Take the lock on `data_dir`
+    ///
+    /// Fails with `StorageError::AlreadyOpen` if another instance holds it.
+    pub fn acquire(data_dir: &Path) -> Result<Self, StorageError> {
+        let conn = Connection::open(data_dir.join(LOCK_FILE))?;
+        // Fail straight away rather than waiting for the other instance
+        conn.busy_timeout(Duration::ZERO)?;
+        // In exclusive mode the lock taken by the first write is kept until
+        // the connection closes
+        conn.execute_batch(
+            "PRAGMA locking_mode = EXCLUSIVE;
+             CREATE TABLE IF NOT EXISTS instance (pid INTEGER NOT NULL);
+             DELETE FROM instance;",
+        )
+        .and_then(|_| conn.execute("INSERT INTO instance (pid) VALUES (?1)", [std::process::id()]))
+        .map_err(|e| match e.sqlite_error_code() {
+            Some(ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked) => {
+                StorageError::AlreadyOpen(data_dir.to_path_buf())
+            }
+            _ => StorageError::Database(e),
+        })?;
+        Ok(Self { conn })
+    }
+}
+
+/// Make sure this process holds the lock on `data_dir`
+///
+/// The first call takes the lock and keeps it for the life of the process;
+/// later calls for the same directory return straight away.
+pub fn hold(data_dir: &Path) -> Result<(), StorageError> {
+    let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+    if !held.contains_key(data_dir) {
+        let lock = InstanceLock::acquire(data_dir)?;
+        held.insert(data_dir.to_path_buf(), lock);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("blam-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_second_open_reports_conflict() {
+        let dir = temp_dir("lock-conflict");
+        let first = InstanceLock::acquire(&dir).unwrap();
+
+        match InstanceLock::acquire(&dir) {
+            Err(StorageError::AlreadyOpen(path)) => assert_eq!(path, dir),
+            Err(e) => panic!("expected AlreadyOpen, got {}", e),
+            Ok(_) => panic!("second instance got the lock"),
+        }
+
+        // Releasing the first lets a new instance in
+        drop(first);
+        InstanceLock::acquire(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_separate_dirs_lock_independently() {
+        let a = temp_dir("lock-a");
+        let b = temp_dir("lock-b");
+        let _lock_a = InstanceLock::acquire(&a).unwrap();
+        let _lock_b = InstanceLock::acquire(&b).unwrap();
+        let _ = std::fs::remove_dir_all(&a);
+        let _ = std::fs::remove_dir_all(&b);
+    }
+
+    #[test]
+    fn test_hold_is_reentrant_within_process() {
+        let dir = temp_dir("lock-hold");
+        hold(&dir).unwrap();
+        hold(&dir).unwrap();
+        assert!(matches!(InstanceLock::acquire(&dir), Err(StorageError::AlreadyOpen(_))));
+    }
+}
@@ -7,23 +7,59 @@
 //! - Append-only event log for CRDT sync
 //! - Actor identity management
 //! - CRDT sync logic for peer-to-peer event exchange
+//! - Optional encryption of event payloads at rest (see `crypt`)
 
+pub mod crypt;
+pub mod lock;
+pub mod rebuild;
 pub mod sync;
 
+use crate::game::MAX_PLAYERS;
+use crate::json;
+use crate::stats::{sort_for_replay, MatchResult, DEFAULT_ELO, DEFAULT_K};
+use crypt::PayloadCipher;
 use directories::ProjectDirs;
 use rusqlite::{params, Connection, Result as SqlResult};
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
 
 /// Current schema version. Bump this when making schema changes.
 /// Version history:
 /// - v1: Initial schema with meta and events tables
 /// - v2: Added derived_stats and derived_elo cache tables
-const SCHEMA_VERSION: u32 = 2;
+/// - v3: Added round_checkpoint table for resuming interrupted solo rounds
+/// - v4: Added season column to meta for season leaderboards
+/// - v5: Added total_claim_chars to derived_stats for average claim length
+/// - v6: Added opt-in rack_difficulty table for tuning the rack generator
+/// - v7: Added house_words to meta for runtime dictionary extensions
+/// - v8: Added elo_k_factor to meta so the Elo cache uses a configured K
+/// - v9: Added compacted_claims, compaction_watermark and compacted_before
+///   for event log compaction
+/// - v10: Added an indexed match_id column to events, set on match_end rows
+/// - v11: Added encryption_salt and encryption_check to meta for encrypting
+///   event payloads at rest
+const SCHEMA_VERSION: u32 = 11;
+
+/// Event types `compact_events` may remove once they're old enough
+const COMPACTABLE_EVENT_TYPES: [&str; 2] = ["word_claimed", "round_start"];
 
 /// Event payload version. Included in all event payloads for forward compatibility.
 /// Older binaries can read newer payloads by ignoring unknown fields.
 pub const PAYLOAD_VERSION: u32 = 1;
 
+/// Environment variable that overrides the data directory
+pub const DATA_DIR_VAR: &str = "BLAM_DATA_DIR";
+
+/// Environment variable holding the passphrase the app opens the data
+/// directory's database with (see `open_encrypted`)
+pub const PASSPHRASE_VAR: &str = "BLAM_PASSPHRASE";
+
+/// Key `open_encrypted` derived for the data directory's database, so the
+/// plain `open`s after it in this process can read the payloads too
+static SESSION_KEY: Mutex<Option<PayloadCipher>> = Mutex::new(None);
+
 /// Errors that can occur during storage operations.
 #[derive(Debug)]
 pub enum StorageError {
@@ -37,6 +73,22 @@ pub enum StorageError {
     CreateDirFailed(std::io::Error),
     /// Migration failed
     MigrationFailed { from: u32, to: u32, reason: String },
+    /// A background cache rebuild stopped without reporting back
+    RebuildAborted,
+    /// Rebuilding a derived cache failed and was rolled back
+    RebuildFailed { cache: &'static str, reason: String },
+    /// A setting was given a value it can't hold
+    InvalidSetting(String),
+    /// Another running instance holds the data directory
+    AlreadyOpen(PathBuf),
+    /// Reading or writing an event export failed
+    Io(std::io::Error),
+    /// A line of an event export couldn't be read as an event
+    InvalidImport { line: usize, reason: String },
+    /// The database's payloads are encrypted and no passphrase was given
+    PassphraseRequired,
+    /// The passphrase doesn't match the one the database was encrypted with
+    WrongPassphrase,
 }
 
 impl std::fmt::Display for StorageError {
@@ -55,6 +107,25 @@ impl std::fmt::Display for StorageError {
             StorageError::MigrationFailed { from, to, reason } => {
                 write!(f, "migration from v{} to v{} failed: {}", from, to, reason)
             }
+            StorageError::RebuildAborted => write!(f, "cache rebuild stopped unexpectedly"),
+            StorageError::RebuildFailed { cache, reason } => {
+                write!(f, "rebuilding the {} cache failed: {}", cache, reason)
+            }
+            StorageError::InvalidSetting(reason) => write!(f, "invalid setting: {}", reason),
+            StorageError::AlreadyOpen(dir) => write!(
+                f,
+                "another BLAM! instance is already using {} (set {} to run a second one elsewhere)",
+                dir.display(),
+                DATA_DIR_VAR
+            ),
+            StorageError::Io(e) => write!(f, "I/O error: {}", e),
+            StorageError::InvalidImport { line, reason } => {
+                write!(f, "invalid event on line {}: {}", line, reason)
+            }
+            StorageError::PassphraseRequired => {
+                write!(f, "the database is encrypted; a passphrase is needed to open it")
+            }
+            StorageError::WrongPassphrase => write!(f, "wrong passphrase for the database"),
         }
     }
 }
@@ -67,6 +138,12 @@ impl From<rusqlite::Error> for StorageError {
     }
 }
 
+impl From<std::io::Error> for StorageError {
+    fn from(e: std::io::Error) -> Self {
+        StorageError::Io(e)
+    }
+}
+
 /// A stored event in the append-only log.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Event {
@@ -116,12 +193,25 @@ impl ActorId {
     pub fn to_hex(&self) -> String {
         self.0.iter().map(|b| format!("{:02x}", b)).collect()
     }
+
+    /// Parse the hex form written by `to_hex`.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        if hex.len() != 32 || !hex.is_ascii() {
+            return None;
+        }
+        let bytes: Option<Vec<u8>> = (0..16)
+            .map(|i| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok())
+            .collect();
+        Self::from_bytes(&bytes?)
+    }
 }
 
 /// The main storage handle for BLAM! data.
 pub struct Storage {
     conn: Connection,
     actor_id: ActorId,
+    /// Set when payloads are encrypted at rest (see `crypt`)
+    cipher: Option<PayloadCipher>,
 }
 
 impl Storage {
@@ -129,47 +219,253 @@ impl Storage {
     ///
     /// Uses OS-standard directories:
     /// - Linux: `$XDG_DATA_HOME/blam/` or `~/.local/share/blam/`
-    /// - macOS: `~/Library/Application Support/blam/`
+    ///   (under Flatpak, `XDG_DATA_HOME` points inside the sandbox)
+    /// - macOS: `~/Library/Application Support/dev.blam.blam/`
+    /// - Windows: `%APPDATA%\blam\blam\data\`
+    ///
+    /// Only one process may use a data directory at a time; a second
+    /// instance gets `StorageError::AlreadyOpen`.
+    ///
+    /// An encrypted database opens once `open_encrypted` has unlocked it in
+    /// this process, and fails with `PassphraseRequired` until then.
     pub fn open() -> Result<Self, StorageError> {
         let data_dir = Self::data_dir()?;
 
         // Ensure directory exists
         std::fs::create_dir_all(&data_dir).map_err(StorageError::CreateDirFailed)?;
+        lock::hold(&data_dir)?;
+        Self::migrate_legacy_database(&data_dir);
 
-        let db_path = data_dir.join("blam.db");
-        let conn = Connection::open(&db_path)?;
+        let key = SESSION_KEY.lock().ok().and_then(|key| key.clone());
+        Self::open_with_key(&data_dir.join("blam.db"), key)
+    }
 
-        let mut storage = Storage {
-            conn,
-            actor_id: ActorId([0; 16]), // Placeholder, will be loaded/created
-        };
+    /// Open or create a database at a specific path.
+    ///
+    /// Several connections may have the same file open (see `rebuild`), so
+    /// each waits briefly for another's write to finish instead of failing.
+    ///
+    /// Fails with `PassphraseRequired` if the database was encrypted with
+    /// `open_encrypted`.
+    pub fn open_at(db_path: &Path) -> Result<Self, StorageError> {
+        Self::open_with_key(db_path, None)
+    }
 
-        storage.initialize_schema()?;
-        storage.actor_id = storage.load_or_create_actor_id()?;
+    /// Open a database at a specific path with a key already derived by
+    /// another connection to it (see `payload_key`)
+    ///
+    /// Fails with `PassphraseRequired` if the database is encrypted and
+    /// `key` is `None`, and `WrongPassphrase` if it isn't the database's key.
+    pub fn open_with_key(db_path: &Path, key: Option<PayloadCipher>) -> Result<Self, StorageError> {
+        let mut storage = Self::open_connection(Connection::open(db_path)?)?;
+        if let Some((_, check)) = storage.encryption_meta()? {
+            let key = key.ok_or(StorageError::PassphraseRequired)?;
+            if !key.verifies(&check) {
+                return Err(StorageError::WrongPassphrase);
+            }
+            storage.cipher = Some(key);
+        }
+        Ok(storage)
+    }
+
+    /// Open the storage database with event payloads encrypted at rest.
+    ///
+    /// Like `open`, but see `open_encrypted_at`. Later `open`s in this
+    /// process reuse the key.
+    pub fn open_encrypted(passphrase: &str) -> Result<Self, StorageError> {
+        let data_dir = Self::data_dir()?;
+
+        std::fs::create_dir_all(&data_dir).map_err(StorageError::CreateDirFailed)?;
+        lock::hold(&data_dir)?;
+        Self::migrate_legacy_database(&data_dir);
+
+        let storage = Self::open_encrypted_at(&data_dir.join("blam.db"), passphrase)?;
+        if let Ok(mut key) = SESSION_KEY.lock() {
+            *key = storage.payload_key();
+        }
+        Ok(storage)
+    }
 
+    /// Open or create a database at a specific path, with event payloads
+    /// encrypted under a key derived from `passphrase`.
+    ///
+    /// A database that isn't encrypted yet is encrypted now, existing
+    /// payloads included, and needs the passphrase from then on. Fails with
+    /// `WrongPassphrase` if it was encrypted with a different one.
+    pub fn open_encrypted_at(db_path: &Path, passphrase: &str) -> Result<Self, StorageError> {
+        let mut storage = Self::open_connection(Connection::open(db_path)?)?;
+        storage.unlock(passphrase)?;
         Ok(storage)
     }
 
     /// Open an in-memory database (for testing).
     #[cfg(test)]
     pub fn open_in_memory() -> Result<Self, StorageError> {
-        let conn = Connection::open_in_memory()?;
+        Self::open_connection(Connection::open_in_memory()?)
+    }
+
+    /// Bring the schema of a freshly opened connection up to date and load
+    /// the actor ID
+    fn open_connection(conn: Connection) -> Result<Self, StorageError> {
+        conn.busy_timeout(Duration::from_secs(5))?;
+
         let mut storage = Storage {
             conn,
-            actor_id: ActorId([0; 16]),
+            actor_id: ActorId([0; 16]), // Placeholder, will be loaded/created
+            cipher: None,
         };
+
         storage.initialize_schema()?;
         storage.actor_id = storage.load_or_create_actor_id()?;
+
         Ok(storage)
     }
 
-    /// Get the OS-standard data directory for BLAM!
+    /// Whether event payloads are encrypted at rest
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    /// The payload key, for opening another connection to this database
+    /// with `open_with_key` without asking for the passphrase again
+    pub fn payload_key(&self) -> Option<PayloadCipher> {
+        self.cipher.clone()
+    }
+
+    // === Payload Encryption Methods ===
+
+    /// The salt and check value stored by `unlock`, if the database is
+    /// encrypted
+    fn encryption_meta(&self) -> SqlResult<Option<(Vec<u8>, String)>> {
+        let (salt, check): (Option<Vec<u8>>, Option<String>) = self.conn.query_row(
+            "SELECT encryption_salt, encryption_check FROM meta LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        Ok(salt.zip(check))
+    }
+
+    /// Derive the payload key from `passphrase`, encrypting the database
+    /// first if it isn't yet
+    fn unlock(&mut self, passphrase: &str) -> Result<(), StorageError> {
+        if let Some((salt, check)) = self.encryption_meta()? {
+            let cipher = PayloadCipher::derive(passphrase, &salt)?;
+            if !cipher.verifies(&check) {
+                return Err(StorageError::WrongPassphrase);
+            }
+            self.cipher = Some(cipher);
+            return Ok(());
+        }
+
+        let salt = PayloadCipher::generate_salt();
+        let cipher = PayloadCipher::derive(passphrase, &salt)?;
+
+        // Encrypt what's already logged, all or nothing
+        let tx = self.conn.unchecked_transaction()?;
+        let rows: Vec<(i64, String)> = self
+            .conn
+            .prepare("SELECT rowid, payload FROM events")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<SqlResult<_>>()?;
+        for (rowid, payload) in rows {
+            if !crypt::is_encrypted(&payload) {
+                self.conn.execute(
+                    "UPDATE events SET payload = ?1 WHERE rowid = ?2",
+                    params![cipher.encrypt(&payload), rowid],
+                )?;
+            }
+        }
+        self.conn.execute(
+            "UPDATE meta SET encryption_salt = ?1, encryption_check = ?2",
+            params![salt.as_slice(), cipher.check_value()],
+        )?;
+        tx.commit()?;
+
+        self.cipher = Some(cipher);
+        Ok(())
+    }
+
+    /// A payload as it's written to the events table
+    fn seal_payload(&self, payload: &str) -> String {
+        match &self.cipher {
+            Some(cipher) => cipher.encrypt(payload),
+            None => payload.to_string(),
+        }
+    }
+
+    /// A payload as read from the events table, or `None` if it can't be
+    /// decrypted
+    fn open_payload(&self, stored: String) -> Option<String> {
+        match &self.cipher {
+            Some(cipher) => cipher.decrypt(&stored),
+            None if crypt::is_encrypted(&stored) => None,
+            None => Some(stored),
+        }
+    }
+
+    /// Run a query selecting a single payload column, decrypting each one
+    ///
+    /// Rows that can't be read or decrypted are skipped.
+    fn query_payloads(
+        &self,
+        sql: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<String>, StorageError> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let payloads = stmt
+            .query_map(params, |row| row.get::<_, String>(0))?
+            .filter_map(|r| r.ok())
+            .filter_map(|payload| self.open_payload(payload))
+            .collect();
+        Ok(payloads)
+    }
+
+    /// Path of the database `open` uses.
+    pub fn db_path() -> Result<PathBuf, StorageError> {
+        Ok(Self::data_dir()?.join("blam.db"))
+    }
+
+    /// Get the data directory for BLAM!
+    ///
+    /// `BLAM_DATA_DIR` overrides the OS-standard location, so testers can
+    /// run several instances side by side.
     pub fn data_dir() -> Result<PathBuf, StorageError> {
-        ProjectDirs::from("", "", "blam")
+        if let Some(dir) = std::env::var_os(DATA_DIR_VAR).filter(|dir| !dir.is_empty()) {
+            return Ok(PathBuf::from(dir));
+        }
+        ProjectDirs::from("dev", "blam", "blam")
             .map(|dirs| dirs.data_dir().to_path_buf())
             .ok_or(StorageError::NoDataDirectory)
     }
 
+    /// Data directory used by older builds, which passed an empty
+    /// qualifier/organization. Only differs from `data_dir` off Linux.
+    fn legacy_data_dir() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "blam").map(|dirs| dirs.data_dir().to_path_buf())
+    }
+
+    /// Move a database left behind in the legacy data directory into the
+    /// current one, so upgrading doesn't appear to wipe history.
+    ///
+    /// Best-effort: on failure the old file is left in place and a fresh
+    /// database is created.
+    fn migrate_legacy_database(data_dir: &std::path::Path) {
+        let Some(legacy_dir) = Self::legacy_data_dir() else {
+            return;
+        };
+        if legacy_dir == data_dir {
+            return;
+        }
+        let legacy_db = legacy_dir.join("blam.db");
+        let db_path = data_dir.join("blam.db");
+        if legacy_db.exists()
+            && !db_path.exists()
+            && std::fs::rename(&legacy_db, &db_path).is_err()
+        {
+            let _ = std::fs::copy(&legacy_db, &db_path);
+        }
+    }
+
     /// Get this device's actor ID.
     pub fn actor_id(&self) -> &ActorId {
         &self.actor_id
@@ -192,6 +488,55 @@ impl Storage {
         Ok(())
     }
 
+    /// Get the current season (matches hosted here are tagged with it).
+    pub fn current_season(&self) -> SqlResult<u32> {
+        self.conn
+            .query_row("SELECT season FROM meta LIMIT 1", [], |row| row.get(0))
+    }
+
+    /// Start the next season, returning its number.
+    ///
+    /// Earlier seasons' matches stay in the log; they just stop counting
+    /// toward the current season's leaderboard.
+    pub fn advance_season(&self) -> SqlResult<u32> {
+        self.conn.execute("UPDATE meta SET season = season + 1", [])?;
+        self.current_season()
+    }
+
+    /// Get the saved house words (extra valid words for this device's games).
+    pub fn house_words(&self) -> SqlResult<Vec<String>> {
+        let stored: String =
+            self.conn
+                .query_row("SELECT house_words FROM meta LIMIT 1", [], |row| row.get(0))?;
+        Ok(stored.lines().map(str::to_string).collect())
+    }
+
+    /// Save the house words, replacing any saved before.
+    pub fn set_house_words(&self, words: &[String]) -> SqlResult<()> {
+        self.conn
+            .execute("UPDATE meta SET house_words = ?1", params![words.join("\n")])?;
+        Ok(())
+    }
+
+    /// Get the K factor the Elo cache is computed with.
+    pub fn elo_k_factor(&self) -> SqlResult<f64> {
+        self.conn
+            .query_row("SELECT elo_k_factor FROM meta LIMIT 1", [], |row| row.get(0))
+    }
+
+    /// Change the Elo K factor and rebuild the derived caches with it.
+    ///
+    /// Every rating depends on every earlier match, so a new K means a full
+    /// replay rather than an incremental update.
+    pub fn set_elo_k_factor(&self, k_factor: f64) -> Result<(), StorageError> {
+        if !k_factor.is_finite() || k_factor <= 0.0 {
+            return Err(StorageError::InvalidSetting(format!("Elo K factor must be positive, got {}", k_factor)));
+        }
+        self.conn
+            .execute("UPDATE meta SET elo_k_factor = ?1", params![k_factor])?;
+        self.rebuild_derived_caches()
+    }
+
     /// Append an event to the log.
     ///
     /// The sequence number is automatically assigned as the next value for this actor.
@@ -201,24 +546,29 @@ impl Storage {
             .map(|d| d.as_millis() as i64)
             .unwrap_or(0);
 
-        // Get next sequence number for this actor
+        // Get next sequence number for this actor, past any compacted away
         let seq: i64 = self
             .conn
             .query_row(
-                "SELECT COALESCE(MAX(seq), 0) + 1 FROM events WHERE actor_id = ?1",
+                "SELECT COALESCE(MAX(seq), 0) + 1 FROM (
+                     SELECT seq FROM events WHERE actor_id = ?1
+                     UNION ALL SELECT seq FROM compaction_watermark WHERE actor_id = ?1
+                 )",
                 params![self.actor_id.as_bytes().as_slice()],
                 |row| row.get(0),
             )
             .unwrap_or(1);
 
         self.conn.execute(
-            "INSERT INTO events (actor_id, seq, event_type, payload, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT INTO events (actor_id, seq, event_type, payload, created_at, match_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             params![
                 self.actor_id.as_bytes().as_slice(),
                 seq,
                 event_type,
-                payload,
-                created_at
+                self.seal_payload(payload),
+                created_at,
+                event_match_id(event_type, payload)
             ],
         )?;
 
@@ -233,16 +583,32 @@ impl Storage {
 
     /// Insert an event from another actor (for CRDT sync).
     ///
-    /// Returns true if the event was inserted, false if it already existed.
+    /// Returns true if the event was inserted, false if it already existed or
+    /// was refused. A `match_end` with more scores than a lobby can hold
+    /// players is refused, so a bad peer can't bloat the log and caches.
+    /// So is a claim or round start older than the last compaction: it (or
+    /// a copy of it) has already been folded away.
     pub fn insert_remote_event(&self, event: &Event) -> Result<bool, StorageError> {
+        if event.event_type == "match_end"
+            && extract_json_scores(&event.payload).is_some_and(|s| s.len() > MAX_PLAYERS)
+        {
+            return Ok(false);
+        }
+        if COMPACTABLE_EVENT_TYPES.contains(&event.event_type.as_str())
+            && event.created_at < self.compacted_before()?
+        {
+            return Ok(false);
+        }
         let result = self.conn.execute(
-            "INSERT OR IGNORE INTO events (actor_id, seq, event_type, payload, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT OR IGNORE INTO events (actor_id, seq, event_type, payload, created_at, match_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             params![
                 event.actor_id.as_bytes().as_slice(),
                 event.seq,
                 &event.event_type,
-                &event.payload,
-                event.created_at
+                self.seal_payload(&event.payload),
+                event.created_at,
+                event_match_id(&event.event_type, &event.payload)
             ],
         )?;
         Ok(result > 0)
@@ -250,9 +616,12 @@ impl Storage {
 
     /// Get the highest sequence number seen for each actor (vector clock).
     pub fn get_vector_clock(&self) -> Result<Vec<(ActorId, i64)>, StorageError> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT actor_id, MAX(seq) FROM events GROUP BY actor_id")?;
+        let mut stmt = self.conn.prepare(
+            "SELECT actor_id, MAX(seq) FROM (
+                 SELECT actor_id, seq FROM events
+                 UNION ALL SELECT actor_id, seq FROM compaction_watermark
+             ) GROUP BY actor_id",
+        )?;
         let rows = stmt.query_map([], |row| {
             let actor_bytes: Vec<u8> = row.get(0)?;
             let seq: i64 = row.get(1)?;
@@ -293,6 +662,9 @@ impl Storage {
         let mut events = Vec::new();
         for row in rows {
             let (actor_bytes, seq, event_type, payload, created_at) = row?;
+            let Some(payload) = self.open_payload(payload) else {
+                continue;
+            };
             if let Some(actor_id) = ActorId::from_bytes(&actor_bytes) {
                 events.push(Event {
                     actor_id,
@@ -308,11 +680,62 @@ impl Storage {
 
     /// Get all events in chronological order (by created_at, then actor_id, then seq).
     pub fn get_all_events(&self) -> Result<Vec<Event>, StorageError> {
-        let mut stmt = self.conn.prepare(
+        self.query_events(
             "SELECT actor_id, seq, event_type, payload, created_at FROM events ORDER BY created_at, actor_id, seq",
-        )?;
+            [],
+        )
+    }
 
-        let rows = stmt.query_map([], |row| {
+    /// Get events created between `start_ms` and `end_ms` (inclusive), in
+    /// chronological order, optionally only those of `event_type`.
+    ///
+    /// Filters in SQL (on the created_at and event_type indexes), so a
+    /// history view doesn't have to load the whole log.
+    pub fn get_events_in_range(
+        &self,
+        event_type: Option<&str>,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<Vec<Event>, StorageError> {
+        match event_type {
+            Some(event_type) => self.query_events(
+                "SELECT actor_id, seq, event_type, payload, created_at FROM events
+                 WHERE event_type = ?1 AND created_at BETWEEN ?2 AND ?3
+                 ORDER BY created_at, actor_id, seq",
+                params![event_type, start_ms, end_ms],
+            ),
+            None => self.query_events(
+                "SELECT actor_id, seq, event_type, payload, created_at FROM events
+                 WHERE created_at BETWEEN ?1 AND ?2
+                 ORDER BY created_at, actor_id, seq",
+                params![start_ms, end_ms],
+            ),
+        }
+    }
+
+    /// Get the `match_end` events recorded for `match_id`, in chronological
+    /// order.
+    ///
+    /// A match synced from several devices has one event per device. Looked
+    /// up on the match_id index rather than by parsing every payload.
+    pub fn get_match_events(&self, match_id: i64) -> Result<Vec<Event>, StorageError> {
+        self.query_events(
+            "SELECT actor_id, seq, event_type, payload, created_at FROM events
+             WHERE match_id = ?1
+             ORDER BY created_at, actor_id, seq",
+            params![match_id],
+        )
+    }
+
+    /// Run a query selecting (actor_id, seq, event_type, payload, created_at)
+    fn query_events(
+        &self,
+        sql: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<Event>, StorageError> {
+        let mut stmt = self.conn.prepare(sql)?;
+
+        let rows = stmt.query_map(params, |row| {
             let actor_bytes: Vec<u8> = row.get(0)?;
             let seq: i64 = row.get(1)?;
             let event_type: String = row.get(2)?;
@@ -324,6 +747,9 @@ impl Storage {
         let mut events = Vec::new();
         for row in rows {
             let (actor_bytes, seq, event_type, payload, created_at) = row?;
+            let Some(payload) = self.open_payload(payload) else {
+                continue;
+            };
             if let Some(actor_id) = ActorId::from_bytes(&actor_bytes) {
                 events.push(Event {
                     actor_id,
@@ -337,6 +763,45 @@ impl Storage {
         Ok(events)
     }
 
+    /// Write every event to `writer`, one JSON object per line.
+    ///
+    /// Each line holds the event's actor_id (hex), seq, event_type,
+    /// created_at and payload, in that order. Returns how many were written.
+    pub fn export_events(&self, mut writer: impl Write) -> Result<usize, StorageError> {
+        let events = self.get_all_events()?;
+        for event in &events {
+            writeln!(writer, "{}", event_to_json_line(event))?;
+        }
+        writer.flush()?;
+        Ok(events.len())
+    }
+
+    /// Read events written by `export_events` and add them to the log.
+    ///
+    /// Each goes through `insert_remote_event`, so events already in the log
+    /// are skipped and importing the same file twice adds nothing the second
+    /// time. Blank lines are ignored. Returns how many events were new; a
+    /// line that isn't an event stops the import with nothing added.
+    pub fn import_events(&self, reader: impl Read) -> Result<usize, StorageError> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut inserted = 0;
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = event_from_json_line(&line).map_err(|reason| StorageError::InvalidImport {
+                line: i + 1,
+                reason: reason.to_string(),
+            })?;
+            if self.insert_remote_event(&event)? {
+                inserted += 1;
+            }
+        }
+        tx.commit()?;
+        Ok(inserted)
+    }
+
     /// Get the total number of events in the log.
     pub fn event_count(&self) -> Result<i64, StorageError> {
         let count: i64 = self
@@ -345,6 +810,135 @@ impl Storage {
         Ok(count)
     }
 
+    /// Next per-host match counter for a match this device is about to record.
+    ///
+    /// Derived from the match_end events this actor has already authored, so
+    /// it keeps increasing regardless of what the wall clock does.
+    pub fn next_match_counter(&self) -> Result<u64, StorageError> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM events WHERE actor_id = ?1 AND event_type = 'match_end'",
+            params![self.actor_id.as_bytes().as_slice()],
+            |row| row.get(0),
+        )?;
+        Ok(count as u64 + 1)
+    }
+
+    /// Cutoff of the last compaction (ms since epoch, 0 if never compacted).
+    pub fn compacted_before(&self) -> SqlResult<i64> {
+        self.conn
+            .query_row("SELECT compacted_before FROM meta LIMIT 1", [], |row| row.get(0))
+    }
+
+    /// Compact the event log: drop `word_claimed` and `round_start` events
+    /// created before `before_ms`, returning how many were removed.
+    ///
+    /// `match_end` events are never touched, so scores, wins and Elo replay
+    /// as before. What the removed claims contributed to each player's stats
+    /// (words claimed, letters, longest word) is folded into the local
+    /// `compacted_claims` table, which cache rebuilds start from. Match
+    /// reports for compacted matches list standings only.
+    ///
+    /// Each actor's highest removed seq is kept as a watermark, so the
+    /// vector clock (and our next seq) never go backward and peers aren't
+    /// asked to resend what was compacted. Copies of compacted events that
+    /// arrive later are refused by `insert_remote_event`.
+    pub fn compact_events(&self, before_ms: i64) -> Result<usize, StorageError> {
+        use std::collections::{HashMap, HashSet};
+
+        let tx = self.conn.unchecked_transaction()?;
+
+        // Claims that stay in the log; older copies of them just go
+        let mut kept: HashSet<(i64, String, String)> = HashSet::new();
+        let kept_payloads: Vec<String> = self.query_payloads(
+            "SELECT payload FROM events WHERE event_type = 'word_claimed' AND created_at >= ?1",
+            params![before_ms],
+        )?;
+        for payload in kept_payloads.iter().filter_map(|p| json::parse(p)) {
+            if let (Some(round_id), Some(word), Some(handle)) = (
+                payload.number_field::<i64>("round_id"),
+                payload.str_field("word"),
+                payload.str_field("player_name"),
+            ) {
+                kept.insert((round_id, word.to_uppercase(), handle));
+            }
+        }
+
+        // Fold the claims being removed into per-player totals, counting each
+        // claim once however many devices logged it (as the rebuild does)
+        let payloads: Vec<String> = self.query_payloads(
+            "SELECT payload FROM events WHERE event_type = 'word_claimed' AND created_at < ?1 ORDER BY created_at",
+            params![before_ms],
+        )?;
+        let mut seen_claims: HashSet<(i64, String, String)> = HashSet::new();
+        let mut totals: HashMap<String, (u32, u32, String)> = HashMap::new();
+        for payload in payloads.iter().filter_map(|p| json::parse(p)) {
+            let (Some(handle), Some(word)) =
+                (payload.str_field("player_name"), payload.str_field("word"))
+            else {
+                continue;
+            };
+            if let Some(round_id) = payload.number_field::<i64>("round_id") {
+                let key = (round_id, word.to_uppercase(), handle.clone());
+                if kept.contains(&key) || !seen_claims.insert(key) {
+                    continue;
+                }
+            }
+            let (words, chars, longest) = totals.entry(handle).or_default();
+            *words += 1;
+            *chars += word.chars().count() as u32;
+            if word.chars().count() > longest.chars().count() {
+                *longest = word;
+            }
+        }
+
+        for (handle, (words, chars, longest)) in &totals {
+            // Earlier compactions' longest word was claimed first, so it
+            // wins ties
+            let previous: Option<String> = self
+                .conn
+                .query_row(
+                    "SELECT longest_word FROM compacted_claims WHERE handle = ?1",
+                    params![handle],
+                    |row| row.get(0),
+                )
+                .ok();
+            let longest = match previous {
+                Some(previous) if previous.chars().count() >= longest.chars().count() => previous,
+                _ => longest.clone(),
+            };
+            self.conn.execute(
+                "INSERT INTO compacted_claims (handle, words_claimed, total_claim_chars, longest_word)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(handle) DO UPDATE SET
+                     words_claimed = words_claimed + excluded.words_claimed,
+                     total_claim_chars = total_claim_chars + excluded.total_claim_chars,
+                     longest_word = excluded.longest_word",
+                params![handle, words, chars, longest],
+            )?;
+        }
+
+        // Watermarks before the delete, while the seqs are still there
+        self.conn.execute(
+            "INSERT INTO compaction_watermark (actor_id, seq)
+             SELECT actor_id, MAX(seq) FROM events
+             WHERE event_type IN ('word_claimed', 'round_start') AND created_at < ?1
+             GROUP BY actor_id
+             ON CONFLICT(actor_id) DO UPDATE SET seq = MAX(seq, excluded.seq)",
+            params![before_ms],
+        )?;
+        let removed = self.conn.execute(
+            "DELETE FROM events WHERE event_type IN ('word_claimed', 'round_start') AND created_at < ?1",
+            params![before_ms],
+        )?;
+        self.conn.execute(
+            "UPDATE meta SET compacted_before = MAX(compacted_before, ?1)",
+            params![before_ms],
+        )?;
+
+        tx.commit()?;
+        Ok(removed)
+    }
+
     // Private helper methods
 
     fn initialize_schema(&self) -> Result<(), StorageError> {
@@ -398,7 +992,14 @@ impl Storage {
                 schema_version INTEGER NOT NULL,
                 actor_id BLOB NOT NULL,
                 handle TEXT,
-                created_at INTEGER NOT NULL
+                created_at INTEGER NOT NULL,
+                season INTEGER NOT NULL DEFAULT 1,
+                rack_telemetry INTEGER NOT NULL DEFAULT 0,
+                house_words TEXT NOT NULL DEFAULT '',
+                elo_k_factor REAL NOT NULL DEFAULT 32.0,
+                compacted_before INTEGER NOT NULL DEFAULT 0,
+                encryption_salt BLOB,
+                encryption_check TEXT
             );
 
             -- Events table: append-only log for CRDT sync
@@ -409,12 +1010,17 @@ impl Storage {
                 event_type TEXT NOT NULL,
                 payload TEXT NOT NULL,
                 created_at INTEGER NOT NULL,
+                match_id INTEGER,
                 PRIMARY KEY (actor_id, seq)
             );
 
             -- Index for efficient event retrieval by type
             CREATE INDEX idx_events_type ON events (event_type);
 
+            -- Index for looking up a match's events (match_id is NULL for
+            -- everything but match_end)
+            CREATE INDEX idx_events_match ON events (match_id);
+
             -- Index for chronological ordering
             CREATE INDEX idx_events_created ON events (created_at);
 
@@ -429,7 +1035,8 @@ impl Storage {
                 longest_word TEXT NOT NULL DEFAULT '',
                 words_claimed INTEGER NOT NULL DEFAULT 0,
                 wins INTEGER NOT NULL DEFAULT 0,
-                last_updated INTEGER NOT NULL
+                last_updated INTEGER NOT NULL,
+                total_claim_chars INTEGER NOT NULL DEFAULT 0
             );
 
             -- Derived Elo history: stores rating snapshots after each match
@@ -453,6 +1060,43 @@ impl Storage {
                 last_rebuilt INTEGER NOT NULL,
                 event_count INTEGER NOT NULL DEFAULT 0
             );
+
+            -- Round checkpoint: snapshot of an in-progress solo round
+            -- Local-only (never synced), holds at most one row
+            CREATE TABLE round_checkpoint (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                letters TEXT NOT NULL,
+                time_remaining INTEGER NOT NULL,
+                score INTEGER NOT NULL,
+                claimed_words TEXT NOT NULL,
+                saved_at INTEGER NOT NULL
+            );
+
+            -- Rack difficulty: local-only, opt-in record of how solvable racks were
+            CREATE TABLE rack_difficulty (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                letters TEXT NOT NULL,
+                valid_words INTEGER NOT NULL,
+                max_points INTEGER NOT NULL,
+                achieved_points INTEGER NOT NULL,
+                recorded_at INTEGER NOT NULL
+            );
+
+            -- Compacted claims: per-player totals of word_claimed events
+            -- removed by compaction. Local-only, folded into derived_stats
+            CREATE TABLE compacted_claims (
+                handle TEXT PRIMARY KEY,
+                words_claimed INTEGER NOT NULL DEFAULT 0,
+                total_claim_chars INTEGER NOT NULL DEFAULT 0,
+                longest_word TEXT NOT NULL DEFAULT ''
+            );
+
+            -- Compaction watermark: highest seq compacted away per actor, so
+            -- the vector clock never goes backward
+            CREATE TABLE compaction_watermark (
+                actor_id BLOB PRIMARY KEY,
+                seq INTEGER NOT NULL
+            );
             "#,
         )?;
 
@@ -482,6 +1126,51 @@ impl Storage {
                     self.migrate_v1_to_v2()?;
                     current_version = 2;
                 }
+                2 => {
+                    // Migrate from v2 to v3: Add round checkpoint table
+                    self.migrate_v2_to_v3()?;
+                    current_version = 3;
+                }
+                3 => {
+                    // Migrate from v3 to v4: Add current season to meta
+                    self.migrate_v3_to_v4()?;
+                    current_version = 4;
+                }
+                4 => {
+                    // Migrate from v4 to v5: Track claimed letters for average claim length
+                    self.migrate_v4_to_v5()?;
+                    current_version = 5;
+                }
+                5 => {
+                    // Migrate from v5 to v6: Add opt-in rack difficulty table
+                    self.migrate_v5_to_v6()?;
+                    current_version = 6;
+                }
+                6 => {
+                    // Migrate from v6 to v7: Add house words
+                    self.migrate_v6_to_v7()?;
+                    current_version = 7;
+                }
+                7 => {
+                    // Migrate from v7 to v8: Add configurable Elo K factor
+                    self.migrate_v7_to_v8()?;
+                    current_version = 8;
+                }
+                8 => {
+                    // Migrate from v8 to v9: Add event log compaction
+                    self.migrate_v8_to_v9()?;
+                    current_version = 9;
+                }
+                9 => {
+                    // Migrate from v9 to v10: Add match_id column to events
+                    self.migrate_v9_to_v10()?;
+                    current_version = 10;
+                }
+                10 => {
+                    // Migrate from v10 to v11: Add payload encryption to meta
+                    self.migrate_v10_to_v11()?;
+                    current_version = 11;
+                }
                 _ => {
                     // Unknown version, can't migrate from it
                     return Err(StorageError::MigrationFailed {
@@ -547,294 +1236,922 @@ impl Storage {
         Ok(())
     }
 
-    fn load_or_create_actor_id(&self) -> Result<ActorId, StorageError> {
-        let actor_bytes: Vec<u8> =
-            self.conn
-                .query_row("SELECT actor_id FROM meta LIMIT 1", [], |row| row.get(0))?;
+    /// Migrate from schema v2 to v3: Add round checkpoint table
+    fn migrate_v2_to_v3(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch(
+            r#"
+            -- Round checkpoint: snapshot of an in-progress solo round
+            -- Local-only (never synced), holds at most one row
+            CREATE TABLE IF NOT EXISTS round_checkpoint (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                letters TEXT NOT NULL,
+                time_remaining INTEGER NOT NULL,
+                score INTEGER NOT NULL,
+                claimed_words TEXT NOT NULL,
+                saved_at INTEGER NOT NULL
+            );
+            "#,
+        )?;
 
-        ActorId::from_bytes(&actor_bytes).ok_or_else(|| {
-            StorageError::Database(rusqlite::Error::InvalidParameterCount(0, 16))
-        })
+        Ok(())
     }
 
-    // === Derived Cache Methods ===
-
-    /// Drop and rebuild all derived caches from the event log.
-    ///
-    /// This is safe to call at any time - derived data can always be
-    /// recomputed from the authoritative event log. Useful after:
-    /// - Schema upgrades
-    /// - CRDT sync that added many events
-    /// - Suspected cache corruption
-    pub fn rebuild_derived_caches(&self) -> Result<(), StorageError> {
-        // Clear existing derived data
+    /// Migrate from schema v3 to v4: Add current season to meta
+    fn migrate_v3_to_v4(&self) -> Result<(), StorageError> {
         self.conn.execute_batch(
             r#"
-            DELETE FROM derived_stats;
-            DELETE FROM derived_elo_history;
-            DELETE FROM derived_cache_meta;
+            -- Season the local player is currently recording matches into
+            ALTER TABLE meta ADD COLUMN season INTEGER NOT NULL DEFAULT 1;
             "#,
         )?;
 
-        // Rebuild from events
-        self.rebuild_stats_cache()?;
-        self.rebuild_elo_cache()?;
-
         Ok(())
     }
 
-    /// Rebuild the derived_stats cache from match_end events.
-    fn rebuild_stats_cache(&self) -> Result<(), StorageError> {
-        use std::collections::HashMap;
-
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis() as i64)
-            .unwrap_or(0);
+    /// Migrate from schema v4 to v5: Track claimed letters for average claim length
+    fn migrate_v4_to_v5(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch(
+            r#"
+            -- Total letters across a player's claimed words (cache, rebuilt from events)
+            ALTER TABLE derived_stats ADD COLUMN total_claim_chars INTEGER NOT NULL DEFAULT 0;
 
-        // Collect all match_end events
-        let mut stmt = self.conn.prepare(
-            "SELECT payload FROM events WHERE event_type = 'match_end' ORDER BY created_at, actor_id, seq"
+            -- The new column starts at zero; mark caches stale so they get rebuilt
+            DELETE FROM derived_cache_meta;
+            "#,
         )?;
 
-        let payloads: Vec<String> = stmt
-            .query_map([], |row| row.get::<_, String>(0))?
-            .filter_map(|r| r.ok())
-            .collect();
+        Ok(())
+    }
 
-        // Track stats for each player
-        struct Stats {
-            elo: f64,
-            rounds_played: u32,
-            total_points: u32,
-            best_score: u32,
-            longest_word: String,
-            words_claimed: u32,
-            wins: u32,
-        }
+    /// Migrate from schema v5 to v6: Add opt-in rack difficulty table
+    fn migrate_v5_to_v6(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch(
+            r#"
+            -- Whether the local player opted in to recording rack difficulty
+            ALTER TABLE meta ADD COLUMN rack_telemetry INTEGER NOT NULL DEFAULT 0;
+
+            -- Rack difficulty: local-only, opt-in record of how solvable racks were
+            CREATE TABLE IF NOT EXISTS rack_difficulty (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                letters TEXT NOT NULL,
+                valid_words INTEGER NOT NULL,
+                max_points INTEGER NOT NULL,
+                achieved_points INTEGER NOT NULL,
+                recorded_at INTEGER NOT NULL
+            );
+            "#,
+        )?;
 
-        let mut player_stats: HashMap<String, Stats> = HashMap::new();
+        Ok(())
+    }
 
-        for payload in &payloads {
-            if let Some(match_result) = parse_match_result_payload(payload) {
-                // Find winner(s)
-                let max_score = match_result.scores.iter().map(|(_, s)| *s).max().unwrap_or(0);
-                let is_multiplayer = match_result.scores.len() >= 2;
+    /// Migrate from schema v6 to v7: Add house words
+    fn migrate_v6_to_v7(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch(
+            r#"
+            -- Extra words the local player counts as valid (newline-separated)
+            ALTER TABLE meta ADD COLUMN house_words TEXT NOT NULL DEFAULT '';
+            "#,
+        )?;
 
-                for (handle, score) in &match_result.scores {
-                    let stats = player_stats.entry(handle.clone()).or_insert(Stats {
-                        elo: 1200.0,
-                        rounds_played: 0,
-                        total_points: 0,
-                        best_score: 0,
-                        longest_word: String::new(),
-                        words_claimed: 0,
-                        wins: 0,
-                    });
+        Ok(())
+    }
 
-                    stats.rounds_played += 1;
-                    stats.total_points += score;
-                    if *score > stats.best_score {
-                        stats.best_score = *score;
-                    }
-                    if is_multiplayer && *score == max_score {
-                        stats.wins += 1;
-                    }
-                }
-            }
+    /// Migrate from schema v7 to v8: Add configurable Elo K factor
+    fn migrate_v7_to_v8(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch(
+            r#"
+            -- K factor used when replaying matches into the Elo cache
+            ALTER TABLE meta ADD COLUMN elo_k_factor REAL NOT NULL DEFAULT 32.0;
+            "#,
+        )?;
+
+        Ok(())
+    }
+
+    /// Migrate from schema v8 to v9: Add event log compaction
+    fn migrate_v8_to_v9(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch(
+            r#"
+            -- Events older than this were compacted; late copies are refused
+            ALTER TABLE meta ADD COLUMN compacted_before INTEGER NOT NULL DEFAULT 0;
+
+            CREATE TABLE IF NOT EXISTS compacted_claims (
+                handle TEXT PRIMARY KEY,
+                words_claimed INTEGER NOT NULL DEFAULT 0,
+                total_claim_chars INTEGER NOT NULL DEFAULT 0,
+                longest_word TEXT NOT NULL DEFAULT ''
+            );
+
+            CREATE TABLE IF NOT EXISTS compaction_watermark (
+                actor_id BLOB PRIMARY KEY,
+                seq INTEGER NOT NULL
+            );
+            "#,
+        )?;
+
+        Ok(())
+    }
+
+    /// Migrate from schema v9 to v10: Add match_id column to events
+    ///
+    /// Backfills match_end rows from their payloads; other event types keep
+    /// NULL. Safe to run again: the column is only added if it's missing and
+    /// only rows still without a match_id are backfilled.
+    fn migrate_v9_to_v10(&self) -> Result<(), StorageError> {
+        let has_column: bool = self.conn.query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('events') WHERE name = 'match_id'",
+            [],
+            |row| row.get(0),
+        )?;
+        if !has_column {
+            self.conn
+                .execute_batch("ALTER TABLE events ADD COLUMN match_id INTEGER;")?;
         }
+        self.conn.execute_batch(
+            r#"
+            -- Index for looking up a match's events
+            CREATE INDEX IF NOT EXISTS idx_events_match ON events (match_id);
+            "#,
+        )?;
 
-        // Also count word claims from word_claimed events
         let mut stmt = self.conn.prepare(
-            "SELECT payload FROM events WHERE event_type = 'word_claimed' ORDER BY created_at"
+            "SELECT rowid, payload FROM events WHERE event_type = 'match_end' AND match_id IS NULL",
         )?;
-
-        let claim_payloads: Vec<String> = stmt
-            .query_map([], |row| row.get::<_, String>(0))?
+        let rows: Vec<(i64, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
             .filter_map(|r| r.ok())
             .collect();
-
-        for payload in &claim_payloads {
-            if let (Some(handle), Some(word)) = (
-                extract_json_string(payload, "player_name"),
-                extract_json_string(payload, "word"),
-            ) {
-                let stats = player_stats.entry(handle).or_insert(Stats {
-                    elo: 1200.0,
-                    rounds_played: 0,
-                    total_points: 0,
-                    best_score: 0,
-                    longest_word: String::new(),
-                    words_claimed: 0,
-                    wins: 0,
-                });
-
-                stats.words_claimed += 1;
-                if word.len() > stats.longest_word.len() {
-                    stats.longest_word = word;
-                }
+        for (rowid, payload) in rows {
+            if let Some(match_id) = event_match_id("match_end", &payload) {
+                self.conn.execute(
+                    "UPDATE events SET match_id = ?1 WHERE rowid = ?2",
+                    params![match_id, rowid],
+                )?;
             }
         }
 
-        // Insert into derived_stats
-        for (handle, stats) in &player_stats {
-            self.conn.execute(
-                "INSERT INTO derived_stats (handle, elo, rounds_played, total_points, best_score, longest_word, words_claimed, wins, last_updated)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-                params![
-                    handle,
-                    stats.elo, // Will be updated by Elo rebuild
-                    stats.rounds_played,
-                    stats.total_points,
-                    stats.best_score,
-                    &stats.longest_word,
-                    stats.words_claimed,
-                    stats.wins,
-                    now
-                ],
-            )?;
-        }
+        Ok(())
+    }
 
-        // Update cache metadata
-        let event_count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM events WHERE event_type IN ('match_end', 'word_claimed')",
-            [],
-            |row| row.get(0),
-        )?;
+    /// Migrate from schema v10 to v11: Add payload encryption to meta
+    fn migrate_v10_to_v11(&self) -> Result<(), StorageError> {
+        self.conn.execute_batch(
+            r#"
+            -- Argon2 salt for the payload key; NULL while payloads are plain
+            ALTER TABLE meta ADD COLUMN encryption_salt BLOB;
 
-        self.conn.execute(
-            "INSERT OR REPLACE INTO derived_cache_meta (cache_name, last_event_seq, last_rebuilt, event_count)
-             VALUES ('stats', 0, ?1, ?2)",
-            params![now, event_count],
+            -- A known value encrypted with the key, to check a passphrase
+            ALTER TABLE meta ADD COLUMN encryption_check TEXT;
+            "#,
         )?;
 
         Ok(())
     }
 
-    /// Rebuild the derived_elo_history and update Elo ratings in derived_stats.
-    fn rebuild_elo_cache(&self) -> Result<(), StorageError> {
-        use std::collections::HashMap;
+    fn load_or_create_actor_id(&self) -> Result<ActorId, StorageError> {
+        let actor_bytes: Vec<u8> =
+            self.conn
+                .query_row("SELECT actor_id FROM meta LIMIT 1", [], |row| row.get(0))?;
 
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis() as i64)
-            .unwrap_or(0);
+        ActorId::from_bytes(&actor_bytes).ok_or_else(|| {
+            StorageError::Database(rusqlite::Error::InvalidParameterCount(0, 16))
+        })
+    }
 
-        // Collect all match_end events sorted by match_id for deterministic replay
-        let mut stmt = self.conn.prepare(
-            "SELECT payload FROM events WHERE event_type = 'match_end' ORDER BY created_at, actor_id, seq"
-        )?;
+    // === Round Checkpoint Methods ===
 
-        let payloads: Vec<String> = stmt
-            .query_map([], |row| row.get::<_, String>(0))?
-            .filter_map(|r| r.ok())
-            .collect();
+    /// Save a snapshot of the in-progress solo round, replacing any previous one.
+    ///
+    /// Unlike the event log this is mutable scratch state: it is never synced
+    /// and is cleared once the round finishes.
+    pub fn save_round_checkpoint(&self, checkpoint: &RoundCheckpoint) -> Result<(), StorageError> {
+        let letters: String = checkpoint.letters.iter().collect();
+        let claimed_words: String = checkpoint
+            .claimed_words
+            .iter()
+            .map(|(word, points)| format!(r#"["{}",{}]"#, escape_json(word), points))
+            .collect::<Vec<_>>()
+            .join(",");
 
-        // Replay matches to compute Elo
-        const K: f64 = 32.0;
-        const DEFAULT_ELO: f64 = 1200.0;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO round_checkpoint (id, letters, time_remaining, score, claimed_words, saved_at)
+             VALUES (1, ?1, ?2, ?3, ?4, ?5)",
+            params![
+                letters,
+                checkpoint.time_remaining,
+                checkpoint.score,
+                format!("[{}]", claimed_words),
+                checkpoint.saved_at,
+            ],
+        )?;
+        Ok(())
+    }
 
-        let mut ratings: HashMap<String, f64> = HashMap::new();
+    /// Load the saved solo round checkpoint, if any.
+    pub fn load_round_checkpoint(&self) -> Result<Option<RoundCheckpoint>, StorageError> {
+        let result = self.conn.query_row(
+            "SELECT letters, time_remaining, score, claimed_words, saved_at FROM round_checkpoint WHERE id = 1",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, u32>(1)?,
+                    row.get::<_, u32>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            },
+        );
 
-        for payload in &payloads {
-            if let Some(match_result) = parse_match_result_payload(payload) {
-                if !match_result.completed || match_result.scores.len() < 2 {
-                    continue;
-                }
+        match result {
+            Ok((letters, time_remaining, score, claimed_words, saved_at)) => {
+                let wrapped = format!(r#"{{"scores":{}}}"#, claimed_words);
+                Ok(Some(RoundCheckpoint {
+                    letters: letters.chars().collect(),
+                    time_remaining,
+                    score,
+                    claimed_words: extract_json_scores(&wrapped).unwrap_or_default(),
+                    saved_at,
+                }))
+            }
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(StorageError::Database(e)),
+        }
+    }
 
-                let n = match_result.scores.len();
-                let k_adjusted = K / (n - 1) as f64;
+    /// Remove the saved solo round checkpoint (round finished or discarded).
+    pub fn clear_round_checkpoint(&self) -> Result<(), StorageError> {
+        self.conn.execute("DELETE FROM round_checkpoint", [])?;
+        Ok(())
+    }
 
-                // Get current ratings
-                let player_ratings: Vec<(String, u32, f64)> = match_result
-                    .scores
-                    .iter()
-                    .map(|(name, score)| {
-                        let rating = *ratings.get(name).unwrap_or(&DEFAULT_ELO);
-                        (name.clone(), *score, rating)
-                    })
-                    .collect();
+    // === Rack Difficulty Methods ===
+
+    /// Whether the local player has opted in to recording rack difficulty.
+    pub fn rack_telemetry_enabled(&self) -> SqlResult<bool> {
+        self.conn
+            .query_row("SELECT rack_telemetry FROM meta LIMIT 1", [], |row| row.get(0))
+    }
 
-                // Calculate rating changes using pairwise comparisons
-                let mut rating_changes: HashMap<String, f64> = HashMap::new();
+    /// Opt in to (or out of) recording rack difficulty.
+    pub fn set_rack_telemetry(&self, enabled: bool) -> SqlResult<()> {
+        self.conn
+            .execute("UPDATE meta SET rack_telemetry = ?1", params![enabled])?;
+        Ok(())
+    }
 
-                for (i, (player_a, score_a, rating_a)) in player_ratings.iter().enumerate() {
-                    let mut total_change = 0.0;
+    /// Record how a round's rack played out, if the player has opted in.
+    ///
+    /// Returns whether a row was written. The data never leaves this device:
+    /// it isn't part of the event log and is never synced.
+    pub fn record_rack_difficulty(&self, difficulty: &RackDifficulty) -> Result<bool, StorageError> {
+        if !self.rack_telemetry_enabled()? {
+            return Ok(false);
+        }
 
-                    for (j, (_, score_b, rating_b)) in player_ratings.iter().enumerate() {
-                        if i == j {
-                            continue;
-                        }
+        let letters: String = difficulty.letters.iter().collect();
+        self.conn.execute(
+            "INSERT INTO rack_difficulty (letters, valid_words, max_points, achieved_points, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                letters,
+                difficulty.valid_words,
+                difficulty.max_points,
+                difficulty.achieved_points,
+                difficulty.recorded_at
+            ],
+        )?;
+        Ok(true)
+    }
 
-                        let actual = match score_a.cmp(score_b) {
-                            std::cmp::Ordering::Greater => 1.0,
-                            std::cmp::Ordering::Equal => 0.5,
-                            std::cmp::Ordering::Less => 0.0,
-                        };
+    /// All recorded rack difficulty rows, oldest first.
+    pub fn rack_difficulty_report(&self) -> Result<Vec<RackDifficulty>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT letters, valid_words, max_points, achieved_points, recorded_at
+             FROM rack_difficulty ORDER BY id",
+        )?;
 
-                        let expected = 1.0 / (1.0 + 10.0_f64.powf((rating_b - rating_a) / 400.0));
-                        total_change += k_adjusted * (actual - expected);
-                    }
+        let rows = stmt.query_map([], |row| {
+            Ok(RackDifficulty {
+                letters: row.get::<_, String>(0)?.chars().collect(),
+                valid_words: row.get(1)?,
+                max_points: row.get(2)?,
+                achieved_points: row.get(3)?,
+                recorded_at: row.get(4)?,
+            })
+        })?;
 
-                    rating_changes.insert(player_a.clone(), total_change);
-                }
+        let mut report = Vec::new();
+        for row in rows {
+            report.push(row?);
+        }
+        Ok(report)
+    }
 
-                // Record Elo history and apply changes
-                for (player, change) in &rating_changes {
-                    let elo_before = *ratings.get(player).unwrap_or(&DEFAULT_ELO);
-                    let elo_after = elo_before + change;
+    // === Derived Cache Methods ===
 
-                    self.conn.execute(
-                        "INSERT OR REPLACE INTO derived_elo_history (match_id, handle, elo_before, elo_after, elo_change)
-                         VALUES (?1, ?2, ?3, ?4, ?5)",
-                        params![match_result.match_id, player, elo_before, elo_after, change],
-                    )?;
+    /// Drop and rebuild all derived caches from the event log.
+    ///
+    /// This is safe to call at any time - derived data can always be
+    /// recomputed from the authoritative event log. Useful after:
+    /// - Schema upgrades
+    /// - CRDT sync that added many events
+    /// - Suspected cache corruption
+    ///
+    /// If any step fails the caches are left exactly as they were and the
+    /// error comes back as `StorageError::RebuildFailed`.
+    pub fn rebuild_derived_caches(&self) -> Result<(), StorageError> {
+        // One transaction, so other connections keep reading the old caches
+        // until the new ones are complete
+        let tx = self.conn.unchecked_transaction()?;
+        self.replace_derived_caches()?;
+        tx.commit()?;
+        Ok(())
+    }
 
-                    ratings.insert(player.clone(), elo_after);
-                }
+    /// Bring the derived caches up to date with events added since they
+    /// were last built.
+    ///
+    /// Only `match_end` and `word_claimed` events past the watermark in
+    /// `derived_cache_meta` are read, and only the players they mention are
+    /// updated. Falls back to `rebuild_derived_caches` when there's no
+    /// usable watermark (never built, built by an older version, or events
+    /// removed since) or when a new match would replay before one already
+    /// in the Elo history.
+    pub fn update_derived_caches_incremental(&self) -> Result<(), StorageError> {
+        let tx = self.conn.unchecked_transaction()?;
+        let updated = match self.cache_watermark()? {
+            Some(watermark) => {
+                self.rebuild_atomically("derived", |storage| storage.apply_events_since(watermark))?
             }
+            None => false,
+        };
+        if !updated {
+            self.replace_derived_caches()?;
         }
+        tx.commit()?;
+        Ok(())
+    }
 
-        // Update Elo ratings in derived_stats
-        for (handle, elo) in &ratings {
-            self.conn.execute(
-                "UPDATE derived_stats SET elo = ?1, last_updated = ?2 WHERE handle = ?3",
-                params![elo, now, handle],
-            )?;
+    /// Clear the derived caches and rebuild them, inside the caller's
+    /// transaction
+    fn replace_derived_caches(&self) -> Result<(), StorageError> {
+        // Clear existing derived data
+        self.conn.execute_batch(
+            r#"
+            DELETE FROM derived_stats;
+            DELETE FROM derived_elo_history;
+            DELETE FROM derived_cache_meta;
+            "#,
+        )?;
+
+        // Rebuild from events
+        self.rebuild_atomically("stats", Self::rebuild_stats_cache)?;
+        self.rebuild_atomically("elo", Self::rebuild_elo_cache)?;
+        Ok(())
+    }
+
+    /// Run `rebuild` inside a savepoint, rolling back everything it wrote
+    /// if it fails partway
+    ///
+    /// The caches are rebuilt with many separate statements; without this a
+    /// failure would leave them half-populated. Errors are reported as
+    /// `RebuildFailed` for `cache`.
+    fn rebuild_atomically<T>(
+        &self,
+        cache: &'static str,
+        rebuild: impl FnOnce(&Self) -> Result<T, StorageError>,
+    ) -> Result<T, StorageError> {
+        self.conn.execute_batch("SAVEPOINT cache_rebuild")?;
+        match rebuild(self) {
+            Ok(value) => {
+                self.conn.execute_batch("RELEASE cache_rebuild")?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.conn
+                    .execute_batch("ROLLBACK TO cache_rebuild; RELEASE cache_rebuild")?;
+                Err(StorageError::RebuildFailed { cache, reason: e.to_string() })
+            }
         }
+    }
 
-        // Update cache metadata
-        let event_count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM events WHERE event_type = 'match_end'",
-            [],
-            |row| row.get(0),
-        )?;
+    /// Rowid of the newest event (0 for an empty log)
+    ///
+    /// Rowids only grow as events are appended, so the caches record the
+    /// newest one they've seen as their watermark.
+    fn event_watermark(&self) -> SqlResult<i64> {
+        self.conn
+            .query_row("SELECT COALESCE(MAX(rowid), 0) FROM events", [], |row| row.get(0))
+    }
+
+    /// How many events feeding `cache_name` are at or below `watermark`
+    fn cached_event_count(&self, cache_name: &str, watermark: i64) -> SqlResult<i64> {
+        let sql = match cache_name {
+            "stats" => {
+                "SELECT COUNT(*) FROM events
+                 WHERE event_type IN ('match_end', 'word_claimed') AND rowid <= ?1"
+            }
+            _ => "SELECT COUNT(*) FROM events WHERE event_type = 'match_end' AND rowid <= ?1",
+        };
+        self.conn.query_row(sql, params![watermark], |row| row.get(0))
+    }
 
+    /// Record that `cache_name` covers every event up to `watermark`
+    fn record_cache_meta(&self, cache_name: &str, watermark: i64, now: i64) -> SqlResult<()> {
+        let event_count = self.cached_event_count(cache_name, watermark)?;
         self.conn.execute(
             "INSERT OR REPLACE INTO derived_cache_meta (cache_name, last_event_seq, last_rebuilt, event_count)
-             VALUES ('elo', 0, ?1, ?2)",
-            params![now, event_count],
+             VALUES (?1, ?2, ?3, ?4)",
+            params![cache_name, watermark, now, event_count],
         )?;
-
         Ok(())
     }
 
-    /// Get cached stats for a player from derived_stats.
-    pub fn get_cached_stats(&self, handle: &str) -> Result<Option<CachedPlayerStats>, StorageError> {
-        let result = self.conn.query_row(
-            "SELECT elo, rounds_played, total_points, best_score, longest_word, words_claimed, wins
-             FROM derived_stats WHERE handle = ?1",
-            params![handle],
-            |row| {
-                Ok(CachedPlayerStats {
-                    handle: handle.to_string(),
-                    elo: row.get(0)?,
-                    rounds_played: row.get(1)?,
+    /// The watermark both caches were last brought up to, if it can be
+    /// trusted
+    ///
+    /// The event counts recorded with it must still match the log: events
+    /// removed since (compaction) would otherwise go unnoticed.
+    fn cache_watermark(&self) -> Result<Option<i64>, StorageError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT cache_name, last_event_seq, event_count FROM derived_cache_meta")?;
+        let rows: Vec<(String, i64, i64)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<SqlResult<_>>()?;
+
+        let meta = |name: &str| {
+            rows.iter().find(|(n, _, _)| n == name).map(|(_, seq, count)| (*seq, *count))
+        };
+        let (Some((watermark, stats_count)), Some((elo_watermark, elo_count))) =
+            (meta("stats"), meta("elo"))
+        else {
+            return Ok(None);
+        };
+        if watermark != elo_watermark || watermark > self.event_watermark()? {
+            return Ok(None);
+        }
+        if self.cached_event_count("stats", watermark)? != stats_count
+            || self.cached_event_count("elo", watermark)? != elo_count
+        {
+            return Ok(None);
+        }
+        Ok(Some(watermark))
+    }
+
+    /// Fold the events after `watermark` into the caches
+    ///
+    /// Returns false, having written nothing, if the new matches can't be
+    /// replayed on top of the existing Elo ratings.
+    fn apply_events_since(&self, watermark: i64) -> Result<bool, StorageError> {
+        use std::collections::{HashMap, HashSet};
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+        let new_watermark = self.event_watermark()?;
+
+        let mut matches: Vec<MatchResult> = self
+            .query_payloads(
+                "SELECT payload FROM events
+                 WHERE event_type = 'match_end' AND rowid > ?1 AND rowid <= ?2
+                 ORDER BY created_at, actor_id, seq",
+                params![watermark, new_watermark],
+            )?
+            .into_iter()
+            .filter_map(|payload| parse_match_result_payload(&payload))
+            .collect();
+        sort_for_replay(&mut matches);
+
+        // Ratings only carry forward if every new rated match replays after
+        // the ones already in the history
+        let last_rated: Option<i64> = self.conn.query_row(
+            "SELECT MAX(match_id) FROM derived_elo_history",
+            [],
+            |row| row.get(0),
+        )?;
+        if let Some(last_rated) = last_rated {
+            if matches
+                .iter()
+                .any(|m| m.completed && m.scores.len() >= 2 && m.match_id <= last_rated)
+            {
+                return Ok(false);
+            }
+        }
+
+        let claims: Vec<json::Value> = self
+            .query_payloads(
+                "SELECT payload FROM events
+                 WHERE event_type = 'word_claimed' AND rowid > ?1 AND rowid <= ?2
+                 ORDER BY created_at",
+                params![watermark, new_watermark],
+            )?
+            .into_iter()
+            .filter_map(|payload| json::parse(&payload))
+            .collect();
+
+        // Claims already counted, for the rounds the new claims belong to
+        let mut seen_claims: HashSet<(i64, String, String)> = HashSet::new();
+        let round_ids: HashSet<i64> =
+            claims.iter().filter_map(|claim| claim.number_field("round_id")).collect();
+        // The LIKE only narrows down plain payloads; encrypted ones are all
+        // read, once
+        let patterns: Vec<String> = if self.is_encrypted() {
+            vec!["%".to_string()]
+        } else {
+            round_ids.iter().map(|round_id| format!("%{}%", round_id)).collect()
+        };
+        for pattern in patterns {
+            let payloads = self.query_payloads(
+                "SELECT payload FROM events
+                 WHERE event_type = 'word_claimed' AND rowid <= ?1 AND payload LIKE ?2",
+                params![watermark, pattern],
+            )?;
+            for payload in payloads.iter().filter_map(|p| json::parse(p)) {
+                if let (Some(round_id), Some(word), Some(handle)) = (
+                    payload.number_field::<i64>("round_id"),
+                    payload.str_field("word"),
+                    payload.str_field("player_name"),
+                ) {
+                    if round_ids.contains(&round_id) {
+                        seen_claims.insert((round_id, word.to_uppercase(), handle));
+                    }
+                }
+            }
+        }
+
+        // Start each affected player from their cached row
+        let handles = matches
+            .iter()
+            .flat_map(|m| m.scores.iter().map(|(handle, _)| handle.clone()))
+            .chain(claims.iter().filter_map(|claim| claim.str_field("player_name")));
+        let mut updated: HashMap<String, CachedPlayerStats> = HashMap::new();
+        for handle in handles {
+            if updated.contains_key(&handle) {
+                continue;
+            }
+            let stats = self.get_cached_stats(&handle)?.unwrap_or(CachedPlayerStats {
+                handle: handle.clone(),
+                elo: DEFAULT_ELO,
+                rounds_played: 0,
+                total_points: 0,
+                best_score: 0,
+                longest_word: String::new(),
+                words_claimed: 0,
+                wins: 0,
+                total_claim_chars: 0,
+            });
+            updated.insert(handle, stats);
+        }
+
+        for match_result in &matches {
+            let max_score = match_result.scores.iter().map(|(_, s)| *s).max().unwrap_or(0);
+            let is_multiplayer = match_result.scores.len() >= 2;
+            for (handle, score) in &match_result.scores {
+                let stats = updated.get_mut(handle).expect("player loaded above");
+                stats.rounds_played += 1;
+                stats.total_points += score;
+                stats.best_score = stats.best_score.max(*score);
+                if is_multiplayer && *score == max_score {
+                    stats.wins += 1;
+                }
+            }
+        }
+
+        for claim in &claims {
+            let (Some(handle), Some(word)) =
+                (claim.str_field("player_name"), claim.str_field("word"))
+            else {
+                continue;
+            };
+            if let Some(round_id) = claim.number_field::<i64>("round_id") {
+                if !seen_claims.insert((round_id, word.to_uppercase(), handle.clone())) {
+                    continue;
+                }
+            }
+            let stats = updated.get_mut(&handle).expect("player loaded above");
+            stats.words_claimed += 1;
+            stats.total_claim_chars += word.chars().count() as u32;
+            if word.len() > stats.longest_word.len() {
+                stats.longest_word = word;
+            }
+        }
+
+        // Replay the new matches from each player's current rating
+        let k_factor = self.elo_k_factor().unwrap_or(DEFAULT_K);
+        let mut ratings: HashMap<String, f64> =
+            updated.values().map(|stats| (stats.handle.clone(), stats.elo)).collect();
+        for match_result in &matches {
+            self.replay_elo_match(match_result, &mut ratings, k_factor)?;
+        }
+
+        for stats in updated.values() {
+            self.conn.execute(
+                "INSERT OR REPLACE INTO derived_stats (handle, elo, rounds_played, total_points, best_score, longest_word, words_claimed, wins, last_updated, total_claim_chars)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    stats.handle,
+                    ratings.get(&stats.handle).copied().unwrap_or(stats.elo),
+                    stats.rounds_played,
+                    stats.total_points,
+                    stats.best_score,
+                    &stats.longest_word,
+                    stats.words_claimed,
+                    stats.wins,
+                    now,
+                    stats.total_claim_chars
+                ],
+            )?;
+        }
+
+        self.record_cache_meta("stats", new_watermark, now)?;
+        self.record_cache_meta("elo", new_watermark, now)?;
+        Ok(true)
+    }
+
+    /// Rebuild the derived_stats cache from match_end events.
+    fn rebuild_stats_cache(&self) -> Result<(), StorageError> {
+        use std::collections::{HashMap, HashSet};
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+
+        // Collect all match_end events
+        let payloads: Vec<String> = self.query_payloads(
+            "SELECT payload FROM events WHERE event_type = 'match_end' ORDER BY created_at, actor_id, seq",
+            [],
+        )?;
+
+        // Track stats for each player
+        struct Stats {
+            elo: f64,
+            rounds_played: u32,
+            total_points: u32,
+            best_score: u32,
+            longest_word: String,
+            words_claimed: u32,
+            total_claim_chars: u32,
+            wins: u32,
+        }
+
+        let mut player_stats: HashMap<String, Stats> = HashMap::new();
+
+        // Start from the claims compaction folded away
+        let mut stmt = self.conn.prepare(
+            "SELECT handle, words_claimed, total_claim_chars, longest_word FROM compacted_claims",
+        )?;
+        let compacted: Vec<(String, u32, u32, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        for (handle, words_claimed, total_claim_chars, longest_word) in compacted {
+            player_stats.insert(
+                handle,
+                Stats {
+                    elo: 1200.0,
+                    rounds_played: 0,
+                    total_points: 0,
+                    best_score: 0,
+                    longest_word,
+                    words_claimed,
+                    total_claim_chars,
+                    wins: 0,
+                },
+            );
+        }
+
+        for payload in &payloads {
+            if let Some(match_result) = parse_match_result_payload(payload) {
+                // Find winner(s)
+                let max_score = match_result.scores.iter().map(|(_, s)| *s).max().unwrap_or(0);
+                let is_multiplayer = match_result.scores.len() >= 2;
+
+                for (handle, score) in &match_result.scores {
+                    let stats = player_stats.entry(handle.clone()).or_insert(Stats {
+                        elo: 1200.0,
+                        rounds_played: 0,
+                        total_points: 0,
+                        best_score: 0,
+                        longest_word: String::new(),
+                        words_claimed: 0,
+                        total_claim_chars: 0,
+                        wins: 0,
+                    });
+
+                    stats.rounds_played += 1;
+                    stats.total_points += score;
+                    if *score > stats.best_score {
+                        stats.best_score = *score;
+                    }
+                    if is_multiplayer && *score == max_score {
+                        stats.wins += 1;
+                    }
+                }
+            }
+        }
+
+        // Also count word claims from word_claimed events
+        let claim_payloads: Vec<String> = self.query_payloads(
+            "SELECT payload FROM events WHERE event_type = 'word_claimed' ORDER BY created_at",
+            [],
+        )?;
+
+        // Host and clients each append their own copy of a claim, so the same
+        // claim can show up under several actor_ids after sync. Count each
+        // (round_id, word, player_name) once. Claims logged without a round_id
+        // predate this and can't be matched up, so they're counted as-is.
+        let mut seen_claims: HashSet<(i64, String, String)> = HashSet::new();
+
+        for payload in claim_payloads.iter().filter_map(|p| json::parse(p)) {
+            if let (Some(handle), Some(word)) =
+                (payload.str_field("player_name"), payload.str_field("word"))
+            {
+                if let Some(round_id) = payload.number_field::<i64>("round_id") {
+                    if !seen_claims.insert((round_id, word.to_uppercase(), handle.clone())) {
+                        continue;
+                    }
+                }
+
+                let stats = player_stats.entry(handle).or_insert(Stats {
+                    elo: 1200.0,
+                    rounds_played: 0,
+                    total_points: 0,
+                    best_score: 0,
+                    longest_word: String::new(),
+                    words_claimed: 0,
+                    total_claim_chars: 0,
+                    wins: 0,
+                });
+
+                stats.words_claimed += 1;
+                stats.total_claim_chars += word.chars().count() as u32;
+                if word.len() > stats.longest_word.len() {
+                    stats.longest_word = word;
+                }
+            }
+        }
+
+        // Insert into derived_stats
+        for (handle, stats) in &player_stats {
+            self.conn.execute(
+                "INSERT INTO derived_stats (handle, elo, rounds_played, total_points, best_score, longest_word, words_claimed, wins, last_updated, total_claim_chars)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    handle,
+                    stats.elo, // Will be updated by Elo rebuild
+                    stats.rounds_played,
+                    stats.total_points,
+                    stats.best_score,
+                    &stats.longest_word,
+                    stats.words_claimed,
+                    stats.wins,
+                    now,
+                    stats.total_claim_chars
+                ],
+            )?;
+        }
+
+        // Update cache metadata
+        self.record_cache_meta("stats", self.event_watermark()?, now)?;
+
+        Ok(())
+    }
+
+    /// Rebuild the derived_elo_history and update Elo ratings in derived_stats.
+    fn rebuild_elo_cache(&self) -> Result<(), StorageError> {
+        use std::collections::HashMap;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+
+        // Collect all match_end events and order them for deterministic replay
+        let mut matches: Vec<MatchResult> = self
+            .query_payloads(
+                "SELECT payload FROM events WHERE event_type = 'match_end' ORDER BY created_at, actor_id, seq",
+                [],
+            )?
+            .into_iter()
+            .filter_map(|payload| parse_match_result_payload(&payload))
+            .collect();
+        sort_for_replay(&mut matches);
+
+        // Replay matches to compute Elo
+        let k_factor = self.elo_k_factor().unwrap_or(DEFAULT_K);
+
+        let mut ratings: HashMap<String, f64> = HashMap::new();
+
+        for match_result in &matches {
+            self.replay_elo_match(match_result, &mut ratings, k_factor)?;
+        }
+
+        // Update Elo ratings in derived_stats
+        for (handle, elo) in &ratings {
+            self.conn.execute(
+                "UPDATE derived_stats SET elo = ?1, last_updated = ?2 WHERE handle = ?3",
+                params![elo, now, handle],
+            )?;
+        }
+
+        // Update cache metadata
+        self.record_cache_meta("elo", self.event_watermark()?, now)?;
+
+        Ok(())
+    }
+
+    /// Replay one match into `ratings`, recording each player's change in
+    /// derived_elo_history
+    ///
+    /// Solo and abandoned matches don't move ratings.
+    fn replay_elo_match(
+        &self,
+        match_result: &MatchResult,
+        ratings: &mut std::collections::HashMap<String, f64>,
+        k_factor: f64,
+    ) -> Result<(), StorageError> {
+        use std::collections::HashMap;
+
+        if !match_result.completed || match_result.scores.len() < 2 {
+            return Ok(());
+        }
+
+        let n = match_result.scores.len();
+        let k_adjusted = k_factor / (n - 1) as f64;
+
+        // Get current ratings
+        let player_ratings: Vec<(String, u32, f64)> = match_result
+            .scores
+            .iter()
+            .map(|(name, score)| {
+                let rating = *ratings.get(name).unwrap_or(&DEFAULT_ELO);
+                (name.clone(), *score, rating)
+            })
+            .collect();
+
+        // Calculate rating changes using pairwise comparisons
+        let mut rating_changes: HashMap<String, f64> = HashMap::new();
+
+        for (i, (player_a, score_a, rating_a)) in player_ratings.iter().enumerate() {
+            let mut total_change = 0.0;
+
+            for (j, (_, score_b, rating_b)) in player_ratings.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+
+                let actual = match score_a.cmp(score_b) {
+                    std::cmp::Ordering::Greater => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Less => 0.0,
+                };
+
+                let expected = 1.0 / (1.0 + 10.0_f64.powf((rating_b - rating_a) / 400.0));
+                total_change += k_adjusted * (actual - expected);
+            }
+
+            rating_changes.insert(player_a.clone(), total_change);
+        }
+
+        // Record Elo history and apply changes
+        for (player, change) in &rating_changes {
+            let elo_before = *ratings.get(player).unwrap_or(&DEFAULT_ELO);
+            let elo_after = elo_before + change;
+
+            self.conn.execute(
+                "INSERT OR REPLACE INTO derived_elo_history (match_id, handle, elo_before, elo_after, elo_change)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![match_result.match_id, player, elo_before, elo_after, change],
+            )?;
+
+            ratings.insert(player.clone(), elo_after);
+        }
+
+        Ok(())
+    }
+
+    /// Get cached stats for a player from derived_stats.
+    pub fn get_cached_stats(&self, handle: &str) -> Result<Option<CachedPlayerStats>, StorageError> {
+        let result = self.conn.query_row(
+            "SELECT elo, rounds_played, total_points, best_score, longest_word, words_claimed, wins, total_claim_chars
+             FROM derived_stats WHERE handle = ?1",
+            params![handle],
+            |row| {
+                Ok(CachedPlayerStats {
+                    handle: handle.to_string(),
+                    elo: row.get(0)?,
+                    rounds_played: row.get(1)?,
                     total_points: row.get(2)?,
                     best_score: row.get(3)?,
                     longest_word: row.get(4)?,
                     words_claimed: row.get(5)?,
                     wins: row.get(6)?,
+                    total_claim_chars: row.get(7)?,
                 })
             },
         );
@@ -846,420 +2163,1215 @@ impl Storage {
         }
     }
 
-    /// Get the Elo leaderboard from cached stats.
-    pub fn get_cached_leaderboard(&self) -> Result<Vec<(String, f64)>, StorageError> {
-        let mut stmt = self.conn.prepare(
-            "SELECT handle, elo FROM derived_stats ORDER BY elo DESC"
-        )?;
-
-        let rows = stmt.query_map([], |row| {
-            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
-        })?;
+    /// Get the Elo leaderboard from cached stats.
+    pub fn get_cached_leaderboard(&self) -> Result<Vec<(String, f64)>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT handle, elo FROM derived_stats ORDER BY elo DESC"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+        })?;
+
+        let mut leaderboard = Vec::new();
+        for row in rows {
+            leaderboard.push(row?);
+        }
+
+        Ok(leaderboard)
+    }
+
+    /// Get cached stats for every player, highest Elo first.
+    pub fn get_all_cached_stats(&self) -> Result<Vec<CachedPlayerStats>, StorageError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT handle, elo, rounds_played, total_points, best_score, longest_word, words_claimed, wins, total_claim_chars
+             FROM derived_stats ORDER BY elo DESC"
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok(CachedPlayerStats {
+                handle: row.get(0)?,
+                elo: row.get(1)?,
+                rounds_played: row.get(2)?,
+                total_points: row.get(3)?,
+                best_score: row.get(4)?,
+                longest_word: row.get(5)?,
+                words_claimed: row.get(6)?,
+                wins: row.get(7)?,
+                total_claim_chars: row.get(8)?,
+            })
+        })?;
+
+        let mut players = Vec::new();
+        for row in rows {
+            players.push(row?);
+        }
+
+        Ok(players)
+    }
+
+    /// Get the leaderboard for a single season.
+    ///
+    /// Aggregates `match_end` events whose payload is tagged with `season`.
+    /// Matches recorded before seasons existed count toward season 1.
+    /// Standings are ordered by wins, then total points.
+    pub fn get_season_leaderboard(&self, season: u32) -> Result<Vec<SeasonStanding>, StorageError> {
+        use std::collections::HashMap;
+
+        let payloads: Vec<String> = self.query_payloads(
+            "SELECT payload FROM events WHERE event_type = 'match_end'",
+            [],
+        )?;
+
+        let mut standings: HashMap<String, SeasonStanding> = HashMap::new();
+        for payload in &payloads {
+            let Some(match_result) = parse_match_result_payload(payload) else {
+                continue;
+            };
+            if match_result.season.unwrap_or(1) != season {
+                continue;
+            }
+
+            let max_score = match_result.scores.iter().map(|(_, s)| *s).max().unwrap_or(0);
+            for (handle, score) in &match_result.scores {
+                let standing = standings.entry(handle.clone()).or_insert_with(|| SeasonStanding {
+                    handle: handle.clone(),
+                    rounds_played: 0,
+                    wins: 0,
+                    total_points: 0,
+                });
+                standing.rounds_played += 1;
+                standing.total_points += score;
+                if match_result.is_multiplayer() && *score == max_score {
+                    standing.wins += 1;
+                }
+            }
+        }
+
+        let mut leaderboard: Vec<SeasonStanding> = standings.into_values().collect();
+        leaderboard.sort_by(|a, b| {
+            b.wins
+                .cmp(&a.wins)
+                .then(b.total_points.cmp(&a.total_points))
+                .then(a.handle.cmp(&b.handle))
+        });
+        Ok(leaderboard)
+    }
+
+    /// Most recent matches first, at most `limit` of them.
+    ///
+    /// A match synced from several devices is listed once.
+    pub fn recent_matches(&self, limit: usize) -> Result<Vec<MatchResult>, StorageError> {
+        let payloads: Vec<String> = self.query_payloads(
+            "SELECT payload FROM events WHERE event_type = 'match_end' ORDER BY created_at DESC, actor_id, seq DESC",
+            [],
+        )?;
+
+        let mut seen = std::collections::HashSet::new();
+        Ok(payloads
+            .iter()
+            .filter_map(|payload| parse_match_result_payload(payload))
+            .filter(|result| seen.insert(result.match_id))
+            .take(limit)
+            .collect())
+    }
+
+    /// Format a match as plain text for sharing: final standings, each
+    /// player's words and the best word.
+    ///
+    /// Words come from the `word_claimed` events logged under the match's
+    /// round_id; matches recorded without one get standings only. Returns
+    /// `None` if no match has this ID.
+    pub fn format_match_report(&self, match_id: i64) -> Result<Option<String>, StorageError> {
+        // The most recent copy, as the match history lists it
+        let Some(result) = self
+            .get_match_events(match_id)?
+            .iter()
+            .rev()
+            .find_map(|event| parse_match_result_payload(&event.payload))
+        else {
+            return Ok(None);
+        };
+
+        // (word, player, points) in claim order, deduplicated across devices
+        let mut claims: Vec<(String, String, u32)> = Vec::new();
+        if let Some(round_id) = result.round_id {
+            let payloads: Vec<String> = self.query_payloads(
+                "SELECT payload FROM events WHERE event_type = 'word_claimed' ORDER BY created_at, actor_id, seq",
+                [],
+            )?;
+            for payload in payloads.iter().filter_map(|p| json::parse(p)) {
+                if payload.number_field::<i64>("round_id") != Some(round_id) {
+                    continue;
+                }
+                let (Some(word), Some(player)) =
+                    (payload.str_field("word"), payload.str_field("player_name"))
+                else {
+                    continue;
+                };
+                let word = word.to_uppercase();
+                let points = payload.number_field("points").unwrap_or(0);
+                if !claims.iter().any(|(w, p, _)| *w == word && *p == player) {
+                    claims.push((word, player, points));
+                }
+            }
+        }
+
+        let mut standings = result.scores.clone();
+        standings.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let name_width = standings.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+
+        let mut report = format!("BLAM! match {}\n\nFinal standings\n", result.match_id);
+        for (rank, (name, score)) in standings.iter().enumerate() {
+            report.push_str(&format!("{:>2}. {:<width$}  {:>3} pts\n", rank + 1, name, score, width = name_width));
+        }
+
+        if !claims.is_empty() {
+            report.push_str("\nWords\n");
+            for (name, _) in &standings {
+                let words: Vec<&str> = claims
+                    .iter()
+                    .filter(|(_, player, _)| player == name)
+                    .map(|(word, _, _)| word.as_str())
+                    .collect();
+                if !words.is_empty() {
+                    report.push_str(&format!("{:<width$}  {}\n", name, words.join(", "), width = name_width));
+                }
+            }
+
+            // First claim wins ties, as it did in the round
+            let mut best = &claims[0];
+            for claim in &claims[1..] {
+                if claim.2 > best.2 {
+                    best = claim;
+                }
+            }
+            report.push_str(&format!("\nBest word: {} by {} ({} pts)\n", best.0, best.1, best.2));
+        }
+
+        Ok(Some(report))
+    }
+
+    /// Check if caches need rebuilding (e.g., after CRDT sync added new events).
+    pub fn caches_need_rebuild(&self) -> Result<bool, StorageError> {
+        // Get current event counts
+        let match_count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM events WHERE event_type = 'match_end'",
+            [],
+            |row| row.get(0),
+        )?;
+
+        // Get cached event count
+        let cached_count: i64 = self.conn.query_row(
+            "SELECT COALESCE(event_count, 0) FROM derived_cache_meta WHERE cache_name = 'elo'",
+            [],
+            |row| row.get(0),
+        ).unwrap_or(0);
+
+        Ok(match_count != cached_count)
+    }
+}
+
+/// Cached player statistics from derived_stats table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedPlayerStats {
+    pub handle: String,
+    pub elo: f64,
+    pub rounds_played: u32,
+    pub total_points: u32,
+    pub best_score: u32,
+    pub longest_word: String,
+    pub words_claimed: u32,
+    pub wins: u32,
+    /// Total letters across all claimed words
+    pub total_claim_chars: u32,
+}
+
+impl CachedPlayerStats {
+    /// Average length of the player's claimed words (0.0 with no claims)
+    pub fn average_claim_length(&self) -> f64 {
+        if self.words_claimed == 0 {
+            0.0
+        } else {
+            self.total_claim_chars as f64 / self.words_claimed as f64
+        }
+    }
+}
+
+/// A player's standing on a season leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeasonStanding {
+    pub handle: String,
+    pub rounds_played: u32,
+    pub wins: u32,
+    pub total_points: u32,
+}
+
+/// Snapshot of an in-progress solo round, used to resume after a crash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundCheckpoint {
+    /// Letter rack for the round
+    pub letters: Vec<char>,
+    /// Seconds left on the timer when the snapshot was taken
+    pub time_remaining: u32,
+    /// Score at the time of the snapshot
+    pub score: u32,
+    /// Words claimed so far with their points
+    pub claimed_words: Vec<(String, u32)>,
+    /// When the snapshot was taken (ms since epoch)
+    pub saved_at: i64,
+}
+
+/// How solvable a round's rack was, for tuning the rack generator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RackDifficulty {
+    /// Letter rack for the round
+    pub letters: Vec<char>,
+    /// Number of dictionary words the rack could make
+    pub valid_words: u32,
+    /// Points available if every one of those words were claimed
+    pub max_points: u32,
+    /// Points the player actually scored
+    pub achieved_points: u32,
+    /// When the round was recorded (ms since epoch)
+    pub recorded_at: i64,
+}
+
+impl RackDifficulty {
+    /// Fraction of the available points the player achieved (0.0 to 1.0)
+    pub fn achieved_fraction(&self) -> f64 {
+        if self.max_points == 0 {
+            0.0
+        } else {
+            self.achieved_points as f64 / self.max_points as f64
+        }
+    }
+}
+
+/// Parse a match_end event payload to extract match result.
+///
+/// Lenient about `host_actor_id`, which some older payloads omit.
+fn parse_match_result_payload(payload: &str) -> Option<MatchResult> {
+    let value = json::parse(payload)?;
+    Some(MatchResult {
+        match_id: value.number_field("match_id")?,
+        scores: crate::stats::extract_pairs(&value, "scores")?,
+        host_actor_id: value.str_field("host_actor_id").unwrap_or_default(),
+        completed: value.bool_field("completed").unwrap_or(true),
+        host_counter: value.number_field("host_counter"),
+        season: value.number_field("season"),
+        round_id: value.number_field("round_id"),
+        match_group: value.number_field("match_group"),
+        teams: crate::stats::teams_of(&value),
+    })
+}
+
+// === Versioned Payload Helpers ===
+
+/// Create a versioned event payload with the current payload version.
+/// The payload_version field enables forward compatibility - older binaries
+/// can read newer payloads by ignoring unknown fields.
+pub fn create_versioned_payload(inner_json: &str) -> String {
+    // Insert payload_version at the beginning of the JSON object
+    if inner_json.starts_with('{') {
+        format!(
+            r#"{{"payload_version":{},"#,
+            PAYLOAD_VERSION
+        ) + &inner_json[1..]
+    } else {
+        // If not a JSON object, wrap it
+        format!(
+            r#"{{"payload_version":{},"data":{}}}"#,
+            PAYLOAD_VERSION, inner_json
+        )
+    }
+}
+
+/// Extract the payload version from a versioned payload.
+/// Returns None if the payload doesn't have a version (pre-versioning payloads).
+pub fn extract_payload_version(payload: &str) -> Option<u32> {
+    extract_json_i64(payload, "payload_version").map(|v| v as u32)
+}
+
+/// Check if a payload is compatible with the current version.
+/// Returns true if:
+/// - The payload has no version (legacy payload, always compatible)
+/// - The payload version is <= current version (backward compatible)
+pub fn is_payload_compatible(payload: &str) -> bool {
+    match extract_payload_version(payload) {
+        None => true, // Legacy payload without version
+        Some(v) => v <= PAYLOAD_VERSION,
+    }
+}
+
+// === JSON Helper Functions ===
+
+fn extract_json_string(json: &str, key: &str) -> Option<String> {
+    json::parse(json)?.str_field(key)
+}
+
+fn extract_json_i64(json: &str, key: &str) -> Option<i64> {
+    json::parse(json)?.number_field(key)
+}
+
+fn extract_json_bool(json: &str, key: &str) -> Option<bool> {
+    json::parse(json)?.bool_field(key)
+}
+
+fn extract_json_scores(json: &str) -> Option<Vec<(String, u32)>> {
+    crate::stats::extract_pairs(&json::parse(json)?, "scores")
+}
+
+/// Value of the events.match_id column: set for `match_end` events only
+fn event_match_id(event_type: &str, payload: &str) -> Option<i64> {
+    if event_type != "match_end" {
+        return None;
+    }
+    extract_json_i64(payload, "match_id")
+}
+
+/// One event as a line of an export
+fn event_to_json_line(event: &Event) -> String {
+    format!(
+        r#"{{"actor_id":"{}","seq":{},"event_type":"{}","created_at":{},"payload":"{}"}}"#,
+        event.actor_id.to_hex(),
+        event.seq,
+        escape_json(&event.event_type),
+        event.created_at,
+        escape_json(&event.payload)
+    )
+}
+
+/// Read a line written by `event_to_json_line`
+fn event_from_json_line(line: &str) -> Result<Event, &'static str> {
+    let value = json::parse(line).ok_or("invalid JSON")?;
+    let actor_id = value.str_field("actor_id").ok_or("missing actor_id")?;
+    Ok(Event {
+        actor_id: ActorId::from_hex(&actor_id).ok_or("invalid actor_id")?,
+        seq: value.number_field("seq").ok_or("missing seq")?,
+        event_type: value.str_field("event_type").ok_or("missing event_type")?,
+        created_at: value.number_field("created_at").ok_or("missing created_at")?,
+        payload: value.str_field("payload").ok_or("missing payload")?,
+    })
+}
+
+fn escape_json(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_actor_id_generation() {
+        let id1 = ActorId::generate();
+        let id2 = ActorId::generate();
+        assert_ne!(id1, id2);
+    }
+
+    #[test]
+    fn test_actor_id_roundtrip() {
+        let id = ActorId::generate();
+        let bytes = id.as_bytes();
+        let recovered = ActorId::from_bytes(bytes).unwrap();
+        assert_eq!(id, recovered);
+    }
+
+    #[test]
+    fn test_actor_id_hex() {
+        let id = ActorId([0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
+                         0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10]);
+        assert_eq!(id.to_hex(), "0123456789abcdeffedcba9876543210");
+        assert_eq!(ActorId::from_hex(&id.to_hex()), Some(id));
+        assert_eq!(ActorId::from_hex("0123"), None);
+        assert_eq!(ActorId::from_hex("zz23456789abcdeffedcba9876543210"), None);
+    }
+
+    #[test]
+    fn test_storage_creation() {
+        let storage = Storage::open_in_memory().unwrap();
+        assert!(!storage.actor_id().as_bytes().iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_handle_storage() {
+        let storage = Storage::open_in_memory().unwrap();
+
+        // Initially no handle
+        assert!(storage.handle().unwrap().is_none());
+
+        // Set and retrieve
+        storage.set_handle("TestPlayer").unwrap();
+        assert_eq!(storage.handle().unwrap(), Some("TestPlayer".to_string()));
+
+        // Update
+        storage.set_handle("NewName").unwrap();
+        assert_eq!(storage.handle().unwrap(), Some("NewName".to_string()));
+    }
+
+    #[test]
+    fn test_append_event() {
+        let storage = Storage::open_in_memory().unwrap();
+
+        let event1 = storage.append_event("test", r#"{"data": 1}"#).unwrap();
+        assert_eq!(event1.seq, 1);
+        assert_eq!(event1.event_type, "test");
+
+        let event2 = storage.append_event("test", r#"{"data": 2}"#).unwrap();
+        assert_eq!(event2.seq, 2);
+
+        assert_eq!(storage.event_count().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_vector_clock() {
+        let storage = Storage::open_in_memory().unwrap();
+
+        storage.append_event("test", "{}").unwrap();
+        storage.append_event("test", "{}").unwrap();
+        storage.append_event("test", "{}").unwrap();
+
+        let vclock = storage.get_vector_clock().unwrap();
+        assert_eq!(vclock.len(), 1);
+        assert_eq!(vclock[0].0, *storage.actor_id());
+        assert_eq!(vclock[0].1, 3);
+    }
+
+    #[test]
+    fn test_insert_remote_event() {
+        let storage = Storage::open_in_memory().unwrap();
+
+        let remote_actor = ActorId::generate();
+        let remote_event = Event {
+            actor_id: remote_actor.clone(),
+            seq: 1,
+            event_type: "remote_test".to_string(),
+            payload: r#"{"from": "remote"}"#.to_string(),
+            created_at: 1234567890000,
+        };
+
+        // First insert should succeed
+        assert!(storage.insert_remote_event(&remote_event).unwrap());
+
+        // Duplicate insert should be ignored
+        assert!(!storage.insert_remote_event(&remote_event).unwrap());
+
+        // A match_end with more scores than a lobby has players is refused
+        let scores: Vec<String> = (0..=MAX_PLAYERS).map(|i| format!(r#"["P{}",1]"#, i)).collect();
+        let oversized = Event {
+            actor_id: remote_actor.clone(),
+            seq: 2,
+            event_type: "match_end".to_string(),
+            payload: format!(
+                r#"{{"match_id":1,"scores":[{}],"host_actor_id":"h","completed":true}}"#,
+                scores.join(",")
+            ),
+            created_at: 1234567890001,
+        };
+        assert!(!storage.insert_remote_event(&oversized).unwrap());
+
+        // Vector clock should show both actors
+        let vclock = storage.get_vector_clock().unwrap();
+        assert_eq!(vclock.len(), 1); // Only remote actor has events
+        assert_eq!(vclock[0].0, remote_actor);
+        assert_eq!(vclock[0].1, 1);
+    }
+
+    #[test]
+    fn test_get_events_after() {
+        let storage = Storage::open_in_memory().unwrap();
+
+        storage.append_event("test", r#"{"n": 1}"#).unwrap();
+        storage.append_event("test", r#"{"n": 2}"#).unwrap();
+        storage.append_event("test", r#"{"n": 3}"#).unwrap();
+
+        let events = storage.get_events_after(storage.actor_id(), 1).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].seq, 2);
+        assert_eq!(events[1].seq, 3);
+
+        let events = storage.get_events_after(storage.actor_id(), 3).unwrap();
+        assert_eq!(events.len(), 0);
+    }
+
+    #[test]
+    fn test_get_all_events() {
+        let storage = Storage::open_in_memory().unwrap();
+
+        storage.append_event("a", "{}").unwrap();
+        storage.append_event("b", "{}").unwrap();
+        storage.append_event("c", "{}").unwrap();
+
+        let events = storage.get_all_events().unwrap();
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn test_get_events_in_range() {
+        let storage = Storage::open_in_memory().unwrap();
+        for (seq, event_type, created_at) in [
+            (1, "round_start", 100),
+            (2, "word_claimed", 150),
+            (3, "match_end", 200),
+            (4, "round_start", 300),
+            (5, "match_end", 400),
+        ] {
+            storage
+                .insert_remote_event(&Event {
+                    actor_id: ActorId([7; 16]),
+                    seq,
+                    event_type: event_type.to_string(),
+                    payload: "{}".to_string(),
+                    created_at,
+                })
+                .unwrap();
+        }
+
+        let seqs = |events: Vec<Event>| events.into_iter().map(|e| e.seq).collect::<Vec<_>>();
+        assert_eq!(seqs(storage.get_events_in_range(None, 150, 300).unwrap()), vec![2, 3, 4]);
+        assert_eq!(seqs(storage.get_events_in_range(Some("match_end"), 0, 1000).unwrap()), vec![3, 5]);
+        assert_eq!(seqs(storage.get_events_in_range(Some("match_end"), 0, 399).unwrap()), vec![3]);
+        assert!(storage.get_events_in_range(None, 500, 600).unwrap().is_empty());
+        assert!(storage.get_events_in_range(None, 300, 100).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        let source = Storage::open_in_memory().unwrap();
+        source
+            .append_event("word_claimed", r#"{"word":"CAT","player_name":"Al \"Cat\" Ice","points":3}"#)
+            .unwrap();
+        source.append_event("note", "line one\nline\ttwo").unwrap();
+        source
+            .insert_remote_event(&Event {
+                actor_id: ActorId([7; 16]),
+                seq: 4,
+                event_type: "match_end".to_string(),
+                payload: r#"{"match_id":1,"scores":[["Alice",3]]}"#.to_string(),
+                created_at: 5,
+            })
+            .unwrap();
+
+        let mut export = Vec::new();
+        assert_eq!(source.export_events(&mut export).unwrap(), 3);
+        assert_eq!(String::from_utf8(export.clone()).unwrap().lines().count(), 3);
+
+        let target = Storage::open_in_memory().unwrap();
+        assert_eq!(target.import_events(export.as_slice()).unwrap(), 3);
+        assert_eq!(target.event_count().unwrap(), source.event_count().unwrap());
+        assert_eq!(target.get_all_events().unwrap(), source.get_all_events().unwrap());
+        assert_eq!(target.get_vector_clock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_import_twice_inserts_nothing_new() {
+        let source = Storage::open_in_memory().unwrap();
+        source.append_event("a", "{}").unwrap();
+        source.append_event("b", "{}").unwrap();
+        let mut export = Vec::new();
+        source.export_events(&mut export).unwrap();
+
+        let target = Storage::open_in_memory().unwrap();
+        assert_eq!(target.import_events(export.as_slice()).unwrap(), 2);
+        assert_eq!(target.import_events(export.as_slice()).unwrap(), 0);
+        assert_eq!(target.event_count().unwrap(), 2);
+
+        // Importing into the log it came from adds nothing either
+        assert_eq!(source.import_events(export.as_slice()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_import_rejects_malformed_line() {
+        let storage = Storage::open_in_memory().unwrap();
+        let mut export = Vec::new();
+        let source = Storage::open_in_memory().unwrap();
+        source.append_event("a", "{}").unwrap();
+        source.export_events(&mut export).unwrap();
+        export.extend_from_slice(b"\n{\"seq\":2}\n");
+
+        let err = storage.import_events(export.as_slice()).unwrap_err();
+        assert!(matches!(err, StorageError::InvalidImport { line: 3, .. }), "{}", err);
+        assert_eq!(storage.event_count().unwrap(), 0, "a bad file imports nothing");
+    }
+
+    #[test]
+    fn test_data_dir() {
+        // Just verify it returns something on supported platforms
+        let result = Storage::data_dir();
+        // This might fail in weird environments, but should work on Linux/macOS
+        if let Ok(path) = result {
+            assert!(path.to_string_lossy().contains("blam"));
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_data_dir_linux_layout() {
+        let path = Storage::data_dir().unwrap();
+        assert!(path.ends_with("blam"));
+
+        // XDG_DATA_HOME (also how Flatpak redirects into its sandbox) wins when absolute
+        match std::env::var_os("XDG_DATA_HOME").map(PathBuf::from) {
+            Some(xdg) if xdg.is_absolute() => assert_eq!(path, xdg.join("blam")),
+            _ => assert!(path.ends_with(".local/share/blam")),
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn test_data_dir_macos_layout() {
+        let path = Storage::data_dir().unwrap();
+        assert!(path.ends_with("Library/Application Support/dev.blam.blam"));
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn test_data_dir_windows_layout() {
+        let path = Storage::data_dir().unwrap();
+        assert!(path.ends_with("blam\\blam\\data"));
+        if let Some(appdata) = std::env::var_os("APPDATA") {
+            assert!(path.starts_with(appdata));
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_legacy_data_dir_matches_on_linux() {
+        // Linux paths only use the application name, so no migration is needed
+        assert_eq!(Storage::legacy_data_dir(), Storage::data_dir().ok());
+    }
+
+    // === Schema Migration Tests ===
+
+    #[test]
+    fn test_schema_version_is_current() {
+        let storage = Storage::open_in_memory().unwrap();
+        let version: u32 = storage
+            .conn
+            .query_row("SELECT schema_version FROM meta", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(version, SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_derived_tables_exist() {
+        let storage = Storage::open_in_memory().unwrap();
+
+        // Verify derived_stats table exists
+        let stats_exists: bool = storage
+            .conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='derived_stats'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(stats_exists, "derived_stats table should exist");
+
+        // Verify derived_elo_history table exists
+        let elo_exists: bool = storage
+            .conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='derived_elo_history'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(elo_exists, "derived_elo_history table should exist");
+
+        // Verify derived_cache_meta table exists
+        let meta_exists: bool = storage
+            .conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='derived_cache_meta'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(meta_exists, "derived_cache_meta table should exist");
+    }
+
+    #[test]
+    fn test_migrate_v2_to_v3_adds_checkpoint_table() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage
+            .conn
+            .execute_batch(
+                "DROP TABLE round_checkpoint; ALTER TABLE meta DROP COLUMN season; \
+                 ALTER TABLE derived_stats DROP COLUMN total_claim_chars; \
+                 ALTER TABLE meta DROP COLUMN rack_telemetry; DROP TABLE rack_difficulty; \
+                 ALTER TABLE meta DROP COLUMN house_words; ALTER TABLE meta DROP COLUMN elo_k_factor; \
+                 ALTER TABLE meta DROP COLUMN compacted_before; \
+                 DROP INDEX idx_events_match; ALTER TABLE events DROP COLUMN match_id; \
+                 ALTER TABLE meta DROP COLUMN encryption_salt; \
+                 ALTER TABLE meta DROP COLUMN encryption_check; \
+                 UPDATE meta SET schema_version = 2;",
+            )
+            .unwrap();
 
-        let mut leaderboard = Vec::new();
-        for row in rows {
-            leaderboard.push(row?);
-        }
+        storage.initialize_schema().unwrap();
 
-        Ok(leaderboard)
+        assert_eq!(storage.get_schema_version().unwrap(), SCHEMA_VERSION);
+        assert!(storage.load_round_checkpoint().unwrap().is_none());
     }
 
-    /// Check if caches need rebuilding (e.g., after CRDT sync added new events).
-    pub fn caches_need_rebuild(&self) -> Result<bool, StorageError> {
-        // Get current event counts
-        let match_count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM events WHERE event_type = 'match_end'",
-            [],
-            |row| row.get(0),
-        )?;
+    #[test]
+    fn test_migrate_v3_to_v4_adds_season() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage
+            .conn
+            .execute_batch(
+                "ALTER TABLE meta DROP COLUMN season; ALTER TABLE derived_stats DROP COLUMN total_claim_chars; \
+                 ALTER TABLE meta DROP COLUMN rack_telemetry; DROP TABLE rack_difficulty; \
+                 ALTER TABLE meta DROP COLUMN house_words; ALTER TABLE meta DROP COLUMN elo_k_factor; \
+                 ALTER TABLE meta DROP COLUMN compacted_before; \
+                 DROP INDEX idx_events_match; ALTER TABLE events DROP COLUMN match_id; \
+                 ALTER TABLE meta DROP COLUMN encryption_salt; \
+                 ALTER TABLE meta DROP COLUMN encryption_check; \
+                 UPDATE meta SET schema_version = 3;",
+            )
+            .unwrap();
 
-        // Get cached event count
-        let cached_count: i64 = self.conn.query_row(
-            "SELECT COALESCE(event_count, 0) FROM derived_cache_meta WHERE cache_name = 'elo'",
-            [],
-            |row| row.get(0),
-        ).unwrap_or(0);
+        storage.initialize_schema().unwrap();
 
-        Ok(match_count != cached_count)
+        assert_eq!(storage.get_schema_version().unwrap(), SCHEMA_VERSION);
+        assert_eq!(storage.current_season().unwrap(), 1);
     }
-}
 
-/// Cached player statistics from derived_stats table.
-#[derive(Debug, Clone, PartialEq)]
-pub struct CachedPlayerStats {
-    pub handle: String,
-    pub elo: f64,
-    pub rounds_played: u32,
-    pub total_points: u32,
-    pub best_score: u32,
-    pub longest_word: String,
-    pub words_claimed: u32,
-    pub wins: u32,
-}
+    #[test]
+    fn test_migrate_v4_to_v5_adds_claim_chars() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage
+            .conn
+            .execute_batch(
+                "ALTER TABLE derived_stats DROP COLUMN total_claim_chars; \
+                 ALTER TABLE meta DROP COLUMN rack_telemetry; DROP TABLE rack_difficulty; \
+                 ALTER TABLE meta DROP COLUMN house_words; ALTER TABLE meta DROP COLUMN elo_k_factor; \
+                 ALTER TABLE meta DROP COLUMN compacted_before; \
+                 DROP INDEX idx_events_match; ALTER TABLE events DROP COLUMN match_id; \
+                 ALTER TABLE meta DROP COLUMN encryption_salt; \
+                 ALTER TABLE meta DROP COLUMN encryption_check; \
+                 UPDATE meta SET schema_version = 4;",
+            )
+            .unwrap();
 
-/// Parsed match result from event payload.
-struct ParsedMatchResult {
-    match_id: i64,
-    scores: Vec<(String, u32)>,
-    completed: bool,
-}
+        storage.initialize_schema().unwrap();
 
-/// Parse a match_end event payload to extract match result.
-fn parse_match_result_payload(payload: &str) -> Option<ParsedMatchResult> {
-    let match_id = extract_json_i64(payload, "match_id")?;
-    let completed = extract_json_bool(payload, "completed").unwrap_or(true);
-    let scores = extract_json_scores(payload)?;
-
-    Some(ParsedMatchResult {
-        match_id,
-        scores,
-        completed,
-    })
-}
+        assert_eq!(storage.get_schema_version().unwrap(), SCHEMA_VERSION);
+        storage
+            .append_event("word_claimed", r#"{"word":"CAT","player_name":"Alice","points":3}"#)
+            .unwrap();
+        storage.rebuild_derived_caches().unwrap();
+        assert_eq!(storage.get_cached_stats("Alice").unwrap().unwrap().total_claim_chars, 3);
+    }
 
-// === Versioned Payload Helpers ===
+    #[test]
+    fn test_migrate_v5_to_v6_adds_rack_difficulty() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage
+            .conn
+            .execute_batch(
+                "ALTER TABLE meta DROP COLUMN rack_telemetry; DROP TABLE rack_difficulty; \
+                 ALTER TABLE meta DROP COLUMN house_words; ALTER TABLE meta DROP COLUMN elo_k_factor; \
+                 ALTER TABLE meta DROP COLUMN compacted_before; \
+                 DROP INDEX idx_events_match; ALTER TABLE events DROP COLUMN match_id; \
+                 ALTER TABLE meta DROP COLUMN encryption_salt; \
+                 ALTER TABLE meta DROP COLUMN encryption_check; \
+                 UPDATE meta SET schema_version = 5;",
+            )
+            .unwrap();
 
-/// Create a versioned event payload with the current payload version.
-/// The payload_version field enables forward compatibility - older binaries
-/// can read newer payloads by ignoring unknown fields.
-pub fn create_versioned_payload(inner_json: &str) -> String {
-    // Insert payload_version at the beginning of the JSON object
-    if inner_json.starts_with('{') {
-        format!(
-            r#"{{"payload_version":{},"#,
-            PAYLOAD_VERSION
-        ) + &inner_json[1..]
-    } else {
-        // If not a JSON object, wrap it
-        format!(
-            r#"{{"payload_version":{},"data":{}}}"#,
-            PAYLOAD_VERSION, inner_json
-        )
+        storage.initialize_schema().unwrap();
+
+        assert_eq!(storage.get_schema_version().unwrap(), SCHEMA_VERSION);
+        assert!(!storage.rack_telemetry_enabled().unwrap());
+        assert!(storage.rack_difficulty_report().unwrap().is_empty());
     }
-}
 
-/// Extract the payload version from a versioned payload.
-/// Returns None if the payload doesn't have a version (pre-versioning payloads).
-pub fn extract_payload_version(payload: &str) -> Option<u32> {
-    extract_json_i64(payload, "payload_version").map(|v| v as u32)
-}
+    #[test]
+    fn test_migrate_v6_to_v7_adds_house_words() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage
+            .conn
+            .execute_batch(
+                "ALTER TABLE meta DROP COLUMN house_words; ALTER TABLE meta DROP COLUMN elo_k_factor; \
+                 ALTER TABLE meta DROP COLUMN compacted_before; \
+                 DROP INDEX idx_events_match; ALTER TABLE events DROP COLUMN match_id; \
+                 ALTER TABLE meta DROP COLUMN encryption_salt; \
+                 ALTER TABLE meta DROP COLUMN encryption_check; \
+                 UPDATE meta SET schema_version = 6;",
+            )
+            .unwrap();
 
-/// Check if a payload is compatible with the current version.
-/// Returns true if:
-/// - The payload has no version (legacy payload, always compatible)
-/// - The payload version is <= current version (backward compatible)
-pub fn is_payload_compatible(payload: &str) -> bool {
-    match extract_payload_version(payload) {
-        None => true, // Legacy payload without version
-        Some(v) => v <= PAYLOAD_VERSION,
-    }
-}
+        storage.initialize_schema().unwrap();
 
-// === JSON Helper Functions ===
+        assert_eq!(storage.get_schema_version().unwrap(), SCHEMA_VERSION);
+        assert!(storage.house_words().unwrap().is_empty());
+    }
 
-fn extract_json_string(json: &str, key: &str) -> Option<String> {
-    let pattern = format!(r#""{}":""#, key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = &json[start..];
-    let end = find_unescaped_quote(rest)?;
-    Some(unescape_json(&rest[..end]))
-}
+    #[test]
+    fn test_migrate_v7_to_v8_adds_elo_k_factor() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage
+            .conn
+            .execute_batch(
+                "ALTER TABLE meta DROP COLUMN elo_k_factor; ALTER TABLE meta DROP COLUMN compacted_before; \
+                 DROP INDEX idx_events_match; ALTER TABLE events DROP COLUMN match_id; \
+                 ALTER TABLE meta DROP COLUMN encryption_salt; \
+                 ALTER TABLE meta DROP COLUMN encryption_check; \
+                 UPDATE meta SET schema_version = 7;",
+            )
+            .unwrap();
 
-fn extract_json_i64(json: &str, key: &str) -> Option<i64> {
-    let pattern = format!(r#""{}":"#, key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = &json[start..];
-    let end = rest.find(|c: char| !c.is_ascii_digit() && c != '-').unwrap_or(rest.len());
-    rest[..end].parse().ok()
-}
+        storage.initialize_schema().unwrap();
 
-fn extract_json_bool(json: &str, key: &str) -> Option<bool> {
-    let pattern = format!(r#""{}":"#, key);
-    let start = json.find(&pattern)? + pattern.len();
-    let rest = &json[start..].trim_start();
-    if rest.starts_with("true") {
-        Some(true)
-    } else if rest.starts_with("false") {
-        Some(false)
-    } else {
-        None
+        assert_eq!(storage.get_schema_version().unwrap(), SCHEMA_VERSION);
+        assert_eq!(storage.elo_k_factor().unwrap(), DEFAULT_K);
     }
-}
 
-fn extract_json_scores(json: &str) -> Option<Vec<(String, u32)>> {
-    let pattern = r#""scores":["#;
-    let start = json.find(pattern)? + pattern.len();
-    let rest = &json[start..];
-
-    // Find matching closing bracket
-    let mut depth = 1;
-    let mut end = 0;
-    for (i, c) in rest.chars().enumerate() {
-        match c {
-            '[' => depth += 1,
-            ']' => {
-                depth -= 1;
-                if depth == 0 {
-                    end = i;
-                    break;
-                }
-            }
-            _ => {}
-        }
-    }
+    #[test]
+    fn test_migrate_v8_to_v9_adds_compaction() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage
+            .conn
+            .execute_batch(
+                "ALTER TABLE meta DROP COLUMN compacted_before;
+                 DROP TABLE compacted_claims;
+                 DROP TABLE compaction_watermark;
+                 DROP INDEX idx_events_match;
+                 ALTER TABLE events DROP COLUMN match_id;
+                 ALTER TABLE meta DROP COLUMN encryption_salt;
+                 ALTER TABLE meta DROP COLUMN encryption_check;
+                 UPDATE meta SET schema_version = 8;",
+            )
+            .unwrap();
 
-    let array = &rest[..end];
-    let mut scores = Vec::new();
-    let mut current = array;
+        storage.initialize_schema().unwrap();
 
-    while let Some(start) = current.find('[') {
-        let inner = &current[start + 1..];
-        if let Some(end) = inner.find(']') {
-            let item = &inner[..end];
-            if let Some(comma) = item.find(',') {
-                let name = item[..comma].trim().trim_matches('"');
-                let score_str = item[comma + 1..].trim();
-                if let Ok(score) = score_str.parse() {
-                    scores.push((unescape_json(name), score));
-                }
-            }
-            current = &inner[end + 1..];
-        } else {
-            break;
-        }
+        assert_eq!(storage.get_schema_version().unwrap(), SCHEMA_VERSION);
+        assert_eq!(storage.compacted_before().unwrap(), 0);
+        assert_eq!(storage.compact_events(0).unwrap(), 0);
     }
 
-    Some(scores)
-}
+    #[test]
+    fn test_migrate_v9_to_v10_backfills_match_id() {
+        let storage = Storage::open_in_memory().unwrap();
+        let match_end = r#"{"match_id":42,"scores":[["Alice",50],["Bob",30]],"host_actor_id":"h","completed":true}"#;
+        storage.append_event("match_end", match_end).unwrap();
+        storage.append_event("match_end", "not json").unwrap();
+        storage
+            .append_event("word_claimed", r#"{"word":"CAT","player_name":"Alice","match_id":42}"#)
+            .unwrap();
+        storage
+            .conn
+            .execute_batch(
+                "DROP INDEX idx_events_match;
+                 ALTER TABLE events DROP COLUMN match_id;
+                 ALTER TABLE meta DROP COLUMN encryption_salt;
+                 ALTER TABLE meta DROP COLUMN encryption_check;
+                 UPDATE meta SET schema_version = 9;",
+            )
+            .unwrap();
 
-fn find_unescaped_quote(s: &str) -> Option<usize> {
-    let mut i = 0;
-    let bytes = s.as_bytes();
-    while i < bytes.len() {
-        if bytes[i] == b'"' {
-            return Some(i);
-        } else if bytes[i] == b'\\' && i + 1 < bytes.len() {
-            i += 2;
-        } else {
-            i += 1;
-        }
+        storage.initialize_schema().unwrap();
+
+        assert_eq!(storage.get_schema_version().unwrap(), SCHEMA_VERSION);
+        let match_ids = |storage: &Storage| -> Vec<(String, Option<i64>)> {
+            let mut stmt = storage
+                .conn
+                .prepare("SELECT event_type, match_id FROM events ORDER BY seq")
+                .unwrap();
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .unwrap()
+                .collect::<SqlResult<_>>()
+                .unwrap()
+        };
+        let expected = vec![
+            ("match_end".to_string(), Some(42)),
+            ("match_end".to_string(), None),
+            ("word_claimed".to_string(), None),
+        ];
+        assert_eq!(match_ids(&storage), expected);
+        assert_eq!(storage.get_match_events(42).unwrap().len(), 1);
+
+        // Running it again changes nothing
+        storage.migrate_v9_to_v10().unwrap();
+        assert_eq!(match_ids(&storage), expected);
     }
-    None
-}
 
-fn unescape_json(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    let mut chars = s.chars().peekable();
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            match chars.next() {
-                Some('"') => result.push('"'),
-                Some('\\') => result.push('\\'),
-                Some('n') => result.push('\n'),
-                Some('r') => result.push('\r'),
-                Some('t') => result.push('\t'),
-                Some(other) => {
-                    result.push('\\');
-                    result.push(other);
-                }
-                None => result.push('\\'),
-            }
-        } else {
-            result.push(c);
-        }
+    #[test]
+    fn test_match_id_column_set_for_new_match_end_events() {
+        let storage = Storage::open_in_memory().unwrap();
+        let result = MatchResult::new(7, vec![("Alice".to_string(), 6)], "host1".to_string());
+        storage.append_event("match_end", &result.to_json()).unwrap();
+        storage.append_event("round_start", r#"{"match_id":7}"#).unwrap();
+        storage
+            .insert_remote_event(&Event {
+                actor_id: ActorId([7; 16]),
+                seq: 1,
+                event_type: "match_end".to_string(),
+                payload: result.to_json(),
+                created_at: 1,
+            })
+            .unwrap();
+
+        let events = storage.get_match_events(7).unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.event_type == "match_end"));
+        assert!(storage.get_match_events(8).unwrap().is_empty());
     }
-    result
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    // === Payload Encryption Tests ===
 
-    #[test]
-    fn test_actor_id_generation() {
-        let id1 = ActorId::generate();
-        let id2 = ActorId::generate();
-        assert_ne!(id1, id2);
+    fn temp_db_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("blam-{}-{}.db", name, std::process::id()))
     }
 
-    #[test]
-    fn test_actor_id_roundtrip() {
-        let id = ActorId::generate();
-        let bytes = id.as_bytes();
-        let recovered = ActorId::from_bytes(bytes).unwrap();
-        assert_eq!(id, recovered);
+    /// Payloads exactly as stored in the events table
+    fn stored_payloads(storage: &Storage) -> Vec<String> {
+        let mut stmt = storage.conn.prepare("SELECT payload FROM events ORDER BY rowid").unwrap();
+        stmt.query_map([], |row| row.get(0)).unwrap().collect::<SqlResult<_>>().unwrap()
     }
 
     #[test]
-    fn test_actor_id_hex() {
-        let id = ActorId([0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef,
-                         0xfe, 0xdc, 0xba, 0x98, 0x76, 0x54, 0x32, 0x10]);
-        assert_eq!(id.to_hex(), "0123456789abcdeffedcba9876543210");
-    }
+    fn test_encrypted_payload_round_trips_with_right_passphrase() {
+        let path = temp_db_path("encrypted");
+        let _ = std::fs::remove_file(&path);
+        let payload = r#"{"word":"CAT","player_name":"Alice","points":3}"#;
+
+        let storage = Storage::open_encrypted_at(&path, "correct horse").unwrap();
+        assert!(storage.is_encrypted());
+        storage.append_event("word_claimed", payload).unwrap();
+        assert!(!stored_payloads(&storage)[0].contains("Alice"));
+        drop(storage);
+
+        assert!(matches!(
+            Storage::open_encrypted_at(&path, "battery staple"),
+            Err(StorageError::WrongPassphrase)
+        ));
+        assert!(matches!(Storage::open_at(&path), Err(StorageError::PassphraseRequired)));
+
+        let storage = Storage::open_encrypted_at(&path, "correct horse").unwrap();
+        let events = storage.get_all_events().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].payload, payload);
 
-    #[test]
-    fn test_storage_creation() {
-        let storage = Storage::open_in_memory().unwrap();
-        assert!(!storage.actor_id().as_bytes().iter().all(|&b| b == 0));
+        drop(storage);
+        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
-    fn test_handle_storage() {
-        let storage = Storage::open_in_memory().unwrap();
+    fn test_encrypting_existing_database_keeps_stats_and_sync_working() {
+        let path = temp_db_path("encrypt-existing");
+        let _ = std::fs::remove_file(&path);
+        let claim = r#"{"word":"ELEPHANT","player_name":"Alice","points":8,"round_id":9}"#;
+        let result = MatchResult::new(
+            7,
+            vec![("Alice".to_string(), 50), ("Bob".to_string(), 30)],
+            "host1".to_string(),
+        )
+        .for_round(9);
+
+        let storage = Storage::open_at(&path).unwrap();
+        assert!(!storage.is_encrypted());
+        storage.append_event("word_claimed", claim).unwrap();
+        let actor_id = storage.actor_id().clone();
+        drop(storage);
+
+        // Existing payloads get encrypted, new and synced ones are too
+        let storage = Storage::open_encrypted_at(&path, "hunter2").unwrap();
+        storage.append_event("match_end", &result.to_json()).unwrap();
+        let remote = Event {
+            actor_id: ActorId([7; 16]),
+            seq: 1,
+            event_type: "word_claimed".to_string(),
+            payload: r#"{"word":"DOG","player_name":"Bob","points":3,"round_id":9}"#.to_string(),
+            created_at: 1,
+        };
+        assert!(storage.insert_remote_event(&remote).unwrap());
+        assert!(stored_payloads(&storage).iter().all(|stored| crypt::is_encrypted(stored)));
 
-        // Initially no handle
-        assert!(storage.handle().unwrap().is_none());
+        // Sync hands peers plaintext
+        let outgoing = storage.get_events_after(&actor_id, 0).unwrap();
+        assert_eq!(outgoing[0].payload, claim);
 
-        // Set and retrieve
-        storage.set_handle("TestPlayer").unwrap();
-        assert_eq!(storage.handle().unwrap(), Some("TestPlayer".to_string()));
+        storage.rebuild_derived_caches().unwrap();
+        let alice = storage.get_cached_stats("Alice").unwrap().unwrap();
+        assert_eq!((alice.rounds_played, alice.words_claimed), (1, 1));
+        assert_eq!(alice.longest_word, "ELEPHANT");
+        assert_eq!(storage.get_cached_stats("Bob").unwrap().unwrap().words_claimed, 1);
+        let report = storage.format_match_report(7).unwrap().unwrap();
+        assert!(report.contains("Best word: ELEPHANT by Alice (8 pts)"), "{}", report);
 
-        // Update
-        storage.set_handle("NewName").unwrap();
-        assert_eq!(storage.handle().unwrap(), Some("NewName".to_string()));
+        drop(storage);
+        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
-    fn test_append_event() {
+    fn test_house_words_roundtrip() {
         let storage = Storage::open_in_memory().unwrap();
+        assert!(storage.house_words().unwrap().is_empty());
 
-        let event1 = storage.append_event("test", r#"{"data": 1}"#).unwrap();
-        assert_eq!(event1.seq, 1);
-        assert_eq!(event1.event_type, "test");
+        let words = vec!["blamzorp".to_string(), "yeet".to_string()];
+        storage.set_house_words(&words).unwrap();
+        assert_eq!(storage.house_words().unwrap(), words);
 
-        let event2 = storage.append_event("test", r#"{"data": 2}"#).unwrap();
-        assert_eq!(event2.seq, 2);
+        storage.set_house_words(&[]).unwrap();
+        assert!(storage.house_words().unwrap().is_empty());
+    }
 
-        assert_eq!(storage.event_count().unwrap(), 2);
+    // === Rack Difficulty Tests ===
+
+    fn sample_difficulty() -> RackDifficulty {
+        RackDifficulty {
+            letters: vec!['C', 'A', 'T', 'S'],
+            valid_words: 12,
+            max_points: 40,
+            achieved_points: 10,
+            recorded_at: 1700000000000,
+        }
     }
 
     #[test]
-    fn test_vector_clock() {
+    fn test_rack_telemetry_disabled_by_default() {
         let storage = Storage::open_in_memory().unwrap();
+        assert!(!storage.rack_telemetry_enabled().unwrap());
 
-        storage.append_event("test", "{}").unwrap();
-        storage.append_event("test", "{}").unwrap();
-        storage.append_event("test", "{}").unwrap();
-
-        let vclock = storage.get_vector_clock().unwrap();
-        assert_eq!(vclock.len(), 1);
-        assert_eq!(vclock[0].0, *storage.actor_id());
-        assert_eq!(vclock[0].1, 3);
+        assert!(!storage.record_rack_difficulty(&sample_difficulty()).unwrap());
+        assert!(storage.rack_difficulty_report().unwrap().is_empty());
     }
 
     #[test]
-    fn test_insert_remote_event() {
+    fn test_rack_difficulty_roundtrip_when_opted_in() {
         let storage = Storage::open_in_memory().unwrap();
+        storage.set_rack_telemetry(true).unwrap();
 
-        let remote_actor = ActorId::generate();
-        let remote_event = Event {
-            actor_id: remote_actor.clone(),
-            seq: 1,
-            event_type: "remote_test".to_string(),
-            payload: r#"{"from": "remote"}"#.to_string(),
-            created_at: 1234567890000,
-        };
+        assert!(storage.record_rack_difficulty(&sample_difficulty()).unwrap());
 
-        // First insert should succeed
-        assert!(storage.insert_remote_event(&remote_event).unwrap());
+        let report = storage.rack_difficulty_report().unwrap();
+        assert_eq!(report, vec![sample_difficulty()]);
+        assert_eq!(report[0].achieved_fraction(), 0.25);
 
-        // Duplicate insert should be ignored
-        assert!(!storage.insert_remote_event(&remote_event).unwrap());
+        // Opting out stops recording but keeps what's there
+        storage.set_rack_telemetry(false).unwrap();
+        assert!(!storage.record_rack_difficulty(&sample_difficulty()).unwrap());
+        assert_eq!(storage.rack_difficulty_report().unwrap().len(), 1);
+    }
 
-        // Vector clock should show both actors
-        let vclock = storage.get_vector_clock().unwrap();
-        assert_eq!(vclock.len(), 1); // Only remote actor has events
-        assert_eq!(vclock[0].0, remote_actor);
-        assert_eq!(vclock[0].1, 1);
+    #[test]
+    fn test_rack_difficulty_not_synced() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage.set_rack_telemetry(true).unwrap();
+        storage.record_rack_difficulty(&sample_difficulty()).unwrap();
+        assert_eq!(storage.event_count().unwrap(), 0);
     }
 
+    // === Season Tests ===
+
     #[test]
-    fn test_get_events_after() {
+    fn test_advance_season() {
         let storage = Storage::open_in_memory().unwrap();
+        assert_eq!(storage.current_season().unwrap(), 1);
+        assert_eq!(storage.advance_season().unwrap(), 2);
+        assert_eq!(storage.current_season().unwrap(), 2);
+    }
 
-        storage.append_event("test", r#"{"n": 1}"#).unwrap();
-        storage.append_event("test", r#"{"n": 2}"#).unwrap();
-        storage.append_event("test", r#"{"n": 3}"#).unwrap();
+    #[test]
+    fn test_season_leaderboard_filters_by_season() {
+        let storage = Storage::open_in_memory().unwrap();
 
-        let events = storage.get_events_after(storage.actor_id(), 1).unwrap();
-        assert_eq!(events.len(), 2);
-        assert_eq!(events[0].seq, 2);
-        assert_eq!(events[1].seq, 3);
+        let season1 = MatchResult::new(1, vec![("Alice".into(), 30), ("Bob".into(), 20)], "h".into())
+            .in_season(1);
+        let season2 = MatchResult::new(2, vec![("Carol".into(), 40), ("Bob".into(), 50)], "h".into())
+            .in_season(2);
+        // Recorded before seasons existed: counts toward season 1
+        let legacy = r#"{"match_id":3,"scores":[["Alice",10],["Bob",25]],"host_actor_id":"h","completed":true}"#;
+        storage.append_event("match_end", &season1.to_json()).unwrap();
+        storage.append_event("match_end", &season2.to_json()).unwrap();
+        storage.append_event("match_end", legacy).unwrap();
+
+        let first = storage.get_season_leaderboard(1).unwrap();
+        let handles: Vec<&str> = first.iter().map(|s| s.handle.as_str()).collect();
+        assert_eq!(handles, vec!["Bob", "Alice"]);
+        assert_eq!(first[0], SeasonStanding {
+            handle: "Bob".into(),
+            rounds_played: 2,
+            wins: 1,
+            total_points: 45,
+        });
+        assert!(!first.iter().any(|s| s.handle == "Carol"));
+
+        let second = storage.get_season_leaderboard(2).unwrap();
+        let handles: Vec<&str> = second.iter().map(|s| s.handle.as_str()).collect();
+        assert_eq!(handles, vec!["Bob", "Carol"]);
+        assert_eq!(second[0].total_points, 50);
+
+        assert!(storage.get_season_leaderboard(3).unwrap().is_empty());
+    }
+
+    // === Round Checkpoint Tests ===
 
-        let events = storage.get_events_after(storage.actor_id(), 3).unwrap();
-        assert_eq!(events.len(), 0);
+    fn sample_checkpoint() -> RoundCheckpoint {
+        RoundCheckpoint {
+            letters: vec!['C', 'A', 'T', 'S', 'D', 'O', 'G'],
+            time_remaining: 42,
+            score: 6,
+            claimed_words: vec![("CAT".to_string(), 3), ("DOG".to_string(), 3)],
+            saved_at: 1700000000000,
+        }
     }
 
     #[test]
-    fn test_get_all_events() {
+    fn test_round_checkpoint_roundtrip() {
         let storage = Storage::open_in_memory().unwrap();
+        assert!(storage.load_round_checkpoint().unwrap().is_none());
 
-        storage.append_event("a", "{}").unwrap();
-        storage.append_event("b", "{}").unwrap();
-        storage.append_event("c", "{}").unwrap();
+        let checkpoint = sample_checkpoint();
+        storage.save_round_checkpoint(&checkpoint).unwrap();
 
-        let events = storage.get_all_events().unwrap();
-        assert_eq!(events.len(), 3);
+        assert_eq!(storage.load_round_checkpoint().unwrap(), Some(checkpoint));
     }
 
     #[test]
-    fn test_data_dir() {
-        // Just verify it returns something on supported platforms
-        let result = Storage::data_dir();
-        // This might fail in weird environments, but should work on Linux/macOS
-        if let Ok(path) = result {
-            assert!(path.to_string_lossy().contains("blam"));
-        }
-    }
+    fn test_round_checkpoint_overwrites_previous() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage.save_round_checkpoint(&sample_checkpoint()).unwrap();
 
-    // === Schema Migration Tests ===
+        let mut later = sample_checkpoint();
+        later.time_remaining = 10;
+        later.score = 10;
+        later.claimed_words.push(("DOGS".to_string(), 4));
+        storage.save_round_checkpoint(&later).unwrap();
 
-    #[test]
-    fn test_schema_version_is_current() {
-        let storage = Storage::open_in_memory().unwrap();
-        let version: u32 = storage
-            .conn
-            .query_row("SELECT schema_version FROM meta", [], |row| row.get(0))
-            .unwrap();
-        assert_eq!(version, SCHEMA_VERSION);
+        assert_eq!(storage.load_round_checkpoint().unwrap(), Some(later));
     }
 
     #[test]
-    fn test_derived_tables_exist() {
+    fn test_round_checkpoint_empty_claims() {
         let storage = Storage::open_in_memory().unwrap();
+        let mut checkpoint = sample_checkpoint();
+        checkpoint.claimed_words.clear();
+        checkpoint.score = 0;
+        storage.save_round_checkpoint(&checkpoint).unwrap();
 
-        // Verify derived_stats table exists
-        let stats_exists: bool = storage
-            .conn
-            .query_row(
-                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='derived_stats'",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap();
-        assert!(stats_exists, "derived_stats table should exist");
+        assert_eq!(storage.load_round_checkpoint().unwrap(), Some(checkpoint));
+    }
 
-        // Verify derived_elo_history table exists
-        let elo_exists: bool = storage
-            .conn
-            .query_row(
-                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='derived_elo_history'",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap();
-        assert!(elo_exists, "derived_elo_history table should exist");
+    #[test]
+    fn test_clear_round_checkpoint() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage.save_round_checkpoint(&sample_checkpoint()).unwrap();
+        storage.clear_round_checkpoint().unwrap();
+        assert!(storage.load_round_checkpoint().unwrap().is_none());
 
-        // Verify derived_cache_meta table exists
-        let meta_exists: bool = storage
-            .conn
-            .query_row(
-                "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='table' AND name='derived_cache_meta'",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap();
-        assert!(meta_exists, "derived_cache_meta table should exist");
+        // Clearing when nothing is saved is a no-op
+        storage.clear_round_checkpoint().unwrap();
     }
 
     // === Versioned Payload Tests ===
@@ -1356,6 +3468,39 @@ mod tests {
         assert_eq!(bob_stats.wins, 1); // Won match 2
     }
 
+    #[test]
+    fn test_elo_k_factor_drives_cache_rebuild() {
+        let storage = Storage::open_in_memory().unwrap();
+        assert_eq!(storage.elo_k_factor().unwrap(), DEFAULT_K);
+
+        let match1 = r#"{"match_id":1,"scores":[["Alice",50],["Bob",30]],"host_actor_id":"host1","completed":true}"#;
+        let match2 = r#"{"match_id":2,"scores":[["Alice",40],["Bob",20]],"host_actor_id":"host1","completed":true}"#;
+        storage.append_event("match_end", match1).unwrap();
+        storage.append_event("match_end", match2).unwrap();
+
+        let spread = |storage: &Storage| {
+            let board = storage.get_cached_leaderboard().unwrap();
+            board.first().unwrap().1 - board.last().unwrap().1
+        };
+
+        // Changing K rebuilds the cache on its own
+        storage.set_elo_k_factor(16.0).unwrap();
+        let narrow = spread(&storage);
+        storage.set_elo_k_factor(64.0).unwrap();
+        let wide = spread(&storage);
+
+        assert_eq!(storage.elo_k_factor().unwrap(), 64.0);
+        assert!(wide > narrow, "K=64 spread {} should exceed K=16 spread {}", wide, narrow);
+    }
+
+    #[test]
+    fn test_invalid_elo_k_factor_rejected() {
+        let storage = Storage::open_in_memory().unwrap();
+        assert!(storage.set_elo_k_factor(0.0).is_err());
+        assert!(storage.set_elo_k_factor(f64::NAN).is_err());
+        assert_eq!(storage.elo_k_factor().unwrap(), DEFAULT_K);
+    }
+
     #[test]
     fn test_cached_leaderboard() {
         let storage = Storage::open_in_memory().unwrap();
@@ -1376,6 +3521,13 @@ mod tests {
         // Bob should be second (lower Elo after loss)
         assert_eq!(leaderboard[1].0, "Bob");
         assert!(leaderboard[1].1 < 1200.0);
+
+        // Full stats come back in the same order
+        let stats = storage.get_all_cached_stats().unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0], storage.get_cached_stats("Alice").unwrap().unwrap());
+        assert_eq!(stats[1].handle, "Bob");
+        assert_eq!(stats[1].total_points, 30);
     }
 
     #[test]
@@ -1430,6 +3582,216 @@ mod tests {
         assert_eq!(bob_stats.longest_word, "DOG");
     }
 
+    #[test]
+    fn test_word_claim_logged_by_two_devices_counted_once() {
+        let storage = Storage::open_in_memory().unwrap();
+
+        // Host's copy of the claim
+        let host_claim = r#"{"word":"CAT","player_name":"Alice","points":3,"round_id":42}"#;
+        storage.append_event("word_claimed", host_claim).unwrap();
+
+        // Client's copy of the same claim, synced in under a different actor
+        storage
+            .insert_remote_event(&Event {
+                actor_id: ActorId([7; 16]),
+                seq: 1,
+                event_type: "word_claimed".to_string(),
+                payload: host_claim.to_string(),
+                created_at: 1,
+            })
+            .unwrap();
+
+        // The same word in a later round is a separate claim
+        let next_round = r#"{"word":"CAT","player_name":"Alice","points":3,"round_id":43}"#;
+        storage.append_event("word_claimed", next_round).unwrap();
+
+        storage.rebuild_derived_caches().unwrap();
+
+        let alice = storage.get_cached_stats("Alice").unwrap().unwrap();
+        assert_eq!(alice.words_claimed, 2);
+    }
+
+    #[test]
+    fn test_word_claim_single_round_two_devices() {
+        let storage = Storage::open_in_memory().unwrap();
+        let claim = r#"{"word":"ELEPHANT","player_name":"Bob","points":8,"round_id":9}"#;
+        storage.append_event("word_claimed", claim).unwrap();
+        storage
+            .insert_remote_event(&Event {
+                actor_id: ActorId([3; 16]),
+                seq: 1,
+                event_type: "word_claimed".to_string(),
+                payload: claim.to_string(),
+                created_at: 2,
+            })
+            .unwrap();
+
+        storage.rebuild_derived_caches().unwrap();
+
+        let bob = storage.get_cached_stats("Bob").unwrap().unwrap();
+        assert_eq!(bob.words_claimed, 1);
+        assert_eq!(bob.longest_word, "ELEPHANT");
+    }
+
+    fn sorted_stats(storage: &Storage) -> Vec<CachedPlayerStats> {
+        let mut stats = storage.get_all_cached_stats().unwrap();
+        stats.sort_by(|a, b| a.handle.cmp(&b.handle));
+        stats
+    }
+
+    #[test]
+    fn test_compaction_shrinks_log_and_keeps_stats() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage.append_event("round_start", r#"{"round_id":1}"#).unwrap();
+        let cat = r#"{"word":"CAT","player_name":"Alice","points":3,"round_id":1}"#;
+        storage.append_event("word_claimed", cat).unwrap();
+        storage
+            .append_event("word_claimed", r#"{"word":"ELEPHANT","player_name":"Alice","points":8,"round_id":1}"#)
+            .unwrap();
+        storage
+            .append_event("word_claimed", r#"{"word":"DOG","player_name":"Bob","points":3,"round_id":1}"#)
+            .unwrap();
+        // A client's copy of Alice's claim
+        storage
+            .insert_remote_event(&Event {
+                actor_id: ActorId([7; 16]),
+                seq: 1,
+                event_type: "word_claimed".to_string(),
+                payload: cat.to_string(),
+                created_at: 1,
+            })
+            .unwrap();
+        let match1 = r#"{"match_id":1,"scores":[["Alice",11],["Bob",3]],"host_actor_id":"h","completed":true}"#;
+        storage.append_event("match_end", match1).unwrap();
+
+        std::thread::sleep(Duration::from_millis(10));
+        let cutoff = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        std::thread::sleep(Duration::from_millis(10));
+
+        storage
+            .append_event("word_claimed", r#"{"word":"TACO","player_name":"Bob","points":4,"round_id":2}"#)
+            .unwrap();
+        let match2 = r#"{"match_id":2,"scores":[["Alice",0],["Bob",4]],"host_actor_id":"h","completed":true}"#;
+        storage.append_event("match_end", match2).unwrap();
+
+        storage.rebuild_derived_caches().unwrap();
+        let stats_before = sorted_stats(&storage);
+        let count_before = storage.event_count().unwrap();
+        let mut clock_before = storage.get_vector_clock().unwrap();
+        clock_before.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+
+        assert_eq!(storage.compact_events(cutoff).unwrap(), 5);
+        assert_eq!(storage.event_count().unwrap(), count_before - 5);
+        let match_ends: Vec<String> = storage
+            .get_all_events()
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.event_type == "match_end")
+            .map(|e| e.payload)
+            .collect();
+        assert_eq!(match_ends, vec![match1.to_string(), match2.to_string()]);
+
+        // The client's actor still reads as seen up to seq 1
+        let mut clock = storage.get_vector_clock().unwrap();
+        clock.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+        assert_eq!(clock, clock_before);
+
+        // Stats are the same, before and after a rebuild
+        assert_eq!(sorted_stats(&storage), stats_before);
+        storage.rebuild_derived_caches().unwrap();
+        assert_eq!(sorted_stats(&storage), stats_before);
+        let alice = storage.get_cached_stats("Alice").unwrap().unwrap();
+        assert_eq!((alice.words_claimed, alice.longest_word.as_str()), (2, "ELEPHANT"));
+
+        // Compacting again is a no-op, and late copies are refused
+        assert_eq!(storage.compact_events(cutoff).unwrap(), 0);
+        let late_copy = Event {
+            actor_id: ActorId([9; 16]),
+            seq: 1,
+            event_type: "word_claimed".to_string(),
+            payload: cat.to_string(),
+            created_at: 2,
+        };
+        assert!(!storage.insert_remote_event(&late_copy).unwrap());
+        storage.rebuild_derived_caches().unwrap();
+        assert_eq!(sorted_stats(&storage), stats_before);
+    }
+
+    #[test]
+    fn test_compaction_longest_word_counts_letters_not_bytes() {
+        let storage = Storage::open_in_memory().unwrap();
+        let claim = |word: &str, round_id: i64| {
+            format!(r#"{{"word":"{}","player_name":"Alice","points":3,"round_id":{}}}"#, word, round_id)
+        };
+        let compact_now = |storage: &Storage| {
+            std::thread::sleep(Duration::from_millis(10));
+            let cutoff = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis() as i64;
+            storage.compact_events(cutoff).unwrap();
+        };
+        let longest = |storage: &Storage| -> String {
+            storage
+                .conn
+                .query_row(
+                    "SELECT longest_word FROM compacted_claims WHERE handle = 'Alice'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap()
+        };
+
+        // ÉTÉ is more bytes than CHAT but fewer letters
+        storage.append_event("word_claimed", &claim("CHAT", 1)).unwrap();
+        storage.append_event("word_claimed", &claim("ÉTÉ", 1)).unwrap();
+        compact_now(&storage);
+        assert_eq!(longest(&storage), "CHAT");
+
+        // The same holds against an earlier compaction's longest word
+        let storage = Storage::open_in_memory().unwrap();
+        storage.append_event("word_claimed", &claim("ÉTÉ", 1)).unwrap();
+        compact_now(&storage);
+        storage.append_event("word_claimed", &claim("CHAT", 2)).unwrap();
+        compact_now(&storage);
+        assert_eq!(longest(&storage), "CHAT");
+    }
+
+    #[test]
+    fn test_compaction_never_moves_own_seq_backward() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage.append_event("word_claimed", r#"{"word":"CAT","player_name":"Alice"}"#).unwrap();
+        let last = storage.append_event("round_start", "{}").unwrap();
+
+        assert_eq!(storage.compact_events(i64::MAX).unwrap(), 2);
+        assert_eq!(storage.event_count().unwrap(), 0);
+        assert_eq!(storage.get_vector_clock().unwrap(), vec![(storage.actor_id().clone(), last.seq)]);
+        assert_eq!(storage.append_event("match_end", "{}").unwrap().seq, last.seq + 1);
+
+        storage.rebuild_derived_caches().unwrap();
+        assert_eq!(storage.get_cached_stats("Alice").unwrap().unwrap().words_claimed, 1);
+    }
+
+    #[test]
+    fn test_average_claim_length() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage
+            .append_event("word_claimed", r#"{"word":"CAT","player_name":"Alice","points":3}"#)
+            .unwrap();
+        storage
+            .append_event("word_claimed", r#"{"word":"ELEPHANT","player_name":"Alice","points":8}"#)
+            .unwrap();
+
+        storage.rebuild_derived_caches().unwrap();
+
+        let alice = storage.get_cached_stats("Alice").unwrap().unwrap();
+        assert_eq!(alice.total_claim_chars, 11);
+        assert_eq!(alice.average_claim_length(), 5.5);
+    }
+
     // === JSON Helper Tests ===
 
     #[test]
@@ -1583,6 +3945,217 @@ mod tests {
         assert!((alice1.elo - alice2.elo).abs() < 0.001);
     }
 
+    /// Every cached player, by handle, plus the Elo history
+    fn cache_snapshot(storage: &Storage) -> (Vec<CachedPlayerStats>, Vec<(i64, String, f64)>) {
+        let mut players = storage.get_all_cached_stats().unwrap();
+        players.sort_by(|a, b| a.handle.cmp(&b.handle));
+        let mut stmt = storage
+            .conn
+            .prepare(
+                "SELECT match_id, handle, elo_after FROM derived_elo_history
+                 ORDER BY match_id, handle",
+            )
+            .unwrap();
+        let history = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<SqlResult<_>>()
+            .unwrap();
+        (players, history)
+    }
+
+    #[test]
+    fn test_incremental_update_matches_full_rebuild() {
+        let storage = Storage::open_in_memory().unwrap();
+        let claims = [
+            r#"{"word":"ELEPHANT","player_name":"Alice","points":8,"round_id":1}"#,
+            r#"{"word":"CAT","player_name":"Bob","points":3,"round_id":1}"#,
+        ];
+        for claim in claims {
+            storage.append_event("word_claimed", claim).unwrap();
+        }
+        let match1 = r#"{"match_id":1,"scores":[["Alice",50],["Bob",30],["Dave",10]],"host_actor_id":"h","completed":true}"#;
+        storage.append_event("match_end", match1).unwrap();
+        storage.rebuild_derived_caches().unwrap();
+
+        // Dave sits the second match out, so an incremental update leaves
+        // his row alone
+        storage
+            .conn
+            .execute("UPDATE derived_stats SET last_updated = 0 WHERE handle = 'Dave'", [])
+            .unwrap();
+
+        let claims = [
+            r#"{"word":"TOAST","player_name":"Bob","points":5,"round_id":2}"#,
+            r#"{"word":"ANTELOPES","player_name":"Carol","points":9,"round_id":2}"#,
+            r#"{"word":"DOG","player_name":"Alice","points":3,"round_id":2}"#,
+        ];
+        for claim in claims {
+            storage.append_event("word_claimed", claim).unwrap();
+        }
+        // Another device's copy of a claim the caches already counted
+        storage
+            .insert_remote_event(&Event {
+                actor_id: ActorId([7; 16]),
+                seq: 1,
+                event_type: "word_claimed".to_string(),
+                payload: r#"{"word":"CAT","player_name":"Bob","points":3,"round_id":1}"#.to_string(),
+                created_at: 1,
+            })
+            .unwrap();
+        let match2 = r#"{"match_id":2,"scores":[["Alice",40],["Bob",60],["Carol",55]],"host_actor_id":"h","completed":true}"#;
+        storage.append_event("match_end", match2).unwrap();
+        assert!(storage.caches_need_rebuild().unwrap());
+
+        storage.update_derived_caches_incremental().unwrap();
+        assert!(!storage.caches_need_rebuild().unwrap());
+        let dave_updated: i64 = storage
+            .conn
+            .query_row("SELECT last_updated FROM derived_stats WHERE handle = 'Dave'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(dave_updated, 0);
+        let incremental = cache_snapshot(&storage);
+
+        storage.rebuild_derived_caches().unwrap();
+        assert_eq!(incremental, cache_snapshot(&storage));
+        assert_eq!(incremental.0.len(), 4);
+        let bob = &incremental.0[1];
+        assert_eq!((bob.rounds_played, bob.wins, bob.words_claimed), (2, 1, 2));
+
+        // Nothing new: the update is a no-op
+        storage.update_derived_caches_incremental().unwrap();
+        assert_eq!(incremental, cache_snapshot(&storage));
+    }
+
+    #[test]
+    fn test_failed_rebuild_leaves_caches_unchanged() {
+        let storage = Storage::open_in_memory().unwrap();
+        let match1 = r#"{"match_id":1,"scores":[["Alice",50],["Bob",30]],"host_actor_id":"h","completed":true}"#;
+        storage.append_event("match_end", match1).unwrap();
+        storage.rebuild_derived_caches().unwrap();
+        let before = cache_snapshot(&storage);
+
+        // Fail partway through the Elo replay, after the stats are rebuilt
+        storage
+            .conn
+            .execute_batch(
+                "CREATE TRIGGER fail_elo BEFORE INSERT ON derived_elo_history
+                 WHEN NEW.match_id = 2
+                 BEGIN SELECT RAISE(ABORT, 'injected failure'); END;",
+            )
+            .unwrap();
+        let match2 = r#"{"match_id":2,"scores":[["Alice",10],["Carol",40]],"host_actor_id":"h","completed":true}"#;
+        storage.append_event("match_end", match2).unwrap();
+
+        match storage.rebuild_derived_caches() {
+            Err(StorageError::RebuildFailed { cache, reason }) => {
+                assert_eq!(cache, "elo");
+                assert!(reason.contains("injected failure"), "{}", reason);
+            }
+            other => panic!("expected RebuildFailed, got {:?}", other),
+        }
+        assert_eq!(cache_snapshot(&storage), before);
+        assert!(storage.caches_need_rebuild().unwrap());
+
+        assert!(matches!(
+            storage.update_derived_caches_incremental(),
+            Err(StorageError::RebuildFailed { cache: "derived", .. })
+        ));
+        assert_eq!(cache_snapshot(&storage), before);
+
+        // Nothing is left open: once the fault is gone the rebuild goes through
+        storage.conn.execute_batch("DROP TRIGGER fail_elo").unwrap();
+        storage.rebuild_derived_caches().unwrap();
+        assert_eq!(storage.get_cached_stats("Carol").unwrap().unwrap().rounds_played, 1);
+    }
+
+    #[test]
+    fn test_incremental_update_falls_back_to_full_rebuild() {
+        let storage = Storage::open_in_memory().unwrap();
+        let match1 = r#"{"match_id":5,"scores":[["Alice",50],["Bob",30]],"host_actor_id":"h","completed":true}"#;
+        storage.append_event("match_end", match1).unwrap();
+
+        // Never built
+        storage.update_derived_caches_incremental().unwrap();
+        let built = cache_snapshot(&storage);
+        storage.rebuild_derived_caches().unwrap();
+        assert_eq!(built, cache_snapshot(&storage));
+
+        // A late match that replays before one already rated
+        let late = r#"{"match_id":3,"scores":[["Alice",10],["Bob",40]],"host_actor_id":"h2","completed":true}"#;
+        storage.append_event("match_end", late).unwrap();
+        storage.update_derived_caches_incremental().unwrap();
+        let updated = cache_snapshot(&storage);
+        storage.rebuild_derived_caches().unwrap();
+        assert_eq!(updated, cache_snapshot(&storage));
+        assert_eq!(updated.1.first().map(|row| row.0), Some(3));
+
+        // Watermark from an older build, which always recorded 0
+        storage.conn.execute("UPDATE derived_cache_meta SET last_event_seq = 0", []).unwrap();
+        assert_eq!(storage.cache_watermark().unwrap(), None);
+        storage.update_derived_caches_incremental().unwrap();
+        assert_eq!(updated, cache_snapshot(&storage));
+        assert!(storage.cache_watermark().unwrap().is_some());
+
+        // An event removed from under the watermark
+        storage.conn.execute("DELETE FROM events WHERE payload = ?1", params![late]).unwrap();
+        assert_eq!(storage.cache_watermark().unwrap(), None);
+        storage.update_derived_caches_incremental().unwrap();
+        assert_eq!(built, cache_snapshot(&storage));
+    }
+
+    #[test]
+    fn test_format_match_report() {
+        let storage = Storage::open_in_memory().unwrap();
+        let claims = [
+            r#"{"word":"cat","player_name":"Alice","points":3,"round_id":42}"#,
+            r#"{"word":"TOAST","player_name":"Bob","points":5,"round_id":42}"#,
+            r#"{"word":"DOG","player_name":"Alice","points":3,"round_id":42}"#,
+            // Same claim synced from another device
+            r#"{"word":"CAT","player_name":"Alice","points":3,"round_id":42}"#,
+            // A different round
+            r#"{"word":"ANT","player_name":"Alice","points":3,"round_id":41}"#,
+        ];
+        for claim in claims {
+            storage.append_event("word_claimed", claim).unwrap();
+        }
+        let result = MatchResult::new(
+            7,
+            vec![("Bob".to_string(), 5), ("Alice".to_string(), 6)],
+            "host1".to_string(),
+        )
+        .for_round(42);
+        storage.append_event("match_end", &result.to_json()).unwrap();
+
+        let report = storage.format_match_report(7).unwrap().unwrap();
+        assert_eq!(
+            report,
+            "BLAM! match 7\n\n\
+             Final standings\n\
+             \x201. Alice    6 pts\n\
+             \x202. Bob      5 pts\n\n\
+             Words\n\
+             Alice  CAT, DOG\n\
+             Bob    TOAST\n\n\
+             Best word: TOAST by Bob (5 pts)\n"
+        );
+
+        assert_eq!(storage.format_match_report(8).unwrap(), None);
+    }
+
+    #[test]
+    fn test_format_match_report_without_round() {
+        let storage = Storage::open_in_memory().unwrap();
+        let legacy = r#"{"match_id":3,"scores":[["Alice",4],["Bob",9]],"completed":true}"#;
+        storage.append_event("match_end", legacy).unwrap();
+
+        let report = storage.format_match_report(3).unwrap().unwrap();
+        assert!(report.contains(" 1. Bob      9 pts"));
+        assert!(!report.contains("Words"));
+    }
+
     #[test]
     fn test_elo_history_recorded() {
         let storage = Storage::open_in_memory().unwrap();
@@ -1601,6 +4174,59 @@ mod tests {
         assert_eq!(count, 2); // One entry per player
     }
 
+    #[test]
+    fn test_next_match_counter_increments() {
+        let storage = Storage::open_in_memory().unwrap();
+        assert_eq!(storage.next_match_counter().unwrap(), 1);
+
+        let result = MatchResult::with_host_counter(
+            1000,
+            storage.next_match_counter().unwrap(),
+            vec![("Alice".to_string(), 5)],
+            "h".to_string(),
+        );
+        storage.append_event("match_end", &result.to_json()).unwrap();
+        storage.append_event("word_claimed", "{}").unwrap();
+
+        assert_eq!(storage.next_match_counter().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_elo_replay_tolerates_backwards_clock() {
+        let storage = Storage::open_in_memory().unwrap();
+
+        // Host's clock jumped back between matches; Bob wins the later match
+        let first = MatchResult::with_host_counter(
+            2_000_000,
+            1,
+            vec![("Alice".to_string(), 50), ("Bob".to_string(), 10)],
+            "h".to_string(),
+        );
+        let second = MatchResult::with_host_counter(
+            1_000_000,
+            2,
+            vec![("Alice".to_string(), 10), ("Bob".to_string(), 50)],
+            "h".to_string(),
+        );
+        storage.append_event("match_end", &first.to_json()).unwrap();
+        storage.append_event("match_end", &second.to_json()).unwrap();
+        storage.rebuild_derived_caches().unwrap();
+
+        // The second match must start from the ratings the first produced
+        let first_after: f64 = storage.conn.query_row(
+            "SELECT elo_after FROM derived_elo_history WHERE match_id = ?1 AND handle = 'Alice'",
+            params![first.match_id],
+            |row| row.get(0),
+        ).unwrap();
+        let second_before: f64 = storage.conn.query_row(
+            "SELECT elo_before FROM derived_elo_history WHERE match_id = ?1 AND handle = 'Alice'",
+            params![second.match_id],
+            |row| row.get(0),
+        ).unwrap();
+        assert!((first_after - second_before).abs() < 0.001);
+        assert!(first_after > 1200.0);
+    }
+
     // === Versioned Payload Edge Cases ===
 
     #[test]
@@ -1665,4 +4291,33 @@ mod tests {
         let result = parse_match_result_payload(payload).unwrap();
         assert!(!result.completed);
     }
+
+    #[test]
+    fn test_adversarial_payloads_rebuild_stats() {
+        let storage = Storage::open_in_memory().unwrap();
+        let points = "\"points\"";
+        let word = "\"scores\":[";
+        let claim = format!(
+            r#"{{"word":"{}","player_name":"{}","points":3,"round_id":1,"extra":{{"word":"X"}}}}"#,
+            escape_json(word),
+            escape_json(points)
+        );
+        storage.append_event("word_claimed", &claim).unwrap();
+        let scores = vec![(points.to_string(), 40), ("Bob".to_string(), 10)];
+        let result = MatchResult::new(1, scores, "h".to_string());
+        storage.append_event("match_end", &result.to_json()).unwrap();
+        // A nested object holding match fields isn't a match of its own
+        storage
+            .append_event("match_end", r#"{"wrapped":{"match_id":2,"scores":[["Bob",99]]}}"#)
+            .unwrap();
+        storage.rebuild_derived_caches().unwrap();
+
+        let stats = storage.get_cached_stats(points).unwrap().unwrap();
+        assert_eq!(stats.rounds_played, 1);
+        assert_eq!(stats.total_points, 40);
+        assert_eq!(stats.words_claimed, 1);
+        assert_eq!(stats.longest_word, word);
+        let bob = storage.get_cached_stats("Bob").unwrap().unwrap();
+        assert_eq!((bob.rounds_played, bob.best_score), (1, 10));
+    }
 }
@@ -0,0 +1,150 @@
+#![allow(dead_code)]
+//! Derived cache rebuilds on a background thread.
+//!
+//! Replaying a large event log can take long enough to freeze the TUI. A
+//! rusqlite `Connection` can't be shared between threads, so the worker
+//! opens its own connection to the same database file and rebuilds there.
+//! The rebuild commits as one transaction: the UI's connection keeps seeing
+//! the old caches until it's done, then reads the new ones. Only events
+//! added since the last rebuild are replayed when that's possible. An
+//! encrypted database's key is handed to the worker, so it never needs the
+//! passphrase.
+
+use crate::storage::crypt::PayloadCipher;
+use crate::storage::{Storage, StorageError};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A cache rebuild running on a background thread
+pub struct CacheRebuild {
+    /// Receives the outcome once the worker is done
+    rx: Receiver<Result<(), StorageError>>,
+    /// When the rebuild was started (drives the spinner)
+    started: Instant,
+}
+
+impl CacheRebuild {
+    /// Start rebuilding the caches of the database at `db_path`
+    ///
+    /// `key` is the database's `Storage::payload_key`, if it's encrypted.
+    pub fn spawn(db_path: PathBuf, key: Option<PayloadCipher>) -> Self {
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            let result = Storage::open_with_key(&db_path, key)
+                .and_then(|storage| storage.update_derived_caches_incremental());
+            let _ = tx.send(result);
+        });
+        Self {
+            rx,
+            started: Instant::now(),
+        }
+    }
+
+    /// Check whether the rebuild has finished, without blocking
+    ///
+    /// Returns `None` while it's still running.
+    pub fn try_finish(&self) -> Option<Result<(), StorageError>> {
+        match self.rx.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(StorageError::RebuildAborted)),
+        }
+    }
+
+    /// How long the rebuild has been running
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_db_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("blam-{}-{}.db", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_background_rebuild_updates_leaderboard() {
+        let path = temp_db_path("rebuild");
+        let _ = std::fs::remove_file(&path);
+        let storage = Storage::open_at(&path).unwrap();
+        for match_id in 1..=20 {
+            let payload = format!(
+                r#"{{"match_id":{},"scores":[["Alice",50],["Bob",30]],"host_actor_id":"host1","completed":true}}"#,
+                match_id
+            );
+            storage.append_event("match_end", &payload).unwrap();
+        }
+        assert!(storage.caches_need_rebuild().unwrap());
+
+        let rebuild = CacheRebuild::spawn(path.clone(), None);
+
+        // The main loop keeps going: polling never blocks, and the UI's own
+        // connection stays usable while the worker runs
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let result = loop {
+            let before = Instant::now();
+            let finished = rebuild.try_finish();
+            assert!(before.elapsed() < Duration::from_millis(100));
+            if let Some(result) = finished {
+                break result;
+            }
+            storage.get_cached_leaderboard().unwrap();
+            assert!(Instant::now() < deadline, "rebuild never finished");
+            thread::sleep(Duration::from_millis(5));
+        };
+        result.unwrap();
+
+        assert!(!storage.caches_need_rebuild().unwrap());
+        let leaderboard = storage.get_cached_leaderboard().unwrap();
+        assert_eq!(leaderboard.len(), 2);
+        assert_eq!(leaderboard[0].0, "Alice");
+
+        drop(storage);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    /// Wait for `rebuild` to finish
+    fn finish(rebuild: CacheRebuild) -> Result<(), StorageError> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            if let Some(result) = rebuild.try_finish() {
+                return result;
+            }
+            assert!(Instant::now() < deadline, "rebuild never finished");
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_background_rebuild_of_encrypted_database() {
+        let path = temp_db_path("rebuild-encrypted");
+        let _ = std::fs::remove_file(&path);
+        let storage = Storage::open_encrypted_at(&path, "hunter2").unwrap();
+        for match_id in 1..=5 {
+            let payload = format!(
+                r#"{{"match_id":{},"scores":[["Alice",50],["Bob",30]],"host_actor_id":"host1","completed":true}}"#,
+                match_id
+            );
+            storage.append_event("match_end", &payload).unwrap();
+        }
+
+        // Without the key the worker can't read the log
+        let locked = finish(CacheRebuild::spawn(path.clone(), None));
+        assert!(matches!(locked, Err(StorageError::PassphraseRequired)));
+        assert!(storage.caches_need_rebuild().unwrap());
+
+        finish(CacheRebuild::spawn(path.clone(), storage.payload_key())).unwrap();
+        assert!(!storage.caches_need_rebuild().unwrap());
+        let leaderboard = storage.get_cached_leaderboard().unwrap();
+        assert_eq!(leaderboard.len(), 2);
+        assert_eq!(leaderboard[0].0, "Alice");
+
+        drop(storage);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -6,9 +6,37 @@
 //! 2. Compute missing events based on clock differences
 //! 3. Transfer missing events
 //! 4. Idempotent merge (INSERT OR IGNORE)
+//!
+//! Events whose payload was written by a newer build (a higher
+//! `payload_version`) are skipped rather than merged, since the cache
+//! rebuild can't be trusted to parse them.
+
+use crate::network::protocol::{decompress_sync_events, Message, SyncEvent};
+use crate::storage::{is_payload_compatible, ActorId, Event, Storage, StorageError};
+use std::collections::HashMap;
+
+/// Outcome of merging a batch of synced events
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// New events stored (duplicates aren't counted)
+    pub inserted: usize,
+    /// Events skipped because a newer version wrote their payload
+    pub incompatible: usize,
+    /// Compatible events held back because they come after an incompatible
+    /// one from the same actor
+    pub deferred: usize,
+}
 
-use crate::network::protocol::{Message, SyncEvent};
-use crate::storage::{ActorId, Event, Storage, StorageError};
+impl SyncReport {
+    /// Notice for the UI when events had to be skipped
+    pub fn skipped_message(&self) -> Option<String> {
+        match self.incompatible {
+            0 => None,
+            1 => Some("1 event from a newer version was skipped".to_string()),
+            n => Some(format!("{} events from a newer version were skipped", n)),
+        }
+    }
+}
 
 /// Convert storage events to protocol sync events.
 pub fn events_to_sync(events: Vec<Event>) -> Vec<SyncEvent> {
@@ -64,6 +92,7 @@ pub fn create_sync_request(storage: &Storage) -> Result<Message, StorageError> {
         .collect();
     Ok(Message::SyncRequest {
         vector_clock: clock_vec,
+        accepts_gzip: true,
     })
 }
 
@@ -105,21 +134,62 @@ pub fn process_sync_request(
 
 /// Process received sync events by inserting them into storage.
 ///
-/// Returns the number of new events inserted (duplicates are ignored).
+/// Duplicates are ignored. Events with an incompatible payload are skipped,
+/// along with any later events from the same actor: the vector clock only
+/// records each actor's highest seq, so inserting past a gap would mean the
+/// skipped event is never requested again, even after upgrading.
 pub fn process_sync_events(
     storage: &Storage,
     sync_events: Vec<SyncEvent>,
-) -> Result<usize, StorageError> {
+) -> Result<SyncReport, StorageError> {
     let events = sync_to_events(sync_events);
-    let mut inserted = 0;
+    let mut report = SyncReport::default();
+
+    // Earliest incompatible seq per actor
+    let mut first_incompatible: HashMap<ActorId, i64> = HashMap::new();
+    for event in events.iter().filter(|e| !is_payload_compatible(&e.payload)) {
+        let seq = first_incompatible.entry(event.actor_id.clone()).or_insert(event.seq);
+        *seq = (*seq).min(event.seq);
+    }
 
     for event in events {
+        if let Some(&blocked_from) = first_incompatible.get(&event.actor_id) {
+            if event.seq == blocked_from || !is_payload_compatible(&event.payload) {
+                report.incompatible += 1;
+                continue;
+            }
+            if event.seq > blocked_from {
+                report.deferred += 1;
+                continue;
+            }
+        }
         if storage.insert_remote_event(&event)? {
-            inserted += 1;
+            report.inserted += 1;
         }
     }
 
-    Ok(inserted)
+    Ok(report)
+}
+
+/// Handle a sync message from a peer, returning our reply if one is due
+///
+/// A `SyncRequest` is answered with the events the peer is missing,
+/// compressed if the peer accepts it; a `SyncEvents` batch (compressed or
+/// not) is merged into our log and needs no reply. Any other message is
+/// ignored.
+pub fn handle_sync_message(storage: &Storage, message: Message) -> Result<Option<Message>, StorageError> {
+    match message {
+        Message::SyncRequest { vector_clock, accepts_gzip } => process_sync_request(storage, &vector_clock)
+            .map(|reply| Some(reply.compress_sync_for(accepts_gzip))),
+        Message::SyncEvents { events } => process_sync_events(storage, events).map(|_| None),
+        // A batch that won't decode is dropped like any other bad frame;
+        // our vector clock still asks for its events next time
+        Message::SyncEventsCompressed { gzip_base64 } => match decompress_sync_events(&gzip_base64) {
+            Ok(events) => process_sync_events(storage, events).map(|_| None),
+            Err(_) => Ok(None),
+        },
+        _ => Ok(None),
+    }
 }
 
 #[cfg(test)]
@@ -163,7 +233,7 @@ mod tests {
         storage.append_event("test", "{}").unwrap();
 
         let msg = create_sync_request(&storage).unwrap();
-        if let Message::SyncRequest { vector_clock } = msg {
+        if let Message::SyncRequest { vector_clock, .. } = msg {
             assert_eq!(vector_clock.len(), 1);
             assert_eq!(vector_clock[0].0, storage.actor_id().to_hex());
             assert_eq!(vector_clock[0].1, 2);
@@ -190,7 +260,7 @@ mod tests {
         // Peer B sends sync request (with empty knowledge of A)
         let b_request = create_sync_request(&storage_b).unwrap();
         let b_clock = match &b_request {
-            Message::SyncRequest { vector_clock } => vector_clock.clone(),
+            Message::SyncRequest { vector_clock, .. } => vector_clock.clone(),
             _ => panic!("Expected SyncRequest"),
         };
 
@@ -205,7 +275,7 @@ mod tests {
         assert_eq!(a_events.len(), 3);
 
         // Peer B processes the events
-        let inserted = process_sync_events(&storage_b, a_events).unwrap();
+        let inserted = process_sync_events(&storage_b, a_events).unwrap().inserted;
         assert_eq!(inserted, 3);
 
         // Now B has 5 events total (2 own + 3 from A)
@@ -214,7 +284,7 @@ mod tests {
         // Reverse: A gets B's events
         let a_request = create_sync_request(&storage_a).unwrap();
         let a_clock = match &a_request {
-            Message::SyncRequest { vector_clock } => vector_clock.clone(),
+            Message::SyncRequest { vector_clock, .. } => vector_clock.clone(),
             _ => panic!("Expected SyncRequest"),
         };
 
@@ -227,7 +297,7 @@ mod tests {
         // B has 2 own events that A doesn't know about
         assert_eq!(b_events.len(), 2);
 
-        let inserted = process_sync_events(&storage_a, b_events).unwrap();
+        let inserted = process_sync_events(&storage_a, b_events).unwrap().inserted;
         assert_eq!(inserted, 2);
 
         // Both have 5 events now
@@ -246,7 +316,7 @@ mod tests {
         // B gets A's events
         let request = create_sync_request(&storage_b).unwrap();
         let clock = match &request {
-            Message::SyncRequest { vector_clock } => vector_clock.clone(),
+            Message::SyncRequest { vector_clock, .. } => vector_clock.clone(),
             _ => panic!(),
         };
         let response = process_sync_request(&storage_a, &clock).unwrap();
@@ -256,11 +326,11 @@ mod tests {
         };
 
         // First sync
-        let inserted1 = process_sync_events(&storage_b, events.clone()).unwrap();
+        let inserted1 = process_sync_events(&storage_b, events.clone()).unwrap().inserted;
         assert_eq!(inserted1, 1);
 
         // Second sync (duplicate) - should insert 0
-        let inserted2 = process_sync_events(&storage_b, events).unwrap();
+        let inserted2 = process_sync_events(&storage_b, events).unwrap().inserted;
         assert_eq!(inserted2, 0);
 
         // Still only 1 event in B
@@ -282,7 +352,7 @@ mod tests {
         // B syncs and gets all 5
         let request = create_sync_request(&storage_b).unwrap();
         let clock = match &request {
-            Message::SyncRequest { vector_clock } => vector_clock.clone(),
+            Message::SyncRequest { vector_clock, .. } => vector_clock.clone(),
             _ => panic!(),
         };
         let response = process_sync_request(&storage_a, &clock).unwrap();
@@ -300,7 +370,7 @@ mod tests {
         // B syncs again - should only get 2 new events
         let request = create_sync_request(&storage_b).unwrap();
         let clock = match &request {
-            Message::SyncRequest { vector_clock } => vector_clock.clone(),
+            Message::SyncRequest { vector_clock, .. } => vector_clock.clone(),
             _ => panic!(),
         };
         let response = process_sync_request(&storage_a, &clock).unwrap();
@@ -310,11 +380,61 @@ mod tests {
         };
         assert_eq!(events.len(), 2);
 
-        let inserted = process_sync_events(&storage_b, events).unwrap();
+        let inserted = process_sync_events(&storage_b, events).unwrap().inserted;
         assert_eq!(inserted, 2);
         assert_eq!(storage_b.event_count().unwrap(), 7);
     }
 
+    #[test]
+    fn test_newer_payload_skipped_before_rebuild() {
+        let storage = Storage::open_in_memory().unwrap();
+        let actor = ActorId::generate().to_hex();
+        let event = |seq: i64, payload: &str| SyncEvent {
+            actor_id: actor.clone(),
+            seq,
+            event_type: "match_end".to_string(),
+            payload: payload.to_string(),
+            created_at: 1700000000000 + seq,
+        };
+        let events = vec![
+            event(1, r#"{"payload_version":1,"match_id":1,"scores":[["Alice",9],["Bob",4]],"host_actor_id":"h","completed":true}"#),
+            event(2, r#"{"payload_version":999,"match_id":2,"scores":[["Carol",9],["Dave",4]],"host_actor_id":"h","completed":true}"#),
+            event(3, r#"{"payload_version":1,"match_id":3,"scores":[["Erin",9],["Finn",4]],"host_actor_id":"h","completed":true}"#),
+        ];
+
+        let report = process_sync_events(&storage, events).unwrap();
+        assert_eq!(report, SyncReport { inserted: 1, incompatible: 1, deferred: 1 });
+        assert_eq!(
+            report.skipped_message().as_deref(),
+            Some("1 event from a newer version was skipped")
+        );
+
+        // The clock stops before the gap, so an upgraded build asks again
+        let clock = storage.get_vector_clock().unwrap();
+        assert_eq!(clock[0].1, 1);
+
+        storage.rebuild_derived_caches().unwrap();
+        let handles: Vec<String> = storage
+            .get_cached_leaderboard()
+            .unwrap()
+            .into_iter()
+            .map(|(handle, _)| handle)
+            .collect();
+        assert_eq!(handles.len(), 2);
+        assert!(handles.contains(&"Alice".to_string()));
+        assert!(handles.contains(&"Bob".to_string()));
+    }
+
+    #[test]
+    fn test_clean_sync_has_no_skipped_message() {
+        assert_eq!(SyncReport { inserted: 3, ..Default::default() }.skipped_message(), None);
+        let report = SyncReport { incompatible: 4, ..Default::default() };
+        assert_eq!(
+            report.skipped_message().as_deref(),
+            Some("4 events from a newer version were skipped")
+        );
+    }
+
     #[test]
     fn test_hex_to_bytes_invalid() {
         // Wrong length
@@ -357,7 +477,7 @@ mod tests {
         // Both empty - sync should produce empty results
         let request = create_sync_request(&storage_a).unwrap();
         let clock = match &request {
-            Message::SyncRequest { vector_clock } => vector_clock.clone(),
+            Message::SyncRequest { vector_clock, .. } => vector_clock.clone(),
             _ => panic!(),
         };
 
@@ -382,13 +502,13 @@ mod tests {
 
         // A syncs with B (bidirectional)
         let req = create_sync_request(&storage_a).unwrap();
-        let clock = match &req { Message::SyncRequest { vector_clock } => vector_clock.clone(), _ => panic!() };
+        let clock = match &req { Message::SyncRequest { vector_clock, .. } => vector_clock.clone(), _ => panic!() };
         let resp = process_sync_request(&storage_b, &clock).unwrap();
         let evts = match resp { Message::SyncEvents { events } => events, _ => panic!() };
         process_sync_events(&storage_a, evts).unwrap();
 
         let req = create_sync_request(&storage_b).unwrap();
-        let clock = match &req { Message::SyncRequest { vector_clock } => vector_clock.clone(), _ => panic!() };
+        let clock = match &req { Message::SyncRequest { vector_clock, .. } => vector_clock.clone(), _ => panic!() };
         let resp = process_sync_request(&storage_a, &clock).unwrap();
         let evts = match resp { Message::SyncEvents { events } => events, _ => panic!() };
         process_sync_events(&storage_b, evts).unwrap();
@@ -398,13 +518,13 @@ mod tests {
 
         // A syncs with C (bidirectional)
         let req = create_sync_request(&storage_a).unwrap();
-        let clock = match &req { Message::SyncRequest { vector_clock } => vector_clock.clone(), _ => panic!() };
+        let clock = match &req { Message::SyncRequest { vector_clock, .. } => vector_clock.clone(), _ => panic!() };
         let resp = process_sync_request(&storage_c, &clock).unwrap();
         let evts = match resp { Message::SyncEvents { events } => events, _ => panic!() };
         process_sync_events(&storage_a, evts).unwrap();
 
         let req = create_sync_request(&storage_c).unwrap();
-        let clock = match &req { Message::SyncRequest { vector_clock } => vector_clock.clone(), _ => panic!() };
+        let clock = match &req { Message::SyncRequest { vector_clock, .. } => vector_clock.clone(), _ => panic!() };
         let resp = process_sync_request(&storage_a, &clock).unwrap();
         let evts = match resp { Message::SyncEvents { events } => events, _ => panic!() };
         process_sync_events(&storage_c, evts).unwrap();
@@ -413,4 +533,35 @@ mod tests {
         assert_eq!(storage_a.event_count().unwrap(), 3);
         assert_eq!(storage_c.event_count().unwrap(), 3);
     }
+
+    #[test]
+    fn test_large_batches_compressed_only_for_peers_that_accept_it() {
+        let storage_a = Storage::open_in_memory().unwrap();
+        let storage_b = Storage::open_in_memory().unwrap();
+        for i in 0..300 {
+            let payload = format!(r#"{{"word":"WORD{}","player":"Alice","points":3}}"#, i);
+            storage_a.append_event("word_claimed", &payload).unwrap();
+        }
+
+        // An older build's request gets a plain batch
+        let Message::SyncRequest { vector_clock, .. } = create_sync_request(&storage_b).unwrap()
+        else {
+            panic!("Expected SyncRequest");
+        };
+        let old_request = Message::SyncRequest { vector_clock, accepts_gzip: false };
+        let reply = handle_sync_message(&storage_a, old_request).unwrap().unwrap();
+        assert!(matches!(&reply, Message::SyncEvents { events } if events.len() == 300));
+
+        // Ours gets it compressed, and it merges all the same
+        let request = create_sync_request(&storage_b).unwrap();
+        let reply = handle_sync_message(&storage_a, request).unwrap().unwrap();
+        assert!(matches!(reply, Message::SyncEventsCompressed { .. }));
+        assert_eq!(handle_sync_message(&storage_b, reply).unwrap(), None);
+        assert_eq!(storage_b.event_count().unwrap(), 300);
+
+        // A corrupt batch is dropped without touching the log
+        let corrupt = Message::SyncEventsCompressed { gzip_base64: "AAAA".to_string() };
+        assert_eq!(handle_sync_message(&storage_b, corrupt).unwrap(), None);
+        assert_eq!(storage_b.event_count().unwrap(), 300);
+    }
 }
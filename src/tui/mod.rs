@@ -1,7 +1,51 @@
 //! Terminal UI components using ratatui
 
+mod mouse;
 mod terminal;
+mod theme;
 mod ui;
 
+pub use mouse::handle_click;
 pub use terminal::Tui;
 pub use ui::render;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppCoordinator;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    /// Draw the coordinator's screen on a `width` x `height` terminal
+    fn draw(coordinator: &AppCoordinator, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| render(frame, coordinator)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn test_render_1x1_does_not_panic() {
+        draw(&AppCoordinator::new(), 1, 1);
+    }
+
+    #[test]
+    fn test_small_terminals_ask_for_more_room() {
+        let coordinator = AppCoordinator::new();
+        let (width, height) = (ui::MIN_WIDTH, ui::MIN_HEIGHT);
+        assert!(draw(&coordinator, width - 1, height).contains("Terminal too small"));
+        assert!(draw(&coordinator, width, height - 1).contains("too small"));
+        assert!(!draw(&coordinator, width, height).contains("too small"));
+    }
+
+    #[test]
+    fn test_offline_screens_draw_at_any_size() {
+        // Solo practice, hotseat and settings
+        for option in [2, 3, 5] {
+            let mut coordinator = AppCoordinator::new();
+            coordinator.menu_click(option);
+            for (width, height) in [(0, 0), (1, 1), (ui::MIN_WIDTH, ui::MIN_HEIGHT), (200, 60)] {
+                draw(&coordinator, width, height);
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+//! Mouse clicks on the menu and the lobby browser
+//!
+//! Clicks are mapped to list rows with the same layout the screens are
+//! drawn with, so they land on the right row at any terminal size.
+
+use crate::app::{AppCoordinator, MenuOption, Screen};
+use ratatui::layout::{Position, Rect};
+
+use super::ui::{browser_list_area, menu_list_area, MIN_HEIGHT, MIN_WIDTH};
+
+/// Row of a one-line-per-item list drawn in `list` that (`column`, `row`) hits
+fn row_at(list: Rect, column: u16, row: u16, count: usize) -> Option<usize> {
+    if !list.contains(Position::new(column, row)) {
+        return None;
+    }
+    let index = (row - list.y) as usize;
+    (index < count).then_some(index)
+}
+
+/// Menu option under a click, for a terminal of size `area`
+pub fn menu_row_at(area: Rect, column: u16, row: u16) -> Option<usize> {
+    row_at(menu_list_area(area), column, row, MenuOption::all().len())
+}
+
+/// Lobby row under a click, for a terminal of size `area` listing `count` lobbies
+pub fn browser_row_at(area: Rect, column: u16, row: u16, count: usize) -> Option<usize> {
+    row_at(browser_list_area(area), column, row, count)
+}
+
+/// Handle a left click at (`column`, `row`) on a terminal of size `area`
+pub fn handle_click(coordinator: &mut AppCoordinator, area: Rect, column: u16, row: u16) {
+    // Too small to draw the lists, so nothing to click on
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        return;
+    }
+    match &coordinator.screen {
+        Screen::Menu { editing_handle: false, .. } => {
+            if let Some(index) = menu_row_at(area, column, row) {
+                coordinator.menu_click(index);
+            }
+        }
+        Screen::Browser { lobbies, .. } => {
+            if let Some(index) = browser_row_at(area, column, row, lobbies.len()) {
+                coordinator.browser_click(index);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TERMINAL: Rect = Rect { x: 0, y: 0, width: 80, height: 30 };
+
+    #[test]
+    fn test_menu_rows_below_logo_and_handle() {
+        // 2 margin + 8 logo + 3 handle + 1 spacer
+        assert_eq!(menu_row_at(TERMINAL, 40, 14), Some(0));
+        assert_eq!(menu_row_at(TERMINAL, 10, 16), Some(2));
+        assert_eq!(menu_row_at(TERMINAL, 40, 13), None);
+        // Past the last option, and outside the margin
+        assert_eq!(menu_row_at(TERMINAL, 40, 14 + MenuOption::all().len() as u16), None);
+        assert_eq!(menu_row_at(TERMINAL, 1, 14), None);
+    }
+
+    #[test]
+    fn test_browser_rows_inside_list_border() {
+        // 1 margin + 3 header + 1 border
+        assert_eq!(browser_row_at(TERMINAL, 40, 5, 3), Some(0));
+        assert_eq!(browser_row_at(TERMINAL, 40, 7, 3), Some(2));
+        assert_eq!(browser_row_at(TERMINAL, 40, 4, 3), None);
+        assert_eq!(browser_row_at(TERMINAL, 40, 8, 3), None);
+        // The border columns aren't rows
+        assert_eq!(browser_row_at(TERMINAL, 1, 5, 3), None);
+        assert_eq!(browser_row_at(TERMINAL, 2, 5, 3), Some(0));
+    }
+
+    #[test]
+    fn test_rows_follow_a_resize() {
+        let small = Rect { x: 0, y: 0, width: 40, height: 20 };
+        assert_eq!(browser_row_at(small, 20, 5, 1), Some(0));
+        assert_eq!(browser_row_at(small, 39, 5, 1), None);
+        // Too short for the logo: the options get squeezed below it
+        let short = Rect { x: 0, y: 0, width: 40, height: 12 };
+        let list = menu_list_area(short);
+        assert_eq!(menu_row_at(short, 20, list.y), Some(0));
+        assert_eq!(menu_row_at(short, 20, list.bottom()), None);
+    }
+}
@@ -40,6 +40,12 @@ impl Tui {
         Ok(())
     }
 
+    /// The terminal's current size, as the area a frame is drawn in
+    pub fn area(&self) -> io::Result<Rect> {
+        let size = self.terminal.size()?;
+        Ok(Rect::new(0, 0, size.width, size.height))
+    }
+
     /// Draw the UI
     pub fn draw<F>(&mut self, f: F) -> io::Result<()>
     where
@@ -0,0 +1,203 @@
+#![allow(dead_code)]
+//! Named styles the renderer draws with
+//!
+//! Every screen asks the active `Theme` for a role ("accept", "muted",
+//! "selection", ...) instead of naming a color, so swapping the theme
+//! recolors the whole UI. Which theme is active is `AppCoordinator::theme`.
+
+use crate::app::ThemeChoice;
+use ratatui::style::{Color, Modifier, Style};
+
+/// The styles for each role in the UI
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Screen titles and the logo
+    pub header: Style,
+    /// Ordinary text
+    pub text: Style,
+    /// Hints, footers and anything greyed out
+    pub muted: Style,
+    /// Draws the eye: leaders, combos, the sorted column
+    pub highlight: Style,
+    /// The local player, the letter rack and text being typed
+    pub info: Style,
+    /// The selected item in a list
+    pub selection: Style,
+    /// Accepted words, saved settings, ready to start
+    pub accept: Style,
+    /// Rejected words and errors
+    pub reject: Style,
+    /// Scores, announcements and celebrations
+    pub special: Style,
+    /// Countdown numbers, indexed by count (0 is the "go" shout)
+    pub countdown: [Style; 4],
+}
+
+impl Theme {
+    /// The original palette
+    pub fn classic() -> Self {
+        Self {
+            header: fg(Color::Yellow),
+            text: fg(Color::White),
+            muted: fg(Color::DarkGray),
+            highlight: fg(Color::Yellow),
+            info: fg(Color::Cyan),
+            selection: fg(Color::Yellow),
+            accept: fg(Color::Green),
+            reject: fg(Color::Red),
+            special: fg(Color::Magenta),
+            countdown: [
+                fg(Color::Magenta),
+                fg(Color::Red),
+                fg(Color::Yellow),
+                fg(Color::Green),
+            ],
+        }
+    }
+
+    /// Bright colors only, with selections and headers drawn reversed
+    pub fn high_contrast() -> Self {
+        Self {
+            header: Style::default().fg(Color::Black).bg(Color::LightYellow),
+            text: fg(Color::White),
+            muted: fg(Color::Gray),
+            highlight: fg(Color::LightYellow),
+            info: fg(Color::LightCyan),
+            selection: Style::default().fg(Color::Black).bg(Color::White),
+            accept: fg(Color::LightGreen),
+            reject: fg(Color::LightRed).add_modifier(Modifier::UNDERLINED),
+            special: fg(Color::LightMagenta),
+            countdown: [
+                fg(Color::LightMagenta),
+                fg(Color::LightRed),
+                fg(Color::LightYellow),
+                fg(Color::LightGreen),
+            ],
+        }
+    }
+
+    /// Sky blue and vermillion in place of green and red
+    pub fn colorblind() -> Self {
+        let sky_blue = Color::Rgb(86, 180, 233);
+        let vermillion = Color::Rgb(213, 94, 0);
+        Self {
+            header: fg(Color::Yellow),
+            text: fg(Color::White),
+            muted: fg(Color::DarkGray),
+            highlight: fg(Color::Yellow),
+            info: fg(Color::White).add_modifier(Modifier::ITALIC),
+            selection: fg(Color::Yellow).add_modifier(Modifier::REVERSED),
+            accept: fg(sky_blue),
+            reject: fg(vermillion),
+            special: fg(Color::Rgb(204, 121, 167)),
+            countdown: [
+                fg(Color::Rgb(204, 121, 167)),
+                fg(vermillion),
+                fg(Color::Yellow),
+                fg(sky_blue),
+            ],
+        }
+    }
+
+    /// The styles for a theme picked in Settings
+    pub fn for_choice(choice: ThemeChoice) -> Self {
+        match choice {
+            ThemeChoice::Classic => Self::classic(),
+            ThemeChoice::HighContrast => Self::high_contrast(),
+            ThemeChoice::Colorblind => Self::colorblind(),
+        }
+    }
+
+    /// Style for a countdown number (counts above 3 use plain text)
+    pub fn countdown_style(&self, count: u32) -> Style {
+        self.countdown.get(count as usize).copied().unwrap_or(self.text)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+fn fg(color: Color) -> Style {
+    Style::default().fg(color)
+}
+
+/// Relative luminance (0 black to 1 white) of a color, as WCAG defines it
+///
+/// Named colors use xterm's default palette; `Reset` and indexed colors
+/// have no fixed value and count as mid-grey.
+pub fn luminance(color: Color) -> f64 {
+    let (r, g, b) = match color {
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 0, 0),
+        Color::LightGreen => (0, 255, 0),
+        Color::LightYellow => (255, 255, 0),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 0, 255),
+        Color::LightCyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Reset | Color::Indexed(_) => (127, 127, 127),
+    };
+    let channel = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Smallest luminance gap that still reads as different without hue
+    const MIN_LUMINANCE_GAP: f64 = 0.15;
+
+    #[test]
+    fn test_accept_and_reject_differ_in_luminance() {
+        for choice in ThemeChoice::all() {
+            let theme = Theme::for_choice(choice);
+            let accept = luminance(theme.accept.fg.expect("accept has a color"));
+            let reject = luminance(theme.reject.fg.expect("reject has a color"));
+            assert!(
+                (accept - reject).abs() >= MIN_LUMINANCE_GAP,
+                "{}: accept {:.3} vs reject {:.3}",
+                choice.label(),
+                accept,
+                reject
+            );
+        }
+    }
+
+    #[test]
+    fn test_themes_are_distinct_and_fully_styled() {
+        let themes: Vec<Theme> = ThemeChoice::all().into_iter().map(Theme::for_choice).collect();
+        for (i, theme) in themes.iter().enumerate() {
+            assert!(theme.countdown.iter().all(|style| style.fg.is_some()));
+            assert!(themes[i + 1..].iter().all(|other| other != theme));
+        }
+        assert_eq!(Theme::default(), Theme::classic());
+        assert_eq!(Theme::classic().countdown_style(9), Theme::classic().text);
+    }
+
+    #[test]
+    fn test_luminance_ends_of_the_scale() {
+        assert_eq!(luminance(Color::Black), 0.0);
+        assert!((luminance(Color::White) - 1.0).abs() < 1e-9);
+        assert!(luminance(Color::LightGreen) > luminance(Color::LightRed));
+    }
+}
@@ -7,67 +7,181 @@
 //! - HostLobby: Hosting a lobby, waiting for players
 //! - JoinedLobby: Joined a lobby, waiting for start
 //! - Playing: In-game screen
+//! - Hotseat: Two players sharing the keyboard, one panel each
+//! - ResumePrompt: Offer to resume an interrupted solo round
 //! - Error: Error message display
 
-use crate::app::{App, AppCoordinator, MenuOption, Screen};
-use crate::lobby::Player;
+use crate::app::hotseat::{HotseatGame, HotseatPlayer};
+use crate::app::state::winner_of;
+use crate::app::{App, AppCoordinator, FlavorText, MenuOption, RankingsSort, RankingsStatus, Screen};
+use crate::game::dictionary;
+use crate::game::Language;
+use crate::game::scoring::letter_points;
+use crate::lobby::{BrowserStatus, Diagnostics, HostedLobby, JoinedLobby};
 use crate::network::PeerInfo;
-use crate::storage::CachedPlayerStats;
+use crate::stats::MatchResult;
+use crate::storage::RoundCheckpoint;
+use super::theme::Theme;
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Narrowest terminal the screens are laid out for
+pub const MIN_WIDTH: u16 = 40;
+
+/// Shortest terminal the screens are laid out for
+pub const MIN_HEIGHT: u16 = 12;
 
 /// Render the appropriate screen based on app state
 pub fn render(frame: &mut Frame, coordinator: &AppCoordinator) {
+    let theme = &Theme::for_choice(coordinator.theme);
+    let area = frame.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        render_too_small(frame, theme);
+        return;
+    }
     match &coordinator.screen {
-        Screen::Menu { selected, handle, handle_input, editing_handle } => {
-            render_menu(frame, *selected, handle, handle_input, *editing_handle);
+        Screen::Menu { selected, handle, handle_input, editing_handle, handle_error } => {
+            render_menu(frame, theme, *selected, handle, handle_input, *editing_handle, handle_error.as_deref());
         }
-        Screen::Browser { lobbies, selected, .. } => {
-            render_browser(frame, lobbies, *selected);
+        Screen::Browser { browser, lobbies, selected, .. } => {
+            render_browser(frame, theme, lobbies, *selected, browser.status());
         }
-        Screen::HostLobby { lobby, countdown } => {
-            render_host_lobby(
-                frame,
-                &lobby.lobby_name,
-                lobby.players(),
-                lobby.port(),
-                lobby.can_start(),
-                *countdown,
-                lobby.current_letters(),
-            );
+        Screen::RackPreview { letters, .. } => {
+            render_rack_preview(frame, theme, letters);
+        }
+        Screen::HostLobby { lobby, countdown, selected } => {
+            render_host_lobby(frame, theme, lobby, *countdown, *selected, &coordinator.flavor);
         }
         Screen::JoinedLobby { lobby, countdown } => {
-            render_joined_lobby(
-                frame,
-                &lobby.lobby_name,
-                &lobby.host_name,
-                lobby.players(),
-                countdown.as_ref(),
-            );
+            // While the host counts down, the countdown takes over the screen
+            if let Some((count, letters, _duration)) = countdown {
+                let go = &coordinator.flavor.go;
+                render_countdown(frame, theme, frame.area(), *count, letters, go);
+            } else {
+                render_joined_lobby(frame, theme, lobby);
+            }
         }
         Screen::Playing { app, .. } => {
-            render_game(frame, app);
+            render_game(frame, theme, app);
+        }
+        Screen::Hotseat { game } => {
+            render_hotseat(frame, theme, game);
+        }
+        Screen::Rankings { .. } => {
+            let spinner = coordinator.cache_rebuild.as_ref().map(|r| spinner_frame(r.elapsed()));
+            render_rankings(frame, theme, &coordinator.screen, spinner);
+        }
+        Screen::MatchHistory { matches, selected, feedback, .. } => {
+            render_match_history(frame, theme, matches, *selected, feedback);
         }
-        Screen::Rankings { players, current_handle, scroll_offset } => {
-            render_rankings(frame, players, current_handle, *scroll_offset);
+        Screen::Settings { .. } => {
+            render_settings(frame, theme, &coordinator.screen, coordinator.theme.label());
         }
-        Screen::Settings { handle_input, editing, feedback, .. } => {
-            render_settings(frame, handle_input, *editing, feedback);
+        Screen::ResumePrompt { checkpoint, .. } => {
+            render_resume_prompt(frame, theme, checkpoint);
         }
         Screen::Error { message } => {
-            render_error(frame, message);
+            render_error(frame, theme, message);
         }
     }
+
+    if let Screen::HostLobby { lobby, .. } = &coordinator.screen {
+        if coordinator.show_diagnostics {
+            render_diagnostics(frame, theme, &lobby.diagnostics());
+        }
+    }
+    if let Some(draft) = &coordinator.announcement_draft {
+        render_prompt(frame, theme, "Announce: ", draft, "  Enter Send  Esc Cancel");
+    }
+    if let Some(draft) = &coordinator.status_draft {
+        render_prompt(frame, theme, "Status: ", draft, "  Enter Set (empty clears)  Esc Cancel");
+    }
 }
 
-/// Render the main menu
-fn render_menu(frame: &mut Frame, selected: usize, handle: &str, handle_input: &str, editing_handle: bool) {
+/// Ask for a bigger terminal instead of squeezing a screen into it
+fn render_too_small(frame: &mut Frame, theme: &Theme) {
     let area = frame.area();
+    let message = format!(
+        "Terminal too small ({}x{}, need {}x{})",
+        area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+    );
+    // Vertically centered, as far as the height allows
+    let top = area.height.saturating_sub(1) / 2;
+    let line = Rect::new(area.x, area.y + top, area.width, area.height - top);
+    let text = Paragraph::new(message)
+        .style(theme.reject)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(text, line);
+}
 
-    // Main layout
-    let layout = Layout::default()
+/// Render a text prompt over the bottom line of the screen
+fn render_prompt(frame: &mut Frame, theme: &Theme, label: &str, draft: &str, hint: &str) {
+    let area = frame.area();
+    let line = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1.min(area.height));
+    let prompt = Line::from(vec![
+        Span::styled(label.to_string(), theme.special.bold()),
+        Span::styled(format!("{}_", draft), theme.text),
+        Span::styled(hint.to_string(), theme.muted),
+    ]);
+    frame.render_widget(Clear, line);
+    frame.render_widget(Paragraph::new(prompt), line);
+}
+
+/// Render the host's connection diagnostics in the top-right corner
+fn render_diagnostics(frame: &mut Frame, theme: &Theme, diagnostics: &Diagnostics) {
+    let area = frame.area();
+    let width = 30.min(area.width);
+    let height = 8.min(area.height);
+    let panel = Rect::new(area.right().saturating_sub(width), area.y, width, height);
+
+    let traffic = &diagnostics.traffic;
+    let lines = vec![
+        Line::from(format!("Uptime     {}", format_timer(diagnostics.uptime.as_secs() as u32))),
+        Line::from(format!("Peers      {}", diagnostics.peers)),
+        Line::from(format!(
+            "Msgs       {} in / {} out",
+            traffic.messages_in, traffic.messages_out
+        )),
+        Line::from(format!(
+            "Bytes      {} / {}",
+            format_bytes(traffic.bytes_in),
+            format_bytes(traffic.bytes_out)
+        )),
+        Line::from(format!("Throughput {}/s", format_bytes(diagnostics.bytes_per_sec))),
+        Line::from(format!("Bad frames {}", traffic.frames_rejected)),
+    ];
+    let paragraph = Paragraph::new(lines)
+        .style(theme.text)
+        .block(Block::default().borders(Borders::ALL).title("Diagnostics"));
+    frame.render_widget(Clear, panel);
+    frame.render_widget(paragraph, panel);
+}
+
+/// Human-readable byte count
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// Banner line for a host announcement
+fn announcement_line(theme: &Theme, text: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("» {} «", text),
+        theme.special.bold(),
+    ))
+}
+
+/// Split the menu screen: logo, handle, spacer, options, footer
+fn menu_layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(8),  // Logo
@@ -77,7 +191,25 @@ fn render_menu(frame: &mut Frame, selected: usize, handle: &str, handle_input: &
             Constraint::Length(2),  // Footer
         ])
         .margin(2)
-        .split(area);
+        .split(area)
+}
+
+/// Where the menu options are drawn, one per row
+pub(super) fn menu_list_area(area: Rect) -> Rect {
+    menu_layout(area)[3]
+}
+
+/// Render the main menu
+fn render_menu(
+    frame: &mut Frame,
+    theme: &Theme,
+    selected: usize,
+    handle: &str,
+    handle_input: &str,
+    editing_handle: bool,
+    handle_error: Option<&str>,
+) {
+    let layout = menu_layout(frame.area());
 
     // Logo
     let logo = r#"
@@ -88,24 +220,26 @@ fn render_menu(frame: &mut Frame, selected: usize, handle: &str, handle_input: &
 |____/|_____/_/   \_\_|  |_(_)
 "#;
     let logo_widget = Paragraph::new(logo)
-        .style(Style::default().fg(Color::Yellow).bold())
+        .style(theme.header.bold())
         .alignment(Alignment::Center);
     frame.render_widget(logo_widget, layout[0]);
 
-    // Handle input
+    // Handle input, with the reason an edit was rejected beneath it
     let handle_display = if editing_handle {
         format!("Handle: [{}]_", handle_input)
     } else {
         format!("Handle: {} (Tab to edit)", handle)
     };
     let handle_style = if editing_handle {
-        Style::default().fg(Color::Cyan)
+        theme.info
     } else {
-        Style::default().fg(Color::DarkGray)
+        theme.muted
     };
-    let handle_widget = Paragraph::new(handle_display)
-        .style(handle_style)
-        .alignment(Alignment::Center);
+    let mut handle_lines = vec![Line::styled(handle_display, handle_style)];
+    if let Some(error) = handle_error {
+        handle_lines.push(Line::styled(error, theme.reject));
+    }
+    let handle_widget = Paragraph::new(handle_lines).alignment(Alignment::Center);
     frame.render_widget(handle_widget, layout[1]);
 
     // Menu options
@@ -114,9 +248,9 @@ fn render_menu(frame: &mut Frame, selected: usize, handle: &str, handle_input: &
         .enumerate()
         .map(|(i, opt)| {
             let style = if i == selected {
-                Style::default().fg(Color::Yellow).bold()
+                theme.selection.bold()
             } else {
-                Style::default().fg(Color::White)
+                theme.text
             };
             let prefix = if i == selected { "> " } else { "  " };
             ListItem::new(format!("{}{}", prefix, opt.label())).style(style)
@@ -125,21 +259,19 @@ fn render_menu(frame: &mut Frame, selected: usize, handle: &str, handle_input: &
 
     let menu = List::new(items)
         .block(Block::default())
-        .highlight_style(Style::default().fg(Color::Yellow));
+        .highlight_style(theme.selection);
     frame.render_widget(menu, layout[3]);
 
     // Footer
     let footer = Paragraph::new("↑↓ Navigate  Enter Select  Esc Quit")
-        .style(Style::default().fg(Color::DarkGray))
+        .style(theme.muted)
         .alignment(Alignment::Center);
     frame.render_widget(footer, layout[4]);
 }
 
-/// Render the lobby browser
-fn render_browser(frame: &mut Frame, lobbies: &[PeerInfo], selected: usize) {
-    let area = frame.area();
-
-    let layout = Layout::default()
+/// Split the lobby browser: header, lobby list, footer
+fn browser_layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),  // Header
@@ -147,35 +279,92 @@ fn render_browser(frame: &mut Frame, lobbies: &[PeerInfo], selected: usize) {
             Constraint::Length(2),  // Footer
         ])
         .margin(1)
-        .split(area);
+        .split(area)
+}
+
+/// Where the lobby rows are drawn, inside the list's border
+pub(super) fn browser_list_area(area: Rect) -> Rect {
+    Block::default().borders(Borders::ALL).inner(browser_layout(area)[1])
+}
+
+/// Render the lobby browser
+fn render_browser(
+    frame: &mut Frame,
+    theme: &Theme,
+    lobbies: &[PeerInfo],
+    selected: usize,
+    status: &BrowserStatus,
+) {
+    let layout = browser_layout(frame.area());
 
     // Header
     let header = Paragraph::new("Available Lobbies")
-        .style(Style::default().fg(Color::Cyan).bold())
+        .style(theme.header.bold())
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::BOTTOM));
     frame.render_widget(header, layout[0]);
 
-    // Lobby list
+    // Lobby list, or why it's empty
     if lobbies.is_empty() {
-        let searching = Paragraph::new("Searching for lobbies on LAN...\n\n(Make sure another player has started a lobby)")
-            .style(Style::default().fg(Color::DarkGray))
-            .alignment(Alignment::Center);
-        frame.render_widget(searching, layout[1]);
+        let (message, style) = match status {
+            BrowserStatus::Searching => (
+                "Searching for lobbies on LAN...\n\n(Make sure another player has started a lobby)"
+                    .to_string(),
+                theme.muted,
+            ),
+            BrowserStatus::Listening => (
+                "No lobbies on your network yet.\n\nWant to host one? Esc, then Start Lobby."
+                    .to_string(),
+                theme.muted,
+            ),
+            BrowserStatus::Failed(reason) => (
+                format!(
+                    "Lobby discovery isn't working ({}).\n\n\
+                     Check that mDNS (UDP port 5353) isn't blocked, then press R to retry.",
+                    reason
+                ),
+                theme.reject,
+            ),
+        };
+        let empty = Paragraph::new(message)
+            .style(style)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(empty, layout[1]);
     } else {
+        let our_dictionary = dictionary::fingerprint();
         let items: Vec<ListItem> = lobbies
             .iter()
             .enumerate()
             .map(|(i, peer)| {
-                let style = if i == selected {
-                    Style::default().fg(Color::Yellow).bold()
+                let style = if !peer.is_joinable() {
+                    theme.muted
+                } else if i == selected {
+                    theme.selection.bold()
                 } else {
-                    Style::default().fg(Color::White)
+                    theme.text
                 };
                 let prefix = if i == selected { "> " } else { "  " };
                 let lobby_name = peer.lobby_name.as_deref().unwrap_or("Unknown");
-                ListItem::new(format!("{}{} (Host: {})", prefix, lobby_name, peer.handle))
-                    .style(style)
+                let resolving = if peer.is_joinable() { "" } else { " resolving…" };
+                let seats = peer
+                    .occupancy()
+                    .map(|occupancy| format!(" {}", occupancy))
+                    .unwrap_or_default();
+                let mut line = Line::from(Span::styled(
+                    format!(
+                        "{}{} (Host: {}){}{}",
+                        prefix, lobby_name, peer.handle, seats, resolving
+                    ),
+                    style,
+                ));
+                if peer.dictionary_differs(our_dictionary) {
+                    line.push_span(Span::styled(
+                        "  ⚠ host uses a different dictionary",
+                        theme.reject,
+                    ));
+                }
+                ListItem::new(line)
             })
             .collect();
 
@@ -185,8 +374,13 @@ fn render_browser(frame: &mut Frame, lobbies: &[PeerInfo], selected: usize) {
     }
 
     // Footer
-    let footer = Paragraph::new("↑↓ Select  Enter Join  Esc Back")
-        .style(Style::default().fg(Color::DarkGray))
+    let footer_text = if matches!(status, BrowserStatus::Failed(_)) {
+        "↑↓ Select  Enter Join  R Retry  Esc Back"
+    } else {
+        "↑↓ Select  Enter Join  Esc Back"
+    };
+    let footer = Paragraph::new(footer_text)
+        .style(theme.muted)
         .alignment(Alignment::Center);
     frame.render_widget(footer, layout[2]);
 }
@@ -194,18 +388,26 @@ fn render_browser(frame: &mut Frame, lobbies: &[PeerInfo], selected: usize) {
 /// Render the host lobby screen
 fn render_host_lobby(
     frame: &mut Frame,
-    lobby_name: &str,
-    players: &[Player],
-    port: u16,
-    can_start: bool,
+    theme: &Theme,
+    lobby: &HostedLobby,
     countdown: Option<u32>,
-    letters: &[char],
+    selected: usize,
+    flavor: &FlavorText,
 ) {
     let area = frame.area();
+    let lobby_name = &lobby.lobby_name;
+    let players = lobby.players();
+    let port = lobby.port();
+    let can_start = lobby.can_start();
 
     // If in countdown, render the countdown screen
     if let Some(count) = countdown {
-        render_countdown(frame, area, count, letters);
+        render_countdown(frame, theme, area, count, lobby.current_letters(), &flavor.go);
+        let hint_area = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1.min(area.height));
+        let hint = Paragraph::new("Backspace Cancel countdown")
+            .style(theme.muted)
+            .alignment(Alignment::Center);
+        frame.render_widget(hint, hint_area);
         return;
     }
 
@@ -213,7 +415,7 @@ fn render_host_lobby(
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),  // Header
-            Constraint::Length(3),  // Lobby info
+            Constraint::Length(4),  // Lobby info
             Constraint::Min(6),     // Player list
             Constraint::Length(3),  // Start button
             Constraint::Length(2),  // Footer
@@ -223,28 +425,91 @@ fn render_host_lobby(
 
     // Header
     let header = Paragraph::new(format!("Lobby: {}", lobby_name))
-        .style(Style::default().fg(Color::Yellow).bold())
+        .style(theme.header.bold())
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::BOTTOM));
     frame.render_widget(header, layout[0]);
 
-    // Lobby info
-    let info = Paragraph::new(format!("Port: {} | Players: {}/12", port, players.len()))
-        .style(Style::default().fg(Color::DarkGray))
+    // Lobby info, with the latest announcement beneath it
+    let rack = lobby.rack_preset();
+    let mut info_lines = vec![Line::from(format!(
+        "Port: {} | Players: {}/12 | Rack: {} ({}-{}{}{}) | Min: {} | Use: {} | Penalty: {} | Target: {} | Repeats: {} | Combos: {} | Grace: {}s",
+        port,
+        players.len(),
+        rack.label(),
+        rack.config().min_size(),
+        rack.config().max_size(),
+        if lobby.blanks() { ", *" } else { "" },
+        match lobby.language() {
+            Language::English => String::new(),
+            language => format!(", {}", language.label()),
+        },
+        lobby.min_word_length(),
+        lobby.letters_used_rule().label(),
+        match lobby.reject_penalty() {
+            0 => "off".to_string(),
+            points => format!("-{}", points),
+        },
+        match lobby.target_score() {
+            0 => "off".to_string(),
+            points => points.to_string(),
+        },
+        if lobby.no_repeats() { "off" } else { "on" },
+        if lobby.combos() { "on" } else { "off" },
+        lobby.disconnect_grace().as_secs()
+    ))];
+    if !lobby.bots().is_empty() {
+        info_lines.push(Line::from(format!(
+            "Bots: {} | Skill: {} | Elo: {}",
+            lobby.bots().len(),
+            lobby.bot_skill().label(),
+            if lobby.bots_rated() { "rated" } else { "unrated" }
+        )));
+    }
+    if !lobby.spectators().is_empty() {
+        info_lines.push(Line::from(format!("Spectators: {}", lobby.spectators().join(", "))));
+    }
+    if let Some(text) = lobby.announcement() {
+        info_lines.push(announcement_line(theme, text));
+    }
+    let info = Paragraph::new(info_lines)
+        .style(theme.muted)
         .alignment(Alignment::Center);
     frame.render_widget(info, layout[1]);
 
     // Player list
     let items: Vec<ListItem> = players
         .iter()
-        .map(|p| {
-            let suffix = if p.is_host { " (Host)" } else { "" };
-            let style = if p.is_local {
-                Style::default().fg(Color::Cyan)
+        .enumerate()
+        .map(|(i, p)| {
+            let suffix = if p.is_host {
+                " (Host)"
+            } else if p.is_bot {
+                " (bot)"
+            } else if lobby.is_away(&p.name) {
+                " (away)"
             } else {
-                Style::default().fg(Color::White)
+                ""
             };
-            ListItem::new(format!("  {} {}{}", "●", p.name, suffix)).style(style)
+            let prefix = if i == selected { ">" } else { " " };
+            let style = if i == selected {
+                theme.selection.bold()
+            } else if lobby.is_away(&p.name) {
+                theme.muted
+            } else if p.is_local {
+                theme.info
+            } else {
+                theme.text
+            };
+            ListItem::new(format!(
+                "{} {} {}{}{}",
+                prefix,
+                "●",
+                p.display_name(),
+                suffix,
+                format_handicap(lobby.handicap(&p.name))
+            ))
+            .style(style)
         })
         .collect();
 
@@ -253,16 +518,18 @@ fn render_host_lobby(
     frame.render_widget(list, layout[2]);
 
     // Start button
-    let start_text = if can_start {
+    let start_text = if can_start && players.len() == 1 {
+        "[ Press ENTER to WARM UP solo ]".to_string()
+    } else if can_start {
         "[ Press ENTER to START ]".to_string()
     } else {
-        "Waiting for players (need at least 2)".to_string()
+        format!("Waiting for players (need at least {}, W to warm up solo)", lobby.min_players())
     };
 
     let start_style = if can_start {
-        Style::default().fg(Color::Green).bold()
+        theme.accept.bold()
     } else {
-        Style::default().fg(Color::DarkGray)
+        theme.muted
     };
 
     let start = Paragraph::new(start_text)
@@ -271,14 +538,21 @@ fn render_host_lobby(
     frame.render_widget(start, layout[3]);
 
     // Footer
-    let footer = Paragraph::new("Enter Start  Esc Cancel")
-        .style(Style::default().fg(Color::DarkGray))
+    let footer = Paragraph::new("↑↓ Select  ←→ Handicap  R Rack  L Min length  U Letters used  P Penalty  T Target  G Grace  N Repeats  C Combos  * Blanks  A Language  W Warm-up  B Bots  K Bot skill  E Bot Elo  D Diagnostics  S Status  Tab Announce  Enter Start  Esc Cancel")
+        .style(theme.muted)
         .alignment(Alignment::Center);
     frame.render_widget(footer, layout[4]);
 }
 
 /// Render the countdown screen (3-2-1-BLAM!)
-fn render_countdown(frame: &mut Frame, area: Rect, count: u32, letters: &[char]) {
+fn render_countdown(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    count: u32,
+    letters: &[char],
+    go: &str,
+) {
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -295,47 +569,29 @@ fn render_countdown(frame: &mut Frame, area: Rect, count: u32, letters: &[char])
         3 => "   3",
         2 => "   2",
         1 => "   1",
-        0 => "BLAM!",
+        0 => go,
         _ => &format!("   {}", count),
     };
 
-    let countdown_color = match count {
-        3 => Color::Green,
-        2 => Color::Yellow,
-        1 => Color::Red,
-        0 => Color::Magenta,
-        _ => Color::White,
-    };
-
     let countdown = Paragraph::new(countdown_text)
-        .style(Style::default().fg(countdown_color).bold())
+        .style(theme.countdown_style(count).bold())
         .alignment(Alignment::Center);
     frame.render_widget(countdown, layout[1]);
 
     // Letters preview
     let letters_display = format_letter_rack(letters);
     let letters_widget = Paragraph::new(letters_display)
-        .style(Style::default().fg(Color::Cyan))
+        .style(theme.info)
         .alignment(Alignment::Center);
     frame.render_widget(letters_widget, layout[2]);
 }
 
 /// Render the joined lobby screen
-fn render_joined_lobby(
-    frame: &mut Frame,
-    lobby_name: &str,
-    host_name: &str,
-    players: &[Player],
-    countdown: Option<&(u32, Vec<char>, u32)>,
-) {
+fn render_joined_lobby(frame: &mut Frame, theme: &Theme, lobby: &JoinedLobby) {
+    let (lobby_name, host_name, players) = (&lobby.lobby_name, &lobby.host_name, lobby.players());
+    let (share_input, spectating) = (lobby.share_input(), lobby.is_spectating());
     let area = frame.area();
 
-    // If in countdown, render the countdown screen
-    if let Some((count, letters, _duration)) = countdown {
-        render_countdown(frame, area, *count, letters);
-        return;
-    }
-
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -350,14 +606,23 @@ fn render_joined_lobby(
 
     // Header
     let header = Paragraph::new(format!("Lobby: {}", lobby_name))
-        .style(Style::default().fg(Color::Yellow).bold())
+        .style(theme.header.bold())
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::BOTTOM));
     frame.render_widget(header, layout[0]);
 
-    // Lobby info
-    let info = Paragraph::new(format!("Host: {} | Players: {}/12", host_name, players.len()))
-        .style(Style::default().fg(Color::DarkGray))
+    // Lobby info, with the latest announcement beneath it
+    let mut info_lines = vec![Line::from(format!(
+        "Host: {} | Players: {}/12{}",
+        host_name,
+        players.len(),
+        if spectating { " | Lobby full: spectating" } else { "" }
+    ))];
+    if let Some(text) = lobby.announcement() {
+        info_lines.push(announcement_line(theme, text));
+    }
+    let info = Paragraph::new(info_lines)
+        .style(theme.muted)
         .alignment(Alignment::Center);
     frame.render_widget(info, layout[1]);
 
@@ -373,11 +638,11 @@ fn render_joined_lobby(
                 ""
             };
             let style = if p.is_local {
-                Style::default().fg(Color::Cyan)
+                theme.info
             } else {
-                Style::default().fg(Color::White)
+                theme.text
             };
-            ListItem::new(format!("  {} {}{}", "●", p.name, suffix)).style(style)
+            ListItem::new(format!("  {} {}{}", "●", p.display_name(), suffix)).style(style)
         })
         .collect();
 
@@ -385,74 +650,277 @@ fn render_joined_lobby(
         .block(Block::default().borders(Borders::ALL).title("Players"));
     frame.render_widget(list, layout[2]);
 
-    // Status
-    let status = Paragraph::new("Waiting for host to start...")
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center);
+    // Status, and whether the host can see what we type
+    let sharing = if share_input {
+        "Sharing your typing with the host"
+    } else {
+        "Your typing is private"
+    };
+    let status = Paragraph::new(vec![
+        Line::from("Waiting for host to start..."),
+        Line::from(sharing),
+    ])
+    .style(theme.muted)
+    .alignment(Alignment::Center);
     frame.render_widget(status, layout[3]);
 
     // Footer
-    let footer = Paragraph::new("Esc Leave")
-        .style(Style::default().fg(Color::DarkGray))
+    let footer = Paragraph::new("S Status  T Share typing  Esc Leave")
+        .style(theme.muted)
         .alignment(Alignment::Center);
     frame.render_widget(footer, layout[4]);
 }
 
 /// Render the in-game screen
-fn render_game(frame: &mut Frame, app: &App) {
+fn render_game(frame: &mut Frame, theme: &Theme, app: &App) {
     let area = frame.area();
 
-    // Main layout: header (3 lines) + content
+    // Main layout: header (3 lines) + announcement banner (if any) + content
+    let banner_height = if app.announcement.is_some() { 1 } else { 0 };
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Header with letters, timer
-            Constraint::Min(0),    // Main content area
+            Constraint::Length(3),             // Header with letters, timer
+            Constraint::Length(banner_height), // Host announcement
+            Constraint::Min(0),                // Main content area
         ])
         .split(area);
 
-    render_header(frame, layout[0], app);
+    render_header(frame, theme, layout[0], &app.letters, app.time_remaining);
+
+    if let Some(text) = &app.announcement {
+        let banner = Paragraph::new(announcement_line(theme, text)).alignment(Alignment::Center);
+        frame.render_widget(banner, layout[1]);
+    }
 
     if app.is_round_over() {
-        render_end_of_round(frame, layout[1], app);
+        render_end_of_round(frame, theme, layout[2], app);
     } else {
-        render_main(frame, layout[1], app);
+        render_main(frame, theme, layout[2], app);
     }
 }
 
+/// Render a hotseat round: shared rack on top, one panel per player
+fn render_hotseat(frame: &mut Frame, theme: &Theme, game: &HotseatGame) {
+    let area = frame.area();
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header with letters, timer
+            Constraint::Min(6),    // Player panels
+            Constraint::Length(1), // Feedback or result
+            Constraint::Length(1), // Footer
+        ])
+        .split(area);
+
+    render_header(frame, theme, layout[0], &game.letters, game.time_remaining);
+
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(layout[1]);
+    for (i, player) in game.players.iter().enumerate() {
+        let active = !game.is_round_over() && i == game.active;
+        render_hotseat_panel(frame, theme, panels[i], player, active);
+    }
+
+    let (message, style) = if game.is_round_over() {
+        let result = match game.leader() {
+            Some(leader) => format!("{} wins!", leader.name),
+            None => "It's a tie!".to_string(),
+        };
+        (result, theme.highlight)
+    } else {
+        (game.feedback.clone(), feedback_style(theme, &game.feedback, &game.flavor))
+    };
+    let message = Paragraph::new(message)
+        .style(style.bold())
+        .alignment(Alignment::Center);
+    frame.render_widget(message, layout[2]);
+
+    let hint = if game.is_round_over() {
+        "Esc Menu"
+    } else {
+        "Tab Switch player  Enter Claim"
+    };
+    let footer = Paragraph::new(hint)
+        .style(theme.muted)
+        .alignment(Alignment::Center);
+    frame.render_widget(footer, layout[3]);
+}
+
+/// Render one hotseat player's panel: input, score and claimed words
+fn render_hotseat_panel(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    player: &HotseatPlayer,
+    active: bool,
+) {
+    let border = if active {
+        theme.info
+    } else {
+        theme.muted
+    };
+    let title = if active {
+        format!("{} (typing)", player.name)
+    } else {
+        player.name.clone()
+    };
+    let block = Block::default().borders(Borders::ALL).border_style(border).title(title);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Input line
+            Constraint::Length(1), // Score
+            Constraint::Min(0),    // Claimed words
+        ])
+        .split(inner);
+
+    let cursor = if active { "_" } else { "" };
+    let input = Paragraph::new(format!("> {}{}", player.input, cursor))
+        .style(theme.text);
+    frame.render_widget(input, rows[0]);
+
+    let score = Paragraph::new(format!(
+        "Score: {}  [{} words]",
+        player.score,
+        player.claimed_words.len()
+    ))
+    .style(theme.special.bold());
+    frame.render_widget(score, rows[1]);
+
+    // Most recent claims first
+    let items: Vec<ListItem> = player
+        .claimed_words
+        .iter()
+        .rev()
+        .map(|cw| ListItem::new(format!("{} +{}", cw.word, cw.points)))
+        .collect();
+    frame.render_widget(List::new(items).style(theme.accept), rows[2]);
+}
+
 /// Render error screen
-fn render_error(frame: &mut Frame, message: &str) {
+fn render_error(frame: &mut Frame, theme: &Theme, message: &str) {
     let area = frame.area();
 
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(40),
-            Constraint::Length(3),
+            Constraint::Length(4),
             Constraint::Length(2),
             Constraint::Percentage(40),
         ])
         .margin(2)
         .split(area);
 
+    // Errors may carry a line of guidance, which can be long
     let error = Paragraph::new(format!("Error: {}", message))
-        .style(Style::default().fg(Color::Red))
-        .alignment(Alignment::Center);
+        .style(theme.reject)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
     frame.render_widget(error, layout[1]);
 
     let hint = Paragraph::new("Press Esc to go back")
-        .style(Style::default().fg(Color::DarkGray))
+        .style(theme.muted)
         .alignment(Alignment::Center);
     frame.render_widget(hint, layout[2]);
 }
 
+/// Render the prompt offering to resume an interrupted solo round
+fn render_resume_prompt(frame: &mut Frame, theme: &Theme, checkpoint: &RoundCheckpoint) {
+    let area = frame.area();
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Length(2), // Title
+            Constraint::Length(2), // Letters
+            Constraint::Length(2), // Round details
+            Constraint::Length(2), // Instructions
+            Constraint::Percentage(30),
+        ])
+        .margin(2)
+        .split(area);
+
+    let title = Paragraph::new("Resume interrupted solo round?")
+        .style(theme.highlight.bold())
+        .alignment(Alignment::Center);
+    frame.render_widget(title, layout[1]);
+
+    let letters = Paragraph::new(format_letter_rack(&checkpoint.letters))
+        .style(theme.info)
+        .alignment(Alignment::Center);
+    frame.render_widget(letters, layout[2]);
+
+    let details = Paragraph::new(format!(
+        "Time left: {} | Score: {} | Words: {}",
+        format_timer(checkpoint.time_remaining),
+        checkpoint.score,
+        checkpoint.claimed_words.len()
+    ))
+    .style(theme.text)
+    .alignment(Alignment::Center);
+    frame.render_widget(details, layout[3]);
+
+    let hint = Paragraph::new("Enter Resume  Esc Discard")
+        .style(theme.muted)
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, layout[4]);
+}
+
+/// Render the sample rack offered before hosting
+fn render_rack_preview(frame: &mut Frame, theme: &Theme, letters: &[char]) {
+    let area = frame.area();
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Length(2), // Title
+            Constraint::Length(2), // Letters
+            Constraint::Length(2), // Explanation
+            Constraint::Length(2), // Instructions
+            Constraint::Percentage(30),
+        ])
+        .margin(2)
+        .split(area);
+
+    let title = Paragraph::new("Preview the first rack")
+        .style(theme.highlight.bold())
+        .alignment(Alignment::Center);
+    frame.render_widget(title, layout[1]);
+
+    let rack = Paragraph::new(vec![
+        Line::from(Span::styled(format_letter_rack(letters), theme.info)),
+        Line::from(Span::styled(format_letter_points(letters), theme.muted)),
+    ])
+    .alignment(Alignment::Center);
+    frame.render_widget(rack, layout[2]);
+
+    let details = Paragraph::new("The first round of your lobby deals this rack")
+        .style(theme.text)
+        .alignment(Alignment::Center);
+    frame.render_widget(details, layout[3]);
+
+    let hint = Paragraph::new("R Reroll  Enter Host  Esc Back")
+        .style(theme.muted)
+        .alignment(Alignment::Center);
+    frame.render_widget(hint, layout[4]);
+}
+
 /// Render the rankings leaderboard
-fn render_rankings(
-    frame: &mut Frame,
-    players: &[CachedPlayerStats],
-    current_handle: &str,
-    scroll_offset: usize,
-) {
+fn render_rankings(frame: &mut Frame, theme: &Theme, screen: &Screen, spinner: Option<char>) {
+    let Screen::Rankings { players, current_handle, scroll_offset, status, sort } = screen else {
+        return;
+    };
+    let (scroll_offset, status, sort) = (*scroll_offset, *status, *sort);
     let area = frame.area();
 
     let layout = Layout::default()
@@ -466,26 +934,52 @@ fn render_rankings(
         .margin(1)
         .split(area);
 
-    // Header
-    let header = Paragraph::new("Rankings")
-        .style(Style::default().fg(Color::Yellow).bold())
+    // Header, noting when the rankings shown are about to be refreshed
+    let title = match (status, spinner) {
+        (RankingsStatus::Rebuilding, Some(spinner)) => format!("Rankings (rebuilding stats {})", spinner),
+        (RankingsStatus::Rebuilding, None) => "Rankings (rebuilding stats...)".to_string(),
+        _ => "Rankings".to_string(),
+    };
+    let header = Paragraph::new(title)
+        .style(theme.header.bold())
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::BOTTOM));
     frame.render_widget(header, layout[0]);
 
     if players.is_empty() {
-        let empty = Paragraph::new("No match history yet.\n\nPlay some multiplayer games to see rankings!")
-            .style(Style::default().fg(Color::DarkGray))
+        let message = if status == RankingsStatus::Rebuilding {
+            "Rebuilding stats..."
+        } else {
+            "No matches yet — host or join a game!"
+        };
+        let empty = Paragraph::new(message)
+            .style(theme.muted)
             .alignment(Alignment::Center);
         frame.render_widget(empty, layout[2]);
     } else {
-        // Column headers
-        let col_header = Paragraph::new(format!(
-            "  {:<4} {:<14} {:>6}  {:>5}  {:>4}  {:>4}",
-            "Rank", "Player", "Elo", "W", "P", "Avg"
-        ))
-        .style(Style::default().fg(Color::DarkGray));
-        frame.render_widget(col_header, layout[1]);
+        // Column headers, with the one the table is sorted by picked out
+        let columns = [
+            (format!("  {:<4} {:<14} ", "Rank", "Player"), None),
+            (format!("{:>6}", "Elo"), Some(RankingsSort::Elo)),
+            (format!("  {:>4}", "W"), Some(RankingsSort::Wins)),
+            (format!("  {:>4}", "P"), None),
+            (format!("  {:>5}", "Pts"), Some(RankingsSort::Points)),
+            (format!("  {:>4}", "Best"), Some(RankingsSort::BestScore)),
+            (format!("  {:>4}", "Avg"), None),
+            (format!("  {:>4}", "Len"), None),
+            (format!("  {:<10}", "Longest"), Some(RankingsSort::LongestWord)),
+        ];
+        let spans: Vec<Span> = columns
+            .into_iter()
+            .map(|(text, column)| {
+                if column == Some(sort) {
+                    Span::styled(text, theme.highlight.bold().underlined())
+                } else {
+                    Span::styled(text, theme.muted)
+                }
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(Line::from(spans)), layout[1]);
 
         // Calculate visible rows
         let visible_rows = layout[2].height as usize;
@@ -497,7 +991,7 @@ fn render_rankings(
             .enumerate()
             .map(|(i, stats)| {
                 let rank = scroll_offset + i + 1;
-                let is_current = stats.handle == current_handle;
+                let is_current = stats.handle == *current_handle;
 
                 let medal = match rank {
                     1 => "  ",
@@ -513,7 +1007,7 @@ fn render_rankings(
                 };
 
                 let line = format!(
-                    "{}{:<4} {:<14} {:>6.0}  {:>5}  {:>4}  {:>4.0}",
+                    "{}{:<4} {:<14} {:>6.0}  {:>4}  {:>4}  {:>5}  {:>4}  {:>4.0}  {:>4.1}  {:<10}",
                     medal,
                     rank,
                     if stats.handle.len() > 14 {
@@ -524,15 +1018,19 @@ fn render_rankings(
                     stats.elo,
                     stats.wins,
                     stats.rounds_played,
+                    stats.total_points,
+                    stats.best_score,
                     avg,
+                    stats.average_claim_length(),
+                    stats.longest_word.chars().take(10).collect::<String>(),
                 );
 
                 let style = if is_current {
-                    Style::default().fg(Color::Cyan).bold()
+                    theme.info.bold()
                 } else if rank == 1 {
-                    Style::default().fg(Color::Yellow)
+                    theme.highlight
                 } else {
-                    Style::default().fg(Color::White)
+                    theme.text
                 };
 
                 ListItem::new(line).style(style)
@@ -545,18 +1043,110 @@ fn render_rankings(
 
     // Footer
     let footer_text = if !players.is_empty() {
-        "↑↓ Scroll  Esc Back"
+        "↑↓ Scroll  ←→ Sort  H History  Esc Back"
+    } else {
+        "H History  Esc Back"
+    };
+    let footer = Paragraph::new(footer_text)
+        .style(theme.muted)
+        .alignment(Alignment::Center);
+    frame.render_widget(footer, layout[3]);
+}
+
+/// Render the match history, one line of final scores per match
+fn render_match_history(
+    frame: &mut Frame,
+    theme: &Theme,
+    matches: &[MatchResult],
+    selected: usize,
+    feedback: &str,
+) {
+    let area = frame.area();
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(6),    // Match list
+            Constraint::Length(1), // Feedback
+            Constraint::Length(2), // Footer
+        ])
+        .margin(1)
+        .split(area);
+
+    let header = Paragraph::new("Match History")
+        .style(theme.header.bold())
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::BOTTOM));
+    frame.render_widget(header, layout[0]);
+
+    if matches.is_empty() {
+        let empty = Paragraph::new("No matches yet — host or join a game!")
+            .style(theme.muted)
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, layout[1]);
     } else {
+        // Keep the selection in view
+        let visible_rows = layout[1].height as usize;
+        let start = selected.saturating_sub(visible_rows.saturating_sub(1));
+        let items: Vec<ListItem> = matches
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(visible_rows)
+            .map(|(i, result)| {
+                let mut scores = result.scores.clone();
+                scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+                let line = scores
+                    .iter()
+                    .map(|(name, score)| format!("{} {}", name, score))
+                    .collect::<Vec<_>>()
+                    .join(" · ");
+                let (prefix, style) = if i == selected {
+                    ("> ", theme.selection.bold())
+                } else {
+                    ("  ", theme.text)
+                };
+                ListItem::new(format!("{}{}", prefix, line)).style(style)
+            })
+            .collect();
+        frame.render_widget(List::new(items), layout[1]);
+    }
+
+    let feedback = Paragraph::new(feedback)
+        .style(theme.accept)
+        .alignment(Alignment::Center);
+    frame.render_widget(feedback, layout[2]);
+
+    let footer_text = if matches.is_empty() {
         "Esc Back"
+    } else {
+        "↑↓ Select  Enter/C Copy report to file  Esc Back"
     };
     let footer = Paragraph::new(footer_text)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(theme.muted)
         .alignment(Alignment::Center);
     frame.render_widget(footer, layout[3]);
 }
 
 /// Render the settings screen
-fn render_settings(frame: &mut Frame, handle_input: &str, _editing: bool, feedback: &str) {
+fn render_settings(frame: &mut Frame, theme: &Theme, screen: &Screen, theme_name: &str) {
+    let Screen::Settings {
+        handle_input,
+        feedback,
+        season,
+        rack_telemetry,
+        rack_report,
+        house_words,
+        house_word_input,
+        elo_k,
+        ..
+    } = screen
+    else {
+        return;
+    };
+    let (season, rack_telemetry, rack_report, elo_k) =
+        (*season, *rack_telemetry, *rack_report, *elo_k);
     let area = frame.area();
 
     let layout = Layout::default()
@@ -568,7 +1158,12 @@ fn render_settings(frame: &mut Frame, handle_input: &str, _editing: bool, feedba
             Constraint::Length(3),  // Handle input
             Constraint::Length(1),  // Validation hint
             Constraint::Length(2),  // Feedback
-            Constraint::Length(2),  // Spacer
+            Constraint::Length(1),  // Season
+            Constraint::Length(1),  // Rack stats
+            Constraint::Length(1),  // House words
+            Constraint::Length(1),  // Elo K factor
+            Constraint::Length(1),  // Theme
+            Constraint::Length(1),  // Spacer
             Constraint::Length(1),  // Instructions
             Constraint::Min(0),    // Remaining
             Constraint::Length(2),  // Footer
@@ -578,26 +1173,26 @@ fn render_settings(frame: &mut Frame, handle_input: &str, _editing: bool, feedba
 
     // Header
     let header = Paragraph::new("Settings")
-        .style(Style::default().fg(Color::Yellow).bold())
+        .style(theme.header.bold())
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::BOTTOM));
     frame.render_widget(header, layout[0]);
 
     // Label
     let label = Paragraph::new("Player Handle")
-        .style(Style::default().fg(Color::White))
+        .style(theme.text)
         .alignment(Alignment::Center);
     frame.render_widget(label, layout[2]);
 
     // Handle input field
     let input_display = format!("[{}]_", handle_input);
     let input = Paragraph::new(input_display)
-        .style(Style::default().fg(Color::Cyan))
+        .style(theme.info)
         .alignment(Alignment::Center)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(theme.info),
         );
     frame.render_widget(input, layout[3]);
 
@@ -607,41 +1202,110 @@ fn render_settings(frame: &mut Frame, handle_input: &str, _editing: bool, feedba
         "Letters, numbers, and _ only ({} chars remaining)",
         chars_left
     ))
-    .style(Style::default().fg(Color::DarkGray))
+    .style(theme.muted)
     .alignment(Alignment::Center);
     frame.render_widget(hint, layout[4]);
 
     // Feedback
     if !feedback.is_empty() {
-        let fb_color = if feedback == "Saved!" {
-            Color::Green
+        let fb_style = if feedback == "Saved!"
+            || feedback.starts_with("Season ")
+            || feedback.starts_with("Rack stats ")
+            || feedback.starts_with("House word ")
+            || feedback.starts_with("Elo K ")
+            || feedback.starts_with("Theme ")
+        {
+            theme.accept
         } else {
-            Color::Red
+            theme.reject
         };
-        let fb = Paragraph::new(feedback)
-            .style(Style::default().fg(fb_color).bold())
+        let fb = Paragraph::new(feedback.as_str())
+            .style(fb_style.bold())
             .alignment(Alignment::Center);
         frame.render_widget(fb, layout[5]);
     }
 
+    // Season
+    let season_line = Paragraph::new(format!("Season {}", season))
+        .style(theme.text)
+        .alignment(Alignment::Center);
+    frame.render_widget(season_line, layout[6]);
+
+    // Rack stats (local-only difficulty report)
+    let (rounds, average) = rack_report;
+    let rack_text = match (rack_telemetry, rounds) {
+        (false, 0) => "Rack stats: off".to_string(),
+        (_, 0) => "Rack stats: on (no rounds yet)".to_string(),
+        (enabled, _) => format!(
+            "Rack stats: {}  {} rounds, {:.0}% of max points on average",
+            if enabled { "on" } else { "off" },
+            rounds,
+            average * 100.0
+        ),
+    };
+    let rack_line = Paragraph::new(rack_text)
+        .style(theme.text)
+        .alignment(Alignment::Center);
+    frame.render_widget(rack_line, layout[7]);
+
+    // House words, or the prompt for editing them
+    let (house_text, house_style) = match house_word_input {
+        Some(input) => (format!("House word: [{}]_", input), theme.info),
+        None if house_words.is_empty() => ("House words: none".to_string(), theme.text),
+        None => (format!("House words: {}", house_words.join(", ")), theme.text),
+    };
+    let house_line = Paragraph::new(house_text)
+        .style(house_style)
+        .alignment(Alignment::Center);
+    frame.render_widget(house_line, layout[8]);
+
+    // Elo K factor
+    let elo_line = Paragraph::new(format!("Elo K factor: {}", elo_k))
+        .style(theme.text)
+        .alignment(Alignment::Center);
+    frame.render_widget(elo_line, layout[9]);
+
+    // Color theme
+    let theme_line = Paragraph::new(format!("Theme: {}", theme_name))
+        .style(theme.text)
+        .alignment(Alignment::Center);
+    frame.render_widget(theme_line, layout[10]);
+
     // Instructions
-    let instructions = Paragraph::new("Enter Save  Esc Back")
-        .style(Style::default().fg(Color::DarkGray))
+    let instructions = if house_word_input.is_some() {
+        "Enter Add/remove word  Esc Cancel"
+    } else {
+        "Enter Save  Tab Next season  F2 Rack stats  F3 House word  F4 Elo K  F5 Theme  Esc Back"
+    };
+    let instructions = Paragraph::new(instructions)
+        .style(theme.muted)
         .alignment(Alignment::Center);
-    frame.render_widget(instructions, layout[7]);
+    frame.render_widget(instructions, layout[12]);
 
     // Footer
     let footer = Paragraph::new("Type to edit your handle")
-        .style(Style::default().fg(Color::DarkGray))
+        .style(theme.muted)
         .alignment(Alignment::Center);
-    frame.render_widget(footer, layout[9]);
+    frame.render_widget(footer, layout[14]);
+}
+
+/// Spinner character for something that has been running for `elapsed`
+fn spinner_frame(elapsed: Duration) -> char {
+    const FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+    FRAMES[(elapsed.as_millis() / 150) as usize % FRAMES.len()]
 }
 
 /// Render the header: logo, letter rack, timer
-fn render_header(frame: &mut Frame, area: Rect, app: &App) {
+fn render_header(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    letters: &[char],
+    time_remaining: u32,
+) {
     let block = Block::default()
         .borders(Borders::BOTTOM)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_style(theme.muted);
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -658,34 +1322,34 @@ fn render_header(frame: &mut Frame, area: Rect, app: &App) {
 
     // Logo
     let logo = Paragraph::new("BLAM!")
-        .style(Style::default().fg(Color::Yellow).bold())
+        .style(theme.header.bold())
         .alignment(Alignment::Left);
     frame.render_widget(logo, header_layout[0]);
 
     // Letter rack - prominent and centered
-    let letters_display = format_letter_rack(&app.letters);
+    let letters_display = format_letter_rack(letters);
     let letters = Paragraph::new(letters_display)
-        .style(Style::default().fg(Color::Cyan).bold())
+        .style(theme.info.bold())
         .alignment(Alignment::Center);
     frame.render_widget(letters, header_layout[1]);
 
     // Timer
-    let timer_display = format_timer(app.time_remaining);
-    let timer_color = if app.time_remaining <= 10 {
-        Color::Red
-    } else if app.time_remaining <= 30 {
-        Color::Yellow
+    let timer_display = format_timer(time_remaining);
+    let timer_style = if time_remaining <= 10 {
+        theme.reject
+    } else if time_remaining <= 30 {
+        theme.highlight
     } else {
-        Color::Green
+        theme.accept
     };
     let timer = Paragraph::new(timer_display)
-        .style(Style::default().fg(timer_color).bold())
+        .style(timer_style.bold())
         .alignment(Alignment::Right);
     frame.render_widget(timer, header_layout[2]);
 }
 
 /// Render the main content area: input, feedback, score, with optional side panels
-fn render_main(frame: &mut Frame, area: Rect, app: &App) {
+fn render_main(frame: &mut Frame, theme: &Theme, area: Rect, app: &App) {
     // Check if we have multiplayer content to show
     let has_scoreboard = !app.scoreboard.is_empty();
     let has_claim_feed = !app.claim_feed.is_empty();
@@ -702,17 +1366,17 @@ fn render_main(frame: &mut Frame, area: Rect, app: &App) {
             ])
             .split(area);
 
-        render_input_area(frame, horizontal_layout[0], app);
-        render_scoreboard(frame, horizontal_layout[1], app);
-        render_claim_feed(frame, horizontal_layout[2], app);
+        render_input_area(frame, theme, horizontal_layout[0], app);
+        render_scoreboard(frame, theme, horizontal_layout[1], app);
+        render_claim_feed(frame, theme, horizontal_layout[2], app);
     } else {
         // Solo mode - just the input area
-        render_input_area(frame, area, app);
+        render_input_area(frame, theme, area, app);
     }
 }
 
 /// Render the input/feedback area (center panel)
-fn render_input_area(frame: &mut Frame, area: Rect, app: &App) {
+fn render_input_area(frame: &mut Frame, theme: &Theme, area: Rect, app: &App) {
     // Vertical layout for main content
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -727,26 +1391,49 @@ fn render_input_area(frame: &mut Frame, area: Rect, app: &App) {
         ])
         .split(area);
 
-    // Input line with cursor indicator
-    let input_display = format!("> {}_", app.input);
-    let input = Paragraph::new(input_display)
-        .style(Style::default().fg(Color::White));
-    frame.render_widget(input, main_layout[0]);
+    // Input line with cursor indicator and the would-be score
+    let estimate = match app.pending_points() {
+        Some(points) => format!("+{}", points),
+        None if app.input.is_empty() => String::new(),
+        None => "-".to_string(),
+    };
+    let input_line = Line::from(vec![
+        Span::styled(format!("> {}_", app.input), theme.text),
+        Span::raw("  "),
+        Span::styled(estimate, theme.muted),
+    ]);
+    frame.render_widget(Paragraph::new(input_line), main_layout[0]);
 
     // Feedback line
     let feedback = Paragraph::new(app.feedback.as_str())
-        .style(Style::default().fg(feedback_color(&app.feedback)));
+        .style(feedback_style(theme, &app.feedback, &app.flavor));
     frame.render_widget(feedback, main_layout[2]);
 
-    // Score
-    let score_display = format!("Score: {}", app.score);
-    let score = Paragraph::new(score_display)
-        .style(Style::default().fg(Color::Magenta).bold());
-    frame.render_widget(score, main_layout[4]);
+    // Score, with a badge for the words claimed and any combo going
+    let mut score_spans = vec![
+        Span::styled(format!("Score: {}", app.score), theme.special.bold()),
+        Span::styled(
+            format!("  [{} words]", app.claimed_words().len()),
+            theme.muted,
+        ),
+    ];
+    if app.combo() >= 2 {
+        score_spans.push(Span::styled(
+            format!("  COMBO x{}", app.combo()),
+            theme.highlight.bold(),
+        ));
+    }
+    if app.min_letters_used > 0 {
+        score_spans.push(Span::styled(
+            format!("  Use {}+ letters", app.min_letters_used),
+            theme.muted,
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(score_spans)), main_layout[4]);
 }
 
 /// Render the live scoreboard (right panel)
-fn render_scoreboard(frame: &mut Frame, area: Rect, app: &App) {
+fn render_scoreboard(frame: &mut Frame, theme: &Theme, area: Rect, app: &App) {
     let items: Vec<ListItem> = app
         .scoreboard
         .iter()
@@ -760,13 +1447,27 @@ fn render_scoreboard(frame: &mut Frame, area: Rect, app: &App) {
             };
             let is_local = app.player_name.as_ref() == Some(&player.name);
             let style = if is_local {
-                Style::default().fg(Color::Cyan).bold()
+                theme.info.bold()
             } else if i == 0 {
-                Style::default().fg(Color::Yellow)
+                theme.highlight
             } else {
-                Style::default().fg(Color::White)
+                theme.text
             };
-            ListItem::new(format!("{} {} - {}", prefix, player.name, player.score)).style(style)
+            let handicap = app.handicaps.get(&player.name).copied().unwrap_or(1.0);
+            let typing = app
+                .input_previews
+                .get(&player.name)
+                .map(|text| format!(" ✎ {}", text))
+                .unwrap_or_default();
+            ListItem::new(format!(
+                "{} {} - {}{}{}",
+                prefix,
+                player.name,
+                player.score,
+                format_handicap(handicap),
+                typing
+            ))
+            .style(style)
         })
         .collect();
 
@@ -774,14 +1475,38 @@ fn render_scoreboard(frame: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
+                .border_style(theme.muted)
                 .title("Scoreboard"),
         );
     frame.render_widget(list, area);
 }
 
+/// Render points across every round of the match (after a rematch)
+fn render_match_totals(frame: &mut Frame, theme: &Theme, area: Rect, app: &App) {
+    let items: Vec<ListItem> = app
+        .match_standings()
+        .iter()
+        .map(|player| {
+            let style = if app.player_name.as_ref() == Some(&player.name) {
+                theme.info.bold()
+            } else {
+                theme.text
+            };
+            ListItem::new(format!("{} - {}", player.name, player.score)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.muted)
+            .title(format!("Match ({} rounds)", app.rounds_banked() + 1)),
+    );
+    frame.render_widget(list, area);
+}
+
 /// Render the claim feed (rightmost panel)
-fn render_claim_feed(frame: &mut Frame, area: Rect, app: &App) {
+fn render_claim_feed(frame: &mut Frame, theme: &Theme, area: Rect, app: &App) {
     // Show most recent claims first (reverse order)
     let items: Vec<ListItem> = app
         .claim_feed
@@ -791,13 +1516,16 @@ fn render_claim_feed(frame: &mut Frame, area: Rect, app: &App) {
         .map(|entry| {
             let is_local = app.player_name.as_ref() == Some(&entry.player_name);
             let style = if is_local {
-                Style::default().fg(Color::Cyan)
+                theme.info
             } else {
-                Style::default().fg(Color::Green)
+                theme.accept
             };
+            // House words are marked so players know why an odd word counted
+            let house = if app.is_house_word(&entry.word) { " ⌂" } else { "" };
+            let bonus = entry.bonus.label().map(|l| format!(" ({})", l)).unwrap_or_default();
             ListItem::new(format!(
-                "{}: {} +{}",
-                entry.player_name, entry.word, entry.points
+                "{}: {} +{}{}{}",
+                entry.player_name, entry.word, entry.points, bonus, house
             ))
             .style(style)
         })
@@ -807,14 +1535,14 @@ fn render_claim_feed(frame: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
+                .border_style(theme.muted)
                 .title("Claims"),
         );
     frame.render_widget(list, area);
 }
 
 /// Render the end-of-round summary
-fn render_end_of_round(frame: &mut Frame, area: Rect, app: &App) {
+fn render_end_of_round(frame: &mut Frame, theme: &Theme, area: Rect, app: &App) {
     let has_scoreboard = !app.scoreboard.is_empty();
 
     if has_scoreboard {
@@ -828,23 +1556,33 @@ fn render_end_of_round(frame: &mut Frame, area: Rect, app: &App) {
             ])
             .split(area);
 
-        render_end_summary(frame, horizontal_layout[0], app);
-        render_scoreboard(frame, horizontal_layout[1], app);
-        render_claim_feed(frame, horizontal_layout[2], app);
+        render_end_summary(frame, theme, horizontal_layout[0], app);
+        if app.rounds_banked() > 0 {
+            // A rematch: the match so far under this round's scores
+            let score_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(horizontal_layout[1]);
+            render_scoreboard(frame, theme, score_layout[0], app);
+            render_match_totals(frame, theme, score_layout[1], app);
+        } else {
+            render_scoreboard(frame, theme, horizontal_layout[1], app);
+        }
+        render_claim_feed(frame, theme, horizontal_layout[2], app);
     } else {
         // Solo end-of-round
-        render_end_summary(frame, area, app);
+        render_end_summary(frame, theme, area, app);
     }
 }
 
 /// Render the end-of-round summary text
-fn render_end_summary(frame: &mut Frame, area: Rect, app: &App) {
+fn render_end_summary(frame: &mut Frame, theme: &Theme, area: Rect, app: &App) {
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
             Constraint::Length(2), // Title
-            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // New personal best (or spacer)
             Constraint::Length(1), // Final score
             Constraint::Length(1), // Spacer
             Constraint::Length(1), // Words claimed
@@ -856,23 +1594,37 @@ fn render_end_summary(frame: &mut Frame, area: Rect, app: &App) {
         ])
         .split(area);
 
-    // TIME'S UP title
-    let title = Paragraph::new("TIME'S UP!")
-        .style(Style::default().fg(Color::Red).bold())
+    // Title explains why the round ended
+    let headline = app.flavor.headline(app.round_end_reason.unwrap_or_default());
+    let title = Paragraph::new(headline)
+        .style(theme.reject.bold())
         .alignment(Alignment::Center);
     frame.render_widget(title, main_layout[0]);
 
+    // Personal record celebration (solo rounds), or the winner's crown
+    if let Some(new_best) = &app.new_best {
+        let celebration = Paragraph::new(new_best.as_str())
+            .style(theme.special.bold())
+            .alignment(Alignment::Center);
+        frame.render_widget(celebration, main_layout[1]);
+    } else if let Some(banner) = winner_of(&app.scoreboard).banner() {
+        let crown = Paragraph::new(banner)
+            .style(theme.highlight.bold())
+            .alignment(Alignment::Center);
+        frame.render_widget(crown, main_layout[1]);
+    }
+
     // Final score
     let score_text = format!("Final Score: {}", app.score);
     let score = Paragraph::new(score_text)
-        .style(Style::default().fg(Color::Yellow).bold())
+        .style(theme.highlight.bold())
         .alignment(Alignment::Center);
     frame.render_widget(score, main_layout[2]);
 
     // Words claimed count
     let words_text = format!("Words Claimed: {}", app.claimed_words().len());
     let words = Paragraph::new(words_text)
-        .style(Style::default().fg(Color::Cyan))
+        .style(theme.info)
         .alignment(Alignment::Center);
     frame.render_widget(words, main_layout[4]);
 
@@ -883,15 +1635,83 @@ fn render_end_summary(frame: &mut Frame, area: Rect, app: &App) {
         "Longest Word: --".to_string()
     };
     let longest = Paragraph::new(longest_text)
-        .style(Style::default().fg(Color::Green))
+        .style(theme.accept)
         .alignment(Alignment::Center);
     frame.render_widget(longest, main_layout[6]);
 
     // Instructions
-    let instructions = Paragraph::new("Press ESC to return to menu")
-        .style(Style::default().fg(Color::DarkGray))
+    let instructions = Paragraph::new(app.end_of_round_hint())
+        .style(theme.muted)
         .alignment(Alignment::Center);
     frame.render_widget(instructions, main_layout[8]);
+
+    let summary = app.round_summary();
+    let usage = summary.letter_usage();
+    let lower_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(usage.len() as u16 + 2), // Letter usage
+            Constraint::Min(0),                         // Definitions
+        ])
+        .split(main_layout[9]);
+    render_letter_usage(frame, theme, lower_layout[0], &usage);
+    render_definitions(frame, theme, lower_layout[1], &summary.definitions);
+}
+
+/// Render what the claimed words mean, for those with a definition
+fn render_definitions(
+    frame: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    definitions: &[(String,
+    &str)],
+) {
+    if definitions.is_empty() {
+        return;
+    }
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::styled("Definitions", theme.text.bold()),
+    ];
+    for (word, definition) in definitions {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", word), theme.highlight.bold()),
+            Span::styled(definition.to_string(), theme.muted),
+        ]));
+    }
+
+    let text = Paragraph::new(lines).alignment(Alignment::Center);
+    frame.render_widget(text, area);
+}
+
+/// Render a bar per rack letter showing how often it was used
+fn render_letter_usage(frame: &mut Frame, theme: &Theme, area: Rect, usage: &[(char, u32)]) {
+    if usage.iter().all(|(_, count)| *count == 0) {
+        return;
+    }
+
+    const MAX_BAR: u32 = 20;
+    let most = usage.iter().map(|(_, count)| *count).max().unwrap_or(1).max(1);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::styled("Letter Usage", theme.text.bold()),
+    ];
+    for (letter, count) in usage {
+        let width = (count * MAX_BAR).div_ceil(most) as usize;
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", letter), theme.highlight.bold()),
+            Span::styled(
+                format!("{:<w$}", "█".repeat(width), w = MAX_BAR as usize),
+                theme.info,
+            ),
+            Span::styled(format!(" {}", count), theme.muted),
+        ]));
+    }
+
+    let chart = Paragraph::new(lines).alignment(Alignment::Center);
+    frame.render_widget(chart, area);
 }
 
 /// Format the letter rack for display
@@ -912,6 +1732,27 @@ fn format_letter_rack(letters: &[char]) -> String {
     result
 }
 
+/// Each distinct letter on the rack with its point value, e.g. "A1 C3 Q10"
+fn format_letter_points(letters: &[char]) -> String {
+    let mut distinct: Vec<char> = letters.iter().map(|c| c.to_ascii_uppercase()).collect();
+    distinct.sort_unstable();
+    distinct.dedup();
+    distinct
+        .iter()
+        .map(|c| format!("{}{}", c, letter_points(*c)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Format a handicap multiplier as a suffix (empty when there's no handicap)
+fn format_handicap(multiplier: f32) -> String {
+    if (multiplier - 1.0).abs() < f32::EPSILON {
+        String::new()
+    } else {
+        format!(" ×{:.2}", multiplier)
+    }
+}
+
 /// Format the timer display
 fn format_timer(seconds: u32) -> String {
     let mins = seconds / 60;
@@ -919,30 +1760,30 @@ fn format_timer(seconds: u32) -> String {
     format!("{}:{:02}", mins, secs)
 }
 
-/// Get the appropriate color for feedback text (avoids cloning the string)
-fn feedback_color(feedback: &str) -> Color {
+/// Get the appropriate style for feedback text (avoids cloning the string)
+fn feedback_style(theme: &Theme, feedback: &str, flavor: &FlavorText) -> Style {
     if feedback.is_empty() {
-        Color::White
+        theme.text
     } else if feedback.starts_with("OK") {
-        Color::Green
-    } else if feedback.starts_with("NOPE")
-        || feedback.starts_with("CLANK")
+        theme.accept
+    } else if flavor.is_rejection(feedback)
         || feedback.starts_with("Not in dictionary")
         || feedback.starts_with("Missing")
         || feedback.starts_with("Too short")
+        || feedback.starts_with("Too long")
     {
-        Color::Red
+        theme.reject
     } else if feedback.starts_with("TOO LATE")
         || feedback.starts_with("Already claimed")
         || feedback.starts_with("Round has ended")
     {
-        Color::Yellow
+        theme.highlight
     } else {
-        Color::White
+        theme.text
     }
 }
 
 // Legacy function for backwards compatibility
 pub fn render_app(frame: &mut Frame, app: &App) {
-    render_game(frame, app);
+    render_game(frame, &Theme::default(), app);
 }
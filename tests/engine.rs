@@ -0,0 +1,49 @@
+//! The engine as another frontend would use it
+//!
+//! Only `blam::engine` is touched here, so running these with
+//! `cargo test --no-default-features --test engine` checks the engine builds
+//! and plays without the terminal or LAN crates.
+
+use blam::engine::{
+    validate_word, App, ClaimResult, EloCalculator, LetterRack, MatchResult, RoundArbitrator, StatsTracker,
+    ValidationResult, DEFAULT_ELO,
+};
+
+const LETTERS: [char; 12] = ['C', 'A', 'T', 'D', 'O', 'G', 'E', 'R', 'S', 'T', 'A', 'N'];
+
+#[test]
+fn engine_plays_a_round_and_rates_it() {
+    assert_eq!(validate_word("cats", &LETTERS), ValidationResult::Valid);
+    assert!(!validate_word("zap", &LETTERS).is_valid());
+
+    let players = vec!["Ada".to_string(), "Grace".to_string()];
+    let mut round = RoundArbitrator::new(LETTERS.to_vec(), &players);
+    assert!(matches!(round.try_claim("cats", "Ada"), ClaimResult::Accepted { .. }));
+    assert!(matches!(round.try_claim("tan", "Grace"), ClaimResult::Accepted { .. }));
+    assert!(matches!(round.try_claim("cats", "Grace"), ClaimResult::AlreadyClaimed { .. }));
+    round.end_round();
+
+    let result = MatchResult::new(1, round.raw_scores(), "host".to_string());
+    let mut elo = EloCalculator::new();
+    elo.process_match(&result);
+    assert!(elo.rating("Ada") > DEFAULT_ELO);
+    assert!(elo.rating("Grace") < DEFAULT_ELO);
+
+    let mut stats = StatsTracker::new();
+    stats.process_match(&result);
+    assert_eq!(stats.get("Ada").map(|s| s.wins), Some(1));
+}
+
+#[test]
+fn engine_drives_a_solo_round() {
+    let rack = LetterRack::generate();
+    assert!(!rack.letters().is_empty());
+
+    let mut app = App::new();
+    app.start_round(LETTERS.to_vec(), 60);
+    for c in "cat".chars() {
+        app.on_key_char(c);
+    }
+    app.on_submit();
+    assert_eq!(app.score, 5);
+}